@@ -135,3 +135,14 @@ vec4 aa_apply(sampler2D tex, vec2 fragCoord, vec2 resolution) {
     //compute FXAA
     return fxaa(tex, scaled_fc, scaled_res, v_rgbNW, v_rgbNE, v_rgbSW, v_rgbSE, v_rgbM);
 }
+
+uniform sampler2D src_outline;
+
+// Value of the outline target at a texel
+float outline_fetch(ivec2 texel) {
+    return texelFetch(src_outline, texel, 0).r;
+}
+
+ivec2 outline_size() {
+    return textureSize(src_outline, 0);
+}
@@ -27,4 +27,19 @@ vec4 aa_apply(sampler2DMS tex, vec2 fragCoord, vec2 resolution) {
     ) / 16.0;
 
     return msaa_color;
-}
\ No newline at end of file
+}
+
+uniform sampler2DMS src_outline;
+
+// Strongest outline marked in any of the samples of a texel
+float outline_fetch(ivec2 texel) {
+    float outline = 0.0;
+    for (int i = 0; i < 16; i ++) {
+        outline = max(outline, texelFetch(src_outline, texel, i).r);
+    }
+    return outline;
+}
+
+ivec2 outline_size() {
+    return textureSize(src_outline);
+}
@@ -16,4 +16,19 @@ vec4 aa_apply(sampler2DMS tex, vec2 fragCoord, vec2 resolution) {
     vec4 msaa_color = (sample1 + sample2 + sample3 + sample4 + sample5 + sample6 + sample7 + sample8) / 8.0;
 
     return msaa_color;
-}
\ No newline at end of file
+}
+
+uniform sampler2DMS src_outline;
+
+// Strongest outline marked in any of the samples of a texel
+float outline_fetch(ivec2 texel) {
+    float outline = 0.0;
+    for (int i = 0; i < 8; i ++) {
+        outline = max(outline, texelFetch(src_outline, texel, i).r);
+    }
+    return outline;
+}
+
+ivec2 outline_size() {
+    return textureSize(src_outline);
+}
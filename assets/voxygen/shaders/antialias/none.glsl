@@ -2,4 +2,15 @@ uniform sampler2D src_color;
 
 vec4 aa_apply(sampler2D tex, vec2 fragCoord, vec2 resolution) {
     return texture(src_color, fragCoord / resolution);
-}
\ No newline at end of file
+}
+
+uniform sampler2D src_outline;
+
+// Value of the outline target at a texel
+float outline_fetch(ivec2 texel) {
+    return texelFetch(src_outline, texel, 0).r;
+}
+
+ivec2 outline_size() {
+    return textureSize(src_outline, 0);
+}
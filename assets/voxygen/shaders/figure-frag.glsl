@@ -52,7 +52,8 @@ uniform u_locals {
     ivec4 atlas_offs;
     vec3 model_pos;
     // bit 0 - is player
-    // bit 1-31 - unused
+    // bit 1-2 - outline kind
+    // bit 3-31 - unused
     int flags;
 };
 
@@ -71,6 +72,7 @@ uniform u_bones {
 #include <lod.glsl>
 
 out vec4 tgt_color;
+out float tgt_outline;
 
 void main() {
     // vec2 texSize = textureSize(t_col_light, 0);
@@ -211,4 +213,7 @@ void main() {
     // }
 
     tgt_color = vec4(surf_color, 1.0);
+    // Group members are only outlined where they are hidden, which the outline pass marks
+    int outline = (flags >> 1) & 3;
+    tgt_outline = outline == OUTLINE_GROUP ? 0.0 : float(outline) / 3.0;
 }
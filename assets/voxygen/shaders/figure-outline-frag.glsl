@@ -0,0 +1,26 @@
+#version 330 core
+
+#include <constants.glsl>
+
+// Only run where the figure is hidden behind something already rendered, marking those parts
+// for the post-process outline.
+
+layout (std140)
+uniform u_locals {
+    mat4 model_mat;
+    vec4 highlight_col;
+    ivec4 atlas_offs;
+    vec3 model_pos;
+    // bit 0 - is player
+    // bit 1-2 - outline kind
+    // bit 3-31 - unused
+    int flags;
+};
+
+out vec4 tgt_color;
+out float tgt_outline;
+
+void main() {
+    tgt_color = vec4(0.0);
+    tgt_outline = float((flags >> 1) & 3) / 3.0;
+}
@@ -43,6 +43,12 @@
 #define LIGHTING_DISTRIBUTION_BECKMANN 0
 #define LIGHTING_DISTRIBUTION_TROWBRIDGE 1
 
+// Outline kinds of figures, matching `Outline` in the figure pipeline
+#define OUTLINE_NONE 0
+#define OUTLINE_TARGET 1
+#define OUTLINE_INTERACTABLE 2
+#define OUTLINE_GROUP 3
+
 /* Constants expected to be defined automatically by configuration: */
 
 /*
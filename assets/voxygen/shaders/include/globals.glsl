@@ -7,6 +7,7 @@ uniform u_globals {
     vec4 focus_off;
     vec4 focus_pos;
     vec4 view_distance;
+    // x: time of day in seconds, y: phase of the moon (0 for new moon, 0.5 for full moon)
     vec4 time_of_day;
     vec4 sun_dir;
     vec4 moon_dir;
@@ -31,6 +31,12 @@ const vec3 SKY_NIGHT_BOT = vec3(0.002, 0.004, 0.004);
 const vec3 NIGHT_LIGHT   = vec3(0.002, 0.02, 0.02);
 // const vec3 NIGHT_LIGHT   = vec3(0.0, 0.0, 0.0);
 
+// Scattering of light from the sky while the sun is low, which tints ambient light at dawn and dusk
+const vec3 DUSK_SCATTER = vec3(0.3, 0.12, 0.08) * 1.5;
+
+// Axis that the star field turns around through the night
+const vec3 STAR_POLE = vec3(0.0, 0.6, 0.8);
+
 // Linear RGB, scattering coefficients for atmosphere at roughly R, G, B wavelengths.
 //
 // See https://en.wikipedia.org/wiki/Diffuse_sky_radiation
@@ -91,8 +97,19 @@ float get_sun_brightness(/*vec3 sun_dir*/) {
     return max(-sun_dir.z + 0.6, 0.0) * 0.9;
 }
 
+// Fraction of the face of the moon that is lit, from 0 at new moon to 1 at full moon
+float get_moon_illumination() {
+    return 0.5 - 0.5 * cos(time_of_day.y * 2.0 * PI);
+}
+
 float get_moon_brightness(/*vec3 moon_dir*/) {
-    return max(-moon_dir.z + 0.6, 0.0) * 0.1;
+    // Even a new moon leaves some light, so that nights stay readable
+    return max(-moon_dir.z + 0.6, 0.0) * 0.1 * mix(0.5, 1.25, get_moon_illumination());
+}
+
+// How close the sun is to the horizon, from 0 when it's well above or below it to 1 at sunrise and sunset
+float get_dusk_factor() {
+    return max(1.0 - abs(sun_dir.z) * 3.0, 0.0);
 }
 
 vec3 get_sun_color(/*vec3 sun_dir*/) {
@@ -211,7 +228,8 @@ DirectionalLight get_moon_info(vec4 _dir, float shade_frac/*, vec4 light_pos[2]*
 // surface_alt is the altitude of the attenuating surface.
 float get_sun_diffuse2(DirectionalLight sun_info, DirectionalLight moon_info, vec3 norm, vec3 dir, vec3 wpos, vec3 mu, vec3 cam_attenuation, float surface_alt, vec3 k_a, vec3 k_d, vec3 k_s, float alpha, vec3 voxel_norm, float voxel_lighting, out vec3 emitted_light, out vec3 reflected_light) {
     const float MIN_SHADOW = 0.15;
-    const vec3 SUN_AMBIANCE = MU_SCATTER;//0.23;/* / 1.8*/;// 0.1 / 3.0;
+    // Light scattered by the sky takes on the colours of dawn and dusk while the sun is low
+    vec3 SUN_AMBIANCE = mix(MU_SCATTER, DUSK_SCATTER, get_dusk_factor());//0.23;/* / 1.8*/;// 0.1 / 3.0;
     const vec3 MOON_AMBIANCE = MU_SCATTER;//0.23;//0.1;
 
     /* vec3 sun_dir = sun_info.dir;
@@ -404,8 +422,12 @@ vec3 get_sky_color(vec3 dir, float time_of_day, vec3 origin, vec3 f_pos, float q
     // Add white dots for stars. Note these flicker and jump due to FXAA
     float star = 0.0;
     if (with_features) {
-        vec3 star_dir = normalize(sun_dir * dir.z + cross(sun_dir, vec3(0, 1, 0)) * dir.x + vec3(0, 1, 0) * dir.y);
-        star = is_star_at(star_dir);
+        // The star field turns around the pole once a day
+        vec3 pole = normalize(STAR_POLE);
+        float star_angle = time_of_day.x * (2.0 * PI / (3600.0 * 24.0));
+        vec3 star_dir = dir * cos(star_angle) + cross(pole, dir) * sin(star_angle) + pole * dot(pole, dir) * (1.0 - cos(star_angle));
+        // Stars are harder to see under a bright moon
+        star = is_star_at(normalize(star_dir)) * mix(1.0, 0.4, get_moon_illumination());
     }
 
     // Sun
@@ -428,10 +450,21 @@ vec3 get_sky_color(vec3 dir, float time_of_day, vec3 origin, vec3 f_pos, float q
     const vec3 MOON_SURF_COLOR = vec3(0.7, 1.0, 1.5) * 500.0;
     const vec3 MOON_HALO_COLOR = vec3(0.015, 0.015, 0.05);
 
-    vec3 moon_halo = pow(max(dot(dir, -moon_dir) + 0.1, 0.0), 8.0) * MOON_HALO_COLOR;
+    float moon_illumination = get_moon_illumination();
+    vec3 moon_halo = pow(max(dot(dir, -moon_dir) + 0.1, 0.0), 8.0) * MOON_HALO_COLOR * mix(0.2, 1.0, moon_illumination);
     vec3 moon_surf = vec3(0);
     if (with_features) {
         moon_surf = pow(max(dot(dir, -moon_dir) - 0.001, 0.0), 5000.0) * MOON_SURF_COLOR; // Hack to prevent moon vanishing too early
+
+        // Only the part of the disc facing the sun is lit. The terminator sweeps from one side of the
+        // disc to the other as the moon waxes, and back again as it wanes.
+        const float MOON_RADIUS = 0.035;
+        vec3 moon_right = normalize(cross(-moon_dir, vec3(0, 1, 0)));
+        vec3 moon_up = cross(moon_right, -moon_dir);
+        vec2 disc_pos = vec2(dot(dir, moon_right), dot(dir, moon_up)) / MOON_RADIUS;
+        float terminator = cos(time_of_day.y * 2.0 * PI) * sqrt(max(1.0 - disc_pos.y * disc_pos.y, 0.0));
+        bool lit = time_of_day.y < 0.5 ? disc_pos.x > terminator : disc_pos.x < -terminator;
+        moon_surf *= lit ? 1.0 : 0.02;
     }
     vec3 moon_light = moon_halo + moon_surf;
 
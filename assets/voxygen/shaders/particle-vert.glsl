@@ -53,6 +53,12 @@ const int HEALING_BEAM = 13;
 const int ENERGY_NATURE = 14;
 const int FLAMETHROWER = 15;
 const int FIRE_SHOCKWAVE = 16;
+const int FOOTPRINT = 17;
+const int SCORCH = 18;
+const int BLOOD_SPLAT = 19;
+const int DEBRIS = 20;
+const int WATER_SPLASH = 21;
+const int WATERFALL_SPRAY = 22;
 
 // meters per second squared (acceleration)
 const float earth_gravity = 9.807;
@@ -302,6 +308,61 @@ void main() {
             vec4(1, 0.6 + rand7 * 0.3 - 5 * inst_lifespan + 2 * lifetime, 0, 0.8 - 3.5 * inst_lifespan),
             spin_in_axis(vec3(rand3, rand4, rand5), rand6)
         );
+    } else if (inst_mode == FOOTPRINT) {
+        attr = Attr(
+            vec3(0, 0, 0.02),
+            vec3(2.5, 4.0, 0.1),
+            vec4(vec3(0.25 + rand0 * 0.05), start_end(0.6, 0.0)),
+            spin_in_axis(vec3(0, 0, 1), atan(-inst_dir.x, inst_dir.y))
+        );
+    } else if (inst_mode == SCORCH) {
+        attr = Attr(
+            vec3(rand0, rand1, 0.03),
+            vec3(vec2(length(inst_dir) * (0.8 + rand2 * 0.2)), 0.1),
+            vec4(vec3(0.05), start_end(0.9, 0.0)),
+            spin_in_axis(vec3(0, 0, 1), rand3 * 3)
+        );
+    } else if (inst_mode == BLOOD_SPLAT) {
+        attr = Attr(
+            vec3(rand0, rand1, 0.04),
+            vec3(vec2(4.0 + rand2 * 2.0), 0.1),
+            vec4(0.45 + rand3 * 0.1, 0, 0, start_end(0.8, 0.0)),
+            spin_in_axis(vec3(0, 0, 1), rand4 * 3)
+        );
+    } else if (inst_mode == DEBRIS) {
+        attr = Attr(
+            linear_motion(
+                vec3(rand0, rand1, rand2) * 0.3,
+                vec3(rand3 * 3, rand4 * 3, 3 + rand5 * 2) + grav_vel(earth_gravity)
+            ),
+            vec3(1.5 + rand6),
+            vec4(vec3(0.45, 0.3, 0.15) * (0.8 + rand7 * 0.4), 1),
+            spin_in_axis(vec3(rand6, rand7, rand8), rand9 * 3 + lifetime * 8)
+        );
+    } else if (inst_mode == WATER_SPLASH) {
+        // The entry speed is passed through the length of inst_dir
+        float speed = length(inst_dir);
+        attr = Attr(
+            linear_motion(
+                vec3(rand0, rand1, 0) * 0.5,
+                vec3(rand2 * speed * 0.15, rand3 * speed * 0.15, speed * (0.3 + abs(rand4) * 0.4))
+                    + grav_vel(earth_gravity)
+            ),
+            vec3(0.8 + rand5 * 0.3),
+            vec4(0.7, 0.85, 1.0, start_end(0.8, 0.0)),
+            spin_in_axis(vec3(rand6, rand7, rand8), rand9 * 3)
+        );
+    } else if (inst_mode == WATERFALL_SPRAY) {
+        // Mist thrown up where falling water hits the pool below
+        attr = Attr(
+            linear_motion(
+                vec3(rand0, rand1, 0) * 0.5,
+                vec3(rand2 * 1.5, rand3 * 1.5, 2.0 + abs(rand4) * 2.0)
+            ),
+            vec3(0.5 + linear_scale(1.5)),
+            vec4(0.85, 0.92, 1.0, start_end(0.6, 0.0)),
+            spin_in_axis(vec3(rand6, rand7, rand8), rand9 * 3 + lifetime)
+        );
     } else {
         attr = Attr(
             linear_motion(
@@ -17,7 +17,8 @@
 #define LIGHTING_DISTRIBUTION LIGHTING_DISTRIBUTION_BECKMANN
 
 #include <globals.glsl>
-// Note: The sampler uniform is declared here because it differs for MSAA
+// Note: The samplers of the color and outline targets are declared here because they differ
+// for MSAA
 #include <anti-aliasing.glsl>
 #include <srgb.glsl>
 #include <cloud.glsl>
@@ -167,12 +168,96 @@ vec3 wpos_at(vec2 uv) {
     }
 }
 
+// How far the view ripples while under water, as a fraction of the screen
+const float UNDERWATER_DISTORTION = 0.003;
+// Distance over which the water swallows most of the light
+const float UNDERWATER_FOG_DIST = 40.0;
+const vec3 UNDERWATER_FOG_DAY = vec3(0.03, 0.18, 0.22);
+const vec3 UNDERWATER_FOG_NIGHT = vec3(0.002, 0.01, 0.02);
+// Size of the caustic pattern, in blocks
+const float CAUSTIC_SCALE = 6.0;
+// Distance over which caustics fade out
+const float CAUSTIC_DIST = 24.0;
+const float CAUSTIC_STRENGTH = 0.8;
+
+// Bright, wavering lines of light focused by the water surface
+float caustics(vec2 wpos) {
+    vec2 p = wpos / CAUSTIC_SCALE;
+    float t = tick.x * 0.6;
+    float c = 0.0;
+    for (int i = 1; i <= 3; i ++) {
+        p += vec2(sin(p.y * 1.7 + t), cos(p.x * 1.3 - t)) / float(i);
+        c += pow(1.0 - abs(sin(p.x + p.y) * cos(p.x - p.y)), 8.0);
+    }
+    return c / 3.0;
+}
+
+// Tints, fogs and lights a surface seen by a camera under water
+vec3 underwater(vec3 color, vec3 wpos, float dist) {
+    float daylight = clamp(-sun_dir.z + 0.2, 0.0, 1.0);
+
+    // Caustics only fall on surfaces facing up towards the water surface
+    vec3 norm = normalize(cross(dFdx(wpos), dFdy(wpos)));
+    norm *= sign(dot(norm, cam_pos.xyz - wpos));
+    float caustic = caustics(wpos.xy + focus_off.xy) * max(norm.z, 0.0) * exp(-dist / CAUSTIC_DIST);
+    color *= 1.0 + caustic * CAUSTIC_STRENGTH * daylight;
+
+    // Water soaks up reds first, leaving everything greener and bluer
+    color = mix(vec3(rel_luminance(color)), color, 0.75) * vec3(0.65, 0.95, 1.0);
+
+    vec3 fog_color = mix(UNDERWATER_FOG_NIGHT, UNDERWATER_FOG_DAY, daylight);
+    return mix(color, fog_color, 1.0 - exp(-dist / UNDERWATER_FOG_DIST));
+}
+
+// Width of figure outlines, in pixels of the render target
+const int OUTLINE_WIDTH = 2;
+const vec3 OUTLINE_TARGET_COLOR = vec3(1.0, 0.3, 0.15);
+const vec3 OUTLINE_INTERACTABLE_COLOR = vec3(1.0, 0.85, 0.3);
+const vec3 OUTLINE_GROUP_COLOR = vec3(0.3, 0.75, 1.0);
+// How strongly hidden group members show through what hides them
+const float OUTLINE_GROUP_FILL = 0.25;
+
+int outline_at(ivec2 texel) {
+    return int(round(outline_fetch(texel) * 3.0));
+}
+
+vec3 outline_color(int kind) {
+    if (kind == OUTLINE_TARGET) {
+        return OUTLINE_TARGET_COLOR;
+    } else if (kind == OUTLINE_INTERACTABLE) {
+        return OUTLINE_INTERACTABLE_COLOR;
+    } else {
+        return OUTLINE_GROUP_COLOR;
+    }
+}
+
+// Draws outlines just outside the edges of the figures marked for them
+vec3 outline(vec3 color, vec2 uv) {
+    ivec2 size = outline_size();
+    ivec2 texel = clamp(ivec2(uv * vec2(size)), ivec2(0), size - 1);
+
+    int center = outline_at(texel);
+    if (center == OUTLINE_GROUP) {
+        return mix(color, OUTLINE_GROUP_COLOR, OUTLINE_GROUP_FILL);
+    } else if (center != OUTLINE_NONE) {
+        return color;
+    }
+
+    int kind = OUTLINE_NONE;
+    for (int x = -OUTLINE_WIDTH; x <= OUTLINE_WIDTH; x ++) {
+        for (int y = -OUTLINE_WIDTH; y <= OUTLINE_WIDTH; y ++) {
+            kind = max(kind, outline_at(clamp(texel + ivec2(x, y), ivec2(0), size - 1)));
+        }
+    }
+    return kind == OUTLINE_NONE ? color : outline_color(kind);
+}
+
 void main() {
     vec2 uv = (f_pos + 1.0) * 0.5;
 
-    /* if (medium.x == 1u) {
-        uv = clamp(uv + vec2(sin(uv.y * 16.0 + tick.x), sin(uv.x * 24.0 + tick.x)) * 0.005, 0, 1);
-    } */
+    if (medium.x == 1u) {
+        uv = clamp(uv + vec2(sin(uv.y * 16.0 + tick.x), sin(uv.x * 24.0 + tick.x)) * UNDERWATER_DISTORTION, 0, 1);
+    }
 
     vec2 c_uv = vec2(0.5);//uv;//vec2(0.5);//uv;
     vec2 delta = /*sqrt*//*sqrt(2.0) / 2.0*//*sqrt(2.0) / 2.0*//*0.5 - */min(uv, 1.0 - uv);//min(uv * (1.0 - uv), 0.25) * 2.0;
@@ -211,6 +296,13 @@ void main() {
         aa_color.rgb = get_cloud_color(aa_color.rgb, dir, cam_pos.xyz, time_of_day.x, dist, 1.0);
     #endif
 
+    if (medium.x == 1u) {
+        vec3 water_wpos = wpos_at(uv);
+        aa_color.rgb = underwater(aa_color.rgb, water_wpos, distance(water_wpos, cam_pos.xyz));
+    }
+
+    aa_color.rgb = outline(aa_color.rgb, uv);
+
     // aa_color.rgb = (wpos + focus_off.xyz) / vec3(32768, 32768, /*view_distance.w*/2048);
     // aa_color.rgb = mod((wpos + focus_off.xyz), vec3(32768, 32768, view_distance.w)) / vec3(32768, 32768, view_distance.w);// / vec3(32768, 32768, view_distance.w);
     // aa_color.rgb = mod((wpos + focus_off.xyz), vec3(32, 32, 16)) / vec3(32, 32, 16);// / vec3(32768, 32768, view_distance.w);
@@ -236,11 +328,5 @@ void main() {
 
     vec4 final_color = pow(aa_color, gamma);
 
-#if (FLUID_MODE == FLUID_MODE_CHEAP)
-    if (medium.x == 1u) {
-        final_color *= vec4(0.2, 0.2, 0.8, 1.0);
-    }
-#endif
-
     tgt_color = vec4(final_color.rgb, 1);
 }
@@ -12,6 +12,7 @@ pub enum Error {
     ServerShutdown,
     TooManyPlayers,
     NotOnWhitelist,
+    AssetPacksNotAllowed,
     AlreadyLoggedIn,
     AuthErr(String),
     AuthClientError(AuthClientError),
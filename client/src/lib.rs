@@ -4,9 +4,10 @@
 
 pub mod cmd;
 pub mod error;
+pub mod net_sim;
 
 // Reexports
-pub use crate::error::Error;
+pub use crate::{error::Error, net_sim::NetSimSettings};
 pub use authc::AuthClientError;
 pub use specs::{
     join::Join,
@@ -14,21 +15,26 @@ pub use specs::{
     Builder, DispatcherBuilder, Entity as EcsEntity, ReadStorage, WorldExt,
 };
 
+use crate::net_sim::NetSim;
 use byteorder::{ByteOrder, LittleEndian};
 use common::{
+    assets,
     character::{CharacterId, CharacterItem},
     comp::{
         self,
         chat::{KillSource, KillType},
-        group, ControlAction, ControlEvent, Controller, ControllerInputs, GroupManip,
-        InventoryManip, InventoryUpdateEvent,
+        group,
+        item::ToolCategory,
+        ControlAction, ControlEvent, Controller, ControllerInputs, GroupManip, InventoryManip,
+        InventoryUpdateEvent,
     },
+    cutscene::CutsceneEvent,
     event::{EventBus, LocalEvent},
     msg::{
         validate_chat_msg, ChatMsgValidationError, ClientGeneral, ClientInGame, ClientMsg,
-        ClientRegister, ClientType, DisconnectReason, InviteAnswer, Notification, PingMsg,
-        PlayerInfo, PlayerListUpdate, RegisterError, ServerGeneral, ServerInfo, ServerInit,
-        ServerRegisterAnswer, MAX_BYTES_CHAT_MSG,
+        ClientRegister, ClientType, DisconnectReason, InviteAnswer, MarketInfo, Notification,
+        PingMsg, PlayerInfo, PlayerListUpdate, RegisterError, ServerGeneral, ServerInfo,
+        ServerInit, ServerRegisterAnswer, MAX_BYTES_CHAT_MSG,
     },
     outcome::Outcome,
     recipe::RecipeBook,
@@ -67,6 +73,11 @@ pub enum Event {
     Notification(Notification),
     SetViewDistance(u32),
     Outcome(Outcome),
+    /// The player talked to a merchant, who showed them the prices at their
+    /// market
+    Market(MarketInfo),
+    /// The server is playing a scripted sequence for the player
+    Cutscene(CutsceneEvent),
 }
 
 pub struct Client {
@@ -118,6 +129,10 @@ pub struct Client {
     register_stream: Stream,
     character_screen_stream: Stream,
     in_game_stream: Stream,
+    /// Messages to the server held back by the simulated bad connection
+    outgoing: NetSim<ClientMsg>,
+    /// Messages from the server held back by the simulated bad connection
+    incoming: NetSim<Received>,
 
     client_timeout: Duration,
     last_server_ping: f64,
@@ -130,12 +145,22 @@ pub struct Client {
     entity: EcsEntity,
 
     view_distance: Option<u32>,
+    /// Position marked on the map by the server
+    map_marker: Option<Vec2<i32>>,
     // TODO: move into voxygen
     loaded_distance: f32,
 
     pending_chunks: HashMap<Vec2<i32>, Instant>,
 }
 
+/// A message from the server, along with the stream it came in on
+enum Received {
+    General(ServerGeneral),
+    Ping(PingMsg),
+    CharacterScreen(ServerGeneral),
+    InGame(ServerGeneral),
+}
+
 /// Holds data related to the current players characters, as well as some
 /// additional state to handle UI.
 #[derive(Default)]
@@ -196,6 +221,7 @@ impl Client {
             ServerInit::GameSync {
                 entity_package,
                 time_of_day,
+                day_length,
                 max_group_size,
                 client_timeout,
                 world_map,
@@ -210,6 +236,7 @@ impl Client {
 
                 let entity = state.ecs_mut().apply_entity_package(entity_package);
                 *state.ecs_mut().write_resource() = time_of_day;
+                *state.ecs_mut().write_resource() = day_length;
 
                 let map_size_lg = common::terrain::MapSizeLg::new(world_map.dimensions_lg)
                     .map_err(|_| {
@@ -366,6 +393,8 @@ impl Client {
         }?;
         ping_stream.send(PingMsg::Ping)?;
 
+        let net_sim = NetSimSettings::from_env();
+
         let mut thread_pool = ThreadPoolBuilder::new()
             .name("veloren-worker".into())
             .build();
@@ -402,6 +431,8 @@ impl Client {
             register_stream,
             character_screen_stream,
             in_game_stream,
+            outgoing: NetSim::new(net_sim),
+            incoming: NetSim::new(net_sim),
 
             client_timeout,
 
@@ -414,6 +445,7 @@ impl Client {
             state,
             entity,
             view_distance,
+            map_marker: None,
             loaded_distance: 0.0,
 
             pending_chunks: HashMap::new(),
@@ -444,13 +476,17 @@ impl Client {
                 }
         ).unwrap_or(Ok(username))?;
 
-        self.send_msg_err(ClientRegister { token_or_username })?;
+        self.send_msg_err(ClientRegister {
+            token_or_username,
+            asset_packs: assets::override_hashes(),
+        })?;
 
         match block_on(self.register_stream.recv::<ServerRegisterAnswer>())? {
             Err(RegisterError::AlreadyLoggedIn) => Err(Error::AlreadyLoggedIn),
             Err(RegisterError::AuthError(err)) => Err(Error::AuthErr(err)),
             Err(RegisterError::InvalidCharacter) => Err(Error::InvalidCharacter),
             Err(RegisterError::NotOnWhitelist) => Err(Error::NotOnWhitelist),
+            Err(RegisterError::AssetPacksNotAllowed) => Err(Error::AssetPacksNotAllowed),
             Err(RegisterError::Banned(reason)) => Err(Error::Banned(reason)),
             Ok(()) => {
                 self.registered = true;
@@ -476,6 +512,18 @@ impl Client {
                 )
             );
         }
+        match msg {
+            // Registering waits for the answer of the server, so it skips the
+            // simulated bad connection
+            ClientMsg::Type(_) | ClientMsg::Register(_) => self.send_now(msg),
+            msg => match self.outgoing.send(msg) {
+                Some(msg) => self.send_now(msg),
+                None => Ok(()),
+            },
+        }
+    }
+
+    fn send_now(&mut self, msg: ClientMsg) -> Result<(), network::StreamError> {
         match msg {
             ClientMsg::Type(msg) => self.register_stream.send(msg),
             ClientMsg::Register(msg) => self.register_stream.send(msg),
@@ -567,6 +615,30 @@ impl Client {
     }
 
     pub fn use_slot(&mut self, slot: comp::slot::Slot) {
+        // Throwables with impact effects are wound up and thrown by the character
+        // state rather than used directly, and instruments are played by it
+        if let comp::slot::Slot::Inventory(index) = slot {
+            let action = self
+                .state
+                .ecs()
+                .read_storage::<comp::Inventory>()
+                .get(self.entity)
+                .and_then(|inv| inv.get(index))
+                .and_then(|item| match item.kind() {
+                    comp::item::ItemKind::Throwable { on_impact, .. } if !on_impact.is_empty() => {
+                        Some(ControlAction::Throw(index))
+                    },
+                    comp::item::ItemKind::Instrument { kind } => {
+                        Some(ControlAction::Perform(*kind))
+                    },
+                    _ => None,
+                });
+            if let Some(action) = action {
+                self.control_action(action);
+                return;
+            }
+        }
+
         self.send_msg(ClientGeneral::ControlEvent(ControlEvent::InventoryManip(
             InventoryManip::Use(slot),
         )));
@@ -584,6 +656,24 @@ impl Client {
         )));
     }
 
+    pub fn dye_armor(&mut self, dye_slot: usize, equip_slot: comp::slot::EquipSlot) {
+        self.send_msg(ClientGeneral::ControlEvent(ControlEvent::InventoryManip(
+            InventoryManip::Dye(dye_slot, equip_slot),
+        )));
+    }
+
+    pub fn set_appearance(&mut self, inv_slot: usize, equip_slot: comp::slot::EquipSlot) {
+        self.send_msg(ClientGeneral::ControlEvent(ControlEvent::InventoryManip(
+            InventoryManip::SetAppearance(inv_slot, equip_slot),
+        )));
+    }
+
+    pub fn clear_appearance(&mut self, equip_slot: comp::slot::EquipSlot) {
+        self.send_msg(ClientGeneral::ControlEvent(ControlEvent::InventoryManip(
+            InventoryManip::ClearAppearance(equip_slot),
+        )));
+    }
+
     pub fn pick_up(&mut self, entity: EcsEntity) {
         if let Some(uid) = self.state.read_component_copied(entity) {
             self.send_msg(ClientGeneral::ControlEvent(ControlEvent::InventoryManip(
@@ -792,6 +882,21 @@ impl Client {
         }
     }
 
+    pub fn toggle_crouch(&mut self) {
+        let is_crouching = self
+            .state
+            .ecs()
+            .read_storage::<comp::CharacterState>()
+            .get(self.entity)
+            .map(|cs| cs.is_crouch());
+
+        match is_crouching {
+            Some(true) => self.control_action(ControlAction::Stand),
+            Some(false) => self.control_action(ControlAction::Crouch),
+            None => warn!("Can't toggle crouch, client entity doesn't have a `CharacterState`"),
+        }
+    }
+
     pub fn toggle_glide(&mut self) {
         let is_gliding = self
             .state
@@ -812,6 +917,53 @@ impl Client {
         }
     }
 
+    /// Starts reviving a downed group member
+    pub fn revive(&mut self, target: Uid) { self.control_action(ControlAction::Revive(target)) }
+
+    /// Casts a line into the water in the look direction, or reels it in if
+    /// already fishing
+    pub fn fish(&mut self) { self.control_action(ControlAction::Fish) }
+
+    /// Plays a note of the instrument being performed with
+    pub fn play_note(&mut self, note: u8) { self.control_action(ControlAction::PlayNote(note)) }
+
+    /// Plays one of the preset songs on the instrument being performed with
+    pub fn play_song(&mut self, song: usize) { self.control_action(ControlAction::PlaySong(song)) }
+
+    /// Reads the bounties posted on the board at the given position
+    pub fn read_bounty_board(&mut self, pos: Vec3<i32>) {
+        self.send_msg(ClientGeneral::ControlEvent(ControlEvent::ReadBountyBoard(
+            pos,
+        )));
+    }
+
+    /// Talks to the NPC `entity`, such as a merchant to see the prices at
+    /// their market
+    pub fn talk(&mut self, entity: EcsEntity) {
+        if let Some(uid) = self.state.read_component_copied(entity) {
+            self.send_msg(ClientGeneral::ControlEvent(ControlEvent::Talk(uid)));
+        }
+    }
+
+    /// Buys one of the good at `good` in the market of the merchant with the
+    /// given uid, or sells one to them
+    pub fn trade(&mut self, merchant: Uid, good: usize, action: comp::TradeAction) {
+        self.send_msg(ClientGeneral::ControlEvent(ControlEvent::Trade {
+            merchant,
+            good,
+            action,
+        }));
+    }
+
+    /// Chooses the option at `index` for an ability slot of a kind of weapon
+    pub fn select_ability(&mut self, tool: ToolCategory, slot: usize, index: usize) {
+        self.send_msg(ClientGeneral::ControlEvent(ControlEvent::SelectAbility {
+            tool,
+            slot,
+            index,
+        }));
+    }
+
     fn control_action(&mut self, control_action: ControlAction) {
         if let Some(controller) = self
             .state
@@ -826,6 +978,8 @@ impl Client {
 
     pub fn view_distance(&self) -> Option<u32> { self.view_distance }
 
+    pub fn map_marker(&self) -> Option<Vec2<i32>> { self.map_marker }
+
     pub fn loaded_distance(&self) -> f32 { self.loaded_distance }
 
     pub fn current_chunk(&self) -> Option<Arc<TerrainChunk>> {
@@ -878,6 +1032,12 @@ impl Client {
         )));
     }
 
+    pub fn unlock_block(&mut self, pos: Vec3<i32>) {
+        self.send_msg(ClientGeneral::ControlEvent(ControlEvent::InventoryManip(
+            InventoryManip::Unlock(pos),
+        )));
+    }
+
     /// Execute a single client tick, handle input and update the game state by
     /// the given duration.
     pub fn tick(
@@ -1084,10 +1244,12 @@ impl Client {
                 self.state.read_storage().get(self.entity).cloned(),
                 self.state.read_storage().get(self.entity).cloned(),
             ) {
-                self.in_game_stream
-                    .send(ClientGeneral::PlayerPhysics { pos, vel, ori })?;
+                self.send_msg_err(ClientGeneral::PlayerPhysics { pos, vel, ori })?;
             }
         }
+        for msg in self.outgoing.arrived() {
+            self.send_now(msg)?;
+        }
 
         /*
         // Output debug metrics
@@ -1384,6 +1546,16 @@ impl Client {
 
                 frontend_events.push(Event::InventoryUpdated(event));
             },
+            ServerGeneral::CombatLog(events) => {
+                let mut combat_logs = self.state.ecs().write_storage::<comp::CombatLog>();
+                if let Some(combat_log) = combat_logs.get_mut(self.entity) {
+                    combat_log.extend(events);
+                } else {
+                    let mut combat_log = comp::CombatLog::default();
+                    combat_log.extend(events);
+                    let _ = combat_logs.insert(self.entity, combat_log);
+                }
+            },
             ServerGeneral::TerrainChunkUpdate { key, chunk } => {
                 if let Ok(chunk) = chunk {
                     self.state.insert_chunk(key, *chunk);
@@ -1411,6 +1583,11 @@ impl Client {
                         impulse,
                     });
             },
+            ServerGeneral::MapMarker(marker) => {
+                self.map_marker = marker;
+            },
+            ServerGeneral::Market(market) => frontend_events.push(Event::Market(market)),
+            ServerGeneral::Cutscene(event) => frontend_events.push(Event::Cutscene(event)),
             _ => unreachable!("Not a in_game message"),
         }
         Ok(())
@@ -1477,21 +1654,36 @@ impl Client {
                 msg = self.in_game_stream.recv().fuse() => (None, None, None, Some(msg)),
             );
             *cnt += 1;
-            if let Some(msg) = m1 {
-                self.handle_server_msg(frontend_events, msg?)?;
-            }
-            if let Some(msg) = m2 {
-                self.handle_ping_msg(msg?)?;
-            }
-            if let Some(msg) = m3 {
-                self.handle_server_character_screen_msg(msg?)?;
-            }
-            if let Some(msg) = m4 {
-                self.handle_server_in_game_msg(frontend_events, msg?)?;
+            let received = if let Some(msg) = m1 {
+                Received::General(msg?)
+            } else if let Some(msg) = m2 {
+                Received::Ping(msg?)
+            } else if let Some(msg) = m3 {
+                Received::CharacterScreen(msg?)
+            } else if let Some(msg) = m4 {
+                Received::InGame(msg?)
+            } else {
+                continue;
+            };
+            if let Some(received) = self.incoming.send(received) {
+                self.handle_received(frontend_events, received)?;
             }
         }
     }
 
+    fn handle_received(
+        &mut self,
+        frontend_events: &mut Vec<Event>,
+        received: Received,
+    ) -> Result<(), Error> {
+        match received {
+            Received::General(msg) => self.handle_server_msg(frontend_events, msg),
+            Received::Ping(msg) => self.handle_ping_msg(msg),
+            Received::CharacterScreen(msg) => self.handle_server_character_screen_msg(msg),
+            Received::InGame(msg) => self.handle_server_in_game_msg(frontend_events, msg),
+        }
+    }
+
     /// Handle new server messages.
     fn handle_new_messages(&mut self) -> Result<Vec<Event>, Error> {
         let mut frontend_events = Vec::new();
@@ -1524,6 +1716,9 @@ impl Client {
                 err = self.handle_messages(&mut frontend_events, &mut handles_msg).fuse() => err,
             )
         })?;
+        for received in self.incoming.arrived() {
+            self.handle_received(&mut frontend_events, received)?;
+        }
 
         if handles_msg == 0
             && self.state.get_time() - self.last_server_pong > self.client_timeout.as_secs() as f64
@@ -1546,6 +1741,16 @@ impl Client {
 
     pub fn get_ping_ms(&self) -> f64 { self.last_ping_delta * 1000.0 }
 
+    /// How bad the simulated connection to the server is, if it is simulated
+    pub fn net_sim(&self) -> Option<NetSimSettings> { self.outgoing.settings() }
+
+    /// Simulates a bad connection to the server for testing, or stops doing so
+    /// with `None`
+    pub fn set_net_sim(&mut self, settings: Option<NetSimSettings>) {
+        self.outgoing.set_settings(settings);
+        self.incoming.set_settings(settings);
+    }
+
     pub fn get_ping_ms_rolling_avg(&self) -> f64 {
         let mut total_weight = 0.;
         let pings = self.ping_deltas.len() as f64;
@@ -1617,6 +1822,7 @@ impl Client {
             .allocate(entity_builder.entity, Some(client_uid));
 
         self.entity = entity_builder.with(uid).build();
+        self.map_marker = None;
     }
 
     /// Change player alias to "You" if client belongs to matching player
@@ -0,0 +1,191 @@
+//! Simulation of a bad connection to the server for development, holding back
+//! messages to and from the server to test prediction, interpolation and
+//! interest management without having to find a bad network.
+//!
+//! It is turned on by setting any of these environment variables:
+//! - `VELOREN_NETSIM_LATENCY`: milliseconds each message is held back for
+//! - `VELOREN_NETSIM_JITTER`: up to how many milliseconds more a message is
+//!   held back for at random
+//! - `VELOREN_NETSIM_LOSS`: the percentage of messages that are lost
+//!
+//! Since every stream to the server is reliable and ordered, lost messages
+//! are sent again after a retransmission timeout the way TCP would, holding
+//! up the messages behind them, rather than disappearing.
+
+use rand::Rng;
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+use tracing::{info, warn};
+
+/// The shortest time before a lost message is sent again
+const MIN_RETRANSMIT: Duration = Duration::from_millis(200);
+/// Largest value that any of the settings can be set to, which keeps the
+/// latency and jitter under a minute
+const MAX_SETTING: f32 = 60_000.0;
+
+/// How bad the simulated connection is, in each direction
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct NetSimSettings {
+    /// How long every message is held back for
+    pub latency: Duration,
+    /// Up to how much longer a message is held back for at random
+    pub jitter: Duration,
+    /// The chance of a message being lost, from 0 to 1
+    pub loss: f32,
+}
+
+impl NetSimSettings {
+    /// Reads the settings from the environment, if any of them are set
+    pub fn from_env() -> Option<Self> {
+        fn var(name: &str) -> Option<f32> { parse_setting(name, &std::env::var(name).ok()?) }
+        let latency = var("VELOREN_NETSIM_LATENCY");
+        let jitter = var("VELOREN_NETSIM_JITTER");
+        let loss = var("VELOREN_NETSIM_LOSS");
+        if latency.is_none() && jitter.is_none() && loss.is_none() {
+            return None;
+        }
+
+        let settings = Self {
+            latency: Duration::from_secs_f32(latency.unwrap_or(0.0) / 1000.0),
+            jitter: Duration::from_secs_f32(jitter.unwrap_or(0.0) / 1000.0),
+            loss: (loss.unwrap_or(0.0) / 100.0).min(1.0),
+        };
+        info!(?settings, "Simulating a bad connection to the server");
+        Some(settings)
+    }
+
+    /// How long a message sent now is held back for
+    fn delay(&self, rng: &mut impl Rng) -> Duration {
+        let mut delay = self.latency + self.jitter.mul_f32(rng.gen());
+        // A lost message is noticed after about a round trip and sent again,
+        // possibly to be lost once more
+        let retransmit = MIN_RETRANSMIT.max(self.latency * 2);
+        while self.loss > 0.0 && rng.gen::<f32>() < self.loss.min(0.9) {
+            delay += retransmit + self.latency;
+        }
+        delay
+    }
+}
+
+/// Parses the value of one of the settings, keeping it in bounds
+fn parse_setting(name: &str, value: &str) -> Option<f32> {
+    match value.trim().parse::<f32>() {
+        Ok(value) if value.is_finite() => Some(value.max(0.0).min(MAX_SETTING)),
+        Ok(_) => {
+            warn!(?name, ?value, "Network simulation setting isn't finite");
+            None
+        },
+        Err(e) => {
+            warn!(?e, ?name, ?value, "Invalid network simulation setting");
+            None
+        },
+    }
+}
+
+/// Messages going one way over the simulated connection, held back until
+/// they would have arrived
+pub struct NetSim<T> {
+    settings: Option<NetSimSettings>,
+    /// Held back messages in the order they were sent, with when they arrive
+    queue: VecDeque<(Instant, T)>,
+}
+
+impl<T> NetSim<T> {
+    pub fn new(settings: Option<NetSimSettings>) -> Self {
+        Self {
+            settings,
+            queue: VecDeque::new(),
+        }
+    }
+
+    pub fn settings(&self) -> Option<NetSimSettings> { self.settings }
+
+    /// Changes how bad the connection is for messages sent from now on, or
+    /// turns the simulation off with `None`
+    pub fn set_settings(&mut self, settings: Option<NetSimSettings>) { self.settings = settings; }
+
+    /// Sends a message over the simulated connection, handing it straight back
+    /// if it doesn't have to be held back
+    pub fn send(&mut self, msg: T) -> Option<T> {
+        let now = Instant::now();
+        let arrival = match self.settings {
+            Some(settings) => now + settings.delay(&mut rand::thread_rng()),
+            // Messages still have to wait for the ones sent before them
+            None if self.queue.is_empty() => return Some(msg),
+            None => now,
+        };
+        // Reliable streams never let a message overtake an earlier one
+        let arrival = self
+            .queue
+            .back()
+            .map_or(arrival, |(last, _)| arrival.max(*last));
+        self.queue.push_back((arrival, msg));
+        None
+    }
+
+    /// Takes the messages that have arrived by now, in the order they were
+    /// sent
+    pub fn arrived(&mut self) -> Vec<T> {
+        let now = Instant::now();
+        let mut arrived = Vec::new();
+        while self
+            .queue
+            .front()
+            .map_or(false, |(arrival, _)| *arrival <= now)
+        {
+            arrived.extend(self.queue.pop_front().map(|(_, msg)| msg));
+        }
+        arrived
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn passes_messages_through_when_off() {
+        let mut sim = NetSim::new(None);
+        assert_eq!(sim.send(1), Some(1));
+        assert!(sim.arrived().is_empty());
+    }
+
+    #[test]
+    fn settings_stay_in_bounds() {
+        assert_eq!(parse_setting("latency", " 150 "), Some(150.0));
+        assert_eq!(parse_setting("latency", "-5"), Some(0.0));
+        assert_eq!(parse_setting("latency", "1e30"), Some(MAX_SETTING));
+        assert_eq!(parse_setting("latency", "inf"), None);
+        assert_eq!(parse_setting("latency", "NaN"), None);
+        assert_eq!(parse_setting("latency", "fast"), None);
+        assert_eq!(
+            Duration::from_secs_f32(MAX_SETTING / 1000.0),
+            Duration::from_secs(60)
+        );
+    }
+
+    #[test]
+    fn keeps_messages_in_order() {
+        let mut sim = NetSim::new(Some(NetSimSettings {
+            latency: Duration::from_millis(50),
+            jitter: Duration::from_millis(20),
+            loss: 0.3,
+        }));
+        for i in 0..20 {
+            assert_eq!(sim.send(i), None);
+        }
+        assert!(sim.arrived().is_empty());
+
+        // Turning the simulation off still waits for the held back messages
+        sim.set_settings(None);
+        assert_eq!(sim.send(20), None);
+        let mut arrived = Vec::new();
+        while arrived.len() < 21 {
+            arrived.append(&mut sim.arrived());
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        assert_eq!(arrived, (0..21).collect::<Vec<_>>());
+    }
+}
@@ -1,4 +1,5 @@
 //! Load assets (images or voxel data) from files
+pub mod pack;
 pub mod watch;
 
 use core::{any::Any, fmt, marker::PhantomData};
@@ -59,6 +60,28 @@ lazy_static! {
     /// The HashMap where all loaded assets are stored in.
     static ref ASSETS: RwLock<HashMap<String, Arc<dyn Any + 'static + Sync + Send>>> =
         RwLock::new(HashMap::new());
+
+    /// Asset packs whose assets are loaded in place of the built-in ones, in
+    /// order of precedence.
+    static ref OVERRIDES: RwLock<Vec<pack::AssetPack>> = RwLock::new(Vec::new());
+}
+
+/// Sets the asset packs whose assets are loaded in place of the built-in ones,
+/// the first pack taking precedence. Assets that were already loaded are
+/// loaded again the next time they are asked for.
+pub fn set_overrides(packs: Vec<pack::AssetPack>) {
+    *OVERRIDES.write().unwrap() = packs;
+    ASSETS.write().unwrap().clear();
+}
+
+/// Hashes of the asset packs in use, as checked by servers that restrict them
+pub fn override_hashes() -> Vec<u64> {
+    OVERRIDES
+        .read()
+        .unwrap()
+        .iter()
+        .map(|pack| pack.hash)
+        .collect()
 }
 
 fn reload<A: Asset>(specifier: &str) -> Result<(), Error>
@@ -245,22 +268,13 @@ pub trait Asset: Sized {
         let asset = Self::load(specifier)?;
 
         // Determine path to watch
-        let path = unpack_specifier(specifier);
-        let mut path_with_extension = None;
-        for ending in Self::ENDINGS {
-            let mut path = path.clone();
-            path.set_extension(ending);
-
-            if path.exists() {
-                path_with_extension = Some(path);
-                break;
-            }
-        }
+        let path_with_extension = find_file(specifier, Self::ENDINGS);
 
         let owned_specifier = specifier.to_string();
         indicator.add(
-            path_with_extension
-                .ok_or_else(|| Error::NotFound(path.to_string_lossy().into_owned()))?,
+            path_with_extension.ok_or_else(|| {
+                Error::NotFound(unpack_specifier(specifier).to_string_lossy().into_owned())
+            })?,
             move || {
                 if let Err(e) = reload::<Self>(&owned_specifier) {
                     error!(?e, ?owned_specifier, "Error reloading owned_specifier");
@@ -431,36 +445,57 @@ fn unpack_specifier(specifier: &str) -> PathBuf {
     path
 }
 
+/// Whether asset packs may replace the asset. Only the client's own assets
+/// (models, textures, sounds and so on) can be replaced, so that packs can't
+/// change gameplay data such as items or loot tables, which the server shares.
+fn is_overridable(specifier: &str) -> bool { specifier.starts_with("voxygen.") }
+
+/// Finds the file of an asset, looking through the asset packs in use before
+/// the built-in assets
+fn find_file(specifier: &str, endings: &[&str]) -> Option<PathBuf> {
+    find_file_in(&OVERRIDES.read().unwrap(), specifier, endings)
+}
+
+fn find_file_in(
+    overrides: &[pack::AssetPack],
+    specifier: &str,
+    endings: &[&str],
+) -> Option<PathBuf> {
+    let relative = specifier.replace(".", "/");
+    let overrides: &[pack::AssetPack] = if is_overridable(specifier) {
+        overrides
+    } else {
+        &[]
+    };
+    overrides
+        .iter()
+        .map(|pack| &pack.path)
+        .chain(std::iter::once(&*ASSETS_PATH))
+        .flat_map(|root| {
+            let path = root.join(&relative);
+            endings
+                .iter()
+                .map(move |ending| path.with_extension(ending))
+        })
+        .find(|path| {
+            trace!(?path, "Trying to access");
+            path.is_file()
+        })
+}
+
 /// Loads a file based on the specifier and possible extensions
 pub fn load_file(specifier: &str, endings: &[&str]) -> Result<BufReader<File>, Error> {
-    let path = unpack_specifier(specifier);
-    for ending in endings {
-        let mut path = path.clone();
-        path.set_extension(ending);
-
-        trace!(?path, "Trying to access");
-        if let Ok(file) = File::open(path) {
-            return Ok(BufReader::new(file));
-        }
+    match find_file(specifier, endings) {
+        Some(path) => Ok(BufReader::new(File::open(path)?)),
+        None => Err(Error::NotFound(
+            unpack_specifier(specifier).to_string_lossy().into_owned(),
+        )),
     }
-
-    Err(Error::NotFound(path.to_string_lossy().into_owned()))
 }
 
 /// Loads a file based on the specifier and possible extensions
 pub fn load_file_glob(specifier: &str, endings: &[&str]) -> Result<BufReader<File>, Error> {
-    let path = unpack_specifier(specifier);
-    for ending in endings {
-        let mut path = path.clone();
-        path.set_extension(ending);
-
-        trace!(?path, "Trying to access");
-        if let Ok(file) = File::open(path) {
-            return Ok(BufReader::new(file));
-        }
-    }
-
-    Err(Error::NotFound(path.to_string_lossy().into_owned()))
+    load_file(specifier, endings)
 }
 
 /// Read directory from `veloren/assets/*`
@@ -506,3 +541,48 @@ fn get_glob_matches(specifier: &str) -> Result<Vec<String>, Error> {
         .collect::<Vec<_>>()
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_pack(name: &str, files: &[&str]) -> pack::AssetPack {
+        let path = std::env::temp_dir().join(format!("veloren-{}-{}", name, std::process::id()));
+        for file in files {
+            let file = path.join(file);
+            fs::create_dir_all(file.parent().unwrap()).unwrap();
+            fs::write(file, "()").unwrap();
+        }
+        pack::AssetPack::open(path).unwrap()
+    }
+
+    #[test]
+    fn only_client_assets_are_overridable() {
+        assert!(is_overridable("voxygen.voxel.sprite.apple"));
+        assert!(is_overridable("voxygen.audio.sfx.footsteps.stepgrass_1"));
+        assert!(!is_overridable(
+            "common.items.weapons.sword.zweihander_sword_0"
+        ));
+        assert!(!is_overridable("common.loot_tables.loot_table"));
+        assert!(!is_overridable("world.manifests.trees"));
+    }
+
+    #[test]
+    fn packs_replace_client_assets_only() {
+        let pack = test_pack("override-test", &[
+            "voxygen/test_pack/icon.ron",
+            "common/items/test_pack/item.ron",
+        ]);
+
+        assert_eq!(
+            find_file_in(&[pack.clone()], "voxygen.test_pack.icon", &["ron"]),
+            Some(pack.path.join("voxygen/test_pack/icon.ron")),
+        );
+        assert_eq!(
+            find_file_in(&[pack.clone()], "common.items.test_pack.item", &["ron"]),
+            None,
+        );
+
+        fs::remove_dir_all(&pack.path).unwrap();
+    }
+}
@@ -0,0 +1,81 @@
+//! Packs of assets that take precedence over the built-in ones.
+//!
+//! A pack is a directory laid out like the `assets` directory. Any model,
+//! texture, sound or other client asset under `voxygen` found in an enabled
+//! pack is loaded in place of the built-in asset with the same specifier, and
+//! packs earlier in the list take precedence over later ones. Gameplay data
+//! such as items and loot tables can't be replaced.
+
+use fxhash::FxHasher64;
+use std::{
+    fs,
+    hash::Hasher,
+    io,
+    path::{Path, PathBuf},
+};
+
+#[derive(Clone, Debug)]
+pub struct AssetPack {
+    pub name: String,
+    pub path: PathBuf,
+    /// Hash of the files in the pack, which servers use to tell which packs
+    /// players are allowed to use
+    pub hash: u64,
+}
+
+impl AssetPack {
+    pub fn open(path: PathBuf) -> io::Result<Self> {
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let mut files = Vec::new();
+        list_files(&path, &mut files)?;
+        // Hash the files in a fixed order, so that the hash is the same on every system
+        files.sort();
+
+        let mut hasher = FxHasher64::default();
+        for file in files {
+            let relative = file.strip_prefix(&path).unwrap_or(&file);
+            for component in relative.components() {
+                hasher.write(component.as_os_str().to_string_lossy().as_bytes());
+                hasher.write_u8(b'/');
+            }
+            hasher.write(&fs::read(&file)?);
+        }
+
+        Ok(Self {
+            name,
+            path,
+            hash: hasher.finish(),
+        })
+    }
+}
+
+fn list_files(dir: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            list_files(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Names of the packs in the given directory, which holds each pack in a
+/// directory of its own
+pub fn find_packs(dir: &Path) -> Vec<String> {
+    let mut names = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| entry.path().is_dir())
+                .map(|entry| entry.file_name().to_string_lossy().into_owned())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    names.sort();
+    names
+}
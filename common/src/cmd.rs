@@ -40,9 +40,13 @@ pub enum ChatCommand {
     Ban,
     Build,
     Campfire,
+    Cart,
+    Date,
     Debug,
     DebugColumn,
+    Difficulty,
     Dummy,
+    Duel,
     Explosion,
     Faction,
     GiveExp,
@@ -65,10 +69,12 @@ pub enum ChatCommand {
     Players,
     Region,
     RemoveLights,
+    Rollback,
     Say,
     SetLevel,
     SetMotd,
     Spawn,
+    Stable,
     Sudo,
     Tell,
     Time,
@@ -87,9 +93,13 @@ pub static CHAT_COMMANDS: &[ChatCommand] = &[
     ChatCommand::Ban,
     ChatCommand::Build,
     ChatCommand::Campfire,
+    ChatCommand::Cart,
+    ChatCommand::Date,
     ChatCommand::Debug,
     ChatCommand::DebugColumn,
+    ChatCommand::Difficulty,
     ChatCommand::Dummy,
+    ChatCommand::Duel,
     ChatCommand::Explosion,
     ChatCommand::Faction,
     ChatCommand::GiveExp,
@@ -112,10 +122,12 @@ pub static CHAT_COMMANDS: &[ChatCommand] = &[
     ChatCommand::Players,
     ChatCommand::Region,
     ChatCommand::RemoveLights,
+    ChatCommand::Rollback,
     ChatCommand::Say,
     ChatCommand::SetLevel,
     ChatCommand::SetMotd,
     ChatCommand::Spawn,
+    ChatCommand::Stable,
     ChatCommand::Sudo,
     ChatCommand::Tell,
     ChatCommand::Time,
@@ -141,6 +153,19 @@ lazy_static! {
         .iter()
         .map(|s| s.to_string())
         .collect();
+    static ref DIFFICULTIES: Vec<String> = comp::DungeonDifficulty::ALL
+        .iter()
+        .map(|d| d.name().to_string())
+        .chain(std::iter::once("confirm".to_string()))
+        .collect();
+    static ref CART_ACTIONS: Vec<String> = vec!["list", "hitch", "unhitch", "load", "unload"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    static ref STABLE_ACTIONS: Vec<String> = vec!["list", "store", "take", "care"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
     static ref ENTITIES: Vec<String> = {
         let npc_names = &*npc::NPC_NAMES;
         npc::ALL_NPCS
@@ -213,13 +238,38 @@ impl ChatCommand {
             ),
             ChatCommand::Build => cmd(vec![], "Toggles build mode on and off", Admin),
             ChatCommand::Campfire => cmd(vec![], "Spawns a campfire", Admin),
+            ChatCommand::Cart => cmd(
+                vec![
+                    Enum("action", CART_ACTIONS.clone(), Optional),
+                    Integer("slot", 1, Optional),
+                ],
+                "List the cargo of the cart next to you, hitch it to or unhitch it from your pet, \
+                 or load and unload inventory slots",
+                NoAdmin,
+            ),
+            ChatCommand::Date => cmd(
+                vec![Integer("day", 1, Optional), Integer("year", 1, Optional)],
+                "Show the date, or set the day of the year and the year (admins only)",
+                NoAdmin,
+            ),
             ChatCommand::Debug => cmd(vec![], "Place all debug items into your pack.", Admin),
             ChatCommand::DebugColumn => cmd(
                 vec![Integer("x", 15000, Required), Integer("y", 15000, Required)],
                 "Prints some debug information about a column",
                 NoAdmin,
             ),
+            ChatCommand::Difficulty => cmd(
+                vec![Enum("difficulty", DIFFICULTIES.clone(), Optional)],
+                "Shows or (as group leader) sets the difficulty of dungeons your group enters, or \
+                 confirms entering the dungeon your group is waiting at",
+                NoAdmin,
+            ),
             ChatCommand::Dummy => cmd(vec![], "Spawns a training dummy", Admin),
+            ChatCommand::Duel => cmd(
+                vec![PlayerName(Required)],
+                "Challenge another player to a duel, or accept their challenge",
+                NoAdmin,
+            ),
             ChatCommand::Explosion => cmd(
                 vec![Float("radius", 5.0, Required)],
                 "Explodes the ground around you",
@@ -337,6 +387,15 @@ impl ChatCommand {
                 "Removes all lights spawned by players",
                 Admin,
             ),
+            ChatCommand::Rollback => cmd(
+                vec![
+                    Any("username", Required),
+                    Any("time", Required),
+                    Float("radius", 64.0, Optional),
+                ],
+                "Undo the blocks a player changed within the given time (like 30m or 2h) near you",
+                Admin,
+            ),
             ChatCommand::Region => cmd(
                 vec![Message(Optional)],
                 "Send messages to everyone in your region of the world",
@@ -365,6 +424,15 @@ impl ChatCommand {
                 "Spawn a test entity",
                 Admin,
             ),
+            ChatCommand::Stable => cmd(
+                vec![
+                    Enum("action", STABLE_ACTIONS.clone(), Optional),
+                    Integer("pet", 1, Optional),
+                ],
+                "List, store or take out your pets, or pay to have them cared for, at a stable \
+                 master",
+                NoAdmin,
+            ),
             ChatCommand::Sudo => cmd(
                 vec![PlayerName(Required), SubCommand],
                 "Run command as if you were another player",
@@ -415,9 +483,13 @@ impl ChatCommand {
             ChatCommand::Ban => "ban",
             ChatCommand::Build => "build",
             ChatCommand::Campfire => "campfire",
+            ChatCommand::Cart => "cart",
+            ChatCommand::Date => "date",
             ChatCommand::Debug => "debug",
             ChatCommand::DebugColumn => "debug_column",
+            ChatCommand::Difficulty => "difficulty",
             ChatCommand::Dummy => "dummy",
+            ChatCommand::Duel => "duel",
             ChatCommand::Explosion => "explosion",
             ChatCommand::Faction => "faction",
             ChatCommand::GiveExp => "give_exp",
@@ -440,10 +512,12 @@ impl ChatCommand {
             ChatCommand::Players => "players",
             ChatCommand::Region => "region",
             ChatCommand::RemoveLights => "remove_lights",
+            ChatCommand::Rollback => "rollback",
             ChatCommand::Say => "say",
             ChatCommand::SetLevel => "set_level",
             ChatCommand::SetMotd => "set_motd",
             ChatCommand::Spawn => "spawn",
+            ChatCommand::Stable => "stable",
             ChatCommand::Sudo => "sudo",
             ChatCommand::Tell => "tell",
             ChatCommand::Time => "time",
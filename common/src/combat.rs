@@ -0,0 +1,239 @@
+use crate::{
+    comp::{Body, Buffs, Group, Player, Scale},
+    sync::Uid,
+};
+use hashbrown::{HashMap, HashSet};
+use specs::{Entity, ReadStorage};
+use vek::*;
+
+/// Mass of a humanoid, which is knocked back exactly as far as attacks intend
+const KNOCKBACK_REFERENCE_MASS: f32 = 80.0;
+/// Most that knockback is amplified for very light bodies
+const MAX_KNOCKBACK_MULTIPLIER: f32 = 2.0;
+/// Seconds that a challenge to a duel can be accepted for
+pub const DUEL_REQUEST_TIMEOUT: f64 = 60.0;
+
+/// Server-configurable rules deciding who can hurt whom, consulted by melee
+/// attacks, projectiles, beams, shockwaves and explosions
+#[derive(Clone, Debug)]
+pub struct CombatRules {
+    /// Whether players can hurt each other outside of duels
+    pub pvp: bool,
+    /// Whether members of the same group are immune to each other's attacks
+    pub group_immunity: bool,
+    /// Players within this distance of a safe zone's center can't be hurt by
+    /// other players, except in duels
+    pub safe_zone_radius: f32,
+    /// Centers of the safe zones, usually the towns players spawn in
+    pub safe_zones: Vec<Vec2<f32>>,
+    pub duels: Duels,
+    /// Whether damage over time is put on hold while its target is
+    /// invulnerable, rather than running its course without doing any damage
+    pub invulnerability_pauses_dots: bool,
+}
+
+impl Default for CombatRules {
+    fn default() -> Self {
+        Self {
+            pvp: true,
+            group_immunity: true,
+            safe_zone_radius: 0.0,
+            safe_zones: Vec::new(),
+            duels: Duels::default(),
+            invulnerability_pauses_dots: false,
+        }
+    }
+}
+
+/// What the combat rules need to know about an entity taking part in an
+/// attack
+#[derive(Copy, Clone, Debug)]
+pub struct Combatant {
+    pub uid: Uid,
+    pub is_player: bool,
+    pub group: Option<Group>,
+    pub pos: Vec3<f32>,
+}
+
+impl Combatant {
+    pub fn new(
+        entity: Entity,
+        uid: Uid,
+        pos: Vec3<f32>,
+        players: &ReadStorage<Player>,
+        groups: &ReadStorage<Group>,
+    ) -> Self {
+        Self {
+            uid,
+            is_player: players.contains(entity),
+            group: groups.get(entity).copied(),
+            pos,
+        }
+    }
+}
+
+impl CombatRules {
+    /// Whether `attacker` is allowed to damage `target`
+    pub fn can_harm(&self, attacker: Combatant, target: Combatant) -> bool {
+        if attacker.uid == target.uid {
+            return true;
+        }
+        if self.group_immunity && attacker.group.is_some() && attacker.group == target.group {
+            return false;
+        }
+        if attacker.is_player && target.is_player {
+            return self.duels.are_dueling(attacker.uid, target.uid)
+                || (self.pvp && !self.in_safe_zone(target.pos.xy()));
+        }
+        true
+    }
+
+    pub fn in_safe_zone(&self, wpos: Vec2<f32>) -> bool {
+        self.safe_zones
+            .iter()
+            .any(|center| center.distance_squared(wpos) < self.safe_zone_radius.powi(2))
+    }
+}
+
+/// Duels players agreed to, in which they can hurt each other regardless of
+/// the other rules
+#[derive(Clone, Debug, Default)]
+pub struct Duels {
+    /// Pairs of challenger and challenged player, with the time of the
+    /// challenge
+    requests: HashMap<(Uid, Uid), f64>,
+    /// Pairs of dueling players, lowest uid first
+    active: HashSet<(Uid, Uid)>,
+}
+
+impl Duels {
+    /// Records that `from` wants to duel `to` at `time`, and starts the duel if
+    /// `to` has asked for it within the last [`DUEL_REQUEST_TIMEOUT`] seconds.
+    /// Returns whether the duel started.
+    pub fn request(&mut self, from: Uid, to: Uid, time: f64) -> bool {
+        self.requests
+            .retain(|_, requested| time - *requested < DUEL_REQUEST_TIMEOUT);
+        if self.requests.remove(&(to, from)).is_some() {
+            self.active.insert(Self::pair(from, to));
+            true
+        } else {
+            self.requests.insert((from, to), time);
+            false
+        }
+    }
+
+    pub fn are_dueling(&self, a: Uid, b: Uid) -> bool { self.active.contains(&Self::pair(a, b)) }
+
+    /// Ends all duels and withdraws all requests involving `uid`, returning the
+    /// players it was dueling
+    pub fn end(&mut self, uid: Uid) -> Vec<Uid> {
+        self.requests
+            .retain(|(from, to), _| *from != uid && *to != uid);
+        let opponents = self
+            .active
+            .iter()
+            .filter_map(|(a, b)| match (*a == uid, *b == uid) {
+                (true, _) => Some(*b),
+                (_, true) => Some(*a),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        self.active.retain(|(a, b)| *a != uid && *b != uid);
+        opponents
+    }
+
+    fn pair(a: Uid, b: Uid) -> (Uid, Uid) { if a.0 < b.0 { (a, b) } else { (b, a) } }
+}
+
+/// Multiplier for the knockback impulses an entity takes. Heavy bodies such
+/// as giants and bosses are pushed less far and small critters further, while
+/// stationary bodies and entities with a buff granting immunity don't move at
+/// all.
+pub fn knockback_multiplier(
+    body: Option<&Body>,
+    scale: Option<&Scale>,
+    buffs: Option<&Buffs>,
+) -> f32 {
+    if body.map_or(false, Body::is_stationary) || buffs.map_or(false, Buffs::is_knockback_immune) {
+        0.0
+    } else {
+        let mass = body.map_or(KNOCKBACK_REFERENCE_MASS, Body::mass)
+            * scale.map_or(1.0, |scale| scale.0.powi(3));
+        (KNOCKBACK_REFERENCE_MASS / mass.max(f32::EPSILON))
+            .sqrt()
+            .min(MAX_KNOCKBACK_MULTIPLIER)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn player(uid: u64) -> Combatant {
+        Combatant {
+            uid: Uid(uid),
+            is_player: true,
+            group: None,
+            pos: Vec3::zero(),
+        }
+    }
+
+    #[test]
+    fn duels_need_consent_and_ignore_pvp() {
+        let mut rules = CombatRules {
+            pvp: false,
+            ..Default::default()
+        };
+        assert!(!rules.can_harm(player(1), player(2)));
+        assert!(!rules.duels.request(Uid(1), Uid(2), 0.0));
+        assert!(!rules.can_harm(player(1), player(2)));
+        assert!(rules.duels.request(Uid(2), Uid(1), 1.0));
+        assert!(rules.can_harm(player(1), player(2)));
+        assert!(rules.can_harm(player(2), player(1)));
+        assert_eq!(rules.duels.end(Uid(2)), vec![Uid(1)]);
+        assert!(!rules.can_harm(player(1), player(2)));
+    }
+
+    #[test]
+    fn duel_requests_expire() {
+        let mut duels = Duels::default();
+        assert!(!duels.request(Uid(1), Uid(2), 0.0));
+        assert!(!duels.request(Uid(2), Uid(1), DUEL_REQUEST_TIMEOUT));
+        assert!(!duels.are_dueling(Uid(1), Uid(2)));
+        // The late answer stands as a challenge of its own
+        assert!(duels.request(Uid(1), Uid(2), DUEL_REQUEST_TIMEOUT + 1.0));
+        assert!(duels.are_dueling(Uid(1), Uid(2)));
+    }
+
+    #[test]
+    fn safe_zones_only_protect_from_players() {
+        let rules = CombatRules {
+            safe_zone_radius: 10.0,
+            safe_zones: vec![Vec2::zero()],
+            ..Default::default()
+        };
+        let npc = Combatant {
+            is_player: false,
+            ..player(3)
+        };
+        assert!(!rules.can_harm(player(1), player(2)));
+        assert!(rules.can_harm(npc, player(2)));
+    }
+
+    #[test]
+    fn knockback_scales_with_mass() {
+        use crate::comp::{bird_small, dragon, humanoid, turret};
+        let humanoid = Body::Humanoid(humanoid::Body::random());
+        let dragon = Body::Dragon(dragon::Body::random());
+        let bird = Body::BirdSmall(bird_small::Body::random());
+        let turret = Body::Turret(turret::Body::random());
+        assert!((knockback_multiplier(Some(&humanoid), None, None) - 1.0).abs() < 0.001);
+        assert!(knockback_multiplier(Some(&dragon), None, None) < 0.1);
+        assert!(knockback_multiplier(Some(&humanoid), Some(&Scale(2.0)), None) < 0.5);
+        assert_eq!(
+            knockback_multiplier(Some(&bird), None, None),
+            MAX_KNOCKBACK_MULTIPLIER
+        );
+        assert_eq!(knockback_multiplier(Some(&turret), None, None), 0.0);
+    }
+}
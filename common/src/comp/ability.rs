@@ -1,7 +1,8 @@
 use crate::{
     comp::{
-        item::{armor::Protection, Item, ItemKind},
-        Body, CharacterState, EnergySource, Gravity, LightEmitter, Projectile, StateUpdate,
+        item::{armor::Protection, Item, ItemKind, Tool, ToolCategory},
+        Body, CharacterState, DamageKind, EnergySource, Gravity, LightEmitter, Projectile,
+        StateUpdate,
     },
     states::{
         utils::{AbilityKey, StageSection},
@@ -13,7 +14,7 @@ use arraygen::Arraygen;
 use serde::{Deserialize, Serialize};
 use specs::{Component, FlaggedStorage};
 use specs_idvs::IdvStorage;
-use std::time::Duration;
+use std::{collections::HashMap, time::Duration};
 use vek::Vec3;
 
 #[derive(Copy, Clone, Hash, Eq, PartialEq, Debug, Serialize, Deserialize)]
@@ -31,6 +32,9 @@ pub enum CharacterAbilityType {
     Shockwave,
     BasicBeam,
     RepeaterRanged,
+    Charge(StageSection),
+    CastSpell(StageSection),
+    DualStrike(StageSection),
 }
 
 impl From<&CharacterState> for CharacterAbilityType {
@@ -49,6 +53,9 @@ impl From<&CharacterState> for CharacterAbilityType {
             CharacterState::Shockwave(_) => Self::ChargedRanged,
             CharacterState::BasicBeam(_) => Self::BasicBeam,
             CharacterState::RepeaterRanged(_) => Self::RepeaterRanged,
+            CharacterState::Charge(data) => Self::Charge(data.stage_section),
+            CharacterState::CastSpell(data) => Self::CastSpell(data.stage_section),
+            CharacterState::DualStrike(data) => Self::DualStrike(data.stage_section),
             _ => Self::BasicMelee,
         }
     }
@@ -190,6 +197,7 @@ pub enum CharacterAbility {
         shockwave_speed: f32,
         shockwave_duration: Duration,
         requires_ground: bool,
+        friendly_fire: bool,
         move_efficiency: f32,
     },
     BasicBeam {
@@ -206,12 +214,44 @@ pub enum CharacterAbility {
         energy_cost: u32,
         energy_drain: u32,
     },
+    Charge {
+        energy_cost: u32,
+        energy_drain: u32,
+        buildup_duration: Duration,
+        charge_duration: Duration,
+        recover_duration: Duration,
+        stagger_duration: Duration,
+        forward_speed: f32,
+        damage: u32,
+        knockback: f32,
+        radius: f32,
+    },
+    CastSpell {
+        energy_cost: u32,
+        cast_duration: Duration,
+        recover_duration: Duration,
+        interrupt_threshold: u32,
+        move_efficiency: f32,
+        payload: cast_spell::SpellPayload,
+    },
+    DualStrike {
+        energy_cost: u32,
+        main_strike: dual_strike::Strike,
+        off_strike: dual_strike::Strike,
+        speed_increase: f32,
+        max_speed_increase: f32,
+        is_interruptible: bool,
+    },
 }
 
 impl CharacterAbility {
     /// Attempts to fulfill requirements, mutating `update` (taking energy) if
     /// applicable.
     pub fn requirements_paid(&self, data: &JoinData, update: &mut StateUpdate) -> bool {
+        // Exhausted entities have to catch their breath first
+        if data.buffs.map_or(false, |buffs| buffs.is_exhausted()) {
+            return false;
+        }
         match self {
             CharacterAbility::Roll => {
                 data.physics.on_ground
@@ -258,6 +298,14 @@ impl CharacterAbility {
                 .energy
                 .try_change_by(-(*energy_cost as i32), EnergySource::Ability)
                 .is_ok(),
+            CharacterAbility::Charge { energy_cost, .. } => update
+                .energy
+                .try_change_by(-(*energy_cost as i32), EnergySource::Ability)
+                .is_ok(),
+            // Spells are paid for once the cast completes
+            CharacterAbility::CastSpell { energy_cost, .. } => {
+                update.energy.current() >= *energy_cost
+            },
             _ => true,
         }
     }
@@ -293,6 +341,61 @@ impl From<Item> for ItemConfig {
     }
 }
 
+impl ItemConfig {
+    /// Replaces the default abilities of the tool with the ones chosen for its
+    /// kind of weapon
+    pub fn apply_ability_selection(&mut self, selection: &AbilitySelection) {
+        if let ItemKind::Tool(tool) = self.item.kind() {
+            let category = ToolCategory::from(&tool.kind);
+            let abilities = [&mut self.ability1, &mut self.ability2, &mut self.ability3];
+            for ((slot, ability), mut options) in abilities
+                .iter_mut()
+                .enumerate()
+                .zip(tool.get_ability_options())
+            {
+                let index = selection.get(category, slot);
+                // Fall back to the default if the option no longer exists
+                let index = if index < options.len() { index } else { 0 };
+                **ability = Some(options.swap_remove(index));
+            }
+        }
+    }
+}
+
+/// Number of ability slots of a weapon that can be chosen
+const SELECTABLE_SLOTS: usize = 3;
+
+/// The abilities a character has chosen for each kind of weapon, stored as the
+/// index of the chosen option for each ability slot
+#[derive(Clone, PartialEq, Default, Debug, Serialize, Deserialize)]
+pub struct AbilitySelection {
+    selected: HashMap<ToolCategory, [usize; SELECTABLE_SLOTS]>,
+}
+
+impl AbilitySelection {
+    /// Index of the option chosen for an ability slot of a kind of weapon
+    pub fn get(&self, tool: ToolCategory, slot: usize) -> usize {
+        self.selected
+            .get(&tool)
+            .and_then(|slots| slots.get(slot))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Chooses an option for an ability slot, returning whether the slot exists
+    pub fn select(&mut self, tool: ToolCategory, slot: usize, index: usize) -> bool {
+        if slot >= SELECTABLE_SLOTS {
+            return false;
+        }
+        self.selected.entry(tool).or_insert([0; SELECTABLE_SLOTS])[slot] = index;
+        true
+    }
+}
+
+impl Component for AbilitySelection {
+    type Storage = FlaggedStorage<Self, IdvStorage<Self>>;
+}
+
 #[derive(Arraygen, Clone, PartialEq, Default, Debug, Serialize, Deserialize)]
 #[gen_array(pub fn get_armor: &Option<Item>)]
 pub struct Loadout {
@@ -327,19 +430,69 @@ pub struct Loadout {
 }
 
 impl Loadout {
-    pub fn get_damage_reduction(&self) -> f32 {
-        let protection = self
-            .get_armor()
+    /// The tools held in the main and off hand, if a weapon made for dual
+    /// wielding is wielded in each
+    pub fn dual_wielded_tools(&self) -> Option<(&Tool, &Tool)> {
+        fn tool(item: &Option<ItemConfig>) -> Option<&Tool> {
+            match item.as_ref().map(|i| i.item.kind()) {
+                Some(ItemKind::Tool(tool)) if tool.can_dual_wield() => Some(tool),
+                _ => None,
+            }
+        }
+        tool(&self.active_item).zip(tool(&self.second_item))
+    }
+
+    /// The alternating attack used in place of the main hand's primary
+    /// ability while dual wielding
+    pub fn dual_strike(&self) -> Option<CharacterAbility> {
+        self.dual_wielded_tools()
+            .map(|(main, off)| Tool::dual_strike(main, off))
+    }
+
+    /// Fraction of blocked damage reflected back by the wielded tools, taken
+    /// from whichever reflects the most
+    pub fn get_block_reflection(&self) -> f32 {
+        self.active_item
+            .iter()
+            .chain(self.second_item.iter())
+            .filter_map(|item| match item.item.kind() {
+                ItemKind::Tool(tool) => Some(tool.reflect_fraction()),
+                _ => None,
+            })
+            .fold(0.0, f32::max)
+    }
+
+    /// Applies the chosen abilities to the wielded tools
+    pub fn apply_ability_selection(&mut self, selection: &AbilitySelection) {
+        for item in self
+            .active_item
+            .iter_mut()
+            .chain(self.second_item.iter_mut())
+        {
+            item.apply_ability_selection(selection);
+        }
+    }
+
+    /// The protection given by each worn piece of armor
+    pub fn get_armor_protection(&self) -> Vec<(&Item, Protection)> {
+        self.get_armor()
             .iter()
             .flat_map(|armor| armor.as_ref())
             .filter_map(|item| {
                 if let ItemKind::Armor(armor) = &item.kind() {
-                    Some(armor.get_protection())
+                    Some((item, armor.get_protection()))
                 } else {
                     None
                 }
             })
-            .map(|protection| match protection {
+            .collect()
+    }
+
+    pub fn get_damage_reduction(&self) -> f32 {
+        let protection = self
+            .get_armor_protection()
+            .into_iter()
+            .map(|(_, protection)| match protection {
                 Protection::Normal(protection) => Some(protection),
                 Protection::Invincible => None,
             })
@@ -349,6 +502,46 @@ impl Loadout {
             None => 1.0,
         }
     }
+
+    /// Portion of damage of the given kind stopped by the resistances of all
+    /// worn armor, which can be negative if the armor is weak against it
+    pub fn get_damage_resistance(&self, kind: DamageKind) -> f32 {
+        self.get_armor()
+            .iter()
+            .flat_map(|armor| armor.as_ref())
+            .filter_map(|item| match item.kind() {
+                ItemKind::Armor(armor) => Some(armor.get_resistances().get(kind)),
+                _ => None,
+            })
+            .sum::<f32>()
+            .max(-1.0)
+            .min(0.9)
+    }
+
+    /// Fraction added to the speed of energy recovery by each worn piece of
+    /// armor that affects it
+    pub fn get_energy_regen(&self) -> Vec<(&Item, f32)> {
+        self.get_armor()
+            .iter()
+            .flat_map(|armor| armor.as_ref())
+            .filter_map(|item| match item.kind() {
+                ItemKind::Armor(armor) if armor.get_energy_regen() != 0.0 => {
+                    Some((item, armor.get_energy_regen()))
+                },
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Multiplier on the speed of energy recovery from all worn armor
+    pub fn get_energy_regen_modifier(&self) -> f32 {
+        (1.0 + self
+            .get_energy_regen()
+            .into_iter()
+            .map(|(_, regen)| regen)
+            .sum::<f32>())
+        .max(0.0)
+    }
 }
 
 impl From<(&CharacterAbility, AbilityKey)> for CharacterState {
@@ -527,6 +720,7 @@ impl From<(&CharacterAbility, AbilityKey)> for CharacterState {
                 spins_remaining: *num_spins - 1,
                 stage_section: StageSection::Buildup,
                 exhausted: false,
+                fall_speed: 0.0,
             }),
             CharacterAbility::ChargedMelee {
                 energy_cost,
@@ -633,6 +827,7 @@ impl From<(&CharacterAbility, AbilityKey)> for CharacterState {
                 shockwave_speed,
                 shockwave_duration,
                 requires_ground,
+                friendly_fire,
                 move_efficiency,
             } => CharacterState::Shockwave(shockwave::Data {
                 static_data: shockwave::StaticData {
@@ -646,6 +841,7 @@ impl From<(&CharacterAbility, AbilityKey)> for CharacterState {
                     shockwave_speed: *shockwave_speed,
                     shockwave_duration: *shockwave_duration,
                     requires_ground: *requires_ground,
+                    friendly_fire: *friendly_fire,
                     move_efficiency: *move_efficiency,
                 },
                 timer: Duration::default(),
@@ -685,6 +881,70 @@ impl From<(&CharacterAbility, AbilityKey)> for CharacterState {
                 particle_ori: None::<Vec3<f32>>,
                 offset: 0.0,
             }),
+            CharacterAbility::Charge {
+                energy_cost: _,
+                energy_drain,
+                buildup_duration,
+                charge_duration,
+                recover_duration,
+                stagger_duration,
+                forward_speed,
+                damage,
+                knockback,
+                radius,
+            } => CharacterState::Charge(charge::Data::new(charge::StaticData {
+                buildup_duration: *buildup_duration,
+                charge_duration: *charge_duration,
+                recover_duration: *recover_duration,
+                stagger_duration: *stagger_duration,
+                forward_speed: *forward_speed,
+                damage: *damage,
+                knockback: *knockback,
+                radius: *radius,
+                energy_drain: *energy_drain,
+            })),
+            CharacterAbility::CastSpell {
+                energy_cost,
+                cast_duration,
+                recover_duration,
+                interrupt_threshold,
+                move_efficiency,
+                payload,
+            } => CharacterState::CastSpell(cast_spell::Data {
+                static_data: cast_spell::StaticData {
+                    cast_duration: *cast_duration,
+                    recover_duration: *recover_duration,
+                    energy_cost: *energy_cost,
+                    interrupt_threshold: *interrupt_threshold,
+                    move_efficiency: *move_efficiency,
+                    payload: payload.clone(),
+                },
+                timer: Duration::default(),
+                stage_section: StageSection::Cast,
+                damage_taken: 0,
+            }),
+            CharacterAbility::DualStrike {
+                energy_cost,
+                main_strike,
+                off_strike,
+                speed_increase,
+                max_speed_increase,
+                is_interruptible,
+            } => CharacterState::DualStrike(dual_strike::Data {
+                static_data: dual_strike::StaticData {
+                    main_strike: *main_strike,
+                    off_strike: *off_strike,
+                    energy_cost: *energy_cost,
+                    speed_increase: *speed_increase,
+                    max_speed_increase: *max_speed_increase,
+                    is_interruptible: *is_interruptible,
+                },
+                hand: dual_strike::Hand::Main,
+                strikes: 0,
+                timer: Duration::default(),
+                stage_section: StageSection::Buildup,
+                exhausted: false,
+            }),
         }
     }
 }
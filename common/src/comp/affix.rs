@@ -0,0 +1,133 @@
+use serde::{Deserialize, Serialize};
+use specs::Component;
+use specs_idvs::IdvStorage;
+use std::str::FromStr;
+
+/// Portion of the melee damage taken by an entity with thorns that is dealt
+/// back to the attacker
+pub const THORNS_REFLECT: f32 = 0.3;
+/// Portion of the melee damage dealt by a vampiric entity that heals it
+pub const VAMPIRIC_LEECH: f32 = 0.25;
+/// Movement acceleration multiplier of fast entities
+pub const FAST_ACCEL: f32 = 1.4;
+
+/// Modifiers given to the inhabitants of dungeons on harder difficulties
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Affix {
+    /// Moves faster
+    Fast,
+    /// Deals part of the melee damage it takes back to the attacker
+    Thorns,
+    /// Heals by part of the melee damage it deals
+    Vampiric,
+}
+
+impl Affix {
+    pub fn name(self) -> &'static str {
+        match self {
+            Affix::Fast => "extra fast",
+            Affix::Thorns => "thorns",
+            Affix::Vampiric => "vampiric",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Affixes(pub Vec<Affix>);
+
+impl Affixes {
+    pub fn has(&self, affix: Affix) -> bool { self.0.contains(&affix) }
+
+    /// Multiplier for the movement acceleration of the entity
+    pub fn accel_modifier(&self) -> f32 {
+        if self.has(Affix::Fast) {
+            FAST_ACCEL
+        } else {
+            1.0
+        }
+    }
+}
+
+impl Component for Affixes {
+    type Storage = IdvStorage<Self>;
+}
+
+/// Difficulty tier of a dungeon instance, chosen by the group leader before
+/// entering
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DungeonDifficulty {
+    Normal,
+    Hard,
+    Nightmare,
+}
+
+impl DungeonDifficulty {
+    pub const ALL: [DungeonDifficulty; 3] = [
+        DungeonDifficulty::Normal,
+        DungeonDifficulty::Hard,
+        DungeonDifficulty::Nightmare,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            DungeonDifficulty::Normal => "normal",
+            DungeonDifficulty::Hard => "hard",
+            DungeonDifficulty::Nightmare => "nightmare",
+        }
+    }
+
+    /// Affixes given to every inhabitant of the dungeon
+    pub fn affixes(self) -> &'static [Affix] {
+        match self {
+            DungeonDifficulty::Normal => &[],
+            DungeonDifficulty::Hard => &[Affix::Fast],
+            DungeonDifficulty::Nightmare => &[Affix::Fast, Affix::Thorns, Affix::Vampiric],
+        }
+    }
+
+    /// How many times the loot of each inhabitant is rolled, keeping the best
+    /// item
+    pub fn loot_rolls(self) -> u32 {
+        match self {
+            DungeonDifficulty::Normal => 1,
+            DungeonDifficulty::Hard => 2,
+            DungeonDifficulty::Nightmare => 3,
+        }
+    }
+
+    /// Human readable summary of the modifiers, e.g. for showing to a group
+    /// before it enters
+    pub fn describe(self) -> String {
+        let affixes = if self.affixes().is_empty() {
+            "none".to_string()
+        } else {
+            self.affixes()
+                .iter()
+                .map(|affix| affix.name())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        format!(
+            "{} (monster affixes: {}, loot rolls: {})",
+            self.name(),
+            affixes,
+            self.loot_rolls()
+        )
+    }
+}
+
+impl Default for DungeonDifficulty {
+    fn default() -> Self { DungeonDifficulty::Normal }
+}
+
+impl FromStr for DungeonDifficulty {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        DungeonDifficulty::ALL
+            .iter()
+            .copied()
+            .find(|difficulty| difficulty.name() == s)
+            .ok_or(())
+    }
+}
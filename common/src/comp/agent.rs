@@ -1,11 +1,24 @@
 use crate::{
-    comp::{humanoid, quadruped_low, quadruped_medium, quadruped_small, Body},
+    comp::{humanoid, quadruped_low, quadruped_small, Body, BuffEffect, Buffs, ControlAction},
     path::Chaser,
     sync::Uid,
 };
 use specs::{Component, Entity as EcsEntity};
 use specs_idvs::IdvStorage;
+use std::{cmp::Ordering, collections::HashMap};
 use vek::*;
+
+/// Fraction of its threat an entity loses each second
+pub const THREAT_DECAY: f32 = 0.1;
+/// Threat below which an entity is forgotten
+const MIN_THREAT: f32 = 1.0;
+/// Fraction of the healing done to an entity that counts as threat against the
+/// healer, for NPCs the healed entity is fighting
+pub const HEAL_THREAT_FACTOR: f32 = 0.5;
+/// How much more threat than the current target an entity needs for an NPC to
+/// switch targets
+pub const THREAT_SWITCH_RATIO: f32 = 1.1;
+
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum Alignment {
     /// Wild animals and gentle giants
@@ -99,15 +112,7 @@ impl<'a> From<&'a Body> for Psyche {
                     quadruped_small::Species::Frog => 0.6,
                     _ => 1.0,
                 },
-                Body::QuadrupedMedium(quadruped_medium) => match quadruped_medium.species {
-                    quadruped_medium::Species::Tuskram => 0.8,
-                    quadruped_medium::Species::Frostfang => 0.9,
-                    quadruped_medium::Species::Mouflon => 0.8,
-                    quadruped_medium::Species::Catoblepas => 0.8,
-                    quadruped_medium::Species::Deer => 0.6,
-                    quadruped_medium::Species::Hirdrasil => 0.7,
-                    _ => 1.0,
-                },
+                Body::QuadrupedMedium(quadruped_medium) => quadruped_medium.stats().aggro,
                 Body::QuadrupedLow(quadruped_low) => match quadruped_low.species {
                     quadruped_low::Species::Crocodile => 1.0,
                     quadruped_low::Species::Alligator => 1.0,
@@ -126,6 +131,9 @@ impl<'a> From<&'a Body> for Psyche {
                 Body::BipedLarge(_) => 1.0,
                 Body::Object(_) => 1.0,
                 Body::Golem(_) => 1.0,
+                Body::Colossus(_) => 1.0,
+                Body::Arthropod(_) => 0.9,
+                Body::Turret(_) => 1.0,
                 Body::Theropod(_) => 1.0,
                 Body::Dragon(_) => 1.0,
             },
@@ -141,6 +149,12 @@ pub struct Agent {
     // TODO move speech patterns into a Behavior component
     pub can_speak: bool,
     pub psyche: Psyche,
+    /// Direction stationary agents are mounted facing, which they can only
+    /// turn so far away from
+    pub facing: Option<Vec2<f32>>,
+    /// Whether the agent walks rounds with its lantern lit at night instead of
+    /// idling, like town guards do
+    pub night_patrol: bool,
 }
 
 impl Agent {
@@ -149,6 +163,16 @@ impl Agent {
         self
     }
 
+    pub fn with_night_patrol(mut self) -> Self {
+        self.night_patrol = true;
+        self
+    }
+
+    pub fn with_facing(mut self, facing: Vec2<f32>) -> Self {
+        self.facing = Some(facing);
+        self
+    }
+
     pub fn new(origin: Vec3<f32>, can_speak: bool, body: &Body) -> Self {
         let patrol_origin = Some(origin);
         Agent {
@@ -164,6 +188,61 @@ impl Component for Agent {
     type Storage = IdvStorage<Self>;
 }
 
+/// Threat other entities built up against an NPC by damaging it (or healing
+/// those it fights), used by its agent to pick who to attack
+#[derive(Clone, Debug, Default)]
+pub struct Threat {
+    table: HashMap<Uid, f32>,
+}
+
+impl Threat {
+    pub fn add(&mut self, by: Uid, amount: f32) { *self.table.entry(by).or_default() += amount; }
+
+    pub fn get(&self, uid: Uid) -> f32 { self.table.get(&uid).copied().unwrap_or(0.0) }
+
+    pub fn contains(&self, uid: Uid) -> bool { self.table.contains_key(&uid) }
+
+    /// The entity with the most threat, along with its threat
+    pub fn highest(&self) -> Option<(Uid, f32)> {
+        self.table
+            .iter()
+            .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(Ordering::Equal))
+            .map(|(uid, threat)| (*uid, *threat))
+    }
+
+    /// Forgets the entities for which `keep` returns false
+    pub fn retain(&mut self, mut keep: impl FnMut(Uid) -> bool) {
+        self.table.retain(|uid, _| keep(*uid));
+    }
+
+    /// Lets threat fade over time, forgetting entities once they pose
+    /// little enough of a threat
+    pub fn decay(&mut self, dt: f32) {
+        let factor = (1.0 - THREAT_DECAY * dt).max(0.0);
+        self.table.retain(|_, threat| {
+            *threat *= factor;
+            *threat >= MIN_THREAT
+        });
+    }
+}
+
+impl Component for Threat {
+    type Storage = IdvStorage<Self>;
+}
+
+/// Multiplier for the threat an entity generates, from its taunting buffs
+pub fn threat_multiplier(buffs: Option<&Buffs>) -> f32 {
+    buffs.map_or(1.0, |buffs| {
+        buffs
+            .iter_active()
+            .flat_map(|buff| buff.effects.iter())
+            .fold(1.0, |multiplier, effect| match effect {
+                BuffEffect::ThreatModifier(value) => multiplier * value,
+                _ => multiplier,
+            })
+    })
+}
+
 #[derive(Clone, Debug)]
 pub enum Activity {
     Idle(Vec2<f32>),
@@ -178,12 +257,23 @@ pub enum Activity {
         been_close: bool,
         powerup: f32,
     },
+    /// Acting out a scripted sequence, which takes the place of the agent's own
+    /// behaviour until the sequence ends
+    Scripted {
+        /// Where the sequence sent the agent walking to
+        dest: Option<Vec3<f32>>,
+        chaser: Chaser,
+        /// Actions the sequence wants the agent to perform
+        actions: Vec<ControlAction>,
+    },
 }
 
 impl Activity {
     pub fn is_follow(&self) -> bool { matches!(self, Activity::Follow { .. }) }
 
     pub fn is_attack(&self) -> bool { matches!(self, Activity::Attack { .. }) }
+
+    pub fn is_scripted(&self) -> bool { matches!(self, Activity::Scripted { .. }) }
 }
 
 impl Default for Activity {
@@ -1,6 +1,8 @@
+pub mod arthropod;
 pub mod biped_large;
 pub mod bird_medium;
 pub mod bird_small;
+pub mod colossus;
 pub mod dragon;
 pub mod fish_medium;
 pub mod fish_small;
@@ -11,9 +13,11 @@ pub mod quadruped_low;
 pub mod quadruped_medium;
 pub mod quadruped_small;
 pub mod theropod;
+pub mod turret;
 
 use crate::{
     assets::{self, Asset},
+    comp::DamageKind,
     make_case_elim,
     npc::NpcKind,
 };
@@ -41,6 +45,9 @@ make_case_elim!(
         Golem(body: golem::Body) = 10,
         Theropod(body: theropod::Body) = 11,
         QuadrupedLow(body: quadruped_low::Body) = 12,
+        Colossus(body: colossus::Body) = 13,
+        Arthropod(body: arthropod::Body) = 14,
+        Turret(body: turret::Body) = 15,
     }
 );
 
@@ -74,6 +81,9 @@ pub struct AllBodies<BodyMeta, SpeciesMeta> {
     pub golem: BodyData<BodyMeta, golem::AllSpecies<SpeciesMeta>>,
     pub theropod: BodyData<BodyMeta, theropod::AllSpecies<SpeciesMeta>>,
     pub quadruped_low: BodyData<BodyMeta, quadruped_low::AllSpecies<SpeciesMeta>>,
+    pub colossus: BodyData<BodyMeta, colossus::AllSpecies<SpeciesMeta>>,
+    pub arthropod: BodyData<BodyMeta, arthropod::AllSpecies<SpeciesMeta>>,
+    pub turret: BodyData<BodyMeta, turret::AllSpecies<SpeciesMeta>>,
 }
 
 /// Can only retrieve body metadata by direct index.
@@ -92,6 +102,9 @@ impl<BodyMeta, SpeciesMeta> core::ops::Index<NpcKind> for AllBodies<BodyMeta, Sp
             NpcKind::Archaeos => &self.theropod.body,
             NpcKind::Reddragon => &self.dragon.body,
             NpcKind::Crocodile => &self.quadruped_low.body,
+            NpcKind::Colossus => &self.colossus.body,
+            NpcKind::Spider => &self.arthropod.body,
+            NpcKind::Turret => &self.turret.body,
         }
     }
 }
@@ -116,6 +129,9 @@ impl<'a, BodyMeta, SpeciesMeta> core::ops::Index<&'a Body> for AllBodies<BodyMet
             Body::Golem(_) => &self.golem.body,
             Body::Theropod(_) => &self.theropod.body,
             Body::QuadrupedLow(_) => &self.quadruped_low.body,
+            Body::Colossus(_) => &self.colossus.body,
+            Body::Arthropod(_) => &self.arthropod.body,
+            Body::Turret(_) => &self.turret.body,
         }
     }
 }
@@ -135,6 +151,109 @@ impl<
 impl Body {
     pub fn is_humanoid(&self) -> bool { matches!(self, Body::Humanoid(_)) }
 
+    /// Whether the body can cling to walls and climb them
+    pub fn can_climb(&self) -> bool { matches!(self, Body::Humanoid(_) | Body::Arthropod(_)) }
+
+    /// Whether the body is fixed in place, only able to turn around
+    pub fn is_stationary(&self) -> bool { matches!(self, Body::Turret(_)) }
+
+    /// Whether two animals can breed, which requires them to be of the same
+    /// species and of different sexes
+    pub fn can_breed_with(&self, other: &Body) -> bool {
+        match (self, other) {
+            (Body::QuadrupedSmall(a), Body::QuadrupedSmall(b)) => {
+                a.species == b.species && a.body_type != b.body_type
+            },
+            (Body::QuadrupedMedium(a), Body::QuadrupedMedium(b)) => {
+                a.species == b.species && a.body_type != b.body_type
+            },
+            (Body::QuadrupedLow(a), Body::QuadrupedLow(b)) => {
+                a.species == b.species && a.body_type != b.body_type
+            },
+            (Body::Arthropod(a), Body::Arthropod(b)) => {
+                a.species == b.species && a.body_type != b.body_type
+            },
+            (Body::BirdMedium(a), Body::BirdMedium(b)) => {
+                a.species == b.species && a.body_type != b.body_type
+            },
+            _ => false,
+        }
+    }
+
+    /// Portion of damage of the given kind that the creature shrugs off, which
+    /// is negative for its weaknesses
+    pub fn damage_resistance(&self, kind: DamageKind) -> f32 {
+        use DamageKind::*;
+        match (self, kind) {
+            (Body::Golem(golem), _) => match (golem.species, kind) {
+                (golem::Species::StoneGolem, Slashing | Piercing) => 0.5,
+                (golem::Species::StoneGolem, Crushing) => -0.5,
+                (golem::Species::StoneGolem, Poison) => 1.0,
+                (golem::Species::Treant, Piercing) => 0.3,
+                (golem::Species::Treant, Fire) => -0.5,
+                _ => 0.0,
+            },
+            (Body::Colossus(_), Slashing | Piercing) => 0.6,
+            (Body::Colossus(_), Crushing) => -0.3,
+            (Body::Colossus(_), Poison) => 1.0,
+            (Body::Arthropod(arthropod), _) => match (arthropod.species, kind) {
+                (arthropod::Species::Beetle, Slashing | Piercing) => 0.4,
+                (arthropod::Species::Scorpion, Slashing) => 0.2,
+                (_, Poison) => 0.75,
+                (_, Fire) => -0.3,
+                _ => 0.0,
+            },
+            (Body::Turret(turret), _) => match (turret.species, kind) {
+                (turret::Species::Ballista, Fire) => -0.5,
+                (turret::Species::Ballista, Piercing) => 0.3,
+                (turret::Species::Statue, Slashing | Piercing) => 0.5,
+                (turret::Species::Statue, Crushing) => -0.5,
+                (_, Poison) => 1.0,
+                _ => 0.0,
+            },
+            (Body::Dragon(_), Fire) => 0.8,
+            (Body::Dragon(_), Frost) => -0.3,
+            (Body::Humanoid(humanoid), Poison) if humanoid.species == humanoid::Species::Undead => {
+                1.0
+            },
+            (Body::Humanoid(humanoid), Fire) if humanoid.species == humanoid::Species::Undead => {
+                -0.25
+            },
+            (Body::Object(_), Poison) => 1.0,
+            _ => 0.0,
+        }
+    }
+
+    /// Rough weight of the body in kilograms, which decides how far it is
+    /// thrown by knockback
+    pub fn mass(&self) -> f32 {
+        match self {
+            Body::Humanoid(_) => 80.0,
+            Body::QuadrupedSmall(_) => 20.0,
+            Body::QuadrupedMedium(_) => 250.0,
+            Body::QuadrupedLow(body) => match body.species {
+                quadruped_low::Species::Rocksnapper => 800.0,
+                quadruped_low::Species::Maneater => 600.0,
+                _ => 150.0,
+            },
+            Body::Theropod(_) => 300.0,
+            Body::BirdMedium(body) => match body.species {
+                bird_medium::Species::Cockatrice => 80.0,
+                _ => 5.0,
+            },
+            Body::FishMedium(_) => 10.0,
+            Body::Dragon(_) => 20000.0,
+            Body::BirdSmall(_) => 1.0,
+            Body::FishSmall(_) => 1.0,
+            Body::BipedLarge(_) => 600.0,
+            Body::Golem(_) => 2000.0,
+            Body::Colossus(_) => 10000.0,
+            Body::Arthropod(_) => 100.0,
+            Body::Turret(_) => 1000.0,
+            Body::Object(_) => 50.0,
+        }
+    }
+
     // Note: this might need to be refined to something more complex for realistic
     // behavior with less cylindrical bodies (e.g. wolfs)
     pub fn radius(&self) -> f32 {
@@ -142,14 +261,7 @@ impl Body {
         match self {
             Body::Humanoid(body) => 0.35 * body.scale(),
             Body::QuadrupedSmall(_) => 0.4,
-            Body::QuadrupedMedium(body) => match body.species {
-                quadruped_medium::Species::Grolgar => 1.9,
-                quadruped_medium::Species::Tarasque => 2.2,
-                quadruped_medium::Species::Lion => 1.9,
-                quadruped_medium::Species::Saber => 1.8,
-                quadruped_medium::Species::Catoblepas => 1.7,
-                _ => 1.5,
-            },
+            Body::QuadrupedMedium(body) => body.stats().radius,
             Body::QuadrupedLow(body) => match body.species {
                 quadruped_low::Species::Asp => 1.8,
                 quadruped_low::Species::Monitor => 1.75,
@@ -166,6 +278,14 @@ impl Body {
             Body::FishSmall(_) => 0.3,
             Body::BipedLarge(_) => 0.75,
             Body::Golem(_) => 0.4,
+            Body::Colossus(_) => 1.2,
+            Body::Arthropod(body) => match body.species {
+                arthropod::Species::Spider => 1.0,
+                arthropod::Species::Scorpion => 0.9,
+                arthropod::Species::Beetle => 0.8,
+            },
+            Body::Turret(_) => 0.9,
+            Body::Object(object::Body::Cart) => 1.0,
             Body::Object(_) => 0.4,
         }
     }
@@ -184,13 +304,7 @@ impl Body {
                 quadruped_small::Species::Truffler => 2.0,
                 _ => 1.0,
             },
-            Body::QuadrupedMedium(body) => match body.species {
-                quadruped_medium::Species::Tarasque => 2.5,
-                quadruped_medium::Species::Lion => 1.8,
-                quadruped_medium::Species::Saber => 1.8,
-                quadruped_medium::Species::Catoblepas => 2.8,
-                _ => 1.6,
-            },
+            Body::QuadrupedMedium(body) => body.stats().height,
             Body::QuadrupedLow(body) => match body.species {
                 quadruped_low::Species::Monitor => 1.5,
                 quadruped_low::Species::Tortoise => 2.0,
@@ -209,10 +323,41 @@ impl Body {
             Body::FishSmall(_) => 0.9,
             Body::BipedLarge(_) => 4.6,
             Body::Golem(_) => 5.8,
+            Body::Colossus(_) => 9.5,
+            Body::Arthropod(body) => match body.species {
+                arthropod::Species::Spider => 1.0,
+                arthropod::Species::Scorpion => 1.2,
+                arthropod::Species::Beetle => 1.1,
+            },
+            Body::Turret(body) => match body.species {
+                turret::Species::Ballista => 1.6,
+                turret::Species::MagicTurret => 2.0,
+                turret::Species::Statue => 3.0,
+            },
+            Body::Object(object::Body::Cart) => 1.2,
             Body::Object(_) => 1.0,
         }
     }
 
+    /// Centre of the head relative to the position of the body when facing
+    /// along +y, along with the radius of the head, for bodies that can be
+    /// hit in the head. The head is sized from the radius and height of the
+    /// body's collider and sits at its top, at the front for quadrupeds.
+    pub fn head_sphere(&self) -> Option<(Vec3<f32>, f32)> {
+        let height = self.height();
+        let radius = self.radius();
+        let (head_radius, forward) = match self {
+            // Humanoids are hardly wider than their heads
+            Body::Humanoid(_) => (radius * 0.85, 0.0),
+            Body::QuadrupedSmall(_) | Body::QuadrupedMedium(_) | Body::QuadrupedLow(_) => {
+                let head_radius = radius.min(height) * 0.4;
+                (head_radius, radius - head_radius)
+            },
+            _ => return None,
+        };
+        Some((Vec3::new(0.0, forward, height - head_radius), head_radius))
+    }
+
     pub fn base_energy(&self) -> u32 {
         match self {
             Body::BipedLarge(biped_large) => match biped_large.species {
@@ -236,23 +381,7 @@ impl Body {
                 quadruped_small::Species::Truffler => 360,
                 _ => 200,
             },
-            Body::QuadrupedMedium(quadruped_medium) => match quadruped_medium.species {
-                quadruped_medium::Species::Grolgar => 600,
-                quadruped_medium::Species::Saber => 400,
-                quadruped_medium::Species::Tiger => 400,
-                quadruped_medium::Species::Tuskram => 600,
-                quadruped_medium::Species::Lion => 800,
-                quadruped_medium::Species::Tarasque => 1200,
-                quadruped_medium::Species::Wolf => 400,
-                quadruped_medium::Species::Frostfang => 400,
-                quadruped_medium::Species::Mouflon => 500,
-                quadruped_medium::Species::Catoblepas => 1000,
-                quadruped_medium::Species::Bonerattler => 400,
-                quadruped_medium::Species::Deer => 300,
-                quadruped_medium::Species::Hirdrasil => 500,
-                quadruped_medium::Species::Roshwalr => 600,
-                _ => 400,
-            },
+            Body::QuadrupedMedium(quadruped_medium) => quadruped_medium.stats().base_health,
             Body::BirdMedium(bird_medium) => match bird_medium.species {
                 bird_medium::Species::Chicken => 50,
                 bird_medium::Species::Duck => 50,
@@ -277,6 +406,17 @@ impl Body {
             },
             Body::Object(_) => 10000,
             Body::Golem(_) => 2560,
+            Body::Colossus(_) => 12000,
+            Body::Arthropod(body) => match body.species {
+                arthropod::Species::Spider => 300,
+                arthropod::Species::Scorpion => 450,
+                arthropod::Species::Beetle => 550,
+            },
+            Body::Turret(body) => match body.species {
+                turret::Species::Ballista => 600,
+                turret::Species::MagicTurret => 400,
+                turret::Species::Statue => 1200,
+            },
             Body::Theropod(_) => 50,
             Body::QuadrupedLow(quadruped_low) => match quadruped_low.species {
                 quadruped_low::Species::Crocodile => 600,
@@ -306,22 +446,8 @@ impl Body {
                 quadruped_small::Species::Truffler => 20,
                 _ => 10,
             },
-            Body::QuadrupedMedium(quadruped_medium) => match quadruped_medium.species {
-                quadruped_medium::Species::Grolgar => 30,
-                quadruped_medium::Species::Saber => 20,
-                quadruped_medium::Species::Tiger => 20,
-                quadruped_medium::Species::Tuskram => 30,
-                quadruped_medium::Species::Lion => 40,
-                quadruped_medium::Species::Tarasque => 60,
-                quadruped_medium::Species::Wolf => 20,
-                quadruped_medium::Species::Frostfang => 40,
-                quadruped_medium::Species::Mouflon => 30,
-                quadruped_medium::Species::Catoblepas => 50,
-                quadruped_medium::Species::Bonerattler => 30,
-                quadruped_medium::Species::Deer => 30,
-                quadruped_medium::Species::Hirdrasil => 30,
-                quadruped_medium::Species::Roshwalr => 40,
-                _ => 20,
+            Body::QuadrupedMedium(quadruped_medium) => {
+                quadruped_medium.stats().base_health_increase
             },
             Body::BirdMedium(bird_medium) => match bird_medium.species {
                 bird_medium::Species::Chicken => 10,
@@ -347,6 +473,9 @@ impl Body {
             },
             Body::Object(_) => 10,
             Body::Golem(_) => 260,
+            Body::Colossus(_) => 800,
+            Body::Arthropod(_) => 20,
+            Body::Turret(_) => 40,
             Body::Theropod(_) => 20,
             Body::QuadrupedLow(quadruped_low) => match quadruped_low.species {
                 quadruped_low::Species::Crocodile => 20,
@@ -376,23 +505,7 @@ impl Body {
                 quadruped_small::Species::Truffler => 6,
                 _ => 4,
             },
-            Body::QuadrupedMedium(quadruped_medium) => match quadruped_medium.species {
-                quadruped_medium::Species::Grolgar => 10,
-                quadruped_medium::Species::Saber => 8,
-                quadruped_medium::Species::Tiger => 8,
-                quadruped_medium::Species::Tuskram => 9,
-                quadruped_medium::Species::Lion => 10,
-                quadruped_medium::Species::Tarasque => 16,
-                quadruped_medium::Species::Wolf => 8,
-                quadruped_medium::Species::Frostfang => 9,
-                quadruped_medium::Species::Mouflon => 7,
-                quadruped_medium::Species::Catoblepas => 10,
-                quadruped_medium::Species::Bonerattler => 10,
-                quadruped_medium::Species::Deer => 7,
-                quadruped_medium::Species::Hirdrasil => 10,
-                quadruped_medium::Species::Roshwalr => 10,
-                _ => 6,
-            },
+            Body::QuadrupedMedium(quadruped_medium) => quadruped_medium.stats().base_exp,
             Body::BirdMedium(bird_medium) => match bird_medium.species {
                 bird_medium::Species::Chicken => 2,
                 bird_medium::Species::Duck => 2,
@@ -415,6 +528,9 @@ impl Body {
             },
             Body::Object(_) => 1,
             Body::Golem(_) => 256,
+            Body::Colossus(_) => 800,
+            Body::Arthropod(_) => 8,
+            Body::Turret(_) => 10,
             Body::Theropod(_) => 2,
             Body::QuadrupedLow(quadruped_low) => match quadruped_low.species {
                 quadruped_low::Species::Crocodile => 10,
@@ -445,6 +561,9 @@ impl Body {
             Body::BipedLarge(_) => 2,
             Body::Object(_) => 0,
             Body::Golem(_) => 12,
+            Body::Colossus(_) => 40,
+            Body::Arthropod(_) => 1,
+            Body::Turret(_) => 1,
             Body::Theropod(_) => 1,
             Body::QuadrupedLow(_) => 1,
         }
@@ -460,18 +579,7 @@ impl Body {
                 quadruped_small::Species::Porcupine => 30,
                 _ => 20,
             },
-            Body::QuadrupedMedium(quadruped_medium) => match quadruped_medium.species {
-                quadruped_medium::Species::Grolgar => 50,
-                quadruped_medium::Species::Lion => 60,
-                quadruped_medium::Species::Tarasque => 70,
-                quadruped_medium::Species::Mouflon => 30,
-                quadruped_medium::Species::Catoblepas => 20,
-                quadruped_medium::Species::Bonerattler => 50,
-                quadruped_medium::Species::Deer => 30,
-                quadruped_medium::Species::Hirdrasil => 50,
-                quadruped_medium::Species::Roshwalr => 60,
-                _ => 40,
-            },
+            Body::QuadrupedMedium(quadruped_medium) => quadruped_medium.stats().base_dmg,
             Body::BirdMedium(bird_medium) => match bird_medium.species {
                 bird_medium::Species::Chicken => 10,
                 bird_medium::Species::Duck => 10,
@@ -496,6 +604,13 @@ impl Body {
             },
             Body::Object(_) => 0,
             Body::Golem(_) => 250,
+            Body::Colossus(_) => 400,
+            Body::Arthropod(body) => match body.species {
+                arthropod::Species::Spider => 40,
+                arthropod::Species::Scorpion => 50,
+                arthropod::Species::Beetle => 40,
+            },
+            Body::Turret(_) => 0,
             Body::Theropod(_) => 10,
             Body::QuadrupedLow(quadruped_low) => match quadruped_low.species {
                 quadruped_low::Species::Crocodile => 50,
@@ -525,6 +640,9 @@ impl Body {
             Body::BipedLarge(_) => 10.0,
             Body::Object(_) => 3.0,
             Body::Golem(_) => 7.5,
+            Body::Colossus(_) => 12.0,
+            Body::Arthropod(_) => 2.5,
+            Body::Turret(_) => 3.0,
             Body::Theropod(_) => 3.0,
             Body::QuadrupedLow(_) => 4.5,
         }
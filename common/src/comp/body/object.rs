@@ -68,6 +68,7 @@ make_case_elim!(
         FireworkYellow = 58,
         MultiArrow = 59,
         BoltNature = 60,
+        Cart = 61,
     }
 );
 
@@ -78,7 +79,7 @@ impl Body {
     }
 }
 
-pub const ALL_OBJECTS: [Body; 61] = [
+pub const ALL_OBJECTS: [Body; 62] = [
     Body::Arrow,
     Body::Bomb,
     Body::Scarecrow,
@@ -140,6 +141,7 @@ pub const ALL_OBJECTS: [Body; 61] = [
     Body::FireworkYellow,
     Body::MultiArrow,
     Body::BoltNature,
+    Body::Cart,
 ];
 
 impl From<Body> for super::Body {
@@ -210,6 +212,7 @@ impl Body {
             Body::FireworkYellow => "firework_yellow",
             Body::MultiArrow => "multi_arrow",
             Body::BoltNature => "bolt_nature",
+            Body::Cart => "cart",
         }
     }
 }
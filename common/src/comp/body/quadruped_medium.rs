@@ -1,6 +1,11 @@
-use crate::{make_case_elim, make_proj_elim};
+use crate::{
+    assets::{Asset, Ron},
+    make_case_elim, make_proj_elim,
+};
+use lazy_static::lazy_static;
 use rand::{seq::SliceRandom, thread_rng};
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 
 make_proj_elim!(
     body,
@@ -23,6 +28,9 @@ impl Body {
         let body_type = *(&ALL_BODY_TYPES).choose(rng).unwrap();
         Self { species, body_type }
     }
+
+    /// Gameplay data of this body's species, see [`SpeciesStats`].
+    pub fn stats(&self) -> &'static SpeciesStats { &SPECIES_STATS[&self.species] }
 }
 
 impl From<Body> for super::Body {
@@ -46,6 +54,8 @@ pub enum Species {
     Deer = 13,
     Hirdrasil = 14,
     Roshwalr = 15,
+    Horse = 16,
+    Camel = 17,
 }
 
 /// Data representing per-species generic data.
@@ -67,6 +77,8 @@ pub struct AllSpecies<SpeciesMeta> {
     pub deer: SpeciesMeta,
     pub hirdrasil: SpeciesMeta,
     pub roshwalr: SpeciesMeta,
+    pub horse: SpeciesMeta,
+    pub camel: SpeciesMeta,
 }
 
 impl<'a, SpeciesMeta> core::ops::Index<&'a Species> for AllSpecies<SpeciesMeta> {
@@ -89,11 +101,60 @@ impl<'a, SpeciesMeta> core::ops::Index<&'a Species> for AllSpecies<SpeciesMeta>
             Species::Deer => &self.deer,
             Species::Hirdrasil => &self.hirdrasil,
             Species::Roshwalr => &self.roshwalr,
+            Species::Horse => &self.horse,
+            Species::Camel => &self.camel,
+        }
+    }
+}
+
+/// Gameplay data for a species, loaded from `common.body.quadruped_medium`
+/// so that adding a species doesn't mean touching a match in every system
+/// that cares about it. Missing fields fall back to [`Default`].
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct SpeciesStats {
+    pub radius: f32,
+    pub height: f32,
+    pub base_health: u32,
+    pub base_health_increase: u32,
+    pub base_exp: u32,
+    pub base_dmg: u32,
+    /// How eager NPCs of this species are to pick fights, see
+    /// `Psyche::aggro`
+    pub aggro: f32,
+    /// Whether NPCs of this species spawned in the wild are hostile
+    pub hostile: bool,
+    /// Item asset NPCs of this species attack with
+    pub main_tool: Option<String>,
+    /// Loot table dropped on death instead of the generic animal tables
+    pub loot_table: Option<String>,
+}
+
+impl Default for SpeciesStats {
+    fn default() -> Self {
+        Self {
+            radius: 1.5,
+            height: 1.6,
+            base_health: 400,
+            base_health_increase: 20,
+            base_exp: 6,
+            base_dmg: 40,
+            aggro: 1.0,
+            hostile: true,
+            main_tool: None,
+            loot_table: None,
         }
     }
 }
 
-pub const ALL_SPECIES: [Species; 14] = [
+pub type AllSpeciesStats = AllSpecies<SpeciesStats>;
+
+lazy_static! {
+    pub static ref SPECIES_STATS: Arc<AllSpeciesStats> =
+        Ron::<AllSpeciesStats>::load_expect("common.body.quadruped_medium");
+}
+
+pub const ALL_SPECIES: [Species; 16] = [
     Species::Grolgar,
     Species::Saber,
     Species::Tiger,
@@ -108,6 +169,8 @@ pub const ALL_SPECIES: [Species; 14] = [
     Species::Deer,
     Species::Hirdrasil,
     Species::Roshwalr,
+    Species::Horse,
+    Species::Camel,
 ];
 
 impl<'a, SpeciesMeta: 'a> IntoIterator for &'a AllSpecies<SpeciesMeta> {
@@ -128,3 +191,17 @@ make_case_elim!(
 );
 
 pub const ALL_BODY_TYPES: [BodyType; 2] = [BodyType::Female, BodyType::Male];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn species_stats_load() {
+        let horse = Body {
+            species: Species::Horse,
+            body_type: BodyType::Female,
+        };
+        assert!(!horse.stats().hostile);
+    }
+}
@@ -0,0 +1,88 @@
+use crate::{make_case_elim, make_proj_elim};
+use rand::{seq::SliceRandom, thread_rng};
+use serde::{Deserialize, Serialize};
+
+make_proj_elim!(
+    body,
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+    pub struct Body {
+        pub species: Species,
+    }
+);
+
+impl Body {
+    pub fn random() -> Self {
+        let mut rng = thread_rng();
+        let species = *(&ALL_SPECIES).choose(&mut rng).unwrap();
+        Self::random_with(&mut rng, &species)
+    }
+
+    #[inline]
+    pub fn random_with(_rng: &mut impl rand::Rng, &species: &Species) -> Self { Self { species } }
+
+    /// How far, in degrees, the turret can turn to either side of the
+    /// direction it is mounted facing
+    pub fn arc(&self) -> f32 {
+        match self.species {
+            Species::Ballista => 60.0,
+            Species::MagicTurret => 180.0,
+            Species::Statue => 45.0,
+        }
+    }
+
+    /// Distance up to which the turret picks targets to shoot at
+    pub fn range(&self) -> f32 {
+        match self.species {
+            Species::Ballista => 60.0,
+            Species::MagicTurret => 35.0,
+            Species::Statue => 30.0,
+        }
+    }
+}
+
+impl From<Body> for super::Body {
+    fn from(body: Body) -> Self { super::Body::Turret(body) }
+}
+
+make_case_elim!(
+    species,
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+    #[repr(u32)]
+    pub enum Species {
+        Ballista = 0,
+        MagicTurret = 1,
+        Statue = 2,
+    }
+);
+
+/// Data representing per-species generic data.
+///
+/// NOTE: Deliberately don't (yet?) implement serialize.
+#[derive(Clone, Debug, Deserialize)]
+pub struct AllSpecies<SpeciesMeta> {
+    pub ballista: SpeciesMeta,
+    pub magic_turret: SpeciesMeta,
+    pub statue: SpeciesMeta,
+}
+
+impl<'a, SpeciesMeta> core::ops::Index<&'a Species> for AllSpecies<SpeciesMeta> {
+    type Output = SpeciesMeta;
+
+    #[inline]
+    fn index(&self, &index: &'a Species) -> &Self::Output {
+        match index {
+            Species::Ballista => &self.ballista,
+            Species::MagicTurret => &self.magic_turret,
+            Species::Statue => &self.statue,
+        }
+    }
+}
+
+pub const ALL_SPECIES: [Species; 3] = [Species::Ballista, Species::MagicTurret, Species::Statue];
+
+impl<'a, SpeciesMeta: 'a> IntoIterator for &'a AllSpecies<SpeciesMeta> {
+    type IntoIter = std::iter::Copied<std::slice::Iter<'static, Self::Item>>;
+    type Item = Species;
+
+    fn into_iter(self) -> Self::IntoIter { ALL_SPECIES.iter().copied() }
+}
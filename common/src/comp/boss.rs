@@ -0,0 +1,124 @@
+use crate::assets::{Asset, Ron};
+use serde::{Deserialize, Serialize};
+use specs::Component;
+use specs_idvs::IdvStorage;
+
+/// Shockwave that a boss releases all around it, reaching across its arena
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ArenaShockwave {
+    pub damage: u32,
+    pub knockback: f32,
+    pub speed: f32,
+    /// How long the shockwave keeps spreading, in seconds
+    pub duration: f32,
+    /// Whether the shockwave deals true damage, which can't be blocked or
+    /// reduced by armor
+    #[serde(default)]
+    pub true_damage: bool,
+}
+
+/// Stage of a boss fight, entered once the health of the boss drops to
+/// `health_fraction` of its maximum
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BossPhase {
+    pub health_fraction: f32,
+    /// Asset specifier of a weapon whose abilities the boss switches to
+    #[serde(default)]
+    pub main_tool: Option<String>,
+    /// Shockwave released as the phase begins
+    #[serde(default)]
+    pub shockwave: Option<ArenaShockwave>,
+}
+
+/// Damage buff a boss gets when a fight drags on for too long
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Enrage {
+    /// Seconds after the boss is first hurt at which it enrages
+    pub after: f32,
+    /// Extra damage dealt while enraged, relative to the normal damage
+    pub strength: f32,
+}
+
+/// How a boss fight unfolds, loaded from the `common.bosses` assets
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BossConfig {
+    /// Phases in the order they are entered, from the highest health fraction
+    /// to the lowest
+    pub phases: Vec<BossPhase>,
+    #[serde(default)]
+    pub enrage: Option<Enrage>,
+}
+
+impl BossConfig {
+    pub fn load_expect(specifier: &str) -> Self { Ron::<Self>::load_expect_cloned(specifier) }
+}
+
+/// Progress of a boss through the phases of its fight
+#[derive(Clone, Debug, PartialEq)]
+pub struct BossState {
+    pub config: BossConfig,
+    /// Number of phases entered so far
+    pub phase: usize,
+    /// Time at which the boss was first hurt, starting the enrage timer
+    pub fight_start: Option<f64>,
+    pub enraged: bool,
+}
+
+impl BossState {
+    pub fn new(config: BossConfig) -> Self {
+        Self {
+            config,
+            phase: 0,
+            fight_start: None,
+            enraged: false,
+        }
+    }
+
+    /// Enters every phase whose health threshold has been reached, returning
+    /// the phases entered
+    pub fn advance(&mut self, health_fraction: f32) -> Vec<BossPhase> {
+        let mut entered = Vec::new();
+        while let Some(phase) = self
+            .config
+            .phases
+            .get(self.phase)
+            .filter(|phase| health_fraction <= phase.health_fraction)
+        {
+            entered.push(phase.clone());
+            self.phase += 1;
+        }
+        entered
+    }
+}
+
+impl Component for BossState {
+    type Storage = IdvStorage<Self>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn phase(health_fraction: f32) -> BossPhase {
+        BossPhase {
+            health_fraction,
+            main_tool: None,
+            shockwave: None,
+        }
+    }
+
+    #[test]
+    fn enters_phases_once_in_order() {
+        let mut boss = BossState::new(BossConfig {
+            phases: vec![phase(0.6), phase(0.3)],
+            enrage: None,
+        });
+        assert!(boss.advance(0.9).is_empty());
+        assert_eq!(boss.advance(0.5), vec![phase(0.6)]);
+        assert!(boss.advance(0.5).is_empty());
+        // A big hit can skip past several thresholds at once
+        boss.phase = 0;
+        assert_eq!(boss.advance(0.1), vec![phase(0.6), phase(0.3)]);
+        assert_eq!(boss.phase, 2);
+    }
+}
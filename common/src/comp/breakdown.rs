@@ -0,0 +1,174 @@
+//! Breakdown of the stats of a character into the sources that contribute to
+//! them, e.g. for showing on a character sheet.
+
+use crate::comp::{
+    affix::{self, Affix},
+    item::armor::Protection,
+    Affixes, Body, BuffEffect, BuffKind, Buffs, CritStats, DamageSource, Energy, Loadout,
+    ModifierKind, Stats,
+};
+
+/// Where part of a stat comes from
+#[derive(Clone, Debug, PartialEq)]
+pub enum StatSource {
+    /// Base value of the character's body
+    Base,
+    /// Gained by leveling up
+    Level,
+    /// A worn piece of armor, by name
+    Armor(String),
+    Buff(BuffKind),
+    Affix(Affix),
+}
+
+/// Part of a stat that comes from a single source
+#[derive(Clone, Debug, PartialEq)]
+pub struct Contribution {
+    pub source: StatSource,
+    pub kind: ModifierKind,
+    pub value: f32,
+}
+
+impl Contribution {
+    fn additive(source: StatSource, value: f32) -> Self {
+        Self {
+            source,
+            kind: ModifierKind::Additive,
+            value,
+        }
+    }
+
+    fn multiplicative(source: StatSource, value: f32) -> Self {
+        Self {
+            source,
+            kind: ModifierKind::Multiplicative,
+            value,
+        }
+    }
+}
+
+/// Applies contributions in order to get the value of the stat
+pub fn total(contributions: &[Contribution]) -> f32 {
+    contributions
+        .iter()
+        .fold(0.0, |stat, c| c.kind.apply(stat, c.value))
+}
+
+/// The stats of a character along with the sources contributing to them
+#[derive(Clone, Debug, PartialEq)]
+pub struct StatBreakdown {
+    pub health: u32,
+    pub max_health: Vec<Contribution>,
+    pub energy: u32,
+    pub max_energy: u32,
+    /// Protection of each worn piece of armor
+    pub armor: Vec<(String, Protection)>,
+    /// Portion of damage prevented by armor
+    pub damage_reduction: f32,
+    /// Movement speed relative to the base speed of the body
+    pub movement_speed: Vec<Contribution>,
+    /// Chance of attacks with the wielded weapon to be critical hits and
+    /// their damage multiplier
+    pub crit: CritStats,
+    /// Portion of each kind of damage that is prevented, when not blocking
+    /// and when blocking
+    pub resistances: Vec<(DamageSource, f32, f32)>,
+    /// Energy recovered per second right now
+    pub energy_regen: f32,
+    /// Speed of energy recovery relative to the base speed, from gear and buffs
+    pub energy_regen_modifier: Vec<Contribution>,
+}
+
+impl StatBreakdown {
+    pub fn new(
+        body: &Body,
+        stats: &Stats,
+        energy: Option<&Energy>,
+        loadout: Option<&Loadout>,
+        buffs: Option<&Buffs>,
+        affixes: Option<&Affixes>,
+    ) -> Self {
+        // Same order as the max health is calculated in, see the buff system
+        let mut max_health = vec![
+            Contribution::additive(StatSource::Base, body.base_health() as f32),
+            Contribution::additive(
+                StatSource::Level,
+                (body.base_health_increase() * stats.level.level()) as f32,
+            ),
+        ];
+        if let Some(buffs) = buffs {
+            max_health.extend(buffs.iter_active().flat_map(|buff| {
+                buff.effects.iter().filter_map(move |effect| match effect {
+                    BuffEffect::MaxHealthModifier { value, kind } => Some(Contribution {
+                        source: StatSource::Buff(buff.kind),
+                        kind: *kind,
+                        value: *value,
+                    }),
+                    _ => None,
+                })
+            }));
+        }
+
+        let armor = loadout.map_or_else(Vec::new, |loadout| {
+            loadout
+                .get_armor_protection()
+                .into_iter()
+                .map(|(item, protection)| (item.name().to_string(), protection))
+                .collect()
+        });
+        let damage_reduction = loadout.map_or(0.0, Loadout::get_damage_reduction);
+
+        let mut movement_speed = vec![Contribution::additive(StatSource::Base, 1.0)];
+        if affixes.map_or(false, |affixes| affixes.has(Affix::Fast)) {
+            movement_speed.push(Contribution::multiplicative(
+                StatSource::Affix(Affix::Fast),
+                affix::FAST_ACCEL,
+            ));
+        }
+
+        let mut energy_regen_modifier = vec![Contribution::additive(StatSource::Base, 1.0)];
+        if let Some(loadout) = loadout {
+            energy_regen_modifier.extend(loadout.get_energy_regen().into_iter().map(
+                |(item, regen)| {
+                    Contribution::additive(StatSource::Armor(item.name().to_string()), regen)
+                },
+            ));
+        }
+        if let Some(buffs) = buffs {
+            energy_regen_modifier.extend(buffs.iter_active().flat_map(|buff| {
+                buff.effects.iter().filter_map(move |effect| match effect {
+                    BuffEffect::EnergyRegenModifier(value) => Some(Contribution::multiplicative(
+                        StatSource::Buff(buff.kind),
+                        *value,
+                    )),
+                    _ => None,
+                })
+            }));
+        }
+
+        let resistances = DamageSource::RESISTIBLE
+            .iter()
+            .map(|source| {
+                (
+                    *source,
+                    source.resistance(false, damage_reduction),
+                    source.resistance(true, damage_reduction),
+                )
+            })
+            .collect();
+
+        Self {
+            health: stats.health.current(),
+            max_health,
+            energy: energy.map_or(0, Energy::current),
+            max_energy: energy.map_or(0, Energy::maximum),
+            armor,
+            damage_reduction,
+            movement_speed,
+            crit: CritStats::new(loadout, buffs),
+            resistances,
+            energy_regen: energy.map_or(0.0, |energy| energy.current_regen),
+            energy_regen_modifier,
+        }
+    }
+}
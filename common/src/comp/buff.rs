@@ -1,4 +1,4 @@
-use crate::sync::Uid;
+use crate::{comp::OnHitEffect, sync::Uid};
 use serde::{Deserialize, Serialize};
 use specs::{Component, FlaggedStorage};
 use specs_idvs::IdvStorage;
@@ -12,11 +12,28 @@ use std::{cmp::Ordering, collections::HashMap, time::Duration};
 pub enum BuffKind {
     /// Restores health/time for some period
     Regeneration,
+    /// Raises the chance of attacks to be critical hits
+    Focused,
+    /// Multiplies the threat caused towards NPCs, drawing their attention
+    Taunting,
     /// Lowers health over time for some duration
     Bleeding,
+    /// Lowers health over time for some duration, is put out by water and can
+    /// spread to nearby entities and flammable terrain
+    Burning,
+    /// Slows movement and attacks, stacks up to a complete freeze
+    Frozen,
+    /// Lowers health over time for some duration, from venomous bites and
+    /// stings
+    Poisoned,
+    /// Keeps the entity from using abilities, after it ran out of energy
+    Exhausted,
     /// Prefixes an entity's name with "Cursed"
     /// Currently placeholder buff to show other stuff is possible
     Cursed,
+    /// Raises the damage of attacks, given to bosses whose fights last too
+    /// long
+    Enraged,
 }
 
 impl BuffKind {
@@ -24,14 +41,56 @@ impl BuffKind {
     pub fn is_buff(self) -> bool {
         match self {
             BuffKind::Regeneration { .. } => true,
+            BuffKind::Focused { .. } => true,
+            BuffKind::Taunting { .. } => true,
             BuffKind::Bleeding { .. } => false,
+            BuffKind::Burning { .. } => false,
+            BuffKind::Frozen { .. } => false,
+            BuffKind::Poisoned { .. } => false,
+            BuffKind::Exhausted { .. } => false,
             BuffKind::Cursed { .. } => false,
+            BuffKind::Enraged { .. } => true,
         }
     }
+
+    /// How buffs of this kind combine when several are applied at once
+    pub fn stacking(self) -> BuffStacking {
+        match self {
+            BuffKind::Regeneration => BuffStacking::RefreshDuration,
+            BuffKind::Focused => BuffStacking::Strongest,
+            BuffKind::Taunting => BuffStacking::Strongest,
+            BuffKind::Bleeding => BuffStacking::StackCount { max: 5 },
+            BuffKind::Burning => BuffStacking::RefreshDuration,
+            BuffKind::Frozen => BuffStacking::StackCount {
+                max: FROZEN_MAX_STACKS,
+            },
+            BuffKind::Poisoned => BuffStacking::StackCount { max: 3 },
+            BuffKind::Exhausted => BuffStacking::RefreshDuration,
+            BuffKind::Cursed => BuffStacking::Strongest,
+            BuffKind::Enraged => BuffStacking::Strongest,
+        }
+    }
+}
+
+/// Rule deciding what happens when a buff is applied to an entity that already
+/// has a buff of the same kind
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BuffStacking {
+    /// Only the strongest buff of the kind is in effect, the others take over
+    /// once it ends
+    Strongest,
+    /// Up to `max` buffs of the kind are in effect at once, applying more
+    /// replaces the weakest
+    StackCount { max: u32 },
+    /// The kind is only applied once, applying it again refreshes the duration
+    /// (and strength, if the new buff is stronger)
+    RefreshDuration,
+    /// Every buff of the kind is in effect, without any limit
+    Independent,
 }
 
 // Struct used to store data relevant to a buff
-#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub struct BuffData {
     pub strength: f32,
     pub duration: Option<Duration>,
@@ -40,21 +99,41 @@ pub struct BuffData {
 /// De/buff category ID.
 /// Similar to `BuffKind`, but to mark a category (for more generic usage, like
 /// positive/negative buffs).
-#[derive(Clone, Copy, Eq, PartialEq, Debug, Serialize, Deserialize)]
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
 pub enum BuffCategory {
     Natural,
     Physical,
     Magical,
+    Poison,
     Divine,
     PersistOnDeath,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+/// How long an entity stays immune to a kind of debuff after it was dispelled
+pub const DISPEL_IMMUNITY: Duration = Duration::from_secs(5);
+
+/// Number of frozen stacks at which an entity is frozen solid and can't act
+pub const FROZEN_MAX_STACKS: u32 = 5;
+
+/// Multiplier on energy recovery while regenerating
+const REGENERATION_ENERGY_REGEN: f32 = 1.5;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub enum ModifierKind {
     Additive,
     Multiplicative,
 }
 
+impl ModifierKind {
+    /// Modifies `stat` by `value`
+    pub fn apply(self, stat: f32, value: f32) -> f32 {
+        match self {
+            ModifierKind::Additive => stat + value,
+            ModifierKind::Multiplicative => stat * value,
+        }
+    }
+}
+
 /// Data indicating and configuring behaviour of a de/buff.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum BuffEffect {
@@ -62,6 +141,22 @@ pub enum BuffEffect {
     HealthChangeOverTime { rate: f32, accumulated: f32 },
     /// Changes maximum health by a certain amount
     MaxHealthModifier { value: f32, kind: ModifierKind },
+    /// Adds to the chance of attacks to be critical hits
+    CritChanceModifier(f32),
+    /// Multiplies the threat caused towards NPCs
+    ThreatModifier(f32),
+    /// Triggers an effect whenever the entity's attacks deal damage
+    OnHit(OnHitEffect),
+    /// Multiplies movement acceleration
+    MovementSpeedModifier(f32),
+    /// Multiplies how fast character states such as attacks progress
+    AttackSpeedModifier(f32),
+    /// Keeps the entity from being knocked back
+    KnockbackImmunity,
+    /// Multiplies the damage dealt by the entity's attacks
+    DamageModifier(f32),
+    /// Multiplies how fast the entity recovers energy
+    EnergyRegenModifier(f32),
 }
 
 /// Actual de/buff.
@@ -105,6 +200,14 @@ pub enum BuffChange {
         any_required: Vec<BuffCategory>,
         none_required: Vec<BuffCategory>,
     },
+    /// Removes up to `count` debuffs (strongest first) that are in at least
+    /// one of `categories`, and makes the entity immune to the removed kinds
+    /// for `immunity`
+    Dispel {
+        categories: Vec<BuffCategory>,
+        count: u32,
+        immunity: Duration,
+    },
 }
 
 impl Buff {
@@ -116,7 +219,7 @@ impl Buff {
         source: BuffSource,
     ) -> Self {
         let (effects, time) = match kind {
-            BuffKind::Bleeding => (
+            BuffKind::Bleeding | BuffKind::Burning | BuffKind::Poisoned => (
                 vec![BuffEffect::HealthChangeOverTime {
                     rate: -data.strength,
                     accumulated: 0.0,
@@ -124,12 +227,36 @@ impl Buff {
                 data.duration,
             ),
             BuffKind::Regeneration => (
-                vec![BuffEffect::HealthChangeOverTime {
-                    rate: data.strength,
-                    accumulated: 0.0,
-                }],
+                vec![
+                    BuffEffect::HealthChangeOverTime {
+                        rate: data.strength,
+                        accumulated: 0.0,
+                    },
+                    BuffEffect::EnergyRegenModifier(REGENERATION_ENERGY_REGEN),
+                ],
                 data.duration,
             ),
+            BuffKind::Focused => (
+                vec![BuffEffect::CritChanceModifier(data.strength)],
+                data.duration,
+            ),
+            BuffKind::Taunting => (
+                vec![
+                    BuffEffect::ThreatModifier(data.strength),
+                    BuffEffect::KnockbackImmunity,
+                ],
+                data.duration,
+            ),
+            BuffKind::Frozen => (
+                vec![
+                    BuffEffect::MovementSpeedModifier(1.0 - data.strength),
+                    BuffEffect::AttackSpeedModifier(1.0 - data.strength),
+                    BuffEffect::EnergyRegenModifier(1.0 - data.strength),
+                ],
+                data.duration,
+            ),
+            // Checked directly by ability use rather than through an effect
+            BuffKind::Exhausted => (Vec::new(), data.duration),
             BuffKind::Cursed => (
                 vec![BuffEffect::MaxHealthModifier {
                     value: -100. * data.strength,
@@ -137,6 +264,10 @@ impl Buff {
                 }],
                 data.duration,
             ),
+            BuffKind::Enraged => (
+                vec![BuffEffect::DamageModifier(1.0 + data.strength)],
+                data.duration,
+            ),
         };
         Buff {
             kind,
@@ -147,6 +278,13 @@ impl Buff {
             source,
         }
     }
+
+    /// Whether the buff drains health over time, like bleeding or poison
+    pub fn is_damage_over_time(&self) -> bool {
+        self.effects.iter().any(|effect| {
+            matches!(effect, BuffEffect::HealthChangeOverTime { rate, .. } if *rate < 0.0)
+        })
+    }
 }
 
 impl PartialOrd for Buff {
@@ -210,28 +348,56 @@ pub struct Buffs {
     /// Uid used for synchronization
     id_counter: u64,
     /// Maps Kinds of buff to Id's of currently applied buffs of that kind
-    pub kinds: HashMap<BuffKind, Vec<BuffId>>,
+    pub kinds: HashMap<BuffKind, BuffStack>,
     // All currently applied buffs stored by Id
     pub buffs: HashMap<BuffId, Buff>,
+    /// Kinds of buff that can't be applied for the remaining time, after being
+    /// dispelled
+    pub immunities: HashMap<BuffKind, Duration>,
+}
+
+/// The currently applied buffs of a single kind
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct BuffStack {
+    /// Id's of the buffs, most powerful first
+    pub ids: Vec<BuffId>,
+    /// How many of the buffs are in effect, according to the kind's
+    /// `BuffStacking`
+    pub stacks: u32,
+}
+
+impl BuffStack {
+    /// Id's of the buffs that are in effect
+    pub fn active_ids(&self) -> &[BuffId] {
+        &self.ids[..(self.stacks as usize).min(self.ids.len())]
+    }
 }
 
 impl Buffs {
     fn sort_kind(&mut self, kind: BuffKind) {
-        if let Some(buff_order) = self.kinds.get_mut(&kind) {
-            if buff_order.is_empty() {
+        if let Some(stack) = self.kinds.get_mut(&kind) {
+            if stack.ids.is_empty() {
                 self.kinds.remove(&kind);
             } else {
                 let buffs = &self.buffs;
                 // Intentionally sorted in reverse so that the strongest buffs are earlier in
                 // the vector
-                buff_order.sort_by(|a, b| buffs[&b].partial_cmp(&buffs[&a]).unwrap());
+                stack
+                    .ids
+                    .sort_by(|a, b| buffs[&b].partial_cmp(&buffs[&a]).unwrap());
+                let count = stack.ids.len() as u32;
+                stack.stacks = match kind.stacking() {
+                    BuffStacking::Strongest | BuffStacking::RefreshDuration => 1,
+                    BuffStacking::StackCount { max } => count.min(max),
+                    BuffStacking::Independent => count,
+                };
             }
         }
     }
 
     pub fn remove_kind(&mut self, kind: BuffKind) {
-        if let Some(buff_ids) = self.kinds.get_mut(&kind) {
-            for id in buff_ids {
+        if let Some(stack) = self.kinds.get_mut(&kind) {
+            for id in &stack.ids {
                 self.buffs.remove(id);
             }
             self.kinds.remove(&kind);
@@ -241,13 +407,51 @@ impl Buffs {
 
     pub fn force_insert(&mut self, id: BuffId, buff: Buff) -> BuffId {
         let kind = buff.kind;
-        self.kinds.entry(kind).or_default().push(id);
+        self.kinds.entry(kind).or_default().ids.push(id);
         self.buffs.insert(id, buff);
         self.sort_kind(kind);
         id
     }
 
+    /// Applies a buff, following the stacking rule of its kind
     pub fn insert(&mut self, buff: Buff) -> BuffId {
+        let kind = buff.kind;
+        match kind.stacking() {
+            BuffStacking::RefreshDuration => {
+                if let Some(id) = self
+                    .kinds
+                    .get(&kind)
+                    .and_then(|stack| stack.ids.first().copied())
+                {
+                    if let Some(existing) = self.buffs.get_mut(&id) {
+                        let (time, duration) = if compare_duration(existing.time, buff.time) {
+                            (existing.time, existing.data.duration)
+                        } else {
+                            (buff.time, buff.data.duration)
+                        };
+                        if buff.data.strength > existing.data.strength {
+                            *existing = buff;
+                        }
+                        existing.time = time;
+                        existing.data.duration = duration;
+                    }
+                    return id;
+                }
+            },
+            BuffStacking::StackCount { max } => {
+                self.id_counter += 1;
+                let id = self.force_insert(self.id_counter, buff);
+                // Drop the weakest stacks beyond the limit
+                let excess = self.kinds.get(&kind).map_or_else(Vec::new, |stack| {
+                    stack.ids.iter().skip(max as usize).copied().collect()
+                });
+                for excess_id in excess {
+                    self.remove(excess_id);
+                }
+                return id;
+            },
+            BuffStacking::Strongest | BuffStacking::Independent => {},
+        }
         self.id_counter += 1;
         self.force_insert(self.id_counter, buff)
     }
@@ -256,28 +460,115 @@ impl Buffs {
     pub fn iter_kind(&self, kind: BuffKind) -> impl Iterator<Item = (BuffId, &Buff)> + '_ {
         self.kinds
             .get(&kind)
-            .map(|ids| ids.iter())
+            .map(|stack| stack.ids.iter())
             .unwrap_or_else(|| (&[]).iter())
             .map(move |id| (*id, &self.buffs[id]))
     }
 
-    // Iterates through all active buffs (the most powerful buff of each kind)
+    // Iterates through all buffs that are in effect
     pub fn iter_active(&self) -> impl Iterator<Item = &Buff> + '_ {
         self.kinds
             .values()
-            .map(move |ids| self.buffs.get(&ids[0]))
-            .filter(|buff| buff.is_some())
-            .map(|buff| buff.unwrap())
+            .flat_map(|stack| stack.active_ids())
+            .filter_map(move |id| self.buffs.get(id))
     }
 
-    // Gets most powerful buff of a given kind
-    // pub fn get_active_kind(&self, kind: BuffKind) -> Buff
+    // Iterates through the most powerful buff of each kind, along with the
+    // number of stacks of that kind in effect
+    pub fn iter_kinds(&self) -> impl Iterator<Item = (&Buff, u32)> + '_ {
+        self.kinds
+            .values()
+            .filter_map(move |stack| Some((self.buffs.get(stack.ids.first()?)?, stack.stacks)))
+    }
+
+    /// Removes up to `count` debuffs (strongest first) that are in at least one
+    /// of `categories`, making the entity immune to the removed kinds for
+    /// `immunity`
+    pub fn dispel(&mut self, categories: &[BuffCategory], count: u32, immunity: Duration) {
+        let mut dispelled = self
+            .buffs
+            .iter()
+            .filter(|(_, buff)| {
+                !buff.kind.is_buff() && buff.cat_ids.iter().any(|cat| categories.contains(cat))
+            })
+            .map(|(id, buff)| (*id, buff.kind, buff.data.strength))
+            .collect::<Vec<_>>();
+        dispelled.sort_by(|(_, _, a), (_, _, b)| b.partial_cmp(a).unwrap_or(Ordering::Equal));
+        for (id, kind, _) in dispelled.into_iter().take(count as usize) {
+            self.remove(id);
+            self.immunities.insert(kind, immunity);
+        }
+    }
+
+    /// Whether buffs of this kind are currently prevented from being applied
+    pub fn is_immune(&self, kind: BuffKind) -> bool { self.immunities.contains_key(&kind) }
+
+    // Number of stacks of a given kind in effect
+    pub fn stacks(&self, kind: BuffKind) -> u32 {
+        self.kinds.get(&kind).map_or(0, |stack| stack.stacks)
+    }
+
+    /// Multiplier for movement acceleration from the buffs in effect
+    pub fn movement_speed_modifier(&self) -> f32 {
+        self.iter_active()
+            .flat_map(|buff| buff.effects.iter())
+            .fold(1.0, |modifier, effect| match effect {
+                BuffEffect::MovementSpeedModifier(value) => modifier * value.max(0.0),
+                _ => modifier,
+            })
+    }
+
+    /// Multiplier for how fast character states progress from the buffs in
+    /// effect
+    pub fn attack_speed_modifier(&self) -> f32 {
+        self.iter_active()
+            .flat_map(|buff| buff.effects.iter())
+            .fold(1.0, |modifier, effect| match effect {
+                BuffEffect::AttackSpeedModifier(value) => modifier * value.max(0.0),
+                _ => modifier,
+            })
+    }
+
+    /// Multiplier for the damage dealt by the entity's attacks from the buffs in
+    /// effect
+    pub fn damage_modifier(&self) -> f32 {
+        self.iter_active()
+            .flat_map(|buff| buff.effects.iter())
+            .fold(1.0, |modifier, effect| match effect {
+                BuffEffect::DamageModifier(value) => modifier * value.max(0.0),
+                _ => modifier,
+            })
+    }
+
+    /// Multiplier for how fast the entity recovers energy from the buffs in
+    /// effect
+    pub fn energy_regen_modifier(&self) -> f32 {
+        self.iter_active()
+            .flat_map(|buff| buff.effects.iter())
+            .fold(1.0, |modifier, effect| match effect {
+                BuffEffect::EnergyRegenModifier(value) => modifier * value.max(0.0),
+                _ => modifier,
+            })
+    }
+
+    /// Whether a buff in effect keeps the entity from being knocked back
+    pub fn is_knockback_immune(&self) -> bool {
+        self.iter_active()
+            .flat_map(|buff| buff.effects.iter())
+            .any(|effect| matches!(effect, BuffEffect::KnockbackImmunity))
+    }
+
+    /// Whether enough frozen stacks have built up to freeze the entity solid
+    pub fn is_frozen(&self) -> bool { self.stacks(BuffKind::Frozen) >= FROZEN_MAX_STACKS }
+
+    /// Whether the entity ran out of energy recently and can't use abilities
+    pub fn is_exhausted(&self) -> bool { self.stacks(BuffKind::Exhausted) > 0 }
 
     pub fn remove(&mut self, buff_id: BuffId) {
         let kind = self.buffs.remove(&buff_id).unwrap().kind;
-        self.kinds
-            .get_mut(&kind)
-            .map(|ids| ids.retain(|id| *id != buff_id));
+        if let Some(stack) = self.kinds.get_mut(&kind) {
+            stack.ids.retain(|id| *id != buff_id);
+        }
         self.sort_kind(kind);
     }
 }
@@ -287,3 +578,75 @@ pub type BuffId = u64;
 impl Component for Buffs {
     type Storage = FlaggedStorage<Self, IdvStorage<Self>>;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn buff(kind: BuffKind, strength: f32, secs: u64) -> Buff {
+        Buff::new(
+            kind,
+            BuffData {
+                strength,
+                duration: Some(Duration::from_secs(secs)),
+            },
+            vec![],
+            BuffSource::Unknown,
+        )
+    }
+
+    #[test]
+    fn bleeding_stacks_up_to_limit() {
+        let mut buffs = Buffs::default();
+        for i in 0..7 {
+            buffs.insert(buff(BuffKind::Bleeding, 1.0 + i as f32, 10));
+        }
+        assert_eq!(buffs.stacks(BuffKind::Bleeding), 5);
+        assert_eq!(buffs.iter_active().count(), 5);
+        // The weakest stacks were dropped
+        assert!(buffs.iter_active().all(|buff| buff.data.strength >= 3.0));
+    }
+
+    #[test]
+    fn regeneration_refreshes_duration() {
+        let mut buffs = Buffs::default();
+        buffs.insert(buff(BuffKind::Regeneration, 2.0, 5));
+        buffs.insert(buff(BuffKind::Regeneration, 1.0, 20));
+        assert_eq!(buffs.stacks(BuffKind::Regeneration), 1);
+        assert_eq!(buffs.buffs.len(), 1);
+        let (_, regen) = buffs.iter_kind(BuffKind::Regeneration).next().unwrap();
+        assert!((regen.data.strength - 2.0).abs() < f32::EPSILON);
+        assert_eq!(regen.time, Some(Duration::from_secs(20)));
+    }
+
+    #[test]
+    fn dispel_removes_matching_debuffs_and_grants_immunity() {
+        let mut buffs = Buffs::default();
+        let mut bleed = buff(BuffKind::Bleeding, 2.0, 10);
+        bleed.cat_ids = vec![BuffCategory::Physical];
+        buffs.insert(bleed);
+        let mut curse = buff(BuffKind::Cursed, 1.0, 10);
+        curse.cat_ids = vec![BuffCategory::Magical];
+        buffs.insert(curse);
+
+        buffs.dispel(&[BuffCategory::Physical], 3, DISPEL_IMMUNITY);
+        assert_eq!(buffs.stacks(BuffKind::Bleeding), 0);
+        assert_eq!(buffs.stacks(BuffKind::Cursed), 1);
+        assert!(buffs.is_immune(BuffKind::Bleeding));
+        assert!(!buffs.is_immune(BuffKind::Cursed));
+    }
+
+    #[test]
+    fn frozen_stacks_slow_then_freeze() {
+        let mut buffs = Buffs::default();
+        buffs.insert(buff(BuffKind::Frozen, 0.2, 10));
+        buffs.insert(buff(BuffKind::Frozen, 0.2, 10));
+        assert!((buffs.movement_speed_modifier() - 0.64).abs() < 0.001);
+        assert!((buffs.attack_speed_modifier() - 0.64).abs() < 0.001);
+        assert!(!buffs.is_frozen());
+        for _ in 0..FROZEN_MAX_STACKS {
+            buffs.insert(buff(BuffKind::Frozen, 0.2, 10));
+        }
+        assert!(buffs.is_frozen());
+    }
+}
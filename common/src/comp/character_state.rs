@@ -1,13 +1,15 @@
 use crate::{
-    comp::{Energy, Ori, Pos, Vel},
+    comp::{BaseDamage, Energy, Ori, Pos, RegenMode, Vel},
     event::{LocalEvent, ServerEvent},
     states::*,
+    sync::Uid,
     sys::character_behavior::JoinData,
 };
 use serde::{Deserialize, Serialize};
 use specs::{Component, FlaggedStorage, VecStorage};
 use specs_idvs::IdvStorage;
 use std::collections::VecDeque;
+use vek::Vec3;
 
 /// Data returned from character behavior fn's to Character Behavior System.
 pub struct StateUpdate {
@@ -42,6 +44,8 @@ pub enum CharacterState {
     Sit,
     Dance,
     Sneak,
+    /// Crouching with a lowered collider, moving slowly
+    Crouch,
     Glide,
     GlideWield,
     /// A basic blocking state
@@ -63,6 +67,9 @@ pub enum CharacterState {
     /// A three-stage attack where each attack pushes player forward
     /// and successive attacks increase in damage, while player holds button.
     ComboMelee(combo_melee::Data),
+    /// Alternating swings with a one-handed weapon in each hand, speeding up
+    /// while the player holds the button
+    DualStrike(dual_strike::Data),
     /// A leap followed by a small aoe ground attack
     LeapMelee(leap_melee::Data),
     /// Spin around, dealing damage to enemies surrounding you
@@ -78,6 +85,27 @@ pub enum CharacterState {
     /// A continuous attack that affects all creatures in a cone originating
     /// from the source
     BasicBeam(basic_beam::Data),
+    /// Wind up and throw an item from the inventory (e.g. bombs, potions)
+    Throw(throw::Data),
+    /// Run forward in a straight line, trampling everything in the way
+    Charge(charge::Data),
+    /// Cast a spell that can be interrupted by taking damage, released once
+    /// the cast completes
+    CastSpell(cast_spell::Data),
+    /// Knocked down at zero health, waiting to be revived by a group member
+    /// before bleeding out
+    Downed(downed::Data),
+    /// Channel to bring a downed group member back on their feet
+    Revive(revive::Data),
+    /// Cast a line into nearby water and wait for a fish to bite, reeling it
+    /// in before it gets away
+    Fishing(fishing::Data),
+    /// Playing an instrument, either note by note or one of the preset songs
+    Perform(perform::Data),
+    /// Flying with wings, from taking off until having landed again
+    Fly(fly::Data),
+    /// Diving into deep water, swimming fast or dodging under water
+    Swim(swim::Data),
 }
 
 impl CharacterState {
@@ -88,6 +116,7 @@ impl CharacterState {
             | CharacterState::BasicRanged(_)
             | CharacterState::DashMelee(_)
             | CharacterState::ComboMelee(_)
+            | CharacterState::DualStrike(_)
             | CharacterState::BasicBlock
             | CharacterState::LeapMelee(_)
             | CharacterState::SpinMelee(_)
@@ -96,6 +125,8 @@ impl CharacterState {
             | CharacterState::RepeaterRanged(_)
             | CharacterState::Shockwave(_)
             | CharacterState::BasicBeam(_)
+            | CharacterState::Charge(_)
+            | CharacterState::CastSpell(_)
         )
     }
 
@@ -105,6 +136,7 @@ impl CharacterState {
             | CharacterState::BasicRanged(_)
             | CharacterState::DashMelee(_)
             | CharacterState::ComboMelee(_)
+            | CharacterState::DualStrike(_)
             | CharacterState::LeapMelee(_)
             | CharacterState::SpinMelee(_)
             | CharacterState::ChargedMelee(_)
@@ -112,6 +144,9 @@ impl CharacterState {
             | CharacterState::RepeaterRanged(_)
             | CharacterState::Shockwave(_)
             | CharacterState::BasicBeam(_)
+            | CharacterState::Throw(_)
+            | CharacterState::Charge(_)
+            | CharacterState::CastSpell(_)
         )
     }
 
@@ -121,6 +156,7 @@ impl CharacterState {
             | CharacterState::BasicRanged(_)
             | CharacterState::DashMelee(_)
             | CharacterState::ComboMelee(_)
+            | CharacterState::DualStrike(_)
             | CharacterState::BasicBlock
             | CharacterState::LeapMelee(_)
             | CharacterState::ChargedMelee(_)
@@ -128,12 +164,67 @@ impl CharacterState {
             | CharacterState::RepeaterRanged(_)
             | CharacterState::Shockwave(_)
             | CharacterState::BasicBeam(_)
+            | CharacterState::Throw(_)
+            | CharacterState::Charge(_)
+            | CharacterState::CastSpell(_)
         )
     }
 
     pub fn is_block(&self) -> bool { matches!(self, CharacterState::BasicBlock) }
 
-    pub fn is_dodge(&self) -> bool { matches!(self, CharacterState::Roll(_)) }
+    pub fn is_dodge(&self) -> bool {
+        match self {
+            CharacterState::Roll(_) => true,
+            CharacterState::Swim(swim) => swim.stage == swim::Stage::Dodge,
+            _ => false,
+        }
+    }
+
+    pub fn is_downed(&self) -> bool { matches!(self, CharacterState::Downed(_)) }
+
+    pub fn is_crouch(&self) -> bool { matches!(self, CharacterState::Crouch) }
+
+    /// How the state affects the recovery of energy. Resting recovers it
+    /// fastest, holding up a block slowly, and using abilities not at all.
+    pub fn energy_regen(&self) -> RegenMode {
+        match self {
+            CharacterState::Sit | CharacterState::Dance | CharacterState::Perform(_) => {
+                RegenMode::Recover(1.5)
+            },
+            CharacterState::Idle
+            | CharacterState::Sneak
+            | CharacterState::Crouch
+            | CharacterState::GlideWield
+            | CharacterState::Wielding
+            | CharacterState::Equipping(_)
+            | CharacterState::Fishing(_)
+            | CharacterState::Boost(_) => RegenMode::Recover(1.0),
+            CharacterState::BasicBlock => RegenMode::Recover(0.3),
+            CharacterState::BasicMelee(_)
+            | CharacterState::DashMelee(_)
+            | CharacterState::LeapMelee(_)
+            | CharacterState::SpinMelee(_)
+            | CharacterState::ComboMelee(_)
+            | CharacterState::DualStrike(_)
+            | CharacterState::BasicRanged(_)
+            | CharacterState::ChargedMelee(_)
+            | CharacterState::ChargedRanged(_)
+            | CharacterState::RepeaterRanged(_)
+            | CharacterState::Shockwave(_)
+            | CharacterState::BasicBeam(_)
+            | CharacterState::Throw(_)
+            | CharacterState::Charge(_)
+            | CharacterState::CastSpell(_) => RegenMode::Reset,
+            // Movement that uses up energy of its own
+            CharacterState::Roll(_)
+            | CharacterState::Climb
+            | CharacterState::Glide
+            | CharacterState::Fly(_)
+            | CharacterState::Swim(_)
+            | CharacterState::Downed(_)
+            | CharacterState::Revive(_) => RegenMode::Stall,
+        }
+    }
 
     /// Compares for shallow equality (does not check internal struct equality)
     pub fn same_variant(&self, other: &Self) -> bool {
@@ -152,7 +243,7 @@ impl Component for CharacterState {
 
 #[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Attacking {
-    pub base_damage: u32,
+    pub base_damage: BaseDamage,
     pub base_heal: u32,
     pub range: f32,
     pub max_angle: f32,
@@ -164,3 +255,35 @@ pub struct Attacking {
 impl Component for Attacking {
     type Storage = VecStorage<Self>;
 }
+
+/// Damages every entity within `radius` of the path the entity moved along
+/// since the last tick, hitting each of them at most once
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Trampling {
+    pub damage: u32,
+    pub knockback: f32,
+    pub radius: f32,
+    /// Where the last sweep ended
+    pub last_pos: Option<Vec3<f32>>,
+    pub hit_entities: Vec<Uid>,
+}
+
+impl Component for Trampling {
+    type Storage = IdvStorage<Self>;
+}
+
+/// Shields the entity from all damage until the given time, e.g. during a dodge
+/// roll
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Invulnerable {
+    /// Time at which the invulnerability wears off
+    pub until: f64,
+}
+
+impl Invulnerable {
+    pub fn is_active(&self, time: f64) -> bool { time < self.until }
+}
+
+impl Component for Invulnerable {
+    type Storage = IdvStorage<Self>;
+}
@@ -0,0 +1,177 @@
+//! Log of the damage, healing and buffs an entity took and dealt, kept for
+//! showing a combat log and death recap to players.
+
+use crate::{
+    comp::{BuffKind, HealthChange},
+    sync::Uid,
+};
+use serde::{Deserialize, Serialize};
+use specs::Component;
+use specs_idvs::IdvStorage;
+use std::collections::VecDeque;
+
+/// Number of events kept in a combat log before the oldest are forgotten
+pub const COMBAT_LOG_LEN: usize = 64;
+/// How many seconds of damage before the killing blow are shown in a death
+/// recap
+pub const RECAP_WINDOW: f64 = 10.0;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum CombatEventKind {
+    /// Health was lost or gained
+    Health {
+        change: HealthChange,
+        /// What the attacker used, currently the name of the weapon they were
+        /// wielding
+        ability: Option<String>,
+    },
+    /// A buff or debuff was applied
+    Buff(BuffKind),
+}
+
+/// Something that happened between two entities in combat
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CombatEvent {
+    /// Time at which the event happened, in the clock of the server
+    pub time: f64,
+    /// The entity that caused the event, if there was one
+    pub attacker: Option<Uid>,
+    /// The entity that the event happened to
+    pub target: Uid,
+    pub kind: CombatEventKind,
+}
+
+impl CombatEvent {
+    /// Health the target lost, if the event damaged it. Damage past the health
+    /// the target had left isn't counted.
+    pub fn damage(&self) -> Option<u32> {
+        match &self.kind {
+            CombatEventKind::Health { change, .. } if change.amount < 0 => {
+                Some((change.amount.abs() as u32).saturating_sub(change.overkill))
+            },
+            _ => None,
+        }
+    }
+}
+
+/// Ring buffer of the latest combat events an entity was involved in, either
+/// as the attacker or as the target
+#[derive(Clone, Debug, Default)]
+pub struct CombatLog {
+    events: VecDeque<CombatEvent>,
+    /// Number of events at the back of the log that weren't yet sent to the
+    /// client owning the entity
+    unsynced: usize,
+}
+
+impl CombatLog {
+    pub fn push(&mut self, event: CombatEvent) {
+        if self.events.len() >= COMBAT_LOG_LEN {
+            self.events.pop_front();
+        }
+        self.events.push_back(event);
+        self.unsynced = (self.unsynced + 1).min(COMBAT_LOG_LEN);
+    }
+
+    /// Adds events received from the server, in the order they happened
+    pub fn extend(&mut self, events: impl IntoIterator<Item = CombatEvent>) {
+        for event in events {
+            self.push(event);
+        }
+        self.unsynced = 0;
+    }
+
+    /// Events in the order they happened, oldest first
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &CombatEvent> { self.events.iter() }
+
+    /// Events that were added since this was last called
+    pub fn take_unsynced(&mut self) -> Vec<CombatEvent> {
+        let unsynced = self.unsynced;
+        self.unsynced = 0;
+        self.events
+            .iter()
+            .skip(self.events.len() - unsynced)
+            .cloned()
+            .collect()
+    }
+
+    /// The damage `uid` took in the moments leading up to the last time it
+    /// was hit, for showing what killed it
+    pub fn death_recap(&self, uid: Uid) -> impl Iterator<Item = &CombatEvent> {
+        let taken = move |event: &&CombatEvent| event.target == uid && event.damage().is_some();
+        let last_hit = self
+            .events
+            .iter()
+            .rev()
+            .find(taken)
+            .map_or(f64::MAX, |event| event.time);
+        self.events
+            .iter()
+            .filter(taken)
+            .filter(move |event| event.time >= last_hit - RECAP_WINDOW)
+    }
+}
+
+impl Component for CombatLog {
+    type Storage = IdvStorage<Self>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::comp::HealthSource;
+
+    fn hit(time: f64, target: u64, amount: i32) -> CombatEvent {
+        CombatEvent {
+            time,
+            attacker: None,
+            target: Uid(target),
+            kind: CombatEventKind::Health {
+                change: HealthChange {
+                    amount,
+                    cause: HealthSource::World,
+                    crit: false,
+                    mitigated: 0,
+                    overkill: 0,
+                },
+                ability: None,
+            },
+        }
+    }
+
+    #[test]
+    fn forgets_oldest_events() {
+        let mut log = CombatLog::default();
+        for i in 0..COMBAT_LOG_LEN + 5 {
+            log.push(hit(i as f64, 1, -1));
+        }
+        assert_eq!(log.iter().count(), COMBAT_LOG_LEN);
+        assert_eq!(log.iter().next().map(|e| e.time), Some(5.0));
+        assert_eq!(log.take_unsynced().len(), COMBAT_LOG_LEN);
+        assert!(log.take_unsynced().is_empty());
+    }
+
+    #[test]
+    fn recap_only_has_recent_damage_taken() {
+        let mut log = CombatLog::default();
+        log.push(hit(0.0, 1, -10));
+        log.push(hit(15.0, 1, -20));
+        log.push(hit(18.0, 2, -30));
+        log.push(hit(19.0, 1, 5));
+        log.push(hit(20.0, 1, -40));
+        let recap = log
+            .death_recap(Uid(1))
+            .filter_map(CombatEvent::damage)
+            .collect::<Vec<_>>();
+        assert_eq!(recap, vec![20, 40]);
+    }
+
+    #[test]
+    fn damage_leaves_out_overkill() {
+        let mut killing_blow = hit(0.0, 1, -50);
+        if let CombatEventKind::Health { change, .. } = &mut killing_blow.kind {
+            change.overkill = 35;
+        }
+        assert_eq!(killing_blow.damage(), Some(15));
+    }
+}
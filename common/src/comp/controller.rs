@@ -1,5 +1,11 @@
 use crate::{
-    comp::{inventory::slot::Slot, BuffKind},
+    comp::{
+        inventory::{
+            item::{Instrument, ToolCategory},
+            slot::{EquipSlot, Slot},
+        },
+        BuffKind,
+    },
     sync::Uid,
     util::Dir,
 };
@@ -16,10 +22,18 @@ pub const DEFAULT_HOLD_DURATION: Duration = Duration::from_millis(200);
 pub enum InventoryManip {
     Pickup(Uid),
     Collect(Vec3<i32>),
+    Unlock(Vec3<i32>),
     Use(Slot),
     Swap(Slot, Slot),
     Drop(Slot),
     CraftRecipe(String),
+    /// Dye the worn armor in an equipment slot with a dye from the inventory
+    Dye(usize, EquipSlot),
+    /// Make the worn armor in an equipment slot look like an item from the
+    /// inventory
+    SetAppearance(usize, EquipSlot),
+    /// Restore the own look of the worn armor in an equipment slot
+    ClearAppearance(EquipSlot),
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -32,6 +46,13 @@ pub enum GroupManip {
     AssignLeader(Uid),
 }
 
+/// Whether a player buys a good from a merchant or sells it to them
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum TradeAction {
+    Buy,
+    Sell,
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum ControlEvent {
     //ToggleLantern,
@@ -43,6 +64,23 @@ pub enum ControlEvent {
     GroupManip(GroupManip),
     RemoveBuff(BuffKind),
     Respawn,
+    /// Read the bounties posted on the board at the given position
+    ReadBountyBoard(Vec3<i32>),
+    /// Talk to the NPC with the given uid
+    Talk(Uid),
+    /// Trade one of the good at the given index in the market of the merchant
+    /// with the given uid
+    Trade {
+        merchant: Uid,
+        good: usize,
+        action: TradeAction,
+    },
+    /// Choose which ability to use in an ability slot of a kind of weapon
+    SelectAbility {
+        tool: ToolCategory,
+        slot: usize,
+        index: usize,
+    },
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
@@ -54,7 +92,21 @@ pub enum ControlAction {
     Sit,
     Dance,
     Sneak,
+    /// Crouch down, shrinking the collider so low passages can be entered
+    Crouch,
     Stand,
+    /// Throw the item in the given inventory slot
+    Throw(usize),
+    /// Revive the given downed group member
+    Revive(Uid),
+    /// Start fishing in nearby water, or reel in the line if already fishing
+    Fish,
+    /// Start performing with an instrument, or switch to another one
+    Perform(Instrument),
+    /// Play a note of the instrument being performed with
+    PlayNote(u8),
+    /// Play one of the preset songs on the instrument being performed with
+    PlaySong(usize),
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -180,6 +232,7 @@ pub struct ControllerInputs {
     pub climb: Option<Climb>,
     pub swimup: Input,
     pub swimdown: Input,
+    pub swimsprint: Input,
     pub move_dir: Vec2<f32>,
     pub look_dir: Dir,
 }
@@ -205,6 +258,7 @@ impl ControllerInputs {
         self.charge.tick(dt);
         self.swimup.tick(dt);
         self.swimdown.tick(dt);
+        self.swimsprint.tick(dt);
     }
 
     pub fn tick_freshness(&mut self) {
@@ -218,6 +272,7 @@ impl ControllerInputs {
         self.charge.tick_freshness();
         self.swimup.tick_freshness();
         self.swimdown.tick_freshness();
+        self.swimsprint.tick_freshness();
     }
 
     /// Updates Controller inputs with new version received from the client
@@ -233,6 +288,7 @@ impl ControllerInputs {
         self.climb = new.climb;
         self.swimup.update_with_new(new.swimup);
         self.swimdown.update_with_new(new.swimdown);
+        self.swimsprint.update_with_new(new.swimsprint);
         self.move_dir = new.move_dir;
         self.look_dir = new.look_dir;
     }
@@ -271,3 +327,11 @@ pub struct Mounting(pub Uid);
 impl Component for Mounting {
     type Storage = FlaggedStorage<Self, IdvStorage<Self>>;
 }
+
+/// Ties a cart to the animal pulling it, which the cart then trails behind
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Hitched(pub Uid);
+
+impl Component for Hitched {
+    type Storage = IdvStorage<Self>;
+}
@@ -1,11 +1,170 @@
-use crate::comp::Loadout;
+use crate::comp::{item::ItemKind, Body, BuffEffect, Buffs, Loadout};
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
 pub const BLOCK_EFFICIENCY: f32 = 0.9;
+/// Chance of melee and projectile attacks to be critical hits, for weapons
+/// that don't specify their own
+pub const CRIT_CHANCE: f32 = 0.5;
+/// Multiplier of the damage of a critical hit, for weapons that don't specify
+/// their own
+pub const CRIT_MULTIPLIER: f32 = 1.3;
+
+/// Kind of damage, which armor and creatures can be more or less resistant to
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DamageKind {
+    Slashing,
+    Piercing,
+    Crushing,
+    Fire,
+    Frost,
+    Poison,
+}
+
+impl DamageKind {
+    pub const ALL: [DamageKind; 6] = [
+        DamageKind::Slashing,
+        DamageKind::Piercing,
+        DamageKind::Crushing,
+        DamageKind::Fire,
+        DamageKind::Frost,
+        DamageKind::Poison,
+    ];
+}
+
+/// Damage of a single kind dealt by an attack, before it is mitigated
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BaseDamage {
+    pub amount: u32,
+    pub kind: DamageKind,
+}
 
 pub struct Damage {
     pub healthchange: f32,
     pub source: DamageSource,
+    /// Untyped damage is only mitigated by blocking and armor protection
+    pub kind: Option<DamageKind>,
+    /// Damage multiplier of the attack if it is a critical hit
+    pub crit: Option<f32>,
+    /// Share of the damage reduction from the target's armor that is ignored
+    pub penetration: f32,
+    /// True damage ignores blocking, armor and resistances, though not
+    /// invincible armor
+    pub true_damage: bool,
+}
+
+/// How likely the hits of an attacker are to be critical, and how much more
+/// damage they deal when they are
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct CritStats {
+    pub chance: f32,
+    pub multiplier: f32,
+}
+
+impl CritStats {
+    /// Crit stats of the wielded weapon, modified by active buffs
+    pub fn new(loadout: Option<&Loadout>, buffs: Option<&Buffs>) -> Self {
+        let (chance, multiplier) = match loadout
+            .and_then(|loadout| loadout.active_item.as_ref())
+            .map(|item| item.item.kind())
+        {
+            Some(ItemKind::Tool(tool)) => (tool.crit_chance(), tool.crit_multiplier()),
+            _ => (CRIT_CHANCE, CRIT_MULTIPLIER),
+        };
+        let chance = buffs.map_or(chance, |buffs| {
+            buffs
+                .iter_active()
+                .flat_map(|buff| buff.effects.iter())
+                .fold(chance, |chance, effect| match effect {
+                    BuffEffect::CritChanceModifier(value) => chance + value,
+                    _ => chance,
+                })
+        });
+        Self {
+            chance: chance.max(0.0).min(1.0),
+            multiplier,
+        }
+    }
+
+    /// Rolls whether an attack is a critical hit, giving its damage multiplier
+    /// if it is
+    pub fn roll(&self) -> Option<f32> {
+        if rand::random::<f32>() < self.chance {
+            Some(self.multiplier)
+        } else {
+            None
+        }
+    }
+}
+
+/// Effect triggered whenever an attack of an entity deals damage, registered
+/// by its weapon or buffs
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum OnHitEffect {
+    /// Heals the attacker by this share of the damage dealt
+    Lifesteal(f32),
+    /// Restores this much energy to the attacker
+    EnergyGain(u32),
+    /// Makes the target bleed with some chance, for `strength` times the
+    /// damage dealt per second
+    Bleed {
+        chance: f32,
+        strength: f32,
+        duration: Duration,
+    },
+    /// Arcs to the closest other enemy within `range` of the target, dealing
+    /// `strength` times the damage dealt to it
+    ChainLightning { range: f32, strength: f32 },
+    /// Adds a frozen stack to the target, slowing it by `strength`
+    Chill { strength: f32, duration: Duration },
+    /// Poisons the target with some chance, for `strength` damage per second
+    /// lessened by its poison resistance
+    Envenom {
+        chance: f32,
+        strength: f32,
+        duration: Duration,
+    },
+}
+
+/// Armor penetration of the wielded weapon
+pub fn armor_penetration(loadout: Option<&Loadout>) -> f32 {
+    match loadout
+        .and_then(|loadout| loadout.active_item.as_ref())
+        .map(|item| item.item.kind())
+    {
+        Some(ItemKind::Tool(tool)) => tool.armor_penetration(),
+        _ => 0.0,
+    }
+}
+
+/// Damage reduction from armor left once `penetration` of it is ignored.
+/// Neither invincible nor weakening armor can be penetrated.
+pub fn penetrated_reduction(damage_reduction: f32, penetration: f32) -> f32 {
+    if damage_reduction <= 0.0 || (damage_reduction - 1.0).abs() < f32::EPSILON {
+        damage_reduction
+    } else {
+        damage_reduction * (1.0 - penetration.max(0.0).min(1.0))
+    }
+}
+
+/// On-hit effects of the wielded weapon and active buffs
+pub fn on_hit_effects(loadout: Option<&Loadout>, buffs: Option<&Buffs>) -> Vec<OnHitEffect> {
+    let weapon_effects = match loadout
+        .and_then(|loadout| loadout.active_item.as_ref())
+        .map(|item| item.item.kind())
+    {
+        Some(ItemKind::Tool(tool)) => tool.on_hit.clone(),
+        _ => Vec::new(),
+    };
+    let buff_effects = buffs
+        .into_iter()
+        .flat_map(|buffs| buffs.iter_active())
+        .flat_map(|buff| buff.effects.iter())
+        .filter_map(|effect| match effect {
+            BuffEffect::OnHit(effect) => Some(*effect),
+            _ => None,
+        });
+    weapon_effects.into_iter().chain(buff_effects).collect()
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -17,63 +176,143 @@ pub enum DamageSource {
     Falling,
     Shockwave,
     Energy,
+    /// Harmful terrain, such as lava and spikes
+    Hazard,
+}
+
+impl DamageSource {
+    /// Kinds of damage that can be resisted
+    pub const RESISTIBLE: [DamageSource; 5] = [
+        DamageSource::Melee,
+        DamageSource::Projectile,
+        DamageSource::Explosion,
+        DamageSource::Shockwave,
+        DamageSource::Energy,
+    ];
+
+    pub fn can_block(self) -> bool {
+        matches!(
+            self,
+            DamageSource::Melee | DamageSource::Projectile | DamageSource::Explosion
+        )
+    }
+
+    /// Portion of damage of this kind that is prevented, given the damage
+    /// reduction from armor
+    pub fn resistance(self, block: bool, damage_reduction: f32) -> f32 {
+        if !Self::RESISTIBLE.contains(&self) {
+            return 0.0;
+        }
+        let block = if block && self.can_block() {
+            BLOCK_EFFICIENCY
+        } else {
+            0.0
+        };
+        1.0 - (1.0 - block) * (1.0 - damage_reduction)
+    }
 }
 
 impl Damage {
-    pub fn modify_damage(&mut self, block: bool, loadout: &Loadout) {
-        match self.source {
-            DamageSource::Melee => {
-                // Critical hit
-                let mut critdamage = 0.0;
-                if rand::random() {
-                    critdamage = self.healthchange * 0.3;
-                }
-                // Block
-                if block {
-                    self.healthchange *= 1.0 - BLOCK_EFFICIENCY
-                }
-                // Armor
-                let damage_reduction = loadout.get_damage_reduction();
-                self.healthchange *= 1.0 - damage_reduction;
+    /// Mitigates the damage by the weaknesses and resistances of the target's
+    /// body and by its armor, returning how much of it was absorbed
+    pub fn modify_damage(
+        &mut self,
+        block: bool,
+        loadout: Option<&Loadout>,
+        body: Option<&Body>,
+    ) -> u32 {
+        let unmitigated = match self.crit {
+            Some(multiplier) if self.source != DamageSource::Healing => {
+                self.healthchange * multiplier
+            },
+            _ => self.healthchange,
+        };
+        let invincible = loadout.map_or(false, |loadout| loadout.get_damage_reduction() >= 1.0);
+        if self.true_damage && !invincible && self.source != DamageSource::Healing {
+            self.healthchange = unmitigated;
+            return 0;
+        }
+        let kind = match self.source {
+            DamageSource::Healing => None,
+            _ => self.kind,
+        };
+        if let (Some(kind), Some(body)) = (kind, body) {
+            self.healthchange *= 1.0 - body.damage_resistance(kind);
+        }
+
+        // Without a loadout, only critical hits modify the damage
+        let (damage_reduction, resistance) = if let Some(loadout) = loadout {
+            let damage_reduction =
+                penetrated_reduction(loadout.get_damage_reduction(), self.penetration);
+            let kind_resistance = match kind {
+                Some(kind) if DamageSource::RESISTIBLE.contains(&self.source) => {
+                    loadout.get_damage_resistance(kind)
+                },
+                _ => 0.0,
+            };
+            let resistance = 1.0
+                - (1.0 - self.source.resistance(block, damage_reduction)) * (1.0 - kind_resistance);
+            (damage_reduction, resistance)
+        } else {
+            (0.0, 0.0)
+        };
+        match (self.source, self.crit) {
+            (DamageSource::Melee, Some(multiplier)) => {
+                let critdamage = self.healthchange * (multiplier - 1.0);
+                // Block and armor
+                self.healthchange *= 1.0 - resistance;
 
                 // Critical damage applies after armor for melee
                 if (damage_reduction - 1.0).abs() > f32::EPSILON {
                     self.healthchange += critdamage;
                 }
             },
-            DamageSource::Projectile => {
-                // Critical hit
-                if rand::random() {
-                    self.healthchange *= 1.2;
-                }
-                // Block
-                if block {
-                    self.healthchange *= 1.0 - BLOCK_EFFICIENCY
-                }
-                // Armor
-                let damage_reduction = loadout.get_damage_reduction();
-                self.healthchange *= 1.0 - damage_reduction;
+            (DamageSource::Healing, _) | (_, None) => self.healthchange *= 1.0 - resistance,
+            (_, Some(multiplier)) => {
+                // Block and armor
+                self.healthchange *= multiplier * (1.0 - resistance);
             },
-            DamageSource::Explosion => {
-                // Block
-                if block {
-                    self.healthchange *= 1.0 - BLOCK_EFFICIENCY
-                }
-                // Armor
-                let damage_reduction = loadout.get_damage_reduction();
-                self.healthchange *= 1.0 - damage_reduction;
-            },
-            DamageSource::Shockwave => {
-                // Armor
-                let damage_reduction = loadout.get_damage_reduction();
-                self.healthchange *= 1.0 - damage_reduction;
-            },
-            DamageSource::Energy => {
-                // Armor
-                let damage_reduction = loadout.get_damage_reduction();
-                self.healthchange *= 1.0 - damage_reduction;
-            },
-            _ => {},
         }
+
+        (unmitigated.abs() - self.healthchange.abs()).max(0.0) as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{comp::Item, LoadoutBuilder};
+
+    #[test]
+    fn penetration_spares_invincible_and_weak_armor() {
+        assert!((penetrated_reduction(0.5, 0.4) - 0.3).abs() < f32::EPSILON);
+        assert!((penetrated_reduction(0.5, 2.0)).abs() < f32::EPSILON);
+        assert!((penetrated_reduction(1.0, 0.4) - 1.0).abs() < f32::EPSILON);
+        assert!((penetrated_reduction(-0.2, 0.4) + 0.2).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn true_damage_is_stopped_by_invincible_armor() {
+        let damage = Damage {
+            healthchange: -100.0,
+            source: DamageSource::Melee,
+            kind: None,
+            crit: None,
+            penetration: 0.0,
+            true_damage: true,
+        };
+
+        let mut unarmored = damage;
+        assert_eq!(unarmored.modify_damage(false, None, None), 0);
+        assert!((unarmored.healthchange + 100.0).abs() < f32::EPSILON);
+
+        let loadout = LoadoutBuilder::new()
+            .chest(Some(Item::new_from_asset_expect(
+                "common.items.debug.cultist_chest_blue",
+            )))
+            .build();
+        let mut invincible = damage;
+        assert_eq!(invincible.modify_damage(false, Some(&loadout), None), 100);
+        assert!(invincible.healthchange.abs() < f32::EPSILON);
     }
 }
@@ -8,12 +8,85 @@ pub struct Energy {
     maximum: u32,
     pub regen_rate: f32,
     pub last_change: Option<(i32, f64, EnergySource)>,
+    pub regen: EnergyRegen,
+    /// Seconds since energy was last spent, which holds back its recovery
+    pub since_spent: f32,
+    /// Whether the energy was spent down to nothing since the last tick, which
+    /// leaves the entity exhausted
+    pub ran_out: bool,
+    /// Energy recovered per second in the last tick, after all modifiers, for
+    /// showing in the HUD
+    pub current_regen: f32,
+    /// What energy was last spent on, which affects how quickly it recovers
+    pub spent_on: Option<EnergySource>,
+    /// Fraction of a point carried over between gradual changes
+    accumulated: f32,
+}
+
+/// How an entity's energy recovers on its own
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EnergyRegen {
+    /// Seconds after energy was spent before it starts recovering
+    pub delay: f32,
+    /// How quickly the recovery speeds up, in energy per second squared
+    pub accel: f32,
+    /// Fastest recovery, in energy per second
+    pub max_rate: f32,
+    /// Multiplier on the recovery while standing still
+    pub still_modifier: f32,
+    /// Multipliers on the recovery after energy was last spent on abilities,
+    /// climbing, gliding and swimming respectively
+    pub ability_modifier: f32,
+    pub climb_modifier: f32,
+    pub glide_modifier: f32,
+    pub swim_modifier: f32,
+}
+
+impl EnergyRegen {
+    /// Multiplier on the recovery after energy was last spent on `source`
+    pub fn source_modifier(&self, source: EnergySource) -> f32 {
+        match source {
+            EnergySource::Ability => self.ability_modifier,
+            EnergySource::Climb => self.climb_modifier,
+            EnergySource::Glide => self.glide_modifier,
+            EnergySource::Swim => self.swim_modifier,
+            _ => 1.0,
+        }
+    }
+}
+
+impl Default for EnergyRegen {
+    fn default() -> Self {
+        Self {
+            delay: 1.0,
+            accel: 10.0,
+            max_rate: 100.0,
+            still_modifier: 1.5,
+            ability_modifier: 1.0,
+            climb_modifier: 0.75,
+            glide_modifier: 0.75,
+            swim_modifier: 0.75,
+        }
+    }
+}
+
+/// How what a character is doing affects the recovery of their energy
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RegenMode {
+    /// Recovers, with the speed multiplied by the modifier
+    Recover(f32),
+    /// Doesn't recover, but keeps the speed the recovery had built up
+    Stall,
+    /// Doesn't recover, and the recovery starts from nothing again afterwards
+    Reset,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub enum EnergySource {
     Ability,
     Climb,
+    Glide,
+    Swim,
     LevelUp,
     HitEnemy,
     Regen,
@@ -21,6 +94,16 @@ pub enum EnergySource {
     Unknown,
 }
 
+impl EnergySource {
+    /// Whether spending energy from this source holds back its recovery
+    pub fn delays_regen(self) -> bool {
+        matches!(
+            self,
+            EnergySource::Ability | EnergySource::Climb | EnergySource::Glide | EnergySource::Swim
+        )
+    }
+}
+
 #[derive(Debug)]
 pub enum StatChangeError {
     Underflow,
@@ -34,6 +117,12 @@ impl Energy {
             maximum: amount,
             regen_rate: 0.0,
             last_change: None,
+            regen: EnergyRegen::default(),
+            since_spent: 0.0,
+            ran_out: false,
+            current_regen: 0.0,
+            spent_on: None,
+            accumulated: 0.0,
         }
     }
 
@@ -50,6 +139,52 @@ impl Energy {
     pub fn change_by(&mut self, amount: i32, cause: EnergySource) {
         self.current = ((self.current as i32 + amount).max(0) as u32).min(self.maximum);
         self.last_change = Some((amount, 0.0, cause));
+        if amount < 0 && cause.delays_regen() {
+            self.since_spent = 0.0;
+            self.regen_rate = 0.0;
+            self.ran_out |= self.current == 0;
+            self.spent_on = Some(cause);
+        }
+    }
+
+    /// Changes the energy gradually at `rate` per second over `dt` seconds,
+    /// carrying fractions of a point over to the next change
+    pub fn change_by_rate(&mut self, rate: f32, dt: f32, cause: EnergySource) {
+        self.accumulated += rate * dt;
+        let amount = self.accumulated.trunc();
+        self.accumulated -= amount;
+        if amount != 0.0 {
+            self.change_by(amount as i32, cause);
+        } else if rate < 0.0 && cause.delays_regen() {
+            self.since_spent = 0.0;
+            self.spent_on = Some(cause);
+        }
+    }
+
+    /// Whether the energy would recover if left alone, which it does once the
+    /// regen delay has passed and until it is full
+    pub fn can_regenerate(&self) -> bool {
+        self.since_spent >= self.regen.delay && self.current < self.maximum
+    }
+
+    /// Recovers energy over `dt` seconds once the regen delay has passed,
+    /// speeding up the longer it goes on, with the speed multiplied by
+    /// `modifier` and by the modifier for what the energy was last spent on
+    pub fn regenerate(&mut self, dt: f32, modifier: f32) {
+        if !self.can_regenerate() {
+            self.current_regen = 0.0;
+            return;
+        }
+        // Accounts for the acceleration over the tick, so that the recovery doesn't
+        // depend on the tick rate
+        let modifier = modifier
+            * self
+                .spent_on
+                .map_or(1.0, |source| self.regen.source_modifier(source));
+        let rate = (self.regen_rate + self.regen.accel * dt / 2.0) * modifier;
+        self.change_by_rate(rate, dt, EnergySource::Regen);
+        self.current_regen = rate;
+        self.regen_rate = (self.regen_rate + self.regen.accel * dt).min(self.regen.max_rate);
     }
 
     pub fn try_change_by(
@@ -76,3 +211,60 @@ impl Energy {
 impl Component for Energy {
     type Storage = FlaggedStorage<Self, IdvStorage<Self>>;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gradual_changes_carry_fractions_over() {
+        let mut energy = Energy::new(100);
+        for _ in 0..10 {
+            energy.change_by_rate(-5.0, 0.1, EnergySource::Ability);
+        }
+        assert_eq!(energy.current(), 95);
+        assert!(!energy.ran_out);
+
+        energy.change_by(-200, EnergySource::Ability);
+        assert_eq!(energy.current(), 0);
+        assert!(energy.ran_out);
+    }
+
+    #[test]
+    fn regen_waits_for_delay_and_scales_with_modifier() {
+        let mut energy = Energy::new(1000);
+        energy.change_by(-500, EnergySource::Ability);
+        energy.regenerate(0.5, 1.0);
+        assert_eq!(energy.current(), 500);
+        assert!(!energy.can_regenerate());
+
+        energy.since_spent = energy.regen.delay;
+        energy.regen_rate = 50.0;
+        let mut slowed = energy;
+        energy.regenerate(1.0, 1.0);
+        slowed.regenerate(1.0, 0.5);
+        assert_eq!(energy.current(), 555);
+        assert_eq!(slowed.current(), 527);
+        assert!((energy.current_regen - 2.0 * slowed.current_regen).abs() < 0.001);
+    }
+
+    #[test]
+    fn regen_depends_on_what_energy_was_spent_on() {
+        let mut after_ability = Energy::new(1000);
+        after_ability.change_by(-500, EnergySource::Ability);
+        let mut after_glide = Energy::new(1000);
+        after_glide.change_by_rate(-500.0, 1.0, EnergySource::Glide);
+        assert_eq!(after_glide.spent_on, Some(EnergySource::Glide));
+
+        after_ability.since_spent = after_ability.regen.delay;
+        after_ability.regenerate(1.0, 1.0);
+        after_glide.since_spent = after_glide.regen.delay;
+        after_glide.regenerate(1.0, 1.0);
+        assert!(
+            (after_glide.current_regen
+                - after_ability.current_regen * after_glide.regen.glide_modifier)
+                .abs()
+                < 0.001
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use crate::comp::DamageKind;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -27,6 +28,37 @@ impl Armor {
 #[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Stats {
     protection: Protection,
+    #[serde(default)]
+    resistances: Resistances,
+    /// Fraction added to the speed at which the wearer recovers energy
+    #[serde(default)]
+    energy_regen: f32,
+}
+
+/// Portion of each kind of damage that a piece of armor stops on top of its
+/// protection. Negative values make the wearer more vulnerable instead.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Resistances {
+    pub slashing: f32,
+    pub piercing: f32,
+    pub crushing: f32,
+    pub fire: f32,
+    pub frost: f32,
+    pub poison: f32,
+}
+
+impl Resistances {
+    pub fn get(&self, kind: DamageKind) -> f32 {
+        match kind {
+            DamageKind::Slashing => self.slashing,
+            DamageKind::Piercing => self.piercing,
+            DamageKind::Crushing => self.crushing,
+            DamageKind::Fire => self.fire,
+            DamageKind::Frost => self.frost,
+            DamageKind::Poison => self.poison,
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
@@ -43,4 +75,8 @@ pub struct Armor {
 
 impl Armor {
     pub fn get_protection(&self) -> Protection { self.stats.protection }
+
+    pub fn get_resistances(&self) -> Resistances { self.stats.resistances }
+
+    pub fn get_energy_regen(&self) -> f32 { self.stats.energy_regen }
 }
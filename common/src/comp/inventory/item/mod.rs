@@ -6,6 +6,7 @@ pub use tool::{Hands, Tool, ToolCategory, ToolKind};
 
 use crate::{
     assets::{self, Asset, Error},
+    comp::projectile,
     effect::Effect,
     lottery::Lottery,
     terrain::{Block, SpriteKind},
@@ -28,6 +29,7 @@ pub enum Throwable {
     Bomb,
     TrainingDummy,
     Firework(Reagent),
+    Potion,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -42,6 +44,16 @@ pub enum Reagent {
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Utility {
     Collar,
+    Key,
+    Coins,
+    Cart,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Instrument {
+    Lute,
+    Drum,
+    Flute,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -62,7 +74,7 @@ impl Lantern {
 pub struct Glider {
     pub kind: String,
 }
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Copy)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Copy)]
 pub enum Quality {
     Low,       // Grey
     Common,    // UI Main Color
@@ -87,13 +99,35 @@ pub enum ItemKind {
     },
     Throwable {
         kind: Throwable,
+        /// Effects applied when the thrown item hits something. Throwables
+        /// without any are dropped into the world as objects instead.
+        #[serde(default)]
+        on_impact: Vec<projectile::Effect>,
+    },
+    /// Shot by bows, which use up one for each arrow they shoot
+    Ammo {
+        kind: String,
+        /// Multiplier for the damage of the arrows it is shot as
+        damage: f32,
+        /// Effects added to those of the arrow when it hits an entity
+        #[serde(default)]
+        on_hit: Vec<projectile::Effect>,
     },
     Utility {
         kind: Utility,
     },
+    /// Played by characters, who perform with it until they move away
+    Instrument {
+        kind: Instrument,
+    },
     Ingredient {
         kind: String,
     },
+    /// Recolors a piece of armor it is used on
+    Dye {
+        kind: String,
+        color: Rgb<u8>,
+    },
 }
 
 pub type ItemId = AtomicCell<Option<NonZeroU64>>;
@@ -126,6 +160,12 @@ pub struct Item {
     /// amount is hidden because it needs to maintain the invariant that only
     /// stackable items can have > 1 amounts.
     amount: NonZeroU32,
+    /// Color the item was dyed with, used to tint its model
+    #[serde(default)]
+    dye: Option<Rgb<u8>>,
+    /// Item whose look is shown in place of this item's own look when worn
+    #[serde(default)]
+    appearance: Option<Arc<ItemDef>>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -143,11 +183,15 @@ impl PartialEq for ItemDef {
 }
 
 impl ItemDef {
+    pub fn item_definition_id(&self) -> &str { &self.item_definition_id }
+
     pub fn is_stackable(&self) -> bool {
         matches!(self.kind, ItemKind::Consumable { .. }
             | ItemKind::Ingredient { .. }
             | ItemKind::Throwable { .. }
-            | ItemKind::Utility { .. })
+            | ItemKind::Ammo { .. }
+            | ItemKind::Utility { .. }
+            | ItemKind::Dye { .. })
     }
 }
 
@@ -185,6 +229,8 @@ impl Item {
             item_id: Arc::new(AtomicCell::new(None)),
             item_def: inner_item,
             amount: NonZeroU32::new(1).unwrap(),
+            dye: None,
+            appearance: None,
         }
     }
 
@@ -216,7 +262,13 @@ impl Item {
     }
 
     /// Duplicates an item, creating an exact copy but with a new item ID
-    pub fn duplicate(&self) -> Self { Item::new(Arc::clone(&self.item_def)) }
+    pub fn duplicate(&self) -> Self {
+        Item {
+            dye: self.dye,
+            appearance: self.appearance.clone(),
+            ..Item::new(Arc::clone(&self.item_def))
+        }
+    }
 
     /// FIXME: HACK: In order to set the entity ID asynchronously, we currently
     /// start it at None, and then atomically set it when it's saved for the
@@ -296,12 +348,43 @@ impl Item {
 
     pub fn quality(&self) -> Quality { self.item_def.quality }
 
+    pub fn dye(&self) -> Option<Rgb<u8>> { self.dye }
+
+    pub fn set_dye(&mut self, dye: Option<Rgb<u8>>) { self.dye = dye; }
+
+    pub fn appearance(&self) -> Option<&ItemDef> { self.appearance.as_deref() }
+
+    /// Makes the item look like another item when worn, returning an item with
+    /// the look it had before if its appearance was already overridden
+    pub fn set_appearance(&mut self, appearance: Option<Item>) -> Option<Item> {
+        std::mem::replace(&mut self.appearance, appearance.map(|item| item.item_def)).map(Item::new)
+    }
+
+    /// Kind of the item whose look is shown when this item is worn, which is
+    /// the item itself unless its appearance has been overridden
+    pub fn look(&self) -> &ItemKind {
+        self.appearance
+            .as_ref()
+            .map_or(&self.item_def.kind, |appearance| &appearance.kind)
+    }
+
+    /// Whether the look of this item can be replaced with the look of `other`,
+    /// which is only the case for armor worn in the same slot
+    pub fn can_look_like(&self, other: &Item) -> bool {
+        match (self.kind(), other.kind()) {
+            (ItemKind::Armor(armor), ItemKind::Armor(other)) => {
+                std::mem::discriminant(&armor.kind) == std::mem::discriminant(&other.kind)
+            },
+            _ => false,
+        }
+    }
+
     pub fn try_reclaim_from_block(block: Block) -> Option<Self> {
         let chosen;
         let mut rng = rand::thread_rng();
         Some(Item::new_from_asset_expect(match block.get_sprite()? {
             SpriteKind::Apple => "common.items.food.apple",
-            SpriteKind::Mushroom => "common.items.food.mushroom",
+            SpriteKind::Mushroom | SpriteKind::GlowMushroom => "common.items.food.mushroom",
             SpriteKind::Velorite => "common.items.ore.velorite",
             SpriteKind::VeloriteFrag => "common.items.ore.veloritefrag",
             SpriteKind::BlueFlower => "common.items.flowers.blue",
@@ -326,7 +409,7 @@ impl Item {
                 });
                 chosen.choose()
             },
-            SpriteKind::Crate => {
+            SpriteKind::Crate | SpriteKind::Pot => {
                 chosen = Lottery::<String>::load_expect(match rng.gen_range(0, 4) {
                     0 => "common.loot_tables.loot_table_crafting",
                     _ => "common.loot_tables.loot_table_food",
@@ -337,6 +420,7 @@ impl Item {
             SpriteKind::Stones => "common.items.crafting_ing.stones",
             SpriteKind::Twigs => "common.items.crafting_ing.twigs",
             SpriteKind::ShinyGem => "common.items.crafting_ing.shiny_gem",
+            SpriteKind::Key => "common.items.utility.key",
             _ => return None,
         }))
     }
@@ -2,8 +2,13 @@
 // version in voxygen\src\meta.rs in order to reset save files to being empty
 
 use crate::{
-    comp::{body::object, projectile, Body, CharacterAbility, Gravity, LightEmitter, Projectile},
-    states::combo_melee,
+    comp::{
+        body::{object, quadruped_medium},
+        damage::{OnHitEffect, CRIT_CHANCE, CRIT_MULTIPLIER},
+        projectile, Body, BuffData, BuffKind, CharacterAbility, DamageKind, Gravity, LightEmitter,
+        Projectile,
+    },
+    states::{cast_spell, combo_melee, dual_strike},
     Explosion,
 };
 use serde::{Deserialize, Serialize};
@@ -43,6 +48,17 @@ impl ToolKind {
             ToolKind::Empty => Hands::OneHand,
         }
     }
+
+    /// Kind of damage dealt when striking with the tool
+    pub fn damage_kind(&self) -> DamageKind {
+        match self {
+            ToolKind::Sword(_) | ToolKind::Axe(_) => DamageKind::Slashing,
+            ToolKind::Bow(_) | ToolKind::Dagger(_) => DamageKind::Piercing,
+            ToolKind::Staff(_) => DamageKind::Fire,
+            ToolKind::Sceptre(_) => DamageKind::Poison,
+            _ => DamageKind::Crushing,
+        }
+    }
 }
 
 pub enum Hands {
@@ -89,12 +105,34 @@ impl From<&ToolKind> for ToolCategory {
 pub struct Stats {
     equip_time_millis: u32,
     power: f32,
+    #[serde(default = "default_crit_chance")]
+    crit_chance: f32,
+    #[serde(default = "default_crit_multiplier")]
+    crit_multiplier: f32,
+    /// Fraction of blocked damage sent back at the attacker
+    #[serde(default)]
+    reflect_fraction: f32,
+    /// Share of the damage reduction from the target's armor that attacks
+    /// with the weapon ignore
+    #[serde(default)]
+    armor_penetration: f32,
+    /// Whether the weapon is made to be wielded together with another such
+    /// weapon in the off hand
+    #[serde(default)]
+    dual_wield: bool,
 }
 
+fn default_crit_chance() -> f32 { CRIT_CHANCE }
+
+fn default_crit_multiplier() -> f32 { CRIT_MULTIPLIER }
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Tool {
     pub kind: ToolKind,
     pub stats: Stats,
+    /// Effects triggered when attacks with this weapon deal damage
+    #[serde(default)]
+    pub on_hit: Vec<OnHitEffect>,
     // TODO: item specific abilities
 }
 
@@ -105,7 +143,13 @@ impl Tool {
             stats: Stats {
                 equip_time_millis: 0,
                 power: 1.00,
+                crit_chance: CRIT_CHANCE,
+                crit_multiplier: CRIT_MULTIPLIER,
+                reflect_fraction: 0.0,
+                armor_penetration: 0.0,
+                dual_wield: false,
             },
+            on_hit: Vec::new(),
         }
     }
 
@@ -116,6 +160,114 @@ impl Tool {
         Duration::from_millis(self.stats.equip_time_millis as u64)
     }
 
+    pub fn crit_chance(&self) -> f32 { self.stats.crit_chance }
+
+    pub fn crit_multiplier(&self) -> f32 { self.stats.crit_multiplier }
+
+    pub fn reflect_fraction(&self) -> f32 { self.stats.reflect_fraction }
+
+    pub fn armor_penetration(&self) -> f32 { self.stats.armor_penetration }
+
+    /// Whether the tool can be wielded together with another one in the off
+    /// hand
+    pub fn can_dual_wield(&self) -> bool {
+        self.stats.dual_wield && matches!(self.kind.hands(), Hands::OneHand)
+    }
+
+    /// The swing made with this tool while dual wielding
+    pub fn strike(&self) -> dual_strike::Strike {
+        match &self.kind {
+            ToolKind::Sword(_) => dual_strike::Strike {
+                base_damage: (70.0 * self.base_power()) as u32,
+                knockback: 4.0,
+                range: 3.5,
+                angle: 30.0,
+                buildup_duration: Duration::from_millis(200),
+                swing_duration: Duration::from_millis(100),
+                recover_duration: Duration::from_millis(250),
+            },
+            _ => dual_strike::Strike {
+                base_damage: (45.0 * self.base_power()) as u32,
+                knockback: 0.0,
+                range: 3.0,
+                angle: 20.0,
+                buildup_duration: Duration::from_millis(100),
+                swing_duration: Duration::from_millis(80),
+                recover_duration: Duration::from_millis(150),
+            },
+        }
+    }
+
+    /// The alternating attack made with a tool in each hand
+    pub fn dual_strike(main: &Tool, off: &Tool) -> CharacterAbility {
+        CharacterAbility::DualStrike {
+            energy_cost: 50,
+            main_strike: main.strike(),
+            // The off hand is weaker, but just as quick
+            off_strike: dual_strike::Strike {
+                base_damage: off.strike().base_damage * 4 / 5,
+                ..off.strike()
+            },
+            speed_increase: 0.1,
+            max_speed_increase: 0.6,
+            is_interruptible: true,
+        }
+    }
+
+    /// The abilities that can be chosen for each ability slot of this tool,
+    /// the first of which is the default
+    pub fn get_ability_options(&self) -> Vec<Vec<CharacterAbility>> {
+        let mut options = self
+            .get_abilities()
+            .into_iter()
+            .map(|ability| vec![ability])
+            .collect::<Vec<_>>();
+        for (slot, ability) in self.get_alternative_abilities() {
+            if let Some(slot) = options.get_mut(slot) {
+                slot.push(ability);
+            }
+        }
+        options
+    }
+
+    /// Abilities that can replace the default ability in a slot, paired with
+    /// the index of that slot
+    fn get_alternative_abilities(&self) -> Vec<(usize, CharacterAbility)> {
+        use CharacterAbility::*;
+        use ToolKind::*;
+
+        match &self.kind {
+            Sword(_) => vec![(2, LeapMelee {
+                energy_cost: 450,
+                buildup_duration: Duration::from_millis(300),
+                movement_duration: Duration::from_millis(300),
+                swing_duration: Duration::from_millis(150),
+                recover_duration: Duration::from_millis(300),
+                base_damage: (200.0 * self.base_power()) as u32,
+                knockback: 10.0,
+                range: 4.0,
+                max_angle: 40.0,
+                forward_leap_strength: 24.0,
+                vertical_leap_strength: 6.0,
+            })],
+            Axe(_) => vec![(2, SpinMelee {
+                buildup_duration: Duration::from_millis(400),
+                swing_duration: Duration::from_millis(400),
+                recover_duration: Duration::from_millis(600),
+                base_damage: (160.0 * self.base_power()) as u32,
+                knockback: 16.0,
+                range: 4.0,
+                energy_cost: 300,
+                is_infinite: false,
+                is_helicopter: false,
+                is_interruptible: true,
+                forward_speed: 0.6,
+                num_spins: 2,
+            })],
+            _ => Vec::new(),
+        }
+    }
+
     pub fn get_abilities(&self) -> Vec<CharacterAbility> {
         use CharacterAbility::*;
         use ToolKind::*;
@@ -294,9 +446,15 @@ impl Tool {
                     prepare_duration: Duration::from_millis(100),
                     recover_duration: Duration::from_millis(400),
                     projectile: Projectile {
-                        hit_solid: vec![projectile::Effect::Stick],
+                        hit_solid: vec![
+                            projectile::Effect::Stick,
+                            projectile::Effect::Recover(projectile::ARROW_ITEM.to_string()),
+                        ],
                         hit_entity: vec![
-                            projectile::Effect::Damage((-40.0 * self.base_power()) as i32),
+                            projectile::Effect::Damage(
+                                (-40.0 * self.base_power()) as i32,
+                                DamageKind::Piercing,
+                            ),
                             projectile::Effect::Knockback(10.0),
                             projectile::Effect::RewardEnergy(50),
                             projectile::Effect::Vanish,
@@ -304,6 +462,9 @@ impl Tool {
                         time_left: Duration::from_secs(15),
                         owner: None,
                         ignore_group: true,
+                        drag: projectile::ARROW_DRAG,
+                        penetration: 0,
+                        hit_entities: Vec::new(),
                     },
                     projectile_body: Body::Object(object::Body::Arrow),
                     projectile_light: None,
@@ -334,9 +495,15 @@ impl Tool {
                     recover_duration: Duration::from_millis(800),
                     leap: Some(10.0),
                     projectile: Projectile {
-                        hit_solid: vec![projectile::Effect::Stick],
+                        hit_solid: vec![
+                            projectile::Effect::Stick,
+                            projectile::Effect::Recover(projectile::ARROW_ITEM.to_string()),
+                        ],
                         hit_entity: vec![
-                            projectile::Effect::Damage((-40.0 * self.base_power()) as i32),
+                            projectile::Effect::Damage(
+                                (-40.0 * self.base_power()) as i32,
+                                DamageKind::Piercing,
+                            ),
                             projectile::Effect::Knockback(10.0),
                             projectile::Effect::RewardEnergy(50),
                             projectile::Effect::Vanish,
@@ -344,6 +511,9 @@ impl Tool {
                         time_left: Duration::from_secs(15),
                         owner: None,
                         ignore_group: true,
+                        drag: projectile::ARROW_DRAG,
+                        penetration: 0,
+                        hit_entities: Vec::new(),
                     },
                     projectile_body: Body::Object(object::Body::Arrow),
                     projectile_light: None,
@@ -409,6 +579,9 @@ impl Tool {
                         time_left: Duration::from_secs(20),
                         owner: None,
                         ignore_group: true,
+                        drag: projectile::DEFAULT_DRAG,
+                        penetration: 0,
+                        hit_entities: Vec::new(),
                     },
                     projectile_body: Body::Object(object::Body::BoltNature),
                     projectile_light: Some(LightEmitter {
@@ -418,6 +591,22 @@ impl Tool {
                     projectile_gravity: Some(Gravity(0.5)),
                     projectile_speed: 40.0,
                 },
+                CastSpell {
+                    energy_cost: 800,
+                    cast_duration: Duration::from_millis(1500),
+                    recover_duration: Duration::from_millis(500),
+                    interrupt_threshold: 150,
+                    move_efficiency: 0.3,
+                    payload: cast_spell::SpellPayload::Summon {
+                        body: Body::QuadrupedMedium(quadruped_medium::Body {
+                            species: quadruped_medium::Species::Wolf,
+                            body_type: quadruped_medium::BodyType::Male,
+                        }),
+                        count: 2,
+                        duration: Duration::from_secs(30),
+                        max_minions: 4,
+                    },
+                },
             ],
             Staff(_) => vec![
                 BasicRanged {
@@ -453,6 +642,9 @@ impl Tool {
                         time_left: Duration::from_secs(20),
                         owner: None,
                         ignore_group: true,
+                        drag: projectile::DEFAULT_DRAG,
+                        penetration: 0,
+                        hit_entities: Vec::new(),
                     },
                     projectile_body: Body::Object(object::Body::BoltFire),
                     projectile_light: Some(LightEmitter {
@@ -488,6 +680,7 @@ impl Tool {
                     shockwave_speed: 20.0,
                     shockwave_duration: Duration::from_millis(500),
                     requires_ground: false,
+                    friendly_fire: false,
                     move_efficiency: 0.1,
                 },
             ],
@@ -502,9 +695,50 @@ impl Tool {
                     max_angle: 120.0,
                 },
                 BasicBlock,
+                CastSpell {
+                    energy_cost: 300,
+                    cast_duration: Duration::from_millis(300),
+                    recover_duration: Duration::from_millis(400),
+                    interrupt_threshold: 100,
+                    move_efficiency: 0.5,
+                    payload: cast_spell::SpellPayload::SelfBuff {
+                        kind: BuffKind::Taunting,
+                        data: BuffData {
+                            strength: 3.0,
+                            duration: Some(Duration::from_secs(10)),
+                        },
+                    },
+                },
             ],
             NpcWeapon(kind) => {
-                if kind == "StoneGolemsFist" {
+                if kind == "ColossusFists" {
+                    vec![
+                        BasicMelee {
+                            energy_cost: 0,
+                            buildup_duration: Duration::from_millis(1200),
+                            recover_duration: Duration::from_millis(600),
+                            knockback: 40.0,
+                            base_healthchange: -400,
+                            range: 9.0,
+                            max_angle: 60.0,
+                        },
+                        Shockwave {
+                            energy_cost: 0,
+                            buildup_duration: Duration::from_millis(1800),
+                            swing_duration: Duration::from_millis(300),
+                            recover_duration: Duration::from_millis(1200),
+                            damage: 600,
+                            knockback: 30.0,
+                            shockwave_angle: 360.0,
+                            shockwave_vertical_angle: 15.0,
+                            shockwave_speed: 15.0,
+                            shockwave_duration: Duration::from_millis(2500),
+                            requires_ground: true,
+                            friendly_fire: false,
+                            move_efficiency: 0.0,
+                        },
+                    ]
+                } else if kind == "StoneGolemsFist" {
                     vec![
                         BasicMelee {
                             energy_cost: 0,
@@ -527,9 +761,34 @@ impl Tool {
                             shockwave_speed: 20.0,
                             shockwave_duration: Duration::from_millis(2000),
                             requires_ground: true,
+                            friendly_fire: false,
                             move_efficiency: 0.05,
                         },
                     ]
+                } else if kind == "BeastHorns" {
+                    vec![
+                        BasicMelee {
+                            energy_cost: 0,
+                            buildup_duration: Duration::from_millis(400),
+                            recover_duration: Duration::from_millis(300),
+                            knockback: 15.0,
+                            base_healthchange: -120,
+                            range: 3.5,
+                            max_angle: 60.0,
+                        },
+                        Charge {
+                            energy_cost: 0,
+                            energy_drain: 100,
+                            buildup_duration: Duration::from_millis(700),
+                            charge_duration: Duration::from_millis(2000),
+                            recover_duration: Duration::from_millis(500),
+                            stagger_duration: Duration::from_millis(2000),
+                            forward_speed: 3.0,
+                            damage: 250,
+                            knockback: 30.0,
+                            radius: 1.5,
+                        },
+                    ]
                 } else if kind == "BeastClaws" {
                     vec![BasicMelee {
                         energy_cost: 0,
@@ -540,6 +799,180 @@ impl Tool {
                         range: 5.0,
                         max_angle: 120.0,
                     }]
+                } else if kind == "FrostFangs" {
+                    vec![BasicMelee {
+                        energy_cost: 0,
+                        buildup_duration: Duration::from_millis(0),
+                        recover_duration: Duration::from_millis(400),
+                        knockback: 0.0,
+                        base_healthchange: -40,
+                        range: 3.5,
+                        max_angle: 15.0,
+                    }]
+                } else if kind == "SpiderFangs" {
+                    vec![BasicMelee {
+                        energy_cost: 0,
+                        buildup_duration: Duration::from_millis(200),
+                        recover_duration: Duration::from_millis(300),
+                        knockback: 0.0,
+                        base_healthchange: -30,
+                        range: 2.5,
+                        max_angle: 30.0,
+                    }]
+                } else if kind == "ScorpionStinger" {
+                    vec![
+                        BasicMelee {
+                            energy_cost: 0,
+                            buildup_duration: Duration::from_millis(200),
+                            recover_duration: Duration::from_millis(300),
+                            knockback: 5.0,
+                            base_healthchange: -25,
+                            range: 2.5,
+                            max_angle: 60.0,
+                        },
+                        BasicMelee {
+                            energy_cost: 0,
+                            buildup_duration: Duration::from_millis(600),
+                            recover_duration: Duration::from_millis(400),
+                            knockback: 0.0,
+                            base_healthchange: -50,
+                            range: 3.5,
+                            max_angle: 20.0,
+                        },
+                    ]
+                } else if kind == "DragonJaws" {
+                    vec![
+                        BasicMelee {
+                            energy_cost: 0,
+                            buildup_duration: Duration::from_millis(600),
+                            recover_duration: Duration::from_millis(500),
+                            knockback: 20.0,
+                            base_healthchange: -250,
+                            range: 6.0,
+                            max_angle: 45.0,
+                        },
+                        BasicBeam {
+                            buildup_duration: Duration::from_millis(800),
+                            recover_duration: Duration::from_millis(600),
+                            beam_duration: Duration::from_millis(750),
+                            base_hps: 0,
+                            base_dps: 200,
+                            tick_rate: 3.0,
+                            range: 25.0,
+                            max_angle: 20.0,
+                            lifesteal_eff: 0.0,
+                            energy_regen: 0,
+                            energy_cost: 0,
+                            energy_drain: 0,
+                        },
+                    ]
+                } else if kind == "Ballista" {
+                    vec![BasicRanged {
+                        energy_cost: 0,
+                        holdable: false,
+                        prepare_duration: Duration::from_millis(1500),
+                        recover_duration: Duration::from_millis(1200),
+                        projectile: Projectile {
+                            hit_solid: vec![projectile::Effect::Stick],
+                            hit_entity: vec![
+                                projectile::Effect::Damage(-150, DamageKind::Piercing),
+                                projectile::Effect::Knockback(20.0),
+                            ],
+                            time_left: Duration::from_secs(15),
+                            owner: None,
+                            ignore_group: true,
+                            drag: projectile::ARROW_DRAG,
+                            penetration: 2,
+                            hit_entities: Vec::new(),
+                        },
+                        projectile_body: Body::Object(object::Body::Arrow),
+                        projectile_light: None,
+                        projectile_gravity: Some(Gravity(0.1)),
+                        projectile_speed: 120.0,
+                    }]
+                } else if kind == "TurretCrystal" {
+                    vec![BasicRanged {
+                        energy_cost: 0,
+                        holdable: false,
+                        prepare_duration: Duration::from_millis(600),
+                        recover_duration: Duration::from_millis(600),
+                        projectile: Projectile {
+                            hit_solid: vec![projectile::Effect::Vanish],
+                            hit_entity: vec![
+                                projectile::Effect::Damage(-50, DamageKind::Fire),
+                                projectile::Effect::Vanish,
+                            ],
+                            time_left: Duration::from_secs(10),
+                            owner: None,
+                            ignore_group: true,
+                            drag: projectile::DEFAULT_DRAG,
+                            penetration: 0,
+                            hit_entities: Vec::new(),
+                        },
+                        projectile_body: Body::Object(object::Body::BoltFire),
+                        projectile_light: Some(LightEmitter {
+                            col: (0.85, 0.5, 0.11).into(),
+                            ..Default::default()
+                        }),
+                        projectile_gravity: None,
+                        projectile_speed: 60.0,
+                    }]
+                } else if kind == "StatueGaze" {
+                    vec![BasicRanged {
+                        energy_cost: 0,
+                        holdable: false,
+                        prepare_duration: Duration::from_millis(1200),
+                        recover_duration: Duration::from_millis(800),
+                        projectile: Projectile {
+                            hit_solid: vec![
+                                projectile::Effect::Explode(Explosion {
+                                    radius: 4.0,
+                                    max_damage: 120,
+                                    min_damage: 0,
+                                    max_heal: 0,
+                                    min_heal: 0,
+                                    terrain_destruction_power: 0.0,
+                                    energy_regen: 0,
+                                }),
+                                projectile::Effect::Vanish,
+                            ],
+                            hit_entity: vec![
+                                projectile::Effect::Explode(Explosion {
+                                    radius: 4.0,
+                                    max_damage: 120,
+                                    min_damage: 0,
+                                    max_heal: 0,
+                                    min_heal: 0,
+                                    terrain_destruction_power: 0.0,
+                                    energy_regen: 0,
+                                }),
+                                projectile::Effect::Vanish,
+                            ],
+                            time_left: Duration::from_secs(10),
+                            owner: None,
+                            ignore_group: true,
+                            drag: projectile::DEFAULT_DRAG,
+                            penetration: 0,
+                            hit_entities: Vec::new(),
+                        },
+                        projectile_body: Body::Object(object::Body::BoltFireBig),
+                        projectile_light: Some(LightEmitter {
+                            col: (0.5, 0.2, 1.0).into(),
+                            ..Default::default()
+                        }),
+                        projectile_gravity: None,
+                        projectile_speed: 40.0,
+                    }]
+                } else if kind == "BeetleMandibles" {
+                    vec![BasicMelee {
+                        energy_cost: 0,
+                        buildup_duration: Duration::from_millis(400),
+                        recover_duration: Duration::from_millis(400),
+                        knockback: 12.0,
+                        base_healthchange: -40,
+                        range: 2.5,
+                        max_angle: 45.0,
+                    }]
                 } else {
                     vec![BasicMelee {
                         energy_cost: 0,
@@ -577,6 +1010,9 @@ impl Tool {
                                 time_left: Duration::from_secs(10),
                                 owner: None,
                                 ignore_group: false,
+                                drag: projectile::ARROW_DRAG,
+                                penetration: 0,
+                                hit_entities: Vec::new(),
                             },
                             projectile_body: Body::Object(object::Body::ArrowSnake),
                             projectile_light: Some(LightEmitter {
@@ -3,7 +3,7 @@ pub mod slot;
 
 use crate::{comp::inventory::item::ItemDef, recipe::Recipe};
 use core::ops::Not;
-use item::Item;
+use item::{Item, ItemKind};
 use serde::{Deserialize, Serialize};
 use specs::{Component, FlaggedStorage, HashMapStorage};
 use specs_idvs::IdvStorage;
@@ -27,9 +27,13 @@ pub enum Error {
 
 #[allow(clippy::len_without_is_empty)] // TODO: Pending review in #587
 impl Inventory {
-    pub fn new_empty() -> Inventory {
+    pub fn new_empty() -> Inventory { Self::with_slots(36) }
+
+    /// An empty inventory with room for `slots` items, used for containers
+    /// that hold more or less than a character does
+    pub fn with_slots(slots: usize) -> Inventory {
         Inventory {
-            slots: vec![None; 36],
+            slots: vec![None; slots],
             amount: 0,
         }
     }
@@ -220,6 +224,23 @@ impl Inventory {
             .sum()
     }
 
+    /// Slot of the ammo that is shot next, which is the first in the inventory
+    pub fn ammo_slot(&self) -> Option<usize> {
+        self.slots
+            .iter()
+            .position(|slot| matches!(slot.as_ref().map(Item::kind), Some(ItemKind::Ammo { .. })))
+    }
+
+    /// How many shots of ammo there are in the inventory
+    pub fn ammo_count(&self) -> u32 {
+        self.slots
+            .iter()
+            .flatten()
+            .filter(|item| matches!(item.kind(), ItemKind::Ammo { .. }))
+            .map(Item::amount)
+            .sum()
+    }
+
     /// Determine whether the inventory contains the ingredients for a recipe.
     /// If it does, return a vector of numbers, where is number corresponds
     /// to an inventory slot, along with the number of items that need
@@ -115,6 +115,27 @@ fn loadout_replace(
     }
 }
 
+/// Get the item in an equipment slot, so it can be modified in place
+fn loadout_get_mut(equip_slot: EquipSlot, loadout: &mut Loadout) -> Option<&mut item::Item> {
+    match equip_slot {
+        EquipSlot::Armor(ArmorSlot::Head) => loadout.head.as_mut(),
+        EquipSlot::Armor(ArmorSlot::Neck) => loadout.neck.as_mut(),
+        EquipSlot::Armor(ArmorSlot::Shoulders) => loadout.shoulder.as_mut(),
+        EquipSlot::Armor(ArmorSlot::Chest) => loadout.chest.as_mut(),
+        EquipSlot::Armor(ArmorSlot::Hands) => loadout.hand.as_mut(),
+        EquipSlot::Armor(ArmorSlot::Ring) => loadout.ring.as_mut(),
+        EquipSlot::Armor(ArmorSlot::Back) => loadout.back.as_mut(),
+        EquipSlot::Armor(ArmorSlot::Belt) => loadout.belt.as_mut(),
+        EquipSlot::Armor(ArmorSlot::Legs) => loadout.pants.as_mut(),
+        EquipSlot::Armor(ArmorSlot::Feet) => loadout.foot.as_mut(),
+        EquipSlot::Armor(ArmorSlot::Tabard) => loadout.tabard.as_mut(),
+        EquipSlot::Lantern => loadout.lantern.as_mut(),
+        EquipSlot::Glider => loadout.glider.as_mut(),
+        EquipSlot::Mainhand => loadout.active_item.as_mut().map(|i| &mut i.item),
+        EquipSlot::Offhand => loadout.second_item.as_mut().map(|i| &mut i.item),
+    }
+}
+
 /// Insert an item into a loadout. If the specified slot is already occupied
 /// the old item is returned.
 #[must_use]
@@ -329,6 +350,78 @@ pub fn unequip(slot: EquipSlot, inventory: &mut Inventory, loadout: &mut Loadout
         .unwrap_none(); // Never fails
 }
 
+/// Dye the armor in an equipment slot with one of the dyes in an inventory
+/// slot. Returns whether the armor was dyed.
+pub fn dye(
+    dye_slot: usize,
+    equip_slot: EquipSlot,
+    inventory: &mut Inventory,
+    loadout: &mut Loadout,
+) -> bool {
+    let color = match inventory.get(dye_slot).map(|i| i.kind()) {
+        Some(item::ItemKind::Dye { color, .. }) => *color,
+        _ => return false,
+    };
+
+    match (equip_slot, loadout_get_mut(equip_slot, loadout)) {
+        (EquipSlot::Armor(_), Some(armor)) if armor.dye() != Some(color) => {
+            armor.set_dye(Some(color));
+            inventory.take(dye_slot);
+            true
+        },
+        _ => false,
+    }
+}
+
+/// Make the armor in an equipment slot look like the armor in an inventory
+/// slot, which is used up in the process. The look the armor had before is put
+/// back into the inventory. Returns whether the look was changed.
+pub fn set_appearance(
+    inv_slot: usize,
+    equip_slot: EquipSlot,
+    inventory: &mut Inventory,
+    loadout: &mut Loadout,
+) -> bool {
+    let armor = match loadout_get_mut(equip_slot, loadout) {
+        Some(armor) => armor,
+        None => return false,
+    };
+    if !inventory
+        .get(inv_slot)
+        .map_or(false, |look| armor.can_look_like(look))
+    {
+        return false;
+    }
+
+    if let Some(old_look) = armor.set_appearance(inventory.remove(inv_slot)) {
+        // The slot that was just emptied always has room for it
+        let _ = inventory.insert(inv_slot, old_look);
+    }
+    true
+}
+
+/// Restore the own look of the armor in an equipment slot, putting the look it
+/// had into the inventory. Leaves the look as it is if there is no room in the
+/// inventory. Returns whether the look was removed.
+pub fn clear_appearance(
+    equip_slot: EquipSlot,
+    inventory: &mut Inventory,
+    loadout: &mut Loadout,
+) -> bool {
+    let armor = match loadout_get_mut(equip_slot, loadout) {
+        Some(armor) => armor,
+        None => return false,
+    };
+    match armor.set_appearance(None).map(|look| inventory.push(look)) {
+        Some(Some(look)) => {
+            armor.set_appearance(Some(look));
+            false
+        },
+        Some(None) => true,
+        None => false,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -443,4 +536,81 @@ mod tests {
         // We should now have nothing equiped
         assert_eq!(None, loadout.active_item);
     }
+
+    #[test]
+    fn test_dye_armor() {
+        let red_dye = Item::new_from_asset_expect("common.items.utility.dye.red");
+
+        let mut inv = Inventory {
+            slots: vec![Some(red_dye)],
+            amount: 1,
+        };
+        inv.slots[0].as_mut().unwrap().set_amount(2).unwrap();
+
+        let mut loadout = LoadoutBuilder::new().defaults().build();
+
+        // Only armor can be dyed
+        assert!(!dye(0, EquipSlot::Mainhand, &mut inv, &mut loadout));
+
+        assert!(dye(
+            0,
+            EquipSlot::Armor(ArmorSlot::Feet),
+            &mut inv,
+            &mut loadout
+        ));
+        assert_eq!(
+            Some(vek::Rgb::new(165, 35, 35)),
+            loadout.foot.as_ref().and_then(|i| i.dye())
+        );
+        // One dye was used up
+        assert_eq!(Some(1), inv.slots[0].as_ref().map(|i| i.amount()));
+
+        // Dyeing with the same color again does nothing
+        assert!(!dye(
+            0,
+            EquipSlot::Armor(ArmorSlot::Feet),
+            &mut inv,
+            &mut loadout
+        ));
+        assert_eq!(Some(1), inv.slots[0].as_ref().map(|i| i.amount()));
+    }
+
+    #[test]
+    fn test_set_and_clear_appearance() {
+        let boots = Item::new_from_asset_expect("common.items.testing.test_boots");
+        let sword = Item::new_from_asset_expect("common.items.weapons.sword.zweihander_sword_0");
+
+        let mut inv = Inventory {
+            slots: vec![Some(sword), Some(boots.duplicate())],
+            amount: 2,
+        };
+
+        let mut loadout = LoadoutBuilder::new().defaults().build();
+
+        // Armor can only look like armor worn in the same slot
+        assert!(!set_appearance(
+            0,
+            EquipSlot::Armor(ArmorSlot::Feet),
+            &mut inv,
+            &mut loadout
+        ));
+
+        assert!(set_appearance(
+            1,
+            EquipSlot::Armor(ArmorSlot::Feet),
+            &mut inv,
+            &mut loadout
+        ));
+        assert_eq!(inv.slots[1], None);
+        assert_eq!(Some(boots.kind()), loadout.foot.as_ref().map(|i| i.look()));
+
+        // The look goes back into the inventory
+        assert!(clear_appearance(
+            EquipSlot::Armor(ArmorSlot::Feet),
+            &mut inv,
+            &mut loadout
+        ));
+        assert_eq!(inv.slots[1], Some(boots));
+        assert!(loadout.foot.as_ref().unwrap().appearance().is_none());
+    }
 }
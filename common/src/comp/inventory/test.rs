@@ -69,3 +69,26 @@ fn push_all_unique_empty() {
         "Pushing unique items into an empty inventory that didn't contain them didn't work!",
     );
 }
+
+/// Ammo is counted across all of its stacks, and the first stack in the
+/// inventory is shot first.
+#[test]
+fn ammo_count_and_slot() {
+    let mut arrows = Item::new_from_asset_expect("common.items.crafting_ing.arrow");
+    arrows.set_amount(20).unwrap();
+    let mut inv = Inventory {
+        slots: vec![
+            Some(Item::new_from_asset_expect("common.items.debug.boost")),
+            None,
+            Some(arrows),
+            Some(Item::new_from_asset_expect("common.items.ammo.fire_arrow")),
+        ],
+        amount: 3,
+    };
+    assert_eq!(inv.ammo_count(), 21);
+    assert_eq!(inv.ammo_slot(), Some(2));
+
+    inv.remove(2);
+    assert_eq!(inv.ammo_count(), 1);
+    assert_eq!(inv.ammo_slot(), Some(3));
+}
@@ -37,3 +37,13 @@ impl Component for WaypointArea {
 impl Default for WaypointArea {
     fn default() -> Self { Self(5.0) }
 }
+
+/// Places an entity inside a private copy of a dungeon. Entities only collide
+/// and fight with entities in the same instance, and entities without an
+/// instance live in the shared overworld.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Instance(pub u64);
+
+impl Component for Instance {
+    type Storage = IdvStorage<Self>;
+}
@@ -1,11 +1,15 @@
 mod ability;
 mod admin;
+pub mod affix;
 pub mod agent;
 pub mod beam;
 pub mod body;
+mod boss;
+pub mod breakdown;
 pub mod buff;
 mod character_state;
 pub mod chat;
+pub mod combat_log;
 mod controller;
 mod damage;
 mod energy;
@@ -24,28 +28,36 @@ mod stats;
 pub mod visual;
 
 // Reexports
-pub use ability::{CharacterAbility, CharacterAbilityType, ItemConfig, Loadout};
+pub use ability::{AbilitySelection, CharacterAbility, CharacterAbilityType, ItemConfig, Loadout};
 pub use admin::Admin;
-pub use agent::{Agent, Alignment};
+pub use affix::{Affix, Affixes, DungeonDifficulty};
+pub use agent::{Agent, Alignment, Threat};
 pub use beam::{Beam, BeamSegment};
 pub use body::{
-    biped_large, bird_medium, bird_small, dragon, fish_medium, fish_small, golem, humanoid, object,
-    quadruped_low, quadruped_medium, quadruped_small, theropod, AllBodies, Body, BodyData,
+    arthropod, biped_large, bird_medium, bird_small, colossus, dragon, fish_medium, fish_small,
+    golem, humanoid, object, quadruped_low, quadruped_medium, quadruped_small, theropod, turret,
+    AllBodies, Body, BodyData,
 };
+pub use boss::{ArenaShockwave, BossConfig, BossPhase, BossState, Enrage};
+pub use breakdown::StatBreakdown;
 pub use buff::{
-    Buff, BuffCategory, BuffChange, BuffData, BuffEffect, BuffId, BuffKind, BuffSource, Buffs,
-    ModifierKind,
+    Buff, BuffCategory, BuffChange, BuffData, BuffEffect, BuffId, BuffKind, BuffSource, BuffStack,
+    BuffStacking, Buffs, ModifierKind,
 };
-pub use character_state::{Attacking, CharacterState, StateUpdate};
+pub use character_state::{Attacking, CharacterState, Invulnerable, StateUpdate, Trampling};
 pub use chat::{
     ChatMode, ChatMsg, ChatType, Faction, SpeechBubble, SpeechBubbleType, UnresolvedChatMsg,
 };
+pub use combat_log::{CombatEvent, CombatEventKind, CombatLog};
 pub use controller::{
-    Climb, ControlAction, ControlEvent, Controller, ControllerInputs, GroupManip, Input,
-    InventoryManip, MountState, Mounting,
+    Climb, ControlAction, ControlEvent, Controller, ControllerInputs, GroupManip, Hitched, Input,
+    InventoryManip, MountState, Mounting, TradeAction,
 };
-pub use damage::{Damage, DamageSource};
-pub use energy::{Energy, EnergySource};
+pub use damage::{
+    armor_penetration, on_hit_effects, BaseDamage, CritStats, Damage, DamageKind, DamageSource,
+    OnHitEffect,
+};
+pub use energy::{Energy, EnergyRegen, EnergySource, RegenMode};
 pub use group::Group;
 pub use inputs::CanBuild;
 pub use inventory::{
@@ -54,7 +66,7 @@ pub use inventory::{
     slot, Inventory, InventoryUpdate, InventoryUpdateEvent, MAX_PICKUP_RANGE_SQR,
 };
 pub use last::Last;
-pub use location::{Waypoint, WaypointArea};
+pub use location::{Instance, Waypoint, WaypointArea};
 pub use misc::Object;
 pub use phys::{Collider, ForceUpdate, Gravity, Mass, Ori, PhysicsState, Pos, Scale, Sticky, Vel};
 pub use player::{Player, MAX_MOUNT_RANGE_SQR};
@@ -1,18 +1,45 @@
-use crate::{sync::Uid, Explosion};
+use crate::{
+    comp::{BuffData, BuffKind, DamageKind},
+    sync::Uid,
+    Explosion,
+};
 use serde::{Deserialize, Serialize};
 use specs::{Component, FlaggedStorage};
 use specs_idvs::IdvStorage;
 use std::time::Duration;
 
+/// Drag for projectiles that fly like anything else thrown through the air
+pub const DEFAULT_DRAG: f32 = 0.0125;
+/// Arrows are streamlined enough to keep their speed for longer
+pub const ARROW_DRAG: f32 = 0.005;
+/// What a spent arrow can be picked back up as
+pub const ARROW_ITEM: &str = "common.items.crafting_ing.arrow";
+/// Share of its damage a penetrating projectile keeps for each target it has
+/// already passed through
+pub const PENETRATION_FALLOFF: f32 = 0.7;
+/// Damage multiplier for projectiles that strike the head of their target
+pub const HEADSHOT_MULTIPLIER: f32 = 1.5;
+/// How far outside of the head a projectile may pass and still count as a
+/// headshot, as projectiles are only checked once per tick
+pub const HEADSHOT_LENIENCY: f32 = 0.15;
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum Effect {
-    Damage(i32),
+    Damage(i32, DamageKind),
     Knockback(f32),
     RewardEnergy(u32),
     Explode(Explosion),
     Vanish,
     Stick,
     Possess,
+    /// Leaves the projectile where it landed as an item that can be picked
+    /// back up
+    Recover(String),
+    /// Applies a buff to the entity hit, such as the burning of a fire arrow
+    Buff {
+        kind: BuffKind,
+        data: BuffData,
+    },
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -26,6 +53,42 @@ pub struct Projectile {
     /// Whether projectile collides with entities in the same group as its
     /// owner
     pub ignore_group: bool,
+    /// Share of velocity lost to air resistance, in the same units as the
+    /// friction used by physics
+    pub drag: f32,
+    /// How many more entities the projectile can pass through before its
+    /// entity effects are used up
+    pub penetration: u32,
+    /// Entities already hit, so that penetrating projectiles hit each of them
+    /// only once
+    pub hit_entities: Vec<Uid>,
+}
+
+impl Projectile {
+    /// Whether the projectile is an arrow, which players need ammo to shoot
+    pub fn is_arrow(&self) -> bool {
+        self.hit_solid
+            .iter()
+            .any(|effect| matches!(effect, Effect::Recover(item) if item == ARROW_ITEM))
+    }
+
+    /// Loads an arrow with ammo, scaling its damage by `damage` and adding the
+    /// effects the ammo has on whatever it hits. The arrow can be picked back
+    /// up as `item`, or not at all if there is none.
+    pub fn load(&mut self, item: Option<&str>, damage: f32, on_hit: &[Effect]) {
+        for effect in self.hit_entity.iter_mut() {
+            if let Effect::Damage(amount, _) = effect {
+                *amount = (*amount as f32 * damage) as i32;
+            }
+        }
+        self.hit_entity.extend(on_hit.iter().cloned());
+        self.hit_solid.retain(|effect| item.is_some() || !matches!(effect, Effect::Recover(_)));
+        for effect in self.hit_solid.iter_mut() {
+            if let (Effect::Recover(recovered), Some(item)) = (effect, item) {
+                *recovered = item.to_string();
+            }
+        }
+    }
 }
 
 impl Component for Projectile {
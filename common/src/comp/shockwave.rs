@@ -12,6 +12,10 @@ pub struct Properties {
     pub damage: u32,
     pub knockback: f32,
     pub requires_ground: bool,
+    /// Whether the shockwave also hits the owner's group
+    pub friendly_fire: bool,
+    /// Whether the shockwave ignores blocking, armor and resistances
+    pub true_damage: bool,
     pub duration: Duration,
     pub owner: Option<Uid>,
 }
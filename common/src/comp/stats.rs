@@ -13,12 +13,23 @@ use std::{error::Error, fmt};
 pub struct HealthChange {
     pub amount: i32,
     pub cause: HealthSource,
+    /// Whether the change was caused by a critical hit
+    pub crit: bool,
+    /// How much of the change was absorbed by resistances, armor and blocking
+    pub mitigated: u32,
+    /// How much damage went past the health that was left, for changes that
+    /// killed. This is filled in when the change is applied.
+    pub overkill: u32,
 }
 
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub enum HealthSource {
     Attack { by: Uid }, // TODO: Implement weapon
-    Projectile { owner: Option<Uid> },
+    Projectile {
+        owner: Option<Uid>,
+        /// Whether the projectile struck the head of its target
+        headshot: bool,
+    },
     Explosion { owner: Option<Uid> },
     Energy { owner: Option<Uid> },
     Buff { owner: Option<Uid> },
@@ -32,6 +43,22 @@ pub enum HealthSource {
     Unknown,
 }
 
+impl HealthSource {
+    /// The entity that dealt the damage, if it was dealt by one
+    pub fn attacker(self) -> Option<Uid> {
+        match self {
+            HealthSource::Attack { by }
+            | HealthSource::Projectile {
+                owner: Some(by), ..
+            }
+            | HealthSource::Explosion { owner: Some(by) }
+            | HealthSource::Energy { owner: Some(by) }
+            | HealthSource::Buff { owner: Some(by) } => Some(by),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 pub struct Health {
     base_max: u32,
@@ -61,13 +88,20 @@ impl Health {
         self.last_change = (0.0, HealthChange {
             amount: amount as i32 - self.current as i32,
             cause,
+            crit: false,
+            mitigated: 0,
+            overkill: 0,
         });
         self.current = amount;
     }
 
     pub fn change_by(&mut self, change: HealthChange) {
-        self.current = ((self.current as i32 + change.amount).max(0) as u32).min(self.maximum);
-        self.last_change = (0.0, change);
+        let current = self.current as i32 + change.amount;
+        self.current = (current.max(0) as u32).min(self.maximum);
+        self.last_change = (0.0, HealthChange {
+            overkill: (-current).max(0) as u32,
+            ..change
+        });
     }
 
     // This is private because max hp is based on the level
@@ -164,6 +198,15 @@ impl Stats {
         self.health
             .set_maximum(body.base_health() + body.base_health_increase() * self.level.amount);
     }
+
+    /// Like [`Stats::update_max_hp`], but scales the max hp by `factor`, e.g.
+    /// for animals that are not fully grown yet
+    pub fn update_max_hp_scaled(&mut self, body: Body, factor: f32) {
+        let max_hp = body.base_health() + body.base_health_increase() * self.level.amount;
+        let max_hp = ((max_hp as f32 * factor) as u32).max(1);
+        self.health.set_base_max(max_hp);
+        self.health.set_maximum(max_hp);
+    }
 }
 
 impl Stats {
@@ -196,6 +239,9 @@ impl Stats {
                 last_change: (0.0, HealthChange {
                     amount: 0,
                     cause: HealthSource::Revive,
+                    crit: false,
+                    mitigated: 0,
+                    overkill: 0,
                 }),
             },
             level: Level { amount: 1 },
@@ -232,6 +278,9 @@ impl Stats {
                 last_change: (0.0, HealthChange {
                     amount: 0,
                     cause: HealthSource::Revive,
+                    crit: false,
+                    mitigated: 0,
+                    overkill: 0,
                 }),
             },
             level: Level { amount: 1 },
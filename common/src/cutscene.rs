@@ -0,0 +1,142 @@
+//! Scripted sequences that play out key story beats of quests.
+//!
+//! A sequence is a list of steps loaded from the `common.cutscenes` assets.
+//! When a quest event happens to a player, the server plays the sequence set
+//! for that event in `common.cutscenes.triggers` step by step: it makes the
+//! NPC nearest to where the event took place walk around, act and talk, and
+//! tells the player's client where to move the camera and when to hold back
+//! their inputs.
+
+use crate::{
+    assets::{Asset, Ron},
+    comp::ControlAction,
+};
+use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
+use vek::*;
+
+/// Things that happen to players as they go about quests, which can start a
+/// sequence
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum QuestEvent {
+    /// The player took on a bounty at a bounty board
+    BountyAccepted,
+    /// The player killed an outlaw with a bounty on them
+    BountyClaimed,
+}
+
+/// Who performs a step of a sequence
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Actor {
+    /// The player the sequence is playing for
+    Player,
+    /// The NPC nearest to where the sequence takes place
+    Npc,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Emote {
+    Sit,
+    Dance,
+    Stand,
+    Wield,
+    Unwield,
+}
+
+impl Emote {
+    pub fn control_action(self) -> ControlAction {
+        match self {
+            Emote::Sit => ControlAction::Sit,
+            Emote::Dance => ControlAction::Dance,
+            Emote::Stand => ControlAction::Stand,
+            Emote::Wield => ControlAction::Wield,
+            Emote::Unwield => ControlAction::Unwield,
+        }
+    }
+}
+
+/// A step of a sequence. Positions are relative to where the sequence takes
+/// place.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum CutsceneStep {
+    /// Moves the camera to `pos`, looking at `focus`, over `duration` seconds
+    Camera {
+        pos: Vec3<f32>,
+        focus: Vec3<f32>,
+        duration: f32,
+    },
+    /// Sends the NPC walking to `pos`. The sequence carries on without waiting
+    /// for them to get there.
+    WalkTo(Vec3<f32>),
+    Emote(Actor, Emote),
+    /// Has the actor say a line, then gives the player `duration` seconds to
+    /// read it. Lines of NPCs are localization keys, since clients translate
+    /// what NPCs say.
+    Say {
+        actor: Actor,
+        line: String,
+        duration: f32,
+    },
+    /// Locks or unlocks the player's inputs. They are always unlocked once the
+    /// sequence ends.
+    LockInput(bool),
+    Wait(f32),
+}
+
+impl CutsceneStep {
+    /// Seconds to wait after the step before playing the next one
+    pub fn duration(&self) -> f32 {
+        match self {
+            CutsceneStep::Camera { duration, .. }
+            | CutsceneStep::Say { duration, .. }
+            | CutsceneStep::Wait(duration) => *duration,
+            CutsceneStep::WalkTo(_) | CutsceneStep::Emote(_, _) | CutsceneStep::LockInput(_) => 0.0,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Cutscene {
+    pub steps: Vec<CutsceneStep>,
+}
+
+impl Cutscene {
+    pub fn load_expect(specifier: &str) -> Self { Ron::<Self>::load_expect_cloned(specifier) }
+}
+
+/// Asset specifiers of the sequence played for each quest event, loaded from
+/// `common.cutscenes.triggers`
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct CutsceneTriggers(pub HashMap<QuestEvent, String>);
+
+impl CutsceneTriggers {
+    pub fn load_expect() -> Self { Ron::<Self>::load_expect_cloned("common.cutscenes.triggers") }
+
+    pub fn get(&self, event: QuestEvent) -> Option<&str> { self.0.get(&event).map(|s| s.as_str()) }
+}
+
+/// What the client has to do to play its part of a sequence
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum CutsceneEvent {
+    /// Move the camera to `pos`, looking at `focus`, over `duration` seconds
+    Camera {
+        pos: Vec3<f32>,
+        focus: Vec3<f32>,
+        duration: f32,
+    },
+    LockInput(bool),
+    /// The sequence is over, so the camera and inputs go back to the player
+    End,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn triggered_cutscenes_load() {
+        for specifier in CutsceneTriggers::load_expect().0.values() {
+            assert!(!Cutscene::load_expect(specifier).steps.is_empty());
+        }
+    }
+}
@@ -6,6 +6,11 @@ use serde::{Deserialize, Serialize};
 pub enum Effect {
     Health(comp::HealthChange),
     Xp(i64),
+    /// Removes up to `count` debuffs of the category
+    Dispel {
+        category: comp::BuffCategory,
+        count: u32,
+    },
 }
 
 impl Effect {
@@ -13,6 +18,7 @@ impl Effect {
         match self {
             Effect::Health(c) => format!("{:+} health", c.amount),
             Effect::Xp(n) => format!("{:+} exp", n),
+            Effect::Dispel { category, count } => format!("cures {} {:?} debuffs", count, category),
         }
     }
 }
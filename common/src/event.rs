@@ -1,11 +1,11 @@
 use crate::{character::CharacterId, comp, sync::Uid, util::Dir, Explosion};
 use comp::{
-    item::{Item, Reagent},
+    item::{Item, Reagent, ToolCategory},
     Ori, Pos,
 };
 use parking_lot::Mutex;
 use specs::Entity as EcsEntity;
-use std::{collections::VecDeque, ops::DerefMut};
+use std::{collections::VecDeque, ops::DerefMut, time::Duration};
 use vek::*;
 
 pub enum LocalEvent {
@@ -42,9 +42,48 @@ pub enum ServerEvent {
         entity: EcsEntity,
         cause: comp::HealthSource,
     },
+    /// A player in a group ran out of health and goes down instead of dying
+    Downed {
+        entity: EcsEntity,
+        cause: comp::HealthSource,
+    },
+    /// A downed player was revived by a group member
+    Revive {
+        target: Uid,
+        by: Uid,
+    },
     InventoryManip(EcsEntity, comp::InventoryManip),
     GroupManip(EcsEntity, comp::GroupManip),
     Respawn(EcsEntity),
+    /// A player reads the bounties posted on the board at the given position
+    ReadBountyBoard(EcsEntity, Vec3<i32>),
+    /// A player talks to the NPC with the given uid
+    Talk(EcsEntity, Uid),
+    /// A player trades a good with the merchant with the given uid
+    Trade {
+        entity: EcsEntity,
+        merchant: Uid,
+        good: usize,
+        action: comp::TradeAction,
+    },
+    /// Two pets of the same owner breed, producing offspring
+    Breed(EcsEntity, EcsEntity),
+    /// A player reeled in a fish, which is added to their inventory
+    CatchFish(EcsEntity),
+    /// A performer played a note on their instrument
+    PlayNote {
+        entity: EcsEntity,
+        instrument: comp::item::Instrument,
+        note: u8,
+    },
+    /// A player chooses which ability to use in an ability slot of a kind of
+    /// weapon
+    SelectAbility {
+        entity: EcsEntity,
+        tool: ToolCategory,
+        slot: usize,
+        index: usize,
+    },
     Shoot {
         entity: EcsEntity,
         dir: Dir,
@@ -54,6 +93,12 @@ pub enum ServerEvent {
         gravity: Option<comp::Gravity>,
         speed: f32,
     },
+    /// Takes one item out of the inventory `slot` and throws it in `dir`
+    Throw {
+        entity: EcsEntity,
+        slot: usize,
+        dir: Dir,
+    },
     Shockwave {
         properties: comp::shockwave::Properties,
         pos: Pos,
@@ -68,10 +113,32 @@ pub enum ServerEvent {
         pos: Pos,
         ori: Ori,
     },
+    /// Sets fire to the flammable block at the given position
+    Ignite(Vec3<i32>),
+    /// A projectile came to rest and is left lying around as an item
+    RecoverProjectile {
+        entity: EcsEntity,
+        item: String,
+    },
+    /// An attack hit the destructible sprite at the given position for some
+    /// damage
+    DamageSprite(Vec3<i32>, u32),
     LandOnGround {
         entity: EcsEntity,
         vel: Vec3<f32>,
     },
+    /// Something plunged into water at the given position and speed
+    Splash {
+        pos: Vec3<f32>,
+        speed: f32,
+    },
+    /// A melee attack landed hard at the given position
+    HeavyHit {
+        pos: Vec3<f32>,
+        attacker: Uid,
+        target: Uid,
+        magnitude: f32,
+    },
     EnableLantern(EcsEntity),
     DisableLantern(EcsEntity),
     Mount(EcsEntity, EcsEntity),
@@ -85,7 +152,14 @@ pub enum ServerEvent {
     },
     UpdateCharacterData {
         entity: EcsEntity,
-        components: (comp::Body, comp::Stats, comp::Inventory, comp::Loadout),
+        components: (
+            comp::Body,
+            comp::Stats,
+            comp::Inventory,
+            comp::Loadout,
+            comp::AbilitySelection,
+            comp::Buffs,
+        ),
     },
     ExitIngame {
         entity: EcsEntity,
@@ -99,6 +173,11 @@ pub enum ServerEvent {
         alignment: comp::Alignment,
         scale: comp::Scale,
         drop_item: Option<Item>,
+        boss: Option<comp::BossState>,
+        /// Id of the site whose market the NPC trades for, if it is a merchant
+        market: Option<u64>,
+        /// Id of the bounty on the NPC, if it is a wanted outlaw
+        bounty: Option<u64>,
     },
     CreateWaypoint(Vec3<f32>),
     ClientDisconnect(EcsEntity),
@@ -110,6 +189,29 @@ pub enum ServerEvent {
         entity: EcsEntity,
         buff_change: comp::BuffChange,
     },
+    /// A player asked to duel another, which starts the duel if the other
+    /// player already asked for it too
+    DuelRequest {
+        challenger: EcsEntity,
+        target: EcsEntity,
+    },
+    /// Summons `count` minions for `summoner`, which vanish after `duration`,
+    /// without letting it have more than `max_minions` at once
+    Summon {
+        summoner: EcsEntity,
+        body: comp::Body,
+        count: u32,
+        duration: Duration,
+        max_minions: u32,
+    },
+    /// An attack by `attacker` dealt `damage` to `target`, triggering the
+    /// attacker's on-hit effects
+    OnHit {
+        attacker: EcsEntity,
+        target: EcsEntity,
+        damage: u32,
+        effects: Vec<comp::OnHitEffect>,
+    },
 }
 
 pub struct EventBus<E> {
@@ -21,6 +21,16 @@ pub struct EntityInfo {
     pub scale: f32,
     pub level: Option<u32>,
     pub loot_drop: Option<Item>,
+    /// Direction stationary entities are mounted facing
+    pub facing: Option<Vec2<f32>>,
+    /// Asset specifier of the boss fight the entity leads, if it is a boss
+    pub boss: Option<String>,
+    /// Whether the entity patrols with a lit lantern at night
+    pub night_patrol: bool,
+    /// Id of the site whose market the entity trades for, if it is a merchant
+    pub market: Option<u64>,
+    /// Id of the bounty on the entity, if it is a wanted outlaw
+    pub bounty: Option<u64>,
 }
 
 impl EntityInfo {
@@ -38,6 +48,11 @@ impl EntityInfo {
             scale: 1.0,
             level: None,
             loot_drop: None,
+            facing: None,
+            boss: None,
+            night_patrol: false,
+            market: None,
+            bounty: None,
         }
     }
 
@@ -98,6 +113,31 @@ impl EntityInfo {
         self
     }
 
+    pub fn with_facing(mut self, facing: Vec2<f32>) -> Self {
+        self.facing = Some(facing);
+        self
+    }
+
+    pub fn with_boss(mut self, boss: impl Into<String>) -> Self {
+        self.boss = Some(boss.into());
+        self
+    }
+
+    pub fn with_night_patrol(mut self) -> Self {
+        self.night_patrol = true;
+        self
+    }
+
+    pub fn with_market(mut self, site: u64) -> Self {
+        self.market = Some(site);
+        self
+    }
+
+    pub fn with_bounty(mut self, bounty: u64) -> Self {
+        self.bounty = Some(bounty);
+        self
+    }
+
     pub fn with_level(mut self, level: u32) -> Self {
         self.level = Some(level);
         self
@@ -117,6 +157,9 @@ impl EntityInfo {
             Body::Dragon(body) => Some(get_npc_name(&NPC_NAMES.dragon, body.species)),
             Body::QuadrupedLow(body) => Some(get_npc_name(&NPC_NAMES.quadruped_low, body.species)),
             Body::Golem(body) => Some(get_npc_name(&NPC_NAMES.golem, body.species)),
+            Body::Colossus(body) => Some(get_npc_name(&NPC_NAMES.colossus, body.species)),
+            Body::Arthropod(body) => Some(get_npc_name(&NPC_NAMES.arthropod, body.species)),
+            Body::Turret(body) => Some(get_npc_name(&NPC_NAMES.turret, body.species)),
             Body::BipedLarge(body) => Some(get_npc_name(&NPC_NAMES.biped_large, body.species)),
             _ => None,
         }
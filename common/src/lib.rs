@@ -22,7 +22,9 @@ pub mod astar;
 pub mod character;
 pub mod clock;
 pub mod cmd;
+pub mod combat;
 pub mod comp;
+pub mod cutscene;
 pub mod effect;
 pub mod event;
 pub mod explosion;
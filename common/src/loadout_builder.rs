@@ -1,7 +1,7 @@
 use crate::comp::{
-    biped_large, golem,
+    arthropod, biped_large, golem,
     item::{Item, ItemKind},
-    Alignment, Body, CharacterAbility, ItemConfig, Loadout,
+    turret, Alignment, Body, CharacterAbility, ItemConfig, Loadout,
 };
 use rand::Rng;
 use std::time::Duration;
@@ -82,6 +82,36 @@ impl LoadoutBuilder {
                 },
                 _ => {},
             },
+            Body::Colossus(_) => {
+                main_tool = Some(Item::new_from_asset_expect(
+                    "common.items.npc_weapons.npcweapon.colossus_fists",
+                ));
+            },
+            Body::Dragon(_) => {
+                main_tool = Some(Item::new_from_asset_expect(
+                    "common.items.npc_weapons.npcweapon.dragon_jaws",
+                ));
+            },
+            Body::Arthropod(arthropod) => {
+                main_tool = Some(Item::new_from_asset_expect(match arthropod.species {
+                    arthropod::Species::Spider => "common.items.npc_weapons.npcweapon.spider_fangs",
+                    arthropod::Species::Scorpion => {
+                        "common.items.npc_weapons.npcweapon.scorpion_stinger"
+                    },
+                    arthropod::Species::Beetle => {
+                        "common.items.npc_weapons.npcweapon.beetle_mandibles"
+                    },
+                }));
+            },
+            Body::Turret(body) => {
+                main_tool = Some(Item::new_from_asset_expect(match body.species {
+                    turret::Species::Ballista => "common.items.npc_weapons.npcweapon.ballista",
+                    turret::Species::MagicTurret => {
+                        "common.items.npc_weapons.npcweapon.turret_crystal"
+                    },
+                    turret::Species::Statue => "common.items.npc_weapons.npcweapon.statue_gaze",
+                }));
+            },
             Body::BipedLarge(biped_large) => match (biped_large.species, biped_large.body_type) {
                 (biped_large::Species::Occultsaurok, _) => {
                     main_tool = Some(Item::new_from_asset_expect(
@@ -134,6 +164,11 @@ impl LoadoutBuilder {
                     ));
                 },
             },
+            Body::QuadrupedMedium(quadruped_medium) => {
+                if let Some(tool) = &quadruped_medium.stats().main_tool {
+                    main_tool = Some(Item::new_from_asset_expect(tool));
+                }
+            },
             Body::Humanoid(_) => {
                 if is_giant {
                     main_tool = Some(Item::new_from_asset_expect(
@@ -313,6 +348,23 @@ impl LoadoutBuilder {
                 },
                 _ => LoadoutBuilder::animal(body).build(),
             },
+            Body::Colossus(_) | Body::Arthropod(_) | Body::Turret(_) => Loadout {
+                active_item,
+                second_item: None,
+                shoulder: None,
+                chest: None,
+                belt: None,
+                hand: None,
+                pants: None,
+                foot: None,
+                back: None,
+                ring: None,
+                neck: None,
+                lantern: None,
+                glider: None,
+                head: None,
+                tabard: None,
+            },
             Body::BipedLarge(_) => Loadout {
                 active_item,
                 second_item: None,
@@ -62,4 +62,20 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_fishing_tables() {
+        for table in &["river", "lake", "ocean", "cold", "desert"] {
+            let test =
+                Lottery::<String>::load_expect(&format!("common.loot_tables.fishing.{}", table));
+
+            for (_, item_asset_specifier) in test.iter() {
+                assert!(
+                    Item::new_from_asset(item_asset_specifier).is_ok(),
+                    "Invalid fishing table item '{}'",
+                    item_asset_specifier
+                );
+            }
+        }
+    }
 }
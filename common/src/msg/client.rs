@@ -42,6 +42,9 @@ pub enum ClientType {
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ClientRegister {
     pub token_or_username: String,
+    /// Hashes of the asset packs the client uses in place of the built-in
+    /// assets
+    pub asset_packs: Vec<u64>,
 }
 
 /// Messages sent from the client to the server
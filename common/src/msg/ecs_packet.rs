@@ -26,6 +26,7 @@ sum_type! {
         Gravity(comp::Gravity),
         Sticky(comp::Sticky),
         Loadout(comp::Loadout),
+        AbilitySelection(comp::AbilitySelection),
         CharacterState(comp::CharacterState),
         Pos(comp::Pos),
         Vel(comp::Vel),
@@ -56,6 +57,7 @@ sum_type! {
         Gravity(PhantomData<comp::Gravity>),
         Sticky(PhantomData<comp::Sticky>),
         Loadout(PhantomData<comp::Loadout>),
+        AbilitySelection(PhantomData<comp::AbilitySelection>),
         CharacterState(PhantomData<comp::CharacterState>),
         Pos(PhantomData<comp::Pos>),
         Vel(PhantomData<comp::Vel>),
@@ -86,6 +88,7 @@ impl sync::CompPacket for EcsCompPacket {
             EcsCompPacket::Gravity(comp) => sync::handle_insert(comp, entity, world),
             EcsCompPacket::Sticky(comp) => sync::handle_insert(comp, entity, world),
             EcsCompPacket::Loadout(comp) => sync::handle_insert(comp, entity, world),
+            EcsCompPacket::AbilitySelection(comp) => sync::handle_insert(comp, entity, world),
             EcsCompPacket::CharacterState(comp) => sync::handle_insert(comp, entity, world),
             EcsCompPacket::Pos(comp) => sync::handle_insert(comp, entity, world),
             EcsCompPacket::Vel(comp) => sync::handle_insert(comp, entity, world),
@@ -114,6 +117,7 @@ impl sync::CompPacket for EcsCompPacket {
             EcsCompPacket::Gravity(comp) => sync::handle_modify(comp, entity, world),
             EcsCompPacket::Sticky(comp) => sync::handle_modify(comp, entity, world),
             EcsCompPacket::Loadout(comp) => sync::handle_modify(comp, entity, world),
+            EcsCompPacket::AbilitySelection(comp) => sync::handle_modify(comp, entity, world),
             EcsCompPacket::CharacterState(comp) => sync::handle_modify(comp, entity, world),
             EcsCompPacket::Pos(comp) => sync::handle_modify(comp, entity, world),
             EcsCompPacket::Vel(comp) => sync::handle_modify(comp, entity, world),
@@ -144,6 +148,9 @@ impl sync::CompPacket for EcsCompPacket {
             EcsCompPhantom::Gravity(_) => sync::handle_remove::<comp::Gravity>(entity, world),
             EcsCompPhantom::Sticky(_) => sync::handle_remove::<comp::Sticky>(entity, world),
             EcsCompPhantom::Loadout(_) => sync::handle_remove::<comp::Loadout>(entity, world),
+            EcsCompPhantom::AbilitySelection(_) => {
+                sync::handle_remove::<comp::AbilitySelection>(entity, world)
+            },
             EcsCompPhantom::CharacterState(_) => {
                 sync::handle_remove::<comp::CharacterState>(entity, world)
             },
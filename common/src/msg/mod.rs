@@ -8,8 +8,9 @@ pub use self::{
     client::{ClientGeneral, ClientMsg, ClientRegister, ClientType},
     ecs_packet::EcsCompPacket,
     server::{
-        CharacterInfo, DisconnectReason, InviteAnswer, Notification, PlayerInfo, PlayerListUpdate,
-        RegisterError, ServerGeneral, ServerInfo, ServerInit, ServerMsg, ServerRegisterAnswer,
+        CharacterInfo, DisconnectReason, InviteAnswer, MarketGood, MarketInfo, Notification,
+        PlayerInfo, PlayerListUpdate, PriceTrend, RegisterError, ServerGeneral, ServerInfo,
+        ServerInit, ServerMsg, ServerRegisterAnswer,
     },
     world_msg::WorldMapMsg,
 };
@@ -2,6 +2,7 @@ use super::{ClientType, EcsCompPacket, PingMsg};
 use crate::{
     character::CharacterItem,
     comp,
+    cutscene::CutsceneEvent,
     outcome::Outcome,
     recipe::RecipeBook,
     state, sync,
@@ -50,6 +51,7 @@ pub enum ServerInit {
     GameSync {
         entity_package: sync::EntityPackage<EcsCompPacket>,
         time_of_day: state::TimeOfDay,
+        day_length: state::DayLength,
         max_group_size: u32,
         client_timeout: Duration,
         world_map: crate::msg::world_msg::WorldMapMsg,
@@ -93,6 +95,8 @@ pub enum ServerGeneral {
     /// from an ingame state
     ExitInGameSuccess,
     InventoryUpdate(comp::Inventory, comp::InventoryUpdateEvent),
+    /// Combat events the client's entity took part in since the last update
+    CombatLog(Vec<comp::CombatEvent>),
     TerrainChunkUpdate {
         key: Vec2<i32>,
         chunk: Result<Box<TerrainChunk>, ()>,
@@ -101,6 +105,13 @@ pub enum ServerGeneral {
     SetViewDistance(u32),
     Outcomes(Vec<Outcome>),
     Knockback(Vec3<f32>),
+    /// Marks a position on the player's map, such as the camp of an outlaw
+    /// they are hunting, or clears the marker
+    MapMarker(Option<Vec2<i32>>),
+    /// Prices at the market of the town whose merchant the player talked to
+    Market(MarketInfo),
+    /// Part of a scripted sequence that the client has to play
+    Cutscene(CutsceneEvent),
     // Always possible
     PlayerListUpdate(PlayerListUpdate),
     /// A message to go into the client chat box. The client is responsible for
@@ -159,6 +170,39 @@ pub enum Notification {
     WaypointSaved,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum PriceTrend {
+    Rising,
+    Steady,
+    Falling,
+}
+
+/// A good traded at a town market
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarketGood {
+    pub name: String,
+    /// Coins the merchant asks for the good
+    pub buy: u32,
+    /// Coins the merchant pays for the good
+    pub sell: u32,
+    pub trend: PriceTrend,
+    /// How many of the good the merchant has for sale
+    pub stock: u32,
+    /// Whether there is an item that stands for the good, so that it can be
+    /// bought and sold rather than just looked at
+    pub tradeable: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarketInfo {
+    /// The merchant the player talked to, who trades the goods
+    pub merchant: Uid,
+    pub goods: Vec<MarketGood>,
+    /// Fraction taken off the buying prices thanks to the player's reputation
+    /// in the town, already applied to `goods`
+    pub discount: f32,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum DisconnectReason {
     /// Server shut down
@@ -176,6 +220,8 @@ pub enum RegisterError {
     Banned(String),
     InvalidCharacter,
     NotOnWhitelist,
+    /// The client uses asset packs that the server doesn't allow
+    AssetPacksNotAllowed,
     //TODO: InvalidAlias,
 }
 
@@ -209,11 +255,15 @@ impl ServerMsg {
                         | ServerGeneral::InviteComplete { .. }
                         | ServerGeneral::ExitInGameSuccess
                         | ServerGeneral::InventoryUpdate(_, _)
+                        | ServerGeneral::CombatLog(_)
                         | ServerGeneral::TerrainChunkUpdate { .. }
                         | ServerGeneral::TerrainBlockUpdates(_)
                         | ServerGeneral::SetViewDistance(_)
                         | ServerGeneral::Outcomes(_)
-                        | ServerGeneral::Knockback(_) => {
+                        | ServerGeneral::Knockback(_)
+                        | ServerGeneral::MapMarker(_)
+                        | ServerGeneral::Market(_)
+                        | ServerGeneral::Cutscene(_) => {
                             c_type == ClientType::Game && in_game.is_some()
                         },
                         // Always possible
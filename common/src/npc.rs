@@ -18,9 +18,12 @@ pub enum NpcKind {
     StoneGolem,
     Reddragon,
     Crocodile,
+    Colossus,
+    Spider,
+    Turret,
 }
 
-pub const ALL_NPCS: [NpcKind; 9] = [
+pub const ALL_NPCS: [NpcKind; 12] = [
     NpcKind::Humanoid,
     NpcKind::Wolf,
     NpcKind::Pig,
@@ -30,6 +33,9 @@ pub const ALL_NPCS: [NpcKind; 9] = [
     NpcKind::StoneGolem,
     NpcKind::Reddragon,
     NpcKind::Crocodile,
+    NpcKind::Colossus,
+    NpcKind::Spider,
+    NpcKind::Turret,
 ];
 
 /// Body-specific NPC name metadata.
@@ -98,6 +104,9 @@ pub fn kind_to_body(kind: NpcKind) -> Body {
         NpcKind::StoneGolem => comp::golem::Body::random().into(),
         NpcKind::Reddragon => comp::dragon::Body::random().into(),
         NpcKind::Crocodile => comp::quadruped_low::Body::random().into(),
+        NpcKind::Colossus => comp::colossus::Body::random().into(),
+        NpcKind::Spider => comp::arthropod::Body::random().into(),
+        NpcKind::Turret => comp::turret::Body::random().into(),
     }
 }
 
@@ -235,6 +244,30 @@ impl NpcBody {
                     comp::quadruped_low::Body::random_with,
                 )
             })
+            .or_else(|| {
+                parse(
+                    s,
+                    NpcKind::Colossus,
+                    &npc_names.colossus,
+                    comp::colossus::Body::random_with,
+                )
+            })
+            .or_else(|| {
+                parse(
+                    s,
+                    NpcKind::Spider,
+                    &npc_names.arthropod,
+                    comp::arthropod::Body::random_with,
+                )
+            })
+            .or_else(|| {
+                parse(
+                    s,
+                    NpcKind::Turret,
+                    &npc_names.turret,
+                    comp::turret::Body::random_with,
+                )
+            })
             .ok_or(())
     }
 }
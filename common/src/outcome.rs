@@ -1,5 +1,5 @@
-use crate::comp;
-use comp::item::Reagent;
+use crate::{comp, sync::Uid, terrain::SpriteKind};
+use comp::item::{Instrument, Reagent};
 use serde::{Deserialize, Serialize};
 use vek::*;
 
@@ -22,6 +22,29 @@ pub enum Outcome {
         body: comp::Body,
         vel: Vec3<f32>,
     },
+    SpriteBreak {
+        pos: Vec3<f32>,
+        sprite: SpriteKind,
+    },
+    /// Something plunged into water at the given speed
+    Splash {
+        pos: Vec3<f32>,
+        speed: f32,
+    },
+    /// A melee attack landed hard enough to be felt, with the magnitude
+    /// growing with the damage, starting at 1.0 for the lightest heavy hit
+    HeavyHit {
+        pos: Vec3<f32>,
+        attacker: Uid,
+        target: Uid,
+        magnitude: f32,
+    },
+    /// A performer played a note on their instrument
+    Note {
+        pos: Vec3<f32>,
+        instrument: Instrument,
+        note: u8,
+    },
 }
 
 impl Outcome {
@@ -29,6 +52,10 @@ impl Outcome {
         match self {
             Outcome::Explosion { pos, .. } => Some(*pos),
             Outcome::ProjectileShot { pos, .. } => Some(*pos),
+            Outcome::SpriteBreak { pos, .. } => Some(*pos),
+            Outcome::Splash { pos, .. } => Some(*pos),
+            Outcome::HeavyHit { pos, .. } => Some(*pos),
+            Outcome::Note { pos, .. } => Some(*pos),
         }
     }
 }
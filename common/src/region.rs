@@ -362,6 +362,68 @@ impl RegionMap {
     }
 }
 
+/// Splits the regions of the world between a number of workers, each owning
+/// one of a row of equally wide stripes running the height of the world.
+///
+/// This is groundwork for simulating different areas of the world on separate
+/// cores: the server still runs a single dispatcher, but reports in its metrics
+/// how many entities each worker would own and how many would have to be handed
+/// over to another worker because they crossed a border. Keeping the area of
+/// each worker in one piece keeps those borders (and so the handoffs) short.
+pub struct RegionPartition {
+    workers: u32,
+    /// Width of the world in regions
+    width: u32,
+}
+
+impl RegionPartition {
+    /// `world_size` is in blocks
+    pub fn new(workers: u32, world_size: Vec2<u32>) -> Self {
+        Self {
+            workers: workers.max(1),
+            width: (world_size.x / REGION_SIZE).max(1),
+        }
+    }
+
+    pub fn workers(&self) -> u32 { self.workers }
+
+    /// Returns the worker owning the region with the given key. Regions
+    /// beyond the edges of the world belong to the outermost stripes.
+    pub fn owner(&self, key: Vec2<i32>) -> u32 {
+        let stripe = key.x as i64 * self.workers as i64 / self.width as i64;
+        stripe.max(0).min(self.workers as i64 - 1) as u32
+    }
+
+    /// Iterates over the entities that moved into a region owned by another
+    /// worker during the last tick of `regions`, yielding (entity id, old
+    /// worker, new worker)
+    pub fn handoffs<'a>(
+        &'a self,
+        regions: &'a RegionMap,
+    ) -> impl Iterator<Item = (u32, u32, u32)> + 'a {
+        regions.iter().flat_map(move |(key, region)| {
+            let owner = self.owner(key);
+            region.events().iter().filter_map(move |event| match event {
+                // Entities entering a region that was only just created don't record where they
+                // came from, so handoffs are found from the regions they left instead
+                Event::Left(id, Some(to)) if self.owner(*to) != owner => {
+                    Some((*id, owner, self.owner(*to)))
+                },
+                _ => None,
+            })
+        })
+    }
+
+    /// Counts the entities owned by each worker, indexed by worker
+    pub fn entity_counts(&self, regions: &RegionMap) -> Vec<usize> {
+        let mut counts = vec![0; self.workers as usize];
+        for (key, region) in regions.iter() {
+            counts[self.owner(key) as usize] += region.entities().iter().count();
+        }
+        counts
+    }
+}
+
 // Note vd is in blocks in this case
 pub fn region_in_vd(key: Vec2<i32>, pos: Vec3<f32>, vd: f32) -> bool {
     let vd_extended = vd + TETHER_LENGTH as f32 * 2.0f32.sqrt();
@@ -418,3 +480,85 @@ pub fn regions_in_vd(pos: Vec3<f32>, vd: f32) -> HashSet<Vec2<i32>> {
 fn morton_code(pos: Vec2<i32>) -> i64 {
     interleave_i32_with_zeros(pos.x) | (interleave_i32_with_zeros(pos.y) << 1)
 }*/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use specs::{Builder, World, WorldExt};
+
+    fn tick(world: &World, regions: &mut RegionMap) {
+        regions.tick(
+            world.read_storage::<Pos>(),
+            world.read_storage::<Vel>(),
+            world.entities(),
+        );
+    }
+
+    /// Position in the middle of the region with the given key
+    fn region_centre(key: Vec2<i32>) -> Pos {
+        let centre = RegionMap::key_pos(key) + REGION_SIZE as i32 / 2;
+        Pos(Vec3::new(centre.x as f32, centre.y as f32, 0.0))
+    }
+
+    #[test]
+    fn test_partition_stripes() {
+        // 64 regions wide, so each worker owns a stripe 16 regions wide
+        let partition = RegionPartition::new(4, Vec2::broadcast(64 * REGION_SIZE));
+        for y in -1..65 {
+            assert_eq!(partition.owner(Vec2::new(-1, y)), 0);
+            assert_eq!(partition.owner(Vec2::new(0, y)), 0);
+            assert_eq!(partition.owner(Vec2::new(15, y)), 0);
+            assert_eq!(partition.owner(Vec2::new(16, y)), 1);
+            assert_eq!(partition.owner(Vec2::new(47, y)), 2);
+            assert_eq!(partition.owner(Vec2::new(48, y)), 3);
+            assert_eq!(partition.owner(Vec2::new(64, y)), 3);
+        }
+        assert_eq!(RegionPartition::new(0, Vec2::zero()).workers(), 1);
+    }
+
+    #[test]
+    fn test_partition_handoffs() {
+        let partition = RegionPartition::new(4, Vec2::broadcast(64 * REGION_SIZE));
+        let mut world = World::new();
+        world.register::<Pos>();
+        world.register::<Vel>();
+        let mut regions = RegionMap::new();
+
+        // A region owned by another worker than the one at the origin
+        let origin = Vec2::zero();
+        let other = (1..)
+            .map(|x| Vec2::new(x, 0))
+            .find(|key| partition.owner(*key) != partition.owner(origin))
+            .unwrap();
+
+        let mover = world.create_entity().with(region_centre(origin)).build();
+        world.create_entity().with(region_centre(origin)).build();
+        tick(&world, &mut regions);
+        assert_eq!(partition.handoffs(&regions).count(), 0);
+        let counts = partition.entity_counts(&regions);
+        assert_eq!(counts.iter().sum::<usize>(), 2);
+        assert_eq!(counts[partition.owner(origin) as usize], 2);
+
+        world
+            .write_storage::<Pos>()
+            .insert(mover, region_centre(other))
+            .unwrap();
+        tick(&world, &mut regions);
+        assert_eq!(partition.handoffs(&regions).collect::<Vec<_>>(), vec![(
+            mover.id(),
+            partition.owner(origin),
+            partition.owner(other),
+        )]);
+        let counts = partition.entity_counts(&regions);
+        assert_eq!(counts[partition.owner(origin) as usize], 1);
+        assert_eq!(counts[partition.owner(other) as usize], 1);
+
+        // Moving within a stripe isn't a handoff
+        world
+            .write_storage::<Pos>()
+            .insert(mover, region_centre(other + Vec2::unit_y()))
+            .unwrap();
+        tick(&world, &mut regions);
+        assert_eq!(partition.handoffs(&regions).count(), 0);
+    }
+}
@@ -1,4 +1,5 @@
 use crate::{
+    combat::CombatRules,
     comp,
     event::{EventBus, LocalEvent, ServerEvent},
     metrics::SysMetrics,
@@ -20,14 +21,26 @@ use specs::{
 use std::{sync::Arc, time::Duration};
 use vek::*;
 
-/// How much faster should an in-game day be compared to a real day?
-// TODO: Don't hard-code this.
-const DAY_CYCLE_FACTOR: f64 = 24.0 * 2.0;
+/// How many real seconds an in-game day lasts by default
+pub const DEFAULT_DAY_LENGTH: f64 = 60.0 * 30.0;
 
 /// A resource that stores the time of day.
 #[derive(Copy, Clone, Debug, Serialize, Deserialize, Default)]
 pub struct TimeOfDay(pub f64);
 
+/// A resource that stores how many real seconds an in-game day lasts.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub struct DayLength(pub f64);
+
+impl Default for DayLength {
+    fn default() -> Self { DayLength(DEFAULT_DAY_LENGTH) }
+}
+
+impl DayLength {
+    /// How much faster an in-game day passes than a real one
+    pub fn cycle_factor(&self) -> f64 { 60.0 * 60.0 * 24.0 / self.0.max(1.0) }
+}
+
 /// A resource that stores the tick (i.e: physics) time.
 #[derive(Copy, Clone, Debug, Default, Serialize, Deserialize)]
 pub struct Time(pub f64);
@@ -61,6 +74,11 @@ impl BlockChange {
         }
     }
 
+    /// Keeps only the changes for which `f` returns true
+    pub fn retain(&mut self, f: impl FnMut(&Vec3<i32>, &mut Block) -> bool) {
+        self.blocks.retain(f);
+    }
+
     pub fn clear(&mut self) { self.blocks.clear(); }
 }
 
@@ -109,6 +127,7 @@ impl State {
         ecs.register_sync_marker();
         // Register server -> all clients synced components.
         ecs.register::<comp::Loadout>();
+        ecs.register::<comp::AbilitySelection>();
         ecs.register::<comp::Body>();
         ecs.register::<comp::Player>();
         ecs.register::<comp::Stats>();
@@ -143,6 +162,9 @@ impl State {
         ecs.register::<comp::Ori>();
         ecs.register::<comp::Inventory>();
 
+        // Register components sent from the server to the client owning the entity
+        ecs.register::<comp::CombatLog>();
+
         // Register client-local components
         // TODO: only register on the client
         ecs.register::<comp::LightAnimation>();
@@ -154,6 +176,7 @@ impl State {
         ecs.register::<comp::Last<comp::Ori>>();
         ecs.register::<comp::Alignment>();
         ecs.register::<comp::Agent>();
+        ecs.register::<comp::Threat>();
         ecs.register::<comp::WaypointArea>();
         ecs.register::<comp::ForceUpdate>();
         ecs.register::<comp::InventoryUpdate>();
@@ -161,15 +184,22 @@ impl State {
         ecs.register::<comp::Waypoint>();
         ecs.register::<comp::Projectile>();
         ecs.register::<comp::Attacking>();
+        ecs.register::<comp::Trampling>();
+        ecs.register::<comp::Invulnerable>();
+        ecs.register::<comp::Instance>();
+        ecs.register::<comp::Hitched>();
+        ecs.register::<comp::Affixes>();
         ecs.register::<comp::ItemDrop>();
         ecs.register::<comp::ChatMode>();
         ecs.register::<comp::Faction>();
         ecs.register::<comp::group::Invite>();
         ecs.register::<comp::group::PendingInvites>();
         ecs.register::<comp::Beam>();
+        ecs.register::<comp::BossState>();
 
         // Register synced resources used by the ECS.
         ecs.insert(TimeOfDay(0.0));
+        ecs.insert(DayLength::default());
 
         // Register unsynced resources used by the ECS.
         ecs.insert(Time(0.0));
@@ -183,6 +213,7 @@ impl State {
         ecs.insert(comp::group::GroupManager::default());
         ecs.insert(RegionMap::new());
         ecs.insert(SysMetrics::default());
+        ecs.insert(CombatRules::default());
 
         ecs
     }
@@ -349,7 +380,8 @@ impl State {
         update_terrain_and_regions: bool,
     ) {
         // Change the time accordingly.
-        self.ecs.write_resource::<TimeOfDay>().0 += dt.as_secs_f64() * DAY_CYCLE_FACTOR;
+        let day_cycle_factor = self.ecs.read_resource::<DayLength>().cycle_factor();
+        self.ecs.write_resource::<TimeOfDay>().0 += dt.as_secs_f64() * day_cycle_factor;
         self.ecs.write_resource::<Time>().0 += dt.as_secs_f64();
 
         // Update delta time.
@@ -62,6 +62,7 @@ impl CharacterBehavior for Data {
 
         handle_move(data, &mut update, 0.4);
         handle_jump(data, &mut update);
+        handle_hover(data, &mut update);
 
         if unwrap_tool_data(data).is_none() {
             update.character = CharacterState::Idle;
@@ -74,10 +75,7 @@ impl CharacterBehavior for Data {
                     // Build up
                     update.character = CharacterState::BasicBeam(Data {
                         static_data: self.static_data,
-                        timer: self
-                            .timer
-                            .checked_add(Duration::from_secs_f32(data.dt.0))
-                            .unwrap_or_default(),
+                        timer: self.timer.checked_add(attack_dt(data)).unwrap_or_default(),
                         stage_section: self.stage_section,
                         particle_ori: Some(*data.inputs.look_dir),
                         offset: self.offset,
@@ -138,18 +136,16 @@ impl CharacterBehavior for Data {
                     });
                     update.character = CharacterState::BasicBeam(Data {
                         static_data: self.static_data,
-                        timer: self
-                            .timer
-                            .checked_add(Duration::from_secs_f32(data.dt.0))
-                            .unwrap_or_default(),
+                        timer: self.timer.checked_add(attack_dt(data)).unwrap_or_default(),
                         stage_section: self.stage_section,
                         particle_ori: Some(*data.inputs.look_dir),
                         offset: self.offset,
                     });
 
                     // Consumes energy if there's enough left and ability key is held down
-                    update.energy.change_by(
-                        -(self.static_data.energy_drain as f32 * data.dt.0) as i32,
+                    update.energy.change_by_rate(
+                        -(self.static_data.energy_drain as f32),
+                        data.dt.0,
                         EnergySource::Ability,
                     );
                 } else {
@@ -166,10 +162,7 @@ impl CharacterBehavior for Data {
                 if self.timer < self.static_data.recover_duration {
                     update.character = CharacterState::BasicBeam(Data {
                         static_data: self.static_data,
-                        timer: self
-                            .timer
-                            .checked_add(Duration::from_secs_f32(data.dt.0))
-                            .unwrap_or_default(),
+                        timer: self.timer.checked_add(attack_dt(data)).unwrap_or_default(),
                         stage_section: self.stage_section,
                         particle_ori: Some(*data.inputs.look_dir),
                         offset: self.offset,
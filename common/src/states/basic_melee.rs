@@ -1,5 +1,5 @@
 use crate::{
-    comp::{Attacking, CharacterState, EnergySource, StateUpdate},
+    comp::{Attacking, BaseDamage, CharacterState, EnergySource, StateUpdate},
     states::utils::*,
     sys::character_behavior::{CharacterBehavior, JoinData},
 };
@@ -36,7 +36,7 @@ impl CharacterBehavior for Data {
             update.character = CharacterState::BasicMelee(Data {
                 buildup_duration: self
                     .buildup_duration
-                    .checked_sub(Duration::from_secs_f32(data.dt.0))
+                    .checked_sub(attack_dt(data))
                     .unwrap_or_default(),
                 recover_duration: self.recover_duration,
                 base_healthchange: self.base_healthchange,
@@ -53,7 +53,10 @@ impl CharacterBehavior for Data {
             };
             // Hit attempt
             data.updater.insert(data.entity, Attacking {
-                base_damage: damage,
+                base_damage: BaseDamage {
+                    amount: damage,
+                    kind: melee_damage_kind(data),
+                },
                 base_heal: heal,
                 range: self.range,
                 max_angle: self.max_angle.to_radians(),
@@ -77,7 +80,7 @@ impl CharacterBehavior for Data {
                 buildup_duration: self.buildup_duration,
                 recover_duration: self
                     .recover_duration
-                    .checked_sub(Duration::from_secs_f32(data.dt.0))
+                    .checked_sub(attack_dt(data))
                     .unwrap_or_default(),
                 base_healthchange: self.base_healthchange,
                 knockback: self.knockback,
@@ -45,7 +45,7 @@ impl CharacterBehavior for Data {
         {
             // Prepare (draw the bow)
             update.character = CharacterState::BasicRanged(Data {
-                prepare_timer: self.prepare_timer + Duration::from_secs_f32(data.dt.0),
+                prepare_timer: self.prepare_timer + attack_dt(data),
                 holdable: self.holdable,
                 prepare_duration: self.prepare_duration,
                 recover_duration: self.recover_duration,
@@ -92,7 +92,7 @@ impl CharacterBehavior for Data {
                 prepare_duration: self.prepare_duration,
                 recover_duration: self
                     .recover_duration
-                    .checked_sub(Duration::from_secs_f32(data.dt.0))
+                    .checked_sub(attack_dt(data))
                     .unwrap_or_default(),
                 projectile: self.projectile.clone(),
                 projectile_body: self.projectile_body,
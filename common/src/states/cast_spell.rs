@@ -0,0 +1,231 @@
+use crate::{
+    comp::{
+        buff::DISPEL_IMMUNITY, Body, Buff, BuffCategory, BuffChange, BuffData, BuffKind,
+        BuffSource, CharacterState, EnergySource, Gravity, LightEmitter, Projectile, StateUpdate,
+    },
+    event::ServerEvent,
+    states::utils::*,
+    sys::character_behavior::{CharacterBehavior, JoinData},
+    terrain::Block,
+    vol::ReadVol,
+    Explosion,
+};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use vek::*;
+
+/// What a spell does once it has been cast
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum SpellPayload {
+    /// Fires a projectile in the look direction
+    ProjectileSpell {
+        projectile: Projectile,
+        projectile_body: Body,
+        projectile_light: Option<LightEmitter>,
+        projectile_gravity: Option<Gravity>,
+        projectile_speed: f32,
+    },
+    /// Applies a buff to the caster
+    SelfBuff { kind: BuffKind, data: BuffData },
+    /// Causes an explosion where the caster is looking, up to some range away
+    GroundTargetedAoE { range: f32, explosion: Explosion },
+    /// Cleanses the caster of up to `count` debuffs of these categories
+    Dispel {
+        categories: Vec<BuffCategory>,
+        count: u32,
+    },
+    /// Summons `count` minions that fight for the caster until `duration` runs
+    /// out, up to `max_minions` at once
+    Summon {
+        body: Body,
+        count: u32,
+        duration: Duration,
+        max_minions: u32,
+    },
+}
+
+/// Separated out to condense update portions of character state
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct StaticData {
+    /// How long the spell takes to cast
+    pub cast_duration: Duration,
+    /// How long the state has until exiting
+    pub recover_duration: Duration,
+    /// Energy paid when the cast completes
+    pub energy_cost: u32,
+    /// How much damage the caster can take while casting before the spell is
+    /// interrupted
+    pub interrupt_threshold: u32,
+    /// Movement speed efficiency
+    pub move_efficiency: f32,
+    /// What the spell does once cast
+    pub payload: SpellPayload,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Data {
+    /// Struct containing data that does not change over the course of the
+    /// character state
+    pub static_data: StaticData,
+    /// Timer for each stage
+    pub timer: Duration,
+    /// What section the character stage is in
+    pub stage_section: StageSection,
+    /// Damage taken since the cast started
+    pub damage_taken: u32,
+}
+
+impl Data {
+    /// How far along the cast is, from 0 to 1, or `None` if the spell isn't
+    /// being cast anymore
+    pub fn cast_progress(&self) -> Option<f32> {
+        if self.stage_section == StageSection::Cast {
+            Some((self.timer.as_secs_f32() / self.static_data.cast_duration.as_secs_f32()).min(1.0))
+        } else {
+            None
+        }
+    }
+
+    /// Records damage taken by the caster, returning whether it was enough to
+    /// interrupt the cast
+    pub fn take_damage(&mut self, amount: u32) -> bool {
+        if self.stage_section != StageSection::Cast {
+            return false;
+        }
+        self.damage_taken = self.damage_taken.saturating_add(amount);
+        self.damage_taken > self.static_data.interrupt_threshold
+    }
+}
+
+impl CharacterBehavior for Data {
+    fn behavior(&self, data: &JoinData) -> StateUpdate {
+        let mut update = StateUpdate::from(data);
+
+        handle_move(data, &mut update, self.static_data.move_efficiency);
+        handle_jump(data, &mut update);
+
+        match self.stage_section {
+            StageSection::Cast => {
+                if self.timer < self.static_data.cast_duration {
+                    // Casts
+                    update.character = CharacterState::CastSpell(Data {
+                        timer: self.timer.checked_add(attack_dt(data)).unwrap_or_default(),
+                        ..self.clone()
+                    });
+                } else {
+                    // The spell fizzles if the caster can't pay for it anymore
+                    if update
+                        .energy
+                        .try_change_by(
+                            -(self.static_data.energy_cost as i32),
+                            EnergySource::Ability,
+                        )
+                        .is_ok()
+                    {
+                        release_payload(data, &mut update, &self.static_data.payload);
+                    }
+
+                    // Transitions to recover
+                    update.character = CharacterState::CastSpell(Data {
+                        timer: Duration::default(),
+                        stage_section: StageSection::Recover,
+                        ..self.clone()
+                    });
+                }
+            },
+            StageSection::Recover => {
+                if self.timer < self.static_data.recover_duration {
+                    // Recovers
+                    update.character = CharacterState::CastSpell(Data {
+                        timer: self.timer.checked_add(attack_dt(data)).unwrap_or_default(),
+                        ..self.clone()
+                    });
+                } else {
+                    // Done
+                    update.character = CharacterState::Wielding;
+                }
+            },
+            _ => {
+                // If it somehow ends up in an incorrect stage section
+                update.character = CharacterState::Wielding;
+            },
+        }
+
+        update
+    }
+}
+
+fn release_payload(data: &JoinData, update: &mut StateUpdate, payload: &SpellPayload) {
+    match payload {
+        SpellPayload::ProjectileSpell {
+            projectile,
+            projectile_body,
+            projectile_light,
+            projectile_gravity,
+            projectile_speed,
+        } => {
+            let mut projectile = projectile.clone();
+            projectile.owner = Some(*data.uid);
+            update.server_events.push_front(ServerEvent::Shoot {
+                entity: data.entity,
+                dir: data.inputs.look_dir,
+                body: *projectile_body,
+                projectile,
+                light: *projectile_light,
+                gravity: *projectile_gravity,
+                speed: *projectile_speed,
+            });
+        },
+        SpellPayload::SelfBuff { kind, data: buff } => {
+            update.server_events.push_front(ServerEvent::Buff {
+                entity: data.entity,
+                buff_change: BuffChange::Add(Buff::new(
+                    *kind,
+                    *buff,
+                    vec![BuffCategory::Magical],
+                    BuffSource::Character { by: *data.uid },
+                )),
+            });
+        },
+        SpellPayload::Dispel { categories, count } => {
+            update.server_events.push_front(ServerEvent::Buff {
+                entity: data.entity,
+                buff_change: BuffChange::Dispel {
+                    categories: categories.clone(),
+                    count: *count,
+                    immunity: DISPEL_IMMUNITY,
+                },
+            });
+        },
+        SpellPayload::Summon {
+            body,
+            count,
+            duration,
+            max_minions,
+        } => {
+            update.server_events.push_front(ServerEvent::Summon {
+                summoner: data.entity,
+                body: *body,
+                count: *count,
+                duration: *duration,
+                max_minions: *max_minions,
+            });
+        },
+        SpellPayload::GroundTargetedAoE { range, explosion } => {
+            // Target the first solid block in the look direction
+            let eye = data.pos.0 + Vec3::unit_z() * data.body.height() * 0.9;
+            let (dist, _) = data
+                .terrain
+                .ray(eye, eye + *data.inputs.look_dir * *range)
+                .until(Block::is_solid)
+                .cast();
+            update.server_events.push_front(ServerEvent::Explosion {
+                pos: eye + *data.inputs.look_dir * dist.min(*range),
+                explosion: explosion.clone(),
+                owner: Some(*data.uid),
+                friendly_damage: false,
+                reagent: None,
+            });
+        },
+    }
+}
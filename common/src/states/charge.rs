@@ -0,0 +1,183 @@
+use crate::{
+    comp::{CharacterState, EnergySource, StateUpdate, Trampling},
+    states::utils::*,
+    sys::character_behavior::{CharacterBehavior, JoinData},
+};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use vek::*;
+
+/// Separated out to condense update portions of character state
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct StaticData {
+    /// How long the charger lowers its head before running
+    pub buildup_duration: Duration,
+    /// How long the charge lasts at most
+    pub charge_duration: Duration,
+    /// How long the state has until exiting after a charge
+    pub recover_duration: Duration,
+    /// How long the charger is stunned after running into a wall
+    pub stagger_duration: Duration,
+    /// How quickly the charger accelerates forward
+    pub forward_speed: f32,
+    /// Damage dealt to each entity run through
+    pub damage: u32,
+    /// Knockback applied to each entity run through
+    pub knockback: f32,
+    /// Radius of the capsule swept along the charger's path
+    pub radius: f32,
+    /// Rate of energy drain while charging
+    pub energy_drain: u32,
+}
+
+/// The charge that a ridden beast makes at its rider's command when it has no
+/// charge of its own
+pub const MOUNTED: StaticData = StaticData {
+    buildup_duration: Duration::from_millis(500),
+    charge_duration: Duration::from_millis(1500),
+    recover_duration: Duration::from_millis(500),
+    stagger_duration: Duration::from_millis(1500),
+    forward_speed: 3.0,
+    damage: 150,
+    knockback: 20.0,
+    radius: 1.5,
+    energy_drain: 100,
+};
+
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Data {
+    /// Struct containing data that does not change over the course of the
+    /// character state
+    pub static_data: StaticData,
+    /// Timer for each stage
+    pub timer: Duration,
+    /// What section the character stage is in
+    pub stage_section: StageSection,
+    /// Whether the charge ended by running into a wall
+    pub staggered: bool,
+}
+
+impl Data {
+    pub fn new(static_data: StaticData) -> Self {
+        Self {
+            static_data,
+            timer: Duration::default(),
+            stage_section: StageSection::Buildup,
+            staggered: false,
+        }
+    }
+}
+
+impl CharacterBehavior for Data {
+    fn behavior(&self, data: &JoinData) -> StateUpdate {
+        let mut update = StateUpdate::from(data);
+
+        match self.stage_section {
+            StageSection::Buildup => {
+                // Line up the charge
+                handle_move(data, &mut update, 0.1);
+
+                if self.timer < self.static_data.buildup_duration {
+                    update.character = CharacterState::Charge(Data {
+                        timer: self
+                            .timer
+                            .checked_add(Duration::from_secs_f32(data.dt.0))
+                            .unwrap_or_default(),
+                        ..*self
+                    });
+                } else {
+                    data.updater.insert(data.entity, Trampling {
+                        damage: self.static_data.damage,
+                        knockback: self.static_data.knockback,
+                        radius: self.static_data.radius,
+                        last_pos: None,
+                        hit_entities: Vec::new(),
+                    });
+
+                    // Transitions to charge section of stage
+                    update.character = CharacterState::Charge(Data {
+                        timer: Duration::default(),
+                        stage_section: StageSection::Charge,
+                        ..*self
+                    });
+                }
+            },
+            StageSection::Charge => {
+                // A wall stops the charge dead and stuns the charger, ignoring the first tick
+                // so charges can be started while brushing against terrain
+                if data.physics.on_wall.is_some() && self.timer > Duration::default() {
+                    update.vel.0 = Vec3::new(0.0, 0.0, update.vel.0.z);
+                    data.updater.remove::<Trampling>(data.entity);
+
+                    update.character = CharacterState::Charge(Data {
+                        timer: Duration::default(),
+                        stage_section: StageSection::Recover,
+                        staggered: true,
+                        ..*self
+                    });
+                } else if self.timer < self.static_data.charge_duration
+                    && update.energy.current() > 0
+                {
+                    // Runs in a straight line, no steering
+                    forward_move(data, &mut update, 0.0, self.static_data.forward_speed);
+
+                    update.character = CharacterState::Charge(Data {
+                        timer: self
+                            .timer
+                            .checked_add(Duration::from_secs_f32(data.dt.0))
+                            .unwrap_or_default(),
+                        ..*self
+                    });
+
+                    // Consumes energy while charging
+                    update.energy.change_by_rate(
+                        -(self.static_data.energy_drain as f32),
+                        data.dt.0,
+                        EnergySource::Ability,
+                    );
+                } else {
+                    data.updater.remove::<Trampling>(data.entity);
+
+                    // Transitions to recover section of stage
+                    update.character = CharacterState::Charge(Data {
+                        timer: Duration::default(),
+                        stage_section: StageSection::Recover,
+                        ..*self
+                    });
+                }
+            },
+            StageSection::Recover => {
+                let recover_duration = if self.staggered {
+                    self.static_data.stagger_duration
+                } else {
+                    self.static_data.recover_duration
+                };
+
+                if self.timer < recover_duration {
+                    if !self.staggered {
+                        handle_move(data, &mut update, 0.3);
+                    }
+
+                    update.character = CharacterState::Charge(Data {
+                        timer: self
+                            .timer
+                            .checked_add(Duration::from_secs_f32(data.dt.0))
+                            .unwrap_or_default(),
+                        ..*self
+                    });
+                } else {
+                    // Done
+                    update.character = CharacterState::Wielding;
+                }
+            },
+            _ => {
+                // If it somehow ends up in an incorrect stage section
+                update.character = CharacterState::Wielding;
+                // Make sure trampling component is removed
+                data.updater.remove::<Trampling>(data.entity);
+            },
+        }
+
+        update
+    }
+}
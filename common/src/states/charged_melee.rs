@@ -1,5 +1,5 @@
 use crate::{
-    comp::{Attacking, CharacterState, EnergySource, StateUpdate},
+    comp::{Attacking, BaseDamage, CharacterState, EnergySource, StateUpdate},
     states::utils::{StageSection, *},
     sys::character_behavior::*,
 };
@@ -69,17 +69,15 @@ impl CharacterBehavior for Data {
                     update.character = CharacterState::ChargedMelee(Data {
                         static_data: self.static_data,
                         stage_section: self.stage_section,
-                        timer: self
-                            .timer
-                            .checked_add(Duration::from_secs_f32(data.dt.0))
-                            .unwrap_or_default(),
+                        timer: self.timer.checked_add(attack_dt(data)).unwrap_or_default(),
                         exhausted: self.exhausted,
                         charge_amount: charge,
                     });
 
                     // Consumes energy if there's enough left and RMB is held down
-                    update.energy.change_by(
-                        -(self.static_data.energy_drain as f32 * data.dt.0) as i32,
+                    update.energy.change_by_rate(
+                        -(self.static_data.energy_drain as f32),
+                        data.dt.0,
                         EnergySource::Ability,
                     );
                 } else if data.inputs.secondary.is_pressed()
@@ -89,17 +87,15 @@ impl CharacterBehavior for Data {
                     update.character = CharacterState::ChargedMelee(Data {
                         static_data: self.static_data,
                         stage_section: self.stage_section,
-                        timer: self
-                            .timer
-                            .checked_add(Duration::from_secs_f32(data.dt.0))
-                            .unwrap_or_default(),
+                        timer: self.timer.checked_add(attack_dt(data)).unwrap_or_default(),
                         exhausted: self.exhausted,
                         charge_amount: self.charge_amount,
                     });
 
                     // Consumes energy if there's enough left and RMB is held down
-                    update.energy.change_by(
-                        -(self.static_data.energy_drain as f32 * data.dt.0 / 5.0) as i32,
+                    update.energy.change_by_rate(
+                        -(self.static_data.energy_drain as f32) / 5.0,
+                        data.dt.0,
                         EnergySource::Ability,
                     );
                 } else {
@@ -124,7 +120,10 @@ impl CharacterBehavior for Data {
 
                     // Hit attempt
                     data.updater.insert(data.entity, Attacking {
-                        base_damage: damage as u32,
+                        base_damage: BaseDamage {
+                            amount: damage as u32,
+                            kind: melee_damage_kind(data),
+                        },
                         base_heal: 0,
                         range: self.static_data.range,
                         max_angle: self.static_data.max_angle.to_radians(),
@@ -137,10 +136,7 @@ impl CharacterBehavior for Data {
                     update.character = CharacterState::ChargedMelee(Data {
                         static_data: self.static_data,
                         stage_section: self.stage_section,
-                        timer: self
-                            .timer
-                            .checked_add(Duration::from_secs_f32(data.dt.0))
-                            .unwrap_or_default(),
+                        timer: self.timer.checked_add(attack_dt(data)).unwrap_or_default(),
                         exhausted: true,
                         charge_amount: self.charge_amount,
                     });
@@ -149,10 +145,7 @@ impl CharacterBehavior for Data {
                     update.character = CharacterState::ChargedMelee(Data {
                         static_data: self.static_data,
                         stage_section: self.stage_section,
-                        timer: self
-                            .timer
-                            .checked_add(Duration::from_secs_f32(data.dt.0))
-                            .unwrap_or_default(),
+                        timer: self.timer.checked_add(attack_dt(data)).unwrap_or_default(),
                         exhausted: self.exhausted,
                         charge_amount: self.charge_amount,
                     });
@@ -173,10 +166,7 @@ impl CharacterBehavior for Data {
                     update.character = CharacterState::ChargedMelee(Data {
                         static_data: self.static_data,
                         stage_section: self.stage_section,
-                        timer: self
-                            .timer
-                            .checked_add(Duration::from_secs_f32(data.dt.0))
-                            .unwrap_or_default(),
+                        timer: self.timer.checked_add(attack_dt(data)).unwrap_or_default(),
                         exhausted: self.exhausted,
                         charge_amount: self.charge_amount,
                     });
@@ -1,7 +1,7 @@
 use crate::{
     comp::{
-        projectile, Body, CharacterState, EnergySource, Gravity, LightEmitter, Projectile,
-        StateUpdate,
+        projectile, Body, CharacterState, DamageKind, EnergySource, Gravity, LightEmitter,
+        Projectile, StateUpdate,
     },
     event::ServerEvent,
     states::utils::*,
@@ -10,6 +10,9 @@ use crate::{
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
+/// How many targets a fully charged shot passes through after the first
+const MAX_PENETRATION: u32 = 2;
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Data {
     /// Whether the attack fired already
@@ -58,7 +61,7 @@ impl CharacterBehavior for Data {
                 max_knockback: self.max_knockback,
                 prepare_duration: self
                     .prepare_duration
-                    .checked_sub(Duration::from_secs_f32(data.dt.0))
+                    .checked_sub(attack_dt(data))
                     .unwrap_or_default(),
                 charge_duration: self.charge_duration,
                 charge_timer: self.charge_timer,
@@ -84,7 +87,7 @@ impl CharacterBehavior for Data {
                 prepare_duration: self.prepare_duration,
                 charge_timer: self
                     .charge_timer
-                    .checked_add(Duration::from_secs_f32(data.dt.0))
+                    .checked_add(attack_dt(data))
                     .unwrap_or_default(),
                 charge_duration: self.charge_duration,
                 recover_duration: self.recover_duration,
@@ -96,8 +99,9 @@ impl CharacterBehavior for Data {
             });
 
             // Consumes energy if there's enough left and RMB is held down
-            update.energy.change_by(
-                -(self.energy_drain as f32 * data.dt.0) as i32,
+            update.energy.change_by_rate(
+                -(self.energy_drain as f32),
+                data.dt.0,
                 EnergySource::Ability,
             );
         } else if data.inputs.secondary.is_pressed() {
@@ -121,8 +125,9 @@ impl CharacterBehavior for Data {
             });
 
             // Consumes energy if there's enough left and RMB is held down
-            update.energy.change_by(
-                -(self.energy_drain as f32 * data.dt.0 / 5.0) as i32,
+            update.energy.change_by_rate(
+                -(self.energy_drain as f32) / 5.0,
+                data.dt.0,
                 EnergySource::Ability,
             );
         } else if !self.exhausted {
@@ -130,12 +135,16 @@ impl CharacterBehavior for Data {
                 (self.charge_timer.as_secs_f32() / self.charge_duration.as_secs_f32()).min(1.0);
             // Fire
             let mut projectile = Projectile {
-                hit_solid: vec![projectile::Effect::Stick],
+                hit_solid: vec![
+                    projectile::Effect::Stick,
+                    projectile::Effect::Recover(projectile::ARROW_ITEM.to_string()),
+                ],
                 hit_entity: vec![
                     projectile::Effect::Damage(
                         -(self.initial_damage as i32
                             + (charge_amount * (self.max_damage - self.initial_damage) as f32)
                                 as i32),
+                        DamageKind::Piercing,
                     ),
                     projectile::Effect::Knockback(
                         self.initial_knockback
@@ -146,6 +155,9 @@ impl CharacterBehavior for Data {
                 time_left: Duration::from_secs(15),
                 owner: None,
                 ignore_group: true,
+                drag: projectile::ARROW_DRAG,
+                penetration: (charge_amount * MAX_PENETRATION as f32) as u32,
+                hit_entities: Vec::new(),
             };
             projectile.owner = Some(*data.uid);
             update.server_events.push_front(ServerEvent::Shoot {
@@ -190,7 +202,7 @@ impl CharacterBehavior for Data {
                 charge_duration: self.charge_duration,
                 recover_duration: self
                     .recover_duration
-                    .checked_sub(Duration::from_secs_f32(data.dt.0))
+                    .checked_sub(attack_dt(data))
                     .unwrap_or_default(),
                 projectile_body: self.projectile_body,
                 projectile_light: self.projectile_light,
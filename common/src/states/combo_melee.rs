@@ -1,5 +1,5 @@
 use crate::{
-    comp::{Attacking, CharacterState, EnergySource, StateUpdate},
+    comp::{Attacking, BaseDamage, CharacterState, EnergySource, StateUpdate},
     states::utils::*,
     sys::character_behavior::{CharacterBehavior, JoinData},
 };
@@ -107,7 +107,8 @@ impl CharacterBehavior for Data {
                                 (1.0 + self.static_data.max_speed_increase
                                     * (1.0
                                         - self.static_data.speed_increase.powi(self.combo as i32)))
-                                    * data.dt.0,
+                                    * data.dt.0
+                                    * attack_speed_modifier(data),
                             ))
                             .unwrap_or_default(),
                         stage_section: self.stage_section,
@@ -126,11 +127,14 @@ impl CharacterBehavior for Data {
 
                     // Hit attempt
                     data.updater.insert(data.entity, Attacking {
-                        base_damage: self.static_data.stage_data[stage_index].max_damage.min(
-                            self.static_data.stage_data[stage_index].base_damage
-                                + self.combo / self.static_data.num_stages
-                                    * self.static_data.stage_data[stage_index].damage_increase,
-                        ),
+                        base_damage: BaseDamage {
+                            amount: self.static_data.stage_data[stage_index].max_damage.min(
+                                self.static_data.stage_data[stage_index].base_damage
+                                    + self.combo / self.static_data.num_stages
+                                        * self.static_data.stage_data[stage_index].damage_increase,
+                            ),
+                            kind: melee_damage_kind(data),
+                        },
                         base_heal: 0,
                         range: self.static_data.stage_data[stage_index].range,
                         max_angle: self.static_data.stage_data[stage_index].angle.to_radians(),
@@ -161,7 +165,8 @@ impl CharacterBehavior for Data {
                                 (1.0 + self.static_data.max_speed_increase
                                     * (1.0
                                         - self.static_data.speed_increase.powi(self.combo as i32)))
-                                    * data.dt.0,
+                                    * data.dt.0
+                                    * attack_speed_modifier(data),
                             ))
                             .unwrap_or_default(),
                         stage_section: self.stage_section,
@@ -197,7 +202,8 @@ impl CharacterBehavior for Data {
                                                 .static_data
                                                 .speed_increase
                                                 .powi(self.combo as i32)))
-                                        * data.dt.0,
+                                        * data.dt.0
+                                        * attack_speed_modifier(data),
                                 ))
                                 .unwrap_or_default(),
                             stage_section: self.stage_section,
@@ -217,7 +223,8 @@ impl CharacterBehavior for Data {
                                                 .static_data
                                                 .speed_increase
                                                 .powi(self.combo as i32)))
-                                        * data.dt.0,
+                                        * data.dt.0
+                                        * attack_speed_modifier(data),
                                 ))
                                 .unwrap_or_default(),
                             stage_section: self.stage_section,
@@ -0,0 +1,72 @@
+use super::utils::*;
+use crate::{
+    comp::{CharacterState, StateUpdate},
+    sys::character_behavior::{CharacterBehavior, JoinData},
+    terrain::Block,
+    vol::ReadVol,
+};
+use vek::*;
+
+/// Fraction of the standing collider height that remains while crouching
+pub const HEIGHT_FACTOR: f32 = 0.5;
+
+pub struct Data;
+
+/// Whether there is enough room above the entity to stand up again
+fn can_stand(data: &JoinData) -> bool {
+    let pos = data.pos.0 + Vec3::unit_z() * data.body.height() * HEIGHT_FACTOR;
+    data.terrain
+        .ray(pos, data.pos.0 + Vec3::unit_z() * data.body.height())
+        .until(Block::is_solid)
+        .cast()
+        .1
+        .map_or(true, |block| block.is_none())
+}
+
+impl CharacterBehavior for Data {
+    fn behavior(&self, data: &JoinData) -> StateUpdate {
+        let mut update = StateUpdate::from(data);
+
+        handle_move(data, &mut update, 0.3);
+
+        // Only leave the crouch when there is headroom, otherwise the collider
+        // would grow into the ceiling of a low tunnel
+        if can_stand(data) {
+            handle_jump(data, &mut update);
+            handle_wield(data, &mut update);
+            handle_climb(data, &mut update);
+            handle_dodge_input(data, &mut update);
+
+            // Try to Fall/Stand up/Move
+            if !data.physics.on_ground {
+                update.character = CharacterState::Idle;
+            }
+        }
+
+        update
+    }
+
+    fn wield(&self, data: &JoinData) -> StateUpdate {
+        let mut update = StateUpdate::from(data);
+        if can_stand(data) {
+            attempt_wield(data, &mut update);
+        }
+        update
+    }
+
+    fn sneak(&self, data: &JoinData) -> StateUpdate {
+        let mut update = StateUpdate::from(data);
+        if can_stand(data) {
+            attempt_sneak(data, &mut update);
+        }
+        update
+    }
+
+    fn stand(&self, data: &JoinData) -> StateUpdate {
+        let mut update = StateUpdate::from(data);
+        if can_stand(data) {
+            update.character = CharacterState::Idle;
+        }
+        update
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{
-    comp::{Attacking, CharacterState, EnergySource, StateUpdate},
+    comp::{Attacking, BaseDamage, CharacterState, EnergySource, StateUpdate},
     states::utils::*,
     sys::character_behavior::{CharacterBehavior, JoinData},
 };
@@ -80,10 +80,7 @@ impl CharacterBehavior for Data {
                     update.character = CharacterState::DashMelee(Data {
                         static_data: self.static_data,
                         end_charge: self.end_charge,
-                        timer: self
-                            .timer
-                            .checked_add(Duration::from_secs_f32(data.dt.0))
-                            .unwrap_or_default(),
+                        timer: self.timer.checked_add(attack_dt(data)).unwrap_or_default(),
                         stage_section: self.stage_section,
                         exhausted: self.exhausted,
                     });
@@ -121,7 +118,10 @@ impl CharacterBehavior for Data {
                             * charge_frac
                             + self.static_data.base_knockback;
                         data.updater.insert(data.entity, Attacking {
-                            base_damage: damage as u32,
+                            base_damage: BaseDamage {
+                                amount: damage as u32,
+                                kind: melee_damage_kind(data),
+                            },
                             base_heal: 0,
                             range: self.static_data.range,
                             max_angle: self.static_data.angle.to_radians(),
@@ -137,10 +137,7 @@ impl CharacterBehavior for Data {
                         update.character = CharacterState::DashMelee(Data {
                             static_data: self.static_data,
                             end_charge: self.end_charge,
-                            timer: self
-                                .timer
-                                .checked_add(Duration::from_secs_f32(data.dt.0))
-                                .unwrap_or_default(),
+                            timer: self.timer.checked_add(attack_dt(data)).unwrap_or_default(),
                             stage_section: StageSection::Charge,
                             exhausted: true,
                         })
@@ -149,10 +146,7 @@ impl CharacterBehavior for Data {
                             update.character = CharacterState::DashMelee(Data {
                                 static_data: self.static_data,
                                 end_charge: !self.static_data.infinite_charge,
-                                timer: self
-                                    .timer
-                                    .checked_add(Duration::from_secs_f32(data.dt.0))
-                                    .unwrap_or_default(),
+                                timer: self.timer.checked_add(attack_dt(data)).unwrap_or_default(),
                                 stage_section: StageSection::Charge,
                                 exhausted: false,
                             })
@@ -160,10 +154,7 @@ impl CharacterBehavior for Data {
                             update.character = CharacterState::DashMelee(Data {
                                 static_data: self.static_data,
                                 end_charge: self.end_charge,
-                                timer: self
-                                    .timer
-                                    .checked_add(Duration::from_secs_f32(data.dt.0))
-                                    .unwrap_or_default(),
+                                timer: self.timer.checked_add(attack_dt(data)).unwrap_or_default(),
                                 stage_section: StageSection::Charge,
                                 exhausted: false,
                             })
@@ -171,10 +162,7 @@ impl CharacterBehavior for Data {
                             update.character = CharacterState::DashMelee(Data {
                                 static_data: self.static_data,
                                 end_charge: !self.static_data.infinite_charge,
-                                timer: self
-                                    .timer
-                                    .checked_add(Duration::from_secs_f32(data.dt.0))
-                                    .unwrap_or_default(),
+                                timer: self.timer.checked_add(attack_dt(data)).unwrap_or_default(),
                                 stage_section: StageSection::Charge,
                                 exhausted: self.exhausted,
                             })
@@ -183,18 +171,16 @@ impl CharacterBehavior for Data {
                         update.character = CharacterState::DashMelee(Data {
                             static_data: self.static_data,
                             end_charge: !self.static_data.infinite_charge,
-                            timer: self
-                                .timer
-                                .checked_add(Duration::from_secs_f32(data.dt.0))
-                                .unwrap_or_default(),
+                            timer: self.timer.checked_add(attack_dt(data)).unwrap_or_default(),
                             stage_section: StageSection::Charge,
                             exhausted: self.exhausted,
                         })
                     }
 
                     // Consumes energy if there's enough left and charge has not stopped
-                    update.energy.change_by(
-                        -(self.static_data.energy_drain as f32 * data.dt.0) as i32,
+                    update.energy.change_by_rate(
+                        -(self.static_data.energy_drain as f32),
+                        data.dt.0,
                         EnergySource::Ability,
                     );
                 } else {
@@ -214,10 +200,7 @@ impl CharacterBehavior for Data {
                     update.character = CharacterState::DashMelee(Data {
                         static_data: self.static_data,
                         end_charge: self.end_charge,
-                        timer: self
-                            .timer
-                            .checked_add(Duration::from_secs_f32(data.dt.0))
-                            .unwrap_or_default(),
+                        timer: self.timer.checked_add(attack_dt(data)).unwrap_or_default(),
                         stage_section: self.stage_section,
                         exhausted: self.exhausted,
                     });
@@ -238,10 +221,7 @@ impl CharacterBehavior for Data {
                     update.character = CharacterState::DashMelee(Data {
                         static_data: self.static_data,
                         end_charge: self.end_charge,
-                        timer: self
-                            .timer
-                            .checked_add(Duration::from_secs_f32(data.dt.0))
-                            .unwrap_or_default(),
+                        timer: self.timer.checked_add(attack_dt(data)).unwrap_or_default(),
                         stage_section: self.stage_section,
                         exhausted: self.exhausted,
                     });
@@ -0,0 +1,69 @@
+use super::utils::*;
+use crate::{
+    comp::{CharacterState, Invulnerable, StateUpdate},
+    sys::character_behavior::{CharacterBehavior, JoinData},
+};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// How long a downed player lasts before bleeding out
+const BLEED_OUT_DURATION: Duration = Duration::from_secs(30);
+/// Movement speed efficiency while crawling around
+const CRAWL_EFFICIENCY: f32 = 0.15;
+/// How long players getting back up are shielded from damage, so that they
+/// aren't immediately downed again
+const GET_UP_INVULNERABILITY: f64 = 2.0;
+
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Data {
+    /// How long the player has been down for
+    pub timer: Duration,
+    /// How long the player can stay down before bleeding out
+    pub bleed_out_duration: Duration,
+}
+
+impl Default for Data {
+    fn default() -> Self {
+        Self {
+            timer: Duration::default(),
+            bleed_out_duration: BLEED_OUT_DURATION,
+        }
+    }
+}
+
+impl Data {
+    /// Whether the player has been down for too long to be revived
+    pub fn bled_out(&self) -> bool { self.timer >= self.bleed_out_duration }
+
+    /// How close the player is to bleeding out, from 0 to 1
+    pub fn bleed_out_progress(&self) -> f32 {
+        (self.timer.as_secs_f32() / self.bleed_out_duration.as_secs_f32()).min(1.0)
+    }
+}
+
+impl CharacterBehavior for Data {
+    fn behavior(&self, data: &JoinData) -> StateUpdate {
+        let mut update = StateUpdate::from(data);
+
+        // Downed players can only crawl until someone revives them
+        handle_move(data, &mut update, CRAWL_EFFICIENCY);
+
+        if data.stats.health.current() > 0 {
+            // Healed back up some other way
+            update.character = CharacterState::Idle;
+            data.updater.insert(data.entity, Invulnerable {
+                until: data.time.0 + GET_UP_INVULNERABILITY,
+            });
+        } else if !self.bled_out() {
+            update.character = CharacterState::Downed(Data {
+                timer: self
+                    .timer
+                    .checked_add(Duration::from_secs_f32(data.dt.0))
+                    .unwrap_or_default(),
+                ..*self
+            });
+        }
+
+        update
+    }
+}
@@ -0,0 +1,205 @@
+use crate::{
+    comp::{Attacking, BaseDamage, CharacterState, EnergySource, StateUpdate},
+    states::utils::*,
+    sys::character_behavior::{CharacterBehavior, JoinData},
+};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// A single swing with one of the two wielded weapons
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Strike {
+    /// Base damage of the swing
+    pub base_damage: u32,
+    /// Knockback of the swing
+    pub knockback: f32,
+    /// Range of the swing
+    pub range: f32,
+    /// Angle of the swing
+    pub angle: f32,
+    /// How long until the swing deals damage
+    pub buildup_duration: Duration,
+    /// How long the swing lasts
+    pub swing_duration: Duration,
+    /// How long until the next swing can start
+    pub recover_duration: Duration,
+}
+
+/// The hand a strike is made with
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Hand {
+    Main,
+    Off,
+}
+
+impl Hand {
+    pub fn other(self) -> Self {
+        match self {
+            Hand::Main => Hand::Off,
+            Hand::Off => Hand::Main,
+        }
+    }
+}
+
+/// Separated out to condense update portions of character state
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct StaticData {
+    /// Swing of the main hand weapon
+    pub main_strike: Strike,
+    /// Swing of the off hand weapon
+    pub off_strike: Strike,
+    /// Energy cost of each strike after the first
+    pub energy_cost: u32,
+    /// Speed gained with each consecutive strike
+    pub speed_increase: f32,
+    /// (100% + max_speed_increase) is the max attack speed
+    pub max_speed_increase: f32,
+    /// Whether the state can be interrupted by other abilities
+    pub is_interruptible: bool,
+}
+
+/// Alternating swings with a weapon in each hand, which speed up while the
+/// attack is held.
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Data {
+    /// Struct containing data that does not change over the course of the
+    /// character state
+    pub static_data: StaticData,
+    /// The hand the current strike is made with
+    pub hand: Hand,
+    /// Number of consecutive strikes
+    pub strikes: u32,
+    /// Timer for each stage
+    pub timer: Duration,
+    /// What section the character stage is in
+    pub stage_section: StageSection,
+    /// Whether the state can deal damage
+    pub exhausted: bool,
+}
+
+impl Data {
+    /// The strike currently being made
+    pub fn strike(&self) -> &Strike {
+        match self.hand {
+            Hand::Main => &self.static_data.main_strike,
+            Hand::Off => &self.static_data.off_strike,
+        }
+    }
+}
+
+impl CharacterBehavior for Data {
+    fn behavior(&self, data: &JoinData) -> StateUpdate {
+        let mut update = StateUpdate::from(data);
+
+        handle_orientation(data, &mut update, 1.0);
+        handle_move(data, &mut update, 0.5);
+        handle_jump(data, &mut update);
+
+        // Allows for other states to interrupt this state
+        if self.static_data.is_interruptible && !data.inputs.primary.is_pressed() {
+            handle_interrupt(data, &mut update);
+            match update.character {
+                CharacterState::DualStrike(_) => {},
+                _ => {
+                    return update;
+                },
+            }
+        }
+
+        let strike = *self.strike();
+        let speed = 1.0
+            + (self.strikes as f32 * self.static_data.speed_increase)
+                .min(self.static_data.max_speed_increase);
+        let timer = self
+            .timer
+            .checked_add(Duration::from_secs_f32(
+                data.dt.0 * speed * attack_speed_modifier(data),
+            ))
+            .unwrap_or_default();
+
+        match self.stage_section {
+            StageSection::Buildup => {
+                if self.timer < strike.buildup_duration {
+                    // Build up
+                    update.character = CharacterState::DualStrike(Data { timer, ..*self });
+                } else {
+                    // Transitions to swing section of stage
+                    update.character = CharacterState::DualStrike(Data {
+                        timer: Duration::default(),
+                        stage_section: StageSection::Swing,
+                        ..*self
+                    });
+                }
+            },
+            StageSection::Swing => {
+                if !self.exhausted {
+                    update.character = CharacterState::DualStrike(Data {
+                        timer: Duration::default(),
+                        exhausted: true,
+                        ..*self
+                    });
+                    // Hit attempt
+                    data.updater.insert(data.entity, Attacking {
+                        base_damage: BaseDamage {
+                            amount: strike.base_damage,
+                            kind: melee_damage_kind(data),
+                        },
+                        base_heal: 0,
+                        range: strike.range,
+                        max_angle: strike.angle.to_radians(),
+                        applied: false,
+                        hit_count: 0,
+                        knockback: strike.knockback,
+                    });
+                } else if self.timer < strike.swing_duration {
+                    // Swings
+                    update.character = CharacterState::DualStrike(Data { timer, ..*self });
+                } else {
+                    // Transitions to recover section of stage
+                    update.character = CharacterState::DualStrike(Data {
+                        timer: Duration::default(),
+                        stage_section: StageSection::Recover,
+                        ..*self
+                    });
+                }
+            },
+            StageSection::Recover => {
+                if self.timer < strike.recover_duration {
+                    // Recover
+                    update.character = CharacterState::DualStrike(Data { timer, ..*self });
+                } else if data.inputs.primary.is_pressed()
+                    && update
+                        .energy
+                        .try_change_by(
+                            -(self.static_data.energy_cost as i32),
+                            EnergySource::Ability,
+                        )
+                        .is_ok()
+                {
+                    // Strike again with the other hand
+                    update.character = CharacterState::DualStrike(Data {
+                        hand: self.hand.other(),
+                        strikes: self.strikes + 1,
+                        timer: Duration::default(),
+                        stage_section: StageSection::Buildup,
+                        exhausted: false,
+                        ..*self
+                    });
+                } else {
+                    // Done
+                    update.character = CharacterState::Wielding;
+                    // Make sure attack component is removed
+                    data.updater.remove::<Attacking>(data.entity);
+                }
+            },
+            _ => {
+                // If it somehow ends up in an incorrect stage section
+                update.character = CharacterState::Wielding;
+                // Make sure attack component is removed
+                data.updater.remove::<Attacking>(data.entity);
+            },
+        }
+
+        update
+    }
+}
@@ -0,0 +1,199 @@
+use crate::{
+    comp::{object, projectile, Body, CharacterState, Gravity, Projectile, StateUpdate},
+    event::ServerEvent,
+    sys::character_behavior::{CharacterBehavior, JoinData},
+    time::DayPeriod,
+};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// How far away water can be for a player to cast their line into it
+pub const FISHING_RANGE: f32 = 12.0;
+/// Shortest and longest time it takes for a fish to bite, before accounting
+/// for the time of day
+const MIN_BITE_SECS: f32 = 5.0;
+const MAX_BITE_SECS: f32 = 20.0;
+const BOBBER_SPEED: f32 = 12.0;
+
+/// Randomized time until a fish bites, which is shorter around dawn and dusk
+/// when fish are feeding and longer at night
+pub fn bite_time(time_of_day: f64, rng: &mut impl Rng) -> Duration {
+    let factor = match DayPeriod::from(time_of_day) {
+        DayPeriod::Morning | DayPeriod::Evening => 0.6,
+        DayPeriod::Noon => 1.0,
+        DayPeriod::Night => 1.5,
+    };
+    Duration::from_secs_f32(rng.gen_range(MIN_BITE_SECS, MAX_BITE_SECS) * factor)
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Stage {
+    /// Winding up to cast the bobber
+    Cast,
+    /// Waiting for a fish to bite
+    Wait,
+    /// A fish is biting, and gets away unless the line is reeled in quickly
+    Bite,
+    /// Reeling in the line
+    Reel,
+}
+
+/// Separated out to condense update portions of character state
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct StaticData {
+    /// How long it takes to cast the bobber
+    pub cast_duration: Duration,
+    /// How long until a fish bites once the bobber is cast
+    pub bite_time: Duration,
+    /// How long the player has to react to a bite before the fish gets away
+    pub reaction_duration: Duration,
+    /// How long it takes to reel in the line
+    pub reel_duration: Duration,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Data {
+    /// Struct containing data that does not change over the course of the
+    /// character state
+    pub static_data: StaticData,
+    /// Timer for each stage
+    pub timer: Duration,
+    /// What stage of fishing the character is in
+    pub stage: Stage,
+    /// Whether a fish is on the hook while reeling in
+    pub hooked: bool,
+}
+
+impl Data {
+    /// How much time is left to reel in a biting fish, from 1 when it bites
+    /// down to 0 when it gets away
+    pub fn bite_progress(&self) -> Option<f32> {
+        if self.stage == Stage::Bite {
+            Some(
+                1.0 - (self.timer.as_secs_f32() / self.static_data.reaction_duration.as_secs_f32())
+                    .min(1.0),
+            )
+        } else {
+            None
+        }
+    }
+
+    fn reel(&self, hooked: bool) -> CharacterState {
+        CharacterState::Fishing(Data {
+            timer: Duration::default(),
+            stage: Stage::Reel,
+            hooked,
+            ..*self
+        })
+    }
+}
+
+impl CharacterBehavior for Data {
+    fn behavior(&self, data: &JoinData) -> StateUpdate {
+        let mut update = StateUpdate::from(data);
+
+        // Moving away or jumping puts the rod away
+        if data.inputs.move_dir.magnitude_squared() > 0.0
+            || data.inputs.jump.is_pressed()
+            || !data.physics.on_ground
+        {
+            update.character = CharacterState::Idle;
+            return update;
+        }
+
+        let timer = self
+            .timer
+            .checked_add(Duration::from_secs_f32(data.dt.0))
+            .unwrap_or_default();
+
+        match self.stage {
+            Stage::Cast => {
+                if self.timer < self.static_data.cast_duration {
+                    // Winds up
+                    update.character = CharacterState::Fishing(Data { timer, ..*self });
+                } else {
+                    // Casts the bobber, which stays around until a biting fish would get away
+                    update.server_events.push_front(ServerEvent::Shoot {
+                        entity: data.entity,
+                        dir: data.inputs.look_dir,
+                        body: Body::Object(object::Body::Pouch),
+                        light: None,
+                        projectile: Projectile {
+                            hit_solid: vec![projectile::Effect::Stick],
+                            hit_entity: vec![projectile::Effect::Vanish],
+                            time_left: self.static_data.bite_time
+                                + self.static_data.reaction_duration,
+                            owner: Some(*data.uid),
+                            ignore_group: true,
+                            drag: projectile::DEFAULT_DRAG,
+                            penetration: 0,
+                            hit_entities: Vec::new(),
+                        },
+                        gravity: Some(Gravity(1.0)),
+                        speed: BOBBER_SPEED,
+                    });
+                    update.character = CharacterState::Fishing(Data {
+                        timer: Duration::default(),
+                        stage: Stage::Wait,
+                        ..*self
+                    });
+                }
+            },
+            Stage::Wait => {
+                if self.timer < self.static_data.bite_time {
+                    // Waits for a fish
+                    update.character = CharacterState::Fishing(Data { timer, ..*self });
+                } else {
+                    // A fish bites
+                    update.character = CharacterState::Fishing(Data {
+                        timer: Duration::default(),
+                        stage: Stage::Bite,
+                        ..*self
+                    });
+                }
+            },
+            Stage::Bite => {
+                if self.timer < self.static_data.reaction_duration {
+                    update.character = CharacterState::Fishing(Data { timer, ..*self });
+                } else {
+                    // The fish got away
+                    update.character = self.reel(false);
+                }
+            },
+            Stage::Reel => {
+                if self.timer < self.static_data.reel_duration {
+                    // Reels in
+                    update.character = CharacterState::Fishing(Data { timer, ..*self });
+                } else {
+                    // Done
+                    if self.hooked {
+                        update
+                            .server_events
+                            .push_front(ServerEvent::CatchFish(data.entity));
+                    }
+                    update.character = CharacterState::Idle;
+                }
+            },
+        }
+
+        update
+    }
+
+    /// Fishing again reels in the line, hooking the fish if one is biting
+    fn fish(&self, data: &JoinData) -> StateUpdate {
+        let mut update = StateUpdate::from(data);
+        match self.stage {
+            Stage::Wait => update.character = self.reel(false),
+            Stage::Bite => update.character = self.reel(true),
+            Stage::Cast | Stage::Reel => {},
+        }
+        update
+    }
+
+    fn stand(&self, data: &JoinData) -> StateUpdate {
+        let mut update = StateUpdate::from(data);
+        update.character = CharacterState::Idle;
+        update
+    }
+}
@@ -0,0 +1,121 @@
+use super::utils::*;
+use crate::{
+    comp::{CharacterState, StateUpdate},
+    sys::character_behavior::{CharacterBehavior, JoinData},
+};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// How long the wingbeats lifting off the ground last
+const TAKEOFF_DURATION: Duration = Duration::from_millis(800);
+/// How long it takes to fold the wings after touching down
+const LANDING_DURATION: Duration = Duration::from_millis(600);
+/// Upwards speed kept up while taking off
+const TAKEOFF_SPEED: f32 = 10.0;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Stage {
+    /// Beating the wings to get off the ground
+    Takeoff,
+    /// Flying freely, hovering in place when not moving
+    Airborne,
+    /// Touched down and folding the wings
+    Landing,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Data {
+    /// Which part of the flight the character is in
+    pub stage: Stage,
+    /// Time spent in the current stage
+    pub timer: Duration,
+}
+
+impl Data {
+    pub fn takeoff() -> Self {
+        Self {
+            stage: Stage::Takeoff,
+            timer: Duration::default(),
+        }
+    }
+
+    pub fn airborne() -> Self {
+        Self {
+            stage: Stage::Airborne,
+            timer: Duration::default(),
+        }
+    }
+
+    fn next(&self, data: &JoinData) -> Self {
+        Self {
+            stage: self.stage,
+            timer: self
+                .timer
+                .checked_add(Duration::from_secs_f32(data.dt.0))
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl CharacterBehavior for Data {
+    fn behavior(&self, data: &JoinData) -> StateUpdate {
+        let mut update = StateUpdate::from(data);
+
+        // Wings are of no use in deep water
+        if data
+            .physics
+            .in_fluid
+            .map(|depth| depth > 1.0)
+            .unwrap_or(false)
+        {
+            update.character = CharacterState::Idle;
+            return update;
+        }
+
+        match self.stage {
+            Stage::Takeoff => {
+                fly_move(data, &mut update, 0.3);
+                update.vel.0.z = update.vel.0.z.max(TAKEOFF_SPEED);
+
+                update.character = if self.timer < TAKEOFF_DURATION {
+                    CharacterState::Fly(self.next(data))
+                } else {
+                    CharacterState::Fly(Data::airborne())
+                };
+            },
+            Stage::Airborne => {
+                // Touching down without trying to rise lands
+                if data.physics.on_ground
+                    && !data.inputs.jump.is_pressed()
+                    && !data.inputs.swimup.is_pressed()
+                {
+                    update.character = CharacterState::Fly(Data {
+                        stage: Stage::Landing,
+                        timer: Duration::default(),
+                    });
+                    return update;
+                }
+
+                fly_move(data, &mut update, 1.0);
+                update.character = CharacterState::Fly(self.next(data));
+
+                handle_ability1_input(data, &mut update);
+                handle_ability2_input(data, &mut update);
+                handle_ability3_input(data, &mut update);
+            },
+            Stage::Landing => {
+                handle_move(data, &mut update, 0.3);
+
+                update.character = if data.inputs.jump.is_pressed() {
+                    CharacterState::Fly(Data::takeoff())
+                } else if self.timer < LANDING_DURATION {
+                    CharacterState::Fly(self.next(data))
+                } else {
+                    CharacterState::Idle
+                };
+            },
+        }
+
+        update
+    }
+}
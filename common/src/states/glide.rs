@@ -1,15 +1,42 @@
 use super::utils::handle_climb;
 use crate::{
-    comp::{CharacterState, StateUpdate},
-    sys::character_behavior::{CharacterBehavior, JoinData},
+    comp::{CharacterState, EnergySource, StateUpdate},
+    sys::{
+        character_behavior::{CharacterBehavior, JoinData},
+        phys::GRAVITY,
+    },
     util::Dir,
 };
 use serde::{Deserialize, Serialize};
-use vek::Vec2;
-// Gravity is 9.81 * 4, so this makes gravity equal to .15
-const GLIDE_ANTIGRAV: f32 = crate::sys::phys::GRAVITY * 0.90;
-const GLIDE_ACCEL: f32 = 12.0;
-const GLIDE_SPEED: f32 = 45.0;
+use vek::*;
+
+/// Airspeed below which the glider stalls and stops producing lift
+const STALL_SPEED: f32 = 8.0;
+/// Lift per squared unit of airspeed when flying level
+const LIFT_COEFF: f32 = 0.1;
+/// Change in lift per radian of angle of attack
+const AOA_LIFT: f32 = 2.5;
+/// Upper limit of the lift, as a multiple of gravity
+const MAX_LIFT: f32 = 2.0;
+/// Drag per squared unit of airspeed
+const DRAG_COEFF: f32 = 0.01;
+/// Extra drag caused by producing lift, as a portion of the lift
+const INDUCED_DRAG: f32 = 0.1;
+/// Steepest the glider can be pitched up or down, in radians
+const MAX_PITCH: f32 = 0.6;
+/// Pitch the nose drops to while stalled, in radians
+const STALL_PITCH: f32 = -0.8;
+/// Bank angle per radian between the flight heading and the look direction
+const ROLL_GAIN: f32 = 1.5;
+/// Steepest the glider can be banked, in radians
+const MAX_ROLL: f32 = 0.8;
+/// Worldgen temperature above which the ground heats the air enough to form
+/// thermals
+const THERMAL_TEMP: f32 = 0.4;
+/// Updraft acceleration over the hottest terrain
+const THERMAL_LIFT: f32 = 12.0;
+/// Energy used per second while pulling up
+const PULL_UP_ENERGY: f32 = 60.0;
 
 #[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize, Eq, Hash)]
 pub struct Data;
@@ -38,31 +65,80 @@ impl CharacterBehavior for Data {
         // climb
         handle_climb(&data, &mut update);
 
-        // Move player according to movement direction vector
-        update.vel.0 += Vec2::broadcast(data.dt.0)
-            * data.inputs.move_dir
-            * if data.vel.0.magnitude_squared() < GLIDE_SPEED.powf(2.0) {
-                GLIDE_ACCEL
+        let vel = update.vel.0;
+        let airspeed = vel.magnitude();
+        let vel_dir = vel.try_normalized().unwrap_or_else(Vec3::unit_z);
+        let path_pitch = vel_dir.z.max(-1.0).min(1.0).asin();
+        let stalled = airspeed < STALL_SPEED;
+
+        // Pitch the glider towards the look direction, dropping the nose when stalled
+        let mut pitch = if stalled {
+            STALL_PITCH
+        } else {
+            data.inputs
+                .look_dir
+                .z
+                .max(-1.0)
+                .min(1.0)
+                .asin()
+                .max(-MAX_PITCH)
+                .min(MAX_PITCH)
+        };
+        // Pulling up takes stamina
+        if !stalled && pitch > path_pitch {
+            if update.energy.current() > 0 {
+                update
+                    .energy
+                    .change_by_rate(-PULL_UP_ENERGY, data.dt.0, EnergySource::Glide);
             } else {
-                0.0
-            };
+                pitch = path_pitch;
+            }
+        }
+        let aoa = pitch - path_pitch;
+
+        // Bank into turns towards the look direction
+        let roll = match (
+            Vec2::<f32>::from(vel).try_normalized(),
+            Vec2::<f32>::from(*data.inputs.look_dir).try_normalized(),
+        ) {
+            (Some(heading), Some(target)) => {
+                let turn = (heading.x * target.y - heading.y * target.x).atan2(heading.dot(target));
+                (turn * ROLL_GAIN).max(-MAX_ROLL).min(MAX_ROLL)
+            },
+            _ => 0.0,
+        };
+
+        // Lift acts perpendicular to the flight path, tilted by the bank angle
+        let lift = if stalled {
+            0.0
+        } else {
+            (LIFT_COEFF * airspeed.powi(2) * (1.0 + AOA_LIFT * aoa))
+                .max(0.0)
+                .min(MAX_LIFT * GRAVITY)
+        };
+        let right = vel_dir
+            .cross(Vec3::unit_z())
+            .try_normalized()
+            .unwrap_or_else(Vec3::unit_x);
+        let up = right.cross(vel_dir);
+        let lift_dir = up * roll.cos() - right * roll.sin();
+        let drag = DRAG_COEFF * airspeed.powi(2) + INDUCED_DRAG * lift;
+
+        // Hot terrain produces thermals that carry the glider upwards
+        let thermal = data
+            .terrain
+            .get_key(data.terrain.pos_key(data.pos.0.map(|e| e.floor() as i32)))
+            .map_or(0.0, |chunk| {
+                ((chunk.meta().temp() - THERMAL_TEMP) / (1.0 - THERMAL_TEMP)).max(0.0)
+                    * THERMAL_LIFT
+            });
+
+        update.vel.0 += (lift_dir * lift - vel_dir * drag + Vec3::unit_z() * thermal) * data.dt.0;
 
         // Determine orientation vector from movement direction vector
         let ori_dir = Vec2::from(update.vel.0);
         update.ori.0 = Dir::slerp_to_vec3(update.ori.0, ori_dir.into(), 2.0 * data.dt.0);
 
-        // Apply Glide antigrav lift
-        if Vec2::<f32>::from(update.vel.0).magnitude_squared() < GLIDE_SPEED.powf(2.0)
-            && update.vel.0.z < 0.0
-        {
-            let lift = GLIDE_ANTIGRAV + update.vel.0.z.abs().powf(2.0) * 0.15;
-            update.vel.0.z += data.dt.0
-                * lift
-                * (Vec2::<f32>::from(update.vel.0).magnitude() * 0.075)
-                    .min(1.0)
-                    .max(0.2);
-        }
-
         update
     }
 
@@ -52,6 +52,12 @@ impl CharacterBehavior for Data {
         update
     }
 
+    fn crouch(&self, data: &JoinData) -> StateUpdate {
+        let mut update = StateUpdate::from(data);
+        attempt_crouch(data, &mut update);
+        update
+    }
+
     fn unwield(&self, data: &JoinData) -> StateUpdate {
         let mut update = StateUpdate::from(data);
         update.character = CharacterState::Idle;
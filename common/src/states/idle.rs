@@ -1,6 +1,7 @@
 use super::utils::*;
 use crate::{
-    comp::StateUpdate,
+    comp::{item::Instrument, StateUpdate},
+    sync::Uid,
     sys::character_behavior::{CharacterBehavior, JoinData},
 };
 
@@ -12,8 +13,10 @@ impl CharacterBehavior for Data {
 
         handle_move(data, &mut update, 1.0);
         handle_jump(data, &mut update);
+        handle_flight(data, &mut update);
         handle_wield(data, &mut update);
         handle_climb(data, &mut update);
+        handle_swim(data, &mut update);
         handle_dodge_input(data, &mut update);
 
         update
@@ -43,6 +46,12 @@ impl CharacterBehavior for Data {
         update
     }
 
+    fn crouch(&self, data: &JoinData) -> StateUpdate {
+        let mut update = StateUpdate::from(data);
+        attempt_crouch(data, &mut update);
+        update
+    }
+
     fn glide_wield(&self, data: &JoinData) -> StateUpdate {
         let mut update = StateUpdate::from(data);
         attempt_glide_wield(data, &mut update);
@@ -54,4 +63,28 @@ impl CharacterBehavior for Data {
         attempt_swap_loadout(data, &mut update);
         update
     }
+
+    fn throw(&self, data: &JoinData, slot: usize) -> StateUpdate {
+        let mut update = StateUpdate::from(data);
+        attempt_throw(data, &mut update, slot);
+        update
+    }
+
+    fn revive(&self, data: &JoinData, target: Uid) -> StateUpdate {
+        let mut update = StateUpdate::from(data);
+        attempt_revive(data, &mut update, target);
+        update
+    }
+
+    fn fish(&self, data: &JoinData) -> StateUpdate {
+        let mut update = StateUpdate::from(data);
+        attempt_fish(data, &mut update);
+        update
+    }
+
+    fn perform(&self, data: &JoinData, instrument: Instrument) -> StateUpdate {
+        let mut update = StateUpdate::from(data);
+        attempt_perform(data, &mut update, instrument);
+        update
+    }
 }
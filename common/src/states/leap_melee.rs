@@ -1,5 +1,5 @@
 use crate::{
-    comp::{Attacking, CharacterState, StateUpdate},
+    comp::{Attacking, BaseDamage, CharacterState, StateUpdate},
     states::utils::{StageSection, *},
     sys::character_behavior::{CharacterBehavior, JoinData},
 };
@@ -58,10 +58,7 @@ impl CharacterBehavior for Data {
                 // Wait for `buildup_duration` to expire
                 if self.timer < self.static_data.buildup_duration {
                     update.character = CharacterState::LeapMelee(Data {
-                        timer: self
-                            .timer
-                            .checked_add(Duration::from_secs_f32(data.dt.0))
-                            .unwrap_or_default(),
+                        timer: self.timer.checked_add(attack_dt(data)).unwrap_or_default(),
                         ..*self
                     });
                 } else {
@@ -103,10 +100,7 @@ impl CharacterBehavior for Data {
                     // outside if block and have else check for > movement
                     // duration * some multiplier
                     update.character = CharacterState::LeapMelee(Data {
-                        timer: self
-                            .timer
-                            .checked_add(Duration::from_secs_f32(data.dt.0))
-                            .unwrap_or_default(),
+                        timer: self.timer.checked_add(attack_dt(data)).unwrap_or_default(),
                         ..*self
                     });
                 } else if data.physics.on_ground {
@@ -122,10 +116,7 @@ impl CharacterBehavior for Data {
                 if self.timer < self.static_data.swing_duration {
                     // Swings weapons
                     update.character = CharacterState::LeapMelee(Data {
-                        timer: self
-                            .timer
-                            .checked_add(Duration::from_secs_f32(data.dt.0))
-                            .unwrap_or_default(),
+                        timer: self.timer.checked_add(attack_dt(data)).unwrap_or_default(),
                         ..*self
                     });
                 } else {
@@ -141,7 +132,10 @@ impl CharacterBehavior for Data {
                 if !self.exhausted {
                     // Hit attempt, when animation plays
                     data.updater.insert(data.entity, Attacking {
-                        base_damage: self.static_data.base_damage,
+                        base_damage: BaseDamage {
+                            amount: self.static_data.base_damage,
+                            kind: melee_damage_kind(data),
+                        },
                         base_heal: 0,
                         range: self.static_data.range,
                         max_angle: self.static_data.max_angle.to_radians(),
@@ -151,20 +145,14 @@ impl CharacterBehavior for Data {
                     });
 
                     update.character = CharacterState::LeapMelee(Data {
-                        timer: self
-                            .timer
-                            .checked_add(Duration::from_secs_f32(data.dt.0))
-                            .unwrap_or_default(),
+                        timer: self.timer.checked_add(attack_dt(data)).unwrap_or_default(),
                         exhausted: true,
                         ..*self
                     });
                 } else if self.timer < self.static_data.recover_duration {
                     // Complete recovery delay before finishing state
                     update.character = CharacterState::LeapMelee(Data {
-                        timer: self
-                            .timer
-                            .checked_add(Duration::from_secs_f32(data.dt.0))
-                            .unwrap_or_default(),
+                        timer: self.timer.checked_add(attack_dt(data)).unwrap_or_default(),
                         ..*self
                     });
                 } else {
@@ -3,22 +3,33 @@ pub mod basic_block;
 pub mod basic_melee;
 pub mod basic_ranged;
 pub mod boost;
+pub mod cast_spell;
+pub mod charge;
 pub mod charged_melee;
 pub mod charged_ranged;
 pub mod climb;
 pub mod combo_melee;
+pub mod crouch;
 pub mod dance;
 pub mod dash_melee;
+pub mod downed;
+pub mod dual_strike;
 pub mod equipping;
+pub mod fishing;
+pub mod fly;
 pub mod glide;
 pub mod glide_wield;
 pub mod idle;
 pub mod leap_melee;
+pub mod perform;
 pub mod repeater_ranged;
+pub mod revive;
 pub mod roll;
 pub mod shockwave;
 pub mod sit;
 pub mod sneak;
 pub mod spin_melee;
+pub mod swim;
+pub mod throw;
 pub mod utils;
 pub mod wielding;
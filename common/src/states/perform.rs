@@ -0,0 +1,167 @@
+use crate::{
+    comp::{item::Instrument, CharacterState, StateUpdate},
+    event::ServerEvent,
+    sys::character_behavior::{CharacterBehavior, JoinData},
+};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// How long a beat of a preset song lasts
+const BEAT_SECS: f32 = 0.35;
+/// Semitones above the lowest note of each note that can be played, making up
+/// a major scale
+const SCALE: [u8; 8] = [0, 2, 4, 5, 7, 9, 11, 12];
+/// How many different notes can be played
+pub const NOTE_COUNT: u8 = SCALE.len() as u8;
+
+/// How much faster than the lowest note a note is played, for shifting the
+/// sound of an instrument to the pitch of the note
+pub fn pitch(note: u8) -> f32 {
+    let semitones = SCALE[(note as usize).min(SCALE.len() - 1)];
+    2.0f32.powf(semitones as f32 / 12.0)
+}
+
+/// A tune performers can play without having to know the notes
+pub struct Song {
+    /// Localization key of the name of the song
+    pub name: &'static str,
+    /// Each note of the song and how many beats it is held for
+    pub notes: &'static [(u8, u8)],
+}
+
+#[rustfmt::skip]
+pub const SONGS: &[Song] = &[
+    Song {
+        name: "hud.perform.song.twinkle",
+        notes: &[
+            (0, 1), (0, 1), (4, 1), (4, 1), (5, 1), (5, 1), (4, 2),
+            (3, 1), (3, 1), (2, 1), (2, 1), (1, 1), (1, 1), (0, 2),
+        ],
+    },
+    Song {
+        name: "hud.perform.song.ode_to_joy",
+        notes: &[
+            (2, 1), (2, 1), (3, 1), (4, 1), (4, 1), (3, 1), (2, 1), (1, 1),
+            (0, 1), (0, 1), (1, 1), (2, 1), (2, 2), (1, 1), (1, 2),
+        ],
+    },
+    Song {
+        name: "hud.perform.song.tavern_jig",
+        notes: &[
+            (0, 1), (2, 1), (4, 1), (7, 2), (4, 1), (5, 1), (4, 1), (2, 2),
+            (3, 1), (5, 1), (4, 1), (2, 1), (1, 1), (0, 2),
+        ],
+    },
+];
+
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Data {
+    /// The instrument being played
+    pub instrument: Instrument,
+    /// The preset song being played, if any
+    pub song: Option<usize>,
+    /// The next note of the song to play
+    pub next_note: usize,
+    /// How long until the next note of the song is played
+    pub timer: Duration,
+}
+
+impl Data {
+    fn play(&self, data: &JoinData, update: &mut StateUpdate, note: u8) {
+        update.server_events.push_front(ServerEvent::PlayNote {
+            entity: data.entity,
+            instrument: self.instrument,
+            note,
+        });
+    }
+}
+
+impl CharacterBehavior for Data {
+    fn behavior(&self, data: &JoinData) -> StateUpdate {
+        let mut update = StateUpdate::from(data);
+
+        // Moving away or jumping stops the performance
+        if data.inputs.move_dir.magnitude_squared() > 0.0
+            || data.inputs.jump.is_pressed()
+            || !data.physics.on_ground
+        {
+            update.character = CharacterState::Idle;
+            return update;
+        }
+
+        if let Some(song) = self.song.and_then(|song| SONGS.get(song)) {
+            let dt = Duration::from_secs_f32(data.dt.0);
+            if self.timer > dt {
+                // Holds the current note
+                update.character = CharacterState::Perform(Data {
+                    timer: self.timer - dt,
+                    ..*self
+                });
+            } else if let Some((note, beats)) = song.notes.get(self.next_note) {
+                self.play(data, &mut update, *note);
+                update.character = CharacterState::Perform(Data {
+                    next_note: self.next_note + 1,
+                    timer: Duration::from_secs_f32(*beats as f32 * BEAT_SECS),
+                    ..*self
+                });
+            } else {
+                // The song is over, but the performer keeps their instrument
+                // out for the next one
+                update.character = CharacterState::Perform(Data {
+                    song: None,
+                    next_note: 0,
+                    timer: Duration::default(),
+                    ..*self
+                });
+            }
+        }
+
+        update
+    }
+
+    /// Playing a note by hand stops the song being played
+    fn play_note(&self, data: &JoinData, note: u8) -> StateUpdate {
+        let mut update = StateUpdate::from(data);
+        if note < NOTE_COUNT {
+            self.play(data, &mut update, note);
+            update.character = CharacterState::Perform(Data {
+                song: None,
+                next_note: 0,
+                timer: Duration::default(),
+                ..*self
+            });
+        }
+        update
+    }
+
+    fn play_song(&self, data: &JoinData, song: usize) -> StateUpdate {
+        let mut update = StateUpdate::from(data);
+        if song < SONGS.len() {
+            update.character = CharacterState::Perform(Data {
+                song: Some(song),
+                next_note: 0,
+                timer: Duration::default(),
+                ..*self
+            });
+        }
+        update
+    }
+
+    /// Switches to another instrument
+    fn perform(&self, data: &JoinData, instrument: Instrument) -> StateUpdate {
+        let mut update = StateUpdate::from(data);
+        update.character = CharacterState::Perform(Data {
+            instrument,
+            song: None,
+            next_note: 0,
+            timer: Duration::default(),
+        });
+        update
+    }
+
+    fn stand(&self, data: &JoinData) -> StateUpdate {
+        let mut update = StateUpdate::from(data);
+        update.character = CharacterState::Idle;
+        update
+    }
+}
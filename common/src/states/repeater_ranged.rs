@@ -67,10 +67,7 @@ impl CharacterBehavior for Data {
                     // Do movement
                     update.character = CharacterState::RepeaterRanged(Data {
                         static_data: self.static_data.clone(),
-                        timer: self
-                            .timer
-                            .checked_add(Duration::from_secs_f32(data.dt.0))
-                            .unwrap_or_default(),
+                        timer: self.timer.checked_add(attack_dt(data)).unwrap_or_default(),
                         stage_section: self.stage_section,
                         reps_remaining: self.reps_remaining,
                     });
@@ -93,10 +90,7 @@ impl CharacterBehavior for Data {
                     // Buildup to attack
                     update.character = CharacterState::RepeaterRanged(Data {
                         static_data: self.static_data.clone(),
-                        timer: self
-                            .timer
-                            .checked_add(Duration::from_secs_f32(data.dt.0))
-                            .unwrap_or_default(),
+                        timer: self.timer.checked_add(attack_dt(data)).unwrap_or_default(),
                         stage_section: self.stage_section,
                         reps_remaining: self.reps_remaining,
                     });
@@ -148,10 +142,7 @@ impl CharacterBehavior for Data {
                     // Shoot projectiles
                     update.character = CharacterState::RepeaterRanged(Data {
                         static_data: self.static_data.clone(),
-                        timer: self
-                            .timer
-                            .checked_add(Duration::from_secs_f32(data.dt.0))
-                            .unwrap_or_default(),
+                        timer: self.timer.checked_add(attack_dt(data)).unwrap_or_default(),
                         stage_section: self.stage_section,
                         reps_remaining: self.reps_remaining - 1,
                     });
@@ -159,10 +150,7 @@ impl CharacterBehavior for Data {
                     // Finish shooting
                     update.character = CharacterState::RepeaterRanged(Data {
                         static_data: self.static_data.clone(),
-                        timer: self
-                            .timer
-                            .checked_add(Duration::from_secs_f32(data.dt.0))
-                            .unwrap_or_default(),
+                        timer: self.timer.checked_add(attack_dt(data)).unwrap_or_default(),
                         stage_section: self.stage_section,
                         reps_remaining: self.reps_remaining,
                     });
@@ -184,10 +172,7 @@ impl CharacterBehavior for Data {
                     // Recover from attack
                     update.character = CharacterState::RepeaterRanged(Data {
                         static_data: self.static_data.clone(),
-                        timer: self
-                            .timer
-                            .checked_add(Duration::from_secs_f32(data.dt.0))
-                            .unwrap_or_default(),
+                        timer: self.timer.checked_add(attack_dt(data)).unwrap_or_default(),
                         stage_section: self.stage_section,
                         reps_remaining: self.reps_remaining,
                     });
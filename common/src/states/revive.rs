@@ -0,0 +1,77 @@
+use crate::{
+    comp::{CharacterState, StateUpdate},
+    event::ServerEvent,
+    sync::Uid,
+    sys::character_behavior::{CharacterBehavior, JoinData},
+};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// How close a player has to stay to a downed group member to revive them
+pub const REVIVE_RANGE: f32 = 3.0;
+
+/// Separated out to condense update portions of character state
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct StaticData {
+    /// Downed group member being revived
+    pub target: Uid,
+    /// How long reviving takes
+    pub channel_duration: Duration,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Data {
+    /// Struct containing data that does not change over the course of the
+    /// character state
+    pub static_data: StaticData,
+    /// How long the player has been reviving for
+    pub timer: Duration,
+}
+
+impl Data {
+    /// How far along the revive is, from 0 to 1
+    pub fn progress(&self) -> f32 {
+        (self.timer.as_secs_f32() / self.static_data.channel_duration.as_secs_f32()).min(1.0)
+    }
+}
+
+impl CharacterBehavior for Data {
+    fn behavior(&self, data: &JoinData) -> StateUpdate {
+        let mut update = StateUpdate::from(data);
+
+        // Moving away or jumping cancels the revive
+        if data.inputs.move_dir.magnitude_squared() > 0.0
+            || data.inputs.jump.is_pressed()
+            || !data.physics.on_ground
+        {
+            update.character = CharacterState::Idle;
+            return update;
+        }
+
+        if self.timer < self.static_data.channel_duration {
+            // Channels
+            update.character = CharacterState::Revive(Data {
+                timer: self
+                    .timer
+                    .checked_add(Duration::from_secs_f32(data.dt.0))
+                    .unwrap_or_default(),
+                ..*self
+            });
+        } else {
+            // Done
+            update.server_events.push_front(ServerEvent::Revive {
+                target: self.static_data.target,
+                by: *data.uid,
+            });
+            update.character = CharacterState::Idle;
+        }
+
+        update
+    }
+
+    fn stand(&self, data: &JoinData) -> StateUpdate {
+        let mut update = StateUpdate::from(data);
+        update.character = CharacterState::Idle;
+        update
+    }
+}
@@ -30,6 +30,8 @@ pub struct StaticData {
     pub shockwave_duration: Duration,
     /// Whether the shockwave requires the target to be on the ground
     pub requires_ground: bool,
+    /// Whether the shockwave can hit members of the caster's group
+    pub friendly_fire: bool,
     /// Movement speed efficiency
     pub move_efficiency: f32,
 }
@@ -57,10 +59,7 @@ impl CharacterBehavior for Data {
                     // Build up
                     update.character = CharacterState::Shockwave(Data {
                         static_data: self.static_data,
-                        timer: self
-                            .timer
-                            .checked_add(Duration::from_secs_f32(data.dt.0))
-                            .unwrap_or_default(),
+                        timer: self.timer.checked_add(attack_dt(data)).unwrap_or_default(),
                         stage_section: self.stage_section,
                     });
                 } else {
@@ -73,6 +72,8 @@ impl CharacterBehavior for Data {
                         damage: self.static_data.damage,
                         knockback: self.static_data.knockback,
                         requires_ground: self.static_data.requires_ground,
+                        friendly_fire: self.static_data.friendly_fire,
+                        true_damage: false,
                         owner: Some(*data.uid),
                     };
                     update.server_events.push_front(ServerEvent::Shockwave {
@@ -94,10 +95,7 @@ impl CharacterBehavior for Data {
                     // Swings
                     update.character = CharacterState::Shockwave(Data {
                         static_data: self.static_data,
-                        timer: self
-                            .timer
-                            .checked_add(Duration::from_secs_f32(data.dt.0))
-                            .unwrap_or_default(),
+                        timer: self.timer.checked_add(attack_dt(data)).unwrap_or_default(),
                         stage_section: self.stage_section,
                     });
                 } else {
@@ -114,10 +112,7 @@ impl CharacterBehavior for Data {
                     // Recovers
                     update.character = CharacterState::Shockwave(Data {
                         static_data: self.static_data,
-                        timer: self
-                            .timer
-                            .checked_add(Duration::from_secs_f32(data.dt.0))
-                            .unwrap_or_default(),
+                        timer: self.timer.checked_add(attack_dt(data)).unwrap_or_default(),
                         stage_section: self.stage_section,
                     });
                 } else {
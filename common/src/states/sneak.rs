@@ -42,6 +42,12 @@ impl CharacterBehavior for Data {
         update
     }
 
+    fn crouch(&self, data: &JoinData) -> StateUpdate {
+        let mut update = StateUpdate::from(data);
+        attempt_crouch(data, &mut update);
+        update
+    }
+
     fn glide_wield(&self, data: &JoinData) -> StateUpdate {
         let mut update = StateUpdate::from(data);
         attempt_glide_wield(data, &mut update);
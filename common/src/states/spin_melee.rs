@@ -1,5 +1,5 @@
 use crate::{
-    comp::{Attacking, CharacterState, EnergySource, StateUpdate},
+    comp::{Attacking, BaseDamage, CharacterState, EnergySource, StateUpdate},
     states::utils::*,
     sys::character_behavior::{CharacterBehavior, JoinData},
 };
@@ -7,6 +7,13 @@ use serde::{Deserialize, Serialize};
 use std::time::Duration;
 use vek::Vec3;
 
+/// How much slower the spin is while in water
+const WATER_SLOWDOWN: f32 = 0.5;
+/// Downward speed needed when landing for a falling spin to deal bonus damage
+const MIN_LANDING_SPEED: f32 = 8.0;
+/// Downward speed at which a landing falling spin deals double damage
+const MAX_LANDING_SPEED: f32 = 25.0;
+
 /// Separated out to condense update portions of character state
 #[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct StaticData {
@@ -49,14 +56,61 @@ pub struct Data {
     pub stage_section: StageSection,
     /// Whether the state can deal damage
     pub exhausted: bool,
+    /// Highest downward speed reached while spinning through the air, zero
+    /// while on the ground
+    pub fall_speed: f32,
 }
 
 impl CharacterBehavior for Data {
     fn behavior(&self, data: &JoinData) -> StateUpdate {
         let mut update = StateUpdate::from(data);
 
+        // Water drags on the spin
+        let speed_mult = if data.physics.in_fluid.is_some() {
+            WATER_SLOWDOWN
+        } else {
+            1.0
+        };
+        let dt = Duration::from_secs_f32(data.dt.0 * speed_mult * attack_speed_modifier(data));
+
+        // Spinning off an edge turns into a falling spin
+        let in_air = !data.physics.on_ground && data.physics.in_fluid.is_none();
+        let fall_speed = if in_air {
+            self.fall_speed.max(-data.vel.0.z)
+        } else {
+            0.0
+        };
+
         if self.static_data.is_helicopter {
-            update.vel.0 = Vec3::new(data.inputs.move_dir.x, data.inputs.move_dir.y, 0.0) * 5.0;
+            // Keep falling when spinning through the air
+            let vel_z = if in_air { update.vel.0.z } else { 0.0 };
+            update.vel.0 = Vec3::new(
+                data.inputs.move_dir.x * 5.0 * speed_mult,
+                data.inputs.move_dir.y * 5.0 * speed_mult,
+                vel_z,
+            );
+        }
+
+        // Landing a falling spin hits harder the faster it fell
+        if data.physics.on_ground
+            && self.fall_speed >= MIN_LANDING_SPEED
+            && self.stage_section == StageSection::Swing
+        {
+            let bonus = ((self.fall_speed - MIN_LANDING_SPEED)
+                / (MAX_LANDING_SPEED - MIN_LANDING_SPEED))
+                .min(1.0);
+            data.updater.insert(data.entity, Attacking {
+                base_damage: BaseDamage {
+                    amount: (self.static_data.base_damage as f32 * (1.0 + bonus)) as u32,
+                    kind: melee_damage_kind(data),
+                },
+                base_heal: 0,
+                range: self.static_data.range,
+                max_angle: 180_f32.to_radians(),
+                applied: false,
+                hit_count: 0,
+                knockback: self.static_data.knockback * (1.0 + bonus),
+            });
         }
 
         // Allows for other states to interrupt this state
@@ -76,13 +130,11 @@ impl CharacterBehavior for Data {
                     // Build up
                     update.character = CharacterState::SpinMelee(Data {
                         static_data: self.static_data,
-                        timer: self
-                            .timer
-                            .checked_add(Duration::from_secs_f32(data.dt.0))
-                            .unwrap_or_default(),
+                        timer: self.timer.checked_add(dt).unwrap_or_default(),
                         spins_remaining: self.spins_remaining,
                         stage_section: self.stage_section,
                         exhausted: self.exhausted,
+                        fall_speed,
                     });
                 } else {
                     // Transitions to swing section of stage
@@ -92,6 +144,7 @@ impl CharacterBehavior for Data {
                         spins_remaining: self.spins_remaining,
                         stage_section: StageSection::Swing,
                         exhausted: self.exhausted,
+                        fall_speed,
                     });
                 }
             },
@@ -103,10 +156,14 @@ impl CharacterBehavior for Data {
                         spins_remaining: self.spins_remaining,
                         stage_section: self.stage_section,
                         exhausted: true,
+                        fall_speed,
                     });
                     // Hit attempt
                     data.updater.insert(data.entity, Attacking {
-                        base_damage: self.static_data.base_damage,
+                        base_damage: BaseDamage {
+                            amount: self.static_data.base_damage,
+                            kind: melee_damage_kind(data),
+                        },
                         base_heal: 0,
                         range: self.static_data.range,
                         max_angle: 180_f32.to_radians(),
@@ -116,20 +173,23 @@ impl CharacterBehavior for Data {
                     });
                 } else if self.timer < self.static_data.swing_duration {
                     if !self.static_data.is_helicopter {
-                        forward_move(data, &mut update, 0.1, self.static_data.forward_speed);
+                        forward_move(
+                            data,
+                            &mut update,
+                            0.1,
+                            self.static_data.forward_speed * speed_mult,
+                        );
                         handle_orientation(data, &mut update, 1.0);
                     }
 
                     // Swings
                     update.character = CharacterState::SpinMelee(Data {
                         static_data: self.static_data,
-                        timer: self
-                            .timer
-                            .checked_add(Duration::from_secs_f32(data.dt.0))
-                            .unwrap_or_default(),
+                        timer: self.timer.checked_add(dt).unwrap_or_default(),
                         spins_remaining: self.spins_remaining,
                         stage_section: self.stage_section,
                         exhausted: self.exhausted,
+                        fall_speed,
                     });
                 } else if update.energy.current() >= self.static_data.energy_cost
                     && (self.spins_remaining != 0
@@ -146,6 +206,7 @@ impl CharacterBehavior for Data {
                         spins_remaining: new_spins_remaining,
                         stage_section: self.stage_section,
                         exhausted: false,
+                        fall_speed,
                     });
                     // Consumes energy if there's enough left and RMB is held down
                     update.energy.change_by(
@@ -160,6 +221,7 @@ impl CharacterBehavior for Data {
                         spins_remaining: self.spins_remaining,
                         stage_section: StageSection::Recover,
                         exhausted: self.exhausted,
+                        fall_speed,
                     });
                 }
             },
@@ -168,13 +230,11 @@ impl CharacterBehavior for Data {
                     // Recover
                     update.character = CharacterState::SpinMelee(Data {
                         static_data: self.static_data,
-                        timer: self
-                            .timer
-                            .checked_add(Duration::from_secs_f32(data.dt.0))
-                            .unwrap_or_default(),
+                        timer: self.timer.checked_add(dt).unwrap_or_default(),
                         spins_remaining: self.spins_remaining,
                         stage_section: self.stage_section,
                         exhausted: self.exhausted,
+                        fall_speed,
                     });
                 } else {
                     // Done
@@ -191,6 +251,14 @@ impl CharacterBehavior for Data {
             },
         }
 
+        // Don't force the spin into walls
+        if let Some(wall_dir) = data.physics.on_wall {
+            let into_wall = update.vel.0.dot(wall_dir);
+            if into_wall > 0.0 {
+                update.vel.0 -= wall_dir * into_wall / wall_dir.magnitude_squared();
+            }
+        }
+
         update
     }
 }
@@ -0,0 +1,192 @@
+use super::utils::*;
+use crate::{
+    comp::{CharacterState, EnergySource, Invulnerable, StateUpdate},
+    sys::character_behavior::{CharacterBehavior, JoinData},
+    util::Dir,
+};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use vek::*;
+
+/// How long it takes to come out of a dive and start swimming
+const DIVE_DURATION: Duration = Duration::from_millis(700);
+/// Fraction of their speed that a diving character loses each second
+const DIVE_DRAG: f32 = 1.5;
+/// How much faster than normal swimming fast is
+const SPRINT_EFFICIENCY: f32 = 2.0;
+/// Energy used up each second while swimming fast
+const SPRINT_ENERGY: f32 = 90.0;
+/// How long a dodge under water lasts, and how fast it is
+const DODGE_DURATION: Duration = Duration::from_millis(450);
+const DODGE_SPEED: f32 = 16.0;
+/// Energy used up by a dodge under water
+const DODGE_ENERGY: i32 = 150;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Stage {
+    /// Plunging into the water after jumping or falling in
+    Dive,
+    /// Swimming fast, which uses up energy
+    Sprint,
+    /// Darting aside under water to get out of the way of an attack
+    Dodge,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Data {
+    /// What the character is doing in the water
+    pub stage: Stage,
+    /// Time spent in the stage
+    pub timer: Duration,
+    /// Direction of a dodge
+    pub dir: Vec3<f32>,
+    /// Whether the character had a weapon out, which they go back to holding
+    pub was_wielded: bool,
+}
+
+impl Data {
+    pub fn dive(was_wielded: bool) -> Self {
+        Self {
+            stage: Stage::Dive,
+            timer: Duration::default(),
+            dir: Vec3::zero(),
+            was_wielded,
+        }
+    }
+
+    pub fn sprint(was_wielded: bool) -> Self {
+        Self {
+            stage: Stage::Sprint,
+            timer: Duration::default(),
+            dir: Vec3::zero(),
+            was_wielded,
+        }
+    }
+
+    /// Dodges in the direction the character is trying to move, or forward if
+    /// they aren't moving
+    pub fn dodge(data: &JoinData, was_wielded: bool) -> Self {
+        let rise = match (
+            data.inputs.swimup.is_pressed(),
+            data.inputs.swimdown.is_pressed(),
+        ) {
+            (true, false) => 1.0,
+            (false, true) => -1.0,
+            _ => 0.0,
+        };
+        let dir = Vec3::from(data.inputs.move_dir)
+            .with_z(rise)
+            .try_normalized()
+            .unwrap_or_else(|| *data.ori.0);
+        Self {
+            stage: Stage::Dodge,
+            timer: Duration::default(),
+            dir,
+            was_wielded,
+        }
+    }
+
+    fn next(&self, data: &JoinData) -> Self {
+        Self {
+            timer: self
+                .timer
+                .checked_add(Duration::from_secs_f32(data.dt.0))
+                .unwrap_or_default(),
+            ..*self
+        }
+    }
+
+    /// Goes back to what the character was doing before
+    fn exit(&self) -> CharacterState {
+        if self.was_wielded {
+            CharacterState::Wielding
+        } else {
+            CharacterState::Idle
+        }
+    }
+}
+
+impl CharacterBehavior for Data {
+    fn behavior(&self, data: &JoinData) -> StateUpdate {
+        let mut update = StateUpdate::from(data);
+
+        // Climbing out of the water or getting stranded in the shallows ends
+        // the swim
+        if !data
+            .physics
+            .in_fluid
+            .map_or(false, |depth| depth > SWIM_DEPTH)
+        {
+            update.character = self.exit();
+            return update;
+        }
+
+        match self.stage {
+            Stage::Dive => {
+                // Divers keep their momentum, slowed by the water, and can barely
+                // steer until they come out of the dive
+                update.vel.0 *= (1.0 - DIVE_DRAG * data.dt.0).max(0.0);
+                handle_move(data, &mut update, 0.2);
+
+                update.character = if self.timer < DIVE_DURATION {
+                    CharacterState::Swim(self.next(data))
+                } else {
+                    self.exit()
+                };
+            },
+            Stage::Sprint => {
+                let energy = (SPRINT_ENERGY * data.dt.0).ceil() as i32;
+                if !data.inputs.swimsprint.is_pressed()
+                    || update
+                        .energy
+                        .try_change_by(-energy, EnergySource::Swim)
+                        .is_err()
+                {
+                    update.character = self.exit();
+                    return update;
+                }
+
+                handle_move(data, &mut update, SPRINT_EFFICIENCY);
+                update.character = CharacterState::Swim(self.next(data));
+                handle_swim_dodge(data, &mut update);
+            },
+            Stage::Dodge => {
+                update.vel.0 = self.dir * DODGE_SPEED;
+                if !self.dir.xy().is_approx_zero() {
+                    update.ori.0 =
+                        Dir::slerp_to_vec3(update.ori.0, self.dir.xy().into(), 9.0 * data.dt.0);
+                }
+
+                update.character = if self.timer < DODGE_DURATION {
+                    CharacterState::Swim(self.next(data))
+                } else {
+                    update.vel.0 *= 0.3;
+                    self.exit()
+                };
+            },
+        }
+
+        update
+    }
+}
+
+/// Dodges under water if the dodge input is pressed and there is energy for
+/// it. Attacks pass harmlessly through the dodging character, as they do
+/// through a rolling one.
+pub fn handle_swim_dodge(data: &JoinData, update: &mut StateUpdate) {
+    if data.inputs.roll.is_pressed()
+        && update
+            .energy
+            .try_change_by(-DODGE_ENERGY, EnergySource::Swim)
+            .is_ok()
+    {
+        let was_wielded = match &update.character {
+            CharacterState::Swim(swim) => swim.was_wielded,
+            character => character.is_wield(),
+        };
+        update.character = CharacterState::Swim(Data::dodge(data, was_wielded));
+        data.updater.insert(data.entity, Invulnerable {
+            until: data.time.0 + DODGE_DURATION.as_secs_f64(),
+        });
+    }
+}
@@ -0,0 +1,125 @@
+use crate::{
+    comp::{CharacterState, StateUpdate},
+    event::ServerEvent,
+    states::utils::*,
+    sys::{
+        character_behavior::{CharacterBehavior, JoinData},
+        phys::GRAVITY,
+    },
+    util::Dir,
+};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use vek::*;
+
+/// Speed at which thrown items leave the hand
+pub const THROW_SPEED: f32 = 25.0;
+/// Gravity multiplier applied to thrown items
+pub const THROW_GRAVITY: f32 = 1.0;
+/// How far above the thrower's feet the item is released
+pub const THROW_HEIGHT: f32 = 1.5;
+
+/// Separated out to condense update portions of character state
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct StaticData {
+    /// Inventory slot of the item being thrown
+    pub slot: usize,
+    /// How long the item is wound up before release
+    pub buildup_duration: Duration,
+    /// How long the state has until exiting
+    pub recover_duration: Duration,
+    /// Had weapon
+    pub was_wielded: bool,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Data {
+    /// Struct containing data that does not change over the course of the
+    /// character state
+    pub static_data: StaticData,
+    /// Timer for each stage
+    pub timer: Duration,
+    /// What section the character stage is in
+    pub stage_section: StageSection,
+}
+
+impl CharacterBehavior for Data {
+    fn behavior(&self, data: &JoinData) -> StateUpdate {
+        let mut update = StateUpdate::from(data);
+
+        handle_move(data, &mut update, 0.5);
+        handle_jump(data, &mut update);
+
+        match self.stage_section {
+            StageSection::Buildup => {
+                if self.timer < self.static_data.buildup_duration {
+                    // Wind up
+                    update.character = CharacterState::Throw(Data {
+                        timer: self
+                            .timer
+                            .checked_add(Duration::from_secs_f32(data.dt.0))
+                            .unwrap_or_default(),
+                        ..*self
+                    });
+                } else {
+                    // Release, the server takes the item from the inventory and only spawns
+                    // the projectile if it was still there
+                    update.server_events.push_front(ServerEvent::Throw {
+                        entity: data.entity,
+                        slot: self.static_data.slot,
+                        dir: data.inputs.look_dir,
+                    });
+
+                    // Transitions to recover
+                    update.character = CharacterState::Throw(Data {
+                        timer: Duration::default(),
+                        stage_section: StageSection::Recover,
+                        ..*self
+                    });
+                }
+            },
+            StageSection::Recover => {
+                if self.timer < self.static_data.recover_duration {
+                    // Recovers
+                    update.character = CharacterState::Throw(Data {
+                        timer: self
+                            .timer
+                            .checked_add(Duration::from_secs_f32(data.dt.0))
+                            .unwrap_or_default(),
+                        ..*self
+                    });
+                } else {
+                    // Done
+                    update.character = if self.static_data.was_wielded {
+                        CharacterState::Wielding
+                    } else {
+                        CharacterState::Idle
+                    };
+                }
+            },
+            _ => {
+                // If it somehow ends up in an incorrect stage section
+                update.character = CharacterState::Idle;
+            },
+        }
+
+        update
+    }
+}
+
+/// Point the thrown item is released from
+pub fn release_pos(pos: Vec3<f32>) -> Vec3<f32> { pos + Vec3::unit_z() * THROW_HEIGHT }
+
+/// Samples the trajectory of an item thrown from `pos` in direction `dir`,
+/// ignoring terrain. Used to preview the arc while winding up.
+pub fn arc_preview(pos: Vec3<f32>, dir: Dir, samples: usize, dt: f32) -> Vec<Vec3<f32>> {
+    let start = release_pos(pos);
+    let vel = *dir * THROW_SPEED;
+    let accel = Vec3::unit_z() * -GRAVITY * THROW_GRAVITY;
+    (0..samples)
+        .map(|i| {
+            let t = i as f32 * dt;
+            start + vel * t + accel * t * t * 0.5
+        })
+        .collect()
+}
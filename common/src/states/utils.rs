@@ -1,17 +1,29 @@
 use crate::{
     comp::{
-        item::{Hands, ItemKind, Tool},
-        Body, CharacterState, StateUpdate,
+        item::{Hands, Instrument, ItemKind, Tool},
+        turret, Body, CharacterState, DamageKind, Invulnerable, StateUpdate,
     },
-    event::LocalEvent,
+    event::{LocalEvent, ServerEvent},
     states::*,
+    sync::Uid,
     sys::{character_behavior::JoinData, phys::GRAVITY},
+    terrain::Block,
     util::Dir,
+    vol::ReadVol,
 };
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 use vek::*;
 
 pub const MOVEMENT_THRESHOLD_VEL: f32 = 3.0;
+/// Depth of water past which characters swim rather than wade
+pub const SWIM_DEPTH: f32 = 1.0;
+/// Downward speed past which plunging into deep water starts a dive
+const DIVE_SPEED: f32 = 6.0;
+/// How deep the water below a character has to be for them to dive into it
+const DIVE_DEPTH: f32 = 2.5;
+/// Rate at which vertical speed is bled off while hovering, per second
+const HOVER_BRAKE: f32 = 3.0;
 const BASE_HUMANOID_AIR_ACCEL: f32 = 8.0;
 const BASE_HUMANOID_WATER_ACCEL: f32 = 150.0;
 const BASE_HUMANOID_WATER_SPEED: f32 = 180.0;
@@ -42,6 +54,9 @@ impl Body {
             Body::BipedLarge(_) => 75.0,
             Body::Object(_) => 40.0,
             Body::Golem(_) => 60.0,
+            Body::Colossus(_) => 45.0,
+            Body::Arthropod(_) => 110.0,
+            Body::Turret(_) => 0.0,
             Body::Theropod(_) => 135.0,
             Body::QuadrupedLow(_) => 120.0,
         }
@@ -60,10 +75,28 @@ impl Body {
             Body::BipedLarge(_) => 12.0,
             Body::Object(_) => 5.0,
             Body::Golem(_) => 8.0,
+            Body::Colossus(_) => 4.0,
+            Body::Arthropod(_) => 18.0,
+            Body::Turret(body) => match body.species {
+                turret::Species::Ballista => 2.0,
+                turret::Species::MagicTurret => 6.0,
+                turret::Species::Statue => 1.5,
+            },
             Body::Theropod(_) => 35.0,
             Body::QuadrupedLow(_) => 12.0,
         }
     }
+
+    /// Acceleration of creatures able to fly while in the air, or `None` for
+    /// those that can't
+    pub fn fly_accel(&self) -> Option<f32> {
+        match self {
+            Body::Dragon(_) => Some(25.0),
+            _ => None,
+        }
+    }
+
+    pub fn can_fly(&self) -> bool { self.fly_accel().is_some() }
 }
 
 /// Handles updating `Components` to move player based on state of `JoinData`
@@ -82,7 +115,7 @@ fn basic_move(data: &JoinData, update: &mut StateUpdate, efficiency: f32) {
         data.body.base_accel()
     } else {
         BASE_HUMANOID_AIR_ACCEL
-    };
+    } * accel_modifier(data);
 
     update.vel.0 =
         update.vel.0 + Vec2::broadcast(data.dt.0) * data.inputs.move_dir * accel * efficiency;
@@ -96,7 +129,7 @@ pub fn forward_move(data: &JoinData, update: &mut StateUpdate, efficiency: f32,
         data.body.base_accel()
     } else {
         BASE_HUMANOID_AIR_ACCEL
-    };
+    } * accel_modifier(data);
 
     update.vel.0 += Vec2::broadcast(data.dt.0)
         * accel
@@ -105,6 +138,69 @@ pub fn forward_move(data: &JoinData, update: &mut StateUpdate, efficiency: f32,
     handle_orientation(data, update, data.body.base_ori_rate() * efficiency);
 }
 
+/// Updates components to move as if flying, rising or sinking when asked to
+/// and holding altitude otherwise
+pub fn fly_move(data: &JoinData, update: &mut StateUpdate, efficiency: f32) {
+    let accel = data.body.fly_accel().unwrap_or(BASE_HUMANOID_AIR_ACCEL) * accel_modifier(data);
+    let climb = match (
+        data.inputs.jump.is_pressed() || data.inputs.swimup.is_pressed(),
+        data.inputs.swimdown.is_pressed(),
+    ) {
+        (true, false) => 1.0,
+        (false, true) => -1.0,
+        _ => 0.0,
+    };
+
+    // Wingbeats cancel out gravity
+    update.vel.0.z += GRAVITY * data.dt.0;
+    if climb == 0.0 {
+        update.vel.0.z *= (1.0 - HOVER_BRAKE * data.dt.0).max(0.0);
+    }
+    update.vel.0 += Vec3::new(data.inputs.move_dir.x, data.inputs.move_dir.y, climb)
+        * data.dt.0
+        * accel
+        * efficiency;
+
+    handle_orientation(data, update, data.body.base_ori_rate());
+}
+
+/// Keeps creatures that can fly aloft while they are busy with something
+/// else, such as channeling an attack
+pub fn handle_hover(data: &JoinData, update: &mut StateUpdate) {
+    if data.body.can_fly() && !data.physics.on_ground && data.physics.in_fluid.is_none() {
+        update.vel.0.z += GRAVITY * data.dt.0;
+        update.vel.0.z *= (1.0 - HOVER_BRAKE * data.dt.0).max(0.0);
+    }
+}
+
+/// Creatures that can fly take to the air when they find themselves off the
+/// ground, rather than falling
+pub fn handle_flight(data: &JoinData, update: &mut StateUpdate) {
+    if data.body.can_fly()
+        && !data.physics.on_ground
+        && data.physics.in_fluid.is_none()
+        && matches!(update.character, CharacterState::Idle | CharacterState::Wielding)
+    {
+        update.character = CharacterState::Fly(fly::Data::airborne());
+    }
+}
+
+/// Multiplier for movement acceleration from affixes and buffs
+fn accel_modifier(data: &JoinData) -> f32 {
+    data.affixes.map_or(1.0, |a| a.accel_modifier())
+        * data.buffs.map_or(1.0, |b| b.movement_speed_modifier())
+}
+
+/// Multiplier for how fast attacks progress, from buffs such as being frozen
+pub fn attack_speed_modifier(data: &JoinData) -> f32 {
+    data.buffs.map_or(1.0, |b| b.attack_speed_modifier())
+}
+
+/// Time an attack progresses by this tick
+pub fn attack_dt(data: &JoinData) -> Duration {
+    Duration::from_secs_f32(data.dt.0 * attack_speed_modifier(data))
+}
+
 pub fn handle_orientation(data: &JoinData, update: &mut StateUpdate, rate: f32) {
     // Set direction based on move direction
     let ori_dir = if update.character.is_block() || update.character.is_attack() {
@@ -129,6 +225,7 @@ fn swim_move(data: &JoinData, update: &mut StateUpdate, efficiency: f32, depth:
         } else {
             0.0
         }
+        * data.buffs.map_or(1.0, |b| b.movement_speed_modifier())
         * efficiency;
 
     handle_orientation(data, update, if data.physics.on_ground { 9.0 } else { 2.0 });
@@ -146,6 +243,35 @@ fn swim_move(data: &JoinData, update: &mut StateUpdate, efficiency: f32, depth:
     }
 }
 
+/// Starts a dive when plunging into deep water, splashing it about, and swims
+/// fast when asked to
+pub fn handle_swim(data: &JoinData, update: &mut StateUpdate) {
+    let depth = match data.physics.in_fluid {
+        Some(depth) if data.body.is_humanoid() => depth,
+        _ => return,
+    };
+    let was_wielded = update.character.is_wield();
+
+    // Only characters that just broke the surface dive, rather than those
+    // already swimming downwards
+    if update.vel.0.z < -DIVE_SPEED && depth < DIVE_DEPTH {
+        let (dist, _) = data
+            .terrain
+            .ray(data.pos.0, data.pos.0 - Vec3::unit_z() * DIVE_DEPTH)
+            .until(|block: &Block| !block.is_liquid())
+            .cast();
+        if dist >= DIVE_DEPTH {
+            update.server_events.push_front(ServerEvent::Splash {
+                pos: data.pos.0 + Vec3::unit_z() * depth,
+                speed: update.vel.0.magnitude(),
+            });
+            update.character = CharacterState::Swim(swim::Data::dive(was_wielded));
+        }
+    } else if depth > SWIM_DEPTH && data.inputs.swimsprint.is_pressed() {
+        update.character = CharacterState::Swim(swim::Data::sprint(was_wielded));
+    }
+}
+
 /// First checks whether `primary`, `secondary` or `ability3` input is pressed,
 /// then attempts to go into Equipping state, otherwise Idle
 pub fn handle_wield(data: &JoinData, update: &mut StateUpdate) {
@@ -187,6 +313,12 @@ pub fn attempt_sneak(data: &JoinData, update: &mut StateUpdate) {
     }
 }
 
+pub fn attempt_crouch(data: &JoinData, update: &mut StateUpdate) {
+    if data.physics.on_ground && data.body.is_humanoid() {
+        update.character = CharacterState::Crouch;
+    }
+}
+
 /// Checks that player can `Climb` and updates `CharacterState` if so
 pub fn handle_climb(data: &JoinData, update: &mut StateUpdate) {
     if data.inputs.climb.is_some()
@@ -198,7 +330,7 @@ pub fn handle_climb(data: &JoinData, update: &mut StateUpdate) {
             .map(|depth| depth > 1.0)
             .unwrap_or(false)
         //&& update.vel.0.z < 0.0
-        && data.body.is_humanoid()
+        && data.body.can_climb()
         && update.energy.current() > 100
     {
         update.character = CharacterState::Climb;
@@ -227,6 +359,83 @@ pub fn attempt_glide_wield(data: &JoinData, update: &mut StateUpdate) {
     }
 }
 
+/// Checks that player can throw the item in `slot` and updates
+/// `CharacterState` if so
+pub fn attempt_throw(data: &JoinData, update: &mut StateUpdate, slot: usize) {
+    if data.physics.on_wall.is_none() && data.body.is_humanoid() {
+        update.character = CharacterState::Throw(throw::Data {
+            static_data: throw::StaticData {
+                slot,
+                buildup_duration: Duration::from_millis(600),
+                recover_duration: Duration::from_millis(300),
+                was_wielded: data.character.is_wield(),
+            },
+            timer: Duration::default(),
+            stage_section: StageSection::Buildup,
+        });
+    }
+}
+
+pub fn attempt_revive(data: &JoinData, update: &mut StateUpdate, target: Uid) {
+    if data.physics.on_ground && data.body.is_humanoid() {
+        update.character = CharacterState::Revive(revive::Data {
+            static_data: revive::StaticData {
+                target,
+                channel_duration: Duration::from_secs(5),
+            },
+            timer: Duration::default(),
+        });
+    }
+}
+
+/// Checks that there is water in reach of the player's line and starts fishing
+/// if so
+pub fn attempt_fish(data: &JoinData, update: &mut StateUpdate) {
+    if data.physics.on_ground
+        && !data
+            .physics
+            .in_fluid
+            .map(|depth| depth > 1.0)
+            .unwrap_or(false)
+        && data.body.is_humanoid()
+    {
+        let eye = data.pos.0 + Vec3::unit_z() * data.body.height() * 0.9;
+        let (_, block) = data
+            .terrain
+            .ray(eye, eye + *data.inputs.look_dir * fishing::FISHING_RANGE)
+            .until(|block: &Block| !block.is_air())
+            .cast();
+        if let Ok(Some(block)) = block {
+            if block.is_liquid() {
+                update.character = CharacterState::Fishing(fishing::Data {
+                    static_data: fishing::StaticData {
+                        cast_duration: Duration::from_millis(800),
+                        bite_time: fishing::bite_time(data.time_of_day.0, &mut rand::thread_rng()),
+                        reaction_duration: Duration::from_millis(1200),
+                        reel_duration: Duration::from_millis(600),
+                    },
+                    timer: Duration::default(),
+                    stage: fishing::Stage::Cast,
+                    hooked: false,
+                });
+            }
+        }
+    }
+}
+
+/// Checks that player is standing on dry ground and starts performing with
+/// `instrument` if so
+pub fn attempt_perform(data: &JoinData, update: &mut StateUpdate, instrument: Instrument) {
+    if data.physics.on_ground && data.physics.in_fluid.is_none() && data.body.is_humanoid() {
+        update.character = CharacterState::Perform(perform::Data {
+            instrument,
+            song: None,
+            next_note: 0,
+            timer: Duration::default(),
+        });
+    }
+}
+
 /// Checks that player can jump and sends jump event if so
 pub fn handle_jump(data: &JoinData, update: &mut StateUpdate) {
     if data.inputs.jump.is_pressed()
@@ -237,20 +446,32 @@ pub fn handle_jump(data: &JoinData, update: &mut StateUpdate) {
             .map(|depth| depth > 1.0)
             .unwrap_or(false)
     {
-        update
-            .local_events
-            .push_front(LocalEvent::Jump(data.entity));
+        // Creatures that can fly take off instead, unless they are busy attacking
+        if data.body.can_fly() {
+            if !update.character.is_attack() {
+                update.character = CharacterState::Fly(fly::Data::takeoff());
+            }
+        } else {
+            update
+                .local_events
+                .push_front(LocalEvent::Jump(data.entity));
+        }
     }
 }
 
-/// Will attempt to go into `loadout.active_item.ability1`
+/// Will attempt to go into `loadout.active_item.ability1`, or into a dual
+/// strike when wielding a weapon made for dual wielding in each hand
 pub fn handle_ability1_input(data: &JoinData, update: &mut StateUpdate) {
     if data.inputs.primary.is_pressed() {
-        if let Some(ability) = data
-            .loadout
-            .active_item
+        let dual_strike = data.loadout.dual_strike();
+        if let Some(ability) = dual_strike
             .as_ref()
-            .and_then(|i| i.ability1.as_ref())
+            .or_else(|| {
+                data.loadout
+                    .active_item
+                    .as_ref()
+                    .and_then(|i| i.ability1.as_ref())
+            })
             .filter(|ability| ability.requirements_paid(data, update))
         {
             update.character = (ability, AbilityKey::Mouse1).into();
@@ -320,6 +541,15 @@ pub fn handle_ability3_input(data: &JoinData, update: &mut StateUpdate) {
 /// Checks that player can perform a dodge, then
 /// attempts to go into `loadout.active_item.dodge_ability`
 pub fn handle_dodge_input(data: &JoinData, update: &mut StateUpdate) {
+    // There is no rolling in deep water, where characters dart aside instead
+    if data
+        .physics
+        .in_fluid
+        .map_or(false, |depth| depth > SWIM_DEPTH)
+    {
+        swim::handle_swim_dodge(data, update);
+        return;
+    }
     if data.inputs.roll.is_pressed() {
         if let Some(ability) = data
             .loadout
@@ -336,6 +566,12 @@ pub fn handle_dodge_input(data: &JoinData, update: &mut StateUpdate) {
             } else {
                 update.character = (ability, AbilityKey::Dodge).into();
             }
+            // Attacks pass harmlessly through a rolling character
+            if let CharacterState::Roll(roll) = &update.character {
+                data.updater.insert(data.entity, Invulnerable {
+                    until: data.time.0 + roll.remaining_duration.as_secs_f64(),
+                });
+            }
         }
     }
 }
@@ -348,6 +584,12 @@ pub fn unwrap_tool_data<'a>(data: &'a JoinData) -> Option<&'a Tool> {
     }
 }
 
+/// Kind of damage dealt by melee attacks with the active tool, which is
+/// crushing for bare hands
+pub fn melee_damage_kind(data: &JoinData) -> DamageKind {
+    unwrap_tool_data(data).map_or(DamageKind::Crushing, |tool| tool.kind.damage_kind())
+}
+
 pub fn handle_interrupt(data: &JoinData, update: &mut StateUpdate) {
     handle_ability1_input(data, update);
     handle_ability2_input(data, update);
@@ -1,6 +1,7 @@
 use super::utils::*;
 use crate::{
-    comp::{CharacterState, StateUpdate},
+    comp::{item::Instrument, CharacterState, StateUpdate},
+    sync::Uid,
     sys::character_behavior::{CharacterBehavior, JoinData},
 };
 
@@ -12,7 +13,9 @@ impl CharacterBehavior for Data {
 
         handle_move(&data, &mut update, 1.0);
         handle_jump(&data, &mut update);
+        handle_flight(&data, &mut update);
         handle_climb(&data, &mut update);
+        handle_swim(&data, &mut update);
         handle_ability1_input(&data, &mut update);
         handle_ability2_input(&data, &mut update);
         handle_ability3_input(&data, &mut update);
@@ -39,6 +42,12 @@ impl CharacterBehavior for Data {
         update
     }
 
+    fn crouch(&self, data: &JoinData) -> StateUpdate {
+        let mut update = StateUpdate::from(data);
+        attempt_crouch(data, &mut update);
+        update
+    }
+
     fn unwield(&self, data: &JoinData) -> StateUpdate {
         let mut update = StateUpdate::from(data);
         update.character = CharacterState::Idle;
@@ -56,4 +65,28 @@ impl CharacterBehavior for Data {
         attempt_swap_loadout(data, &mut update);
         update
     }
+
+    fn throw(&self, data: &JoinData, slot: usize) -> StateUpdate {
+        let mut update = StateUpdate::from(data);
+        attempt_throw(data, &mut update, slot);
+        update
+    }
+
+    fn revive(&self, data: &JoinData, target: Uid) -> StateUpdate {
+        let mut update = StateUpdate::from(data);
+        attempt_revive(data, &mut update, target);
+        update
+    }
+
+    fn fish(&self, data: &JoinData) -> StateUpdate {
+        let mut update = StateUpdate::from(data);
+        attempt_fish(data, &mut update);
+        update
+    }
+
+    fn perform(&self, data: &JoinData, instrument: Instrument) -> StateUpdate {
+        let mut update = StateUpdate::from(data);
+        attempt_perform(data, &mut update, instrument);
+        update
+    }
 }
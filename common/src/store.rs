@@ -1,3 +1,4 @@
+use rayon::prelude::*;
 use std::{
     cmp::{Eq, PartialEq},
     fmt, hash,
@@ -57,6 +58,14 @@ impl<T> Store<T> {
 
     pub fn values_mut(&mut self) -> impl Iterator<Item = &mut T> { self.items.iter_mut() }
 
+    /// Iterates over the items in parallel, in the order of their ids
+    pub fn par_values_mut(&mut self) -> impl IndexedParallelIterator<Item = &mut T>
+    where
+        T: Send,
+    {
+        self.items.par_iter_mut()
+    }
+
     pub fn iter(&self) -> impl Iterator<Item = (Id<T>, &T)> { self.ids().zip(self.values()) }
 
     pub fn iter_mut(&mut self) -> impl Iterator<Item = (Id<T>, &mut T)> {
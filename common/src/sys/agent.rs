@@ -1,13 +1,13 @@
 use crate::{
     comp::{
         self,
-        agent::Activity,
+        agent::{Activity, THREAT_SWITCH_RATIO},
         group,
         group::Invite,
         item::{tool::ToolKind, ItemKind},
         Agent, Alignment, Body, ControlAction, ControlEvent, Controller, Energy, GroupManip,
-        LightEmitter, Loadout, MountState, Ori, PhysicsState, Pos, Scale, Stats, UnresolvedChatMsg,
-        Vel,
+        Instance, LightEmitter, Loadout, MountState, Ori, PhysicsState, Pos, Scale, Stats, Threat,
+        UnresolvedChatMsg, Vel,
     },
     event::{EventBus, ServerEvent},
     metrics::SysMetrics,
@@ -60,6 +60,8 @@ impl<'a> System<'a> for Sys {
         ReadStorage<'a, Invite>,
         Read<'a, TimeOfDay>,
         ReadStorage<'a, LightEmitter>,
+        ReadStorage<'a, Instance>,
+        WriteStorage<'a, Threat>,
     );
 
     #[allow(clippy::or_fun_call)] // TODO: Pending review in #587
@@ -89,6 +91,8 @@ impl<'a> System<'a> for Sys {
             invites,
             time_of_day,
             light_emitter,
+            instances,
+            mut threats,
         ): Self::SystemData,
     ) {
         let start_time = std::time::Instant::now();
@@ -163,10 +167,10 @@ impl<'a> System<'a> for Sys {
                 let mut rng = thread_rng();
                 if day_period.is_dark() && !lantern_turned_on {
                     // Agents with turned off lanterns turn them on randomly once it's nighttime and
-                    // keep them on
+                    // keep them on, except for those on night patrol who light them right away
                     // Only emit event for agents that sill need to
                     // turn on their lantern
-                    if let 0 = rng.gen_range(0, 1000) {
+                    if agent.night_patrol || rng.gen_range(0, 1000) == 0 {
                         controller.events.push(ControlEvent::EnableLantern)
                     }
                 } else if lantern_turned_on && day_period.is_light() {
@@ -191,6 +195,12 @@ impl<'a> System<'a> for Sys {
             const SIGHT_DIST: f32 = 80.0;
             const MIN_ATTACK_DIST: f32 = 2.0;
             const MAX_FLEE_DIST: f32 = 20.0;
+            const STRAFE_DIST: f32 = 20.0;
+            const STRAFE_ALTITUDE: f32 = 12.0;
+            const BREATH_RANGE: f32 = 25.0;
+            // Length of the bearing of agents on patrol, which sets their walking speed
+            const PATROL_PACE: f32 = 0.6;
+            const TURRET_AIM_ANGLE: f32 = 5.0;
 
             let scale = scales.get(entity).map(|s| s.0).unwrap_or(1.0);
 
@@ -201,24 +211,58 @@ impl<'a> System<'a> for Sys {
             let node_tolerance = scale * 1.5;
             let slow_factor = body.map(|b| b.base_accel() / 250.0).unwrap_or(0.0).min(1.0);
 
+            // Stationary agents keep to the way they were mounted facing when spawned, and
+            // only pick targets within the arc they can turn through
+            let facing = if body.map_or(false, |b| b.is_stationary()) {
+                Some(*agent.facing.get_or_insert_with(|| {
+                    Vec2::from(*ori.0)
+                        .try_normalized()
+                        .unwrap_or_else(Vec2::unit_y)
+                }))
+            } else {
+                None
+            };
+            let within_arc = |tgt_pos: Vec3<f32>| match (body, facing) {
+                (Some(Body::Turret(turret)), Some(facing)) => Vec2::from(tgt_pos - pos.0)
+                    .try_normalized()
+                    .map_or(true, |dir| {
+                        dir.angle_between(facing) <= turret.arc().to_radians()
+                    }),
+                _ => true,
+            };
+            let search_dist = match body {
+                Some(Body::Turret(turret)) => turret.range(),
+                _ => SEARCH_DIST,
+            };
+
             let mut do_idle = false;
             let mut choose_target = false;
+            let patrolling = agent.night_patrol && day_period.is_dark();
 
             'activity: {
                 match &mut agent.activity {
                     Activity::Idle(bearing) => {
-                        *bearing += Vec2::new(
-                            thread_rng().gen::<f32>() - 0.5,
-                            thread_rng().gen::<f32>() - 0.5,
-                        ) * 0.1
-                            - *bearing * 0.003
-                            - agent.patrol_origin.map_or(Vec2::zero(), |patrol_origin| {
-                                (pos.0 - patrol_origin).xy() * 0.0002
-                            });
-
-                        // Stop if we're too close to a wall
-                        *bearing *= 0.1
-                            + if terrain
+                        if let Some(facing) = facing {
+                            // Stationary agents can't wander off, they only turn back to their
+                            // post
+                            inputs.move_dir = facing;
+                        } else {
+                            *bearing += Vec2::new(
+                                thread_rng().gen::<f32>() - 0.5,
+                                thread_rng().gen::<f32>() - 0.5,
+                            ) * 0.1
+                                - *bearing * 0.003
+                                - agent.patrol_origin.map_or(Vec2::zero(), |patrol_origin| {
+                                    (pos.0 - patrol_origin).xy() * 0.0002
+                                });
+
+                            // Patrols keep walking their rounds instead of slowing to a stop
+                            if patrolling && bearing.magnitude_squared() < PATROL_PACE.powi(2) {
+                                let dir = bearing.try_normalized().unwrap_or(Vec2::unit_y());
+                                *bearing = dir * PATROL_PACE;
+                            }
+
+                            let wall_ahead = terrain
                                 .ray(
                                     pos.0 + Vec3::unit_z(),
                                     pos.0
@@ -231,15 +275,25 @@ impl<'a> System<'a> for Sys {
                                 .until(Block::is_solid)
                                 .cast()
                                 .1
-                                .map_or(true, |b| b.is_none())
-                            {
-                                0.9
-                            } else {
-                                0.0
-                            };
+                                .map_or(false, |b| b.is_some());
+                            if wall_ahead {
+                                if patrolling {
+                                    // Turn to walk along the wall
+                                    *bearing = Vec2::new(-bearing.y, bearing.x);
+                                } else {
+                                    // Stop if we're too close to a wall
+                                    *bearing *= 0.1;
+                                }
+                            }
+
+                            if bearing.magnitude_squared() > 0.5f32.powf(2.0) {
+                                inputs.move_dir = *bearing * 0.65;
+                            }
 
-                        if bearing.magnitude_squared() > 0.5f32.powf(2.0) {
-                            inputs.move_dir = *bearing * 0.65;
+                            // Sit
+                            if !patrolling && thread_rng().gen::<f32>() < 0.0035 {
+                                controller.actions.push(ControlAction::Sit);
+                            }
                         }
 
                         // Put away weapon
@@ -247,11 +301,6 @@ impl<'a> System<'a> for Sys {
                             controller.actions.push(ControlAction::Unwield);
                         }
 
-                        // Sit
-                        if thread_rng().gen::<f32>() < 0.0035 {
-                            controller.actions.push(ControlAction::Sit);
-                        }
-
                         // Sometimes try searching for new targets
                         if thread_rng().gen::<f32>() < 0.1 {
                             choose_target = true;
@@ -306,6 +355,9 @@ impl<'a> System<'a> for Sys {
                             RangedPowerup,
                             Staff,
                             StoneGolemBoss,
+                            Charge,
+                            Dragon,
+                            Turret,
                         }
 
                         let tactic = match loadout.active_item.as_ref().and_then(|ic| {
@@ -321,7 +373,10 @@ impl<'a> System<'a> for Sys {
                             Some(ToolKind::Sword(_)) => Tactic::Sword,
                             Some(ToolKind::Axe(_)) => Tactic::Axe,
                             Some(ToolKind::NpcWeapon(kind)) => match kind.as_str() {
-                                "StoneGolemsFist" => Tactic::StoneGolemBoss,
+                                "StoneGolemsFist" | "ColossusFists" => Tactic::StoneGolemBoss,
+                                "BeastHorns" => Tactic::Charge,
+                                "DragonJaws" => Tactic::Dragon,
+                                "Ballista" | "TurretCrystal" | "StatueGaze" => Tactic::Turret,
                                 _ => Tactic::Melee,
                             },
                             _ => Tactic::Melee,
@@ -391,6 +446,65 @@ impl<'a> System<'a> for Sys {
                                 } else {
                                     do_idle = true;
                                 }
+                            } else if tactic == Tactic::Dragon && dist_sqrd < SIGHT_DIST.powf(2.0) {
+                                let flat = Vec2::<f32>::from(tgt_pos.0 - pos.0);
+                                let reach = body.map_or(0.0, |b| b.radius()) + MIN_ATTACK_DIST;
+                                if physics_state.on_ground && flat.magnitude() < reach {
+                                    // Bite whatever comes close while on the ground
+                                    inputs.primary.set_state(true);
+                                } else {
+                                    // Circle the target from the air, keeping at a distance
+                                    let toward = flat.try_normalized().unwrap_or(Vec2::unit_y());
+                                    let around = Vec2::new(-toward.y, toward.x);
+                                    let closing = ((flat.magnitude() - STRAFE_DIST) / STRAFE_DIST)
+                                        .max(-1.0)
+                                        .min(1.0);
+                                    inputs.move_dir = (around + toward * closing)
+                                        .try_normalized()
+                                        .unwrap_or(around);
+
+                                    // Take off, then hold altitude above the target
+                                    let altitude = pos.0.z - tgt_pos.0.z;
+                                    inputs.jump.set_state(physics_state.on_ground);
+                                    inputs.swimup.set_state(altitude < STRAFE_ALTITUDE - 2.0);
+                                    inputs.swimdown.set_state(altitude > STRAFE_ALTITUDE + 2.0);
+
+                                    // Breathe fire on every pass that comes within range
+                                    if dist_sqrd < BREATH_RANGE.powf(2.0) {
+                                        if *powerup < 3.0 {
+                                            inputs.secondary.set_state(true);
+                                        } else if *powerup > 6.0 {
+                                            *powerup = 0.0;
+                                        }
+                                        *powerup += dt.0;
+                                    }
+                                }
+                            } else if tactic == Tactic::Turret {
+                                let can_see_tgt = terrain
+                                    .ray(pos.0 + Vec3::unit_z(), tgt_pos.0 + Vec3::unit_z())
+                                    .until(Block::is_opaque)
+                                    .cast()
+                                    .0
+                                    .powf(2.0)
+                                    >= dist_sqrd;
+
+                                if dist_sqrd < search_dist.powf(2.0)
+                                    && within_arc(tgt_pos.0)
+                                    && can_see_tgt
+                                {
+                                    // Swivel towards the target, only shooting once lined up
+                                    let flat = Vec2::<f32>::from(tgt_pos.0 - pos.0)
+                                        .try_normalized()
+                                        .unwrap_or(Vec2::unit_y());
+                                    inputs.move_dir = flat;
+                                    if Vec2::<f32>::from(*ori.0).angle_between(flat)
+                                        < TURRET_AIM_ANGLE.to_radians()
+                                    {
+                                        inputs.primary.set_state(true);
+                                    }
+                                } else {
+                                    do_idle = true;
+                                }
                             } else if (tactic == Tactic::Staff
                                 && dist_sqrd < (5.0 * MIN_ATTACK_DIST * scale).powf(2.0))
                                 || dist_sqrd < (MIN_ATTACK_DIST * scale).powf(2.0)
@@ -406,7 +520,8 @@ impl<'a> System<'a> for Sys {
                                     Tactic::Sword
                                     | Tactic::Melee
                                     | Tactic::Hammer
-                                    | Tactic::StoneGolemBoss => inputs.primary.set_state(true),
+                                    | Tactic::StoneGolemBoss
+                                    | Tactic::Charge => inputs.primary.set_state(true),
                                     Tactic::Staff => {
                                         // Kind of arbitrary values, but feel right in game
                                         if energy.current() > 800 && thread_rng().gen::<f32>() > 0.8
@@ -476,6 +591,16 @@ impl<'a> System<'a> for Sys {
                                         } else {
                                             *powerup += dt.0;
                                         }
+                                    } else if let Tactic::Charge = tactic {
+                                        // Needs a run-up to be worth charging
+                                        if *powerup > 4.0
+                                            && dist_sqrd > (3.0 * MIN_ATTACK_DIST * scale).powf(2.0)
+                                        {
+                                            inputs.secondary.set_state(true);
+                                            *powerup = 0.0;
+                                        } else {
+                                            *powerup += dt.0;
+                                        }
                                     }
                                 }
 
@@ -527,6 +652,14 @@ impl<'a> System<'a> for Sys {
                                             bearing.xy().try_normalized().unwrap_or(Vec2::zero())
                                                 * speed;
                                         inputs.jump.set_state(bearing.z > 1.5);
+                                        // Arthropods scale walls rather than getting
+                                        // stuck at their foot
+                                        if matches!(body, Some(Body::Arthropod(_)))
+                                            && physics_state.on_wall.is_some()
+                                            && bearing.z > 1.5
+                                        {
+                                            inputs.climb = Some(comp::Climb::Up);
+                                        }
                                         inputs.swimup.set_state(bearing.z > 0.5);
                                         inputs.swimdown.set_state(bearing.z < 0.5);
                                     }
@@ -545,6 +678,30 @@ impl<'a> System<'a> for Sys {
                             do_idle = true;
                         }
                     },
+                    Activity::Scripted {
+                        dest,
+                        chaser,
+                        actions,
+                    } => {
+                        controller.actions.append(actions);
+                        if let Some(dest) = *dest {
+                            if pos.0.distance_squared(dest) > 1.0 {
+                                if let Some((bearing, speed)) =
+                                    chaser.chase(&*terrain, pos.0, vel.0, dest, TraversalConfig {
+                                        node_tolerance,
+                                        slow_factor,
+                                        on_ground: physics_state.on_ground,
+                                        min_tgt_dist: 1.0,
+                                    })
+                                {
+                                    inputs.move_dir =
+                                        bearing.xy().try_normalized().unwrap_or(Vec2::zero())
+                                            * speed.min(0.65);
+                                    inputs.jump.set_state(bearing.z > 1.5);
+                                }
+                            }
+                        }
+                    },
                 }
             }
 
@@ -552,6 +709,14 @@ impl<'a> System<'a> for Sys {
                 agent.activity = Activity::Idle(Vec2::zero());
             }
 
+            // Creatures that can fly come back down once there is nothing left to attack
+            if body.map_or(false, |b| b.can_fly())
+                && !physics_state.on_ground
+                && !matches!(agent.activity, Activity::Attack { .. })
+            {
+                inputs.swimdown.set_state(true);
+            }
+
             // Choose a new target to attack: only go out of our way to attack targets we
             // are hostile toward!
             if choose_target {
@@ -560,13 +725,15 @@ impl<'a> System<'a> for Sys {
                 let closest_entity = (&entities, &positions, &stats, alignments.maybe())
                     .join()
                     .filter(|(e, e_pos, e_stats, e_alignment)| {
-                        ((e_pos.0.distance_squared(pos.0) < SEARCH_DIST.powf(2.0) &&
+                        ((e_pos.0.distance_squared(pos.0) < search_dist.powf(2.0) &&
                             // Within our view
                             (e_pos.0 - pos.0).try_normalized().map(|v| v.dot(*inputs.look_dir) > 0.15).unwrap_or(true))
                                 // Within listen distance
                                 || e_pos.0.distance_squared(pos.0) < LISTEN_DIST.powf(2.0))
                             && *e != entity
                             && !e_stats.is_dead
+                            && within_arc(e_pos.0)
+                            && instances.get(*e) == instances.get(entity)
                             && alignment
                                 .and_then(|a| e_alignment.map(|b| a.hostile_towards(*b)))
                                 .unwrap_or(false)
@@ -594,43 +761,47 @@ impl<'a> System<'a> for Sys {
             // --- Activity overrides (in reverse order of priority: most important goes
             // last!) ---
 
-            // Attack a target that's attacking us
-            if let Some(my_stats) = stats.get(entity) {
-                // Only if the attack was recent
-                if my_stats.health.last_change.0 < 3.0 {
-                    if let comp::HealthSource::Attack { by }
-                    | comp::HealthSource::Projectile { owner: Some(by) }
-                    | comp::HealthSource::Energy { owner: Some(by) }
-                    | comp::HealthSource::Buff { owner: Some(by) }
-                    | comp::HealthSource::Explosion { owner: Some(by) } =
-                        my_stats.health.last_change.1.cause
-                    {
-                        if !agent.activity.is_attack() {
-                            if let Some(attacker) = uid_allocator.retrieve_entity_internal(by.id())
+            // Attack whoever poses the biggest threat to us
+            if let Some(threat) = threats.get_mut(entity) {
+                threat.decay(dt.0);
+                // Forget about those that are dead or gone
+                threat.retain(|by| {
+                    uid_allocator
+                        .retrieve_entity_internal(by.id())
+                        .and_then(|e| stats.get(e))
+                        .map_or(false, |s| !s.is_dead)
+                });
+
+                if let Some((by, highest_threat)) = threat.highest() {
+                    if let Some(attacker) = uid_allocator.retrieve_entity_internal(by.id()) {
+                        let current_threat = match agent.activity {
+                            Activity::Attack { target, .. } => {
+                                uids.get(target).map_or(0.0, |uid| threat.get(*uid))
+                            },
+                            _ => 0.0,
+                        };
+                        match agent.activity {
+                            Activity::Attack { target, .. } if target == attacker => {},
+                            // Only switch targets if the new one is clearly more threatening, so
+                            // that we don't flip-flop between similar threats
+                            _ if highest_threat > current_threat * THREAT_SWITCH_RATIO
+                                && positions.get(attacker).map_or(true, |p| within_arc(p.0)) =>
                             {
-                                if stats.get(attacker).map_or(false, |a| !a.is_dead) {
-                                    match agent.activity {
-                                        Activity::Attack { target, .. } if target == attacker => {},
-                                        _ => {
-                                            if agent.can_speak {
-                                                let msg =
-                                                    "npc.speech.villager_under_attack".to_string();
-                                                event_emitter.emit(ServerEvent::Chat(
-                                                    UnresolvedChatMsg::npc(*uid, msg),
-                                                ));
-                                            }
-
-                                            agent.activity = Activity::Attack {
-                                                target: attacker,
-                                                chaser: Chaser::default(),
-                                                time: time.0,
-                                                been_close: false,
-                                                powerup: 0.0,
-                                            };
-                                        },
-                                    }
+                                if agent.can_speak && !agent.activity.is_attack() {
+                                    let msg = "npc.speech.villager_under_attack".to_string();
+                                    event_emitter
+                                        .emit(ServerEvent::Chat(UnresolvedChatMsg::npc(*uid, msg)));
                                 }
-                            }
+
+                                agent.activity = Activity::Attack {
+                                    target: attacker,
+                                    chaser: Chaser::default(),
+                                    time: time.0,
+                                    been_close: false,
+                                    powerup: 0.0,
+                                };
+                            },
+                            _ => {},
                         }
                     }
                 }
@@ -643,7 +814,10 @@ impl<'a> System<'a> for Sys {
 
                     let owner_pos = positions.get(owner)?;
                     let dist_sqrd = pos.0.distance_squared(owner_pos.0);
-                    if dist_sqrd > MAX_FOLLOW_DIST.powf(2.0) && !agent.activity.is_follow() {
+                    if dist_sqrd > MAX_FOLLOW_DIST.powf(2.0)
+                        && !agent.activity.is_follow()
+                        && facing.is_none()
+                    {
                         agent.activity = Activity::Follow {
                             target: owner,
                             chaser: Chaser::default(),
@@ -660,6 +834,9 @@ impl<'a> System<'a> for Sys {
                         {
                             if !agent.activity.is_attack() {
                                 let attacker = uid_allocator.retrieve_entity_internal(by.id())?;
+                                if !within_arc(positions.get(attacker)?.0) {
+                                    return None;
+                                }
 
                                 agent.activity = Activity::Attack {
                                     target: attacker,
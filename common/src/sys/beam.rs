@@ -1,7 +1,9 @@
 use crate::{
+    combat::{CombatRules, Combatant},
     comp::{
-        group, Beam, BeamSegment, Body, CharacterState, Damage, DamageSource, Energy, EnergySource,
-        HealthChange, HealthSource, Last, Loadout, Ori, Pos, Scale, Stats,
+        armor_penetration, group, Beam, BeamSegment, Body, CharacterState, Damage, DamageSource,
+        Energy, EnergySource, HealthChange, HealthSource, Instance, Invulnerable, Last, Loadout,
+        Ori, Player, Pos, Scale, Stats,
     },
     event::{EventBus, ServerEvent},
     state::{DeltaTime, Time},
@@ -23,6 +25,7 @@ impl<'a> System<'a> for Sys {
         Read<'a, Time>,
         Read<'a, DeltaTime>,
         Read<'a, UidAllocator>,
+        Read<'a, CombatRules>,
         ReadStorage<'a, Uid>,
         ReadStorage<'a, Pos>,
         ReadStorage<'a, Last<Pos>>,
@@ -33,6 +36,9 @@ impl<'a> System<'a> for Sys {
         ReadStorage<'a, Loadout>,
         ReadStorage<'a, group::Group>,
         ReadStorage<'a, CharacterState>,
+        ReadStorage<'a, Player>,
+        ReadStorage<'a, Invulnerable>,
+        ReadStorage<'a, Instance>,
         WriteStorage<'a, Energy>,
         WriteStorage<'a, BeamSegment>,
         WriteStorage<'a, Beam>,
@@ -46,6 +52,7 @@ impl<'a> System<'a> for Sys {
             time,
             dt,
             uid_allocator,
+            combat_rules,
             uids,
             positions,
             last_positions,
@@ -56,6 +63,9 @@ impl<'a> System<'a> for Sys {
             loadouts,
             groups,
             character_states,
+            players,
+            invulnerables,
+            instances,
             mut energies,
             mut beam_segments,
             mut beams,
@@ -107,6 +117,15 @@ impl<'a> System<'a> for Sys {
             // Group to ignore collisions with
             // Might make this more nuanced if beams are used for non damage effects
             let group = beam_owner.and_then(|e| groups.get(e));
+            let owner = beam_owner.and_then(|e| {
+                Some(Combatant::new(
+                    e,
+                    beam_segment.owner?,
+                    positions.get(e)?.0,
+                    &players,
+                    &groups,
+                ))
+            });
 
             let hit_entities = if let Some(beam) = beam_owner.and_then(|e| beams.get_mut(e)) {
                 &mut beam.hit_entities
@@ -139,8 +158,11 @@ impl<'a> System<'a> for Sys {
             )
                 .join()
             {
-                // Check to see if entity has already been hit recently
-                if hit_entities.iter().any(|&uid| uid == *uid_b) {
+                // Check to see if entity has already been hit recently, beams only reach
+                // entities in the same dungeon instance as their owner
+                if hit_entities.iter().any(|&uid| uid == *uid_b)
+                    || instances.get(entity) != instances.get(b)
+                {
                     continue;
                 }
 
@@ -167,9 +189,17 @@ impl<'a> System<'a> for Sys {
                         continue;
                     }
                     // Don't heal if outside group
-                    // Don't damage in the same group
-                    let is_damage = !same_group && (beam_segment.damage > 0);
+                    // Don't damage those the combat rules protect or that are invulnerable
                     let is_heal = same_group && (beam_segment.heal > 0);
+                    let is_damage = !is_heal
+                        && beam_segment.damage > 0
+                        && owner.map_or(!same_group, |owner| {
+                            combat_rules.can_harm(
+                                owner,
+                                Combatant::new(b, *uid_b, pos_b.0, &players, &groups),
+                            )
+                        })
+                        && !invulnerables.get(b).map_or(false, |i| i.is_active(time));
                     if !is_heal && !is_damage {
                         continue;
                     }
@@ -189,15 +219,17 @@ impl<'a> System<'a> for Sys {
                     let mut damage = Damage {
                         healthchange,
                         source,
+                        kind: None,
+                        crit: None,
+                        penetration: armor_penetration(beam_owner.and_then(|e| loadouts.get(e))),
+                        true_damage: false,
                     };
 
                     let block = character_b.map(|c_b| c_b.is_block()).unwrap_or(false)
                         // TODO: investigate whether this calculation is proper for beams
                         && ori_b.0.angle_between(pos.0 - pos_b.0) < BLOCK_ANGLE.to_radians() / 2.0;
 
-                    if let Some(loadout) = loadouts.get(b) {
-                        damage.modify_damage(block, loadout);
-                    }
+                    let mitigated = damage.modify_damage(block, loadouts.get(b), Some(body_b));
 
                     if is_damage {
                         server_emitter.emit(ServerEvent::Damage {
@@ -207,6 +239,9 @@ impl<'a> System<'a> for Sys {
                                 cause: HealthSource::Energy {
                                     owner: beam_segment.owner,
                                 },
+                                crit: false,
+                                mitigated,
+                                overkill: 0,
                             },
                         });
                         if beam_segment.lifesteal_eff > 0.0 {
@@ -218,6 +253,9 @@ impl<'a> System<'a> for Sys {
                                     cause: HealthSource::Healing {
                                         by: beam_segment.owner,
                                     },
+                                    crit: false,
+                                    mitigated: 0,
+                                    overkill: 0,
                                 },
                             });
                         }
@@ -244,6 +282,9 @@ impl<'a> System<'a> for Sys {
                                         cause: HealthSource::Healing {
                                             by: beam_segment.owner,
                                         },
+                                        crit: false,
+                                        mitigated,
+                                        overkill: 0,
                                     },
                                 });
                             }
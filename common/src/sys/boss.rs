@@ -0,0 +1,113 @@
+use crate::{
+    comp::{
+        shockwave, BossState, Buff, BuffCategory, BuffChange, BuffData, BuffKind, BuffSource, Item,
+        ItemConfig, Loadout, Ori, Pos, Stats,
+    },
+    event::{EventBus, ServerEvent},
+    state::Time,
+    sync::Uid,
+};
+use specs::{Entities, Join, Read, ReadStorage, System, WriteStorage};
+use std::time::Duration;
+
+/// This system moves bosses through the phases of their fights as their
+/// health drops, and enrages them once a fight has lasted too long
+pub struct Sys;
+impl<'a> System<'a> for Sys {
+    #[allow(clippy::type_complexity)]
+    type SystemData = (
+        Entities<'a>,
+        Read<'a, Time>,
+        Read<'a, EventBus<ServerEvent>>,
+        ReadStorage<'a, Uid>,
+        ReadStorage<'a, Pos>,
+        ReadStorage<'a, Ori>,
+        ReadStorage<'a, Stats>,
+        WriteStorage<'a, BossState>,
+        WriteStorage<'a, Loadout>,
+    );
+
+    fn run(
+        &mut self,
+        (
+            entities,
+            time,
+            server_bus,
+            uids,
+            positions,
+            orientations,
+            stats,
+            mut bosses,
+            mut loadouts,
+        ): Self::SystemData,
+    ) {
+        let mut server_emitter = server_bus.emitter();
+        for (entity, uid, pos, ori, stats, boss) in (
+            &entities,
+            &uids,
+            &positions,
+            &orientations,
+            &stats,
+            &mut bosses,
+        )
+            .join()
+        {
+            if stats.is_dead {
+                continue;
+            }
+
+            let health_fraction =
+                stats.health.current() as f32 / stats.health.maximum().max(1) as f32;
+            if boss.fight_start.is_none() && health_fraction < 1.0 {
+                boss.fight_start = Some(time.0);
+            }
+
+            for phase in boss.advance(health_fraction) {
+                if let Some(main_tool) = &phase.main_tool {
+                    if let Some(loadout) = loadouts.get_mut(entity) {
+                        loadout.active_item =
+                            Some(ItemConfig::from(Item::new_from_asset_expect(main_tool)));
+                    }
+                }
+                if let Some(arena_shockwave) = phase.shockwave {
+                    server_emitter.emit(ServerEvent::Shockwave {
+                        properties: shockwave::Properties {
+                            angle: 360.0,
+                            vertical_angle: 90.0,
+                            speed: arena_shockwave.speed,
+                            damage: arena_shockwave.damage,
+                            knockback: arena_shockwave.knockback,
+                            requires_ground: false,
+                            friendly_fire: false,
+                            true_damage: arena_shockwave.true_damage,
+                            duration: Duration::from_secs_f32(arena_shockwave.duration),
+                            owner: Some(*uid),
+                        },
+                        pos: *pos,
+                        ori: *ori,
+                    });
+                }
+            }
+
+            if let (Some(enrage), Some(fight_start), false) =
+                (boss.config.enrage, boss.fight_start, boss.enraged)
+            {
+                if time.0 - fight_start >= f64::from(enrage.after) {
+                    boss.enraged = true;
+                    server_emitter.emit(ServerEvent::Buff {
+                        entity,
+                        buff_change: BuffChange::Add(Buff::new(
+                            BuffKind::Enraged,
+                            BuffData {
+                                strength: enrage.strength,
+                                duration: None,
+                            },
+                            vec![BuffCategory::Natural],
+                            BuffSource::World,
+                        )),
+                    });
+                }
+            }
+        }
+    }
+}
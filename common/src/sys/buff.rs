@@ -1,14 +1,38 @@
 use crate::{
+    combat::CombatRules,
     comp::{
-        BuffCategory, BuffChange, BuffEffect, BuffId, BuffSource, Buffs, HealthChange,
-        HealthSource, Loadout, ModifierKind, Stats,
+        Buff, BuffCategory, BuffChange, BuffData, BuffEffect, BuffId, BuffKind, BuffSource, Buffs,
+        HealthChange, HealthSource, Instance, Invulnerable, Loadout, PhysicsState, Pos, Stats,
     },
     event::{EventBus, ServerEvent},
-    state::DeltaTime,
+    state::{DeltaTime, Time},
     sync::Uid,
+    terrain::{BlockKind, TerrainGrid},
+    vol::ReadVol,
+};
+use rand::Rng;
+use specs::{
+    Entities, Entity as EcsEntity, Join, Read, ReadExpect, ReadStorage, System, WriteStorage,
 };
-use specs::{Entities, Join, Read, ReadStorage, System, WriteStorage};
 use std::time::Duration;
+use vek::*;
+
+/// Distance within which burning entities can set others on fire
+const BURNING_SPREAD_RADIUS: f32 = 2.0;
+/// Chance per second that a burning entity sets fire to something nearby
+const BURNING_SPREAD_CHANCE: f32 = 0.2;
+/// Share of the time that a fire has left to burn that it passes on when it
+/// spreads, so that fire dies out as it spreads
+const BURNING_SPREAD_DECAY: f32 = 0.5;
+/// Shortest burn that a fire can pass on when it spreads
+const BURNING_SPREAD_MIN: Duration = Duration::from_secs(1);
+/// Chance per second that standing on ice adds a frozen stack
+const ICE_CHILL_CHANCE: f32 = 0.25;
+/// Frozen stack added by standing on ice
+const ICE_CHILL: BuffData = BuffData {
+    strength: 0.1,
+    duration: Some(Duration::from_secs(5)),
+};
 
 pub struct Sys;
 impl<'a> System<'a> for Sys {
@@ -16,23 +40,59 @@ impl<'a> System<'a> for Sys {
     type SystemData = (
         Entities<'a>,
         Read<'a, DeltaTime>,
+        Read<'a, Time>,
+        Read<'a, CombatRules>,
         Read<'a, EventBus<ServerEvent>>,
+        ReadExpect<'a, TerrainGrid>,
         ReadStorage<'a, Uid>,
         ReadStorage<'a, Loadout>,
+        ReadStorage<'a, Pos>,
+        ReadStorage<'a, PhysicsState>,
+        ReadStorage<'a, Invulnerable>,
+        ReadStorage<'a, Instance>,
         WriteStorage<'a, Stats>,
         WriteStorage<'a, Buffs>,
     );
 
     fn run(
         &mut self,
-        (entities, dt, server_bus, uids, loadouts, mut stats, mut buffs): Self::SystemData,
+        (
+            entities,
+            dt,
+            time,
+            combat_rules,
+            server_bus,
+            terrain,
+            uids,
+            loadouts,
+            positions,
+            physics_states,
+            invulnerables,
+            instances,
+            mut stats,
+            mut buffs,
+        ): Self::SystemData,
     ) {
         let mut server_emitter = server_bus.emitter();
+        let mut rng = rand::thread_rng();
+        // Burning entities that set fire to their surroundings this tick
+        let mut spreading_fires = Vec::<(EcsEntity, Vec3<f32>, BuffData)>::new();
         // Set to false to avoid spamming server
         buffs.set_event_emission(false);
         for (entity, buff_comp, uid, stat) in (&entities, &mut buffs, &uids, &mut stats).join() {
             let mut expired_buffs = Vec::<BuffId>::new();
+            let invulnerable = invulnerables
+                .get(entity)
+                .map_or(false, |invulnerable| invulnerable.is_active(time.0));
             for (id, buff) in buff_comp.buffs.iter_mut() {
+                // Damage over time waits for the invulnerability to wear off, if the combat
+                // rules say so
+                if invulnerable
+                    && combat_rules.invulnerability_pauses_dots
+                    && buff.is_damage_over_time()
+                {
+                    continue;
+                }
                 // Tick the buff and subtract delta from it
                 if let Some(remaining_time) = &mut buff.time {
                     if let Some(new_duration) =
@@ -51,6 +111,18 @@ impl<'a> System<'a> for Sys {
                 }
             }
 
+            // Count down immunities from dispelled debuffs
+            buff_comp.immunities.retain(|_, remaining_time| {
+                if let Some(new_duration) =
+                    remaining_time.checked_sub(Duration::from_secs_f32(dt.0))
+                {
+                    *remaining_time = new_duration;
+                    true
+                } else {
+                    false
+                }
+            });
+
             if let Some(loadout) = loadouts.get(entity) {
                 let damage_reduction = loadout.get_damage_reduction();
                 if (damage_reduction - 1.0).abs() < f32::EPSILON {
@@ -62,13 +134,67 @@ impl<'a> System<'a> for Sys {
                 }
             }
 
+            if let Some((_, burning)) = buff_comp.iter_kind(BuffKind::Burning).next() {
+                if physics_states
+                    .get(entity)
+                    .map_or(false, |physics| physics.in_fluid.is_some())
+                {
+                    // Water puts out the fire
+                    server_emitter.emit(ServerEvent::Buff {
+                        entity,
+                        buff_change: BuffChange::RemoveByKind(BuffKind::Burning),
+                    });
+                } else if let Some(pos) = positions.get(entity) {
+                    // The fire passes on part of the time it has left to burn
+                    let duration = burning
+                        .time
+                        .or(burning.data.duration)
+                        .map(|time| time.mul_f32(BURNING_SPREAD_DECAY));
+                    if duration.map_or(true, |duration| duration >= BURNING_SPREAD_MIN)
+                        && rng.gen_bool((BURNING_SPREAD_CHANCE * dt.0).min(1.0) as f64)
+                    {
+                        spreading_fires.push((entity, pos.0, BuffData {
+                            duration,
+                            ..burning.data
+                        }));
+                    }
+                }
+            }
+
+            // Icy ground slowly freezes whoever stands on it
+            let on_ice = physics_states
+                .get(entity)
+                .filter(|physics| physics.on_ground)
+                .zip(positions.get(entity))
+                .and_then(|(_, pos)| {
+                    terrain
+                        .get((pos.0 - Vec3::unit_z() * 0.5).map(|e| e.floor() as i32))
+                        .ok()
+                })
+                .map_or(false, |block| block.kind() == BlockKind::Ice);
+            if on_ice && rng.gen_bool((ICE_CHILL_CHANCE * dt.0).min(1.0) as f64) {
+                server_emitter.emit(ServerEvent::Buff {
+                    entity,
+                    buff_change: BuffChange::Add(Buff::new(
+                        BuffKind::Frozen,
+                        ICE_CHILL,
+                        vec![BuffCategory::Natural],
+                        BuffSource::World,
+                    )),
+                });
+            }
+
             // Call to reset stats to base values
             stat.health.reset_max();
 
             // Iterator over the lists of buffs by kind
-            for buff_ids in buff_comp.kinds.values() {
-                // Get the strongest of this buff kind
-                if let Some(buff) = buff_comp.buffs.get_mut(&buff_ids[0]) {
+            for stack in buff_comp.kinds.values() {
+                // Get the buffs of this kind that are in effect
+                for buff in stack
+                    .active_ids()
+                    .iter()
+                    .filter_map(|id| buff_comp.buffs.get_mut(id))
+                {
                     // Get buff owner?
                     let buff_owner = if let BuffSource::Character { by: owner } = buff.source {
                         Some(owner)
@@ -80,6 +206,11 @@ impl<'a> System<'a> for Sys {
                     for effect in &mut buff.effects {
                         match effect {
                             BuffEffect::HealthChangeOverTime { rate, accumulated } => {
+                                // Invulnerable entities take no damage over time, whether it is
+                                // paused or keeps running out
+                                if *rate < 0.0 && invulnerable {
+                                    continue;
+                                }
                                 *accumulated += *rate * dt.0;
                                 // Apply damage only once a second (with a minimum of 1 damage), or
                                 // when a buff is removed
@@ -96,23 +227,33 @@ impl<'a> System<'a> for Sys {
                                         change: HealthChange {
                                             amount: *accumulated as i32,
                                             cause,
+                                            crit: false,
+                                            mitigated: 0,
+                                            overkill: 0,
                                         },
                                     });
                                     *accumulated = 0.0;
                                 };
                             },
-                            BuffEffect::MaxHealthModifier { value, kind } => match kind {
-                                ModifierKind::Multiplicative => {
-                                    stat.health.set_maximum(
-                                        (stat.health.maximum() as f32 * *value) as u32,
-                                    );
-                                },
-                                ModifierKind::Additive => {
-                                    stat.health.set_maximum(
-                                        (stat.health.maximum() as f32 + *value) as u32,
-                                    );
-                                },
+                            BuffEffect::MaxHealthModifier { value, kind } => {
+                                stat.health.set_maximum(
+                                    kind.apply(stat.health.maximum() as f32, *value) as u32,
+                                );
                             },
+                            // Applied when rolling for critical hits
+                            BuffEffect::CritChanceModifier(_) => {},
+                            // Applied when threat is generated
+                            BuffEffect::ThreatModifier(_) => {},
+                            // Applied when the entity's attacks deal damage
+                            BuffEffect::OnHit(_) => {},
+                            BuffEffect::DamageModifier(_) => {},
+                            // Applied by character states
+                            BuffEffect::MovementSpeedModifier(_) => {},
+                            BuffEffect::AttackSpeedModifier(_) => {},
+                            // Applied when the entity recovers energy
+                            BuffEffect::EnergyRegenModifier(_) => {},
+                            // Applied when the entity is knocked back
+                            BuffEffect::KnockbackImmunity => {},
                         };
                     }
                 }
@@ -138,6 +279,38 @@ impl<'a> System<'a> for Sys {
                 });
             }
         }
+
+        for (burning_entity, burning_pos, data) in spreading_fires {
+            // Set fire to an entity close by, in the same dungeon instance, that isn't
+            // already burning or in water
+            let target = (&entities, &positions, &buffs, physics_states.maybe())
+                .join()
+                .find(|(entity, pos, buffs, physics)| {
+                    *entity != burning_entity
+                        && instances.get(*entity) == instances.get(burning_entity)
+                        && pos.0.distance_squared(burning_pos) < BURNING_SPREAD_RADIUS.powi(2)
+                        && !buffs.kinds.contains_key(&BuffKind::Burning)
+                        && physics.map_or(true, |physics| physics.in_fluid.is_none())
+                })
+                .map(|(entity, _, _, _)| entity);
+
+            if let Some(entity) = target {
+                server_emitter.emit(ServerEvent::Buff {
+                    entity,
+                    buff_change: BuffChange::Add(Buff::new(
+                        BuffKind::Burning,
+                        data,
+                        vec![BuffCategory::Natural],
+                        BuffSource::Buff,
+                    )),
+                });
+            } else {
+                // Otherwise the fire catches on whatever the entity is standing on
+                server_emitter.emit(ServerEvent::Ignite(
+                    (burning_pos - Vec3::unit_z()).map(|e| e.floor() as i32),
+                ));
+            }
+        }
         buffs.set_event_emission(true);
     }
 }
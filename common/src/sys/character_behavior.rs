@@ -1,14 +1,16 @@
 use crate::{
     comp::{
-        Attacking, Beam, Body, CharacterState, ControlAction, Controller, ControllerInputs, Energy,
-        Loadout, Mounting, Ori, PhysicsState, Pos, StateUpdate, Stats, Vel,
+        item::Instrument, Affixes, Attacking, Beam, Body, Buffs, CharacterAbility, CharacterState,
+        ControlAction, Controller, ControllerInputs, Energy, Loadout, MountState, Mounting, Ori,
+        PhysicsState, Pos, StateUpdate, Stats, Vel,
     },
     event::{EventBus, LocalEvent, ServerEvent},
     metrics::SysMetrics,
     span,
-    state::DeltaTime,
+    state::{DeltaTime, Time, TimeOfDay},
     states,
     sync::{Uid, UidAllocator},
+    terrain::TerrainGrid,
 };
 
 use specs::{
@@ -31,7 +33,16 @@ pub trait CharacterBehavior {
     fn sit(&self, data: &JoinData) -> StateUpdate { StateUpdate::from(data) }
     fn dance(&self, data: &JoinData) -> StateUpdate { StateUpdate::from(data) }
     fn sneak(&self, data: &JoinData) -> StateUpdate { StateUpdate::from(data) }
+    fn crouch(&self, data: &JoinData) -> StateUpdate { StateUpdate::from(data) }
     fn stand(&self, data: &JoinData) -> StateUpdate { StateUpdate::from(data) }
+    fn throw(&self, data: &JoinData, _slot: usize) -> StateUpdate { StateUpdate::from(data) }
+    fn revive(&self, data: &JoinData, _target: Uid) -> StateUpdate { StateUpdate::from(data) }
+    fn fish(&self, data: &JoinData) -> StateUpdate { StateUpdate::from(data) }
+    fn perform(&self, data: &JoinData, _instrument: Instrument) -> StateUpdate {
+        StateUpdate::from(data)
+    }
+    fn play_note(&self, data: &JoinData, _note: u8) -> StateUpdate { StateUpdate::from(data) }
+    fn play_song(&self, data: &JoinData, _song: usize) -> StateUpdate { StateUpdate::from(data) }
     fn handle_event(&self, data: &JoinData, event: ControlAction) -> StateUpdate {
         match event {
             ControlAction::SwapLoadout => self.swap_loadout(data),
@@ -41,7 +52,14 @@ pub trait CharacterBehavior {
             ControlAction::Sit => self.sit(data),
             ControlAction::Dance => self.dance(data),
             ControlAction::Sneak => self.sneak(data),
+            ControlAction::Crouch => self.crouch(data),
             ControlAction::Stand => self.stand(data),
+            ControlAction::Throw(slot) => self.throw(data, slot),
+            ControlAction::Revive(target) => self.revive(data, target),
+            ControlAction::Fish => self.fish(data),
+            ControlAction::Perform(instrument) => self.perform(data, instrument),
+            ControlAction::PlayNote(note) => self.play_note(data, note),
+            ControlAction::PlaySong(song) => self.play_song(data, song),
         }
     }
     // fn init(data: &JoinData) -> CharacterState;
@@ -56,6 +74,8 @@ pub struct JoinData<'a> {
     pub vel: &'a Vel,
     pub ori: &'a Ori,
     pub dt: &'a DeltaTime,
+    pub time: &'a Time,
+    pub time_of_day: &'a TimeOfDay,
     pub controller: &'a Controller,
     pub inputs: &'a ControllerInputs,
     pub stats: &'a Stats,
@@ -64,6 +84,9 @@ pub struct JoinData<'a> {
     pub body: &'a Body,
     pub physics: &'a PhysicsState,
     pub attacking: Option<&'a Attacking>,
+    pub affixes: Option<&'a Affixes>,
+    pub buffs: Option<&'a Buffs>,
+    pub terrain: &'a TerrainGrid,
     pub updater: &'a LazyUpdate,
 }
 
@@ -90,6 +113,8 @@ pub type JoinTuple<'a> = (
     &'a PhysicsState,
     Option<&'a Attacking>,
     Option<&'a Beam>,
+    Option<&'a Affixes>,
+    Option<&'a Buffs>,
 );
 
 fn incorporate_update(tuple: &mut JoinTuple, state_update: StateUpdate) {
@@ -110,8 +135,26 @@ fn incorporate_update(tuple: &mut JoinTuple, state_update: StateUpdate) {
     }
 }
 
+/// Whether the weapon in hand charges with its secondary ability
+fn has_own_charge(loadout: &Loadout) -> bool {
+    matches!(
+        loadout
+            .active_item
+            .as_ref()
+            .and_then(|i| i.ability2.as_ref()),
+        Some(CharacterAbility::Charge { .. })
+    )
+}
+
 impl<'a> JoinData<'a> {
-    fn new(j: &'a JoinTuple<'a>, updater: &'a LazyUpdate, dt: &'a DeltaTime) -> Self {
+    fn new(
+        j: &'a JoinTuple<'a>,
+        updater: &'a LazyUpdate,
+        dt: &'a DeltaTime,
+        time: &'a Time,
+        time_of_day: &'a TimeOfDay,
+        terrain: &'a TerrainGrid,
+    ) -> Self {
         Self {
             entity: j.0,
             uid: j.1,
@@ -127,8 +170,13 @@ impl<'a> JoinData<'a> {
             body: j.10,
             physics: j.11,
             attacking: j.12,
+            affixes: j.14,
+            buffs: j.15,
+            terrain,
             updater,
             dt,
+            time,
+            time_of_day,
         }
     }
 }
@@ -146,8 +194,11 @@ impl<'a> System<'a> for Sys {
         Read<'a, EventBus<ServerEvent>>,
         Read<'a, EventBus<LocalEvent>>,
         Read<'a, DeltaTime>,
+        Read<'a, Time>,
+        Read<'a, TimeOfDay>,
         Read<'a, LazyUpdate>,
         ReadExpect<'a, SysMetrics>,
+        ReadExpect<'a, TerrainGrid>,
         WriteStorage<'a, CharacterState>,
         WriteStorage<'a, Pos>,
         WriteStorage<'a, Vel>,
@@ -162,6 +213,9 @@ impl<'a> System<'a> for Sys {
         ReadStorage<'a, Beam>,
         ReadStorage<'a, Uid>,
         ReadStorage<'a, Mounting>,
+        ReadStorage<'a, MountState>,
+        ReadStorage<'a, Affixes>,
+        ReadStorage<'a, Buffs>,
     );
 
     #[allow(clippy::while_let_on_iterator)] // TODO: Pending review in #587
@@ -173,8 +227,11 @@ impl<'a> System<'a> for Sys {
             server_bus,
             local_bus,
             dt,
+            time,
+            time_of_day,
             updater,
             sys_metrics,
+            terrain,
             mut character_states,
             mut positions,
             mut velocities,
@@ -189,6 +246,9 @@ impl<'a> System<'a> for Sys {
             beam_storage,
             uids,
             mountings,
+            mount_states,
+            affixes,
+            buffs,
         ): Self::SystemData,
     ) {
         let start_time = std::time::Instant::now();
@@ -211,6 +271,8 @@ impl<'a> System<'a> for Sys {
             &physics_states,
             attacking_storage.maybe(),
             beam_storage.maybe(),
+            affixes.maybe(),
+            buffs.maybe(),
         )
             .join()
         {
@@ -219,6 +281,10 @@ impl<'a> System<'a> for Sys {
                 // Do nothing
                 continue;
             }
+            // Frozen solid characters can't act until they thaw
+            if tuple.15.map_or(false, |buffs| buffs.is_frozen()) {
+                continue;
+            }
             // If mounted, character state is controlled by mount
             // TODO: Make mounting a state
             if let Some(Mounting(_)) = mountings.get(tuple.0) {
@@ -229,9 +295,26 @@ impl<'a> System<'a> for Sys {
                 continue;
             }
 
+            // Ridden beasts charge at their rider's command, using their own charge if they
+            // have one and are wielding it
+            if let Some(MountState::MountedBy(_)) = mount_states.get(tuple.0) {
+                let can_charge = match tuple.2.get_unchecked() {
+                    CharacterState::Idle => true,
+                    CharacterState::Wielding => !has_own_charge(tuple.7.get_unchecked()),
+                    _ => false,
+                };
+                if can_charge
+                    && matches!(tuple.10, Body::QuadrupedMedium(_))
+                    && tuple.8.inputs.secondary.is_pressed()
+                {
+                    *tuple.2.get_mut_unchecked() =
+                        CharacterState::Charge(states::charge::Data::new(states::charge::MOUNTED));
+                }
+            }
+
             let actions = std::mem::replace(&mut tuple.8.actions, Vec::new());
             for action in actions {
-                let j = JoinData::new(&tuple, &updater, &dt);
+                let j = JoinData::new(&tuple, &updater, &dt, &time, &time_of_day, &terrain);
                 let mut state_update = match j.character {
                     CharacterState::Idle => states::idle::Data.handle_event(&j, action),
                     CharacterState::Climb => states::climb::Data.handle_event(&j, action),
@@ -248,6 +331,9 @@ impl<'a> System<'a> for Sys {
                     CharacterState::Sneak => {
                         states::sneak::Data::handle_event(&states::sneak::Data, &j, action)
                     },
+                    CharacterState::Crouch => {
+                        states::crouch::Data::handle_event(&states::crouch::Data, &j, action)
+                    },
                     CharacterState::BasicBlock => {
                         states::basic_block::Data.handle_event(&j, action)
                     },
@@ -255,6 +341,7 @@ impl<'a> System<'a> for Sys {
                     CharacterState::Wielding => states::wielding::Data.handle_event(&j, action),
                     CharacterState::Equipping(data) => data.handle_event(&j, action),
                     CharacterState::ComboMelee(data) => data.handle_event(&j, action),
+                    CharacterState::DualStrike(data) => data.handle_event(&j, action),
                     CharacterState::BasicMelee(data) => data.handle_event(&j, action),
                     CharacterState::BasicRanged(data) => data.handle_event(&j, action),
                     CharacterState::Boost(data) => data.handle_event(&j, action),
@@ -266,13 +353,22 @@ impl<'a> System<'a> for Sys {
                     CharacterState::RepeaterRanged(data) => data.handle_event(&j, action),
                     CharacterState::Shockwave(data) => data.handle_event(&j, action),
                     CharacterState::BasicBeam(data) => data.handle_event(&j, action),
+                    CharacterState::Throw(data) => data.handle_event(&j, action),
+                    CharacterState::Charge(data) => data.handle_event(&j, action),
+                    CharacterState::CastSpell(data) => data.handle_event(&j, action),
+                    CharacterState::Downed(data) => data.handle_event(&j, action),
+                    CharacterState::Revive(data) => data.handle_event(&j, action),
+                    CharacterState::Fishing(data) => data.handle_event(&j, action),
+                    CharacterState::Perform(data) => data.handle_event(&j, action),
+                    CharacterState::Fly(data) => data.handle_event(&j, action),
+                    CharacterState::Swim(data) => data.handle_event(&j, action),
                 };
                 local_emitter.append(&mut state_update.local_events);
                 server_emitter.append(&mut state_update.server_events);
                 incorporate_update(&mut tuple, state_update);
             }
 
-            let j = JoinData::new(&tuple, &updater, &dt);
+            let j = JoinData::new(&tuple, &updater, &dt, &time, &time_of_day, &terrain);
 
             let mut state_update = match j.character {
                 CharacterState::Idle => states::idle::Data.behavior(&j),
@@ -282,11 +378,13 @@ impl<'a> System<'a> for Sys {
                 CharacterState::Sit => states::sit::Data::behavior(&states::sit::Data, &j),
                 CharacterState::Dance => states::dance::Data::behavior(&states::dance::Data, &j),
                 CharacterState::Sneak => states::sneak::Data::behavior(&states::sneak::Data, &j),
+                CharacterState::Crouch => states::crouch::Data::behavior(&states::crouch::Data, &j),
                 CharacterState::BasicBlock => states::basic_block::Data.behavior(&j),
                 CharacterState::Roll(data) => data.behavior(&j),
                 CharacterState::Wielding => states::wielding::Data.behavior(&j),
                 CharacterState::Equipping(data) => data.behavior(&j),
                 CharacterState::ComboMelee(data) => data.behavior(&j),
+                CharacterState::DualStrike(data) => data.behavior(&j),
                 CharacterState::BasicMelee(data) => data.behavior(&j),
                 CharacterState::BasicRanged(data) => data.behavior(&j),
                 CharacterState::Boost(data) => data.behavior(&j),
@@ -298,6 +396,15 @@ impl<'a> System<'a> for Sys {
                 CharacterState::RepeaterRanged(data) => data.behavior(&j),
                 CharacterState::Shockwave(data) => data.behavior(&j),
                 CharacterState::BasicBeam(data) => data.behavior(&j),
+                CharacterState::Throw(data) => data.behavior(&j),
+                CharacterState::Charge(data) => data.behavior(&j),
+                CharacterState::CastSpell(data) => data.behavior(&j),
+                CharacterState::Downed(data) => data.behavior(&j),
+                CharacterState::Revive(data) => data.behavior(&j),
+                CharacterState::Fishing(data) => data.behavior(&j),
+                CharacterState::Perform(data) => data.behavior(&j),
+                CharacterState::Fly(data) => data.behavior(&j),
+                CharacterState::Swim(data) => data.behavior(&j),
             };
 
             local_emitter.append(&mut state_update.local_events);
@@ -1,13 +1,20 @@
 use crate::{
+    combat::{CombatRules, Combatant},
     comp::{
-        buff, group, Attacking, Body, CharacterState, Damage, DamageSource, HealthChange,
-        HealthSource, Loadout, Ori, Pos, Scale, Stats,
+        affix, armor_penetration, buff, group, on_hit_effects, Affix, Affixes, Attacking, Body,
+        Buffs, CharacterState, CritStats, Damage, DamageKind, DamageSource, HealthChange,
+        HealthSource, Instance, Invulnerable, Loadout, MountState, Ori, Player, Pos, Scale, Stats,
+        Trampling,
     },
     event::{EventBus, LocalEvent, ServerEvent},
     metrics::SysMetrics,
     span,
+    state::Time,
+    states::{charge, utils::StageSection},
     sync::Uid,
+    terrain::TerrainGrid,
     util::Dir,
+    vol::ReadVol,
 };
 use rand::{thread_rng, Rng};
 use specs::{Entities, Join, Read, ReadExpect, ReadStorage, System, WriteStorage};
@@ -16,6 +23,9 @@ use vek::*;
 
 pub const BLOCK_EFFICIENCY: f32 = 0.9;
 pub const BLOCK_ANGLE: f32 = 180.0;
+/// Damage from a single melee hit at which the hit counts as heavy, which
+/// frontends give extra weight to
+pub const HEAVY_HIT_DAMAGE: f32 = 150.0;
 
 /// This system is responsible for handling accepted inputs like moving or
 /// attacking
@@ -27,6 +37,9 @@ impl<'a> System<'a> for Sys {
         Read<'a, EventBus<ServerEvent>>,
         Read<'a, EventBus<LocalEvent>>,
         ReadExpect<'a, SysMetrics>,
+        ReadExpect<'a, TerrainGrid>,
+        Read<'a, CombatRules>,
+        Read<'a, Time>,
         ReadStorage<'a, Uid>,
         ReadStorage<'a, Pos>,
         ReadStorage<'a, Ori>,
@@ -36,7 +49,14 @@ impl<'a> System<'a> for Sys {
         ReadStorage<'a, Loadout>,
         ReadStorage<'a, group::Group>,
         ReadStorage<'a, CharacterState>,
+        ReadStorage<'a, Instance>,
+        ReadStorage<'a, Affixes>,
+        ReadStorage<'a, Buffs>,
+        ReadStorage<'a, Player>,
+        ReadStorage<'a, Invulnerable>,
+        ReadStorage<'a, MountState>,
         WriteStorage<'a, Attacking>,
+        WriteStorage<'a, Trampling>,
     );
 
     fn run(
@@ -46,6 +66,9 @@ impl<'a> System<'a> for Sys {
             server_bus,
             local_bus,
             sys_metrics,
+            terrain,
+            combat_rules,
+            time,
             uids,
             positions,
             orientations,
@@ -55,7 +78,14 @@ impl<'a> System<'a> for Sys {
             loadouts,
             groups,
             character_states,
+            instances,
+            affixes,
+            buffs,
+            players,
+            invulnerables,
+            mount_states,
             mut attacking_storage,
+            mut trampling_storage,
         ): Self::SystemData,
     ) {
         let start_time = std::time::Instant::now();
@@ -104,6 +134,7 @@ impl<'a> System<'a> for Sys {
                 // Check if it is a hit
                 if entity != b
                     && !stats_b.is_dead
+                    && instances.get(entity) == instances.get(b)
                     // Spherical wedge shaped attack field
                     && pos.0.distance_squared(pos_b.0) < (rad_b + scale * attack.range).powi(2)
                     && ori2.angle_between(pos_b2 - pos2) < attack.max_angle + (rad_b / pos2.distance(pos_b2)).atan()
@@ -114,9 +145,15 @@ impl<'a> System<'a> for Sys {
                         .map(|group_a| Some(group_a) == groups.get(b))
                         .unwrap_or(false);
                     // Don't heal if outside group
-                    // Don't damage in the same group
-                    let is_damage = !same_group && (attack.base_damage > 0);
+                    // Don't damage those the combat rules protect or that are invulnerable
                     let is_heal = same_group && (attack.base_heal > 0);
+                    let is_damage = !is_heal
+                        && attack.base_damage.amount > 0
+                        && combat_rules.can_harm(
+                            Combatant::new(entity, *uid, pos.0, &players, &groups),
+                            Combatant::new(b, *uid_b, pos_b.0, &players, &groups),
+                        )
+                        && !invulnerables.get(b).map_or(false, |i| i.is_active(time.0));
                     if !is_heal && !is_damage {
                         continue;
                     }
@@ -125,18 +162,44 @@ impl<'a> System<'a> for Sys {
                     let (source, healthchange) = if is_heal {
                         (DamageSource::Healing, attack.base_heal as f32)
                     } else {
-                        (DamageSource::Melee, -(attack.base_damage as f32))
+                        let modifier = buffs.get(entity).map_or(1.0, |b| b.damage_modifier());
+                        (
+                            DamageSource::Melee,
+                            -(attack.base_damage.amount as f32) * modifier,
+                        )
                     };
                     let mut damage = Damage {
                         healthchange,
                         source,
+                        kind: Some(attack.base_damage.kind),
+                        crit: if is_damage {
+                            CritStats::new(loadouts.get(entity), buffs.get(entity)).roll()
+                        } else {
+                            None
+                        },
+                        penetration: armor_penetration(loadouts.get(entity)),
+                        true_damage: false,
                     };
 
                     let block = character_b.map(|c_b| c_b.is_block()).unwrap_or(false)
                         && ori_b.0.angle_between(pos.0 - pos_b.0) < BLOCK_ANGLE.to_radians() / 2.0;
 
-                    if let Some(loadout) = loadouts.get(b) {
-                        damage.modify_damage(block, loadout);
+                    let unmitigated = damage.healthchange;
+                    let mitigated = damage.modify_damage(block, loadouts.get(b), Some(body_b));
+
+                    // Shields can send part of what they stopped back at the attacker
+                    let reflect = loadouts.get(b).map_or(0.0, |l| l.get_block_reflection());
+                    if block && is_damage && reflect > 0.0 {
+                        server_emitter.emit(ServerEvent::Damage {
+                            uid: *uid,
+                            change: HealthChange {
+                                amount: (unmitigated * BLOCK_EFFICIENCY * reflect) as i32,
+                                cause: HealthSource::Attack { by: *uid_b },
+                                crit: false,
+                                mitigated: 0,
+                                overkill: 0,
+                            },
+                        });
                     }
 
                     if damage.healthchange != 0.0 {
@@ -150,9 +213,63 @@ impl<'a> System<'a> for Sys {
                             change: HealthChange {
                                 amount: damage.healthchange as i32,
                                 cause,
+                                crit: damage.crit.is_some(),
+                                mitigated,
+                                overkill: 0,
                             },
                         });
 
+                        if !is_heal {
+                            if -damage.healthchange >= HEAVY_HIT_DAMAGE {
+                                server_emitter.emit(ServerEvent::HeavyHit {
+                                    pos: (pos.0 + pos_b.0) / 2.0 + Vec3::unit_z(),
+                                    attacker: *uid,
+                                    target: *uid_b,
+                                    magnitude: -damage.healthchange / HEAVY_HIT_DAMAGE,
+                                });
+                            }
+                            // Thorns deal part of the damage back to the attacker
+                            if affixes.get(b).map_or(false, |a| a.has(Affix::Thorns)) {
+                                server_emitter.emit(ServerEvent::Damage {
+                                    uid: *uid,
+                                    change: HealthChange {
+                                        amount: (damage.healthchange * affix::THORNS_REFLECT)
+                                            as i32,
+                                        cause: HealthSource::Attack { by: *uid_b },
+                                        crit: false,
+                                        mitigated: 0,
+                                        overkill: 0,
+                                    },
+                                });
+                            }
+                            // Vampiric attackers heal by part of the damage they deal
+                            if affixes
+                                .get(entity)
+                                .map_or(false, |a| a.has(Affix::Vampiric))
+                            {
+                                server_emitter.emit(ServerEvent::Damage {
+                                    uid: *uid,
+                                    change: HealthChange {
+                                        amount: -(damage.healthchange * affix::VAMPIRIC_LEECH)
+                                            as i32,
+                                        cause: HealthSource::Healing { by: Some(*uid) },
+                                        crit: false,
+                                        mitigated: 0,
+                                        overkill: 0,
+                                    },
+                                });
+                            }
+                            let effects = on_hit_effects(loadouts.get(entity), buffs.get(entity));
+                            if !effects.is_empty() {
+                                server_emitter.emit(ServerEvent::OnHit {
+                                    attacker: entity,
+                                    target: b,
+                                    damage: -damage.healthchange as u32,
+                                    effects,
+                                });
+                            }
+                        }
+
                         // Apply bleeding buff on melee hits with 10% chance
                         // TODO: Don't have buff uniformly applied on all melee attacks
                         if thread_rng().gen::<f32>() < 0.1 {
@@ -162,7 +279,7 @@ impl<'a> System<'a> for Sys {
                                 buff_change: BuffChange::Add(Buff::new(
                                     BuffKind::Bleeding,
                                     BuffData {
-                                        strength: attack.base_damage as f32 / 10.0,
+                                        strength: attack.base_damage.amount as f32 / 10.0,
                                         duration: Some(Duration::from_secs(10)),
                                     },
                                     vec![BuffCategory::Physical],
@@ -182,10 +299,168 @@ impl<'a> System<'a> for Sys {
                     }
                 }
             }
+
+            // Props caught in the swing take damage too
+            if attack.base_damage.amount > 0 {
+                let range = scale_maybe.map_or(1.0, |s| s.0) * attack.range;
+                let min = (pos.0 - range).map(|e| e.floor() as i32);
+                let max = (pos.0 + range).map(|e| e.ceil() as i32);
+                for x in min.x..=max.x {
+                    for y in min.y..=max.y {
+                        for z in min.z..=max.z {
+                            let block_pos = Vec3::new(x, y, z);
+                            let center = block_pos.map(|e| e as f32 + 0.5);
+                            let to_block = Vec2::from(center - pos.0);
+                            if center.distance_squared(pos.0) < range.powi(2)
+                                && Vec2::from(*ori.0).angle_between(to_block) < attack.max_angle
+                                && terrain
+                                    .get(block_pos)
+                                    .map_or(false, |block| block.is_destructible())
+                            {
+                                server_emitter.emit(ServerEvent::DamageSprite(
+                                    block_pos,
+                                    attack.base_damage.amount,
+                                ));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        // Trampling lasts only as long as the charge, however the charge ends, so that
+        // a charger that is stunned or killed mid-charge doesn't go on trampling
+        let stopped_charging = (&entities, &trampling_storage)
+            .join()
+            .filter(|(entity, _)| {
+                stats.get(*entity).map_or(true, |stats| stats.is_dead)
+                    || !matches!(
+                        character_states.get(*entity),
+                        Some(CharacterState::Charge(charge::Data {
+                            stage_section: StageSection::Charge,
+                            ..
+                        }))
+                    )
+            })
+            .map(|(entity, _)| entity)
+            .collect::<Vec<_>>();
+        for entity in stopped_charging {
+            trampling_storage.remove(entity);
+        }
+
+        // Trampling, sweeps a capsule along the path moved since the last tick
+        for (entity, uid, pos, ori, scale_maybe, trample) in (
+            &entities,
+            &uids,
+            &positions,
+            &orientations,
+            scales.maybe(),
+            &mut trampling_storage,
+        )
+            .join()
+        {
+            let from = trample.last_pos.unwrap_or(pos.0);
+            trample.last_pos = Some(pos.0);
+            let scale = scale_maybe.map_or(1.0, |s| s.0);
+            let rider = match mount_states.get(entity) {
+                Some(MountState::MountedBy(rider)) => Some(*rider),
+                _ => None,
+            };
+
+            for (b, uid_b, pos_b, ori_b, scale_b_maybe, character_b, stats_b, body_b) in (
+                &entities,
+                &uids,
+                &positions,
+                &orientations,
+                scales.maybe(),
+                character_states.maybe(),
+                &stats,
+                &bodies,
+            )
+                .join()
+            {
+                if entity == b
+                    || rider == Some(*uid_b)
+                    || stats_b.is_dead
+                    || instances.get(entity) != instances.get(b)
+                    || trample.hit_entities.contains(uid_b)
+                {
+                    continue;
+                }
+
+                let rad_b = body_b.radius() * scale_b_maybe.map_or(1.0, |s| s.0);
+                let closest = closest_on_segment(from, pos.0, pos_b.0);
+                if closest.distance_squared(pos_b.0) > (trample.radius * scale + rad_b).powi(2) {
+                    continue;
+                }
+
+                // Don't trample those the combat rules protect or that are invulnerable
+                if !combat_rules.can_harm(
+                    Combatant::new(entity, *uid, pos.0, &players, &groups),
+                    Combatant::new(b, *uid_b, pos_b.0, &players, &groups),
+                ) || invulnerables.get(b).map_or(false, |i| i.is_active(time.0))
+                {
+                    continue;
+                }
+
+                trample.hit_entities.push(*uid_b);
+
+                let mut damage = Damage {
+                    healthchange: -(trample.damage as f32),
+                    source: DamageSource::Melee,
+                    kind: Some(DamageKind::Crushing),
+                    crit: None,
+                    penetration: 0.0,
+                    true_damage: false,
+                };
+
+                let block = character_b.map(|c_b| c_b.is_block()).unwrap_or(false)
+                    && ori_b.0.angle_between(pos.0 - pos_b.0) < BLOCK_ANGLE.to_radians() / 2.0;
+
+                let mitigated = damage.modify_damage(block, loadouts.get(b), Some(body_b));
+
+                if damage.healthchange != 0.0 {
+                    server_emitter.emit(ServerEvent::Damage {
+                        uid: *uid_b,
+                        change: HealthChange {
+                            amount: damage.healthchange as i32,
+                            cause: HealthSource::Attack { by: *uid },
+                            crit: false,
+                            mitigated,
+                            overkill: 0,
+                        },
+                    });
+                }
+                if trample.knockback != 0.0 {
+                    // Thrown aside, away from the charger's path
+                    let kb_dir = Dir::new(
+                        (pos_b.0 - closest + *ori.0)
+                            .try_normalized()
+                            .unwrap_or(*ori.0),
+                    );
+                    server_emitter.emit(ServerEvent::Knockback {
+                        entity: b,
+                        impulse: trample.knockback
+                            * *Dir::slerp(kb_dir, Dir::new(Vec3::new(0.0, 0.0, 1.0)), 0.5),
+                    });
+                }
+            }
         }
+
         sys_metrics.combat_ns.store(
             start_time.elapsed().as_nanos() as i64,
             std::sync::atomic::Ordering::Relaxed,
         );
     }
 }
+
+/// Point on the segment from `a` to `b` closest to `p`
+fn closest_on_segment(a: Vec3<f32>, b: Vec3<f32>, p: Vec3<f32>) -> Vec3<f32> {
+    let ab = b - a;
+    let len_sqrd = ab.magnitude_squared();
+    if len_sqrd == 0.0 {
+        a
+    } else {
+        a + ab * ((p - a).dot(ab) / len_sqrd).max(0.0).min(1.0)
+    }
+}
@@ -115,6 +115,28 @@ impl<'a> System<'a> for Sys {
                         server_emitter.emit(ServerEvent::GroupManip(entity, manip))
                     },
                     ControlEvent::Respawn => server_emitter.emit(ServerEvent::Respawn(entity)),
+                    ControlEvent::ReadBountyBoard(pos) => {
+                        server_emitter.emit(ServerEvent::ReadBountyBoard(entity, pos))
+                    },
+                    ControlEvent::Talk(npc) => server_emitter.emit(ServerEvent::Talk(entity, npc)),
+                    ControlEvent::Trade {
+                        merchant,
+                        good,
+                        action,
+                    } => server_emitter.emit(ServerEvent::Trade {
+                        entity,
+                        merchant,
+                        good,
+                        action,
+                    }),
+                    ControlEvent::SelectAbility { tool, slot, index } => {
+                        server_emitter.emit(ServerEvent::SelectAbility {
+                            entity,
+                            tool,
+                            slot,
+                            index,
+                        })
+                    },
                 }
             }
         }
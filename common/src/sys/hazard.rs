@@ -0,0 +1,135 @@
+use crate::{
+    comp::{
+        Body, Buff, BuffCategory, BuffChange, BuffSource, Damage, DamageSource, HealthChange,
+        HealthSource, Invulnerable, Loadout, Pos, Scale, Stats,
+    },
+    event::{EventBus, ServerEvent},
+    state::{DeltaTime, Time},
+    sync::Uid,
+    terrain::TerrainGrid,
+    vol::ReadVol,
+};
+use specs::{Entities, Join, Read, ReadExpect, ReadStorage, System};
+use vek::*;
+
+/// Seconds between the moments at which hazardous terrain harms the entities
+/// touching it
+const HAZARD_INTERVAL: f64 = 0.5;
+/// How far below their feet entities touch the blocks they stand on
+const FOOTING: f32 = 0.1;
+
+/// This system applies the damage and debuffs of hazardous terrain, such as
+/// lava and spikes, to the entities touching it
+pub struct Sys;
+impl<'a> System<'a> for Sys {
+    #[allow(clippy::type_complexity)]
+    type SystemData = (
+        Entities<'a>,
+        Read<'a, DeltaTime>,
+        Read<'a, Time>,
+        Read<'a, EventBus<ServerEvent>>,
+        ReadExpect<'a, TerrainGrid>,
+        ReadStorage<'a, Uid>,
+        ReadStorage<'a, Pos>,
+        ReadStorage<'a, Body>,
+        ReadStorage<'a, Scale>,
+        ReadStorage<'a, Stats>,
+        ReadStorage<'a, Loadout>,
+        ReadStorage<'a, Invulnerable>,
+    );
+
+    fn run(
+        &mut self,
+        (
+            entities,
+            dt,
+            time,
+            server_bus,
+            terrain,
+            uids,
+            positions,
+            bodies,
+            scales,
+            stats,
+            loadouts,
+            invulnerables,
+        ): Self::SystemData,
+    ) {
+        // Hazards only strike at fixed intervals, so that damage isn't sent every tick
+        let interval = |time: f64| (time / HAZARD_INTERVAL).floor();
+        if interval(time.0) == interval(time.0 - dt.0 as f64) {
+            return;
+        }
+
+        let mut server_emitter = server_bus.emitter();
+        for (entity, uid, pos, body, stats, scale) in (
+            &entities,
+            &uids,
+            &positions,
+            &bodies,
+            &stats,
+            scales.maybe(),
+        )
+            .join()
+        {
+            if stats.is_dead
+                || invulnerables
+                    .get(entity)
+                    .map_or(false, |invulnerable| invulnerable.is_active(time.0))
+            {
+                continue;
+            }
+
+            // The worst hazard among the blocks the entity's body overlaps
+            let scale = scale.map_or(1.0, |s| s.0);
+            let radius = body.radius() * scale;
+            let min = (pos.0 - Vec3::new(radius, radius, FOOTING)).map(|e| e.floor() as i32);
+            let max = (pos.0 + Vec3::new(radius, radius, body.height() * scale))
+                .map(|e| e.floor() as i32);
+            let hazard = (min.x..=max.x)
+                .flat_map(|x| (min.y..=max.y).map(move |y| (x, y)))
+                .flat_map(|(x, y)| (min.z..=max.z).map(move |z| Vec3::new(x, y, z)))
+                .filter_map(|block_pos| terrain.get(block_pos).ok().and_then(|b| b.hazard()))
+                .max_by(|a, b| {
+                    a.damage
+                        .partial_cmp(&b.damage)
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                });
+            let hazard = match hazard {
+                Some(hazard) => hazard,
+                None => continue,
+            };
+
+            let mut damage = Damage {
+                healthchange: -hazard.damage * HAZARD_INTERVAL as f32,
+                source: DamageSource::Hazard,
+                kind: Some(hazard.kind),
+                crit: None,
+                penetration: 0.0,
+                true_damage: false,
+            };
+            let mitigated = damage.modify_damage(false, loadouts.get(entity), Some(body));
+            server_emitter.emit(ServerEvent::Damage {
+                uid: *uid,
+                change: HealthChange {
+                    amount: damage.healthchange as i32,
+                    cause: HealthSource::World,
+                    crit: false,
+                    mitigated,
+                    overkill: 0,
+                },
+            });
+            if let Some((kind, data)) = hazard.buff {
+                server_emitter.emit(ServerEvent::Buff {
+                    entity,
+                    buff_change: BuffChange::Add(Buff::new(
+                        kind,
+                        data,
+                        vec![BuffCategory::Natural],
+                        BuffSource::World,
+                    )),
+                });
+            }
+        }
+    }
+}
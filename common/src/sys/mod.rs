@@ -1,9 +1,11 @@
 pub mod agent;
 mod beam;
+mod boss;
 mod buff;
 pub mod character_behavior;
 pub mod combat;
 pub mod controller;
+mod hazard;
 mod mount;
 pub mod phys;
 mod projectile;
@@ -18,6 +20,7 @@ pub const CHARACTER_BEHAVIOR_SYS: &str = "character_behavior_sys";
 pub const COMBAT_SYS: &str = "combat_sys";
 pub const AGENT_SYS: &str = "agent_sys";
 pub const BEAM_SYS: &str = "beam_sys";
+pub const BOSS_SYS: &str = "boss_sys";
 pub const CONTROLLER_SYS: &str = "controller_sys";
 pub const MOUNT_SYS: &str = "mount_sys";
 pub const PHYS_SYS: &str = "phys_sys";
@@ -25,6 +28,7 @@ pub const PROJECTILE_SYS: &str = "projectile_sys";
 pub const SHOCKWAVE_SYS: &str = "shockwave_sys";
 pub const STATS_SYS: &str = "stats_sys";
 pub const BUFFS_SYS: &str = "buffs_sys";
+pub const HAZARD_SYS: &str = "hazard_sys";
 
 pub fn add_local_systems(dispatch_builder: &mut DispatcherBuilder) {
     dispatch_builder.add(agent::Sys, AGENT_SYS, &[]);
@@ -35,7 +39,9 @@ pub fn add_local_systems(dispatch_builder: &mut DispatcherBuilder) {
     ]);
     dispatch_builder.add(stats::Sys, STATS_SYS, &[]);
     dispatch_builder.add(buff::Sys, BUFFS_SYS, &[]);
+    dispatch_builder.add(boss::Sys, BOSS_SYS, &[STATS_SYS]);
     dispatch_builder.add(phys::Sys, PHYS_SYS, &[CONTROLLER_SYS, MOUNT_SYS, STATS_SYS]);
+    dispatch_builder.add(hazard::Sys, HAZARD_SYS, &[PHYS_SYS]);
     dispatch_builder.add(projectile::Sys, PROJECTILE_SYS, &[PHYS_SYS]);
     dispatch_builder.add(shockwave::Sys, SHOCKWAVE_SYS, &[PHYS_SYS]);
     dispatch_builder.add(beam::Sys, BEAM_SYS, &[PHYS_SYS]);
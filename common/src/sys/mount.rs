@@ -1,8 +1,9 @@
 use crate::{
-    comp::{Controller, MountState, Mounting, Ori, Pos, Vel},
+    comp::{Controller, Hitched, MountState, Mounting, Ori, Pos, Vel},
     metrics::SysMetrics,
     span,
     sync::UidAllocator,
+    util::Dir,
 };
 use specs::{
     saveload::{Marker, MarkerAllocator},
@@ -10,6 +11,11 @@ use specs::{
 };
 use vek::*;
 
+/// Distance a hitched cart keeps behind the animal pulling it
+const HITCH_LENGTH: f32 = 3.5;
+/// Distance at which the hitch breaks, e.g. when the animal is teleported away
+const MAX_HITCH_LENGTH: f32 = 16.0;
+
 /// This system is responsible for controlling mounts
 pub struct Sys;
 impl<'a> System<'a> for Sys {
@@ -21,6 +27,7 @@ impl<'a> System<'a> for Sys {
         WriteStorage<'a, Controller>,
         WriteStorage<'a, MountState>,
         WriteStorage<'a, Mounting>,
+        WriteStorage<'a, Hitched>,
         WriteStorage<'a, Pos>,
         WriteStorage<'a, Vel>,
         WriteStorage<'a, Ori>,
@@ -35,6 +42,7 @@ impl<'a> System<'a> for Sys {
             mut controllers,
             mut mount_state,
             mut mountings,
+            mut hitches,
             mut positions,
             mut velocities,
             mut orientations,
@@ -42,6 +50,50 @@ impl<'a> System<'a> for Sys {
     ) {
         let start_time = std::time::Instant::now();
         span!(_guard, "run", "mount::Sys::run");
+        // Hitched carts are dragged along behind the animal pulling them, before riders
+        // are moved along with their mounts below.
+        let mut to_unhitch = Vec::new();
+        for (entity, Hitched(puller_uid)) in (&entities, &hitches).join() {
+            let puller = uid_allocator
+                .retrieve_entity_internal(puller_uid.id())
+                .filter(|puller| entities.is_alive(*puller))
+                .and_then(|puller| Some((*positions.get(puller)?, *velocities.get(puller)?)));
+            let (pos, puller_pos, puller_vel) = match (positions.get(entity), puller) {
+                (Some(pos), Some((puller_pos, puller_vel))) => (pos.0, puller_pos, puller_vel),
+                _ => {
+                    to_unhitch.push(entity);
+                    continue;
+                },
+            };
+
+            let to_puller = puller_pos.0.xy() - pos.xy();
+            let dist = to_puller.magnitude();
+            if dist > MAX_HITCH_LENGTH {
+                to_unhitch.push(entity);
+                continue;
+            } else if dist < f32::EPSILON {
+                continue;
+            }
+            let dir = to_puller / dist;
+
+            // The cart can't get further than the length of the hitch from the animal, and
+            // only rolls forward along the direction it is being pulled in
+            if dist > HITCH_LENGTH {
+                let xy = puller_pos.0.xy() - dir * HITCH_LENGTH;
+                let _ = positions.insert(entity, Pos(Vec3::new(xy.x, xy.y, pos.z)));
+            }
+            if let Some(vel) = velocities.get_mut(entity) {
+                let xy = dir * puller_vel.0.xy().dot(dir).max(0.0);
+                vel.0 = Vec3::new(xy.x, xy.y, vel.0.z);
+            }
+            if let Some(dir) = Dir::from_unnormalized(Vec3::new(dir.x, dir.y, 0.0)) {
+                let _ = orientations.insert(entity, Ori(dir));
+            }
+        }
+        for entity in to_unhitch {
+            hitches.remove(entity);
+        }
+
         // Mounted entities.
         for (entity, mut mount_states) in (&entities, &mut mount_state.restrict_mut()).join() {
             match mount_states.get_unchecked() {
@@ -1,12 +1,13 @@
 use crate::{
     comp::{
-        BeamSegment, Collider, Gravity, Mass, Mounting, Ori, PhysicsState, Pos, Projectile, Scale,
-        Shockwave, Sticky, Vel,
+        BeamSegment, CharacterState, Collider, Gravity, Instance, Mass, Mounting, Ori,
+        PhysicsState, Pos, Projectile, Scale, Shockwave, Sticky, Vel,
     },
     event::{EventBus, ServerEvent},
     metrics::SysMetrics,
     span,
     state::DeltaTime,
+    states::crouch,
     sync::Uid,
     terrain::{Block, TerrainGrid},
     vol::ReadVol,
@@ -43,6 +44,16 @@ fn integrate_forces(dt: f32, mut lv: Vec3<f32>, grav: f32, damp: f32) -> Vec3<f3
     lv * linear_damp
 }
 
+/// Lowers the top of the collider for crouching entities
+fn crouched_z_limits(z_limits: (f32, f32), character_state: Option<&CharacterState>) -> (f32, f32) {
+    if character_state.map_or(false, |cs| cs.is_crouch()) {
+        let (z_min, z_max) = z_limits;
+        (z_min, z_min + (z_max - z_min) * crouch::HEIGHT_FACTOR)
+    } else {
+        z_limits
+    }
+}
+
 /// This system applies forces and calculates new positions and velocities.
 pub struct Sys;
 impl<'a> System<'a> for Sys {
@@ -67,6 +78,8 @@ impl<'a> System<'a> for Sys {
         ReadStorage<'a, Projectile>,
         ReadStorage<'a, BeamSegment>,
         ReadStorage<'a, Shockwave>,
+        ReadStorage<'a, Instance>,
+        ReadStorage<'a, CharacterState>,
     );
 
     #[allow(clippy::or_fun_call)] // TODO: Pending review in #587
@@ -93,6 +106,8 @@ impl<'a> System<'a> for Sys {
             projectiles,
             beams,
             shockwaves,
+            instances,
+            character_states,
         ): Self::SystemData,
     ) {
         let start_time = std::time::Instant::now();
@@ -150,7 +165,10 @@ impl<'a> System<'a> for Sys {
         {
             let scale = scale.map(|s| s.0).unwrap_or(1.0);
             let radius = collider.map(|c| c.get_radius()).unwrap_or(0.5);
-            let z_limits = collider.map(|c| c.get_z_limits()).unwrap_or((-0.5, 0.5));
+            let z_limits = crouched_z_limits(
+                collider.map(|c| c.get_z_limits()).unwrap_or((-0.5, 0.5)),
+                character_states.get(entity),
+            );
             let mass = mass.map(|m| m.0).unwrap_or(scale);
 
             // Resets touch_entities in physics
@@ -189,11 +207,19 @@ impl<'a> System<'a> for Sys {
                     continue;
                 }
 
+                // Entities in different dungeon instances pass through each other
+                if instances.get(entity) != instances.get(entity_other) {
+                    continue;
+                }
+
                 let scale_other = scale_other.map(|s| s.0).unwrap_or(1.0);
                 let radius_other = collider_other.map(|c| c.get_radius()).unwrap_or(0.5);
-                let z_limits_other = collider_other
-                    .map(|c| c.get_z_limits())
-                    .unwrap_or((-0.5, 0.5));
+                let z_limits_other = crouched_z_limits(
+                    collider_other
+                        .map(|c| c.get_z_limits())
+                        .unwrap_or((-0.5, 0.5)),
+                    character_states.get(entity_other),
+                );
                 let mass_other = mass_other.map(|m| m.0).unwrap_or(scale_other);
                 if mass_other == 0.0 {
                     continue;
@@ -285,7 +311,8 @@ impl<'a> System<'a> for Sys {
             let old_vel = *vel;
             // Integrate forces
             // Friction is assumed to be a constant dependent on location
-            let friction = FRIC_AIR
+            let air_friction = projectiles.get(entity).map_or(FRIC_AIR, |p| p.drag);
+            let friction = air_friction
                 .max(if physics_state.on_ground {
                     FRIC_GROUND
                 } else {
@@ -310,7 +337,13 @@ impl<'a> System<'a> for Sys {
             } else {
                 GRAVITY
             } * gravities.get(entity).map(|g| g.0).unwrap_or_default();
-            vel.0 = integrate_forces(dt.0, vel.0, downward_force, friction);
+            // Rivers carry whatever is in them along with their flow
+            let flow = if physics_state.in_fluid.is_some() {
+                Vec3::from(terrain.flow_at(pos.0.map(|e| e.floor() as i32)))
+            } else {
+                Vec3::zero()
+            };
+            vel.0 = integrate_forces(dt.0, vel.0 - flow, downward_force, friction) + flow;
 
             // Don't move if we're not in a loaded chunk
             let mut pos_delta = if in_loaded_chunk {
@@ -334,6 +367,8 @@ impl<'a> System<'a> for Sys {
                     let radius = radius.min(0.45); // * scale;
                     let z_min = z_min; // * scale;
                     let z_max = z_max.clamped(1.2, 1.95); // * scale;
+                    let (z_min, z_max) =
+                        crouched_z_limits((z_min, z_max), character_states.get(entity));
 
                     // Probe distances
                     let hdist = radius.ceil() as i32;
@@ -1,15 +1,20 @@
 use crate::{
+    combat::{self, CombatRules, Combatant},
     comp::{
-        projectile, Damage, DamageSource, Energy, EnergySource, Group, HealthChange, HealthSource,
-        Loadout, Ori, PhysicsState, Pos, Projectile, Vel,
+        armor_penetration, on_hit_effects, projectile, Body, Buff, BuffCategory, BuffChange,
+        BuffSource, Buffs, CharacterState, CritStats, Damage, DamageSource, Energy, EnergySource,
+        Group, HealthChange, HealthSource, Invulnerable, Loadout, Ori, PhysicsState, Player, Pos,
+        Projectile, Scale, Vel,
     },
     event::{EventBus, LocalEvent, ServerEvent},
     metrics::SysMetrics,
     span,
-    state::DeltaTime,
+    state::{DeltaTime, Time},
     sync::UidAllocator,
+    sys::combat::BLOCK_ANGLE,
     util::Dir,
 };
+use rand::{thread_rng, Rng};
 use specs::{
     saveload::MarkerAllocator, Entities, Join, Read, ReadExpect, ReadStorage, System, WriteStorage,
 };
@@ -23,10 +28,12 @@ impl<'a> System<'a> for Sys {
     type SystemData = (
         Entities<'a>,
         Read<'a, DeltaTime>,
+        Read<'a, Time>,
         Read<'a, UidAllocator>,
         Read<'a, EventBus<LocalEvent>>,
         Read<'a, EventBus<ServerEvent>>,
         ReadExpect<'a, SysMetrics>,
+        Read<'a, CombatRules>,
         ReadStorage<'a, Pos>,
         ReadStorage<'a, PhysicsState>,
         ReadStorage<'a, Vel>,
@@ -35,6 +42,12 @@ impl<'a> System<'a> for Sys {
         WriteStorage<'a, Energy>,
         ReadStorage<'a, Loadout>,
         ReadStorage<'a, Group>,
+        ReadStorage<'a, Body>,
+        ReadStorage<'a, Buffs>,
+        ReadStorage<'a, Player>,
+        ReadStorage<'a, Invulnerable>,
+        ReadStorage<'a, CharacterState>,
+        ReadStorage<'a, Scale>,
     );
 
     fn run(
@@ -42,10 +55,12 @@ impl<'a> System<'a> for Sys {
         (
             entities,
             dt,
+            time,
             uid_allocator,
             local_bus,
             server_bus,
             sys_metrics,
+            combat_rules,
             positions,
             physics_states,
             velocities,
@@ -54,19 +69,34 @@ impl<'a> System<'a> for Sys {
             mut energies,
             loadouts,
             groups,
+            bodies,
+            buffs,
+            players,
+            invulnerables,
+            character_states,
+            scales,
         ): Self::SystemData,
     ) {
         let start_time = std::time::Instant::now();
         span!(_guard, "run", "projectile::Sys::run");
         let mut local_emitter = local_bus.emitter();
         let mut server_emitter = server_bus.emitter();
+        let combatant = |entity, uid| {
+            positions
+                .get(entity)
+                .map(|pos| Combatant::new(entity, uid, pos.0, &players, &groups))
+        };
+
+        // Orientations can't be changed while the defenders' facing is looked up, so new
+        // orientations are applied once all projectiles are handled
+        let mut ori_updates = Vec::new();
 
         // Attacks
         for (entity, pos, physics, ori, projectile) in (
             &entities,
             &positions,
             &physics_states,
-            &mut orientations,
+            &orientations,
             &mut projectiles,
         )
             .join()
@@ -91,25 +121,148 @@ impl<'a> System<'a> for Sys {
                     continue;
                 }
 
-                if projectile.owner == Some(other) {
+                if projectile.owner == Some(other) || projectile.hit_entities.contains(&other) {
+                    continue;
+                }
+
+                // Invulnerable entities are passed through as if they weren't there
+                if uid_allocator
+                    .retrieve_entity_internal(other.into())
+                    .and_then(|e| invulnerables.get(e))
+                    .map_or(false, |i| i.is_active(time.0))
+                {
                     continue;
                 }
 
-                for effect in projectile.hit_entity.drain(..) {
+                // Projectiles coming from in front of a blocking target are stopped by its
+                // shield
+                let other_entity = uid_allocator.retrieve_entity_internal(other.into());
+                let block = other_entity.map_or(false, |e| {
+                    character_states.get(e).map_or(false, |c| c.is_block())
+                        && match (positions.get(e), orientations.get(e)) {
+                            (Some(pos_b), Some(ori_b)) => {
+                                ori_b.0.angle_between(pos.0 - pos_b.0)
+                                    < BLOCK_ANGLE.to_radians() / 2.0
+                            },
+                            _ => false,
+                        }
+                });
+
+                // Shields that reflect damage have a chance to deflect the projectile along
+                // the defender's facing, turning it against whoever is in front of them
+                let reflect = other_entity
+                    .and_then(|e| loadouts.get(e))
+                    .map_or(0.0, |l| l.get_block_reflection());
+                if block && thread_rng().gen::<f32>() < reflect {
+                    if let Some((ori_b, speed)) = other_entity
+                        .and_then(|e| orientations.get(e))
+                        .zip(velocities.get(entity).map(|vel| vel.0.magnitude()))
+                    {
+                        local_emitter.emit(LocalEvent::ApplyImpulse {
+                            entity,
+                            impulse: *ori_b.0 * speed,
+                        });
+                        projectile.owner = Some(other);
+                        projectile.hit_entities.push(other);
+                        continue;
+                    }
+                }
+
+                // Penetrating projectiles keep their effects for the next target, but lose
+                // some damage with each one they pass through
+                let penetrates = projectile.penetration > 0;
+                let damage_falloff =
+                    projectile::PENETRATION_FALLOFF.powi(projectile.hit_entities.len() as i32);
+                let effects = if penetrates {
+                    projectile.penetration -= 1;
+                    projectile.hit_entity.clone()
+                } else {
+                    projectile.hit_entity.drain(..).collect()
+                };
+                projectile.hit_entities.push(other);
+
+                // Projectiles that strike within the head of the target deal extra damage
+                let headshot = other_entity.map_or(false, |e| {
+                    match (positions.get(e), orientations.get(e), bodies.get(e)) {
+                        (Some(pos_b), Some(ori_b), Some(body_b)) => {
+                            body_b.head_sphere().map_or(false, |(offset, radius)| {
+                                let scale = scales.get(e).map_or(1.0, |s| s.0);
+                                let forward = Vec2::from(*ori_b.0)
+                                    .try_normalized()
+                                    .unwrap_or_else(Vec2::unit_y);
+                                let right = Vec2::new(forward.y, -forward.x);
+                                let head = pos_b.0
+                                    + Vec3::from(right * offset.x + forward * offset.y) * scale
+                                    + Vec3::unit_z() * offset.z * scale;
+                                head.distance_squared(pos.0)
+                                    < (radius * scale + projectile::HEADSHOT_LENIENCY).powi(2)
+                            })
+                        },
+                        _ => false,
+                    }
+                });
+
+                for effect in effects {
                     match effect {
-                        projectile::Effect::Damage(healthchange) => {
+                        projectile::Effect::Damage(healthchange, kind) => {
+                            let healthchange = (healthchange as f32 * damage_falloff) as i32;
+                            let headshot = headshot && healthchange < 0;
+                            let healthchange = if headshot {
+                                (healthchange as f32 * projectile::HEADSHOT_MULTIPLIER) as i32
+                            } else {
+                                healthchange
+                            };
                             let owner_uid = projectile.owner.unwrap();
+                            let owner_entity =
+                                uid_allocator.retrieve_entity_internal(owner_uid.into());
+                            // Only damage can be critical, not healing
+                            let crit = if healthchange < 0 {
+                                CritStats::new(
+                                    owner_entity.and_then(|e| loadouts.get(e)),
+                                    owner_entity.and_then(|e| buffs.get(e)),
+                                )
+                                .roll()
+                            } else {
+                                None
+                            };
+                            // Only damage is raised by the owner's buffs, not healing
+                            let modifier = if healthchange < 0 {
+                                owner_entity
+                                    .and_then(|e| buffs.get(e))
+                                    .map_or(1.0, |b| b.damage_modifier())
+                            } else {
+                                1.0
+                            };
                             let mut damage = Damage {
-                                healthchange: healthchange as f32,
+                                healthchange: healthchange as f32 * modifier,
                                 source: DamageSource::Projectile,
+                                kind: Some(kind),
+                                crit,
+                                penetration: armor_penetration(
+                                    owner_entity.and_then(|e| loadouts.get(e)),
+                                ),
+                                true_damage: false,
                             };
 
-                            let other_entity = uid_allocator.retrieve_entity_internal(other.into());
-                            if let Some(loadout) = other_entity.and_then(|e| loadouts.get(e)) {
-                                damage.modify_damage(false, loadout);
-                            }
+                            let mitigated = damage.modify_damage(
+                                block,
+                                other_entity.and_then(|e| loadouts.get(e)),
+                                other_entity.and_then(|e| bodies.get(e)),
+                            );
+
+                            // Don't damage those the combat rules protect
+                            let protected = damage.healthchange < 0.0
+                                && match (
+                                    owner_entity.and_then(|e| combatant(e, owner_uid)),
+                                    other_entity.and_then(|e| combatant(e, other)),
+                                ) {
+                                    (Some(attacker), Some(target)) => {
+                                        !combat_rules.can_harm(attacker, target)
+                                    },
+                                    _ => false,
+                                };
 
-                            if other != owner_uid {
+                            if other != owner_uid && !protected {
                                 if damage.healthchange < 0.0 {
                                     server_emitter.emit(ServerEvent::Damage {
                                         uid: other,
@@ -117,9 +270,29 @@ impl<'a> System<'a> for Sys {
                                             amount: damage.healthchange as i32,
                                             cause: HealthSource::Projectile {
                                                 owner: Some(owner_uid),
+                                                headshot,
                                             },
+                                            crit: damage.crit.is_some(),
+                                            mitigated,
+                                            overkill: 0,
                                         },
                                     });
+                                    if let (Some(attacker), Some(target)) =
+                                        (owner_entity, other_entity)
+                                    {
+                                        let effects = on_hit_effects(
+                                            loadouts.get(attacker),
+                                            buffs.get(attacker),
+                                        );
+                                        if !effects.is_empty() {
+                                            server_emitter.emit(ServerEvent::OnHit {
+                                                attacker,
+                                                target,
+                                                damage: -damage.healthchange as u32,
+                                                effects,
+                                            });
+                                        }
+                                    }
                                 } else if damage.healthchange > 0.0 {
                                     server_emitter.emit(ServerEvent::Damage {
                                         uid: other,
@@ -128,6 +301,9 @@ impl<'a> System<'a> for Sys {
                                             cause: HealthSource::Healing {
                                                 by: Some(owner_uid),
                                             },
+                                            crit: false,
+                                            mitigated,
+                                            overkill: 0,
                                         },
                                     });
                                 }
@@ -137,11 +313,19 @@ impl<'a> System<'a> for Sys {
                             if let Some(entity) =
                                 uid_allocator.retrieve_entity_internal(other.into())
                             {
-                                local_emitter.emit(LocalEvent::ApplyImpulse {
-                                    entity,
-                                    impulse: knockback
-                                        * *Dir::slerp(ori.0, Dir::new(Vec3::unit_z()), 0.5),
-                                });
+                                let knockback = knockback
+                                    * combat::knockback_multiplier(
+                                        bodies.get(entity),
+                                        scales.get(entity),
+                                        buffs.get(entity),
+                                    );
+                                if knockback != 0.0 {
+                                    local_emitter.emit(LocalEvent::ApplyImpulse {
+                                        entity,
+                                        impulse: knockback
+                                            * *Dir::slerp(ori.0, Dir::new(Vec3::unit_z()), 0.5),
+                                    });
+                                }
                             }
                         },
                         projectile::Effect::RewardEnergy(energy) => {
@@ -162,10 +346,29 @@ impl<'a> System<'a> for Sys {
                                 reagent: None,
                             })
                         },
-                        projectile::Effect::Vanish => server_emitter.emit(ServerEvent::Destroy {
-                            entity,
-                            cause: HealthSource::World,
-                        }),
+                        projectile::Effect::Vanish if !penetrates => {
+                            server_emitter.emit(ServerEvent::Destroy {
+                                entity,
+                                cause: HealthSource::World,
+                            })
+                        },
+                        // Shields stop whatever the arrow was tipped with
+                        projectile::Effect::Buff { kind, data } if !block => {
+                            if let Some(entity) = other_entity {
+                                let source = projectile
+                                    .owner
+                                    .map_or(BuffSource::Item, |by| BuffSource::Character { by });
+                                server_emitter.emit(ServerEvent::Buff {
+                                    entity,
+                                    buff_change: BuffChange::Add(Buff::new(
+                                        kind,
+                                        data,
+                                        vec![BuffCategory::Physical],
+                                        source,
+                                    )),
+                                });
+                            }
+                        },
                         projectile::Effect::Possess => {
                             if other != projectile.owner.unwrap() {
                                 if let Some(owner) = projectile.owner {
@@ -195,6 +398,9 @@ impl<'a> System<'a> for Sys {
                             entity,
                             cause: HealthSource::World,
                         }),
+                        projectile::Effect::Recover(item) => {
+                            server_emitter.emit(ServerEvent::RecoverProjectile { entity, item })
+                        },
                         _ => {},
                     }
                 }
@@ -202,7 +408,7 @@ impl<'a> System<'a> for Sys {
                 .get(entity)
                 .and_then(|vel| vel.0.try_normalized())
             {
-                ori.0 = dir.into();
+                ori_updates.push((entity, Ori(dir.into())));
             }
 
             if projectile.time_left == Duration::default() {
@@ -216,6 +422,10 @@ impl<'a> System<'a> for Sys {
                 .checked_sub(Duration::from_secs_f32(dt.0))
                 .unwrap_or_default();
         }
+        for (entity, ori) in ori_updates {
+            let _ = orientations.insert(entity, ori);
+        }
+
         sys_metrics.projectile_ns.store(
             start_time.elapsed().as_nanos() as i64,
             std::sync::atomic::Ordering::Relaxed,
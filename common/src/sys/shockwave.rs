@@ -1,7 +1,9 @@
 use crate::{
+    combat::{CombatRules, Combatant},
     comp::{
-        group, Body, CharacterState, Damage, DamageSource, HealthChange, HealthSource, Last,
-        Loadout, Ori, PhysicsState, Pos, Scale, Shockwave, ShockwaveHitEntities, Stats,
+        armor_penetration, group, Body, Buffs, CharacterState, Damage, DamageKind, DamageSource,
+        HealthChange, HealthSource, Instance, Invulnerable, Last, Loadout, Ori, PhysicsState,
+        Player, Pos, Scale, Shockwave, ShockwaveHitEntities, Stats,
     },
     event::{EventBus, LocalEvent, ServerEvent},
     state::{DeltaTime, Time},
@@ -25,6 +27,7 @@ impl<'a> System<'a> for Sys {
         Read<'a, Time>,
         Read<'a, DeltaTime>,
         Read<'a, UidAllocator>,
+        Read<'a, CombatRules>,
         ReadStorage<'a, Uid>,
         ReadStorage<'a, Pos>,
         ReadStorage<'a, Last<Pos>>,
@@ -36,6 +39,10 @@ impl<'a> System<'a> for Sys {
         ReadStorage<'a, group::Group>,
         ReadStorage<'a, CharacterState>,
         ReadStorage<'a, PhysicsState>,
+        ReadStorage<'a, Player>,
+        ReadStorage<'a, Invulnerable>,
+        ReadStorage<'a, Buffs>,
+        ReadStorage<'a, Instance>,
         WriteStorage<'a, Shockwave>,
         WriteStorage<'a, ShockwaveHitEntities>,
     );
@@ -49,6 +56,7 @@ impl<'a> System<'a> for Sys {
             time,
             dt,
             uid_allocator,
+            combat_rules,
             uids,
             positions,
             last_positions,
@@ -60,6 +68,10 @@ impl<'a> System<'a> for Sys {
             groups,
             character_states,
             physics_states,
+            players,
+            invulnerables,
+            buffs,
+            instances,
             mut shockwaves,
             mut shockwave_hit_lists,
         ): Self::SystemData,
@@ -123,6 +135,30 @@ impl<'a> System<'a> for Sys {
                 .owner
                 .and_then(|uid| uid_allocator.retrieve_entity_internal(uid.into()))
                 .and_then(|e| groups.get(e));
+            let owner = shockwave.owner.and_then(|uid| {
+                let owner = uid_allocator.retrieve_entity_internal(uid.into())?;
+                let pos = positions.get(owner)?;
+                Some(Combatant {
+                    // Friendly fire shockwaves ignore group immunity
+                    group: if shockwave.friendly_fire {
+                        None
+                    } else {
+                        groups.get(owner).copied()
+                    },
+                    ..Combatant::new(owner, uid, pos.0, &players, &groups)
+                })
+            });
+            let damage_modifier = shockwave
+                .owner
+                .and_then(|uid| uid_allocator.retrieve_entity_internal(uid.into()))
+                .and_then(|e| buffs.get(e))
+                .map_or(1.0, |b| b.damage_modifier());
+            let penetration = armor_penetration(
+                shockwave
+                    .owner
+                    .and_then(|uid| uid_allocator.retrieve_entity_internal(uid.into()))
+                    .and_then(|e| loadouts.get(e)),
+            );
 
             // Go through all other effectable entities
             for (
@@ -151,11 +187,13 @@ impl<'a> System<'a> for Sys {
             )
                 .join()
             {
-                // Check to see if entity has already been hit
+                // Check to see if entity has already been hit, shockwaves only reach
+                // entities in the same dungeon instance as their owner
                 if shockwave_hit_list
                     .hit_entities
                     .iter()
                     .any(|&uid| uid == *uid_b)
+                    || instances.get(entity) != instances.get(b)
                 {
                     continue;
                 }
@@ -176,6 +214,15 @@ impl<'a> System<'a> for Sys {
                 let same_group = group
                     .map(|group_a| Some(group_a) == groups.get(b))
                     .unwrap_or(Some(*uid_b) == shockwave.owner);
+                // Friendly fire still spares the caster
+                let spared = if shockwave.friendly_fire {
+                    Some(*uid_b) == shockwave.owner
+                } else {
+                    same_group
+                } || owner.map_or(false, |owner| {
+                    !combat_rules
+                        .can_harm(owner, Combatant::new(b, *uid_b, pos_b.0, &players, &groups))
+                }) || invulnerables.get(b).map_or(false, |i| i.is_active(time));
 
                 // Check if it is a hit
                 let hit = entity != b
@@ -190,20 +237,22 @@ impl<'a> System<'a> for Sys {
                     }
                     && (pos_b_ground - pos.0).angle_between(pos_b.0 - pos.0) < max_angle
                     && (!shockwave.requires_ground || physics_state_b.on_ground)
-                    && !same_group;
+                    && !spared;
 
                 if hit {
                     let mut damage = Damage {
-                        healthchange: -(shockwave.damage as f32),
+                        healthchange: -(shockwave.damage as f32) * damage_modifier,
                         source: DamageSource::Shockwave,
+                        kind: Some(DamageKind::Crushing),
+                        crit: None,
+                        penetration,
+                        true_damage: shockwave.true_damage,
                     };
 
                     let block = character_b.map(|c_b| c_b.is_block()).unwrap_or(false)
                         && ori_b.0.angle_between(pos.0 - pos_b.0) < BLOCK_ANGLE.to_radians() / 2.0;
 
-                    if let Some(loadout) = loadouts.get(b) {
-                        damage.modify_damage(block, loadout);
-                    }
+                    let mitigated = damage.modify_damage(block, loadouts.get(b), Some(body_b));
 
                     if damage.healthchange != 0.0 {
                         let cause = if damage.healthchange < 0.0 {
@@ -220,6 +269,9 @@ impl<'a> System<'a> for Sys {
                             change: HealthChange {
                                 amount: damage.healthchange as i32,
                                 cause,
+                                crit: false,
+                                mitigated,
+                                overkill: 0,
                             },
                         });
                         shockwave_hit_list.hit_entities.push(*uid_b);
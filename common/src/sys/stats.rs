@@ -1,15 +1,23 @@
 use crate::{
-    comp::{CharacterState, Energy, EnergySource, HealthSource, Stats},
+    comp::{
+        Buff, BuffCategory, BuffChange, BuffData, BuffKind, BuffSource, Buffs, CharacterState,
+        Energy, Group, HealthSource, Loadout, Player, RegenMode, Stats, Vel,
+    },
     event::{EventBus, ServerEvent},
     metrics::SysMetrics,
     span,
     state::DeltaTime,
 };
 use specs::{Entities, Join, Read, ReadExpect, ReadStorage, System, WriteStorage};
+use std::time::Duration;
 
-const ENERGY_REGEN_ACCEL: f32 = 10.0;
+/// How long entities stay exhausted after running out of energy
+const EXHAUSTION_DURATION: Duration = Duration::from_secs(3);
+/// Speed below which entities count as standing still, recovering energy faster
+const STILL_SPEED: f32 = 0.5;
 
-/// This system kills players, levels them up, and regenerates energy.
+/// This system kills players (or downs them while in a group), levels them up,
+/// and regenerates energy.
 pub struct Sys;
 impl<'a> System<'a> for Sys {
     #[allow(clippy::type_complexity)]
@@ -19,13 +27,31 @@ impl<'a> System<'a> for Sys {
         Read<'a, EventBus<ServerEvent>>,
         ReadExpect<'a, SysMetrics>,
         ReadStorage<'a, CharacterState>,
+        ReadStorage<'a, Player>,
+        ReadStorage<'a, Group>,
+        ReadStorage<'a, Vel>,
+        ReadStorage<'a, Buffs>,
+        ReadStorage<'a, Loadout>,
         WriteStorage<'a, Stats>,
         WriteStorage<'a, Energy>,
     );
 
     fn run(
         &mut self,
-        (entities, dt, server_event_bus, sys_metrics, character_states, mut stats, mut energies): Self::SystemData,
+        (
+            entities,
+            dt,
+            server_event_bus,
+            sys_metrics,
+            character_states,
+            players,
+            groups,
+            velocities,
+            buffs,
+            loadouts,
+            mut stats,
+            mut energies,
+        ): Self::SystemData,
     ) {
         let start_time = std::time::Instant::now();
         span!(_guard, "run", "stats::Sys::run");
@@ -37,6 +63,11 @@ impl<'a> System<'a> for Sys {
             stat.health.last_change.0 += f64::from(dt.0);
         }
         stats.set_event_emission(true);
+        energies.set_event_emission(false);
+        for energy in (&mut energies).join() {
+            energy.since_spent += dt.0;
+        }
+        energies.set_event_emission(true);
 
         // Update stats
         for (entity, mut stats) in (&entities, &mut stats.restrict_mut()).join() {
@@ -49,13 +80,30 @@ impl<'a> System<'a> for Sys {
             };
 
             if set_dead {
-                let stat = stats.get_mut_unchecked();
-                server_event_emitter.emit(ServerEvent::Destroy {
-                    entity,
-                    cause: stat.health.last_change.1.cause,
-                });
+                match character_states.get(entity) {
+                    // Downed players die once they bleed out
+                    Some(CharacterState::Downed(data)) if !data.bled_out() => {},
+                    // Players in a group go down first, giving the group a chance to revive them
+                    Some(character_state)
+                        if !character_state.is_downed()
+                            && players.contains(entity)
+                            && groups.contains(entity) =>
+                    {
+                        server_event_emitter.emit(ServerEvent::Downed {
+                            entity,
+                            cause: stats.get_unchecked().health.last_change.1.cause,
+                        });
+                    },
+                    _ => {
+                        let stat = stats.get_mut_unchecked();
+                        server_event_emitter.emit(ServerEvent::Destroy {
+                            entity,
+                            cause: stat.health.last_change.1.cause,
+                        });
 
-                stat.is_dead = true;
+                        stat.is_dead = true;
+                    },
+                }
             }
 
             if level_up {
@@ -74,70 +122,66 @@ impl<'a> System<'a> for Sys {
         }
 
         // Update energies
-        for (character_state, mut energy) in
-            (&character_states, &mut energies.restrict_mut()).join()
+        for (entity, character_state, vel, buffs, loadout, mut energy) in (
+            &entities,
+            &character_states,
+            velocities.maybe(),
+            buffs.maybe(),
+            loadouts.maybe(),
+            &mut energies.restrict_mut(),
+        )
+            .join()
         {
-            match character_state {
-                // Accelerate recharging energy.
-                CharacterState::Idle { .. }
-                | CharacterState::Sit { .. }
-                | CharacterState::Dance { .. }
-                | CharacterState::Sneak { .. }
-                | CharacterState::Glide { .. }
-                | CharacterState::GlideWield { .. }
-                | CharacterState::Wielding { .. }
-                | CharacterState::Equipping { .. }
-                | CharacterState::Boost { .. } => {
-                    let res = {
-                        let energy = energy.get_unchecked();
-                        energy.current() < energy.maximum()
-                    };
+            // Running out of energy leaves the entity exhausted for a while
+            if energy.get_unchecked().ran_out {
+                energy.get_mut_unchecked().ran_out = false;
+                if !buffs.map_or(false, |buffs| buffs.is_exhausted()) {
+                    server_event_emitter.emit(ServerEvent::Buff {
+                        entity,
+                        buff_change: BuffChange::Add(Buff::new(
+                            BuffKind::Exhausted,
+                            BuffData {
+                                strength: 1.0,
+                                duration: Some(EXHAUSTION_DURATION),
+                            },
+                            vec![BuffCategory::Natural],
+                            BuffSource::World,
+                        )),
+                    });
+                }
+            }
 
-                    if res {
-                        let mut energy = energy.get_mut_unchecked();
-                        // Have to account for Calc I differential equations due to acceleration
-                        energy.change_by(
-                            (energy.regen_rate * dt.0 + ENERGY_REGEN_ACCEL * dt.0.powf(2.0) / 2.0)
-                                as i32,
-                            EnergySource::Regen,
-                        );
-                        energy.regen_rate =
-                            (energy.regen_rate + ENERGY_REGEN_ACCEL * dt.0).min(100.0);
-                    }
-                },
-                // Ability use does not regen and sets the rate back to zero.
-                CharacterState::BasicMelee { .. }
-                | CharacterState::DashMelee { .. }
-                | CharacterState::LeapMelee { .. }
-                | CharacterState::SpinMelee { .. }
-                | CharacterState::ComboMelee { .. }
-                | CharacterState::BasicRanged { .. }
-                | CharacterState::ChargedMelee { .. }
-                | CharacterState::ChargedRanged { .. }
-                | CharacterState::RepeaterRanged { .. }
-                | CharacterState::Shockwave { .. }
-                | CharacterState::BasicBeam { .. } => {
-                    if energy.get_unchecked().regen_rate != 0.0 {
-                        energy.get_mut_unchecked().regen_rate = 0.0
-                    }
+            // How fast energy recovers depends on what the entity is doing, on
+            // whether it is standing still, and on its gear and buffs
+            let (regen, current_regen) = {
+                let energy = energy.get_unchecked();
+                (energy.regen_rate, energy.current_regen)
+            };
+            match character_state.energy_regen() {
+                RegenMode::Recover(modifier) if energy.get_unchecked().can_regenerate() => {
+                    let mut energy = energy.get_mut_unchecked();
+                    let still =
+                        vel.map_or(true, |vel| vel.0.magnitude_squared() < STILL_SPEED.powi(2));
+                    let modifier = modifier
+                        * if still {
+                            energy.regen.still_modifier
+                        } else {
+                            1.0
+                        }
+                        * loadout.map_or(1.0, Loadout::get_energy_regen_modifier)
+                        * buffs.map_or(1.0, Buffs::energy_regen_modifier);
+                    energy.regenerate(dt.0, modifier);
                 },
-                // recover small amount of passive energy from blocking, and bonus energy from
-                // blocking attacks?
-                CharacterState::BasicBlock => {
-                    let res = {
-                        let energy = energy.get_unchecked();
-                        energy.current() < energy.maximum()
-                    };
-
-                    if res {
-                        energy
-                            .get_mut_unchecked()
-                            .change_by(-3, EnergySource::Regen);
-                    }
+                // Using abilities sets the speed of the recovery back to nothing
+                RegenMode::Reset if regen != 0.0 || current_regen != 0.0 => {
+                    let mut energy = energy.get_mut_unchecked();
+                    energy.regen_rate = 0.0;
+                    energy.current_regen = 0.0;
                 },
-                // Non-combat abilities that consume energy;
-                // temporarily stall energy gain, but preserve regen_rate.
-                CharacterState::Roll { .. } | CharacterState::Climb { .. } => {},
+                // Moving in ways that use up energy, or waiting for the delay after
+                // spending it, holds the recovery back but keeps its speed
+                _ if current_regen != 0.0 => energy.get_mut_unchecked().current_regen = 0.0,
+                _ => {},
             }
         }
         sys_metrics.stats_ns.store(
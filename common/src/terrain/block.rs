@@ -1,4 +1,4 @@
-use super::SpriteKind;
+use super::{hazard, sprite::SPRITE_HEALTH_UNIT, Hazard, SpriteKind};
 use crate::make_case_elim;
 use enum_iterator::IntoEnumIterator;
 use lazy_static::lazy_static;
@@ -32,7 +32,8 @@ make_case_elim!(
         // being *very* fast).
         Rock = 0x10,
         WeakRock = 0x11, // Explodable
-        // 0x12 <= x < 0x20 is reserved for future rocks
+        Lava = 0x12,     // Molten rock, which burns whatever touches it
+        // 0x13 <= x < 0x20 is reserved for future rocks
         Grass = 0x20, // Note: *not* the same as grass sprites
         // 0x21 <= x < 0x30 is reserved for future grasses
         Earth = 0x30,
@@ -41,6 +42,8 @@ make_case_elim!(
         Wood = 0x40,
         Leaves = 0x41,
         // 0x42 <= x < 0x50 is reserved for future tree parts
+        Ice = 0x50,
+        // 0x51 <= x < 0x60 is reserved for future frozen blocks
 
         // Covers all other cases (we sometimes have bizarrely coloured misc blocks, and also we
         // often want to experiment with new kinds of block without allocating them a
@@ -71,6 +74,14 @@ impl BlockKind {
     /// fields.
     #[inline]
     pub const fn has_color(&self) -> bool { self.is_filled() }
+
+    /// Harm done to entities touching blocks of this kind
+    pub fn hazard(&self) -> Option<Hazard> {
+        match self {
+            BlockKind::Lava => Some(hazard::LAVA),
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Display for BlockKind {
@@ -187,10 +198,20 @@ impl Block {
             // Explodable means that the terrain sprite will get removed anyway, so all is good for
             // empty fluids.
             // TODO: Handle the case of terrain sprites we don't want to have explode
-            _ => self.get_sprite().is_some(),
+            // Locked sprites can only be removed by unlocking them
+            _ => self.get_sprite().map_or(false, |s| !s.is_locked()),
         }
     }
 
+    /// Whether fire can spread onto this block
+    #[inline]
+    pub fn is_flammable(&self) -> bool {
+        matches!(
+            self.kind(),
+            BlockKind::Wood | BlockKind::Leaves | BlockKind::Grass
+        )
+    }
+
     #[inline]
     pub fn is_collectible(&self) -> bool {
         self.get_sprite()
@@ -198,6 +219,52 @@ impl Block {
             .unwrap_or(false)
     }
 
+    #[inline]
+    pub fn is_locked(&self) -> bool { self.get_sprite().map_or(false, |s| s.is_locked()) }
+
+    /// Whether attacks can break the sprite in this block
+    #[inline]
+    pub fn is_destructible(&self) -> bool {
+        self.get_sprite()
+            .map_or(false, |s| s.max_health().is_some())
+    }
+
+    /// Health left on a destructible sprite. The third attribute byte holds
+    /// the damage taken so far, so freshly generated sprites are unharmed.
+    #[inline]
+    pub fn get_sprite_health(&self) -> Option<u8> {
+        let max_health = self.get_sprite()?.max_health()?;
+        Some(max_health.saturating_sub(self.attr[2]))
+    }
+
+    /// Damage a destructible sprite, returning `None` if this breaks it (or if
+    /// it can't be damaged in the first place).
+    #[inline]
+    pub fn with_sprite_damage(mut self, damage: u32) -> Option<Self> {
+        let health = u32::from(self.get_sprite_health()?);
+        let lost = (damage / SPRITE_HEALTH_UNIT).max(1);
+        if lost >= health {
+            None
+        } else {
+            self.attr[2] += lost as u8;
+            Some(self)
+        }
+    }
+
+    /// Harm done to entities touching this block, from its sprite if it has
+    /// one and otherwise from its kind
+    #[inline]
+    pub fn hazard(&self) -> Option<Hazard> {
+        self.get_sprite()
+            .and_then(|s| s.hazard())
+            .or_else(|| self.kind().hazard())
+    }
+
+    #[inline]
+    pub fn is_bounty_board(&self) -> bool {
+        self.get_sprite().map_or(false, |s| s.is_bounty_board())
+    }
+
     #[inline]
     pub fn is_opaque(&self) -> bool { self.kind().is_filled() }
 
@@ -253,4 +320,22 @@ mod tests {
         assert_eq!(std::mem::size_of::<BlockKind>(), 1);
         assert_eq!(std::mem::size_of::<Block>(), 4);
     }
+
+    #[test]
+    fn sprite_breaks_after_enough_damage() {
+        let pot = Block::air(SpriteKind::Pot);
+        assert_eq!(pot.get_sprite_health(), Some(3));
+        let pot = pot.with_sprite_damage(15).unwrap();
+        assert_eq!(pot.get_sprite_health(), Some(2));
+        assert!(pot.with_sprite_damage(20).is_none());
+        assert!(Block::air(SpriteKind::Chest)
+            .with_sprite_damage(5)
+            .is_none());
+    }
+
+    #[test]
+    fn ember_hazard_goes_out_with_the_sprite() {
+        assert_eq!(Block::air(SpriteKind::Ember).hazard(), Some(hazard::EMBER));
+        assert_eq!(Block::air(SpriteKind::Empty).hazard(), None);
+    }
 }
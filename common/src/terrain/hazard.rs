@@ -0,0 +1,54 @@
+//! Harm that terrain does to the entities touching it.
+//!
+//! Block kinds and sprites look up their hazard here, so that the numbers for
+//! all of them can be compared and tuned in one place.
+
+use crate::comp::{BuffData, BuffKind, DamageKind};
+use std::time::Duration;
+
+/// Harm done to entities touching a hazardous block or sprite
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Hazard {
+    /// Health lost per second while touching the block
+    pub damage: f32,
+    pub kind: DamageKind,
+    /// Debuff applied, and kept refreshed, while touching the block
+    pub buff: Option<(BuffKind, BuffData)>,
+}
+
+pub const LAVA: Hazard = Hazard {
+    damage: 200.0,
+    kind: DamageKind::Fire,
+    buff: Some((BuffKind::Burning, BuffData {
+        strength: 50.0,
+        duration: Some(Duration::from_secs(5)),
+    })),
+};
+
+/// Embers only burn for as long as their sprite is there. Those left behind by
+/// spreading fire are put out by the server after a while, taking their hazard
+/// with them, while those that worldgen places in the hearths of houses keep
+/// burning.
+pub const EMBER: Hazard = Hazard {
+    damage: 30.0,
+    kind: DamageKind::Fire,
+    buff: Some((BuffKind::Burning, BuffData {
+        strength: 20.0,
+        duration: Some(Duration::from_secs(3)),
+    })),
+};
+
+pub const CACTUS: Hazard = Hazard {
+    damage: 20.0,
+    kind: DamageKind::Piercing,
+    buff: None,
+};
+
+pub const SPIKES: Hazard = Hazard {
+    damage: 150.0,
+    kind: DamageKind::Piercing,
+    buff: Some((BuffKind::Bleeding, BuffData {
+        strength: 30.0,
+        duration: Some(Duration::from_secs(8)),
+    })),
+};
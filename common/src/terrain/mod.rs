@@ -1,6 +1,7 @@
 pub mod biome;
 pub mod block;
 pub mod chonk;
+pub mod hazard;
 pub mod map;
 pub mod sprite;
 pub mod structure;
@@ -9,6 +10,7 @@ pub mod structure;
 pub use self::{
     biome::BiomeKind,
     block::{Block, BlockKind},
+    hazard::Hazard,
     map::MapSizeLg,
     sprite::SpriteKind,
     structure::Structure,
@@ -50,21 +52,39 @@ impl RectVolSize for TerrainChunkSize {
 pub struct TerrainChunkMeta {
     name: Option<String>,
     biome: BiomeKind,
+    temp: f32,
+    flow: Vec2<f32>,
 }
 
 impl TerrainChunkMeta {
-    pub fn new(name: Option<String>, biome: BiomeKind) -> Self { Self { name, biome } }
+    pub fn new(name: Option<String>, biome: BiomeKind, temp: f32, flow: Vec2<f32>) -> Self {
+        Self {
+            name,
+            biome,
+            temp,
+            flow,
+        }
+    }
 
     pub fn void() -> Self {
         Self {
             name: None,
             biome: BiomeKind::Void,
+            temp: 0.0,
+            flow: Vec2::zero(),
         }
     }
 
     pub fn name(&self) -> &str { self.name.as_deref().unwrap_or("Wilderness") }
 
     pub fn biome(&self) -> BiomeKind { self.biome }
+
+    /// Worldgen temperature of the chunk, roughly in the range -1 to 1
+    pub fn temp(&self) -> f32 { self.temp }
+
+    /// Velocity of the river flowing through the chunk, in blocks per second,
+    /// which is zero where there is no river
+    pub fn flow(&self) -> Vec2<f32> { self.flow }
 }
 
 // Terrain type aliases
@@ -72,6 +92,14 @@ impl TerrainChunkMeta {
 pub type TerrainChunk = chonk::Chonk<Block, TerrainChunkSize, TerrainChunkMeta>;
 pub type TerrainGrid = VolGrid2d<TerrainChunk>;
 
+impl TerrainGrid {
+    /// Velocity of the water flowing at a position, in blocks per second
+    pub fn flow_at(&self, wpos: Vec3<i32>) -> Vec2<f32> {
+        self.get_key(self.pos_key(wpos))
+            .map_or(Vec2::zero(), |chunk| chunk.meta().flow())
+    }
+}
+
 // Terrain helper functions used across multiple crates.
 
 /// Computes the position Vec2 of a SimChunk from an index, where the index was
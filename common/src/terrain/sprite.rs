@@ -1,3 +1,4 @@
+use super::{hazard, Hazard};
 use crate::make_case_elim;
 use enum_iterator::IntoEnumIterator;
 use lazy_static::lazy_static;
@@ -5,6 +6,9 @@ use num_derive::FromPrimitive;
 use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, convert::TryFrom, fmt};
 
+/// Damage that takes away one point of a sprite's health
+pub const SPRITE_HEALTH_UNIT: u32 = 10;
+
 make_case_elim!(
     sprite_kind,
     #[derive(
@@ -102,6 +106,11 @@ make_case_elim!(
         Reed = 0x4C,
         Beehive = 0x4D,
         LargeCactus = 0x4E,
+        KeyDoor = 0x4F,
+        Key = 0x50,
+        BountyBoard = 0x51,
+        Spikes = 0x52,
+        GlowMushroom = 0x53,
     }
 );
 
@@ -154,7 +163,8 @@ impl SpriteKind {
             | SpriteKind::Window2
             | SpriteKind::Window3
             | SpriteKind::Window4
-            | SpriteKind::DropGate => 1.0,
+            | SpriteKind::DropGate
+            | SpriteKind::KeyDoor => 1.0,
             // TODO: Figure out if this should be solid or not.
             SpriteKind::Shelf => 1.0,
             _ => return None,
@@ -175,6 +185,7 @@ impl SpriteKind {
             SpriteKind::ShortGrass => false,
             SpriteKind::Apple => true,
             SpriteKind::Mushroom => true,
+            SpriteKind::GlowMushroom => true,
             SpriteKind::Velorite => true,
             SpriteKind::VeloriteFrag => true,
             SpriteKind::Chest => true,
@@ -184,10 +195,51 @@ impl SpriteKind {
             SpriteKind::ShinyGem => true,
             SpriteKind::Crate => true,
             SpriteKind::Beehive => true,
+            SpriteKind::Key => true,
             _ => false,
         }
     }
 
+    /// The item needed to unlock this sprite, if it is locked
+    pub fn key_item(&self) -> Option<&'static str> {
+        match self {
+            SpriteKind::KeyDoor => Some("common.items.utility.key"),
+            _ => None,
+        }
+    }
+
+    pub fn is_locked(&self) -> bool { self.key_item().is_some() }
+
+    /// How much health (in units of [`SPRITE_HEALTH_UNIT`]) this sprite has
+    /// before attacks break it. Sprites without health can't be broken.
+    pub fn max_health(&self) -> Option<u8> {
+        match self {
+            SpriteKind::Pot => Some(3),
+            SpriteKind::HangingBasket | SpriteKind::Planter => Some(6),
+            SpriteKind::Crate => Some(15),
+            _ => None,
+        }
+    }
+
+    /// Whether players can read the bounties posted by the nearby town on this
+    /// sprite
+    pub fn is_bounty_board(&self) -> bool { matches!(self, SpriteKind::BountyBoard) }
+
+    /// Harm done to entities touching this sprite
+    pub fn hazard(&self) -> Option<Hazard> {
+        match self {
+            SpriteKind::Ember => Some(hazard::EMBER),
+            SpriteKind::BarrelCactus
+            | SpriteKind::RoundCactus
+            | SpriteKind::ShortCactus
+            | SpriteKind::MedFlatCactus
+            | SpriteKind::ShortFlatCactus
+            | SpriteKind::LargeCactus => Some(hazard::CACTUS),
+            SpriteKind::Spikes => Some(hazard::SPIKES),
+            _ => None,
+        }
+    }
+
     pub fn has_ori(&self) -> bool {
         matches!(
             self,
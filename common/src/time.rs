@@ -1,3 +1,6 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
 #[derive(Copy, Clone, PartialEq, Eq)]
 pub enum DayPeriod {
     Night,
@@ -28,3 +31,140 @@ impl DayPeriod {
 
     pub fn is_light(&self) -> bool { !self.is_dark() }
 }
+
+/// In-game days from one new moon to the next
+pub const LUNAR_CYCLE_DAYS: f64 = 8.0;
+
+/// Phase of the moon at the given time of day, from 0 at new moon through 0.5
+/// at full moon and back towards 1
+pub fn moon_phase(time_of_day: f64) -> f32 {
+    (time_of_day / (60.0 * 60.0 * 24.0 * LUNAR_CYCLE_DAYS)).rem_euclid(1.0) as f32
+}
+
+/// Days in a year of the calendar
+pub const DAYS_PER_YEAR: f32 = 360.0;
+/// How far into the year midsummer is, from 0 to 1
+const MIDSUMMER: f32 = 0.375;
+
+/// Seasons of the year, each a quarter of it long. Years start with spring.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Season {
+    Spring,
+    Summer,
+    Autumn,
+    Winter,
+}
+
+impl Season {
+    /// The season on the given day, counting from the start of the first year
+    pub fn from_day(day: f32) -> Self {
+        match (year_progress(day) * 4.0) as u32 {
+            0 => Season::Spring,
+            1 => Season::Summer,
+            2 => Season::Autumn,
+            _ => Season::Winter,
+        }
+    }
+
+    /// How warm the given day is compared to the rest of the year, from -1 in
+    /// midwinter to 1 in midsummer
+    pub fn warmth(day: f32) -> f32 {
+        ((year_progress(day) - MIDSUMMER) * 2.0 * std::f32::consts::PI).cos()
+    }
+}
+
+/// How far into its year the given day is, from 0 to 1
+fn year_progress(day: f32) -> f32 { day.rem_euclid(DAYS_PER_YEAR) / DAYS_PER_YEAR }
+
+/// Seconds in an in-game day
+pub const DAY_SECS: f64 = 60.0 * 60.0 * 24.0;
+
+/// A day of the in-game calendar, which starts at the first day of spring of
+/// the first year
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Date {
+    /// The year, counting from 1
+    pub year: u32,
+    /// The day of the year, counting from 0
+    pub day: u32,
+}
+
+impl Date {
+    /// The date at the given time of day
+    pub fn from_time_of_day(time_of_day: f64) -> Self {
+        let days = (time_of_day / DAY_SECS).floor().max(0.0) as u64;
+        let days_per_year = DAYS_PER_YEAR as u64;
+        Self {
+            year: (days / days_per_year) as u32 + 1,
+            day: (days % days_per_year) as u32,
+        }
+    }
+
+    /// The time of day at midnight at the start of this date
+    pub fn start(&self) -> f64 {
+        (self.year.saturating_sub(1) as f64 * DAYS_PER_YEAR as f64 + self.day as f64) * DAY_SECS
+    }
+
+    pub fn season(&self) -> Season { Season::from_day(self.day as f32) }
+
+    /// The day of the season, counting from 0
+    pub fn day_of_season(&self) -> u32 { self.day % (DAYS_PER_YEAR as u32 / 4) }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Day {} of {:?}, Year {}",
+            self.day_of_season() + 1,
+            self.season(),
+            self.year
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn date_rolls_over_at_midnight_and_new_year() {
+        let year = DAYS_PER_YEAR as f64 * DAY_SECS;
+        assert_eq!(Date::from_time_of_day(0.0), Date { year: 1, day: 0 });
+        assert_eq!(Date::from_time_of_day(-1.0), Date { year: 1, day: 0 });
+        assert_eq!(Date::from_time_of_day(DAY_SECS - 1.0), Date {
+            year: 1,
+            day: 0
+        });
+        assert_eq!(Date::from_time_of_day(DAY_SECS), Date { year: 1, day: 1 });
+        assert_eq!(Date::from_time_of_day(year - 1.0), Date {
+            year: 1,
+            day: 359
+        });
+        assert_eq!(Date::from_time_of_day(year), Date { year: 2, day: 0 });
+    }
+
+    #[test]
+    fn date_starts_where_it_was_read_from() {
+        for date in [Date { year: 1, day: 0 }, Date { year: 1, day: 359 }, Date {
+            year: 501,
+            day: 42,
+        }]
+        .iter()
+        {
+            assert_eq!(Date::from_time_of_day(date.start()), *date);
+            assert_eq!(Date::from_time_of_day(date.start() + DAY_SECS - 1.0), *date);
+        }
+    }
+
+    #[test]
+    fn seasons_change_every_quarter_year() {
+        let date = |day| Date { year: 1, day };
+        assert_eq!(date(0).season(), Season::Spring);
+        assert_eq!(date(89).season(), Season::Spring);
+        assert_eq!(date(90).season(), Season::Summer);
+        assert_eq!(date(90).day_of_season(), 0);
+        assert_eq!(date(359).season(), Season::Winter);
+        assert_eq!(date(359).to_string(), "Day 90 of Winter, Year 1");
+    }
+}
@@ -0,0 +1,271 @@
+//! Bounties that towns put on outlaws.
+//!
+//! Outlaws are generated along with the history of the world and hide out in
+//! camps away from the towns that want them dead. Players can read the
+//! bounties at a town's bounty board, which marks the camp of the outlaw on
+//! their map, and are paid out in coins when they kill the outlaw. Outlaws are
+//! spawned with the id of the bounty on them, which is their place in the list
+//! of bounties.
+
+use crate::{cutscene, market, state_ext::StateExt, Server};
+use common::{
+    comp::{ChatType, HealthSource, Item, Player, Pos},
+    cutscene::QuestEvent,
+    generation::EntityInfo,
+    msg::ServerGeneral,
+    sync::{Uid, WorldSyncExt},
+};
+use hashbrown::HashMap;
+use specs::{Component, Entity as EcsEntity, WorldExt};
+use specs_idvs::IdvStorage;
+use tracing::error;
+use vek::*;
+
+/// How far away from a town its bounty board can be read from
+const BOARD_RANGE: f32 = 400.0;
+/// How close a player has to be to a bounty board to read it
+const MAX_READ_DIST: f32 = 8.0;
+
+pub struct Bounty {
+    /// Name of the wanted outlaw
+    pub outlaw: String,
+    pub crime: String,
+    /// World position of the town that posted the bounty
+    pub town: Vec2<i32>,
+    /// World position of the outlaw's camp
+    pub camp: Vec2<i32>,
+    /// Coins paid out for killing the outlaw
+    pub reward: u32,
+    pub claimed: bool,
+}
+
+/// Marks an NPC as the outlaw wanted by the bounty with the given id
+#[derive(Copy, Clone, Debug)]
+pub struct Wanted(pub u64);
+
+impl Component for Wanted {
+    type Storage = IdvStorage<Self>;
+}
+
+#[derive(Default)]
+pub struct Bounties {
+    bounties: Vec<Bounty>,
+    /// The bounty that each player is hunting
+    hunters: HashMap<Uid, usize>,
+}
+
+impl Bounties {
+    pub fn new(bounties: Vec<(String, String, Vec2<i32>, Vec2<i32>, u32)>) -> Self {
+        Self {
+            bounties: bounties
+                .into_iter()
+                .map(|(outlaw, crime, town, camp, reward)| Bounty {
+                    outlaw,
+                    crime,
+                    town,
+                    camp,
+                    reward,
+                    claimed: false,
+                })
+                .collect(),
+            hunters: HashMap::new(),
+        }
+    }
+
+    pub fn bounties(&self) -> &[Bounty] { &self.bounties }
+
+    /// Whether the given entity is an outlaw whose bounty was already claimed,
+    /// so that they stay dead
+    pub fn is_claimed(&self, entity: &EntityInfo) -> bool {
+        entity
+            .bounty
+            .and_then(|id| self.bounties.get(id as usize))
+            .map_or(false, |b| b.claimed)
+    }
+}
+
+/// Lists the bounties posted on the board at `pos` to the player reading it
+/// and marks the camp of the outlaw wanted by the nearest town on their map.
+pub fn handle_read_bounty_board(server: &mut Server, entity: EcsEntity, pos: Vec3<i32>) {
+    let ecs = server.state.ecs();
+    let (uid, player_pos) = match (
+        ecs.read_storage::<Uid>().get(entity).copied(),
+        ecs.read_storage::<Pos>().get(entity).copied(),
+    ) {
+        (Some(uid), Some(player_pos)) => (uid, player_pos),
+        _ => return,
+    };
+    let board_pos = pos.map(|e| e as f32) + 0.5;
+    if player_pos.0.distance_squared(board_pos) > MAX_READ_DIST.powi(2) {
+        return;
+    }
+
+    let (messages, marker) = {
+        let mut bounties = ecs.write_resource::<Bounties>();
+        let mut posted = bounties
+            .bounties
+            .iter()
+            .enumerate()
+            .filter(|(_, b)| {
+                !b.claimed
+                    && b.town.map(|e| e as f32).distance_squared(board_pos.xy())
+                        < BOARD_RANGE.powi(2)
+            })
+            .map(|(i, b)| (i, b.town.map(|e| e as f32).distance_squared(board_pos.xy())))
+            .collect::<Vec<_>>();
+        posted.sort_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+        if let Some((nearest, _)) = posted.first().copied() {
+            let mut messages = posted
+                .iter()
+                .map(|(i, _)| {
+                    let bounty = &bounties.bounties[*i];
+                    format!(
+                        "WANTED: {}, {}. Reward: {} coins",
+                        bounty.outlaw, bounty.crime, bounty.reward
+                    )
+                })
+                .collect::<Vec<_>>();
+            messages.push(format!(
+                "The camp of {} has been marked on your map.",
+                bounties.bounties[nearest].outlaw
+            ));
+            let camp = bounties.bounties[nearest].camp;
+            bounties.hunters.insert(uid, nearest);
+            (messages, Some(camp))
+        } else {
+            (
+                vec!["There are no bounties posted on this board.".to_string()],
+                None,
+            )
+        }
+    };
+
+    for message in messages {
+        server.notify_client(entity, ChatType::Meta.server_msg(message));
+    }
+    if marker.is_some() {
+        server.notify_client(entity, ServerGeneral::MapMarker(marker));
+        cutscene::trigger_quest_event(server, entity, QuestEvent::BountyAccepted, board_pos);
+    }
+}
+
+/// Pays out the bounty on `entity` to the player who killed it, if it is a
+/// wanted outlaw.
+pub fn claim_bounty(server: &mut Server, entity: EcsEntity, cause: HealthSource) {
+    let by = match cause {
+        HealthSource::Attack { by }
+        | HealthSource::Projectile {
+            owner: Some(by), ..
+        }
+        | HealthSource::Energy { owner: Some(by) }
+        | HealthSource::Buff { owner: Some(by) }
+        | HealthSource::Explosion { owner: Some(by) } => by,
+        _ => return,
+    };
+
+    let ecs = server.state.ecs();
+    let killer = match ecs.entity_from_uid(by.into()) {
+        Some(killer) => killer,
+        None => return,
+    };
+    let killer_name = match ecs.read_storage::<Player>().get(killer) {
+        Some(player) => player.alias.clone(),
+        None => return,
+    };
+    let index = match ecs.read_storage::<Wanted>().get(entity) {
+        Some(Wanted(id)) => *id as usize,
+        None => return,
+    };
+
+    let (outlaw, reward, town, hunters) = {
+        let mut bounties = ecs.write_resource::<Bounties>();
+        let bounty = match bounties.bounties.get_mut(index) {
+            Some(bounty) if !bounty.claimed => bounty,
+            _ => return,
+        };
+        bounty.claimed = true;
+        let (outlaw, reward, town) = (bounty.outlaw.clone(), bounty.reward, bounty.town);
+
+        let hunters = bounties
+            .hunters
+            .iter()
+            .filter(|(_, hunted)| **hunted == index)
+            .map(|(uid, _)| *uid)
+            .collect::<Vec<_>>();
+        hunters.iter().for_each(|uid| {
+            bounties.hunters.remove(uid);
+        });
+        (outlaw, reward, town, hunters)
+    };
+
+    // The reward comes out of the treasury of the town that posted the bounty,
+    // which is grateful, and pays what it can
+    let (reward, site) = {
+        let mut markets = server.state.ecs().write_resource::<market::Markets>();
+        (
+            markets.pay_out(town.map(|e| e as f32), reward),
+            markets.town_near(town.map(|e| e as f32)),
+        )
+    };
+
+    // Pay out the reward, dropping it at the killer's feet if it doesn't fit in
+    // their inventory
+    if reward > 0 {
+        let mut coins = Item::new_from_asset_expect("common.items.utility.coins");
+        if let Err(e) = coins.set_amount(reward) {
+            error!(?e, "Failed to set the amount of coins for a bounty");
+        }
+        market::give_item(server, killer, coins);
+    }
+    if let (Some(site), Ok(entry)) = (
+        site,
+        server
+            .state
+            .ecs()
+            .write_storage::<market::Reputation>()
+            .entry(killer),
+    ) {
+        entry
+            .or_insert_with(market::Reputation::default)
+            .add(site, market::BOUNTY_REPUTATION);
+    }
+
+    server.state.send_chat(ChatType::Meta.chat_msg(format!(
+        "{} has claimed the bounty of {} coins on {}!",
+        killer_name, reward, outlaw
+    )));
+
+    let ecs = server.state.ecs();
+    for hunter in hunters {
+        if let Some(hunter) = ecs.entity_from_uid(hunter.into()) {
+            server.notify_client(hunter, ServerGeneral::MapMarker(None));
+        }
+    }
+
+    // The sequence plays around the killer, who may have struck from afar
+    if let Some(killer_pos) = server.state.read_component_copied::<Pos>(killer) {
+        cutscene::trigger_quest_event(server, killer, QuestEvent::BountyClaimed, killer_pos.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_the_outlaw_of_a_claimed_bounty_stays_dead() {
+        let camp = |x| Vec2::new(x, 0);
+        let mut bounties = Bounties::new(vec![
+            ("Bob".to_string(), String::new(), camp(0), camp(100), 10),
+            ("Bob".to_string(), String::new(), camp(0), camp(200), 20),
+        ]);
+        bounties.bounties[0].claimed = true;
+
+        let bob = || EntityInfo::at(Vec3::new(100.0, 0.0, 0.0)).with_name("Bob");
+        assert!(bounties.is_claimed(&bob().with_bounty(0)));
+        // Another outlaw of the same name, and anyone else who happens to share it
+        assert!(!bounties.is_claimed(&bob().with_bounty(1)));
+        assert!(!bounties.is_claimed(&bob()));
+    }
+}
@@ -0,0 +1,222 @@
+//! Carts that tamed animals can pull, for hauling goods along roads.
+//!
+//! A cart is placed from an item and belongs to the player who placed it. Once
+//! hitched to one of their pets it trails along behind the animal, and anyone
+//! can climb onto it for the ride. Only the owner can move cargo in and out of
+//! it.
+
+use crate::{state_ext::StateExt, Server};
+use common::{
+    comp::{self, Alignment, ChatType, Hitched, Inventory, InventoryUpdateEvent, Pos, Stats},
+    state::State,
+    sync::Uid,
+};
+use specs::{Builder, Component, Entity as EcsEntity, Join, WorldExt};
+use specs_idvs::IdvStorage;
+
+/// How many stacks of items a cart can carry
+pub const CART_SLOTS: usize = 72;
+/// Mass of a cart, so that it isn't pushed around by whoever walks into it
+const CART_MASS: f32 = 200.0;
+/// How close a player has to be to their cart to hitch it or move its cargo
+const MAX_CART_DIST: f32 = 6.0;
+/// How close an animal has to be to a cart to be hitched to it
+const MAX_HITCH_DIST: f32 = 10.0;
+
+/// A cart placed by a player
+#[derive(Clone, Debug, PartialEq)]
+pub struct Cart {
+    pub owner: Uid,
+}
+
+impl Component for Cart {
+    type Storage = IdvStorage<Self>;
+}
+
+/// Whether animals with this body are strong enough to pull a cart
+pub fn can_pull(body: &comp::Body) -> bool {
+    matches!(body, comp::Body::QuadrupedMedium(_) | comp::Body::QuadrupedLow(_))
+}
+
+/// Places an empty cart belonging to `owner`
+pub fn create_cart(state: &mut State, pos: Pos, ori: comp::Ori, owner: Uid) {
+    state
+        .create_object(pos, comp::object::Body::Cart)
+        .with(ori)
+        .with(comp::Mass(CART_MASS))
+        .with(Inventory::with_slots(CART_SLOTS))
+        .with(comp::MountState::Unmounted)
+        .with(Cart { owner })
+        .build();
+}
+
+/// Hitches the closest pet of the player that can pull a cart to the cart
+/// next to them
+pub fn hitch(server: &mut Server, entity: EcsEntity) {
+    let result = nearest_cart(server, entity).and_then(|(cart, owner)| {
+        let ecs = server.state.ecs();
+        let cart_pos = ecs
+            .read_storage::<Pos>()
+            .get(cart)
+            .copied()
+            .ok_or_else(|| "Your cart has no position!".to_string())?;
+        let pulling = ecs
+            .read_storage::<Hitched>()
+            .join()
+            .map(|Hitched(puller)| *puller)
+            .collect::<Vec<_>>();
+        let (animal, name) = (
+            &ecs.read_storage::<Uid>(),
+            &ecs.read_storage::<Alignment>(),
+            &ecs.read_storage::<comp::Body>(),
+            &ecs.read_storage::<Pos>(),
+            &ecs.read_storage::<Stats>(),
+        )
+            .join()
+            .filter(|(uid, alignment, body, _, stats)| {
+                **alignment == Alignment::Owned(owner)
+                    && can_pull(body)
+                    && !stats.is_dead
+                    && !pulling.contains(uid)
+            })
+            .map(|(uid, _, _, pos, stats)| {
+                (*uid, stats.name.clone(), pos.0.distance_squared(cart_pos.0))
+            })
+            .filter(|(_, _, dist)| *dist < MAX_HITCH_DIST.powi(2))
+            .min_by(|(_, _, a), (_, _, b)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
+            .map(|(uid, name, _)| (uid, name))
+            .ok_or_else(|| {
+                "None of your pets that can pull a cart are close enough to it.".to_string()
+            })?;
+
+        let _ = ecs.write_storage().insert(cart, Hitched(animal));
+        Ok(format!("You hitch {} to your cart.", name))
+    });
+    notify(server, entity, result);
+}
+
+/// Frees the cart next to the player from the animal pulling it
+pub fn unhitch(server: &mut Server, entity: EcsEntity) {
+    let result = nearest_cart(server, entity).and_then(|(cart, _)| {
+        server
+            .state
+            .delete_component::<Hitched>(cart)
+            .map(|_| "You unhitch your cart.".to_string())
+            .ok_or_else(|| "Your cart isn't hitched to anything.".to_string())
+    });
+    notify(server, entity, result);
+}
+
+/// Lists the cargo of the cart next to the player
+pub fn list_cargo(server: &mut Server, entity: EcsEntity) {
+    let messages = nearest_cart(server, entity).map(|(cart, _)| {
+        let inventories = server.state.ecs().read_storage::<Inventory>();
+        let mut messages = inventories
+            .get(cart)
+            .into_iter()
+            .flat_map(|inventory| inventory.slots().iter().enumerate())
+            .filter_map(|(i, slot)| {
+                slot.as_ref()
+                    .map(|item| format!("{}: {} x{}", i + 1, item.name(), item.amount()))
+            })
+            .collect::<Vec<_>>();
+        if messages.is_empty() {
+            messages.push("Your cart is empty.".to_string());
+        }
+        messages
+    });
+
+    match messages {
+        Ok(messages) => {
+            for message in messages {
+                server.notify_client(entity, ChatType::CommandInfo.server_msg(message));
+            }
+        },
+        Err(error) => server.notify_client(entity, ChatType::CommandError.server_msg(error)),
+    }
+}
+
+/// Moves the items in `slot` of the player's inventory into the cart next to
+/// them
+pub fn load(server: &mut Server, entity: EcsEntity, slot: usize) {
+    let result = nearest_cart(server, entity).and_then(|(cart, _)| {
+        move_stack(server, entity, slot, cart)?;
+        server.state.write_component(
+            entity,
+            comp::InventoryUpdate::new(InventoryUpdateEvent::Gave),
+        );
+        Ok("You load the cart.".to_string())
+    });
+    notify(server, entity, result);
+}
+
+/// Moves the items in `slot` of the cart next to the player into their
+/// inventory
+pub fn unload(server: &mut Server, entity: EcsEntity, slot: usize) {
+    let result = nearest_cart(server, entity).and_then(|(cart, _)| {
+        move_stack(server, cart, slot, entity)?;
+        server.state.write_component(
+            entity,
+            comp::InventoryUpdate::new(InventoryUpdateEvent::Given),
+        );
+        Ok("You unload the cart.".to_string())
+    });
+    notify(server, entity, result);
+}
+
+/// Moves a whole stack of items from one inventory to another, leaving it
+/// where it was if there is no room for it
+fn move_stack(server: &Server, from: EcsEntity, slot: usize, to: EcsEntity) -> Result<(), String> {
+    let mut inventories = server.state.ecs().write_storage::<Inventory>();
+    let item = inventories
+        .get_mut(from)
+        .and_then(|inventory| inventory.remove(slot))
+        .ok_or_else(|| format!("There is nothing in slot {}.", slot + 1))?;
+    let leftover = match inventories.get_mut(to) {
+        Some(inventory) => inventory.push(item),
+        None => Some(item),
+    };
+    match leftover {
+        None => Ok(()),
+        Some(item) => {
+            if let Some(inventory) = inventories.get_mut(from) {
+                let _ = inventory.insert(slot, item);
+            }
+            Err("There is no room for that.".to_string())
+        },
+    }
+}
+
+/// The closest cart owned by the player within reach, along with the uid of
+/// the player
+fn nearest_cart(server: &Server, entity: EcsEntity) -> Result<(EcsEntity, Uid), String> {
+    let ecs = server.state.ecs();
+    let (uid, pos) = match (
+        ecs.read_storage::<Uid>().get(entity).copied(),
+        ecs.read_storage::<Pos>().get(entity).copied(),
+    ) {
+        (Some(uid), Some(pos)) => (uid, pos),
+        _ => return Err("You have no position!".to_string()),
+    };
+    let cart = (
+        &ecs.entities(),
+        &ecs.read_storage::<Cart>(),
+        &ecs.read_storage::<Pos>(),
+    )
+        .join()
+        .filter(|(_, cart, _)| cart.owner == uid)
+        .map(|(cart, _, cart_pos)| (cart, cart_pos.0.distance_squared(pos.0)))
+        .filter(|(_, dist)| *dist < MAX_CART_DIST.powi(2))
+        .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
+        .map(|(cart, _)| cart)
+        .ok_or_else(|| "You need to be next to one of your carts to do that.".to_string())?;
+    Ok((cart, uid))
+}
+
+fn notify(server: &Server, entity: EcsEntity, result: Result<String, String>) {
+    let msg = match result {
+        Ok(message) => ChatType::CommandInfo.server_msg(message),
+        Err(error) => ChatType::CommandError.server_msg(error),
+    };
+    server.notify_client(entity, msg);
+}
@@ -1,6 +1,6 @@
-use crate::persistence::character_loader::CharacterLoader;
+use crate::{market::Reputation, persistence::character_loader::CharacterLoader, stable::Stable};
 use common::{
-    comp::{Body, Inventory, Stats},
+    comp::{AbilitySelection, Body, Buffs, Inventory, Stats},
     loadout_builder::LoadoutBuilder,
 };
 use specs::{Entity, ReadExpect};
@@ -28,6 +28,15 @@ pub fn create_character(
         entity,
         player_uuid,
         character_alias,
-        (body, stats, inventory, loadout),
+        (
+            body,
+            stats,
+            inventory,
+            loadout,
+            AbilitySelection::default(),
+            Buffs::default(),
+            Stable::default(),
+            Reputation::default(),
+        ),
     );
 }
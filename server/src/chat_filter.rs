@@ -0,0 +1,407 @@
+//! Filtering of chat messages from players before they are sent on to other
+//! players. A message runs through a list of rules, each of which comes with
+//! an action to take on messages that break it, and more rules can be added
+//! with [`ChatFilter::push`]. Messages from admins are never filtered.
+
+use crate::{client::Client, Server};
+use common::{
+    comp::{self, ChatType, UnresolvedChatMsg},
+    state::Time,
+    sync::{Uid, WorldSyncExt},
+};
+use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
+use specs::{Join, WorldExt};
+use std::ops::Range;
+
+/// Endings of words that are taken to be web addresses
+const LINK_ENDINGS: [&str; 8] = [".com", ".net", ".org", ".io", ".gg", ".ru", ".xyz", ".co"];
+
+/// What happens to a message that breaks a rule of the chat filter
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum FilterAction {
+    /// The offending parts of the message are starred out, and the message is
+    /// held back altogether if all of it is offending
+    Censor,
+    /// The message is held back and the sender can't talk for a while
+    Mute,
+    /// The message is sent on as it is, and online admins are told about it
+    Report,
+}
+
+/// How chat messages from players are filtered
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChatFilterSettings {
+    /// What happens to messages with words from the banned words files in
+    /// them (None lets them through)
+    pub banned_words: Option<FilterAction>,
+    /// What happens to messages with links in them (None lets them through)
+    pub links: Option<FilterAction>,
+    /// What happens to messages that repeat the sender's last message too
+    /// often (None lets them through)
+    pub spam: Option<FilterAction>,
+    /// How many times in a row a player can send the same message before it
+    /// counts as spam
+    pub spam_repeats: u32,
+    /// Seconds after which sending the same message again no longer counts as
+    /// a repeat
+    pub spam_window: f64,
+    /// Seconds that muted players can't talk for
+    pub mute_duration: f64,
+}
+
+impl Default for ChatFilterSettings {
+    fn default() -> Self {
+        Self {
+            banned_words: Some(FilterAction::Censor),
+            links: None,
+            spam: Some(FilterAction::Mute),
+            spam_repeats: 3,
+            spam_window: 10.0,
+            mute_duration: 60.0,
+        }
+    }
+}
+
+/// A rule that chat messages from players are checked against
+pub trait ChatRule: Send + Sync {
+    /// What breaking the rule is called when moderators are told about it
+    fn name(&self) -> &'static str;
+
+    /// Checks a message from `sender` sent at `time`, returning the byte
+    /// ranges of the parts of the message that break the rule
+    fn check(&mut self, sender: Uid, message: &str, time: f64) -> Vec<Range<usize>>;
+}
+
+/// Each whitespace separated word of `message`, along with its byte range
+fn words(message: &str) -> impl Iterator<Item = (Range<usize>, &str)> + '_ {
+    message.split_whitespace().map(move |word| {
+        let start = word.as_ptr() as usize - message.as_ptr() as usize;
+        (start..start + word.len(), word)
+    })
+}
+
+/// Words containing any of a list of banned words, regardless of case
+pub struct BannedWords(Vec<String>);
+
+impl BannedWords {
+    pub fn new(banned_words: &[String]) -> Self {
+        Self(
+            banned_words
+                .iter()
+                .map(|word| word.to_lowercase())
+                .collect(),
+        )
+    }
+}
+
+impl ChatRule for BannedWords {
+    fn name(&self) -> &'static str { "banned word" }
+
+    fn check(&mut self, _sender: Uid, message: &str, _time: f64) -> Vec<Range<usize>> {
+        words(message)
+            .filter(|(_, word)| {
+                let word = word.to_lowercase();
+                self.0.iter().any(|banned| word.contains(banned.as_str()))
+            })
+            .map(|(range, _)| range)
+            .collect()
+    }
+}
+
+/// Words that look like web addresses
+pub struct Links;
+
+impl ChatRule for Links {
+    fn name(&self) -> &'static str { "link" }
+
+    fn check(&mut self, _sender: Uid, message: &str, _time: f64) -> Vec<Range<usize>> {
+        words(message)
+            .filter(|(_, word)| {
+                let word = word
+                    .trim_end_matches(|c: char| !c.is_alphanumeric() && c != '/')
+                    .to_lowercase();
+                word.contains("://")
+                    || word.starts_with("www.")
+                    || LINK_ENDINGS.iter().any(|ending| {
+                        word.len() > ending.len() && word.trim_end_matches('/').ends_with(ending)
+                    })
+            })
+            .map(|(range, _)| range)
+            .collect()
+    }
+}
+
+/// The same message sent by a player too many times in a row
+pub struct Spam {
+    repeats: u32,
+    window: f64,
+    /// The last message of each player, how many times in a row they sent it,
+    /// and when they last did
+    last: HashMap<Uid, (String, u32, f64)>,
+}
+
+impl Spam {
+    pub fn new(repeats: u32, window: f64) -> Self {
+        Self {
+            repeats,
+            window,
+            last: HashMap::new(),
+        }
+    }
+}
+
+impl ChatRule for Spam {
+    fn name(&self) -> &'static str { "spam" }
+
+    fn check(&mut self, sender: Uid, message: &str, time: f64) -> Vec<Range<usize>> {
+        let normalized = message.trim().to_lowercase();
+        let window = self.window;
+        let count = match self.last.get_mut(&sender) {
+            Some((last, count, last_time)) if *last == normalized && time - *last_time < window => {
+                *count += 1;
+                *last_time = time;
+                *count
+            },
+            _ => {
+                self.last.insert(sender, (normalized, 1, time));
+                1
+            },
+        };
+
+        if count > self.repeats {
+            vec![0..message.len()]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+/// What became of a message that ran through the chat filter
+#[derive(Debug, PartialEq)]
+pub enum Filtered {
+    /// The message is sent on, possibly censored, and moderators are told
+    /// about the rules it broke that are reported
+    Send {
+        message: String,
+        reports: Vec<&'static str>,
+    },
+    /// The message is held back
+    Held,
+    /// The message is held back, and the sender was muted for breaking a rule
+    Muted(&'static str),
+    /// The message is held back because the sender is still muted, for this
+    /// many more seconds
+    StillMuted(f64),
+}
+
+/// The rules that chat messages from players are checked against, and the
+/// players muted for breaking them
+pub struct ChatFilter {
+    rules: Vec<(Box<dyn ChatRule>, FilterAction)>,
+    mute_duration: f64,
+    /// When each muted player can talk again
+    muted: HashMap<Uid, f64>,
+}
+
+impl ChatFilter {
+    pub fn new(settings: &ChatFilterSettings, banned_words: &[String]) -> Self {
+        let mut filter = Self {
+            rules: Vec::new(),
+            mute_duration: settings.mute_duration,
+            muted: HashMap::new(),
+        };
+        if let Some(action) = settings.banned_words.filter(|_| !banned_words.is_empty()) {
+            filter.push(BannedWords::new(banned_words), action);
+        }
+        if let Some(action) = settings.links {
+            filter.push(Links, action);
+        }
+        if let Some(action) = settings.spam {
+            filter.push(
+                Spam::new(settings.spam_repeats, settings.spam_window),
+                action,
+            );
+        }
+        filter
+    }
+
+    /// Adds a rule that messages are checked against after the existing ones
+    pub fn push(&mut self, rule: impl ChatRule + 'static, action: FilterAction) {
+        self.rules.push((Box::new(rule), action));
+    }
+
+    /// Checks a message from `sender` sent at `time` against the rules
+    pub fn filter(&mut self, sender: Uid, message: &str, time: f64) -> Filtered {
+        if let Some(until) = self.muted.get(&sender).copied() {
+            if time < until {
+                return Filtered::StillMuted(until - time);
+            }
+            self.muted.remove(&sender);
+        }
+
+        let mut censored = Vec::new();
+        let mut reports = Vec::new();
+        for (rule, action) in self.rules.iter_mut() {
+            let offending = rule.check(sender, message, time);
+            if offending.is_empty() {
+                continue;
+            }
+            match action {
+                FilterAction::Censor => censored.extend(offending),
+                FilterAction::Mute => {
+                    self.muted.insert(sender, time + self.mute_duration);
+                    return Filtered::Muted(rule.name());
+                },
+                FilterAction::Report => reports.push(rule.name()),
+            }
+        }
+
+        if censored
+            .iter()
+            .any(|range| range.start == 0 && range.end >= message.len())
+        {
+            return Filtered::Held;
+        }
+        let message = message
+            .char_indices()
+            .map(|(i, c)| {
+                if censored.iter().any(|range| range.contains(&i)) {
+                    '*'
+                } else {
+                    c
+                }
+            })
+            .collect();
+        Filtered::Send { message, reports }
+    }
+}
+
+/// Runs a chat message through the chat filter, returning it as it should be
+/// sent on, if at all. Senders are told when they are muted, and online admins
+/// about reported messages. Messages from admins and NPCs are let through.
+pub fn filter_chat(server: &Server, mut msg: UnresolvedChatMsg) -> Option<UnresolvedChatMsg> {
+    let ecs = server.state.ecs();
+    let (sender, entity) = match msg
+        .uid()
+        .and_then(|uid| Some((uid, ecs.entity_from_uid(uid.0)?)))
+    {
+        Some(sender) => sender,
+        None => return Some(msg),
+    };
+    let alias = match ecs.read_storage::<comp::Player>().get(entity) {
+        Some(player) if !server.entity_is_admin(entity) => player.alias.clone(),
+        _ => return Some(msg),
+    };
+
+    let time = ecs.read_resource::<Time>().0;
+    let filtered = ecs
+        .write_resource::<ChatFilter>()
+        .filter(sender, &msg.message, time);
+    match filtered {
+        Filtered::Send { message, reports } => {
+            if !reports.is_empty() {
+                let report = ChatType::CommandInfo.server_msg(format!(
+                    "[Chat filter] {} sent a message with a {}: {}",
+                    alias,
+                    reports.join(" and a "),
+                    msg.message
+                ));
+                let mut clients = ecs.write_storage::<Client>();
+                for (client, _) in (&mut clients, &ecs.read_storage::<comp::Admin>()).join() {
+                    client.send_msg(report.clone());
+                }
+            }
+            msg.message = message;
+            Some(msg)
+        },
+        Filtered::Held => None,
+        Filtered::Muted(rule) => {
+            let duration = ecs.read_resource::<ChatFilter>().mute_duration;
+            server.notify_client(
+                entity,
+                ChatType::CommandError.server_msg(format!(
+                    "You have been muted for {:.0} seconds for sending a message with a {}",
+                    duration, rule
+                )),
+            );
+            None
+        },
+        Filtered::StillMuted(remaining) => {
+            server.notify_client(
+                entity,
+                ChatType::CommandError.server_msg(format!(
+                    "You are muted for {:.0} more seconds",
+                    remaining.ceil()
+                )),
+            );
+            None
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter(settings: ChatFilterSettings) -> ChatFilter {
+        ChatFilter::new(&settings, &["bad".to_owned()])
+    }
+
+    #[test]
+    fn censors_banned_words() {
+        let mut filter = filter(ChatFilterSettings::default());
+        assert_eq!(
+            filter.filter(Uid(1), "a BADly good day", 0.0),
+            Filtered::Send {
+                message: "a ***** good day".to_owned(),
+                reports: Vec::new(),
+            }
+        );
+    }
+
+    #[test]
+    fn reports_links() {
+        let mut filter = filter(ChatFilterSettings {
+            links: Some(FilterAction::Report),
+            ..Default::default()
+        });
+        assert_eq!(
+            filter.filter(Uid(1), "see example.com!", 0.0),
+            Filtered::Send {
+                message: "see example.com!".to_owned(),
+                reports: vec!["link"],
+            }
+        );
+        assert_eq!(
+            filter.filter(Uid(1), "see you at 5.30", 0.0),
+            Filtered::Send {
+                message: "see you at 5.30".to_owned(),
+                reports: Vec::new(),
+            }
+        );
+    }
+
+    #[test]
+    fn mutes_spammers() {
+        let mut filter = filter(ChatFilterSettings::default());
+        for time in 0..3 {
+            assert!(matches!(
+                filter.filter(Uid(1), "hello", time as f64),
+                Filtered::Send { .. }
+            ));
+        }
+        assert_eq!(filter.filter(Uid(1), "hello", 3.0), Filtered::Muted("spam"));
+        assert_eq!(filter.filter(Uid(1), "hi", 4.0), Filtered::StillMuted(59.0));
+        // Other players can still talk
+        assert!(matches!(
+            filter.filter(Uid(2), "hello", 4.0),
+            Filtered::Send { .. }
+        ));
+        assert!(matches!(
+            filter.filter(Uid(1), "hi", 64.0),
+            Filtered::Send { .. }
+        ));
+    }
+}
@@ -74,11 +74,15 @@ impl Client {
                     | ServerGeneral::InviteComplete { .. }
                     | ServerGeneral::ExitInGameSuccess
                     | ServerGeneral::InventoryUpdate(_, _)
+                    | ServerGeneral::CombatLog(_)
                     | ServerGeneral::TerrainChunkUpdate { .. }
                     | ServerGeneral::TerrainBlockUpdates(_)
                     | ServerGeneral::SetViewDistance(_)
                     | ServerGeneral::Outcomes(_)
-                    | ServerGeneral::Knockback(_) => &mut self.in_game_stream,
+                    | ServerGeneral::Knockback(_)
+                    | ServerGeneral::MapMarker(_)
+                    | ServerGeneral::Market(_)
+                    | ServerGeneral::Cutscene(_) => &mut self.in_game_stream,
                     // Always possible
                     ServerGeneral::PlayerListUpdate(_)
                     | ServerGeneral::ChatMsg(_)
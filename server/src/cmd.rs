@@ -3,9 +3,12 @@
 //! `CHAT_COMMANDS` and provide a handler function.
 
 use crate::{
+    cart,
     client::Client,
+    instance::DungeonInstances,
+    persistence::block_log::{self, BlockLogger},
     settings::{BanRecord, EditableSetting},
-    Server, StateExt,
+    stable, Server, StateExt,
 };
 use chrono::{NaiveTime, Timelike};
 use common::{
@@ -17,8 +20,9 @@ use common::{
     state::TimeOfDay,
     sync::{Uid, WorldSyncExt},
     terrain::{Block, BlockKind, SpriteKind, TerrainChunkSize},
+    time::{Date, DAYS_PER_YEAR, DAY_SECS},
     util::Dir,
-    vol::RectVolSize,
+    vol::{ReadVol, RectVolSize},
     Explosion, LoadoutBuilder,
 };
 use rand::Rng;
@@ -72,9 +76,13 @@ fn get_handler(cmd: &ChatCommand) -> CommandHandler {
         ChatCommand::Ban => handle_ban,
         ChatCommand::Build => handle_build,
         ChatCommand::Campfire => handle_spawn_campfire,
+        ChatCommand::Cart => handle_cart,
+        ChatCommand::Date => handle_date,
         ChatCommand::Debug => handle_debug,
         ChatCommand::DebugColumn => handle_debug_column,
+        ChatCommand::Difficulty => handle_difficulty,
         ChatCommand::Dummy => handle_spawn_training_dummy,
+        ChatCommand::Duel => handle_duel,
         ChatCommand::Explosion => handle_explosion,
         ChatCommand::Faction => handle_faction,
         ChatCommand::GiveExp => handle_give_exp,
@@ -97,10 +105,12 @@ fn get_handler(cmd: &ChatCommand) -> CommandHandler {
         ChatCommand::Players => handle_players,
         ChatCommand::Region => handle_region,
         ChatCommand::RemoveLights => handle_remove_lights,
+        ChatCommand::Rollback => handle_rollback,
         ChatCommand::Say => handle_say,
         ChatCommand::SetLevel => handle_set_level,
         ChatCommand::SetMotd => handle_set_motd,
         ChatCommand::Spawn => handle_spawn,
+        ChatCommand::Stable => handle_stable,
         ChatCommand::Sudo => handle_sudo,
         ChatCommand::Tell => handle_tell,
         ChatCommand::Time => handle_time,
@@ -428,8 +438,14 @@ fn handle_time(
         },
     };
 
-    server.state.ecs_mut().write_resource::<TimeOfDay>().0 =
-        new_time.num_seconds_from_midnight() as f64;
+    // The time only moves forward, so that the date carries on
+    let time_of_day = server.state.ecs().read_resource::<TimeOfDay>().0;
+    let mut new_time_of_day =
+        Date::from_time_of_day(time_of_day).start() + new_time.num_seconds_from_midnight() as f64;
+    if new_time_of_day < time_of_day {
+        new_time_of_day += DAY_SECS;
+    }
+    server.state.ecs_mut().write_resource::<TimeOfDay>().0 = new_time_of_day;
 
     server.notify_client(
         client,
@@ -440,6 +456,60 @@ fn handle_time(
     );
 }
 
+fn handle_date(
+    server: &mut Server,
+    client: EcsEntity,
+    _target: EcsEntity,
+    args: String,
+    action: &ChatCommand,
+) {
+    let time_of_day = server.state.ecs().read_resource::<TimeOfDay>().0;
+    let date = Date::from_time_of_day(time_of_day);
+    let (day, year) = scan_fmt_some!(&args, &action.arg_fmt(), u32, u32);
+    let day = match day {
+        Some(day) if (1..=DAYS_PER_YEAR as u32).contains(&day) => day,
+        Some(_) => {
+            server.notify_client(
+                client,
+                ChatType::CommandError.server_msg(format!(
+                    "The day of the year must be between 1 and {}.",
+                    DAYS_PER_YEAR
+                )),
+            );
+            return;
+        },
+        None => {
+            server.notify_client(
+                client,
+                ChatType::CommandInfo.server_msg(format!("It is {}", date)),
+            );
+            return;
+        },
+    };
+
+    if !server.entity_is_admin(client) {
+        server.notify_client(
+            client,
+            ChatType::CommandError
+                .server_msg("You don't have permission to set the date.".to_string()),
+        );
+        return;
+    }
+
+    // The time of day stays the same
+    let new_date = Date {
+        year: year.unwrap_or(date.year).max(1),
+        day: day - 1,
+    };
+    server.state.ecs_mut().write_resource::<TimeOfDay>().0 =
+        new_date.start() + (time_of_day - date.start());
+
+    server.notify_client(
+        client,
+        ChatType::CommandInfo.server_msg(format!("Date changed to: {}", new_date)),
+    );
+}
+
 fn handle_health(
     server: &mut Server,
     client: EcsEntity,
@@ -1289,6 +1359,57 @@ fn handle_tell(
     }
 }
 
+fn handle_duel(
+    server: &mut Server,
+    client: EcsEntity,
+    target: EcsEntity,
+    args: String,
+    action: &ChatCommand,
+) {
+    if client != target {
+        // This happens when [ab]using /sudo
+        server.notify_client(
+            client,
+            ChatType::CommandError.server_msg("It's rude to impersonate people"),
+        );
+        return;
+    }
+    if let Some(alias) = scan_fmt_some!(&args, &action.arg_fmt(), String) {
+        let ecs = server.state.ecs();
+        if let Some(player) = (&ecs.entities(), &ecs.read_storage::<comp::Player>())
+            .join()
+            .find(|(_, player)| player.alias == alias)
+            .map(|(entity, _)| entity)
+        {
+            if player == client {
+                server.notify_client(
+                    client,
+                    ChatType::CommandError.server_msg("You can't duel yourself."),
+                );
+                return;
+            }
+            server
+                .state
+                .ecs()
+                .read_resource::<EventBus<ServerEvent>>()
+                .emit_now(ServerEvent::DuelRequest {
+                    challenger: client,
+                    target: player,
+                });
+        } else {
+            server.notify_client(
+                client,
+                ChatType::CommandError.server_msg(format!("Player '{}' not found!", alias)),
+            );
+        }
+    } else {
+        server.notify_client(
+            client,
+            ChatType::CommandError.server_msg(action.help_string()),
+        );
+    }
+}
+
 fn handle_faction(
     server: &mut Server,
     client: EcsEntity,
@@ -1353,6 +1474,152 @@ fn handle_group(
     }
 }
 
+fn handle_difficulty(
+    server: &mut Server,
+    client: EcsEntity,
+    target: EcsEntity,
+    args: String,
+    action: &ChatCommand,
+) {
+    if client != target {
+        // This happens when [ab]using /sudo
+        server.notify_client(
+            client,
+            ChatType::CommandError.server_msg("It's rude to impersonate people"),
+        );
+        return;
+    }
+    let group = if let Some(group) = server.state.read_component_copied::<comp::Group>(client) {
+        group
+    } else {
+        server.notify_client(
+            client,
+            ChatType::CommandError.server_msg("Please create a group first"),
+        );
+        return;
+    };
+
+    if let Ok(difficulty) = scan_fmt!(&args, &action.arg_fmt(), String) {
+        let is_leader = server
+            .state
+            .ecs()
+            .read_resource::<comp::group::GroupManager>()
+            .group_info(group)
+            .map_or(false, |info| info.leader == client);
+        if !is_leader {
+            server.notify_client(
+                client,
+                ChatType::CommandError.server_msg("Only the group leader can change this"),
+            );
+            return;
+        }
+        if difficulty == "confirm" {
+            let waiting = server
+                .state
+                .ecs()
+                .write_resource::<DungeonInstances>()
+                .confirm(group);
+            if !waiting {
+                server.notify_client(
+                    client,
+                    ChatType::CommandError
+                        .server_msg("Your group isn't waiting at the entrance of a dungeon"),
+                );
+            }
+            return;
+        }
+        let difficulty = if let Ok(difficulty) = difficulty.parse::<comp::DungeonDifficulty>() {
+            difficulty
+        } else {
+            server.notify_client(
+                client,
+                ChatType::CommandError.server_msg(action.help_string()),
+            );
+            return;
+        };
+        server
+            .state
+            .ecs()
+            .write_resource::<DungeonInstances>()
+            .set_difficulty(group, difficulty);
+        // Show the modifiers to the whole group before anyone enters
+        server
+            .state
+            .send_chat(ChatType::GroupMeta(group).chat_msg(format!(
+                "Dungeon difficulty set to {}",
+                difficulty.describe()
+            )));
+    } else {
+        let difficulty = server
+            .state
+            .ecs()
+            .read_resource::<DungeonInstances>()
+            .difficulty(group);
+        server.notify_client(
+            client,
+            ChatType::CommandInfo
+                .server_msg(format!("Dungeon difficulty: {}", difficulty.describe())),
+        );
+    }
+}
+
+fn handle_stable(
+    server: &mut Server,
+    client: EcsEntity,
+    target: EcsEntity,
+    args: String,
+    action: &ChatCommand,
+) {
+    if client != target {
+        // This happens when [ab]using /sudo
+        server.notify_client(
+            client,
+            ChatType::CommandError.server_msg("It's rude to impersonate people"),
+        );
+        return;
+    }
+    let (stable_action, pet) = scan_fmt_some!(&args, &action.arg_fmt(), String, usize);
+    match (stable_action.as_deref(), pet) {
+        (None, _) | (Some("list"), _) => stable::list_pets(server, client),
+        (Some("store"), _) => stable::store_pet(server, client),
+        (Some("take"), Some(pet)) if pet > 0 => stable::take_pet(server, client, pet - 1),
+        (Some("care"), _) => stable::care_for_pets(server, client),
+        _ => server.notify_client(
+            client,
+            ChatType::CommandError.server_msg(action.help_string()),
+        ),
+    }
+}
+
+fn handle_cart(
+    server: &mut Server,
+    client: EcsEntity,
+    target: EcsEntity,
+    args: String,
+    action: &ChatCommand,
+) {
+    if client != target {
+        // This happens when [ab]using /sudo
+        server.notify_client(
+            client,
+            ChatType::CommandError.server_msg("It's rude to impersonate people"),
+        );
+        return;
+    }
+    let (cart_action, slot) = scan_fmt_some!(&args, &action.arg_fmt(), String, usize);
+    match (cart_action.as_deref(), slot) {
+        (None, _) | (Some("list"), _) => cart::list_cargo(server, client),
+        (Some("hitch"), _) => cart::hitch(server, client),
+        (Some("unhitch"), _) => cart::unhitch(server, client),
+        (Some("load"), Some(slot)) if slot > 0 => cart::load(server, client, slot - 1),
+        (Some("unload"), Some(slot)) if slot > 0 => cart::unload(server, client, slot - 1),
+        _ => server.notify_client(
+            client,
+            ChatType::CommandError.server_msg(action.help_string()),
+        ),
+    }
+}
+
 fn handle_region(
     server: &mut Server,
     client: EcsEntity,
@@ -1731,6 +1998,118 @@ fn handle_debug(
     }
 }
 
+/// Reads a duration like `90s`, `30m`, `2h` or `1d` as a number of seconds. A
+/// bare number is taken to be minutes.
+fn parse_duration_secs(s: &str) -> Option<i64> {
+    let (amount, unit) = match s.char_indices().last()? {
+        (i, c) if c.is_ascii_alphabetic() => (&s[..i], c),
+        _ => (s, 'm'),
+    };
+    let scale = match unit {
+        's' => 1,
+        'm' => 60,
+        'h' => 60 * 60,
+        'd' => 24 * 60 * 60,
+        _ => return None,
+    };
+    amount
+        .parse::<i64>()
+        .ok()
+        .filter(|amount| *amount > 0)
+        .map(|amount| amount * scale)
+}
+
+fn handle_rollback(
+    server: &mut Server,
+    client: EcsEntity,
+    target: EcsEntity,
+    args: String,
+    action: &ChatCommand,
+) {
+    let (username, time, radius) = scan_fmt_some!(&args, &action.arg_fmt(), String, String, f32);
+    let (username, secs) = match (username, time.as_deref().and_then(parse_duration_secs)) {
+        (Some(username), Some(secs)) => (username, secs),
+        _ => {
+            server.notify_client(
+                client,
+                ChatType::CommandError.server_msg(action.help_string()),
+            );
+            return;
+        },
+    };
+    let radius = radius.unwrap_or(64.0);
+
+    let center = match server.state.read_component_copied::<comp::Pos>(target) {
+        Some(pos) => pos.0,
+        None => {
+            server.notify_client(
+                client,
+                ChatType::CommandError.server_msg("You have no position."),
+            );
+            return;
+        },
+    };
+
+    let uuid = match server
+        .state
+        .ecs()
+        .read_resource::<LoginProvider>()
+        .username_to_uuid(&username)
+    {
+        Ok(uuid) => uuid,
+        Err(_) => {
+            server.notify_client(
+                client,
+                ChatType::CommandError.server_msg(format!(
+                    "Unable to determine UUID for username \"{}\"",
+                    username
+                )),
+            );
+            return;
+        },
+    };
+
+    let changes = server
+        .state
+        .ecs()
+        .read_resource::<BlockLogger>()
+        .load_changes_since(uuid, block_log::now() - secs);
+    let changes = match changes {
+        Ok(changes) => changes,
+        Err(e) => {
+            error!(?e, "Failed to read the block log");
+            server.notify_client(
+                client,
+                ChatType::CommandError.server_msg(format!("Failed to read the block log: {}", e)),
+            );
+            return;
+        },
+    };
+
+    // Blocks someone else has changed since are left alone
+    let restored = {
+        let terrain = server.state.terrain();
+        let changes = changes
+            .into_iter()
+            .filter(|change| change.pos.map(|e| e as f32 + 0.5).distance(center) < radius)
+            .collect::<Vec<_>>();
+        block_log::undo(&changes, |pos| terrain.get(pos).ok().copied())
+    };
+
+    let count = restored.len();
+    for (pos, block) in restored {
+        server.state.set_block(pos, block);
+    }
+
+    server.notify_client(
+        client,
+        ChatType::CommandInfo.server_msg(format!(
+            "Rolled back {} blocks changed by {}",
+            count, username
+        )),
+    );
+}
+
 fn handle_remove_lights(
     server: &mut Server,
     client: EcsEntity,
@@ -0,0 +1,249 @@
+//! Playing scripted sequences for players.
+//!
+//! Quest events look up the sequence they trigger and start playing it for
+//! the player. Each tick, the steps that are due are played: the NPC taking
+//! part is steered through its agent, and the camera moves and input locks are
+//! sent to the player's client.
+
+use crate::{state_ext::StateExt, Server};
+use common::{
+    comp::{self, Agent, Alignment, ChatType, Pos},
+    cutscene::{Actor, Cutscene, CutsceneEvent, CutsceneStep, CutsceneTriggers, QuestEvent},
+    msg::ServerGeneral,
+    path::Chaser,
+    state::DeltaTime,
+    sync::Uid,
+};
+use hashbrown::HashMap;
+use specs::{Entity as EcsEntity, Join, WorldExt};
+use vek::*;
+
+/// How far from where a sequence takes place the NPC taking part can be
+const NPC_RANGE: f32 = 32.0;
+
+/// A sequence playing for a player
+struct Playback {
+    cutscene: Cutscene,
+    /// Index of the next step to play
+    next: usize,
+    /// Seconds left before the next step is played
+    wait: f32,
+    /// Where the sequence takes place
+    anchor: Vec3<f32>,
+    npc: Option<EcsEntity>,
+}
+
+pub struct Cutscenes {
+    triggers: CutsceneTriggers,
+    playing: HashMap<EcsEntity, Playback>,
+    /// The NPCs taking part in sequences, with the number of sequences each
+    /// takes part in and what it was doing before the first of them
+    npcs: HashMap<EcsEntity, (usize, comp::Activity)>,
+}
+
+impl Default for Cutscenes {
+    fn default() -> Self {
+        Self {
+            triggers: CutsceneTriggers::load_expect(),
+            playing: HashMap::new(),
+            npcs: HashMap::new(),
+        }
+    }
+}
+
+impl Cutscenes {
+    pub fn is_playing(&self, entity: EcsEntity) -> bool { self.playing.contains_key(&entity) }
+}
+
+/// Starts the sequence set for `event` for the player, taking place around
+/// `anchor`. Nothing happens if no sequence is set for the event, or if the
+/// player is already watching one.
+pub fn trigger_quest_event(
+    server: &mut Server,
+    entity: EcsEntity,
+    event: QuestEvent,
+    anchor: Vec3<f32>,
+) {
+    let ecs = server.state.ecs();
+    let mut cutscenes = ecs.write_resource::<Cutscenes>();
+    if cutscenes.is_playing(entity) {
+        return;
+    }
+    let cutscene = match cutscenes.triggers.get(event) {
+        Some(specifier) => Cutscene::load_expect(specifier),
+        None => return,
+    };
+
+    // The nearest villager takes part in the sequence
+    let npc = (
+        &ecs.entities(),
+        &ecs.read_storage::<Pos>(),
+        &ecs.read_storage::<Agent>(),
+        &ecs.read_storage::<Alignment>(),
+    )
+        .join()
+        .filter(|(_, _, _, alignment)| matches!(alignment, Alignment::Npc))
+        .map(|(npc, pos, _, _)| (npc, pos.0.distance_squared(anchor)))
+        .filter(|(_, dist_sqrd)| *dist_sqrd < NPC_RANGE.powi(2))
+        .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
+        .map(|(npc, _)| npc);
+    let mut agents = ecs.write_storage::<Agent>();
+    if let Some((npc, agent)) = npc.and_then(|npc| Some((npc, agents.get_mut(npc)?))) {
+        // An NPC can take part in the sequences of several players at once
+        let (count, _) = cutscenes.npcs.entry(npc).or_insert_with(|| {
+            let previous = std::mem::replace(&mut agent.activity, comp::Activity::Scripted {
+                dest: None,
+                chaser: Chaser::default(),
+                actions: Vec::new(),
+            });
+            (0, previous)
+        });
+        *count += 1;
+    }
+
+    cutscenes.playing.insert(entity, Playback {
+        cutscene,
+        next: 0,
+        wait: 0.0,
+        anchor,
+        npc,
+    });
+}
+
+/// Plays the steps of each sequence that are due, and ends the sequences that
+/// are over
+pub fn update_cutscenes(server: &mut Server) {
+    let ecs = server.state.ecs();
+    let dt = ecs.read_resource::<DeltaTime>().0;
+    let mut due = Vec::new();
+    let mut finished = Vec::new();
+    ecs.write_resource::<Cutscenes>()
+        .playing
+        .retain(|entity, playback| {
+            if !ecs.is_alive(*entity) {
+                finished.push((*entity, playback.npc));
+                return false;
+            }
+            playback.wait -= dt;
+            while playback.wait <= 0.0 {
+                match playback.cutscene.steps.get(playback.next) {
+                    Some(step) => {
+                        playback.wait += step.duration();
+                        playback.next += 1;
+                        due.push((*entity, playback.anchor, playback.npc, step.clone()));
+                    },
+                    None => {
+                        finished.push((*entity, playback.npc));
+                        return false;
+                    },
+                }
+            }
+            true
+        });
+
+    for (entity, anchor, npc, step) in due {
+        play_step(server, entity, anchor, npc, step);
+    }
+
+    for (entity, npc) in finished {
+        // Once the NPC takes part in no more sequences, it goes back to what it was
+        // doing before them
+        let previous = npc.and_then(|npc| {
+            let mut cutscenes = server.state.ecs().write_resource::<Cutscenes>();
+            let (count, _) = cutscenes.npcs.get_mut(&npc)?;
+            *count -= 1;
+            if *count > 0 {
+                return None;
+            }
+            cutscenes
+                .npcs
+                .remove(&npc)
+                .map(|(_, activity)| (npc, activity))
+        });
+        if let Some((npc, activity)) = previous {
+            let mut agents = server.state.ecs().write_storage::<Agent>();
+            if let Some(agent) = agents
+                .get_mut(npc)
+                .filter(|agent| agent.activity.is_scripted())
+            {
+                agent.activity = activity;
+            }
+        }
+        server.notify_client(entity, ServerGeneral::Cutscene(CutsceneEvent::End));
+    }
+}
+
+fn play_step(
+    server: &mut Server,
+    entity: EcsEntity,
+    anchor: Vec3<f32>,
+    npc: Option<EcsEntity>,
+    step: CutsceneStep,
+) {
+    let actor_entity = |actor| match actor {
+        Actor::Player => Some(entity),
+        Actor::Npc => npc,
+    };
+
+    match step {
+        CutsceneStep::Camera {
+            pos,
+            focus,
+            duration,
+        } => server.notify_client(
+            entity,
+            ServerGeneral::Cutscene(CutsceneEvent::Camera {
+                pos: anchor + pos,
+                focus: anchor + focus,
+                duration,
+            }),
+        ),
+        CutsceneStep::WalkTo(pos) => {
+            let mut agents = server.state.ecs().write_storage::<Agent>();
+            if let Some(comp::Activity::Scripted { dest, .. }) = npc
+                .and_then(|npc| agents.get_mut(npc))
+                .map(|agent| &mut agent.activity)
+            {
+                *dest = Some(anchor + pos);
+            }
+        },
+        CutsceneStep::Emote(actor, emote) => {
+            let action = emote.control_action();
+            let ecs = server.state.ecs();
+            match actor {
+                // NPCs are steered through their agent, which resets their controller each tick
+                Actor::Npc => {
+                    let mut agents = ecs.write_storage::<Agent>();
+                    if let Some(comp::Activity::Scripted { actions, .. }) = npc
+                        .and_then(|npc| agents.get_mut(npc))
+                        .map(|agent| &mut agent.activity)
+                    {
+                        actions.push(action);
+                    }
+                },
+                Actor::Player => {
+                    if let Some(controller) =
+                        ecs.write_storage::<comp::Controller>().get_mut(entity)
+                    {
+                        controller.actions.push(action);
+                    }
+                },
+            }
+        },
+        CutsceneStep::Say { actor, line, .. } => {
+            let uid = actor_entity(actor)
+                .and_then(|speaker| server.state.read_component_copied::<Uid>(speaker));
+            if let Some(uid) = uid {
+                server.state.send_chat(match actor {
+                    Actor::Player => ChatType::Say(uid).chat_msg(line),
+                    Actor::Npc => comp::UnresolvedChatMsg::npc(uid, line),
+                });
+            }
+        },
+        CutsceneStep::LockInput(locked) => server.notify_client(
+            entity,
+            ServerGeneral::Cutscene(CutsceneEvent::LockInput(locked)),
+        ),
+        CutsceneStep::Wait(_) => {},
+    }
+}
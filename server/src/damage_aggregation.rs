@@ -0,0 +1,114 @@
+//! Combining rapid hits into single health changes.
+//!
+//! Attacks that hit many times in quick succession, like spinning melee
+//! attacks, would otherwise flood their targets with tiny damage numbers. When
+//! the server sets a damage aggregation window, all damage that the same
+//! attacker deals to the same target with their attacks, such as melee hits
+//! and shockwaves, is held back until the window closes, and then applied as
+//! one combined change.
+
+use crate::{events::apply_damage, Server};
+use common::{
+    comp::{HealthChange, HealthSource},
+    state::Time,
+    sync::Uid,
+};
+use hashbrown::HashMap;
+use specs::WorldExt;
+
+/// Damage from attacks held back until its aggregation window closes
+#[derive(Default)]
+pub struct DamageAggregator {
+    /// Seconds over which hits are combined (0 disables aggregation)
+    window: f64,
+    /// The combined change of each target and attacker pair, and when its
+    /// window opened
+    pending: HashMap<(Uid, Uid), (f64, HealthChange)>,
+}
+
+impl DamageAggregator {
+    pub fn new(window: f32) -> Self {
+        Self {
+            window: f64::from(window),
+            pending: HashMap::new(),
+        }
+    }
+
+    /// Holds back `change` to `target` to combine it with the hits following
+    /// it, or gives it back if it should be applied right away
+    pub fn hold(&mut self, target: Uid, change: HealthChange, time: f64) -> Option<HealthChange> {
+        let attacker = match change.cause {
+            HealthSource::Attack { by } if self.window > 0.0 && change.amount < 0 => by,
+            _ => return Some(change),
+        };
+        self.pending
+            .entry((target, attacker))
+            .and_modify(|(_, pending)| {
+                pending.amount += change.amount;
+                pending.crit |= change.crit;
+                pending.mitigated += change.mitigated;
+            })
+            .or_insert((time, change));
+        None
+    }
+
+    /// Takes out the combined changes whose window has closed by `time`
+    pub fn take_due(&mut self, time: f64) -> Vec<(Uid, HealthChange)> {
+        let window = self.window;
+        let mut due = Vec::new();
+        self.pending.retain(|(target, _), (opened, change)| {
+            let closed = time - *opened >= window;
+            if closed {
+                due.push((*target, *change));
+            }
+            !closed
+        });
+        due
+    }
+}
+
+/// Applies the combined damage whose aggregation window has closed
+pub fn apply_aggregated_damage(server: &Server) {
+    let due = {
+        let ecs = server.state.ecs();
+        let time = ecs.read_resource::<Time>().0;
+        ecs.write_resource::<DamageAggregator>().take_due(time)
+    };
+    for (target, change) in due {
+        apply_damage(server, target, change);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hit(by: u64, amount: i32) -> HealthChange {
+        HealthChange {
+            amount,
+            cause: HealthSource::Attack { by: Uid(by) },
+            crit: false,
+            mitigated: 0,
+            overkill: 0,
+        }
+    }
+
+    #[test]
+    fn combines_hits_within_window() {
+        let mut aggregator = DamageAggregator::new(0.5);
+        assert_eq!(aggregator.hold(Uid(1), hit(2, -5), 0.0), None);
+        assert_eq!(aggregator.hold(Uid(1), hit(2, -7), 0.2), None);
+        assert_eq!(aggregator.hold(Uid(1), hit(3, -1), 0.3), None);
+        assert!(aggregator.take_due(0.4).is_empty());
+
+        let due = aggregator.take_due(0.5);
+        assert_eq!(due, vec![(Uid(1), hit(2, -12))]);
+        assert_eq!(aggregator.take_due(0.8), vec![(Uid(1), hit(3, -1))]);
+    }
+
+    #[test]
+    fn disabled_window_applies_right_away() {
+        let mut aggregator = DamageAggregator::new(0.0);
+        assert_eq!(aggregator.hold(Uid(1), hit(2, -5), 0.0), Some(hit(2, -5)));
+    }
+}
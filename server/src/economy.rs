@@ -0,0 +1,139 @@
+//! Keeps the economies of the sites of the world going while the server runs.
+//!
+//! The calendar of the server is the clock of the history simulation, which
+//! goes on from the day that the history got to when the world was generated.
+//! Every few real minutes the economy is updated, moving the history simulation
+//! on by each season that has passed on the calendar since it last ticked,
+//! after which the town markets take on the new values and stocks of their
+//! goods. Before each update the coins and goods that town markets took in and
+//! paid out are added to and taken from the stocks of their sites, the coins
+//! serving as the treasury of each town. The state of the economy is saved
+//! after each update, so that it carries on from there when the server restarts
+//! instead of going back to how it was when the world was generated. When the
+//! economy isn't kept running, the trades at the markets are still passed on
+//! and saved in the same way.
+
+use crate::{
+    market::Markets,
+    persistence::economy::{load_economy, EconomySaver},
+    Server,
+};
+use common::time::DAY_SECS;
+use specs::WorldExt;
+use std::path::Path;
+use tracing::{debug, error, info, warn};
+use world::{sim2, IndexOwned};
+
+/// Most ticks that the economy is moved on by in one update, so that the server
+/// doesn't stall when the date is set far ahead
+const MAX_CATCH_UP_TICKS: u32 = 4;
+/// How many times in a row an update of the economy can be put off between
+/// warnings about it
+const DELAY_WARN_INTERVAL: u32 = 600;
+
+/// Real time passed since the economy was last updated
+#[derive(Default)]
+pub struct EconomyClock {
+    /// Seconds
+    elapsed: f64,
+    /// How many times in a row the update has been put off because the index
+    /// was in use
+    delayed: u32,
+}
+
+/// Restores the economy saved by a previous run of the server, if there is one
+pub fn restore_economy(index: &mut IndexOwned, db_dir: &Path) {
+    let state = match load_economy(db_dir) {
+        Ok(Some(state)) => state,
+        Ok(None) => return,
+        Err(e) => {
+            error!(
+                ?e,
+                "Couldn't load the economy, starting from the generated one"
+            );
+            return;
+        },
+    };
+    // Nothing else holds on to the index while the server is starting
+    if let Some(index) = index.get_mut() {
+        if sim2::load_state(index, &state) {
+            info!("Economy carries on from where it was saved");
+        }
+    }
+}
+
+/// Updates the economy once enough real time has passed since its last update,
+/// ticking it up to the date on the calendar, and passes the new prices and
+/// stocks on to the markets
+pub fn update_economy(server: &mut Server) {
+    let live_economy = server.settings().live_economy;
+    let today = (server.state.get_time_of_day() / DAY_SECS) as f32;
+    let tick_length = server.settings().economy_tick_length * 60.0;
+    let dt = f64::from(server.state.get_delta_time());
+    {
+        let mut clock = server.state.ecs().write_resource::<EconomyClock>();
+        clock.elapsed += dt;
+        if clock.elapsed < tick_length {
+            return;
+        }
+    }
+
+    // Chunks that are being generated hold on to the index, in which case the tick
+    // waits until they are done
+    let index = match server.index.get_mut() {
+        Some(index) => index,
+        None => {
+            let mut clock = server.state.ecs().write_resource::<EconomyClock>();
+            if clock.delayed % DELAY_WARN_INTERVAL == 0 {
+                warn!(
+                    delayed = clock.delayed,
+                    "Putting off the economy update while chunks are being generated"
+                );
+            }
+            clock.delayed += 1;
+            return;
+        },
+    };
+    let changes = server
+        .state
+        .ecs()
+        .write_resource::<Markets>()
+        .take_changes();
+    sim2::change_stocks(index, &changes);
+    if live_economy {
+        let mut ticks = 0;
+        while index.time + sim2::TICK_PERIOD <= today && ticks < MAX_CATCH_UP_TICKS {
+            sim2::tick(index, sim2::TICK_PERIOD);
+            ticks += 1;
+        }
+        if ticks > 0 {
+            debug!(time = index.time, ticks, "Ticked the economy");
+        }
+    }
+    {
+        let mut clock = server.state.ecs().write_resource::<EconomyClock>();
+        if clock.delayed > 0 {
+            info!(
+                delayed = clock.delayed,
+                "Updated the economy after putting it off"
+            );
+        }
+        *clock = EconomyClock::default();
+    }
+
+    let markets = server.world.markets(server.index.as_index_ref());
+    server
+        .state
+        .ecs()
+        .write_resource::<Markets>()
+        .refresh(markets);
+
+    match sim2::save_state(&server.index) {
+        Ok(state) => server
+            .state
+            .ecs()
+            .read_resource::<EconomySaver>()
+            .save(state),
+        Err(e) => error!(?e, "Couldn't serialize the economy to save it"),
+    }
+}
@@ -1,18 +1,31 @@
-use crate::{sys, Server, StateExt};
+use crate::{
+    bounty::Wanted,
+    husbandry::{self, Growth},
+    market::Merchant,
+    settings::EmptyQuiver,
+    sys, Server, StateExt,
+};
 use common::{
     character::CharacterId,
     comp::{
         self, beam, humanoid::DEFAULT_HUMANOID_EYE_HEIGHT, shockwave, Agent, Alignment, Body,
-        Gravity, Item, ItemDrop, LightEmitter, Loadout, Ori, Pos, Projectile, Scale, Stats, Vel,
-        WaypointArea,
+        BossState, Gravity, Item, ItemDrop, LightEmitter, Loadout, Ori, Pos, Projectile, Scale,
+        Stats, Vel, WaypointArea,
     },
     outcome::Outcome,
+    state::State,
+    sync::{Uid, WorldSyncExt},
     util::Dir,
 };
 use comp::group;
 use specs::{Builder, Entity as EcsEntity, WorldExt};
 use vek::{Rgb, Vec3};
 
+const STATIONARY_MASS: f32 = 1000.0;
+/// Damage multiplier for the arrows shot by players without ammo, when the
+/// server lets them shoot at all
+const WEAKENED_ARROW_DAMAGE: f32 = 0.5;
+
 pub fn handle_initialize_character(
     server: &mut Server,
     entity: EcsEntity,
@@ -24,7 +37,14 @@ pub fn handle_initialize_character(
 pub fn handle_loaded_character_data(
     server: &mut Server,
     entity: EcsEntity,
-    loaded_components: (comp::Body, comp::Stats, comp::Inventory, comp::Loadout),
+    loaded_components: (
+        comp::Body,
+        comp::Stats,
+        comp::Inventory,
+        comp::Loadout,
+        comp::AbilitySelection,
+        comp::Buffs,
+    ),
 ) {
     server
         .state
@@ -36,14 +56,29 @@ pub fn handle_loaded_character_data(
 pub fn handle_create_npc(
     server: &mut Server,
     pos: Pos,
-    stats: Stats,
+    mut stats: Stats,
     loadout: Loadout,
     body: Body,
     agent: impl Into<Option<Agent>>,
     alignment: Alignment,
-    scale: Scale,
+    mut scale: Scale,
     drop_item: Option<Item>,
-) {
+    boss: Option<BossState>,
+    market: Option<u64>,
+    bounty: Option<u64>,
+) -> EcsEntity {
+    // Farm animals grow up, so some of them are still young
+    let growth = if alignment == Alignment::Tame && husbandry::grows(&body) {
+        let growth = Growth::random(&mut rand::thread_rng());
+        scale.0 *= growth.apply(body, &mut stats).0;
+        stats
+            .health
+            .set_to(stats.health.maximum(), comp::HealthSource::Revive);
+        Some(growth)
+    } else {
+        None
+    };
+
     let group = match alignment {
         Alignment::Wild => None,
         Alignment::Passive => None,
@@ -65,6 +100,13 @@ pub fn handle_create_npc(
         entity
     };
 
+    // Stationary entities are too heavy to be shoved out of place
+    let entity = if body.is_stationary() {
+        entity.with(comp::Mass(STATIONARY_MASS))
+    } else {
+        entity
+    };
+
     let entity = if let Some(agent) = agent.into() {
         entity.with(agent)
     } else {
@@ -77,7 +119,31 @@ pub fn handle_create_npc(
         entity
     };
 
-    entity.build();
+    let entity = if let Some(growth) = growth {
+        entity.with(growth)
+    } else {
+        entity
+    };
+
+    let entity = if let Some(boss) = boss {
+        entity.with(boss)
+    } else {
+        entity
+    };
+
+    let entity = if let Some(site) = market {
+        entity.with(Merchant { site })
+    } else {
+        entity
+    };
+
+    let entity = if let Some(bounty) = bounty {
+        entity.with(Wanted(bounty))
+    } else {
+        entity
+    };
+
+    entity.build()
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -91,8 +157,18 @@ pub fn handle_shoot(
     gravity: Option<Gravity>,
     speed: f32,
 ) {
+    let empty_quiver = server.settings().empty_quiver;
     let state = server.state_mut();
 
+    // Players use up ammo to shoot arrows
+    let mut projectile = projectile;
+    if projectile.is_arrow()
+        && state.ecs().read_storage::<comp::Player>().contains(entity)
+        && !load_ammo(state, entity, &mut projectile, empty_quiver)
+    {
+        return;
+    }
+
     let mut pos = state
         .ecs()
         .read_storage::<Pos>()
@@ -115,6 +191,8 @@ pub fn handle_shoot(
 
     pos.z += eye_height;
 
+    let instance = state.read_component_copied::<comp::Instance>(entity);
+
     let mut builder = state.create_projectile(Pos(pos), Vel(vel), body, projectile);
     if let Some(light) = light {
         builder = builder.with(light)
@@ -122,10 +200,52 @@ pub fn handle_shoot(
     if let Some(gravity) = gravity {
         builder = builder.with(gravity)
     }
+    // Projectiles only hit entities in the same dungeon instance as the shooter
+    if let Some(instance) = instance {
+        builder = builder.with(instance)
+    }
 
     builder.build();
 }
 
+/// Loads an arrow with the next ammo in the inventory of its shooter, using
+/// it up. Returns whether the arrow can be shot, which for shooters who are out
+/// of ammo depends on the server settings.
+fn load_ammo(
+    state: &mut State,
+    entity: EcsEntity,
+    projectile: &mut Projectile,
+    empty_quiver: EmptyQuiver,
+) -> bool {
+    let ammo = state
+        .ecs()
+        .write_storage::<comp::Inventory>()
+        .get_mut(entity)
+        .and_then(|inventory| {
+            let slot = inventory.ammo_slot()?;
+            inventory.take(slot)
+        });
+
+    match ammo.as_ref().map(|item| (item, item.kind())) {
+        Some((item, comp::item::ItemKind::Ammo { damage, on_hit, .. })) => {
+            projectile.load(Some(item.item_definition_id()), *damage, on_hit);
+            state.write_component(
+                entity,
+                comp::InventoryUpdate::new(comp::InventoryUpdateEvent::Used),
+            );
+            true
+        },
+        _ => match empty_quiver {
+            EmptyQuiver::Unlimited => true,
+            EmptyQuiver::Weakened => {
+                projectile.load(None, WEAKENED_ARROW_DAMAGE, &[]);
+                true
+            },
+            EmptyQuiver::Disabled => false,
+        },
+    }
+}
+
 pub fn handle_shockwave(
     server: &mut Server,
     properties: shockwave::Properties,
@@ -133,12 +253,29 @@ pub fn handle_shockwave(
     ori: Ori,
 ) {
     let state = server.state_mut();
-    state.create_shockwave(properties, pos, ori).build();
+    let instance = owner_instance(state, properties.owner);
+    let mut builder = state.create_shockwave(properties, pos, ori);
+    // Shockwaves only hit entities in the same dungeon instance as their owner
+    if let Some(instance) = instance {
+        builder = builder.with(instance)
+    }
+    builder.build();
 }
 
 pub fn handle_beam(server: &mut Server, properties: beam::Properties, pos: Pos, ori: Ori) {
     let state = server.state_mut();
-    state.create_beam(properties, pos, ori).build();
+    let instance = owner_instance(state, properties.owner);
+    let mut builder = state.create_beam(properties, pos, ori);
+    // Beams only hit entities in the same dungeon instance as their owner
+    if let Some(instance) = instance {
+        builder = builder.with(instance)
+    }
+    builder.build();
+}
+
+fn owner_instance(state: &State, owner: Option<Uid>) -> Option<comp::Instance> {
+    let owner = state.ecs().entity_from_uid(owner?.into())?;
+    state.read_component_copied::<comp::Instance>(owner)
 }
 
 pub fn handle_create_waypoint(server: &mut Server, pos: Vec3<f32>) {
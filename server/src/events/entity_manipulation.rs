@@ -1,23 +1,31 @@
 use crate::{
+    bounty,
     client::Client,
-    comp::{biped_large, quadruped_medium, quadruped_small},
+    comp::{biped_large, quadruped_small},
+    damage_aggregation::DamageAggregator,
+    fire::Embers,
+    instance::DungeonInstances,
+    summon,
+    territory::Territories,
     Server, SpawnPoint, StateExt,
 };
 use common::{
     assets::Asset,
+    combat::{self, CombatRules, Combatant},
     comp::{
-        self, buff,
+        self, agent, buff,
         chat::{KillSource, KillType},
-        object, Alignment, Body, Damage, DamageSource, Group, HealthChange, HealthSource, Item,
-        Player, Pos, Stats,
+        object, Alignment, Body, Damage, DamageKind, DamageSource, Group, HealthChange,
+        HealthSource, Item, Player, Pos, Stats,
     },
     lottery::Lottery,
     msg::{PlayerListUpdate, ServerGeneral},
     outcome::Outcome,
-    state::BlockChange,
+    state::{BlockChange, Time},
+    states,
     sync::{Uid, UidAllocator, WorldSyncExt},
     sys::combat::BLOCK_ANGLE,
-    terrain::{Block, TerrainGrid},
+    terrain::{Block, SpriteKind, TerrainGrid},
     vol::ReadVol,
     Explosion,
 };
@@ -27,18 +35,297 @@ use specs::{join::Join, saveload::MarkerAllocator, Entity as EcsEntity, WorldExt
 use tracing::error;
 use vek::Vec3;
 
+/// Share of their maximum health that revived players come back with
+const REVIVE_HEALTH: f32 = 0.25;
+
+/// Chance that a broken prop leaves some of its contents behind
+const SPRITE_LOOT_CHANCE: f64 = 0.5;
+
 pub fn handle_damage(server: &Server, uid: Uid, change: HealthChange) {
+    let ecs = server.state.ecs();
+    let time = ecs.read_resource::<Time>().0;
+    let change = ecs
+        .write_resource::<DamageAggregator>()
+        .hold(uid, change, time);
+    if let Some(change) = change {
+        apply_damage(server, uid, change);
+    }
+}
+
+/// Applies a change in health right away, without combining it with others
+pub fn apply_damage(server: &Server, uid: Uid, change: HealthChange) {
     let state = &server.state;
     let ecs = state.ecs();
     if let Some(entity) = ecs.entity_from_uid(uid.into()) {
-        if let Some(stats) = ecs.write_storage::<Stats>().get_mut(entity) {
-            stats.health.change_by(change);
+        // Players can't hurt each other in outposts held by friendly factions
+        if change.amount < 0 && is_player_attack_in_safe_zone(server, entity, change.cause) {
+            return;
+        }
+
+        // Applying the change works out how much of it was overkill
+        let change = ecs
+            .write_storage::<Stats>()
+            .get_mut(entity)
+            .map_or(change, |stats| {
+                stats.health.change_by(change);
+                stats.health.last_change.1
+            });
+
+        log_health_change(ecs, uid, change);
+        add_threat(server, entity, uid, change);
+
+        // Taking too much damage while casting a spell interrupts it, and any damage
+        // interrupts reviving, fishing and performing
+        if change.amount < 0 {
+            let mut character_states = ecs.write_storage::<comp::CharacterState>();
+            if let Some(character_state) = character_states.get_mut(entity) {
+                match character_state {
+                    comp::CharacterState::CastSpell(data) => {
+                        if data.take_damage(change.amount.abs() as u32) {
+                            *character_state = comp::CharacterState::Wielding;
+                        }
+                    },
+                    comp::CharacterState::Revive(_)
+                    | comp::CharacterState::Fishing(_)
+                    | comp::CharacterState::Perform(_) => {
+                        *character_state = comp::CharacterState::Idle;
+                    },
+                    _ => {},
+                }
+            }
+        }
+    }
+}
+
+/// Records a change in health in the combat logs of the entity it happened to
+/// and of whoever caused it
+fn log_health_change(ecs: &specs::World, uid: Uid, change: HealthChange) {
+    let attacker = match change.cause {
+        HealthSource::Healing { by } => by,
+        cause => cause.attacker(),
+    };
+    // Buffs aren't dealt with the weapon their applier happens to wield now
+    let ability = match change.cause {
+        HealthSource::Attack { .. }
+        | HealthSource::Projectile { .. }
+        | HealthSource::Explosion { .. }
+        | HealthSource::Energy { .. } => attacker
+            .and_then(|uid| ecs.entity_from_uid(uid.into()))
+            .and_then(|e| {
+                ecs.read_storage::<comp::Loadout>()
+                    .get(e)
+                    .and_then(|loadout| loadout.active_item.as_ref())
+                    .map(|item| item.item.name().to_string())
+            }),
+        _ => None,
+    };
+
+    log_combat_event(ecs, comp::CombatEvent {
+        time: ecs.read_resource::<Time>().0,
+        attacker,
+        target: uid,
+        kind: comp::CombatEventKind::Health { change, ability },
+    });
+}
+
+/// Adds an event to the combat logs of the entities involved in it, for those
+/// that keep one
+fn log_combat_event(ecs: &specs::World, event: comp::CombatEvent) {
+    let mut combat_logs = ecs.write_storage::<comp::CombatLog>();
+    if let Some(log) = event
+        .attacker
+        .filter(|attacker| *attacker != event.target)
+        .and_then(|attacker| ecs.entity_from_uid(attacker.into()))
+        .and_then(|attacker| combat_logs.get_mut(attacker))
+    {
+        log.push(event.clone());
+    }
+    if let Some(log) = ecs
+        .entity_from_uid(event.target.into())
+        .and_then(|target| combat_logs.get_mut(target))
+    {
+        log.push(event);
+    }
+}
+
+/// Makes NPCs remember who hurt them, and who healed those they are fighting
+fn add_threat(server: &Server, entity: EcsEntity, uid: Uid, change: HealthChange) {
+    let ecs = server.state.ecs();
+    let mut threats = ecs.write_storage::<comp::Threat>();
+    let buffs = ecs.read_storage::<comp::Buffs>();
+    let multiplier = |by: Uid| {
+        ecs.entity_from_uid(by.into())
+            .map_or(1.0, |e| agent::threat_multiplier(buffs.get(e)))
+    };
+
+    if change.amount < 0 {
+        let attacker = change.cause.attacker().filter(|by| *by != uid);
+        if let Some(by) = attacker {
+            if ecs.read_storage::<comp::Agent>().contains(entity) {
+                if let Ok(entry) = threats.entry(entity) {
+                    entry
+                        .or_insert_with(Default::default)
+                        .add(by, -change.amount as f32 * multiplier(by));
+                }
+            }
         }
+    } else if let HealthSource::Healing { by: Some(by) } = change.cause {
+        let threat = change.amount as f32 * agent::HEAL_THREAT_FACTOR * multiplier(by);
+        for threat_table in (&mut threats).join() {
+            if threat_table.contains(uid) {
+                threat_table.add(by, threat);
+            }
+        }
+    }
+}
+
+/// Puts a player that ran out of health into the downed state if a group member
+/// is around to revive them, and kills them otherwise
+pub fn handle_downed(server: &mut Server, entity: EcsEntity, cause: HealthSource) {
+    // Going down loses a duel just as dying does
+    end_duels(server, entity);
+
+    // Someone who isn't down themselves has to be left to do the reviving
+    let group = {
+        let ecs = server.state.ecs();
+        ecs.read_storage::<Group>()
+            .get(entity)
+            .copied()
+            .filter(|group| {
+                (
+                    &ecs.entities(),
+                    &ecs.read_storage::<Player>(),
+                    &ecs.read_storage::<Group>(),
+                    &ecs.read_storage::<Stats>(),
+                    &ecs.read_storage::<comp::CharacterState>(),
+                )
+                    .join()
+                    .any(|(member, _, member_group, stats, character_state)| {
+                        member != entity
+                            && member_group == group
+                            && !stats.is_dead
+                            && !character_state.is_downed()
+                    })
+            })
+    };
+    let group = match group {
+        Some(group) => group,
+        None => return handle_death(server, entity, cause),
+    };
+
+    let state = &server.state;
+    let _ = state.ecs().write_storage().insert(
+        entity,
+        comp::CharacterState::Downed(states::downed::Data::default()),
+    );
+    if let Some(stats) = state.ecs().read_storage::<Stats>().get(entity) {
+        state.send_chat(
+            comp::ChatType::GroupMeta(group)
+                .chat_msg(format!("{} is down and needs to be revived!", stats.name)),
+        );
+    }
+}
+
+/// Kills an entity that would have gone down, the same way running out of
+/// health normally does
+fn handle_death(server: &mut Server, entity: EcsEntity, cause: HealthSource) {
+    if let Some(stats) = server.state.ecs().write_storage::<Stats>().get_mut(entity) {
+        stats.is_dead = true;
+    }
+    handle_destroy(server, entity, cause);
+}
+
+/// Brings a downed player back on their feet if the group member reviving them
+/// is still next to them
+pub fn handle_revive(server: &Server, target: Uid, by: Uid) {
+    let state = &server.state;
+    let ecs = state.ecs();
+    let (target, reviver) = match (
+        ecs.entity_from_uid(target.into()),
+        ecs.entity_from_uid(by.into()),
+    ) {
+        (Some(target), Some(reviver)) => (target, reviver),
+        _ => return,
+    };
+
+    let positions = ecs.read_storage::<Pos>();
+    let groups = ecs.read_storage::<Group>();
+    let in_range = match (positions.get(target), positions.get(reviver)) {
+        (Some(target_pos), Some(reviver_pos)) => {
+            target_pos.0.distance_squared(reviver_pos.0) < states::revive::REVIVE_RANGE.powi(2)
+        },
+        _ => false,
+    };
+    let same_group = groups.get(target).is_some() && groups.get(target) == groups.get(reviver);
+    let mut character_states = ecs.write_storage::<comp::CharacterState>();
+    let is_downed = character_states
+        .get(target)
+        .map_or(false, |character_state| character_state.is_downed());
+    if !in_range || !same_group || !is_downed {
+        return;
+    }
+
+    if let Some(stats) = ecs.write_storage::<Stats>().get_mut(target) {
+        let amount = (stats.health.maximum() as f32 * REVIVE_HEALTH) as i32;
+        stats.health.change_by(HealthChange {
+            amount: amount.max(1),
+            cause: HealthSource::Healing { by: Some(by) },
+            crit: false,
+            mitigated: 0,
+            overkill: 0,
+        });
+    }
+    let _ = character_states.insert(target, comp::CharacterState::Idle);
+}
+
+/// Ends the duels of a player who died or went down, letting their opponents
+/// know
+fn end_duels(server: &Server, entity: EcsEntity) {
+    let ecs = server.state.ecs();
+    let uid = match ecs.read_storage::<Uid>().get(entity) {
+        Some(uid) => *uid,
+        None => return,
+    };
+    let opponents = ecs.write_resource::<CombatRules>().duels.end(uid);
+    for opponent in opponents
+        .into_iter()
+        .filter_map(|uid| ecs.entity_from_uid(uid.into()))
+    {
+        server.notify_client(
+            opponent,
+            comp::ChatType::Meta.server_msg("Your duel is over.".to_owned()),
+        );
     }
 }
 
+/// Whether the damage was dealt by a player to another player standing in an
+/// outpost held by a friendly faction
+fn is_player_attack_in_safe_zone(server: &Server, entity: EcsEntity, cause: HealthSource) -> bool {
+    let ecs = server.state.ecs();
+    let players = ecs.read_storage::<Player>();
+    let attacker = cause
+        .attacker()
+        .and_then(|by| ecs.entity_from_uid(by.into()));
+    let player_attack = attacker.map_or(false, |attacker| {
+        attacker != entity && players.contains(attacker) && players.contains(entity)
+    });
+    player_attack
+        && ecs.read_storage::<Pos>().get(entity).map_or(false, |pos| {
+            ecs.read_resource::<Territories>().is_safe_zone(pos.0.xy())
+        })
+}
+
 pub fn handle_knockback(server: &Server, entity: EcsEntity, impulse: Vec3<f32>) {
     let state = &server.state;
+    let impulse = impulse
+        * combat::knockback_multiplier(
+            state.ecs().read_storage::<Body>().get(entity),
+            state.ecs().read_storage::<comp::Scale>().get(entity),
+            state.ecs().read_storage::<comp::Buffs>().get(entity),
+        );
+    if impulse == Vec3::zero() {
+        return;
+    }
     let mut velocities = state.ecs().write_storage::<comp::Vel>();
     if let Some(vel) = velocities.get_mut(entity) {
         vel.0 = impulse;
@@ -49,6 +336,146 @@ pub fn handle_knockback(server: &Server, entity: EcsEntity, impulse: Vec3<f32>)
     }
 }
 
+/// Applies the on-hit effects of an attack by `attacker` that dealt `damage` to
+/// `target`
+pub fn handle_on_hit(
+    server: &mut Server,
+    attacker: EcsEntity,
+    target: EcsEntity,
+    damage: u32,
+    effects: Vec<comp::OnHitEffect>,
+) {
+    let attacker_uid = match server.state.ecs().read_storage::<Uid>().get(attacker) {
+        Some(uid) => *uid,
+        None => return,
+    };
+
+    for effect in effects {
+        match effect {
+            comp::OnHitEffect::Lifesteal(share) => {
+                handle_damage(server, attacker_uid, HealthChange {
+                    amount: (damage as f32 * share) as i32,
+                    cause: HealthSource::Healing {
+                        by: Some(attacker_uid),
+                    },
+                    crit: false,
+                    mitigated: 0,
+                    overkill: 0,
+                });
+            },
+            comp::OnHitEffect::EnergyGain(amount) => {
+                let ecs = server.state.ecs();
+                if let Some(energy) = ecs.write_storage::<comp::Energy>().get_mut(attacker) {
+                    energy.change_by(amount as i32, comp::EnergySource::HitEnemy);
+                }
+            },
+            comp::OnHitEffect::Bleed {
+                chance,
+                strength,
+                duration,
+            } => {
+                if thread_rng().gen::<f32>() < chance {
+                    let bleed = buff::Buff::new(
+                        buff::BuffKind::Bleeding,
+                        buff::BuffData {
+                            strength: damage as f32 * strength,
+                            duration: Some(duration),
+                        },
+                        vec![buff::BuffCategory::Physical],
+                        buff::BuffSource::Character { by: attacker_uid },
+                    );
+                    handle_buff(server, target, buff::BuffChange::Add(bleed));
+                }
+            },
+            comp::OnHitEffect::ChainLightning { range, strength } => {
+                if let Some(next_uid) = chain_target(server, attacker, target, range) {
+                    handle_damage(server, next_uid, HealthChange {
+                        amount: -(damage as f32 * strength) as i32,
+                        cause: HealthSource::Attack { by: attacker_uid },
+                        crit: false,
+                        mitigated: 0,
+                        overkill: 0,
+                    });
+                }
+            },
+            comp::OnHitEffect::Chill { strength, duration } => {
+                let chill = buff::Buff::new(
+                    buff::BuffKind::Frozen,
+                    buff::BuffData {
+                        strength,
+                        duration: Some(duration),
+                    },
+                    vec![buff::BuffCategory::Magical],
+                    buff::BuffSource::Character { by: attacker_uid },
+                );
+                handle_buff(server, target, buff::BuffChange::Add(chill));
+            },
+            comp::OnHitEffect::Envenom {
+                chance,
+                strength,
+                duration,
+            } => {
+                let resistance = server
+                    .state
+                    .ecs()
+                    .read_storage::<comp::Body>()
+                    .get(target)
+                    .map_or(0.0, |body| body.damage_resistance(comp::DamageKind::Poison));
+                if resistance < 1.0 && thread_rng().gen::<f32>() < chance {
+                    let poison = buff::Buff::new(
+                        buff::BuffKind::Poisoned,
+                        buff::BuffData {
+                            strength: strength * (1.0 - resistance),
+                            duration: Some(duration),
+                        },
+                        vec![buff::BuffCategory::Poison],
+                        buff::BuffSource::Character { by: attacker_uid },
+                    );
+                    handle_buff(server, target, buff::BuffChange::Add(poison));
+                }
+            },
+        }
+    }
+}
+
+/// The closest living entity within `range` of `target` that `attacker` can
+/// hurt, other than the target itself
+fn chain_target(
+    server: &Server,
+    attacker: EcsEntity,
+    target: EcsEntity,
+    range: f32,
+) -> Option<Uid> {
+    let ecs = server.state.ecs();
+    let positions = ecs.read_storage::<Pos>();
+    let groups = ecs.read_storage::<Group>();
+    let instances = ecs.read_storage::<comp::Instance>();
+    let target_pos = positions.get(target)?.0;
+    let attacker_group = groups.get(attacker);
+
+    (
+        &ecs.entities(),
+        &positions,
+        &ecs.read_storage::<Stats>(),
+        &ecs.read_storage::<Uid>(),
+    )
+        .join()
+        .filter(|(entity, pos, stats, _)| {
+            *entity != attacker
+                && *entity != target
+                && !stats.is_dead
+                && instances.get(*entity) == instances.get(target)
+                && (attacker_group.is_none() || groups.get(*entity) != attacker_group)
+                && pos.0.distance_squared(target_pos) < range.powi(2)
+        })
+        .min_by(|(_, a, _, _), (_, b, _, _)| {
+            a.0.distance_squared(target_pos)
+                .partial_cmp(&b.0.distance_squared(target_pos))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })
+        .map(|(_, _, _, uid)| *uid)
+}
+
 /// Handle an entity dying. If it is a player, it will send a message to all
 /// other players. If the entity that killed it had stats, then give it exp for
 /// the kill. Experience given is equal to the level of the entity that was
@@ -63,14 +490,19 @@ pub fn handle_knockback(server: &Server, entity: EcsEntity, impulse: Vec3<f32>)
 // rescan every entity on the server again.
 #[allow(clippy::needless_collect)]
 pub fn handle_destroy(server: &mut Server, entity: EcsEntity, cause: HealthSource) {
-    let state = server.state_mut();
-
     // TODO: Investigate duplicate `Destroy` events (but don't remove this).
     // If the entity was already deleted, it can't be destroyed again.
-    if !state.ecs().is_alive(entity) {
+    if !server.state().ecs().is_alive(entity) {
         return;
     }
 
+    // Pay out the bounty if a wanted outlaw was killed
+    bounty::claim_bounty(server, entity, cause);
+
+    end_duels(server, entity);
+
+    let state = server.state_mut();
+
     // Chat message
     // If it was a player that died
     if let Some(_player) = state.ecs().read_storage::<Player>().get(entity) {
@@ -98,7 +530,9 @@ pub fn handle_destroy(server: &mut Server, entity: EcsEntity, cause: HealthSourc
                         KillSource::NonPlayer("<?>".to_string(), KillType::Melee)
                     }
                 },
-                HealthSource::Projectile { owner: Some(by) } => {
+                HealthSource::Projectile {
+                    owner: Some(by), ..
+                } => {
                     // Get projectile owner entity TODO: add names to projectiles and send in
                     // message
                     if let Some(char_entity) = state.ecs().entity_from_uid(by.into()) {
@@ -189,7 +623,7 @@ pub fn handle_destroy(server: &mut Server, entity: EcsEntity, cause: HealthSourc
                 },
                 HealthSource::World => KillSource::FallDamage,
                 HealthSource::Suicide => KillSource::Suicide,
-                HealthSource::Projectile { owner: None }
+                HealthSource::Projectile { owner: None, .. }
                 | HealthSource::Explosion { owner: None }
                 | HealthSource::Energy { owner: None }
                 | HealthSource::Buff { owner: None }
@@ -210,7 +644,9 @@ pub fn handle_destroy(server: &mut Server, entity: EcsEntity, cause: HealthSourc
     (|| {
         let mut stats = state.ecs().write_storage::<Stats>();
         let by = if let HealthSource::Attack { by }
-        | HealthSource::Projectile { owner: Some(by) }
+        | HealthSource::Projectile {
+            owner: Some(by), ..
+        }
         | HealthSource::Energy { owner: Some(by) }
         | HealthSource::Buff { owner: Some(by) }
         | HealthSource::Explosion { owner: Some(by) } = cause
@@ -323,7 +759,9 @@ pub fn handle_destroy(server: &mut Server, entity: EcsEntity, cause: HealthSourc
             .ecs()
             .write_storage::<comp::CharacterState>()
             .insert(entity, comp::CharacterState::default());
-    } else if state.ecs().read_storage::<comp::Agent>().contains(entity) {
+    } else if state.ecs().read_storage::<comp::Agent>().contains(entity)
+        && !state.ecs().read_storage::<summon::Owner>().contains(entity)
+    {
         use specs::Builder;
 
         // Decide for a loot drop before turning into a lootbag
@@ -353,12 +791,9 @@ pub fn handle_destroy(server: &mut Server, entity: EcsEntity, cause: HealthSourc
                     }
                 },
                 Some(common::comp::Body::QuadrupedMedium(quadruped_medium)) => {
-                    match quadruped_medium.species {
-                        quadruped_medium::Species::Frostfang
-                        | quadruped_medium::Species::Roshwalr => {
-                            "common.loot_tables.loot_table_animal_ice"
-                        },
-                        _ => match rng.gen_range(0, 4) {
+                    match &quadruped_medium.stats().loot_table {
+                        Some(loot_table) => loot_table.as_str(),
+                        None => match rng.gen_range(0, 4) {
                             0 => "common.loot_tables.loot_table_food",
                             2 => "common.loot_tables.loot_table_animal_parts",
                             _ => "common.loot_tables.loot_table_animal_parts",
@@ -396,9 +831,17 @@ pub fn handle_destroy(server: &mut Server, entity: EcsEntity, cause: HealthSourc
                     7 => "common.loot_tables.loot_table_weapon_rare",
                     _ => "common.loot_tables.loot_table",
                 },
+                Some(common::comp::Body::Colossus(_)) => match rng.gen_range(0, 4) {
+                    0 => "common.loot_tables.loot_table_armor_heavy",
+                    1 => "common.loot_tables.loot_table_weapon_uncommon",
+                    _ => "common.loot_tables.loot_table_weapon_rare",
+                },
                 Some(common::comp::Body::Theropod(_)) => {
                     "common.loot_tables.loot_table_animal_parts"
                 },
+                Some(common::comp::Body::Arthropod(_)) => {
+                    "common.loot_tables.loot_table_animal_parts"
+                },
                 Some(common::comp::Body::Dragon(_)) => "common.loot_tables.loot_table_weapon_rare",
                 Some(common::comp::Body::QuadrupedLow(_)) => match rng.gen_range(0, 3) {
                     0 => "common.loot_tables.loot_table_food",
@@ -409,10 +852,29 @@ pub fn handle_destroy(server: &mut Server, entity: EcsEntity, cause: HealthSourc
             })
         };
 
+        // Harder dungeon instances roll the loot several times, keeping the best item
+        let loot_rolls = state
+            .ecs()
+            .read_storage::<comp::Instance>()
+            .get(entity)
+            .and_then(|instance| {
+                state
+                    .ecs()
+                    .read_resource::<DungeonInstances>()
+                    .get(*instance)
+                    .map(|instance| instance.difficulty.loot_rolls())
+            })
+            .unwrap_or(1);
+
         let item = {
             let mut item_drops = state.ecs().write_storage::<comp::ItemDrop>();
             item_drops.remove(entity).map_or_else(
-                || Item::new_from_asset_expect(lottery().choose()),
+                || {
+                    (0..loot_rolls.max(1))
+                        .map(|_| Item::new_from_asset_expect(lottery().choose()))
+                        .max_by_key(|item| item.quality())
+                        .expect("At least one loot roll")
+                },
                 |item_drop| item_drop.0,
             )
         };
@@ -437,6 +899,7 @@ pub fn handle_destroy(server: &mut Server, entity: EcsEntity, cause: HealthSourc
             .delete_entity_recorded(entity)
             .map_err(|e| error!(?e, ?entity, "Failed to delete destroyed entity"));
     } else {
+        // Summoned minions vanish without dropping loot
         let _ = state
             .delete_entity_recorded(entity)
             .map_err(|e| error!(?e, ?entity, "Failed to delete destroyed entity"));
@@ -459,18 +922,129 @@ pub fn handle_land_on_ground(server: &Server, entity: EcsEntity, vel: Vec3<f32>)
             let mut damage = Damage {
                 healthchange: -falldmg,
                 source: DamageSource::Falling,
+                kind: None,
+                crit: None,
+                penetration: 0.0,
+                true_damage: false,
             };
-            if let Some(loadout) = state.ecs().read_storage::<comp::Loadout>().get(entity) {
-                damage.modify_damage(false, loadout);
-            }
-            stats.health.change_by(comp::HealthChange {
+            let mitigated = damage.modify_damage(
+                false,
+                state.ecs().read_storage::<comp::Loadout>().get(entity),
+                None,
+            );
+            let change = comp::HealthChange {
                 amount: damage.healthchange as i32,
                 cause: comp::HealthSource::World,
+                crit: false,
+                mitigated,
+                overkill: 0,
+            };
+            stats.health.change_by(change);
+            if let Some(uid) = state.ecs().read_storage::<Uid>().get(entity) {
+                log_health_change(state.ecs(), *uid, change);
+            }
+        }
+    }
+}
+
+/// Splashes water about where something plunged into it
+pub fn handle_splash(server: &Server, pos: Vec3<f32>, speed: f32) {
+    server
+        .state
+        .ecs()
+        .write_resource::<Vec<Outcome>>()
+        .push(Outcome::Splash { pos, speed });
+}
+
+pub fn handle_heavy_hit(
+    server: &Server,
+    pos: Vec3<f32>,
+    attacker: Uid,
+    target: Uid,
+    magnitude: f32,
+) {
+    server
+        .state
+        .ecs()
+        .write_resource::<Vec<Outcome>>()
+        .push(Outcome::HeavyHit {
+            pos,
+            attacker,
+            target,
+            magnitude,
+        });
+}
+
+/// Lights a fire on top of the block at `pos` if it can burn and there is room
+/// above it. The fire goes out after a while.
+pub fn handle_ignite(server: &Server, pos: Vec3<i32>) {
+    let ecs = server.state.ecs();
+    let terrain = ecs.read_resource::<TerrainGrid>();
+    let above = pos + Vec3::unit_z();
+    if terrain.get(pos).map_or(false, |block| block.is_flammable())
+        && terrain
+            .get(above)
+            .map_or(false, |block| *block == Block::air(SpriteKind::Empty))
+    {
+        if ecs
+            .write_resource::<BlockChange>()
+            .try_set(above, Block::air(SpriteKind::Ember))
+            .is_some()
+        {
+            ecs.write_resource::<Embers>()
+                .lit(above, ecs.read_resource::<Time>().0);
+        }
+    }
+}
+
+/// Damages the destructible sprite at `pos`, breaking it (and maybe dropping
+/// some loot) once its health runs out
+pub fn handle_damage_sprite(server: &mut Server, pos: Vec3<i32>, damage: u32) {
+    let state = &mut server.state;
+    let block = match state.get_block(pos) {
+        Some(block) if block.is_destructible() && state.can_set_block(pos) => block,
+        _ => return,
+    };
+
+    if let Some(damaged) = block.with_sprite_damage(damage) {
+        state.set_block(pos, damaged);
+        return;
+    }
+
+    state.set_block(pos, block.into_vacant());
+    if let Some(sprite) = block.get_sprite() {
+        state
+            .ecs()
+            .write_resource::<Vec<Outcome>>()
+            .push(Outcome::SpriteBreak {
+                pos: pos.map(|e| e as f32 + 0.5),
+                sprite,
             });
+    }
+
+    if thread_rng().gen_bool(SPRITE_LOOT_CHANCE) {
+        if let Some(item) = Item::try_reclaim_from_block(block) {
+            state
+                .create_object(comp::Pos(pos.map(|e| e as f32 + 0.5)), object::Body::Pouch)
+                .with(item)
+                .with(comp::Vel(Vec3::zero()))
+                .build();
         }
     }
 }
 
+/// Turns a projectile that came to rest into an item lying where it landed
+pub fn handle_recover_projectile(server: &Server, entity: EcsEntity, item: String) {
+    let ecs = server.state.ecs();
+    if ecs.read_storage::<comp::Projectile>().get(entity).is_none() {
+        return;
+    }
+    ecs.write_storage::<comp::Projectile>().remove(entity);
+    let _ = ecs
+        .write_storage()
+        .insert(entity, Item::new_from_asset_expect(&item));
+}
+
 pub fn handle_respawn(server: &Server, entity: EcsEntity) {
     let state = &server.state;
 
@@ -491,6 +1065,11 @@ pub fn handle_respawn(server: &Server, entity: EcsEntity) {
             .write_storage::<comp::Stats>()
             .get_mut(entity)
             .map(|stats| stats.revive());
+        // Players that bled out would otherwise still be down
+        let _ = state
+            .ecs()
+            .write_storage()
+            .insert(entity, comp::CharacterState::Idle);
         state
             .ecs()
             .write_storage::<comp::Pos>()
@@ -542,14 +1121,36 @@ pub fn handle_explosion(
             .retrieve_entity_internal(uid.into())
     });
     let groups = ecs.read_storage::<comp::Group>();
+    let players = ecs.read_storage::<Player>();
+    let uids = ecs.read_storage::<Uid>();
+    let combat_rules = ecs.read_resource::<CombatRules>();
+    let invulnerables = ecs.read_storage::<comp::Invulnerable>();
+    let instances = ecs.read_storage::<comp::Instance>();
+    let time = ecs.read_resource::<Time>().0;
+    // Explosions only hit entities in the same dungeon instance as whoever set them off,
+    // while ownerless ones are shared by every instance, like the terrain they go off in
+    let owner_instance = owner_entity.map(|entity| instances.get(entity).copied());
+    let owner_combatant = owner_entity.zip(owner).and_then(|(entity, uid)| {
+        let pos = ecs.read_storage::<comp::Pos>().get(entity)?.0;
+        Some(Combatant {
+            // Friendly damage ignores group immunity
+            group: if friendly_damage {
+                None
+            } else {
+                groups.get(entity).copied()
+            },
+            ..Combatant::new(entity, uid, pos, &players, &groups)
+        })
+    });
 
-    for (entity_b, pos_b, ori_b, character_b, stats_b, loadout_b) in (
+    for (entity_b, pos_b, ori_b, character_b, stats_b, loadout_b, body_b) in (
         &ecs.entities(),
         &ecs.read_storage::<comp::Pos>(),
         &ecs.read_storage::<comp::Ori>(),
         ecs.read_storage::<comp::CharacterState>().maybe(),
         &mut ecs.write_storage::<comp::Stats>(),
         ecs.read_storage::<comp::Loadout>().maybe(),
+        ecs.read_storage::<comp::Body>().maybe(),
     )
         .join()
     {
@@ -558,6 +1159,7 @@ pub fn handle_explosion(
         if !stats_b.is_dead
             // RADIUS
             && distance_squared < explosion.radius.powi(2)
+            && owner_instance.map_or(true, |instance| instances.get(entity_b).copied() == instance)
         {
             // See if entities are in the same group
             let mut same_group = owner_entity
@@ -569,8 +1171,19 @@ pub fn handle_explosion(
                 }
             }
             // Don't heal if outside group
-            // Don't damage in the same group
-            let is_damage = (friendly_damage || !same_group) && explosion.max_damage > 0;
+            // Don't damage in the same group, those the combat rules protect, or those that
+            // are invulnerable
+            let protected = match (owner_combatant, uids.get(entity_b)) {
+                (Some(owner), Some(uid_b)) => !combat_rules.can_harm(
+                    owner,
+                    Combatant::new(entity_b, *uid_b, pos_b.0, &players, &groups),
+                ),
+                _ => false,
+            } || invulnerables
+                .get(entity_b)
+                .map_or(false, |i| i.is_active(time));
+            let is_damage =
+                (friendly_damage || !same_group) && explosion.max_damage > 0 && !protected;
             let is_heal = same_group && explosion.max_heal > 0 && !friendly_damage;
             if !is_heal && !is_damage {
                 continue;
@@ -594,14 +1207,16 @@ pub fn handle_explosion(
             let mut damage = Damage {
                 healthchange,
                 source,
+                kind: Some(DamageKind::Fire),
+                crit: None,
+                penetration: 0.0,
+                true_damage: false,
             };
 
             let block = character_b.map(|c_b| c_b.is_block()).unwrap_or(false)
                 && ori_b.0.angle_between(pos - pos_b.0) < BLOCK_ANGLE.to_radians() / 2.0;
 
-            if let Some(loadout) = loadout_b {
-                damage.modify_damage(block, loadout);
-            }
+            let mitigated = damage.modify_damage(block, loadout_b, body_b);
 
             if damage.healthchange != 0.0 {
                 let cause = if is_heal {
@@ -609,10 +1224,17 @@ pub fn handle_explosion(
                 } else {
                     HealthSource::Explosion { owner }
                 };
-                stats_b.health.change_by(HealthChange {
+                let change = HealthChange {
                     amount: damage.healthchange as i32,
                     cause,
-                });
+                    crit: false,
+                    mitigated,
+                    overkill: 0,
+                };
+                stats_b.health.change_by(change);
+                if let Some(uid_b) = uids.get(entity_b) {
+                    log_health_change(ecs, *uid_b, change);
+                }
                 if let Some(owner) = owner_entity {
                     if let Some(energy) = ecs.write_storage::<comp::Energy>().get_mut(owner) {
                         energy
@@ -706,7 +1328,20 @@ pub fn handle_buff(server: &mut Server, entity: EcsEntity, buff_change: buff::Bu
         use buff::BuffChange;
         match buff_change {
             BuffChange::Add(new_buff) => {
-                buffs.insert(new_buff);
+                if !buffs.is_immune(new_buff.kind) {
+                    if let Some(uid) = ecs.read_storage::<Uid>().get(entity) {
+                        log_combat_event(ecs, comp::CombatEvent {
+                            time: ecs.read_resource::<Time>().0,
+                            attacker: match new_buff.source {
+                                buff::BuffSource::Character { by } => Some(by),
+                                _ => None,
+                            },
+                            target: *uid,
+                            kind: comp::CombatEventKind::Buff(new_buff.kind),
+                        });
+                    }
+                    buffs.insert(new_buff);
+                }
             },
             BuffChange::RemoveById(ids) => {
                 for id in ids {
@@ -757,6 +1392,13 @@ pub fn handle_buff(server: &mut Server, entity: EcsEntity, buff_change: buff::Bu
                     buffs.remove(id);
                 }
             },
+            BuffChange::Dispel {
+                categories,
+                count,
+                immunity,
+            } => {
+                buffs.dispel(&categories, count, immunity);
+            },
         }
     }
 }
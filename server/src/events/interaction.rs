@@ -1,14 +1,22 @@
 use crate::{
     client::{Client, RegionSubscription},
+    state_ext::StateExt,
     Server,
 };
 use common::{
-    comp::{self, item},
+    assets::Asset,
+    combat::{CombatRules, DUEL_REQUEST_TIMEOUT},
+    comp::{self, item, ChatType},
+    lottery::Lottery,
     msg::ServerGeneral,
+    outcome::Outcome,
+    state::Time,
     sync::{Uid, WorldSyncExt},
 };
-use specs::{world::WorldExt, Entity as EcsEntity};
+use specs::{world::WorldExt, Builder, Component, Entity as EcsEntity};
+use specs_idvs::IdvStorage;
 use tracing::error;
+use vek::*;
 
 pub fn handle_lantern(server: &mut Server, entity: EcsEntity, enable: bool) {
     let ecs = server.state_mut().ecs();
@@ -193,3 +201,149 @@ pub fn handle_possess(server: &Server, possessor_uid: Uid, possesse_uid: Uid) {
         }
     }
 }
+
+/// Gives a player who reeled in a fish one from the waters they are fishing in,
+/// dropping it at their feet if it doesn't fit in their inventory
+pub fn handle_catch_fish(server: &mut Server, entity: EcsEntity) {
+    let pos = match server.state.read_component_copied::<comp::Pos>(entity) {
+        Some(pos) => pos,
+        None => return,
+    };
+    let fish_table = server
+        .world
+        .fish_table(pos.0.xy().map(|e| e.floor() as i32));
+    let fish =
+        item::Item::new_from_asset_expect(Lottery::<String>::load_expect(fish_table).choose());
+
+    let leftover = server
+        .state
+        .ecs()
+        .write_storage::<comp::Inventory>()
+        .get_mut(entity)
+        .and_then(|inventory| inventory.push(fish.clone()));
+    match leftover {
+        None => server.state.write_component(
+            entity,
+            comp::InventoryUpdate::new(comp::InventoryUpdateEvent::Collected(fish)),
+        ),
+        Some(fish) => {
+            server
+                .state
+                .create_object(Default::default(), comp::object::Body::Pouch)
+                .with(comp::Pos(pos.0 + Vec3::unit_z() * 0.25))
+                .with(fish)
+                .with(comp::Vel(Vec3::zero()))
+                .build();
+        },
+    }
+}
+
+/// Least time in seconds between two notes of the same performer that other
+/// players get to hear
+const MIN_NOTE_INTERVAL: f64 = 0.1;
+
+/// When a performer last played a note that other players heard
+pub struct LastNote(pub f64);
+
+impl Component for LastNote {
+    type Storage = IdvStorage<Self>;
+}
+
+/// Lets nearby players hear a note played by a performer, stopping the
+/// performance instead if they don't carry the instrument they play. Notes
+/// played too quickly after the last one are dropped, so that performers can't
+/// flood the players around them with sounds.
+pub fn handle_play_note(
+    server: &mut Server,
+    entity: EcsEntity,
+    instrument: item::Instrument,
+    note: u8,
+) {
+    let state = server.state_mut();
+    let carries_instrument = state
+        .read_storage::<comp::Inventory>()
+        .get(entity)
+        .map_or(false, |inventory| {
+            inventory.slots().iter().flatten().any(|item| {
+                matches!(item.kind(), item::ItemKind::Instrument { kind } if *kind == instrument)
+            })
+            });
+    if !carries_instrument {
+        state.write_component(entity, comp::CharacterState::Idle);
+        return;
+    }
+
+    let time = state.ecs().read_resource::<Time>().0;
+    let mut last_notes = state.ecs().write_storage::<LastNote>();
+    if last_notes
+        .get(entity)
+        .map_or(false, |last| time - last.0 < MIN_NOTE_INTERVAL)
+    {
+        return;
+    }
+    let _ = last_notes.insert(entity, LastNote(time));
+    drop(last_notes);
+
+    if let Some(pos) = state.read_component_copied::<comp::Pos>(entity) {
+        state
+            .ecs()
+            .write_resource::<Vec<Outcome>>()
+            .push(Outcome::Note {
+                pos: pos.0,
+                instrument,
+                note,
+            });
+    }
+}
+
+/// Starts a duel once both players have asked for it, letting them hurt each
+/// other regardless of the server's PvP rules until one of them dies
+pub fn handle_duel_request(server: &Server, challenger: EcsEntity, target: EcsEntity) {
+    let ecs = server.state.ecs();
+    let (challenger_uid, target_uid, challenger_alias, target_alias) = {
+        let uids = ecs.read_storage::<Uid>();
+        let players = ecs.read_storage::<comp::Player>();
+        match (
+            uids.get(challenger),
+            uids.get(target),
+            players.get(challenger),
+            players.get(target),
+        ) {
+            (Some(challenger_uid), Some(target_uid), Some(challenger), Some(target)) => (
+                *challenger_uid,
+                *target_uid,
+                challenger.alias.clone(),
+                target.alias.clone(),
+            ),
+            _ => return,
+        }
+    };
+
+    let started = ecs.write_resource::<CombatRules>().duels.request(
+        challenger_uid,
+        target_uid,
+        ecs.read_resource::<Time>().0,
+    );
+    if started {
+        server.notify_client(
+            challenger,
+            ChatType::Meta.server_msg(format!("Your duel with {} has begun!", target_alias)),
+        );
+        server.notify_client(
+            target,
+            ChatType::Meta.server_msg(format!("Your duel with {} has begun!", challenger_alias)),
+        );
+    } else {
+        server.notify_client(
+            challenger,
+            ChatType::Meta.server_msg(format!("You challenged {} to a duel.", target_alias)),
+        );
+        server.notify_client(
+            target,
+            ChatType::Meta.server_msg(format!(
+                "{} challenged you to a duel, use /duel {} within {} seconds to accept.",
+                challenger_alias, challenger_alias, DUEL_REQUEST_TIMEOUT
+            )),
+        );
+    }
+}
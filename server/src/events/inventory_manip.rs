@@ -1,18 +1,27 @@
-use crate::{client::Client, Server, StateExt};
+use crate::{
+    cart,
+    client::Client,
+    husbandry::{self, Growth},
+    Server, StateExt,
+};
 use common::{
     comp::{
-        self, item,
+        self,
+        item::{self, ToolCategory},
         slot::{self, Slot},
         Pos, MAX_PICKUP_RANGE_SQR,
     },
     msg::ServerGeneral,
     recipe::default_recipe_book,
+    states::throw,
     sync::{Uid, WorldSyncExt},
+    util::Dir,
     vol::ReadVol,
 };
 use comp::LightEmitter;
 use rand::Rng;
 use specs::{join::Join, world::WorldExt, Builder, Entity as EcsEntity, WriteStorage};
+use std::time::Duration;
 use tracing::{debug, error};
 use vek::{Rgb, Vec3};
 
@@ -37,6 +46,11 @@ pub fn handle_inventory(server: &mut Server, entity: EcsEntity, manip: comp::Inv
     let state = server.state_mut();
     let mut dropped_items = Vec::new();
     let mut thrown_items = Vec::new();
+    let mut placed_carts = Vec::new();
+    let may_change_loadout = matches!(
+        manip,
+        comp::InventoryManip::Use(_) | comp::InventoryManip::Swap(..)
+    );
 
     match manip {
         comp::InventoryManip::Pickup(uid) => {
@@ -151,6 +165,8 @@ pub fn handle_inventory(server: &mut Server, entity: EcsEntity, manip: comp::Inv
             }
         },
 
+        comp::InventoryManip::Unlock(pos) => return handle_unlock(server, entity, pos),
+
         comp::InventoryManip::Use(slot) => {
             let mut inventories = state.ecs().write_storage::<comp::Inventory>();
             let inventory = if let Some(inventory) = inventories.get_mut(entity) {
@@ -290,6 +306,18 @@ pub fn handle_inventory(server: &mut Server, entity: EcsEntity, manip: comp::Inv
                                             .ecs()
                                             .write_storage()
                                             .insert(tameable_entity, comp::Agent::default());
+
+                                        // Wild animals are always tamed as adults
+                                        if state
+                                            .read_storage::<comp::Body>()
+                                            .get(tameable_entity)
+                                            .map_or(false, husbandry::grows)
+                                        {
+                                            let _ = state
+                                                .ecs()
+                                                .write_storage()
+                                                .insert(tameable_entity, Growth::default());
+                                        }
                                         false
                                     } else {
                                         true
@@ -304,6 +332,27 @@ pub fn handle_inventory(server: &mut Server, entity: EcsEntity, manip: comp::Inv
 
                                 Some(comp::InventoryUpdateEvent::Used)
                             },
+                            ItemKind::Utility {
+                                kind: comp::item::Utility::Cart,
+                                ..
+                            } => {
+                                if let (Some(pos), Some(uid)) = (
+                                    state.read_component_copied::<comp::Pos>(entity),
+                                    state.read_component_copied::<Uid>(entity),
+                                ) {
+                                    placed_carts.push((
+                                        pos,
+                                        state
+                                            .read_component_copied::<comp::Ori>(entity)
+                                            .unwrap_or_default(),
+                                        uid,
+                                    ));
+                                    Some(comp::InventoryUpdateEvent::Used)
+                                } else {
+                                    let _ = inventory.insert_or_stack(slot, item);
+                                    None
+                                }
+                            },
                             _ => {
                                 inventory.insert_or_stack(slot, item).unwrap();
                                 None
@@ -388,6 +437,69 @@ pub fn handle_inventory(server: &mut Server, entity: EcsEntity, manip: comp::Inv
             );
         },
 
+        comp::InventoryManip::Dye(dye_slot, equip_slot) => {
+            let ecs = state.ecs();
+            let mut inventories = ecs.write_storage();
+            let mut loadouts = ecs.write_storage();
+            let dyed = match (inventories.get_mut(entity), loadouts.get_mut(entity)) {
+                (Some(inventory), Some(loadout)) => {
+                    slot::dye(dye_slot, equip_slot, inventory, loadout)
+                },
+                _ => false,
+            };
+            drop(loadouts);
+            drop(inventories);
+
+            if dyed {
+                state.write_component(
+                    entity,
+                    comp::InventoryUpdate::new(comp::InventoryUpdateEvent::Used),
+                );
+            }
+        },
+
+        comp::InventoryManip::SetAppearance(inv_slot, equip_slot) => {
+            let ecs = state.ecs();
+            let mut inventories = ecs.write_storage();
+            let mut loadouts = ecs.write_storage();
+            let changed = match (inventories.get_mut(entity), loadouts.get_mut(entity)) {
+                (Some(inventory), Some(loadout)) => {
+                    slot::set_appearance(inv_slot, equip_slot, inventory, loadout)
+                },
+                _ => false,
+            };
+            drop(loadouts);
+            drop(inventories);
+
+            if changed {
+                state.write_component(
+                    entity,
+                    comp::InventoryUpdate::new(comp::InventoryUpdateEvent::Used),
+                );
+            }
+        },
+
+        comp::InventoryManip::ClearAppearance(equip_slot) => {
+            let ecs = state.ecs();
+            let mut inventories = ecs.write_storage();
+            let mut loadouts = ecs.write_storage();
+            let changed = match (inventories.get_mut(entity), loadouts.get_mut(entity)) {
+                (Some(inventory), Some(loadout)) => {
+                    slot::clear_appearance(equip_slot, inventory, loadout)
+                },
+                _ => false,
+            };
+            drop(loadouts);
+            drop(inventories);
+
+            if changed {
+                state.write_component(
+                    entity,
+                    comp::InventoryUpdate::new(comp::InventoryUpdateEvent::Used),
+                );
+            }
+        },
+
         comp::InventoryManip::CraftRecipe(recipe) => {
             if let Some(inv) = state
                 .ecs()
@@ -423,6 +535,12 @@ pub fn handle_inventory(server: &mut Server, entity: EcsEntity, manip: comp::Inv
         },
     }
 
+    // Equipping a weapon gives it its default abilities, so the chosen ones have to
+    // be applied again
+    if may_change_loadout {
+        apply_ability_selection(state.ecs(), entity);
+    }
+
     // Drop items
     for (pos, ori, item) in dropped_items {
         let vel = *ori.0 * 5.0
@@ -437,6 +555,12 @@ pub fn handle_inventory(server: &mut Server, entity: EcsEntity, manip: comp::Inv
             .build();
     }
 
+    // Place carts in front of whoever used them
+    for (pos, ori, owner) in placed_carts {
+        let pos = comp::Pos(pos.0 + *ori.0 * 2.0 + Vec3::unit_z() * 0.25);
+        cart::create_cart(state, pos, ori, owner);
+    }
+
     let mut rng = rand::thread_rng();
 
     // Throw items
@@ -458,17 +582,7 @@ pub fn handle_inventory(server: &mut Server, entity: EcsEntity, manip: comp::Inv
         let uid = state.read_component_copied::<Uid>(entity);
 
         let mut new_entity = state
-            .create_object(Default::default(), match kind {
-                item::Throwable::Bomb => comp::object::Body::Bomb,
-                item::Throwable::Firework(reagent) => match reagent {
-                    item::Reagent::Blue => comp::object::Body::FireworkBlue,
-                    item::Reagent::Green => comp::object::Body::FireworkGreen,
-                    item::Reagent::Purple => comp::object::Body::FireworkPurple,
-                    item::Reagent::Red => comp::object::Body::FireworkRed,
-                    item::Reagent::Yellow => comp::object::Body::FireworkYellow,
-                },
-                item::Throwable::TrainingDummy => comp::object::Body::TrainingDummy,
-            })
+            .create_object(Default::default(), throwable_body(kind))
             .with(comp::Pos(pos.0 + Vec3::unit_z() * 0.25))
             .with(comp::Vel(vel));
 
@@ -495,12 +609,211 @@ pub fn handle_inventory(server: &mut Server, entity: EcsEntity, manip: comp::Inv
                     comp::object::Body::TrainingDummy.into(),
                 ));
             },
+            item::Throwable::Potion => {},
         };
 
         new_entity.build();
     }
 }
 
+/// Throws one item out of `slot` as a projectile carrying the item's impact
+/// effects. The item is taken from the inventory in the same step the
+/// projectile is created, so a thrown item can't be duplicated or lost.
+pub fn handle_throw(server: &mut Server, entity: EcsEntity, slot: usize, dir: Dir) {
+    let state = server.state_mut();
+
+    let (kind, on_impact) = {
+        let mut inventories = state.ecs().write_storage::<comp::Inventory>();
+        let inventory = if let Some(inventory) = inventories.get_mut(entity) {
+            inventory
+        } else {
+            error!(?entity, "Can't throw, entity doesn't have an inventory");
+            return;
+        };
+
+        let throwable = inventory.get(slot).and_then(|item| match item.kind() {
+            item::ItemKind::Throwable { kind, on_impact } if !on_impact.is_empty() => {
+                Some((*kind, on_impact.clone()))
+            },
+            _ => None,
+        });
+
+        match throwable {
+            Some(throwable) if inventory.take(slot).is_some() => throwable,
+            _ => {
+                debug!(?entity, ?slot, "Nothing throwable left in slot");
+                return;
+            },
+        }
+    };
+
+    state.write_component(
+        entity,
+        comp::InventoryUpdate::new(comp::InventoryUpdateEvent::Used),
+    );
+
+    let pos = match state.read_component_copied::<comp::Pos>(entity) {
+        Some(pos) => pos,
+        None => return,
+    };
+    let vel = state
+        .read_component_copied::<comp::Vel>(entity)
+        .unwrap_or_default();
+    let owner = state.read_component_copied::<Uid>(entity);
+    let instance = state.read_component_copied::<comp::Instance>(entity);
+
+    let mut builder = state
+        .create_projectile(
+            comp::Pos(throw::release_pos(pos.0)),
+            comp::Vel(vel.0 + *dir * throw::THROW_SPEED),
+            throwable_body(kind).into(),
+            comp::Projectile {
+                hit_solid: on_impact.clone(),
+                hit_entity: on_impact,
+                time_left: Duration::from_secs(10),
+                owner,
+                ignore_group: true,
+                drag: comp::projectile::DEFAULT_DRAG,
+                penetration: 0,
+                hit_entities: Vec::new(),
+            },
+        )
+        .with(comp::Gravity(throw::THROW_GRAVITY));
+
+    if let Some(instance) = instance {
+        builder = builder.with(instance)
+    }
+
+    builder.build();
+}
+
+fn throwable_body(kind: item::Throwable) -> comp::object::Body {
+    match kind {
+        item::Throwable::Bomb => comp::object::Body::Bomb,
+        item::Throwable::Firework(reagent) => match reagent {
+            item::Reagent::Blue => comp::object::Body::FireworkBlue,
+            item::Reagent::Green => comp::object::Body::FireworkGreen,
+            item::Reagent::Purple => comp::object::Body::FireworkPurple,
+            item::Reagent::Red => comp::object::Body::FireworkRed,
+            item::Reagent::Yellow => comp::object::Body::FireworkYellow,
+        },
+        item::Throwable::TrainingDummy => comp::object::Body::TrainingDummy,
+        item::Throwable::Potion => comp::object::Body::PotionRed,
+    }
+}
+
+/// Most blocks of a single gate that are opened by one key
+const MAX_GATE_BLOCKS: usize = 512;
+
+/// Opens the locked gate that the block at `pos` belongs to, if the entity is
+/// close enough and carries the right key. The key is consumed unless the
+/// dungeon's keys are reusable.
+fn handle_unlock(server: &mut Server, entity: EcsEntity, pos: Vec3<i32>) {
+    let key_item = match server
+        .state
+        .terrain()
+        .get(pos)
+        .ok()
+        .and_then(|block| block.get_sprite())
+        .and_then(|sprite| sprite.key_item())
+    {
+        Some(key_item) => key_item,
+        None => {
+            debug!(?pos, "Can't unlock block: block is not locked");
+            return;
+        },
+    };
+    if !within_pickup_range(
+        server.state.ecs().read_storage::<Pos>().get(entity),
+        Some(&Pos(pos.map(|e| e as f32 + 0.5))),
+    ) {
+        debug!(?pos, "Can't unlock block: not within range");
+        return;
+    }
+    if !server.state.can_set_block(pos) {
+        debug!(?pos, "Can't unlock block: block was already set this tick");
+        return;
+    }
+
+    // Find all the locked blocks making up the gate
+    let gate = {
+        let terrain = server.state.terrain();
+        let mut gate = vec![pos];
+        let mut i = 0;
+        while i < gate.len() && gate.len() < MAX_GATE_BLOCKS {
+            let current = gate[i];
+            i += 1;
+            for dir in &[
+                Vec3::unit_x(),
+                -Vec3::unit_x(),
+                Vec3::unit_y(),
+                -Vec3::unit_y(),
+                Vec3::unit_z(),
+                -Vec3::unit_z(),
+            ] {
+                let neighbor = current + dir;
+                if !gate.contains(&neighbor)
+                    && terrain
+                        .get(neighbor)
+                        .map_or(false, |block| block.is_locked())
+                {
+                    gate.push(neighbor);
+                }
+            }
+        }
+        gate
+    };
+
+    let reusable = server
+        .world
+        .dungeon_at(server.index.as_index_ref(), pos)
+        .map_or(false, |(site, _)| {
+            server
+                .world
+                .dungeon_reusable_keys(server.index.as_index_ref(), site)
+        });
+
+    // Check for the key and use it up
+    let key_used = {
+        let mut inventories = server.state.ecs().write_storage::<comp::Inventory>();
+        inventories.get_mut(entity).and_then(|inventory| {
+            let slot = inventory.slots().iter().position(|slot| {
+                slot.as_ref()
+                    .map_or(false, |item| item.item_definition_id() == key_item)
+            })?;
+            if reusable {
+                Some(false)
+            } else {
+                inventory.take(slot).map(|_| true)
+            }
+        })
+    };
+    match key_used {
+        Some(consumed) => {
+            if consumed {
+                server.state.write_component(
+                    entity,
+                    comp::InventoryUpdate::new(comp::InventoryUpdateEvent::Used),
+                );
+            }
+        },
+        None => {
+            server.notify_client(
+                entity,
+                comp::ChatType::Meta.server_msg("You need a key to open this gate.".to_owned()),
+            );
+            return;
+        },
+    }
+
+    for pos in gate {
+        let block = server.state.terrain().get(pos).ok().copied();
+        if let Some(block) = block {
+            server.state.set_block(pos, block.into_vacant());
+        }
+    }
+}
+
 fn within_pickup_range(player_position: Option<&Pos>, item_position: Option<&Pos>) -> bool {
     match (player_position, item_position) {
         (Some(ppos), Some(ipos)) => ppos.0.distance_squared(ipos.0) < MAX_PICKUP_RANGE_SQR,
@@ -508,6 +821,66 @@ fn within_pickup_range(player_position: Option<&Pos>, item_position: Option<&Pos
     }
 }
 
+fn apply_ability_selection(ecs: &specs::World, entity: EcsEntity) {
+    if let (Some(selection), Some(loadout)) = (
+        ecs.read_storage::<comp::AbilitySelection>().get(entity),
+        ecs.write_storage::<comp::Loadout>().get_mut(entity),
+    ) {
+        loadout.apply_ability_selection(selection);
+    }
+}
+
+/// Chooses an alternative ability for a weapon the player is currently wielding
+pub fn handle_select_ability(
+    server: &mut Server,
+    entity: EcsEntity,
+    tool: ToolCategory,
+    slot: usize,
+    index: usize,
+) {
+    let ecs = server.state.ecs();
+    let option_count = ecs
+        .read_storage::<comp::Loadout>()
+        .get(entity)
+        .and_then(|loadout| {
+            loadout
+                .active_item
+                .iter()
+                .chain(loadout.second_item.iter())
+                .find_map(|item| match item.item.kind() {
+                    item::ItemKind::Tool(t) if ToolCategory::from(&t.kind) == tool => t
+                        .get_ability_options()
+                        .get(slot)
+                        .map(|options| options.len()),
+                    _ => None,
+                })
+        })
+        .unwrap_or(0);
+    if index >= option_count {
+        debug!(
+            ?entity,
+            ?tool,
+            ?slot,
+            ?index,
+            "Tried to select an unavailable ability"
+        );
+        return;
+    }
+
+    let selected = ecs
+        .write_storage::<comp::AbilitySelection>()
+        .entry(entity)
+        .map(|entry| {
+            entry
+                .or_insert_with(comp::AbilitySelection::default)
+                .select(tool, slot, index)
+        })
+        .unwrap_or(false);
+    if selected {
+        apply_ability_selection(ecs, entity);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,19 +1,35 @@
-use crate::{state_ext::StateExt, Server};
+use crate::{
+    bounty::handle_read_bounty_board,
+    chat_filter::filter_chat,
+    husbandry::handle_breed,
+    market::{handle_talk, handle_trade},
+    state_ext::StateExt,
+    summon::handle_summon,
+    Server,
+};
 use common::{
     event::{EventBus, ServerEvent},
     span,
 };
+pub(crate) use entity_creation::handle_create_npc;
 use entity_creation::{
-    handle_beam, handle_create_npc, handle_create_waypoint, handle_initialize_character,
-    handle_loaded_character_data, handle_shockwave, handle_shoot,
+    handle_beam, handle_create_waypoint, handle_initialize_character, handle_loaded_character_data,
+    handle_shockwave, handle_shoot,
 };
+pub(crate) use entity_manipulation::apply_damage;
 use entity_manipulation::{
-    handle_buff, handle_damage, handle_destroy, handle_explosion, handle_knockback,
-    handle_land_on_ground, handle_level_up, handle_respawn,
+    handle_buff, handle_damage, handle_damage_sprite, handle_destroy, handle_downed,
+    handle_explosion, handle_heavy_hit, handle_ignite, handle_knockback, handle_land_on_ground,
+    handle_level_up, handle_on_hit, handle_recover_projectile, handle_respawn, handle_revive,
+    handle_splash,
 };
 use group_manip::handle_group;
-use interaction::{handle_lantern, handle_mount, handle_possess, handle_unmount};
-use inventory_manip::handle_inventory;
+pub(crate) use interaction::LastNote;
+use interaction::{
+    handle_catch_fish, handle_duel_request, handle_lantern, handle_mount, handle_play_note,
+    handle_possess, handle_unmount,
+};
+use inventory_manip::{handle_inventory, handle_select_ability, handle_throw};
 use player::{handle_client_disconnect, handle_exit_ingame};
 use specs::{Entity as EcsEntity, WorldExt};
 
@@ -70,6 +86,7 @@ impl Server {
                     gravity,
                     speed,
                 } => handle_shoot(self, entity, dir, body, light, projectile, gravity, speed),
+                ServerEvent::Throw { entity, slot, dir } => handle_throw(self, entity, slot, dir),
                 ServerEvent::Shockwave {
                     properties,
                     pos,
@@ -85,12 +102,49 @@ impl Server {
                 },
                 ServerEvent::Damage { uid, change } => handle_damage(&self, uid, change),
                 ServerEvent::Destroy { entity, cause } => handle_destroy(self, entity, cause),
+                ServerEvent::Downed { entity, cause } => handle_downed(self, entity, cause),
+                ServerEvent::Revive { target, by } => handle_revive(&self, target, by),
                 ServerEvent::InventoryManip(entity, manip) => handle_inventory(self, entity, manip),
                 ServerEvent::GroupManip(entity, manip) => handle_group(self, entity, manip),
                 ServerEvent::Respawn(entity) => handle_respawn(&self, entity),
+                ServerEvent::ReadBountyBoard(entity, pos) => {
+                    handle_read_bounty_board(self, entity, pos)
+                },
+                ServerEvent::Talk(entity, npc) => handle_talk(self, entity, npc),
+                ServerEvent::Trade {
+                    entity,
+                    merchant,
+                    good,
+                    action,
+                } => handle_trade(self, entity, merchant, good, action),
+                ServerEvent::Breed(parent, mate) => handle_breed(self, parent, mate),
+                ServerEvent::CatchFish(entity) => handle_catch_fish(self, entity),
+                ServerEvent::PlayNote {
+                    entity,
+                    instrument,
+                    note,
+                } => handle_play_note(self, entity, instrument, note),
+                ServerEvent::SelectAbility {
+                    entity,
+                    tool,
+                    slot,
+                    index,
+                } => handle_select_ability(self, entity, tool, slot, index),
+                ServerEvent::Ignite(pos) => handle_ignite(&self, pos),
+                ServerEvent::DamageSprite(pos, damage) => handle_damage_sprite(self, pos, damage),
+                ServerEvent::RecoverProjectile { entity, item } => {
+                    handle_recover_projectile(&self, entity, item)
+                },
                 ServerEvent::LandOnGround { entity, vel } => {
                     handle_land_on_ground(&self, entity, vel)
                 },
+                ServerEvent::Splash { pos, speed } => handle_splash(&self, pos, speed),
+                ServerEvent::HeavyHit {
+                    pos,
+                    attacker,
+                    target,
+                    magnitude,
+                } => handle_heavy_hit(&self, pos, attacker, target, magnitude),
                 ServerEvent::EnableLantern(entity) => handle_lantern(self, entity, true),
                 ServerEvent::DisableLantern(entity) => handle_lantern(self, entity, false),
                 ServerEvent::Mount(mounter, mountee) => handle_mount(self, mounter, mountee),
@@ -116,9 +170,15 @@ impl Server {
                     alignment,
                     scale,
                     drop_item,
-                } => handle_create_npc(
-                    self, pos, stats, loadout, body, agent, alignment, scale, drop_item,
-                ),
+                    boss,
+                    market,
+                    bounty,
+                } => {
+                    handle_create_npc(
+                        self, pos, stats, loadout, body, agent, alignment, scale, drop_item, boss,
+                        market, bounty,
+                    );
+                },
                 ServerEvent::CreateWaypoint(pos) => handle_create_waypoint(self, pos),
                 ServerEvent::ClientDisconnect(entity) => {
                     frontend_events.push(handle_client_disconnect(self, entity))
@@ -137,6 +197,22 @@ impl Server {
                     entity,
                     buff_change,
                 } => handle_buff(self, entity, buff_change),
+                ServerEvent::DuelRequest { challenger, target } => {
+                    handle_duel_request(&self, challenger, target)
+                },
+                ServerEvent::OnHit {
+                    attacker,
+                    target,
+                    damage,
+                    effects,
+                } => handle_on_hit(self, attacker, target, damage, effects),
+                ServerEvent::Summon {
+                    summoner,
+                    body,
+                    count,
+                    duration,
+                    max_minions,
+                } => handle_summon(self, summoner, body, count, duration, max_minions),
             }
         }
 
@@ -150,7 +226,9 @@ impl Server {
         }
 
         for msg in chat_messages {
-            self.state.send_chat(msg);
+            if let Some(msg) = filter_chat(self, msg) {
+                self.state.send_chat(msg);
+            }
         }
 
         frontend_events
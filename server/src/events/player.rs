@@ -1,6 +1,7 @@
 use super::Event;
 use crate::{
-    client::Client, login_provider::LoginProvider, persistence, state_ext::StateExt, Server,
+    client::Client, login_provider::LoginProvider, market::Reputation, persistence, stable::Stable,
+    state_ext::StateExt, Server,
 };
 use common::{
     comp,
@@ -151,7 +152,16 @@ pub fn handle_client_disconnect(server: &mut Server, entity: EcsEntity) -> Event
             .read_resource::<persistence::character_updater::CharacterUpdater>(),
     ) {
         if let Some(character_id) = player.character_id {
-            updater.update(character_id, stats, inventory, loadout);
+            updater.update(
+                character_id,
+                stats,
+                inventory,
+                loadout,
+                state.read_storage::<comp::AbilitySelection>().get(entity),
+                state.read_storage::<comp::Buffs>().get(entity),
+                state.read_storage::<Stable>().get(entity),
+                state.read_storage::<Reputation>().get(entity),
+            );
         }
     }
 
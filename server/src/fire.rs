@@ -0,0 +1,51 @@
+//! Fires lit on the terrain by burning entities.
+//!
+//! The embers that spreading fire leaves behind burn for a while and then go
+//! out, so that a fight with fire doesn't leave the ground burning forever.
+//! Embers placed by worldgen, such as those in hearths, aren't tracked and keep
+//! burning.
+
+use crate::Server;
+use common::{
+    state::{BlockChange, Time},
+    terrain::{Block, SpriteKind, TerrainGrid},
+    vol::ReadVol,
+};
+use specs::WorldExt;
+use std::collections::VecDeque;
+use vek::*;
+
+/// Seconds that a lit ember burns for before going out
+const EMBER_LIFETIME: f64 = 20.0;
+
+/// Embers lit on the terrain, in the order in which they go out
+#[derive(Default)]
+pub struct Embers(VecDeque<(Vec3<i32>, f64)>);
+
+impl Embers {
+    /// Keeps track of an ember lit at `pos` at `time`, so that it goes out
+    pub fn lit(&mut self, pos: Vec3<i32>, time: f64) {
+        self.0.push_back((pos, time + EMBER_LIFETIME));
+    }
+}
+
+/// Puts out the embers whose time is up, unless something else has taken their
+/// place
+pub fn update_embers(server: &Server) {
+    let ecs = server.state.ecs();
+    let time = ecs.read_resource::<Time>().0;
+    let terrain = ecs.read_resource::<TerrainGrid>();
+    let mut block_change = ecs.write_resource::<BlockChange>();
+    let mut embers = ecs.write_resource::<Embers>();
+    while embers
+        .0
+        .front()
+        .map_or(false, |(_, expires)| *expires <= time)
+    {
+        if let Some((pos, _)) = embers.0.pop_front() {
+            if terrain.get(pos).ok() == Some(&Block::air(SpriteKind::Ember)) {
+                block_change.try_set(pos, Block::air(SpriteKind::Empty));
+            }
+        }
+    }
+}
@@ -0,0 +1,265 @@
+//! Growth and breeding of tamed and farm animals.
+//!
+//! Animals are born as juveniles that are smaller and weaker than adults and
+//! grow up over real time. Two adults of the same species and of different
+//! sexes that are owned by the same player breed when kept close together,
+//! producing a juvenile whose vigor is blended from that of its parents.
+
+use crate::{client::Client, state_ext::StateExt, Server};
+use common::{
+    comp::{
+        self, bird_medium, quadruped_low, quadruped_medium, quadruped_small, Alignment, Body,
+        ChatType, Pos, Scale, Stats,
+    },
+    loadout_builder::LoadoutBuilder,
+    msg::ServerGeneral,
+    sync::{Uid, WorldSyncExt},
+};
+use rand::Rng;
+use specs::{Builder, Component, Entity as EcsEntity, Join, WorldExt};
+use specs_idvs::IdvStorage;
+use vek::*;
+
+/// Real time in seconds it takes for a newborn animal to become an adult
+pub const GROWTH_TIME: f64 = 2.0 * 60.0 * 60.0;
+/// Real time in seconds an adult has to wait before it can breed again
+pub const BREED_COOLDOWN: f64 = 30.0 * 60.0;
+/// How close two adults have to be to each other to breed
+pub const BREED_RANGE: f32 = 4.0;
+/// Size of a newborn animal relative to an adult
+const NEWBORN_SCALE: f32 = 0.5;
+/// Max hp of a newborn animal relative to an adult
+const NEWBORN_HEALTH: f32 = 0.4;
+/// How much the vigor of offspring may deviate from that of its parents
+const VIGOR_MUTATION: f32 = 0.1;
+const MIN_VIGOR: f32 = 0.5;
+const MAX_VIGOR: f32 = 1.5;
+/// How many pets a player can have following them
+pub const MAX_PETS: usize = 3;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Growth {
+    /// Real time in seconds the animal has been growing for
+    pub age: f64,
+    /// Hereditary multiplier of the animal's max hp
+    pub vigor: f32,
+    /// Real time in seconds until the animal can breed again
+    pub breed_cooldown: f64,
+}
+
+impl Component for Growth {
+    type Storage = IdvStorage<Self>;
+}
+
+impl Default for Growth {
+    fn default() -> Self { Self::adult(1.0) }
+}
+
+impl Growth {
+    pub fn newborn(vigor: f32) -> Self {
+        Self {
+            age: 0.0,
+            vigor,
+            breed_cooldown: 0.0,
+        }
+    }
+
+    pub fn adult(vigor: f32) -> Self {
+        Self {
+            age: GROWTH_TIME,
+            vigor,
+            breed_cooldown: 0.0,
+        }
+    }
+
+    /// Growth of an animal found in the world, which is most likely an adult
+    pub fn random(rng: &mut impl Rng) -> Self {
+        let vigor = rng.gen_range(0.8, 1.2);
+        if rng.gen_bool(0.25) {
+            Self {
+                age: rng.gen_range(0.0, GROWTH_TIME),
+                ..Self::newborn(vigor)
+            }
+        } else {
+            Self::adult(vigor)
+        }
+    }
+
+    /// Growth of the offspring of two animals
+    pub fn offspring(a: &Self, b: &Self, rng: &mut impl Rng) -> Self {
+        let vigor = (a.vigor + b.vigor) / 2.0 + rng.gen_range(-VIGOR_MUTATION, VIGOR_MUTATION);
+        Self::newborn(vigor.max(MIN_VIGOR).min(MAX_VIGOR))
+    }
+
+    pub fn is_adult(&self) -> bool { self.age >= GROWTH_TIME }
+
+    pub fn can_breed(&self) -> bool { self.is_adult() && self.breed_cooldown <= 0.0 }
+
+    /// How far the animal has grown, from 0 when newborn to 1 when adult
+    pub fn maturity(&self) -> f32 { (self.age / GROWTH_TIME).max(0.0).min(1.0) as f32 }
+
+    pub fn scale(&self) -> Scale { Scale(Lerp::lerp(NEWBORN_SCALE, 1.0, self.maturity())) }
+
+    /// Updates the max hp and size of the animal for its current growth
+    pub fn apply(&self, body: Body, stats: &mut Stats) -> Scale {
+        let health_factor = Lerp::lerp(NEWBORN_HEALTH, 1.0, self.maturity()) * self.vigor;
+        stats.update_max_hp_scaled(body, health_factor);
+        self.scale()
+    }
+}
+
+/// Whether animals with this body grow up and can breed
+pub fn grows(body: &Body) -> bool {
+    matches!(
+        body,
+        Body::QuadrupedSmall(_)
+            | Body::QuadrupedMedium(_)
+            | Body::QuadrupedLow(_)
+            | Body::BirdMedium(_)
+    )
+}
+
+/// Body of a newborn of the same species as `parent`
+fn offspring_body(parent: &Body, rng: &mut impl Rng) -> Body {
+    match parent {
+        Body::QuadrupedSmall(body) => quadruped_small::Body::random_with(rng, &body.species).into(),
+        Body::QuadrupedMedium(body) => {
+            quadruped_medium::Body::random_with(rng, &body.species).into()
+        },
+        Body::QuadrupedLow(body) => quadruped_low::Body::random_with(rng, &body.species).into(),
+        Body::BirdMedium(body) => bird_medium::Body::random_with(rng, &body.species).into(),
+        body => *body,
+    }
+}
+
+/// Two pets have bred, so their offspring is born next to them and joins their
+/// owner
+pub fn handle_breed(server: &mut Server, parent: EcsEntity, mate: EcsEntity) {
+    let ecs = server.state.ecs();
+    let (owner, pos, body, name, level, growth) = {
+        let alignments = ecs.read_storage::<Alignment>();
+        let positions = ecs.read_storage::<Pos>();
+        let bodies = ecs.read_storage::<Body>();
+        let stats = ecs.read_storage::<Stats>();
+        let growths = ecs.read_storage::<Growth>();
+        let owner = match (alignments.get(parent), alignments.get(mate)) {
+            (Some(Alignment::Owned(a)), Some(Alignment::Owned(b))) if a == b => *a,
+            _ => return,
+        };
+        match (
+            positions.get(parent),
+            bodies.get(parent),
+            bodies.get(mate),
+            stats.get(parent),
+            stats.get(mate),
+            growths.get(parent),
+            growths.get(mate),
+        ) {
+            (
+                Some(pos),
+                Some(body),
+                Some(mate_body),
+                Some(parent_stats),
+                Some(mate_stats),
+                Some(parent_growth),
+                Some(mate_growth),
+            ) if body.can_breed_with(mate_body)
+                && parent_growth.can_breed()
+                && mate_growth.can_breed()
+                && !parent_stats.is_dead
+                && !mate_stats.is_dead =>
+            {
+                let mut rng = rand::thread_rng();
+                (
+                    owner,
+                    *pos,
+                    offspring_body(body, &mut rng),
+                    parent_stats.name.clone(),
+                    (parent_stats.level.level() + mate_stats.level.level()) / 2,
+                    Growth::offspring(parent_growth, mate_growth, &mut rng),
+                )
+            },
+            _ => return,
+        }
+    };
+    let owner_entity = match server.state.ecs().entity_from_uid(owner.into()) {
+        Some(entity) => entity,
+        None => return,
+    };
+    // Same limit as for taming pets
+    let pet_count = (
+        &server.state.ecs().read_storage::<Alignment>(),
+        &server.state.ecs().read_storage::<comp::Agent>(),
+    )
+        .join()
+        .filter(|(alignment, _)| **alignment == Alignment::Owned(owner))
+        .count();
+    if pet_count >= MAX_PETS {
+        return;
+    }
+
+    for parent in [parent, mate].iter() {
+        if let Some(growth) = server
+            .state
+            .ecs()
+            .write_storage::<Growth>()
+            .get_mut(*parent)
+        {
+            growth.breed_cooldown = BREED_COOLDOWN;
+        }
+    }
+
+    let mut stats = Stats::new(name.clone(), body);
+    stats.level.set_level(level);
+    let scale = growth.apply(body, &mut stats);
+    stats
+        .health
+        .set_to(stats.health.maximum(), comp::HealthSource::Revive);
+    let alignment = Alignment::Owned(owner);
+
+    let offspring = server
+        .state
+        .create_npc(
+            pos,
+            stats,
+            LoadoutBuilder::build_loadout(body, alignment, None, false).build(),
+            body,
+        )
+        .with(scale)
+        .with(growth)
+        .with(comp::MountState::Unmounted)
+        .with(alignment)
+        .with(comp::Agent::default())
+        .build();
+
+    // Add to group system
+    {
+        let state = server.state();
+        let mut clients = state.ecs().write_storage::<Client>();
+        let uids = state.ecs().read_storage::<Uid>();
+        let mut group_manager = state.ecs().write_resource::<comp::group::GroupManager>();
+        group_manager.new_pet(
+            offspring,
+            owner_entity,
+            &mut state.ecs().write_storage(),
+            &state.ecs().entities(),
+            &state.ecs().read_storage(),
+            &uids,
+            &mut |entity, group_change| {
+                clients
+                    .get_mut(entity)
+                    .and_then(|c| {
+                        group_change
+                            .try_map(|e| uids.get(e).copied())
+                            .map(|g| (g, c))
+                    })
+                    .map(|(g, c)| c.send_msg(ServerGeneral::GroupUpdate(g)));
+            },
+        );
+    }
+
+    server.notify_client(
+        owner_entity,
+        ChatType::Meta.server_msg(format!("Your {} has given birth!", name)),
+    );
+}
@@ -0,0 +1,385 @@
+//! Private copies of dungeons for groups of players.
+//!
+//! The terrain of a dungeon is shared by everyone, but when instancing is
+//! enabled each group entering a dungeon gets its own set of inhabitants.
+//! Entities only see, collide with and fight entities in their own instance,
+//! and so do their projectiles, beams, shockwaves and explosions. Since the
+//! terrain is shared by every instance, it can't be changed inside dungeons
+//! while instancing is enabled, so that no group can dig through, burn or blow
+//! up the dungeon of another. Sprites can still be taken away, so that chests
+//! can be looted and gates unlocked.
+
+use crate::{events::handle_create_npc, state_ext::StateExt, sys, Server, Tick};
+use common::{
+    comp::{self, Affixes, Alignment, ChatType, DungeonDifficulty, Group, Instance},
+    event::ServerEvent,
+    state::BlockChange,
+    sync::Uid,
+    terrain::{SpriteKind, TerrainGrid},
+    vol::ReadVol,
+};
+use hashbrown::HashMap;
+use specs::{Entity as EcsEntity, Join, WorldExt};
+use std::sync::Arc;
+use tracing::{debug, error};
+use vek::*;
+
+/// How many ticks pass between checks for players entering or leaving dungeons
+const UPDATE_INTERVAL: u64 = 30;
+/// Extra levels given to the inhabitants of an instance for every group member
+/// after the first
+const LEVELS_PER_MEMBER: u32 = 3;
+
+pub struct DungeonInstance {
+    /// Site id of the dungeon
+    pub site: u64,
+    /// Group that the instance belongs to
+    pub group: Group,
+    /// Size of the group when the instance was created
+    pub group_size: usize,
+    /// Difficulty the group chose before entering
+    pub difficulty: DungeonDifficulty,
+}
+
+#[derive(Default)]
+pub struct DungeonInstances {
+    next_id: u64,
+    instances: HashMap<u64, DungeonInstance>,
+    /// Difficulty each group will get in the next dungeon it enters
+    difficulties: HashMap<Group, DungeonDifficulty>,
+    /// Site id of the dungeon that each group is waiting at the entrance of, and
+    /// whether its leader has confirmed entering it
+    waiting: HashMap<Group, (u64, bool)>,
+    /// Bounds of the dungeons whose terrain is shared between instances
+    protected: Vec<Aabb<i32>>,
+}
+
+impl DungeonInstances {
+    /// Makes the instances of the dungeons with the given bounds, whose terrain
+    /// can't be changed anymore
+    pub fn new(protected: Vec<Aabb<i32>>) -> Self {
+        Self {
+            protected,
+            ..Self::default()
+        }
+    }
+
+    /// Whether a block is inside a dungeon whose terrain is shared between
+    /// instances, and so can't be changed
+    pub fn is_protected(&self, pos: Vec3<i32>) -> bool {
+        self.protected
+            .iter()
+            .any(|bounds| bounds.contains_point(pos))
+    }
+
+    pub fn difficulty(&self, group: Group) -> DungeonDifficulty {
+        self.difficulties.get(&group).copied().unwrap_or_default()
+    }
+
+    pub fn set_difficulty(&mut self, group: Group, difficulty: DungeonDifficulty) {
+        self.difficulties.insert(group, difficulty);
+    }
+
+    /// Lets a group waiting at the entrance of a dungeon in. Returns whether the
+    /// group was waiting at one.
+    pub fn confirm(&mut self, group: Group) -> bool {
+        if let Some((_, confirmed)) = self.waiting.get_mut(&group) {
+            *confirmed = true;
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn get(&self, instance: Instance) -> Option<&DungeonInstance> {
+        self.instances.get(&instance.0)
+    }
+
+    pub fn find(&self, site: u64, group: Group) -> Option<Instance> {
+        self.instances
+            .iter()
+            .find(|(_, i)| i.site == site && i.group == group)
+            .map(|(id, _)| Instance(*id))
+    }
+
+    fn insert(&mut self, instance: DungeonInstance) -> Instance {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.instances.insert(id, instance);
+        Instance(id)
+    }
+}
+
+/// Moves grouped players into their group's instance of the dungeon they are
+/// in, creating it once their leader has confirmed the difficulty, moves
+/// players that left a dungeon back to the overworld and despawns instances
+/// that nobody is in anymore.
+pub fn update_dungeon_instances(server: &mut Server) {
+    if !server.settings().instanced_dungeons
+        || server.state.ecs().read_resource::<Tick>().0 % UPDATE_INTERVAL != 0
+    {
+        return;
+    }
+
+    let world = Arc::clone(&server.world);
+    let index = server.index.clone();
+
+    // Work out which dungeon and group each player should share an instance with
+    let players = {
+        let ecs = server.state.ecs();
+        let instances = ecs.read_storage::<Instance>();
+        (
+            &ecs.entities(),
+            &ecs.read_storage::<comp::Player>(),
+            &ecs.read_storage::<comp::Pos>(),
+            ecs.read_storage::<Group>().maybe(),
+        )
+            .join()
+            .map(|(entity, _, pos, group)| {
+                let target = group.copied().and_then(|group| {
+                    world
+                        .dungeon_at(index.as_index_ref(), pos.0.map(|e| e.floor() as i32))
+                        .map(|(site, _)| (site, group))
+                });
+                (entity, instances.get(entity).copied(), target)
+            })
+            .collect::<Vec<_>>()
+    };
+
+    // Groups that left the entrance they were waiting at have to confirm again
+    server
+        .state
+        .ecs_mut()
+        .write_resource::<DungeonInstances>()
+        .waiting
+        .retain(|group, (site, _)| {
+            players
+                .iter()
+                .any(|(_, _, target)| *target == Some((*site, *group)))
+        });
+
+    for (entity, current, target) in players {
+        let new = match target {
+            Some((site, group)) => {
+                let (existing, waiting) = {
+                    let instances = server.state.ecs().read_resource::<DungeonInstances>();
+                    (
+                        instances.find(site, group),
+                        instances.waiting.get(&group).copied(),
+                    )
+                };
+                match (existing, waiting) {
+                    (Some(instance), _) => Some(instance),
+                    (None, Some((waiting_site, true))) if waiting_site == site => {
+                        server
+                            .state
+                            .ecs_mut()
+                            .write_resource::<DungeonInstances>()
+                            .waiting
+                            .remove(&group);
+                        Some(create_instance(server, site, group))
+                    },
+                    (None, Some((waiting_site, false))) if waiting_site == site => None,
+                    (None, _) => {
+                        wait_at_entrance(server, site, group);
+                        None
+                    },
+                }
+            },
+            None => None,
+        };
+
+        if new != current {
+            move_to_instance(server, entity, new);
+        }
+    }
+
+    // Despawn instances without players
+    let occupied = {
+        let ecs = server.state.ecs();
+        (
+            &ecs.read_storage::<comp::Player>(),
+            &ecs.read_storage::<Instance>(),
+        )
+            .join()
+            .map(|(_, instance)| *instance)
+            .collect::<Vec<_>>()
+    };
+    let empty = server
+        .state
+        .ecs_mut()
+        .write_resource::<DungeonInstances>()
+        .instances
+        .drain_filter(|id, _| !occupied.contains(&Instance(*id)))
+        .map(|(id, _)| Instance(id))
+        .collect::<Vec<_>>();
+    if !empty.is_empty() {
+        let to_delete = {
+            let ecs = server.state.ecs();
+            (&ecs.entities(), &ecs.read_storage::<Instance>())
+                .join()
+                .filter(|(_, instance)| empty.contains(instance))
+                .map(|(entity, _)| entity)
+                .collect::<Vec<_>>()
+        };
+        debug!(?empty, "Despawning empty dungeon instances");
+        for entity in to_delete {
+            if let Err(e) = server.state.delete_entity_recorded(entity) {
+                error!(?e, "Failed to delete entity of an empty dungeon instance");
+            }
+        }
+    }
+}
+
+/// Throws away the changes to the terrain of dungeons made this tick, by
+/// digging, explosions, fire or anything else, since they would show up in
+/// every instance. Only changes that damage or take away a sprite are kept.
+pub fn protect_dungeon_terrain(server: &mut Server) {
+    let ecs = server.state.ecs();
+    let instances = ecs.read_resource::<DungeonInstances>();
+    if instances.protected.is_empty() {
+        return;
+    }
+    let terrain = ecs.read_resource::<TerrainGrid>();
+    ecs.write_resource::<BlockChange>().retain(|pos, new| {
+        !instances.is_protected(*pos)
+            || terrain.get(*pos).map_or(false, |old| {
+                let sprite = old.get_sprite().filter(|s| *s != SpriteKind::Empty);
+                sprite.is_some()
+                    && new.kind() == old.kind()
+                    && (new.get_sprite() == Some(SpriteKind::Empty) || new.get_sprite() == sprite)
+            })
+    });
+}
+
+/// Holds a group at the entrance of a dungeon, showing it the difficulty it
+/// would enter on until its leader confirms it
+fn wait_at_entrance(server: &mut Server, site: u64, group: Group) {
+    let difficulty = {
+        let mut instances = server.state.ecs_mut().write_resource::<DungeonInstances>();
+        instances.waiting.insert(group, (site, false));
+        instances.difficulty(group)
+    };
+    server
+        .state
+        .send_chat(ChatType::GroupMeta(group).chat_msg(format!(
+            "This dungeon will be on {}. The group leader can change it with /difficulty and \
+             enter with /difficulty confirm",
+            difficulty.describe()
+        )));
+}
+
+/// Spawns a fresh copy of the inhabitants of a dungeon, made tougher for
+/// larger groups
+fn create_instance(server: &mut Server, site: u64, group: Group) -> Instance {
+    let group_size = {
+        let ecs = server.state.ecs();
+        (
+            &ecs.read_storage::<comp::Player>(),
+            &ecs.read_storage::<Group>(),
+        )
+            .join()
+            .filter(|(_, g)| **g == group)
+            .count()
+    };
+    let (instance, difficulty) = {
+        let mut instances = server.state.ecs_mut().write_resource::<DungeonInstances>();
+        let difficulty = instances.difficulty(group);
+        let instance = instances.insert(DungeonInstance {
+            site,
+            group,
+            group_size,
+            difficulty,
+        });
+        (instance, difficulty)
+    };
+    debug!(
+        ?instance,
+        ?site,
+        ?group_size,
+        ?difficulty,
+        "Creating dungeon instance"
+    );
+    server
+        .state
+        .send_chat(ChatType::GroupMeta(group).chat_msg(format!(
+            "Entering a private copy of the dungeon on {}",
+            difficulty.describe()
+        )));
+
+    let supplement =
+        server
+            .world
+            .dungeon_supplement(server.index.as_index_ref(), site, &mut rand::thread_rng());
+    let extra_levels = LEVELS_PER_MEMBER * group_size.saturating_sub(1) as u32;
+    for entity in supplement.entities.into_iter().filter(|e| !e.is_waypoint) {
+        if let ServerEvent::CreateNpc {
+            pos,
+            mut stats,
+            loadout,
+            body,
+            agent,
+            alignment,
+            scale,
+            drop_item,
+            boss,
+            market,
+            bounty,
+        } = sys::terrain::create_npc_event(entity)
+        {
+            stats.level.set_level(stats.level.level() + extra_levels);
+            stats.update_max_hp(stats.body_type);
+            stats
+                .health
+                .set_to(stats.health.maximum(), comp::HealthSource::Revive);
+
+            let npc = handle_create_npc(
+                server, pos, stats, loadout, body, agent, alignment, scale, drop_item, boss,
+                market, bounty,
+            );
+            let _ = server.state.ecs().write_storage().insert(npc, instance);
+            if !difficulty.affixes().is_empty() {
+                let _ = server
+                    .state
+                    .ecs()
+                    .write_storage()
+                    .insert(npc, Affixes(difficulty.affixes().to_vec()));
+            }
+        }
+    }
+
+    instance
+}
+
+/// Moves a player and their pets into an instance, or back into the overworld
+fn move_to_instance(server: &mut Server, entity: EcsEntity, instance: Option<Instance>) {
+    let ecs = server.state.ecs();
+    let pets = ecs
+        .read_storage::<Uid>()
+        .get(entity)
+        .map(|uid| {
+            (&ecs.entities(), &ecs.read_storage::<Alignment>())
+                .join()
+                .filter(|(_, alignment)| **alignment == Alignment::Owned(*uid))
+                .map(|(pet, _)| pet)
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    {
+        let mut instances = ecs.write_storage::<Instance>();
+        for e in std::iter::once(entity).chain(pets) {
+            match instance {
+                Some(instance) => {
+                    let _ = instances.insert(e, instance);
+                },
+                None => {
+                    instances.remove(e);
+                },
+            }
+        }
+    }
+
+    // The set of entities the player can see changed
+    sys::subscription::reset_region_subscription(ecs, entity);
+}
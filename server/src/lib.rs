@@ -5,21 +5,34 @@
 #![cfg_attr(not(feature = "worldgen"), feature(const_panic))]
 
 pub mod alias_validator;
+pub mod bounty;
+pub mod cart;
 mod character_creator;
+pub mod chat_filter;
 pub mod chunk_generator;
 pub mod client;
 pub mod cmd;
 pub mod connection_handler;
+pub mod cutscene;
+pub mod damage_aggregation;
 mod data_dir;
+#[cfg(feature = "worldgen")] pub mod economy;
 pub mod error;
 pub mod events;
+pub mod fire;
+pub mod husbandry;
 pub mod input;
+pub mod instance;
 pub mod login_provider;
+pub mod market;
 pub mod metrics;
 pub mod persistence;
 pub mod settings;
+pub mod stable;
 pub mod state_ext;
+pub mod summon;
 pub mod sys;
+pub mod territory;
 #[cfg(not(feature = "worldgen"))] mod test_world;
 
 // Reexports
@@ -33,6 +46,7 @@ pub use crate::{
 
 use crate::{
     alias_validator::AliasValidator,
+    chat_filter::ChatFilter,
     chunk_generator::ChunkGenerator,
     client::{Client, RegionSubscription},
     cmd::ChatCommandExt,
@@ -44,6 +58,7 @@ use crate::{
 };
 use common::{
     cmd::ChatCommand,
+    combat::{CombatRules, Duels},
     comp::{self, ChatType},
     event::{EventBus, ServerEvent},
     msg::{
@@ -51,17 +66,22 @@ use common::{
     },
     outcome::Outcome,
     recipe::default_recipe_book,
-    state::{State, TimeOfDay},
+    region::{RegionMap, RegionPartition},
+    state::{DayLength, State, TimeOfDay},
     sync::WorldSyncExt,
     terrain::TerrainChunkSize,
+    time::{Date, DAY_SECS},
     vol::{ReadVol, RectVolSize},
 };
 use futures_executor::block_on;
 use metrics::{ServerMetrics, StateTickMetrics, TickMetrics};
 use network::{Network, Pid, ProtocolAddr};
 use persistence::{
+    block_log::BlockLogger,
     character_loader::{CharacterLoader, CharacterLoaderResponseType},
     character_updater::CharacterUpdater,
+    economy::EconomySaver,
+    world_time::{load_world_time, WorldTimeSaver},
 };
 use specs::{join::Join, Builder, Entity as EcsEntity, RunNow, SystemData, WorldExt};
 use std::{
@@ -159,7 +179,20 @@ impl Server {
         state
             .ecs_mut()
             .insert(CharacterLoader::new(&persistence_db_dir)?);
+        state.ecs_mut().insert(BlockLogger::new(
+            &persistence_db_dir,
+            settings.block_log_retention,
+        )?);
+        state
+            .ecs_mut()
+            .insert(WorldTimeSaver::new(&persistence_db_dir)?);
+        state
+            .ecs_mut()
+            .insert(EconomySaver::new(&persistence_db_dir)?);
+        #[cfg(feature = "worldgen")]
+        state.ecs_mut().insert(economy::EconomyClock::default());
         state.ecs_mut().insert(Vec::<Outcome>::new());
+        state.ecs_mut().insert(fire::Embers::default());
 
         // System timers for performance monitoring
         state.ecs_mut().insert(sys::EntitySyncTimer::default());
@@ -171,15 +204,27 @@ impl Server {
         state.ecs_mut().insert(sys::WaypointTimer::default());
         state.ecs_mut().insert(sys::InviteTimeoutTimer::default());
         state.ecs_mut().insert(sys::PersistenceTimer::default());
+        state.ecs_mut().insert(sys::HusbandryTimer::default());
 
         // System schedulers to control execution of systems
         state
             .ecs_mut()
             .insert(sys::PersistenceScheduler::every(Duration::from_secs(10)));
+        state
+            .ecs_mut()
+            .insert(sys::HusbandryScheduler::every(Duration::from_secs(10)));
 
         // Server-only components
         state.ecs_mut().register::<RegionSubscription>();
         state.ecs_mut().register::<Client>();
+        state.ecs_mut().register::<stable::Stable>();
+        state.ecs_mut().register::<cart::Cart>();
+        state.ecs_mut().register::<husbandry::Growth>();
+        state.ecs_mut().register::<market::Merchant>();
+        state.ecs_mut().register::<bounty::Wanted>();
+        state.ecs_mut().register::<market::Reputation>();
+        state.ecs_mut().register::<summon::Owner>();
+        state.ecs_mut().register::<events::LastNote>();
 
         //Alias validator
         let banned_words_paths = &settings.banned_words_files;
@@ -210,10 +255,13 @@ impl Server {
         let banned_words_count = banned_words.len();
         tracing::debug!(?banned_words_count);
         tracing::trace!(?banned_words);
+        state
+            .ecs_mut()
+            .insert(ChatFilter::new(&settings.chat_filter, &banned_words));
         state.ecs_mut().insert(AliasValidator::new(banned_words));
 
         #[cfg(feature = "worldgen")]
-        let (world, index) = World::generate(settings.world_seed, WorldOpts {
+        let (world, mut index) = World::generate(settings.world_seed, WorldOpts {
             seed_elements: true,
             world_file: if let Some(ref opts) = settings.map_file {
                 opts.clone()
@@ -221,7 +269,7 @@ impl Server {
                 // Load default map from assets.
                 FileOpts::LoadAsset(DEFAULT_WORLD_MAP.into())
             },
-            ..WorldOpts::default()
+            history: settings.history.clone(),
         });
         #[cfg(feature = "worldgen")]
         let map = world.get_map_data(index.as_index_ref());
@@ -304,9 +352,86 @@ impl Server {
 
         // set the spawn point we calculated above
         state.ecs_mut().insert(SpawnPoint(spawn_point));
+        state.ecs_mut().insert(RegionPartition::new(
+            settings.region_workers,
+            map.dimensions_lg
+                .map2(TerrainChunkSize::RECT_SIZE, |e, sz| (1 << e) * sz),
+        ));
+
+        // The terrain of dungeons is shared between instances, so it has to be kept as
+        // it is
+        state.ecs_mut().insert(instance::DungeonInstances::new(
+            if settings.instanced_dungeons {
+                world.dungeon_bounds(index.as_index_ref())
+            } else {
+                Vec::new()
+            },
+        ));
 
-        // Set starting time for the server.
-        state.ecs_mut().write_resource::<TimeOfDay>().0 = settings.start_time;
+        // Factions start out holding the outposts they were generated with
+        state.ecs_mut().insert(territory::Territories::new(
+            world.outposts(index.as_index_ref()),
+        ));
+
+        // Players can be kept from hurting each other around towns
+        #[cfg(feature = "worldgen")]
+        let towns = world
+            .civs()
+            .sites()
+            .filter(|site| matches!(site.kind, SiteKind::Settlement | SiteKind::Port))
+            .map(|site| {
+                site.center.map2(TerrainChunkSize::RECT_SIZE, |e, sz| {
+                    (e * sz as i32 + sz as i32 / 2) as f32
+                })
+            })
+            .collect();
+        #[cfg(not(feature = "worldgen"))]
+        let towns = Vec::new();
+        state.ecs_mut().insert(CombatRules {
+            pvp: settings.pvp_enabled,
+            group_immunity: settings.group_immunity,
+            safe_zone_radius: settings.town_safe_zone_radius,
+            safe_zones: towns,
+            duels: Duels::default(),
+            invulnerability_pauses_dots: settings.invulnerability_pauses_dots,
+        });
+        state
+            .ecs_mut()
+            .insert(damage_aggregation::DamageAggregator::new(
+                settings.damage_aggregation_window,
+            ));
+
+        // Towns post bounties on the outlaws from the world's history
+        state
+            .ecs_mut()
+            .insert(bounty::Bounties::new(world.bounties()));
+        state.ecs_mut().insert(cutscene::Cutscenes::default());
+
+        // The economy carries on from where it was when the server was stopped
+        #[cfg(feature = "worldgen")]
+        economy::restore_economy(&mut index, &persistence_db_dir);
+
+        // Town markets open with the prices that their economies settled on
+        state
+            .ecs_mut()
+            .insert(market::Markets::new(world.markets(index.as_index_ref())));
+
+        // Set starting time and length of days for the server. The calendar is the
+        // clock of the world's history, so the first time the server starts it
+        // begins on the day that the history got to, at the starting time. After
+        // that the time carries on from where it was when the server was stopped.
+        let history_day = Date::from_time_of_day(f64::from(index.time) * DAY_SECS).start();
+        let time_of_day = match load_world_time(&persistence_db_dir) {
+            Ok(time_of_day) => time_of_day,
+            Err(e) => {
+                error!(?e, "Couldn't load the world time, using the starting time");
+                None
+            },
+        }
+        .filter(|time_of_day| *time_of_day >= history_day)
+        .unwrap_or(history_day + settings.start_time);
+        state.ecs_mut().write_resource::<TimeOfDay>().0 = time_of_day;
+        state.ecs_mut().write_resource::<DayLength>().0 = settings.day_length * 60.0;
 
         // Register trackers
         sys::sentinel::register_trackers(&mut state.ecs_mut());
@@ -472,6 +597,32 @@ impl Server {
         // Handle game events
         frontend_events.append(&mut self.handle_events());
 
+        // Apply melee damage that was held back to be combined
+        damage_aggregation::apply_aggregated_damage(self);
+
+        // Play the scripted sequences that players are watching
+        cutscene::update_cutscenes(self);
+
+        // Move players in and out of dungeon instances
+        instance::update_dungeon_instances(self);
+
+        // Run the fights over outposts
+        territory::update_territories(self);
+
+        // Move the economy on, and let the prices at town markets drift
+        #[cfg(feature = "worldgen")]
+        economy::update_economy(self);
+        market::update_markets(self);
+
+        // Despawn summoned minions whose time is up
+        summon::update_summons(self);
+
+        // Put out the fires that have burnt out
+        fire::update_embers(self);
+
+        // Keep the terrain of instanced dungeons from changing
+        instance::protect_dungeon_terrain(self);
+
         let before_update_terrain_and_regions = Instant::now();
 
         // Apply terrain changes and update the region map after processing server
@@ -481,6 +632,14 @@ impl Server {
         self.state.update_region_map();
         self.state.apply_terrain_changes();
 
+        let handoffs = self
+            .state
+            .ecs()
+            .read_resource::<RegionPartition>()
+            .handoffs(&self.state.ecs().read_resource::<RegionMap>())
+            .count();
+        self.tick_metrics.region_handoffs.inc_by(handoffs as u64);
+
         let before_sync = Instant::now();
 
         // 6) Synchronise clients with the new state of the world.
@@ -535,9 +694,40 @@ impl Server {
                 },
                 CharacterLoaderResponseType::CharacterData(result) => {
                     let message = match *result {
-                        Ok(character_data) => ServerEvent::UpdateCharacterData {
-                            entity: query_result.entity,
-                            components: character_data,
+                        Ok((
+                            body,
+                            stats,
+                            inventory,
+                            loadout,
+                            ability_selection,
+                            buffs,
+                            stable,
+                            reputation,
+                        )) => {
+                            // Stables and reputation are only known to the server, so they are
+                            // inserted here rather than being passed along with the other
+                            // components
+                            let _ = self
+                                .state
+                                .ecs()
+                                .write_storage()
+                                .insert(query_result.entity, stable);
+                            let _ = self
+                                .state
+                                .ecs()
+                                .write_storage()
+                                .insert(query_result.entity, reputation);
+                            ServerEvent::UpdateCharacterData {
+                                entity: query_result.entity,
+                                components: (
+                                    body,
+                                    stats,
+                                    inventory,
+                                    loadout,
+                                    ability_selection,
+                                    buffs,
+                                ),
+                            }
                         },
                         Err(error) => {
                             // We failed to load data for the character from the DB. Notify the
@@ -774,6 +964,26 @@ impl Server {
 
             let entity_count = self.state.ecs().entities().join().count();
             self.tick_metrics.entity_count.set(entity_count as i64);
+
+            let worker_entities = self
+                .state
+                .ecs()
+                .read_resource::<RegionPartition>()
+                .entity_counts(&self.state.ecs().read_resource::<RegionMap>());
+            for (worker, count) in worker_entities.into_iter().enumerate() {
+                self.tick_metrics
+                    .region_worker_entities
+                    .with_label_values(&[&worker.to_string()])
+                    .set(count as i64);
+            }
+
+            let markets = self.state.ecs().read_resource::<market::Markets>();
+            self.tick_metrics
+                .coins_minted
+                .set(markets.coins_minted() as i64);
+            self.tick_metrics
+                .coins_destroyed
+                .set(markets.coins_destroyed() as i64);
         }
         //self.metrics.entity_count.set(self.state.);
         self.tick_metrics
@@ -843,6 +1053,7 @@ impl Server {
                     entity_package: TrackedComps::fetch(&self.state.ecs())
                         .create_entity_package(entity, None, None, None),
                     time_of_day: *self.state.ecs().read_resource(),
+                    day_length: *self.state.ecs().read_resource(),
                     max_group_size: self.settings().max_player_group_size,
                     client_timeout: self.settings().client_timeout,
                     world_map: self.map.clone(),
@@ -943,6 +1154,11 @@ impl Server {
 
 impl Drop for Server {
     fn drop(&mut self) {
+        let time_of_day = self.state.ecs().read_resource::<TimeOfDay>().0;
+        self.state
+            .ecs()
+            .read_resource::<WorldTimeSaver>()
+            .save(time_of_day);
         self.state
             .notify_registered_clients(ServerGeneral::Disconnect(DisconnectReason::Shutdown));
     }
@@ -0,0 +1,692 @@
+//! Markets where town merchants trade goods.
+//!
+//! Every town starts out with the prices its economy settled on when the world
+//! was generated, after which prices drift around those values. When the
+//! economy is kept running on the server, the values move along with it as it
+//! ticks. Talking to a
+//! town's merchant shows the current prices, and players who have earned a
+//! reputation in the town by claiming the bounties it posted get a discount.
+//! Merchants are marked with the site of the town they trade for when they are
+//! spawned, and reputation is kept by site and saved with the character.
+//!
+//! The treasury of each town is the stock of coins of its site in the economy
+//! of the world. Coins that towns pay out, such as bounty rewards, enter the
+//! world from it, and coins that players pay to towns, such as stable fees,
+//! leave the world into it. The coins are added to and taken from the stock
+//! when the economy is next updated, and until then a town that paid out more
+//! than it took in raises its prices and one that took in more lowers them.
+//! The totals are reported as metrics so that inflation can be watched.
+//!
+//! Merchants only trade during the day, and turn players away after dark.
+//!
+//! Each merchant sells the goods of their town out of its stock in the economy
+//! of the world, and buys goods from players into it as long as the treasury
+//! can pay for them. The goods that players bought or sold are taken out of or
+//! added to the stock when the economy is next updated, along with the coins,
+//! and goods that the town is short of get dearer while those it has plenty
+//! of get cheaper.
+
+use crate::{state_ext::StateExt, Server, Tick};
+use common::{
+    comp::{self, ChatType, Inventory, Item, Pos, Stats, TradeAction},
+    msg::{MarketGood, MarketInfo, PriceTrend, ServerGeneral},
+    sync::{Uid, WorldSyncExt},
+};
+use hashbrown::HashMap;
+use rand::{seq::SliceRandom, Rng};
+use specs::{Builder, Component, Entity as EcsEntity, WorldExt};
+use specs_idvs::IdvStorage;
+use vek::*;
+
+/// Reputation gained with a town by claiming one of its bounties
+pub const BOUNTY_REPUTATION: u32 = 5;
+
+/// How many ticks pass between changes in prices
+const UPDATE_INTERVAL: u64 = 600;
+/// Largest fraction by which a price can randomly change in one update
+const MAX_DRIFT: f32 = 0.05;
+/// Fraction of the difference between a price and what the good costs in
+/// the town's economy that is made up in one update
+const REVERSION: f32 = 0.1;
+/// Fraction of the price that merchants pay for goods they buy from players
+const SELL_FRACTION: f32 = 0.7;
+/// Discount per point of reputation
+const DISCOUNT_PER_REPUTATION: f32 = 0.01;
+const MAX_DISCOUNT: f32 = 0.2;
+/// Relative change in price under which its trend counts as steady
+const STEADY_CHANGE: f32 = 0.005;
+/// How far away from a town its merchants can be
+const MARKET_RANGE: f32 = 400.0;
+/// How close a player has to be to an NPC to talk to it
+const MAX_TALK_DIST: f32 = 8.0;
+/// Bounds of the factor by which the coins a town took in or paid out since the
+/// last update of the economy scale its prices
+const MIN_PRICE_LEVEL: f32 = 0.5;
+const MAX_PRICE_LEVEL: f32 = 2.0;
+/// How strongly the price of a good responds to the town's stock of it running
+/// short of or above its usual level
+const SCARCITY_ELASTICITY: f32 = 0.5;
+/// Bounds of the factor by which the stock of a good scales its price
+const MIN_SCARCITY: f32 = 0.5;
+const MAX_SCARCITY: f32 = 3.0;
+const COINS_ITEM: &str = "common.items.utility.coins";
+/// Items that stand for the goods of the economy when merchants trade them
+/// with players. Goods without an item can only be looked at.
+const GOOD_ITEMS: [(&str, &str); 6] = [
+    ("Fish", "common.items.food.fish.trout"),
+    ("Leather", "common.items.crafting_ing.leather_scraps"),
+    ("Cloth", "common.items.crafting_ing.cloth_scraps"),
+    ("Food", "common.items.food.cheese"),
+    ("Wood", "common.items.crafting_ing.twigs"),
+    ("Stone", "common.items.crafting_ing.stones"),
+];
+
+struct Good {
+    name: String,
+    /// Item that stands for the good when it is traded with players
+    item: Option<&'static str>,
+    /// Price in coins of the good in the town's economy, which prices drift
+    /// around
+    value: f32,
+    price: f32,
+    last_price: f32,
+    /// Stock of the good in the town's economy as of its last tick, which the
+    /// town works back towards as it makes and uses the good
+    base_stock: f32,
+    /// Stock of the good in the town's economy as of the last update
+    stock: f32,
+    /// Number of the good that players bought from the merchant since the last
+    /// update, less the number they sold
+    traded: i32,
+    /// Number of the good that players bought from the merchant since the last
+    /// update of the economy, less the number they sold
+    unsettled: i32,
+}
+
+impl Good {
+    /// How many of the good the merchant has left to sell
+    fn for_sale(&self) -> u32 { (self.stock - self.traded as f32).max(0.0) as u32 }
+
+    /// Factor by which the price of the good is scaled by how short of it the
+    /// town is
+    fn scarcity(&self) -> f32 {
+        ((self.base_stock + 1.0) / (self.stock + 1.0))
+            .powf(SCARCITY_ELASTICITY)
+            .max(MIN_SCARCITY)
+            .min(MAX_SCARCITY)
+    }
+
+    fn trend(&self) -> PriceTrend {
+        let change = (self.price - self.last_price) / self.last_price;
+        if change > STEADY_CHANGE {
+            PriceTrend::Rising
+        } else if change < -STEADY_CHANGE {
+            PriceTrend::Falling
+        } else {
+            PriceTrend::Steady
+        }
+    }
+}
+
+struct Market {
+    /// Id of the site of the town
+    site: u64,
+    /// World position of the town
+    town: Vec2<i32>,
+    goods: Vec<Good>,
+    /// Stock of coins in the town's economy as of the last update
+    treasury: f32,
+    /// Coins that the town took in since the last update of the economy, less
+    /// those it paid out
+    coins: i64,
+}
+
+impl Market {
+    /// Factor by which prices are scaled, which rises as the treasury of the
+    /// town empties
+    fn price_level(&self) -> f32 {
+        (self.treasury.max(1.0) / (self.treasury + self.coins as f32).max(1.0))
+            .max(MIN_PRICE_LEVEL)
+            .min(MAX_PRICE_LEVEL)
+    }
+
+    /// Coins that the town has left to pay out
+    fn funds(&self) -> u32 { (self.treasury + self.coins as f32).max(0.0) as u32 }
+}
+
+#[derive(Default)]
+pub struct Markets {
+    markets: Vec<Market>,
+    /// Coins paid out to players since the server started
+    minted: u64,
+    /// Coins paid by players since the server started
+    destroyed: u64,
+}
+
+impl Markets {
+    pub fn new(markets: Vec<(u64, Vec2<i32>, f32, Vec<(String, f32, f32)>)>) -> Self {
+        Self {
+            markets: markets
+                .into_iter()
+                .map(|(site, town, treasury, goods)| Market {
+                    site,
+                    town,
+                    goods: goods
+                        .into_iter()
+                        .filter(|(_, value, _)| *value > 0.0)
+                        .map(|(name, value, stock)| Good {
+                            item: GOOD_ITEMS
+                                .iter()
+                                .find(|(good, _)| *good == name)
+                                .map(|(_, item)| *item),
+                            name,
+                            value,
+                            price: value,
+                            last_price: value,
+                            base_stock: stock.max(0.0),
+                            stock: stock.max(0.0),
+                            traded: 0,
+                            unsettled: 0,
+                        })
+                        .collect(),
+                    treasury: treasury.max(0.0),
+                    coins: 0,
+                })
+                .collect(),
+            minted: 0,
+            destroyed: 0,
+        }
+    }
+
+    /// Brings the treasury of each market and the values and usual stocks of
+    /// its goods up to date with the economy of its town, taking the same list
+    /// of markets as `new`. Goods that weren't traded at a market before aren't
+    /// added to it.
+    pub fn refresh(&mut self, markets: Vec<(u64, Vec2<i32>, f32, Vec<(String, f32, f32)>)>) {
+        for (site, _, treasury, goods) in markets {
+            let market = match self.get_mut(site) {
+                Some(market) => market,
+                None => continue,
+            };
+            market.treasury = treasury.max(0.0);
+            for (name, value, stock) in goods.into_iter().filter(|(_, value, _)| *value > 0.0) {
+                if let Some(good) = market.goods.iter_mut().find(|good| good.name == name) {
+                    good.value = value;
+                    good.base_stock = stock.max(0.0);
+                }
+            }
+        }
+    }
+
+    /// Records that the town closest to `wpos` pays `amount` coins to a
+    /// player, returning how many it can pay, since a town can't pay out more
+    /// than its treasury holds
+    pub fn pay_out(&mut self, wpos: Vec2<f32>, amount: u32) -> u32 {
+        let paid = match self.nearest_mut(wpos) {
+            Some(market) => {
+                let paid = amount.min(market.funds());
+                market.coins -= i64::from(paid);
+                paid
+            },
+            None => amount,
+        };
+        self.minted += u64::from(paid);
+        paid
+    }
+
+    /// Records that a player paid `amount` coins to the town closest to `wpos`
+    pub fn take_in(&mut self, wpos: Vec2<f32>, amount: u32) {
+        self.destroyed += u64::from(amount);
+        if let Some(market) = self.nearest_mut(wpos) {
+            market.coins += i64::from(amount);
+        }
+    }
+
+    /// Records that players bought `amount` of the good with the given index
+    /// from the market of the town with the `site` id, or sold it if `amount`
+    /// is negative
+    fn record_trade(&mut self, site: u64, good: usize, amount: i32) {
+        if let Some(good) = self
+            .get_mut(site)
+            .and_then(|market| market.goods.get_mut(good))
+        {
+            good.traded += amount;
+            good.unsettled += amount;
+        }
+    }
+
+    /// Takes the coins that each town took in less those it paid out, and the
+    /// goods that players sold to it less those they bought, since this was
+    /// last called, as changes to the stocks of the sites of the towns in the
+    /// form that `sim2::change_stocks` takes them
+    pub fn take_changes(&mut self) -> Vec<(u64, String, f32)> {
+        let mut changes = Vec::new();
+        for market in self.markets.iter_mut() {
+            if market.coins != 0 {
+                let coins = std::mem::take(&mut market.coins) as f32;
+                market.treasury = (market.treasury + coins).max(0.0);
+                changes.push((market.site, "Coin".to_string(), coins));
+            }
+            for good in market.goods.iter_mut().filter(|good| good.unsettled != 0) {
+                let bought = std::mem::take(&mut good.unsettled) as f32;
+                changes.push((market.site, good.name.clone(), -bought));
+            }
+        }
+        changes
+    }
+
+    /// Site id of the town closest to `wpos`, if there is one in range
+    pub fn town_near(&self, wpos: Vec2<f32>) -> Option<u64> {
+        self.nearest(wpos).map(|market| market.site)
+    }
+
+    pub fn coins_minted(&self) -> u64 { self.minted }
+
+    pub fn coins_destroyed(&self) -> u64 { self.destroyed }
+
+    fn get(&self, site: u64) -> Option<&Market> {
+        self.markets.iter().find(|market| market.site == site)
+    }
+
+    fn get_mut(&mut self, site: u64) -> Option<&mut Market> {
+        self.markets.iter_mut().find(|market| market.site == site)
+    }
+
+    /// The market of the town closest to `wpos`, if there is one in range
+    fn nearest(&self, wpos: Vec2<f32>) -> Option<&Market> {
+        self.markets
+            .iter()
+            .map(|market| (market, market.town.map(|e| e as f32).distance_squared(wpos)))
+            .filter(|(_, dist)| *dist < MARKET_RANGE.powi(2))
+            .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
+            .map(|(market, _)| market)
+    }
+
+    fn nearest_mut(&mut self, wpos: Vec2<f32>) -> Option<&mut Market> {
+        self.markets
+            .iter_mut()
+            .map(|market| {
+                let dist = market.town.map(|e| e as f32).distance_squared(wpos);
+                (market, dist)
+            })
+            .filter(|(_, dist)| *dist < MARKET_RANGE.powi(2))
+            .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
+            .map(|(market, _)| market)
+    }
+}
+
+/// Marks an NPC as the merchant of the market of the town with the `site` id
+#[derive(Copy, Clone, Debug)]
+pub struct Merchant {
+    pub site: u64,
+}
+
+impl Component for Merchant {
+    type Storage = IdvStorage<Self>;
+}
+
+/// Standing of a player with the towns they did favours for
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Reputation {
+    /// Reputation with each town, by the site id of the town
+    pub towns: HashMap<u64, u32>,
+}
+
+impl Reputation {
+    pub fn add(&mut self, site: u64, amount: u32) {
+        *self.towns.entry(site).or_default() += amount;
+    }
+
+    /// Fraction taken off the prices at the market of the town with the `site`
+    /// id
+    pub fn discount(&self, site: u64) -> f32 {
+        let reputation = self.towns.get(&site).copied().unwrap_or(0);
+        (reputation as f32 * DISCOUNT_PER_REPUTATION).min(MAX_DISCOUNT)
+    }
+}
+
+impl Component for Reputation {
+    type Storage = IdvStorage<Self>;
+}
+
+/// Takes the goods that players traded out of the stock of each town, and lets
+/// the prices at every market drift a little, pulled back towards the values
+/// of the goods in the economy of each town scaled by its treasury and by how
+/// short of each good it is
+pub fn update_markets(server: &mut Server) {
+    if server.state.ecs().read_resource::<Tick>().0 % UPDATE_INTERVAL != 0 {
+        return;
+    }
+
+    let mut rng = rand::thread_rng();
+    let mut markets = server.state.ecs().write_resource::<Markets>();
+    for market in markets.markets.iter_mut() {
+        let price_level = market.price_level();
+        for good in market.goods.iter_mut() {
+            good.stock = (good.stock - good.traded as f32).max(0.0);
+            good.stock += (good.base_stock - good.stock) * REVERSION;
+            good.traded = 0;
+
+            let drift = rng.gen_range(-MAX_DRIFT, MAX_DRIFT);
+            let target = good.value * price_level * good.scarcity();
+            good.last_price = good.price;
+            good.price = good.price * (1.0 + drift) + (target - good.price) * REVERSION;
+        }
+    }
+}
+
+/// Handles a player talking to an NPC. Merchants show the player the prices
+/// at the market of their town, and tell of something that happened to the
+/// town in the past.
+pub fn handle_talk(server: &mut Server, entity: EcsEntity, npc: Uid) {
+    if let Some(site) = open_merchant(server, entity, npc) {
+        send_market(server, entity, npc, site);
+
+        let lore = server.world.site_lore(server.index.as_index_ref(), site);
+        if let Some(line) = lore.choose(&mut rand::thread_rng()) {
+            server
+                .state
+                .send_chat(comp::UnresolvedChatMsg::npc(npc, line.clone()));
+        }
+    }
+}
+
+/// Handles a player buying one of a good from a merchant or selling one to
+/// them. Coins paid for goods go into the treasury of the town, and coins paid
+/// out for them come out of it, so merchants stop buying when it runs dry.
+pub fn handle_trade(
+    server: &mut Server,
+    entity: EcsEntity,
+    merchant: Uid,
+    good: usize,
+    action: TradeAction,
+) {
+    let site = match open_merchant(server, entity, merchant) {
+        Some(site) => site,
+        None => return,
+    };
+
+    let (name, item, price, town) = {
+        let ecs = server.state.ecs();
+        let markets = ecs.read_resource::<Markets>();
+        let market = match markets.get(site) {
+            Some(market) => market,
+            None => return,
+        };
+        let good = match market.goods.get(good) {
+            Some(good) => good,
+            None => return,
+        };
+        let item = match good.item {
+            Some(item) => item,
+            None => return,
+        };
+        if action == TradeAction::Buy && good.for_sale() == 0 {
+            let message = format!("The merchant has no {} left to sell.", good.name);
+            server.notify_client(entity, ChatType::Meta.server_msg(message));
+            return;
+        }
+        let discount = ecs
+            .read_storage::<Reputation>()
+            .get(entity)
+            .map_or(0.0, |reputation| reputation.discount(site));
+        let price = match action {
+            TradeAction::Buy => coins(good.price * (1.0 - discount)),
+            TradeAction::Sell => coins(good.price * SELL_FRACTION),
+        };
+        if action == TradeAction::Sell && market.funds() < price {
+            let message = "The merchant can't afford to buy anything more from you.";
+            server.notify_client(entity, ChatType::Meta.server_msg(message.to_string()));
+            return;
+        }
+        (
+            good.name.clone(),
+            item,
+            price,
+            market.town.map(|e| e as f32),
+        )
+    };
+
+    let result = match action {
+        TradeAction::Buy => pay_coins(server, entity, price)
+            .map(|()| give_item(server, entity, Item::new_from_asset_expect(item))),
+        TradeAction::Sell => take_items(server, entity, item, 1)
+            .map_err(|_| format!("You have no {} to sell.", name))
+            .map(|()| {
+                let paid = server
+                    .state
+                    .ecs()
+                    .write_resource::<Markets>()
+                    .pay_out(town, price);
+                let mut payment = Item::new_from_asset_expect(COINS_ITEM);
+                if payment.set_amount(paid).is_ok() {
+                    give_item(server, entity, payment);
+                }
+            }),
+    };
+    match result {
+        Ok(()) => {
+            server
+                .state
+                .ecs()
+                .write_resource::<Markets>()
+                .record_trade(site, good, match action {
+                    TradeAction::Buy => 1,
+                    TradeAction::Sell => -1,
+                });
+            // Show the player the stock that the merchant has left
+            send_market(server, entity, merchant, site);
+        },
+        Err(message) => server.notify_client(entity, ChatType::Meta.server_msg(message)),
+    }
+}
+
+/// Finds the site whose market the merchant with the given uid trades for, if
+/// the player is close enough to trade with them. If the merchant has closed
+/// shop for the night, the player is told so.
+fn open_merchant(server: &Server, entity: EcsEntity, npc: Uid) -> Option<u64> {
+    let ecs = server.state.ecs();
+    let npc = ecs.entity_from_uid(npc.into())?;
+    let player_pos = ecs.read_storage::<Pos>().get(entity).copied()?;
+    let npc_pos = ecs.read_storage::<Pos>().get(npc).copied()?;
+    let site = ecs.read_storage::<Merchant>().get(npc)?.site;
+    let is_alive = ecs
+        .read_storage::<Stats>()
+        .get(npc)
+        .map_or(false, |stats| !stats.is_dead);
+    if !is_alive || player_pos.0.distance_squared(npc_pos.0) > MAX_TALK_DIST.powi(2) {
+        return None;
+    }
+
+    // Markets keep the same hours as the lights and patrols of their town
+    if server.state.get_day_period().is_dark() {
+        let message = "The merchant has closed shop for the night. Come back in the morning.";
+        server.notify_client(entity, ChatType::Meta.server_msg(message.to_string()));
+        return None;
+    }
+
+    Some(site)
+}
+
+/// Sends the player the prices and stock at the market of the town with the
+/// `site` id, as shown by the merchant with the given uid
+fn send_market(server: &Server, entity: EcsEntity, merchant: Uid, site: u64) {
+    let info = {
+        let ecs = server.state.ecs();
+        let markets = ecs.read_resource::<Markets>();
+        let market = match markets.get(site) {
+            Some(market) => market,
+            None => return,
+        };
+        let discount = ecs
+            .read_storage::<Reputation>()
+            .get(entity)
+            .map_or(0.0, |reputation| reputation.discount(site));
+        MarketInfo {
+            merchant,
+            goods: market
+                .goods
+                .iter()
+                .map(|good| MarketGood {
+                    name: good.name.clone(),
+                    buy: coins(good.price * (1.0 - discount)),
+                    sell: coins(good.price * SELL_FRACTION),
+                    trend: good.trend(),
+                    stock: good.for_sale(),
+                    tradeable: good.item.is_some(),
+                })
+                .collect(),
+            discount,
+        }
+    };
+    server.notify_client(entity, ServerGeneral::Market(info));
+}
+
+/// Number of coins asked or paid for a good at the given price
+fn coins(price: f32) -> u32 { price.round().max(1.0) as u32 }
+
+/// Takes `amount` coins out of the player's inventory and into the treasury of
+/// the nearest town
+pub fn pay_coins(server: &mut Server, entity: EcsEntity, amount: u32) -> Result<(), String> {
+    take_items(server, entity, COINS_ITEM, amount).map_err(|coins| {
+        format!(
+            "You need {} coins for that, but only have {}.",
+            amount, coins
+        )
+    })?;
+
+    if let Some(pos) = server.state.read_component_copied::<Pos>(entity) {
+        server
+            .state
+            .ecs()
+            .write_resource::<Markets>()
+            .take_in(pos.0.xy(), amount);
+    }
+    Ok(())
+}
+
+/// Takes `amount` of the item `item` out of the player's inventory. If they
+/// have too few of it, nothing is taken and the number they have is returned.
+fn take_items(server: &mut Server, entity: EcsEntity, item: &str, amount: u32) -> Result<(), u32> {
+    let mut inventories = server.state.ecs().write_storage::<Inventory>();
+    let inventory = inventories.get_mut(entity).ok_or(0u32)?;
+
+    let slots = inventory
+        .slots()
+        .iter()
+        .enumerate()
+        .filter_map(|(slot, slot_item)| {
+            slot_item
+                .as_ref()
+                .filter(|slot_item| slot_item.item_definition_id() == item)
+                .map(|slot_item| (slot, slot_item.amount()))
+        })
+        .collect::<Vec<_>>();
+    let held = slots.iter().map(|(_, amount)| amount).sum::<u32>();
+    if held < amount {
+        return Err(held);
+    }
+
+    let mut remaining = amount;
+    for (slot, held) in slots {
+        if remaining == 0 {
+            break;
+        }
+        if let Some(mut slot_item) = inventory.remove(slot) {
+            if held > remaining {
+                let _ = slot_item.decrease_amount(remaining);
+                let _ = inventory.insert(slot, slot_item);
+                remaining = 0;
+            } else {
+                remaining -= held;
+            }
+        }
+    }
+    drop(inventories);
+
+    server.state.write_component(
+        entity,
+        comp::InventoryUpdate::new(comp::InventoryUpdateEvent::Gave),
+    );
+    Ok(())
+}
+
+/// Puts an item into the player's inventory, dropping it at their feet if it
+/// doesn't fit
+pub fn give_item(server: &mut Server, entity: EcsEntity, item: Item) {
+    let leftover = server
+        .state
+        .ecs()
+        .write_storage::<Inventory>()
+        .get_mut(entity)
+        .and_then(|inventory| inventory.push(item.clone()));
+    match leftover {
+        None => server.state.write_component(
+            entity,
+            comp::InventoryUpdate::new(comp::InventoryUpdateEvent::Collected(item)),
+        ),
+        Some(item) => {
+            if let Some(pos) = server.state.read_component_copied::<Pos>(entity) {
+                server
+                    .state
+                    .create_object(Default::default(), comp::object::Body::Pouch)
+                    .with(Pos(pos.0 + Vec3::unit_z() * 0.25))
+                    .with(item)
+                    .with(comp::Vel(Vec3::zero()))
+                    .build();
+            }
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SITE: u64 = 3;
+
+    fn town(treasury: f32, wood: f32) -> Vec<(u64, Vec2<i32>, f32, Vec<(String, f32, f32)>)> {
+        vec![(SITE, Vec2::zero(), treasury, vec![(
+            "Wood".to_string(),
+            2.0,
+            wood,
+        )])]
+    }
+
+    #[test]
+    fn trades_reach_the_economy_once() {
+        let mut markets = Markets::new(town(100.0, 50.0));
+        markets.take_in(Vec2::zero(), 10);
+        assert_eq!(markets.pay_out(Vec2::zero(), 4), 4);
+        markets.record_trade(SITE, 0, 3);
+        markets.record_trade(SITE, 0, -1);
+
+        let changes = markets.take_changes();
+        assert_eq!(changes, vec![
+            (SITE, "Coin".to_string(), 6.0),
+            (SITE, "Wood".to_string(), -2.0),
+        ]);
+        assert!(markets.take_changes().is_empty());
+
+        // The economy ticks with the changes applied, and the markets take on the
+        // result
+        markets.refresh(town(106.0, 48.0));
+        assert_eq!(markets.get(SITE).unwrap().treasury, 106.0);
+        assert_eq!(markets.get(SITE).unwrap().goods[0].base_stock, 48.0);
+    }
+
+    #[test]
+    fn towns_pay_out_no_more_than_their_treasury() {
+        let mut markets = Markets::new(town(10.0, 0.0));
+        assert_eq!(markets.pay_out(Vec2::zero(), 8), 8);
+        assert_eq!(markets.pay_out(Vec2::zero(), 8), 2);
+        assert_eq!(markets.pay_out(Vec2::zero(), 8), 0);
+        assert_eq!(markets.coins_minted(), 10);
+        assert_eq!(markets.take_changes(), vec![(
+            SITE,
+            "Coin".to_string(),
+            -10.0
+        )]);
+        assert_eq!(markets.get(SITE).unwrap().treasury, 0.0);
+    }
+}
@@ -52,6 +52,10 @@ pub struct TickMetrics {
     pub start_time: IntGauge,
     pub time_of_day: Gauge,
     pub light_count: IntGauge,
+    pub coins_minted: IntGauge,
+    pub coins_destroyed: IntGauge,
+    pub region_worker_entities: IntGaugeVec,
+    pub region_handoffs: IntCounter,
     tick: Arc<AtomicU64>,
 }
 
@@ -260,6 +264,25 @@ impl TickMetrics {
             "light_count",
             "number of all lights currently active on the server",
         ))?;
+        let coins_minted = IntGauge::with_opts(Opts::new(
+            "coins_minted",
+            "number of coins that towns paid out to players since the server started",
+        ))?;
+        let coins_destroyed = IntGauge::with_opts(Opts::new(
+            "coins_destroyed",
+            "number of coins that players paid to towns since the server started",
+        ))?;
+        let region_worker_entities = IntGaugeVec::new(
+            Opts::new(
+                "region_worker_entities",
+                "number of entities in the regions that each region worker would own",
+            ),
+            &["worker"],
+        )?;
+        let region_handoffs = IntCounter::with_opts(Opts::new(
+            "region_handoffs",
+            "number of entities that crossed into a region owned by another region worker",
+        ))?;
         let tick_time = IntGaugeVec::new(
             Opts::new("tick_time", "time in ns required for a tick of the server"),
             &["period"],
@@ -278,6 +301,10 @@ impl TickMetrics {
         let start_time_clone = start_time.clone();
         let time_of_day_clone = time_of_day.clone();
         let light_count_clone = light_count.clone();
+        let coins_minted_clone = coins_minted.clone();
+        let coins_destroyed_clone = coins_destroyed.clone();
+        let region_worker_entities_clone = region_worker_entities.clone();
+        let region_handoffs_clone = region_handoffs.clone();
         let tick_time_clone = tick_time.clone();
 
         let f = |registry: &Registry| {
@@ -289,6 +316,10 @@ impl TickMetrics {
             registry.register(Box::new(start_time_clone))?;
             registry.register(Box::new(time_of_day_clone))?;
             registry.register(Box::new(light_count_clone))?;
+            registry.register(Box::new(coins_minted_clone))?;
+            registry.register(Box::new(coins_destroyed_clone))?;
+            registry.register(Box::new(region_worker_entities_clone))?;
+            registry.register(Box::new(region_handoffs_clone))?;
             registry.register(Box::new(tick_time_clone))?;
             Ok(())
         };
@@ -304,6 +335,10 @@ impl TickMetrics {
                 start_time,
                 time_of_day,
                 light_count,
+                coins_minted,
+                coins_destroyed,
+                region_worker_entities,
+                region_handoffs,
                 tick,
             },
             Box::new(f),
@@ -0,0 +1,11 @@
+-- Pets kept in a stable by a character, in the order they were stabled
+CREATE TABLE pet
+(
+    character_id INTEGER NOT NULL
+        REFERENCES character(character_id),
+    position     INT NOT NULL,
+    name         TEXT NOT NULL,
+    level        INT NOT NULL,
+    body_data    TEXT NOT NULL,
+    PRIMARY KEY (character_id, position)
+);
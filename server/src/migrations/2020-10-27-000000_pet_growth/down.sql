@@ -0,0 +1,23 @@
+-- SQLite does not support dropping columns, so the table is recreated without
+-- them
+CREATE TABLE _pet_new
+(
+    character_id INTEGER NOT NULL
+        REFERENCES character(character_id),
+    position     INT NOT NULL,
+    name         TEXT NOT NULL,
+    level        INT NOT NULL,
+    body_data    TEXT NOT NULL,
+    PRIMARY KEY (character_id, position)
+);
+
+INSERT INTO _pet_new
+SELECT  character_id,
+        position,
+        name,
+        level,
+        body_data
+FROM    pet;
+
+DROP TABLE pet;
+ALTER TABLE _pet_new RENAME TO pet;
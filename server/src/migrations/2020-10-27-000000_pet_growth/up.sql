@@ -0,0 +1,4 @@
+-- Growth of stabled pets. Pets stabled before animals could grow up are adults
+-- (an age of two hours) of average vigor.
+ALTER TABLE pet ADD COLUMN age REAL NOT NULL DEFAULT 7200.0;
+ALTER TABLE pet ADD COLUMN vigor REAL NOT NULL DEFAULT 1.0;
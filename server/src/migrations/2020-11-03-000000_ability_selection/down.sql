@@ -0,0 +1 @@
+DROP TABLE ability_selection;
@@ -0,0 +1,8 @@
+-- The abilities a character has chosen for each kind of weapon
+CREATE TABLE ability_selection
+(
+    character_id   INTEGER NOT NULL
+        PRIMARY KEY
+        REFERENCES character(character_id),
+    selection_data TEXT NOT NULL
+);
@@ -0,0 +1,34 @@
+PRAGMA foreign_keys=off;
+
+-- SQLite does not support dropping columns, so the table is recreated without
+-- them
+CREATE TABLE _item_new
+(
+    item_id                  INTEGER NOT NULL
+        PRIMARY KEY
+        REFERENCES entity(entity_id),
+    parent_container_item_id INTEGER NOT NULL
+        REFERENCES _item_new(item_id),
+    item_definition_id       TEXT NOT NULL,
+    stack_size               INTEGER NOT NULL,
+    position                 TEXT NOT NULL
+);
+
+INSERT INTO _item_new
+SELECT  item_id,
+        parent_container_item_id,
+        item_definition_id,
+        stack_size,
+        position
+FROM    item;
+
+DROP TABLE item;
+ALTER TABLE _item_new RENAME TO item;
+
+CREATE UNIQUE INDEX idx_parent_container_item_id_position
+    ON item(parent_container_item_id, position);
+
+CREATE INDEX idx_item_definition_id
+    ON item(item_definition_id);
+
+PRAGMA foreign_keys=on;
@@ -0,0 +1,4 @@
+-- Color an item was dyed with, packed as 0xRRGGBB, and the item definition of
+-- the item whose look it has
+ALTER TABLE item ADD COLUMN dye INTEGER;
+ALTER TABLE item ADD COLUMN appearance TEXT;
@@ -0,0 +1,16 @@
+-- Every block placed or broken by a player, so that griefing can be rolled
+-- back. Blocks are stored as JSON.
+CREATE TABLE block_change
+(
+    block_change_id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    player_uuid     TEXT NOT NULL,
+    time            BIGINT NOT NULL,
+    x               INT NOT NULL,
+    y               INT NOT NULL,
+    z               INT NOT NULL,
+    old_block       TEXT NOT NULL,
+    new_block       TEXT NOT NULL
+);
+
+CREATE INDEX idx_block_change_player_time
+    ON block_change(player_uuid, time);
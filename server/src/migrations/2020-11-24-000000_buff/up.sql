@@ -0,0 +1,8 @@
+-- The long-lasting buffs a character had when it was last saved
+CREATE TABLE buff
+(
+    character_id INTEGER NOT NULL
+        PRIMARY KEY
+        REFERENCES character(character_id),
+    buff_data    TEXT NOT NULL
+);
@@ -0,0 +1,7 @@
+-- The in-game time of day of the world, so that the time and date carry on
+-- where they left off when the server restarts. There is only ever one row.
+CREATE TABLE world_time
+(
+    world_time_id INTEGER NOT NULL PRIMARY KEY,
+    time_of_day   DOUBLE NOT NULL
+);
@@ -0,0 +1,7 @@
+-- The state of the economies of the sites of the world, which keep changing
+-- while the server runs. There is only ever one row.
+CREATE TABLE economy
+(
+    economy_id INTEGER NOT NULL PRIMARY KEY,
+    state      BLOB NOT NULL
+);
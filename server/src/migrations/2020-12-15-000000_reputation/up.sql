@@ -0,0 +1,10 @@
+-- Reputation of characters with the towns they did favours for, by the site id
+-- of the town
+CREATE TABLE reputation
+(
+    character_id INTEGER NOT NULL
+        REFERENCES character(character_id),
+    site_id      BIGINT NOT NULL,
+    amount       INT NOT NULL,
+    PRIMARY KEY (character_id, site_id)
+);
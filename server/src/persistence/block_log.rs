@@ -0,0 +1,253 @@
+//! A record of the blocks players place and break, kept so that admins can
+//! undo griefing with `/rollback`
+
+use crate::persistence::{
+    error::Error,
+    establish_connection,
+    models::{BlockChange, NewBlockChange},
+    schema, VelorenConnection,
+};
+use authc::Uuid;
+use common::terrain::Block;
+use crossbeam::channel;
+use diesel::prelude::*;
+use hashbrown::{HashMap, HashSet};
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+use tracing::{error, trace};
+use vek::*;
+
+/// A single block changed by a player
+#[derive(Clone, Debug)]
+pub struct LoggedBlockChange {
+    pub player_uuid: Uuid,
+    /// Seconds since the unix epoch
+    pub time: i64,
+    pub pos: Vec3<i32>,
+    pub old_block: Block,
+    pub new_block: Block,
+}
+
+/// Seconds since the unix epoch, the time format used by the block log
+pub fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs() as i64)
+}
+
+/// How often the writer thread deletes changes that are older than the
+/// retention period
+const PRUNE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Works out which blocks to set to undo `changes`, which must be ordered
+/// newest first. `current` gives the block currently at a position.
+///
+/// Each position is walked back only as long as it still holds what the
+/// player left there. Once someone else has changed it, it and every older
+/// change to it are left alone.
+pub fn undo(
+    changes: &[LoggedBlockChange],
+    current: impl Fn(Vec3<i32>) -> Option<Block>,
+) -> HashMap<Vec3<i32>, Block> {
+    let mut restored = HashMap::new();
+    let mut skipped = HashSet::new();
+    for change in changes {
+        if skipped.contains(&change.pos) {
+            continue;
+        }
+        let block = restored
+            .get(&change.pos)
+            .copied()
+            .or_else(|| current(change.pos));
+        if block == Some(change.new_block) {
+            restored.insert(change.pos, change.old_block);
+        } else {
+            skipped.insert(change.pos);
+        }
+    }
+    restored
+}
+
+/// Writes player block changes to the database in a background thread, and
+/// loads them back for rollbacks.
+pub struct BlockLogger {
+    db_dir: PathBuf,
+    log_tx: Option<channel::Sender<LoggedBlockChange>>,
+    handle: Option<std::thread::JoinHandle<()>>,
+}
+
+impl BlockLogger {
+    /// Changes older than `retention` are deleted from the log, or never if
+    /// it is zero.
+    pub fn new(db_dir: &Path, retention: Duration) -> diesel::QueryResult<Self> {
+        let (log_tx, log_rx) = channel::unbounded::<LoggedBlockChange>();
+
+        let mut conn = establish_connection(db_dir)?;
+        prune_changes(retention, &mut conn);
+
+        let handle = std::thread::spawn(move || {
+            let mut last_prune = Instant::now();
+            while let Ok(change) = log_rx.recv() {
+                if last_prune.elapsed() > PRUNE_INTERVAL {
+                    prune_changes(retention, &mut conn);
+                    last_prune = Instant::now();
+                }
+
+                // Builders change many blocks in quick succession, so write everything
+                // that is already queued up in one go
+                let changes = std::iter::once(change)
+                    .chain(log_rx.try_iter())
+                    .collect::<Vec<_>>();
+                trace!(count = changes.len(), "Writing block changes to the log");
+                insert_changes(changes, &mut conn);
+            }
+        });
+
+        Ok(Self {
+            db_dir: db_dir.to_owned(),
+            log_tx: Some(log_tx),
+            handle: Some(handle),
+        })
+    }
+
+    /// Records that a player changed the block at `pos`
+    pub fn log(&self, player_uuid: Uuid, pos: Vec3<i32>, old_block: Block, new_block: Block) {
+        if old_block == new_block {
+            return;
+        }
+
+        if let Err(e) = self.log_tx.as_ref().unwrap().send(LoggedBlockChange {
+            player_uuid,
+            time: now(),
+            pos,
+            old_block,
+            new_block,
+        }) {
+            error!(?e, "Could not send block change to the block log");
+        }
+    }
+
+    /// Loads every block change a player made at or after `since`, newest
+    /// first.
+    ///
+    /// This blocks on the database, which is fine for the occasional admin
+    /// command but shouldn't be done every tick.
+    pub(crate) fn load_changes_since(
+        &self,
+        uuid: Uuid,
+        since: i64,
+    ) -> Result<Vec<LoggedBlockChange>, Error> {
+        use schema::block_change::dsl::*;
+
+        let mut conn = establish_connection(&self.db_dir)?;
+        conn.transaction::<_, Error, _>(|txn| {
+            block_change
+                .filter(player_uuid.eq(uuid.to_string()))
+                .filter(time.ge(since))
+                .order(block_change_id.desc())
+                .load::<BlockChange>(&*txn)?
+                .into_iter()
+                .map(|change| {
+                    Ok(LoggedBlockChange {
+                        player_uuid: uuid,
+                        time: change.time,
+                        pos: Vec3::new(change.x, change.y, change.z),
+                        old_block: serde_json::from_str(&change.old_block)?,
+                        new_block: serde_json::from_str(&change.new_block)?,
+                    })
+                })
+                .collect()
+        })
+    }
+}
+
+fn insert_changes(changes: Vec<LoggedBlockChange>, connection: &mut VelorenConnection) {
+    if let Err(e) = connection.transaction::<_, Error, _>(|txn| {
+        let new_changes = changes
+            .into_iter()
+            .map(|change| {
+                Ok(NewBlockChange {
+                    player_uuid: change.player_uuid.to_string(),
+                    time: change.time,
+                    x: change.pos.x,
+                    y: change.pos.y,
+                    z: change.pos.z,
+                    old_block: serde_json::to_string(&change.old_block)?,
+                    new_block: serde_json::to_string(&change.new_block)?,
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        diesel::insert_into(schema::block_change::table)
+            .values(&new_changes)
+            .execute(&*txn)?;
+
+        Ok(())
+    }) {
+        error!(?e, "Error while writing to the block log");
+    }
+}
+
+fn prune_changes(retention: Duration, connection: &mut VelorenConnection) {
+    use schema::block_change::dsl::*;
+
+    if retention == Duration::default() {
+        return;
+    }
+
+    let cutoff = now() - retention.as_secs() as i64;
+    match connection.transaction::<_, Error, _>(|txn| {
+        Ok(diesel::delete(block_change.filter(time.lt(cutoff))).execute(&*txn)?)
+    }) {
+        Ok(count) => trace!(count, "Pruned old changes from the block log"),
+        Err(e) => error!(?e, "Error while pruning the block log"),
+    }
+}
+
+impl Drop for BlockLogger {
+    fn drop(&mut self) {
+        drop(self.log_tx.take());
+        if let Err(e) = self.handle.take().unwrap().join() {
+            error!(?e, "Error from joining block log thread");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::terrain::{BlockKind, SpriteKind};
+
+    fn change(pos: Vec3<i32>, old_block: Block, new_block: Block) -> LoggedBlockChange {
+        LoggedBlockChange {
+            player_uuid: Uuid::nil(),
+            time: 0,
+            pos,
+            old_block,
+            new_block,
+        }
+    }
+
+    #[test]
+    fn undo_stops_at_changes_made_by_others() {
+        let air = Block::air(SpriteKind::Empty);
+        let rock = Block::new(BlockKind::Rock, Rgb::zero());
+        let wood = Block::new(BlockKind::Wood, Rgb::zero());
+        let (a, b) = (Vec3::zero(), Vec3::unit_x());
+
+        // Newest first: the player dug out `a` and built on it again, while someone
+        // else has since replaced the wood at `b`
+        let changes = [
+            change(a, air, wood),
+            change(b, air, wood),
+            change(a, rock, air),
+            change(b, rock, air),
+        ];
+        let restored = undo(&changes, |pos| Some(if pos == a { wood } else { air }));
+
+        assert_eq!(restored.get(&a), Some(&rock));
+        assert_eq!(restored.get(&b), None);
+    }
+}
@@ -8,18 +8,22 @@ extern crate diesel;
 
 use super::{error::Error, models::*, schema, VelorenTransaction};
 use crate::{
-    comp,
+    comp, market,
     persistence::{
         character::conversions::{
-            convert_body_from_database, convert_body_to_database_json,
-            convert_character_from_database, convert_inventory_from_database_items,
-            convert_items_to_database_items, convert_loadout_from_database_items,
-            convert_stats_from_database, convert_stats_to_database,
+            convert_ability_selection_from_database, convert_ability_selection_to_database,
+            convert_body_from_database, convert_body_to_database_json, convert_buffs_from_database,
+            convert_buffs_to_database, convert_character_from_database,
+            convert_inventory_from_database_items, convert_items_to_database_items,
+            convert_loadout_from_database_items, convert_reputation_from_database,
+            convert_reputation_to_database, convert_stable_from_database,
+            convert_stable_to_database, convert_stats_from_database, convert_stats_to_database,
         },
         character_loader::{CharacterDataResult, CharacterListResult},
         error::Error::DatabaseError,
         PersistedComponents,
     },
+    stable::Stable,
 };
 use common::character::{CharacterId, CharacterItem, MAX_CHARACTERS_PER_PLAYER};
 use core::ops::Range;
@@ -56,7 +60,10 @@ pub fn load_character_data(
     char_id: CharacterId,
     connection: VelorenTransaction,
 ) -> CharacterDataResult {
-    use schema::{body::dsl::*, character::dsl::*, item::dsl::*, stats::dsl::*};
+    use schema::{
+        ability_selection::dsl::*, body::dsl::*, buff::dsl::*, character::dsl::*, item::dsl::*,
+        pet::dsl::*, reputation::dsl::*, stats::dsl::*,
+    };
 
     let character_containers = get_pseudo_containers(connection, char_id)?;
 
@@ -83,11 +90,34 @@ pub fn load_character_data(
         .filter(schema::body::dsl::body_id.eq(char_id))
         .first::<Body>(&*connection)?;
 
+    let stabled_pets = pet
+        .filter(schema::pet::dsl::character_id.eq(char_id))
+        .order(schema::pet::dsl::position.asc())
+        .load::<Pet>(&*connection)?;
+
+    let town_reputation = reputation
+        .filter(schema::reputation::dsl::character_id.eq(char_id))
+        .load::<Reputation>(&*connection)?;
+
+    let selection = ability_selection
+        .filter(schema::ability_selection::dsl::character_id.eq(char_id))
+        .first::<AbilitySelection>(&*connection)
+        .optional()?;
+
+    let char_buffs = buff
+        .filter(schema::buff::dsl::character_id.eq(char_id))
+        .first::<Buff>(&*connection)
+        .optional()?;
+
     Ok((
         convert_body_from_database(&char_body)?,
         convert_stats_from_database(&stats_data, character_data.alias),
         convert_inventory_from_database_items(&inventory_items)?,
         convert_loadout_from_database_items(&loadout_items)?,
+        convert_ability_selection_from_database(selection.as_ref())?,
+        convert_buffs_from_database(char_buffs.as_ref())?,
+        convert_stable_from_database(&stabled_pets)?,
+        convert_reputation_from_database(&town_reputation),
     ))
 }
 
@@ -157,7 +187,10 @@ pub fn create_character(
 
     use schema::{body, character, stats};
 
-    let (body, stats, inventory, loadout) = persisted_components;
+    // New characters have no chosen abilities, buffs, stabled pets or reputation
+    // yet
+    let (body, stats, inventory, loadout, _ability_selection, _buffs, _stable, _reputation) =
+        persisted_components;
 
     // Fetch new entity IDs for character, inventory and loadout
     let mut new_entity_ids = get_new_entity_ids(connection, |next_id| next_id + 3)?;
@@ -173,6 +206,8 @@ pub fn create_character(
             parent_container_item_id: WORLD_PSEUDO_CONTAINER_ID,
             item_definition_id: CHARACTER_PSEUDO_CONTAINER_DEF_ID.to_owned(),
             position: character_id.to_string(),
+            dye: None,
+            appearance: None,
         },
         Item {
             stack_size: 1,
@@ -180,6 +215,8 @@ pub fn create_character(
             parent_container_item_id: character_id,
             item_definition_id: INVENTORY_PSEUDO_CONTAINER_DEF_ID.to_owned(),
             position: INVENTORY_PSEUDO_CONTAINER_POSITION.to_owned(),
+            dye: None,
+            appearance: None,
         },
         Item {
             stack_size: 1,
@@ -187,6 +224,8 @@ pub fn create_character(
             parent_container_item_id: character_id,
             item_definition_id: LOADOUT_PSEUDO_CONTAINER_DEF_ID.to_owned(),
             position: LOADOUT_PSEUDO_CONTAINER_POSITION.to_owned(),
+            dye: None,
+            appearance: None,
         },
     ];
     let pseudo_container_count = diesel::insert_into(item)
@@ -288,7 +327,10 @@ pub fn delete_character(
     char_id: CharacterId,
     connection: VelorenTransaction,
 ) -> CharacterListResult {
-    use schema::{body::dsl::*, character::dsl::*, stats::dsl::*};
+    use schema::{
+        ability_selection::dsl::*, body::dsl::*, buff::dsl::*, character::dsl::*, pet::dsl::*,
+        reputation::dsl::*, stats::dsl::*,
+    };
 
     // Load the character to delete - ensures that the requesting player
     // owns the character
@@ -300,6 +342,23 @@ pub fn delete_character(
         )
         .first::<Character>(&*connection)?;
 
+    // Delete stabled pets
+    diesel::delete(pet.filter(schema::pet::dsl::character_id.eq(char_id))).execute(&*connection)?;
+
+    // Delete reputation with towns
+    diesel::delete(reputation.filter(schema::reputation::dsl::character_id.eq(char_id)))
+        .execute(&*connection)?;
+
+    // Delete chosen abilities
+    diesel::delete(
+        ability_selection.filter(schema::ability_selection::dsl::character_id.eq(char_id)),
+    )
+    .execute(&*connection)?;
+
+    // Delete buffs
+    diesel::delete(buff.filter(schema::buff::dsl::character_id.eq(char_id)))
+        .execute(&*connection)?;
+
     // Delete character
     let character_count = diesel::delete(
         character
@@ -505,14 +564,22 @@ fn get_pseudo_container_id(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn update(
     char_id: CharacterId,
     char_stats: comp::Stats,
     inventory: comp::Inventory,
     loadout: comp::Loadout,
+    selection: comp::AbilitySelection,
+    buffs: comp::Buffs,
+    stable: Stable,
+    town_reputation: market::Reputation,
     connection: VelorenTransaction,
 ) -> Result<Vec<Arc<common::comp::item::ItemId>>, Error> {
-    use super::schema::{item::dsl::*, stats::dsl::*};
+    use super::schema::{
+        ability_selection::dsl::*, buff::dsl::*, item::dsl::*, pet::dsl::*, reputation::dsl::*,
+        stats::dsl::*,
+    };
 
     let pseudo_containers = get_pseudo_containers(connection, char_id)?;
 
@@ -587,5 +654,38 @@ pub fn update(
         )));
     }
 
+    let db_selection = convert_ability_selection_to_database(char_id, &selection)?;
+    diesel::replace_into(ability_selection)
+        .values(&db_selection)
+        .execute(&*connection)?;
+
+    let db_buffs = convert_buffs_to_database(char_id, &buffs)?;
+    diesel::replace_into(buff)
+        .values(&db_buffs)
+        .execute(&*connection)?;
+
+    // Replace the stabled pets, which are few enough to simply be rewritten
+    diesel::delete(pet.filter(schema::pet::dsl::character_id.eq(char_id))).execute(&*connection)?;
+    let db_pets = convert_stable_to_database(char_id, &stable)?;
+    if !db_pets.is_empty() {
+        let pet_count = diesel::insert_into(pet)
+            .values(&db_pets)
+            .execute(&*connection)?;
+        if pet_count != db_pets.len() {
+            return Err(Error::OtherError(format!(
+                "Error updating pet table for char_id {}",
+                char_id
+            )));
+        }
+    }
+
+    // Reputation only ever grows, so it is simply replaced
+    let db_reputation = convert_reputation_to_database(char_id, &town_reputation);
+    if !db_reputation.is_empty() {
+        diesel::replace_into(reputation)
+            .values(&db_reputation)
+            .execute(&*connection)?;
+    }
+
     Ok(upserted_comps)
 }
@@ -1,9 +1,17 @@
-use crate::persistence::{
-    character::EntityId,
-    models::{Body, Character, Item, Stats},
+use crate::{
+    husbandry::Growth,
+    market,
+    persistence::{
+        character::EntityId,
+        models::{AbilitySelection, Body, Buff, Character, Item, Pet, Reputation, Stats},
+    },
+    stable::{Stable, StabledPet},
 };
 
-use crate::persistence::{error::Error, json_models::HumanoidBody};
+use crate::persistence::{
+    error::Error,
+    json_models::{self, HumanoidBody},
+};
 use common::{
     character::CharacterId,
     comp::{Body as CompBody, *},
@@ -11,7 +19,10 @@ use common::{
 };
 use core::{convert::TryFrom, num::NonZeroU64};
 use itertools::{Either, Itertools};
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
+
+/// Buffs with less time than this left aren't saved with a character
+const PERSISTED_BUFF_MIN_TIME: Duration = Duration::from_secs(30);
 
 pub struct ItemModelPair {
     pub comp: Arc<common::comp::item::ItemId>,
@@ -145,6 +156,12 @@ pub fn convert_items_to_database_items(
                         } else {
                             1
                         },
+                        dye: item.dye().map(|dye| {
+                            i32::from(dye.r) << 16 | i32::from(dye.g) << 8 | i32::from(dye.b)
+                        }),
+                        appearance: item
+                            .appearance()
+                            .map(|look| look.item_definition_id().to_owned()),
                     },
                     // Continue to remember the atomic, in case we detect an error later and want
                     // to roll back to preserve liveness.
@@ -165,6 +182,83 @@ pub fn convert_body_to_database_json(body: &CompBody) -> Result<String, Error> {
     serde_json::to_string(&json_model).map_err(Error::SerializationError)
 }
 
+pub fn convert_stable_to_database(
+    character_id: CharacterId,
+    stable: &Stable,
+) -> Result<Vec<Pet>, Error> {
+    stable
+        .pets
+        .iter()
+        .enumerate()
+        .map(|(position, pet)| {
+            Ok(Pet {
+                character_id,
+                position: position as i32,
+                name: pet.name.clone(),
+                level: pet.level as i32,
+                body_data: serde_json::to_string(&pet.body).map_err(Error::SerializationError)?,
+                age: pet.growth.age,
+                vigor: pet.growth.vigor,
+            })
+        })
+        .collect()
+}
+
+pub fn convert_reputation_to_database(
+    character_id: CharacterId,
+    reputation: &market::Reputation,
+) -> Vec<Reputation> {
+    reputation
+        .towns
+        .iter()
+        .map(|(site, amount)| Reputation {
+            character_id,
+            site_id: *site as i64,
+            amount: *amount as i32,
+        })
+        .collect()
+}
+
+pub fn convert_ability_selection_to_database(
+    character_id: CharacterId,
+    selection: &common::comp::AbilitySelection,
+) -> Result<AbilitySelection, Error> {
+    Ok(AbilitySelection {
+        character_id,
+        selection_data: serde_json::to_string(selection).map_err(Error::SerializationError)?,
+    })
+}
+
+pub fn convert_buffs_to_database(
+    character_id: CharacterId,
+    buffs: &common::comp::Buffs,
+) -> Result<Buff, Error> {
+    let buffs = buffs
+        .buffs
+        .values()
+        .filter(|buff| {
+            buff.time
+                .map_or(true, |time| time >= PERSISTED_BUFF_MIN_TIME)
+        })
+        .map(|buff| json_models::Buff {
+            kind: buff.kind,
+            data: buff.data,
+            cat_ids: buff.cat_ids.clone(),
+            time: buff.time,
+            // Uids are handed out anew each session, so the character that applied the buff
+            // would be mistaken for whoever gets its uid next
+            source: match buff.source {
+                BuffSource::Character { .. } => BuffSource::Unknown,
+                source => source,
+            },
+        })
+        .collect::<Vec<_>>();
+    Ok(Buff {
+        character_id,
+        buff_data: serde_json::to_string(&buffs).map_err(Error::SerializationError)?,
+    })
+}
+
 pub fn convert_stats_to_database(character_id: CharacterId, stats: &common::comp::Stats) -> Stats {
     Stats {
         stats_id: character_id,
@@ -201,6 +295,8 @@ pub fn convert_inventory_from_database_items(database_items: &[Item]) -> Result<
             .map_err(|_| Error::ConversionError("Error setting amount for item".to_owned()))?;
         }
 
+        convert_look_from_database(&mut item, db_item)?;
+
         // Insert item into inventory
 
         // Slot position
@@ -233,7 +329,8 @@ pub fn convert_inventory_from_database_items(database_items: &[Item]) -> Result<
 pub fn convert_loadout_from_database_items(database_items: &[Item]) -> Result<Loadout, Error> {
     let mut loadout = loadout_builder::LoadoutBuilder::new();
     for db_item in database_items.iter() {
-        let item = common::comp::Item::new_from_asset(db_item.item_definition_id.as_str())?;
+        let mut item = common::comp::Item::new_from_asset(db_item.item_definition_id.as_str())?;
+        convert_look_from_database(&mut item, db_item)?;
         // NOTE: item id is currently *unique*, so we can store the ID safely.
         let comp = item.get_item_id_for_database();
         comp.store(Some(NonZeroU64::try_from(db_item.item_id as u64).map_err(
@@ -268,6 +365,19 @@ pub fn convert_loadout_from_database_items(database_items: &[Item]) -> Result<Lo
     Ok(loadout.build())
 }
 
+/// Restores the dye and appearance override of an item
+fn convert_look_from_database(item: &mut common::comp::Item, db_item: &Item) -> Result<(), Error> {
+    item.set_dye(
+        db_item
+            .dye
+            .map(|dye| vek::Rgb::new((dye >> 16) as u8, (dye >> 8) as u8, dye as u8)),
+    );
+    if let Some(appearance) = &db_item.appearance {
+        item.set_appearance(Some(common::comp::Item::new_from_asset(appearance)?));
+    }
+    Ok(())
+}
+
 pub fn convert_body_from_database(body: &Body) -> Result<CompBody, Error> {
     Ok(match body.variant.as_str() {
         "humanoid" => {
@@ -329,3 +439,56 @@ pub fn convert_stats_from_database(stats: &Stats, alias: String) -> common::comp
 
     new_stats
 }
+
+pub fn convert_stable_from_database(pets: &[Pet]) -> Result<Stable, Error> {
+    Ok(Stable {
+        pets: pets
+            .iter()
+            .map(|pet| {
+                Ok(StabledPet {
+                    name: pet.name.clone(),
+                    body: serde_json::de::from_str::<CompBody>(&pet.body_data)?,
+                    level: pet.level as u32,
+                    growth: Growth {
+                        age: pet.age,
+                        vigor: pet.vigor,
+                        ..Growth::default()
+                    },
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()?,
+    })
+}
+
+pub fn convert_reputation_from_database(reputation: &[Reputation]) -> market::Reputation {
+    market::Reputation {
+        towns: reputation
+            .iter()
+            .map(|town| (town.site_id as u64, town.amount as u32))
+            .collect(),
+    }
+}
+
+pub fn convert_ability_selection_from_database(
+    selection: Option<&AbilitySelection>,
+) -> Result<common::comp::AbilitySelection, Error> {
+    match selection {
+        Some(selection) => Ok(serde_json::de::from_str(&selection.selection_data)?),
+        // Characters that have never chosen an ability use the defaults
+        None => Ok(common::comp::AbilitySelection::default()),
+    }
+}
+
+pub fn convert_buffs_from_database(buffs: Option<&Buff>) -> Result<common::comp::Buffs, Error> {
+    let mut comp_buffs = common::comp::Buffs::default();
+    // Characters saved before buffs were persisted have none
+    if let Some(buffs) = buffs {
+        for buff in serde_json::de::from_str::<Vec<json_models::Buff>>(&buffs.buff_data)? {
+            let mut comp_buff =
+                common::comp::Buff::new(buff.kind, buff.data, buff.cat_ids, buff.source);
+            comp_buff.time = buff.time;
+            comp_buffs.insert(comp_buff);
+        }
+    }
+    Ok(comp_buffs)
+}
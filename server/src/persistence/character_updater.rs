@@ -1,4 +1,4 @@
-use crate::comp;
+use crate::{comp, market::Reputation, stable::Stable};
 use common::{character::CharacterId, comp::item::ItemId};
 
 use crate::persistence::{establish_connection, VelorenConnection};
@@ -6,7 +6,15 @@ use crossbeam::channel;
 use std::{path::Path, sync::Arc};
 use tracing::{error, trace};
 
-pub type CharacterUpdateData = (comp::Stats, comp::Inventory, comp::Loadout);
+pub type CharacterUpdateData = (
+    comp::Stats,
+    comp::Inventory,
+    comp::Loadout,
+    comp::AbilitySelection,
+    comp::Buffs,
+    Stable,
+    Reputation,
+);
 
 /// A unidirectional messaging resource for saving characters in a
 /// background thread.
@@ -48,17 +56,40 @@ impl CharacterUpdater {
                 &'a comp::Stats,
                 &'a comp::Inventory,
                 &'a comp::Loadout,
+                Option<&'a comp::AbilitySelection>,
+                Option<&'a comp::Buffs>,
+                Option<&'a Stable>,
+                Option<&'a Reputation>,
             ),
         >,
     ) {
         let updates = updates
-            .map(|(character_id, stats, inventory, loadout)| {
-                (
+            .map(
+                |(
                     character_id,
-                    (stats.clone(), inventory.clone(), loadout.clone()),
-                )
-            })
-            .collect::<Vec<(CharacterId, (comp::Stats, comp::Inventory, comp::Loadout))>>();
+                    stats,
+                    inventory,
+                    loadout,
+                    selection,
+                    buffs,
+                    stable,
+                    reputation,
+                )| {
+                    (
+                        character_id,
+                        (
+                            stats.clone(),
+                            inventory.clone(),
+                            loadout.clone(),
+                            selection.cloned().unwrap_or_default(),
+                            buffs.cloned().unwrap_or_default(),
+                            stable.cloned().unwrap_or_default(),
+                            reputation.cloned().unwrap_or_default(),
+                        ),
+                    )
+                },
+            )
+            .collect::<Vec<(CharacterId, CharacterUpdateData)>>();
 
         if let Err(e) = self.update_tx.as_ref().unwrap().send(updates) {
             error!(?e, "Could not send stats updates");
@@ -66,14 +97,28 @@ impl CharacterUpdater {
     }
 
     /// Updates a single character based on their id and components
+    #[allow(clippy::too_many_arguments)]
     pub fn update(
         &self,
         character_id: CharacterId,
         stats: &comp::Stats,
         inventory: &comp::Inventory,
         loadout: &comp::Loadout,
+        selection: Option<&comp::AbilitySelection>,
+        buffs: Option<&comp::Buffs>,
+        stable: Option<&Stable>,
+        reputation: Option<&Reputation>,
     ) {
-        self.batch_update(std::iter::once((character_id, stats, inventory, loadout)));
+        self.batch_update(std::iter::once((
+            character_id,
+            stats,
+            inventory,
+            loadout,
+            selection,
+            buffs,
+            stable,
+            reputation,
+        )));
     }
 }
 
@@ -84,12 +129,18 @@ fn execute_batch_update(
     let mut inserted_items = Vec::<Arc<ItemId>>::new();
 
     if let Err(e) = connection.transaction::<_, super::error::Error, _>(|txn| {
-        for (character_id, (stats, inventory, loadout)) in updates {
+        for (character_id, (stats, inventory, loadout, selection, buffs, stable, reputation)) in
+            updates
+        {
             inserted_items.append(&mut super::character::update(
                 character_id,
                 stats,
                 inventory,
                 loadout,
+                selection,
+                buffs,
+                stable,
+                reputation,
                 txn,
             )?);
         }
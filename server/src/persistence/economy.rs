@@ -0,0 +1,86 @@
+//! The state of the economies of the sites of the world, kept in the database
+//! so that prices and stocks carry on where they left off when the server
+//! restarts
+
+use crate::persistence::{
+    error::Error, establish_connection, models::Economy, schema, VelorenConnection,
+};
+use crossbeam::channel;
+use diesel::prelude::*;
+use std::path::Path;
+use tracing::{error, trace};
+
+/// The id of the only row of the economy table
+const ECONOMY_ID: i32 = 0;
+
+/// Loads the state of the economy as it was when it was last saved, if it was
+/// ever saved
+pub fn load_economy(db_dir: &Path) -> Result<Option<Vec<u8>>, Error> {
+    use schema::economy::dsl::*;
+
+    let mut conn = establish_connection(db_dir)?;
+    conn.transaction::<_, Error, _>(|txn| {
+        Ok(economy
+            .filter(economy_id.eq(ECONOMY_ID))
+            .first::<Economy>(&*txn)
+            .optional()?
+            .map(|saved| saved.state))
+    })
+}
+
+/// Writes the state of the economy to the database in a background thread
+pub struct EconomySaver {
+    save_tx: Option<channel::Sender<Vec<u8>>>,
+    handle: Option<std::thread::JoinHandle<()>>,
+}
+
+impl EconomySaver {
+    pub fn new(db_dir: &Path) -> diesel::QueryResult<Self> {
+        let (save_tx, save_rx) = channel::unbounded::<Vec<u8>>();
+
+        let mut conn = establish_connection(db_dir)?;
+
+        let handle = std::thread::spawn(move || {
+            while let Ok(state) = save_rx.recv() {
+                // Only the latest state is worth writing
+                let state = save_rx.try_iter().last().unwrap_or(state);
+                trace!(len = state.len(), "Saving the economy");
+                save(state, &mut conn);
+            }
+        });
+
+        Ok(Self {
+            save_tx: Some(save_tx),
+            handle: Some(handle),
+        })
+    }
+
+    pub fn save(&self, state: Vec<u8>) {
+        if let Err(e) = self.save_tx.as_ref().unwrap().send(state) {
+            error!(?e, "Could not send the economy to be saved");
+        }
+    }
+}
+
+fn save(state: Vec<u8>, connection: &mut VelorenConnection) {
+    if let Err(e) = connection.transaction::<_, Error, _>(|txn| {
+        diesel::replace_into(schema::economy::table)
+            .values(&Economy {
+                economy_id: ECONOMY_ID,
+                state,
+            })
+            .execute(&*txn)?;
+        Ok(())
+    }) {
+        error!(?e, "Error while saving the economy");
+    }
+}
+
+impl Drop for EconomySaver {
+    fn drop(&mut self) {
+        drop(self.save_tx.take());
+        if let Err(e) = self.handle.take().unwrap().join() {
+            error!(?e, "Error from joining economy thread");
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use common::comp;
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
 #[derive(Serialize, Deserialize)]
 pub struct HumanoidBody {
@@ -29,3 +30,15 @@ impl From<&comp::humanoid::Body> for HumanoidBody {
         }
     }
 }
+
+/// A buff saved with a character. Its effects are rebuilt from the kind when it
+/// is loaded, so that changes to a kind's effects also apply to saved buffs.
+#[derive(Serialize, Deserialize)]
+pub struct Buff {
+    pub kind: comp::BuffKind,
+    pub data: comp::BuffData,
+    pub cat_ids: Vec<comp::BuffCategory>,
+    /// Time left, or `None` for buffs that last until they are removed
+    pub time: Option<Duration>,
+    pub source: comp::BuffSource,
+}
@@ -6,14 +6,18 @@
 //! - [`diesel-cli`](https://github.com/diesel-rs/diesel/tree/master/diesel_cli/)
 //!   for generating and testing migrations
 
+pub mod block_log;
 pub(in crate::persistence) mod character;
 pub mod character_loader;
 pub mod character_updater;
+pub mod economy;
 mod error;
 mod json_models;
 mod models;
 mod schema;
+pub mod world_time;
 
+use crate::{market::Reputation, stable::Stable};
 use common::comp;
 use diesel::{connection::SimpleConnection, prelude::*};
 use diesel_migrations::embed_migrations;
@@ -21,7 +25,16 @@ use std::{fs, path::Path};
 use tracing::info;
 
 /// A tuple of the components that are persisted to the DB for each character
-pub type PersistedComponents = (comp::Body, comp::Stats, comp::Inventory, comp::Loadout);
+pub type PersistedComponents = (
+    comp::Body,
+    comp::Stats,
+    comp::Inventory,
+    comp::Loadout,
+    comp::AbilitySelection,
+    comp::Buffs,
+    Stable,
+    Reputation,
+);
 
 // See: https://docs.rs/diesel_migrations/1.4.0/diesel_migrations/macro.embed_migrations.html
 // This macro is called at build-time, and produces the necessary migration info
@@ -1,6 +1,9 @@
 extern crate serde_json;
 
-use super::schema::{body, character, entity, item, stats};
+use super::schema::{
+    ability_selection, block_change, body, buff, character, economy, entity, item, pet, reputation,
+    stats, world_time,
+};
 
 #[derive(Debug, Insertable, PartialEq)]
 #[table_name = "entity"]
@@ -34,6 +37,10 @@ pub struct Item {
     pub item_definition_id: String,
     pub stack_size: i32,
     pub position: String,
+    /// Color the item was dyed with, packed as 0xRRGGBB
+    pub dye: Option<i32>,
+    /// Item definition of the item whose look the item has
+    pub appearance: Option<String>,
 }
 
 #[derive(Associations, AsChangeset, Identifiable, Queryable, Debug, Insertable)]
@@ -56,3 +63,75 @@ pub struct Body {
     pub variant: String,
     pub body_data: String,
 }
+
+#[derive(Debug, Insertable, Queryable)]
+#[table_name = "pet"]
+pub struct Pet {
+    pub character_id: i64,
+    pub position: i32,
+    pub name: String,
+    pub level: i32,
+    pub body_data: String,
+    pub age: f64,
+    pub vigor: f32,
+}
+
+#[derive(Debug, Insertable, Queryable)]
+#[table_name = "reputation"]
+pub struct Reputation {
+    pub character_id: i64,
+    pub site_id: i64,
+    pub amount: i32,
+}
+
+#[derive(Debug, Insertable, Queryable)]
+#[table_name = "ability_selection"]
+pub struct AbilitySelection {
+    pub character_id: i64,
+    pub selection_data: String,
+}
+
+#[derive(Debug, Insertable, Queryable)]
+#[table_name = "buff"]
+pub struct Buff {
+    pub character_id: i64,
+    pub buff_data: String,
+}
+
+#[derive(Debug, Insertable)]
+#[table_name = "block_change"]
+pub struct NewBlockChange {
+    pub player_uuid: String,
+    pub time: i64,
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+    pub old_block: String,
+    pub new_block: String,
+}
+
+#[derive(Debug, Queryable)]
+pub struct BlockChange {
+    pub block_change_id: i64,
+    pub player_uuid: String,
+    pub time: i64,
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+    pub old_block: String,
+    pub new_block: String,
+}
+
+#[derive(Debug, Insertable, Queryable)]
+#[table_name = "world_time"]
+pub struct WorldTime {
+    pub world_time_id: i32,
+    pub time_of_day: f64,
+}
+
+#[derive(Debug, Insertable, Queryable)]
+#[table_name = "economy"]
+pub struct Economy {
+    pub economy_id: i32,
+    pub state: Vec<u8>,
+}
@@ -1,3 +1,23 @@
+table! {
+    ability_selection (character_id) {
+        character_id -> BigInt,
+        selection_data -> Text,
+    }
+}
+
+table! {
+    block_change (block_change_id) {
+        block_change_id -> BigInt,
+        player_uuid -> Text,
+        time -> BigInt,
+        x -> Integer,
+        y -> Integer,
+        z -> Integer,
+        old_block -> Text,
+        new_block -> Text,
+    }
+}
+
 table! {
     body (body_id) {
         body_id -> BigInt,
@@ -6,6 +26,13 @@ table! {
     }
 }
 
+table! {
+    buff (character_id) {
+        character_id -> BigInt,
+        buff_data -> Text,
+    }
+}
+
 table! {
     character (character_id) {
         character_id -> BigInt,
@@ -14,6 +41,13 @@ table! {
     }
 }
 
+table! {
+    economy (economy_id) {
+        economy_id -> Integer,
+        state -> Binary,
+    }
+}
+
 table! {
     entity (entity_id) {
         entity_id -> BigInt,
@@ -27,6 +61,28 @@ table! {
         item_definition_id -> Text,
         stack_size -> Integer,
         position -> Text,
+        dye -> Nullable<Integer>,
+        appearance -> Nullable<Text>,
+    }
+}
+
+table! {
+    pet (character_id, position) {
+        character_id -> BigInt,
+        position -> Integer,
+        name -> Text,
+        level -> Integer,
+        body_data -> Text,
+        age -> Double,
+        vigor -> Float,
+    }
+}
+
+table! {
+    reputation (character_id, site_id) {
+        character_id -> BigInt,
+        site_id -> BigInt,
+        amount -> Integer,
     }
 }
 
@@ -41,7 +97,31 @@ table! {
     }
 }
 
+table! {
+    world_time (world_time_id) {
+        world_time_id -> Integer,
+        time_of_day -> Double,
+    }
+}
+
+joinable!(ability_selection -> character (character_id));
+joinable!(buff -> character (character_id));
 joinable!(character -> body (character_id));
 joinable!(character -> stats (character_id));
+joinable!(pet -> character (character_id));
+joinable!(reputation -> character (character_id));
 
-allow_tables_to_appear_in_same_query!(body, character, entity, item, stats,);
+allow_tables_to_appear_in_same_query!(
+    ability_selection,
+    block_change,
+    body,
+    buff,
+    character,
+    economy,
+    entity,
+    item,
+    pet,
+    reputation,
+    stats,
+    world_time,
+);
@@ -0,0 +1,85 @@
+//! The in-game time of the world, kept in the database so that the time and
+//! date carry on where they left off when the server restarts
+
+use crate::persistence::{
+    error::Error, establish_connection, models::WorldTime, schema, VelorenConnection,
+};
+use crossbeam::channel;
+use diesel::prelude::*;
+use std::path::Path;
+use tracing::{error, trace};
+
+/// The id of the only row of the world time table
+const WORLD_TIME_ID: i32 = 0;
+
+/// Loads the time of day the world was at when it was last saved, if it was
+/// ever saved
+pub fn load_world_time(db_dir: &Path) -> Result<Option<f64>, Error> {
+    use schema::world_time::dsl::*;
+
+    let mut conn = establish_connection(db_dir)?;
+    conn.transaction::<_, Error, _>(|txn| {
+        Ok(world_time
+            .filter(world_time_id.eq(WORLD_TIME_ID))
+            .first::<WorldTime>(&*txn)
+            .optional()?
+            .map(|saved| saved.time_of_day))
+    })
+}
+
+/// Writes the time of day of the world to the database in a background thread
+pub struct WorldTimeSaver {
+    save_tx: Option<channel::Sender<f64>>,
+    handle: Option<std::thread::JoinHandle<()>>,
+}
+
+impl WorldTimeSaver {
+    pub fn new(db_dir: &Path) -> diesel::QueryResult<Self> {
+        let (save_tx, save_rx) = channel::unbounded::<f64>();
+
+        let mut conn = establish_connection(db_dir)?;
+
+        let handle = std::thread::spawn(move || {
+            while let Ok(time_of_day) = save_rx.recv() {
+                // Only the latest time is worth writing
+                let time_of_day = save_rx.try_iter().last().unwrap_or(time_of_day);
+                trace!(?time_of_day, "Saving the world time");
+                save(time_of_day, &mut conn);
+            }
+        });
+
+        Ok(Self {
+            save_tx: Some(save_tx),
+            handle: Some(handle),
+        })
+    }
+
+    pub fn save(&self, time_of_day: f64) {
+        if let Err(e) = self.save_tx.as_ref().unwrap().send(time_of_day) {
+            error!(?e, "Could not send the world time to be saved");
+        }
+    }
+}
+
+fn save(time_of_day: f64, connection: &mut VelorenConnection) {
+    if let Err(e) = connection.transaction::<_, Error, _>(|txn| {
+        diesel::replace_into(schema::world_time::table)
+            .values(&WorldTime {
+                world_time_id: WORLD_TIME_ID,
+                time_of_day,
+            })
+            .execute(&*txn)?;
+        Ok(())
+    }) {
+        error!(?e, "Error while saving the world time");
+    }
+}
+
+impl Drop for WorldTimeSaver {
+    fn drop(&mut self) {
+        drop(self.save_tx.take());
+        if let Err(e) = self.handle.take().unwrap().join() {
+            error!(?e, "Error from joining world time thread");
+        }
+    }
+}
@@ -2,6 +2,7 @@ mod editable;
 
 pub use editable::EditableSetting;
 
+use crate::chat_filter::ChatFilterSettings;
 use authc::Uuid;
 use hashbrown::{HashMap, HashSet};
 use portpicker::pick_unused_port;
@@ -14,7 +15,7 @@ use std::{
     time::Duration,
 };
 use tracing::{error, warn};
-use world::sim::FileOpts;
+use world::{sim::FileOpts, sim2::HistoryOpts};
 
 const DEFAULT_WORLD_SEED: u32 = 59686;
 const CONFIG_DIR: &str = "server_config";
@@ -32,16 +33,78 @@ pub struct Settings {
     pub auth_server_address: Option<String>,
     pub max_players: usize,
     pub world_seed: u32,
-    //pub pvp_enabled: bool,
+    /// When disabled, players can only hurt each other in duels
+    pub pvp_enabled: bool,
+    /// When enabled, members of the same group can't hurt each other
+    pub group_immunity: bool,
+    /// Players can't hurt each other within this distance of a town, except in
+    /// duels (0 disables these safe zones)
+    pub town_safe_zone_radius: f32,
+    /// When enabled, damage over time effects are paused while their target is
+    /// invulnerable (e.g. dodge rolling) instead of still running out
+    pub invulnerability_pauses_dots: bool,
+    /// Damage from the attacks of the same attacker on the same target within
+    /// this many seconds is combined into a single change, so that rapid hits
+    /// don't flood players with damage numbers (0 disables this)
+    pub damage_aggregation_window: f32,
     pub server_name: String,
     pub start_time: f64,
+    /// How many real minutes an in-game day lasts
+    pub day_length: f64,
     /// When set to None, loads the default map file (if available); otherwise,
     /// uses the value of the file options to decide how to proceed.
     pub map_file: Option<FileOpts>,
+    /// How many years of history the world is simulated for, and optionally
+    /// a file to checkpoint that simulation in. With a checkpoint, raising the
+    /// years later simulates only the extra years on the next start, keeping
+    /// the terrain and sites of the world as they are. Reports on the
+    /// economies of sites can also be written out as CSV or JSON lines.
+    pub history: HistoryOpts,
+    /// When enabled, the economies of sites carry on being simulated while the
+    /// server runs, so that prices and stocks keep changing
+    pub live_economy: bool,
+    /// How many real minutes pass between updates of the economy, which pass
+    /// on what was traded at town markets and, when the economy is kept
+    /// running, tick it for each season that has passed on the calendar
+    pub economy_tick_length: f64,
     pub max_view_distance: Option<u32>,
     pub banned_words_files: Vec<PathBuf>,
     pub max_player_group_size: u32,
     pub client_timeout: Duration,
+    /// When enabled, groups entering a dungeon get their own private copy of
+    /// its inhabitants, and the terrain of dungeons can't be changed
+    pub instanced_dungeons: bool,
+    /// When enabled, factions periodically fight over the control of outposts
+    pub territory_events: bool,
+    /// When enabled, player factions can capture outposts by holding them (meant
+    /// for PvP servers)
+    pub player_territory_capture: bool,
+    /// When set, players can only use the asset packs with these hashes in
+    /// place of the built-in assets (an empty list allows no packs at all)
+    pub allowed_asset_packs: Option<Vec<u64>>,
+    /// What happens when a player shoots a bow without any ammo left
+    pub empty_quiver: EmptyQuiver,
+    /// Which chat messages from players are censored, held back or reported
+    /// to admins
+    pub chat_filter: ChatFilterSettings,
+    /// How many workers the regions of the world are split between when
+    /// reporting in the metrics how the load of the server would be shared
+    /// out if it ran on several cores
+    pub region_workers: u32,
+    /// How long player block changes are kept in the block log for
+    /// `/rollback`, or forever if zero
+    pub block_log_retention: Duration,
+}
+
+/// How bows behave for players who are out of ammo
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum EmptyQuiver {
+    /// Plain arrows are shot without using anything up, as if ammo didn't exist
+    Unlimited,
+    /// Weak arrows are shot that can't be picked back up
+    Weakened,
+    /// Nothing is shot
+    Disabled,
 }
 
 impl Default for Settings {
@@ -51,14 +114,31 @@ impl Default for Settings {
             metrics_address: SocketAddr::from(([0; 4], 14005)),
             auth_server_address: Some("https://auth.veloren.net".into()),
             world_seed: DEFAULT_WORLD_SEED,
+            pvp_enabled: true,
+            group_immunity: true,
+            town_safe_zone_radius: 0.0,
+            invulnerability_pauses_dots: false,
+            damage_aggregation_window: 0.0,
             server_name: "Veloren Alpha".into(),
             max_players: 100,
             start_time: 9.0 * 3600.0,
+            day_length: 30.0,
             map_file: None,
+            history: HistoryOpts::default(),
+            live_economy: true,
+            economy_tick_length: 60.0,
             max_view_distance: Some(30),
             banned_words_files: Vec::new(),
             max_player_group_size: 6,
             client_timeout: Duration::from_secs(40),
+            instanced_dungeons: false,
+            territory_events: false,
+            player_territory_capture: false,
+            allowed_asset_packs: None,
+            empty_quiver: EmptyQuiver::Weakened,
+            chat_filter: ChatFilterSettings::default(),
+            region_workers: 4,
+            block_log_retention: Duration::from_secs(30 * 24 * 60 * 60),
         }
     }
 }
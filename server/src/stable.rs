@@ -0,0 +1,338 @@
+//! Stables where players can keep the pets they have tamed.
+//!
+//! Up to [`MAX_PETS`] pets follow a player around; any others are left with a
+//! stable master in town, who will also feed and heal the active companions
+//! for a few coins. Stabled pets are persisted along with the character.
+
+use crate::{
+    client::Client,
+    husbandry::{Growth, MAX_PETS},
+    market,
+    state_ext::StateExt,
+    summon, Server,
+};
+use common::{
+    comp::{self, Alignment, ChatType, Pos, Stats},
+    loadout_builder::LoadoutBuilder,
+    msg::ServerGeneral,
+    sync::Uid,
+};
+use specs::{Builder, Component, Entity as EcsEntity, Join, WorldExt};
+use specs_idvs::IdvStorage;
+use vek::*;
+
+/// How many pets a character can keep in a stable
+pub const MAX_STABLED_PETS: usize = 8;
+/// Name of the NPCs that look after stabled pets
+pub const STABLE_MASTER_NAME: &str = "Stable Master";
+/// How close a player has to be to a stable master to use the stable
+const MAX_STABLE_MASTER_DIST: f32 = 8.0;
+/// How close a pet has to be to its owner to be stabled
+const MAX_PET_DIST: f32 = 24.0;
+/// Coins charged per level of a pet for feeding and healing it
+const CARE_COST_PER_LEVEL: u32 = 2;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct StabledPet {
+    pub name: String,
+    pub body: comp::Body,
+    pub level: u32,
+    pub growth: Growth,
+}
+
+/// The pets a character keeps in stables
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Stable {
+    pub pets: Vec<StabledPet>,
+}
+
+impl Component for Stable {
+    type Storage = IdvStorage<Self>;
+}
+
+/// Lists the pets of the player, both the active ones and the stabled ones
+pub fn list_pets(server: &mut Server, entity: EcsEntity) {
+    let mut messages = active_pets(server, entity)
+        .into_iter()
+        .map(|(_, stats)| {
+            format!(
+                "Active: {} (level {}, {}/{} health)",
+                stats.name,
+                stats.level.level(),
+                stats.health.current(),
+                stats.health.maximum(),
+            )
+        })
+        .collect::<Vec<_>>();
+    if let Some(stable) = server.state.ecs().read_storage::<Stable>().get(entity) {
+        messages.extend(
+            stable
+                .pets
+                .iter()
+                .enumerate()
+                .map(|(i, pet)| {
+                    format!(
+                        "{}: {} (level {}{})",
+                        i + 1,
+                        pet.name,
+                        pet.level,
+                        if pet.growth.is_adult() { "" } else { ", young" },
+                    )
+                }),
+        );
+    }
+    if messages.is_empty() {
+        messages.push("You don't own any pets.".to_string());
+    }
+
+    for message in messages {
+        server.notify_client(entity, ChatType::CommandInfo.server_msg(message));
+    }
+}
+
+/// Leaves the active pet closest to the player at the stable
+pub fn store_pet(server: &mut Server, entity: EcsEntity) {
+    if let Err(error) = near_stable_master(server, entity).and_then(|_| stable_pet(server, entity))
+    {
+        server.notify_client(entity, ChatType::CommandError.server_msg(error));
+    }
+}
+
+/// Takes the pet at `index` out of the stable, leaving the active pet in its
+/// place
+pub fn take_pet(server: &mut Server, entity: EcsEntity, index: usize) {
+    if let Err(error) = near_stable_master(server, entity).and_then(|pos| {
+        let pet = server
+            .state
+            .ecs_mut()
+            .write_storage::<Stable>()
+            .get_mut(entity)
+            .filter(|stable| index < stable.pets.len())
+            .map(|stable| stable.pets.remove(index))
+            .ok_or_else(|| format!("There is no pet number {} in your stable.", index + 1))?;
+
+        // Swap places with the closest companion, if no more can follow the player
+        if active_pets(server, entity).len() >= MAX_PETS {
+            if let Err(error) = stable_pet(server, entity) {
+                let mut stables = server.state.ecs().write_storage::<Stable>();
+                if let Some(stable) = stables.get_mut(entity) {
+                    stable.pets.insert(index, pet);
+                }
+                return Err(error);
+            }
+        }
+
+        spawn_pet(server, entity, pos, &pet);
+        server.notify_client(
+            entity,
+            ChatType::CommandInfo.server_msg(format!("{} is happy to see you.", pet.name)),
+        );
+        Ok(())
+    }) {
+        server.notify_client(entity, ChatType::CommandError.server_msg(error));
+    }
+}
+
+/// Pays the stable master to feed and heal the player's active pets
+pub fn care_for_pets(server: &mut Server, entity: EcsEntity) {
+    match near_stable_master(server, entity).and_then(|_| {
+        let pets = active_pets(server, entity)
+            .into_iter()
+            .filter(|(_, stats)| stats.health.current() < stats.health.maximum())
+            .collect::<Vec<_>>();
+        if pets.is_empty() {
+            return Err("Your pets are already well fed and healthy.".to_string());
+        }
+
+        let cost = pets
+            .iter()
+            .map(|(_, stats)| stats.level.level().max(1) * CARE_COST_PER_LEVEL)
+            .sum::<u32>();
+        market::pay_coins(server, entity, cost)?;
+
+        let mut stats = server.state.ecs().write_storage::<Stats>();
+        for (pet, _) in pets {
+            if let Some(stats) = stats.get_mut(pet) {
+                let maximum = stats.health.maximum();
+                stats
+                    .health
+                    .set_to(maximum, comp::HealthSource::Healing { by: None });
+            }
+        }
+        Ok(cost)
+    }) {
+        Ok(cost) => server.notify_client(
+            entity,
+            ChatType::CommandInfo.server_msg(format!(
+                "The stable master feeds and tends to your pets for {} coins.",
+                cost
+            )),
+        ),
+        Err(error) => server.notify_client(entity, ChatType::CommandError.server_msg(error)),
+    }
+}
+
+/// Returns the position of the player if they are close to a stable master
+fn near_stable_master(server: &Server, entity: EcsEntity) -> Result<Pos, String> {
+    let ecs = server.state.ecs();
+    let pos = ecs
+        .read_storage::<Pos>()
+        .get(entity)
+        .copied()
+        .ok_or_else(|| "You have no position!".to_string())?;
+    let is_near = (
+        &ecs.read_storage::<Pos>(),
+        &ecs.read_storage::<Stats>(),
+        &ecs.read_storage::<Alignment>(),
+    )
+        .join()
+        .any(|(master_pos, stats, alignment)| {
+            *alignment == Alignment::Npc
+                && stats.name == STABLE_MASTER_NAME
+                && !stats.is_dead
+                && master_pos.0.distance_squared(pos.0) < MAX_STABLE_MASTER_DIST.powi(2)
+        });
+
+    if is_near {
+        Ok(pos)
+    } else {
+        Err("You need to be next to a stable master to do that.".to_string())
+    }
+}
+
+/// Pets owned by the player that are following them around. Summoned minions
+/// don't count, so they can't be stabled to outlast their summoning.
+fn active_pets(server: &Server, entity: EcsEntity) -> Vec<(EcsEntity, Stats)> {
+    let ecs = server.state.ecs();
+    let uid = match ecs.read_storage::<Uid>().get(entity).copied() {
+        Some(uid) => uid,
+        None => return Vec::new(),
+    };
+    (
+        &ecs.entities(),
+        &ecs.read_storage::<Alignment>(),
+        &ecs.read_storage::<comp::Agent>(),
+        &ecs.read_storage::<Stats>(),
+        !&ecs.read_storage::<summon::Owner>(),
+    )
+        .join()
+        .filter(|(_, alignment, _, stats, _)| {
+            **alignment == Alignment::Owned(uid) && !stats.is_dead
+        })
+        .map(|(pet, _, _, stats, _)| (pet, stats.clone()))
+        .collect()
+}
+
+/// Moves the active pet closest to the player into their stable
+fn stable_pet(server: &mut Server, entity: EcsEntity) -> Result<(), String> {
+    let pos = server
+        .state
+        .read_component_copied::<Pos>(entity)
+        .ok_or_else(|| "You have no position!".to_string())?;
+    if server
+        .state
+        .ecs()
+        .read_storage::<Stable>()
+        .get(entity)
+        .map_or(false, |stable| stable.pets.len() >= MAX_STABLED_PETS)
+    {
+        return Err("Your stable is full.".to_string());
+    }
+
+    let (pet, pet_stats, body, growth) = {
+        let positions = server.state.ecs().read_storage::<Pos>();
+        let bodies = server.state.ecs().read_storage::<comp::Body>();
+        let growths = server.state.ecs().read_storage::<Growth>();
+        active_pets(server, entity)
+            .into_iter()
+            .filter_map(|(pet, stats)| {
+                let dist = positions.get(pet)?.0.distance_squared(pos.0);
+                Some((pet, stats, *bodies.get(pet)?, dist))
+            })
+            .filter(|(_, _, _, dist)| *dist < MAX_PET_DIST.powi(2))
+            .min_by(|(_, _, _, a), (_, _, _, b)| {
+                a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .map(|(pet, stats, body, _)| {
+                let growth = growths.get(pet).copied().unwrap_or_default();
+                (pet, stats, body, growth)
+            })
+            .ok_or_else(|| "None of your pets are close enough to be stabled.".to_string())?
+    };
+
+    server
+        .state
+        .ecs_mut()
+        .write_storage::<Stable>()
+        .entry(entity)
+        .map_err(|_| "You have no stable.".to_string())?
+        .or_insert_with(Stable::default)
+        .pets
+        .push(StabledPet {
+            name: pet_stats.name.clone(),
+            body,
+            level: pet_stats.level.level(),
+            growth,
+        });
+    let _ = server.state.delete_entity_recorded(pet);
+
+    server.notify_client(
+        entity,
+        ChatType::CommandInfo.server_msg(format!("{} has been stabled.", pet_stats.name)),
+    );
+    Ok(())
+}
+
+fn spawn_pet(server: &mut Server, owner: EcsEntity, pos: Pos, pet: &StabledPet) {
+    let uid = match server.state.read_component_copied::<Uid>(owner) {
+        Some(uid) => uid,
+        None => return,
+    };
+    let alignment = Alignment::Owned(uid);
+
+    let mut stats = Stats::new(pet.name.clone(), pet.body);
+    stats.level.set_level(pet.level);
+    let scale = pet.growth.apply(pet.body, &mut stats);
+    stats
+        .health
+        .set_to(stats.health.maximum(), comp::HealthSource::Revive);
+
+    let new_pet = server
+        .state
+        .create_npc(
+            pos,
+            stats,
+            LoadoutBuilder::build_loadout(pet.body, alignment, None, false).build(),
+            pet.body,
+        )
+        .with(scale)
+        .with(pet.growth)
+        .with(comp::MountState::Unmounted)
+        .with(alignment)
+        .with(comp::Agent::default())
+        .build();
+
+    // Add to group system
+    let state = server.state();
+    let mut clients = state.ecs().write_storage::<Client>();
+    let uids = state.ecs().read_storage::<Uid>();
+    let mut group_manager = state.ecs().write_resource::<comp::group::GroupManager>();
+    group_manager.new_pet(
+        new_pet,
+        owner,
+        &mut state.ecs().write_storage(),
+        &state.ecs().entities(),
+        &state.ecs().read_storage(),
+        &uids,
+        &mut |entity, group_change| {
+            clients
+                .get_mut(entity)
+                .and_then(|c| {
+                    group_change
+                        .try_map(|e| uids.get(e).copied())
+                        .map(|g| (g, c))
+                })
+                .map(|(g, c)| c.send_msg(ServerGeneral::GroupUpdate(g)));
+        },
+    );
+}
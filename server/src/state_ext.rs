@@ -1,10 +1,9 @@
-use crate::{
-    client::Client, persistence::PersistedComponents, sys::sentinel::DeletedEntities, SpawnPoint,
-};
+use crate::{client::Client, sys::sentinel::DeletedEntities, SpawnPoint};
 use common::{
     character::CharacterId,
     comp,
     effect::Effect,
+    event::{EventBus, ServerEvent},
     msg::{CharacterInfo, ClientInGame, PlayerListUpdate, ServerGeneral, ServerMsg},
     state::State,
     sync::{Uid, UidAllocator, WorldSyncExt},
@@ -56,7 +55,18 @@ pub trait StateExt {
     fn initialize_character_data(&mut self, entity: EcsEntity, character_id: CharacterId);
     /// Update the components associated with the entity's current character.
     /// Performed after loading component data from the database
-    fn update_character_data(&mut self, entity: EcsEntity, components: PersistedComponents);
+    fn update_character_data(
+        &mut self,
+        entity: EcsEntity,
+        components: (
+            comp::Body,
+            comp::Stats,
+            comp::Inventory,
+            comp::Loadout,
+            comp::AbilitySelection,
+            comp::Buffs,
+        ),
+    );
     /// Iterates over registered clients and send each `ServerMsg`
     fn send_chat(&self, msg: comp::UnresolvedChatMsg);
     fn notify_registered_clients(&self, msg: ServerGeneral);
@@ -83,6 +93,18 @@ impl StateExt for State {
                     .get_mut(entity)
                     .map(|stats| stats.exp.change_by(xp));
             },
+            Effect::Dispel { category, count } => {
+                self.ecs()
+                    .read_resource::<EventBus<ServerEvent>>()
+                    .emit_now(ServerEvent::Buff {
+                        entity,
+                        buff_change: comp::BuffChange::Dispel {
+                            categories: vec![category],
+                            count,
+                            immunity: comp::buff::DISPEL_IMMUNITY,
+                        },
+                    });
+            },
         }
     }
 
@@ -204,6 +226,7 @@ impl StateExt for State {
             comp::Alignment::Owned(self.read_component_copied(entity).unwrap()),
         );
         self.write_component(entity, comp::Buffs::default());
+        self.write_component(entity, comp::CombatLog::default());
 
         // Make sure physics components are updated
         self.write_component(entity, comp::ForceUpdate);
@@ -227,8 +250,19 @@ impl StateExt for State {
         }
     }
 
-    fn update_character_data(&mut self, entity: EcsEntity, components: PersistedComponents) {
-        let (body, stats, inventory, loadout) = components;
+    fn update_character_data(
+        &mut self,
+        entity: EcsEntity,
+        components: (
+            comp::Body,
+            comp::Stats,
+            comp::Inventory,
+            comp::Loadout,
+            comp::AbilitySelection,
+            comp::Buffs,
+        ),
+    ) {
+        let (body, stats, inventory, mut loadout, ability_selection, buffs) = components;
 
         if let Some(player_uid) = self.read_component_copied::<Uid>(entity) {
             // Notify clients of a player list update
@@ -247,7 +281,11 @@ impl StateExt for State {
             self.write_component(entity, body);
             self.write_component(entity, stats);
             self.write_component(entity, inventory);
+            // Loaded weapons come with their default abilities
+            loadout.apply_ability_selection(&ability_selection);
             self.write_component(entity, loadout);
+            self.write_component(entity, ability_selection);
+            self.write_component(entity, buffs);
 
             self.write_component(
                 entity,
@@ -0,0 +1,149 @@
+//! Minions summoned by abilities.
+//!
+//! Minions fight alongside whoever summoned them: they join the summoner's
+//! group, so that neither can hurt the other, and vanish once their time is up
+//! or their summoner dies. Each summoner can only have so many minions at once.
+
+use crate::{client::Client, state_ext::StateExt, Server};
+use common::{
+    comp::{self, Alignment, Body, Pos, Stats},
+    loadout_builder::LoadoutBuilder,
+    msg::ServerGeneral,
+    state::Time,
+    sync::{Uid, WorldSyncExt},
+};
+use specs::{Builder, Component, Entity as EcsEntity, Join, WorldExt};
+use specs_idvs::IdvStorage;
+use std::time::Duration;
+use tracing::error;
+use vek::*;
+
+/// How far from their summoner minions appear
+const SUMMON_RADIUS: f32 = 2.0;
+
+/// Marks an entity as a minion summoned by the entity with the `owner` uid
+#[derive(Copy, Clone, Debug)]
+pub struct Owner {
+    pub owner: Uid,
+    /// Time at which the minion vanishes
+    pub expires: f64,
+}
+
+impl Component for Owner {
+    type Storage = IdvStorage<Self>;
+}
+
+/// Summons up to `count` minions with the given body around `summoner`,
+/// without letting them have more than `max_minions` at once
+pub fn handle_summon(
+    server: &mut Server,
+    summoner: EcsEntity,
+    body: Body,
+    count: u32,
+    duration: Duration,
+    max_minions: u32,
+) {
+    let (uid, pos, name, level, count, expires) = {
+        let ecs = server.state.ecs();
+        let (uid, pos, stats) = match (
+            ecs.read_storage::<Uid>().get(summoner).copied(),
+            ecs.read_storage::<Pos>().get(summoner).copied(),
+            ecs.read_storage::<Stats>().get(summoner).cloned(),
+        ) {
+            (Some(uid), Some(pos), Some(stats)) if !stats.is_dead => (uid, pos, stats),
+            _ => return,
+        };
+        let minions = ecs
+            .read_storage::<Owner>()
+            .join()
+            .filter(|minion| minion.owner == uid)
+            .count() as u32;
+        (
+            uid,
+            pos,
+            stats.name,
+            stats.level.level(),
+            count.min(max_minions.saturating_sub(minions)),
+            ecs.read_resource::<Time>().0 + duration.as_secs_f64(),
+        )
+    };
+
+    let alignment = Alignment::Owned(uid);
+    for i in 0..count {
+        // Spread the minions out in a circle around their summoner
+        let angle = i as f32 / count as f32 * 2.0 * std::f32::consts::PI;
+        let offset = Vec2::new(angle.cos(), angle.sin()) * SUMMON_RADIUS;
+
+        let mut stats = Stats::new(format!("{}'s Minion", name), body);
+        stats.level.set_level(level);
+        stats
+            .health
+            .set_to(stats.health.maximum(), comp::HealthSource::Revive);
+
+        let minion = server
+            .state
+            .create_npc(
+                Pos(pos.0 + Vec3::from(offset)),
+                stats,
+                LoadoutBuilder::build_loadout(body, alignment, None, false).build(),
+                body,
+            )
+            .with(alignment)
+            .with(comp::Agent::default())
+            .with(Owner {
+                owner: uid,
+                expires,
+            })
+            .build();
+
+        // Add to group system
+        let state = server.state();
+        let mut clients = state.ecs().write_storage::<Client>();
+        let uids = state.ecs().read_storage::<Uid>();
+        let mut group_manager = state.ecs().write_resource::<comp::group::GroupManager>();
+        group_manager.new_pet(
+            minion,
+            summoner,
+            &mut state.ecs().write_storage(),
+            &state.ecs().entities(),
+            &state.ecs().read_storage(),
+            &uids,
+            &mut |entity, group_change| {
+                clients
+                    .get_mut(entity)
+                    .and_then(|c| {
+                        group_change
+                            .try_map(|e| uids.get(e).copied())
+                            .map(|g| (g, c))
+                    })
+                    .map(|(g, c)| c.send_msg(ServerGeneral::GroupUpdate(g)));
+            },
+        );
+    }
+}
+
+/// Despawns minions whose time is up or whose summoner is dead or gone
+pub fn update_summons(server: &mut Server) {
+    let expired = {
+        let ecs = server.state.ecs();
+        let time = ecs.read_resource::<Time>().0;
+        let stats = ecs.read_storage::<Stats>();
+        (&ecs.entities(), &ecs.read_storage::<Owner>())
+            .join()
+            .filter(|(_, minion)| {
+                minion.expires <= time
+                    || ecs
+                        .entity_from_uid(minion.owner.into())
+                        .and_then(|owner| stats.get(owner))
+                        .map_or(true, |stats| stats.is_dead)
+            })
+            .map(|(minion, _)| minion)
+            .collect::<Vec<_>>()
+    };
+
+    for minion in expired {
+        if let Err(e) = server.state.delete_entity_recorded(minion) {
+            error!(?e, "Failed to delete summoned minion");
+        }
+    }
+}
@@ -7,7 +7,9 @@ use crate::{
     Tick,
 };
 use common::{
-    comp::{ForceUpdate, Inventory, InventoryUpdate, Last, Ori, Player, Pos, Vel},
+    comp::{
+        CombatLog, ForceUpdate, Instance, Inventory, InventoryUpdate, Last, Ori, Player, Pos, Vel,
+    },
     msg::ServerGeneral,
     outcome::Outcome,
     region::{Event as RegionEvent, RegionMap},
@@ -18,7 +20,8 @@ use common::{
     vol::RectVolSize,
 };
 use specs::{
-    Entities, Entity as EcsEntity, Join, Read, ReadExpect, ReadStorage, System, Write, WriteStorage,
+    BitSet, Entities, Entity as EcsEntity, Join, Read, ReadExpect, ReadStorage, System, Write,
+    WriteStorage,
 };
 use vek::*;
 
@@ -37,8 +40,10 @@ impl<'a> System<'a> for Sys {
         ReadStorage<'a, Vel>,
         ReadStorage<'a, Ori>,
         ReadStorage<'a, Inventory>,
+        WriteStorage<'a, CombatLog>,
         ReadStorage<'a, RegionSubscription>,
         ReadStorage<'a, Player>,
+        ReadStorage<'a, Instance>,
         WriteStorage<'a, Last<Pos>>,
         WriteStorage<'a, Last<Vel>>,
         WriteStorage<'a, Last<Ori>>,
@@ -64,8 +69,10 @@ impl<'a> System<'a> for Sys {
             velocities,
             orientations,
             inventories,
+            mut combat_logs,
             subscriptions,
             players,
+            instances,
             mut last_pos,
             mut last_vel,
             mut last_ori,
@@ -108,7 +115,8 @@ impl<'a> System<'a> for Sys {
                 .join()
                 .filter_map(|(client, entity, subscription, pos)| {
                     if client.in_game.is_some() && subscription.regions.contains(&key) {
-                        Some((client, &subscription.regions, entity, *pos))
+                        let instance = instances.get(entity).copied();
+                        Some((client, &subscription.regions, entity, *pos, instance))
                     } else {
                         None
                     }
@@ -135,13 +143,17 @@ impl<'a> System<'a> for Sys {
                                     vel.copied(),
                                     ori.copied(),
                                 ));
-                            for (client, regions, client_entity, _) in &mut subscribers {
+                            for (client, regions, client_entity, _, client_instance) in
+                                &mut subscribers
+                            {
                                 if maybe_key
                                     .as_ref()
                                     .map(|key| !regions.contains(key))
                                     .unwrap_or(true)
                                     // Client doesn't need to know about itself
                                     && *client_entity != entity
+                                    // Or about entities in other dungeon instances
+                                    && instances.get(entity).copied() == *client_instance
                                 {
                                     client.send_msg(create_msg.clone());
                                 }
@@ -151,7 +163,7 @@ impl<'a> System<'a> for Sys {
                     RegionEvent::Left(id, maybe_key) => {
                         // Lookup UID for entity
                         if let Some(&uid) = uids.get(entities.entity(*id)) {
-                            for (client, regions, _, _) in &mut subscribers {
+                            for (client, regions, _, _, _) in &mut subscribers {
                                 if maybe_key
                                     .as_ref()
                                     .map(|key| !regions.contains(key))
@@ -167,26 +179,52 @@ impl<'a> System<'a> for Sys {
 
             // Sync tracked components
             // Get deleted entities in this region from DeletedEntities
-            let (entity_sync_package, comp_sync_package) = trackers.create_sync_packages(
-                &tracked_comps,
-                region.entities(),
-                deleted_entities
-                    .take_deleted_in_region(key)
-                    .unwrap_or_default(),
-            );
-            let entity_sync_msg = ServerGeneral::EntitySync(entity_sync_package);
-            let comp_sync_msg = ServerGeneral::CompSync(comp_sync_package);
-            subscribers.iter_mut().for_each(move |(client, _, _, _)| {
-                client.send_msg(entity_sync_msg.clone());
-                client.send_msg(comp_sync_msg.clone());
-            });
+            let deleted = deleted_entities
+                .take_deleted_in_region(key)
+                .unwrap_or_default();
+            // Clients only know about entities in their own dungeon instance, so each instance
+            // present among the subscribers gets its own packages
+            let mut subscriber_instances = Vec::new();
+            for (_, _, _, _, instance) in &subscribers {
+                if !subscriber_instances.contains(instance) {
+                    subscriber_instances.push(*instance);
+                }
+            }
+            for instance in subscriber_instances {
+                let mut visible = BitSet::new();
+                let filter = if instances.mask().is_empty() {
+                    region.entities()
+                } else {
+                    for (entity, _) in (&entities, region.entities()).join() {
+                        if instances.get(entity).copied() == instance {
+                            visible.add(entity.id());
+                        }
+                    }
+                    &visible
+                };
+                let (entity_sync_package, comp_sync_package) =
+                    trackers.create_sync_packages(&tracked_comps, filter, deleted.clone());
+                let entity_sync_msg = ServerGeneral::EntitySync(entity_sync_package);
+                let comp_sync_msg = ServerGeneral::CompSync(comp_sync_package);
+                subscribers
+                    .iter_mut()
+                    .filter(|(_, _, _, _, client_instance)| *client_instance == instance)
+                    .for_each(|(client, _, _, _, _)| {
+                        client.send_msg(entity_sync_msg.clone());
+                        client.send_msg(comp_sync_msg.clone());
+                    });
+            }
 
             let mut send_msg = |msg: ServerGeneral,
                                 entity: EcsEntity,
                                 pos: Pos,
                                 force_update: Option<&ForceUpdate>,
                                 throttle: bool| {
-                for (client, _, client_entity, client_pos) in &mut subscribers {
+                for (client, _, client_entity, client_pos, client_instance) in &mut subscribers {
+                    if instances.get(entity).copied() != *client_instance {
+                        continue;
+                    }
+
                     if if client_entity == &entity {
                         // Don't send client physics updates about itself unless force update is set
                         force_update.is_some()
@@ -326,6 +364,14 @@ impl<'a> System<'a> for Sys {
             ));
         }
 
+        // Sync combat logs to the clients they belong to
+        for (client, combat_log) in (&mut clients, &mut combat_logs).join() {
+            let events = combat_log.take_unsynced();
+            if !events.is_empty() {
+                client.send_msg(ServerGeneral::CombatLog(events));
+            }
+        }
+
         // Sync outcomes
         for (client, player, pos) in (&mut clients, &players, positions.maybe()).join() {
             let is_near = |o_pos: Vec3<f32>| {
@@ -0,0 +1,92 @@
+use super::{SysScheduler, SysTimer};
+use crate::husbandry::{Growth, BREED_RANGE};
+use common::{
+    comp::{Alignment, Body, Pos, Scale, Stats},
+    event::{EventBus, ServerEvent},
+    span,
+};
+use specs::{Entities, Join, Read, ReadStorage, System, Write, WriteStorage};
+
+/// This system grows up juvenile animals and lets pets of the same owner that
+/// are kept together breed
+pub struct Sys;
+impl<'a> System<'a> for Sys {
+    #[allow(clippy::type_complexity)]
+    type SystemData = (
+        Entities<'a>,
+        ReadStorage<'a, Pos>,
+        ReadStorage<'a, Body>,
+        ReadStorage<'a, Alignment>,
+        WriteStorage<'a, Stats>,
+        WriteStorage<'a, Scale>,
+        WriteStorage<'a, Growth>,
+        Read<'a, EventBus<ServerEvent>>,
+        Write<'a, SysScheduler<Self>>,
+        Write<'a, SysTimer<Self>>,
+    );
+
+    fn run(
+        &mut self,
+        (
+            entities,
+            positions,
+            bodies,
+            alignments,
+            mut stats,
+            mut scales,
+            mut growths,
+            server_bus,
+            mut scheduler,
+            mut timer,
+        ): Self::SystemData,
+    ) {
+        span!(_guard, "run", "husbandry::Sys::run");
+        if !scheduler.should_run() {
+            return;
+        }
+        timer.start();
+
+        // Growth is measured in real time, so this only needs to run every now and then
+        let dt = scheduler.interval().as_secs_f64();
+
+        for (entity, body, stats, growth) in (&entities, &bodies, &mut stats, &mut growths).join() {
+            growth.breed_cooldown = (growth.breed_cooldown - dt).max(0.0);
+            if !growth.is_adult() {
+                growth.age += dt;
+                let _ = scales.insert(entity, growth.apply(*body, stats));
+            }
+        }
+
+        let breeders = (&entities, &positions, &bodies, &alignments, &growths)
+            .join()
+            .filter(|(_, _, _, alignment, growth)| {
+                matches!(alignment, Alignment::Owned(_)) && growth.can_breed()
+            })
+            .map(|(entity, pos, body, alignment, _)| (entity, pos, body, alignment))
+            .collect::<Vec<_>>();
+
+        let mut server_emitter = server_bus.emitter();
+        let mut bred = Vec::new();
+        for (i, (entity, pos, body, alignment)) in breeders.iter().enumerate() {
+            if bred.contains(entity) {
+                continue;
+            }
+            let mate = breeders[i + 1..]
+                .iter()
+                .find(|(mate, mate_pos, mate_body, mate_alignment)| {
+                    !bred.contains(mate)
+                        && mate_alignment == alignment
+                        && body.can_breed_with(mate_body)
+                        && mate_pos.0.distance_squared(pos.0) < BREED_RANGE.powi(2)
+                })
+                .map(|(mate, _, _, _)| *mate);
+            if let Some(mate) = mate {
+                bred.push(*entity);
+                bred.push(mate);
+                server_emitter.emit(ServerEvent::Breed(*entity, mate));
+            }
+        }
+
+        timer.end();
+    }
+}
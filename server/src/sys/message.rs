@@ -5,7 +5,7 @@ use crate::{
     client::Client,
     login_provider::LoginProvider,
     metrics::{NetworkRequestMetrics, PlayerMetrics},
-    persistence::character_loader::CharacterLoader,
+    persistence::{block_log::BlockLogger, character_loader::CharacterLoader},
     EditableSettings, Settings,
 };
 use common::{
@@ -94,6 +94,7 @@ impl Sys {
         force_updates: &ReadStorage<'_, ForceUpdate>,
         stats: &mut WriteStorage<'_, Stats>,
         block_changes: &mut Write<'_, BlockChange>,
+        block_logger: &ReadExpect<'_, BlockLogger>,
         positions: &mut WriteStorage<'_, Pos>,
         velocities: &mut WriteStorage<'_, Vel>,
         orientations: &mut WriteStorage<'_, Ori>,
@@ -180,11 +181,16 @@ impl Sys {
             ClientGeneral::BreakBlock(pos) => {
                 if let Some(block) = can_build.get(entity).and_then(|_| terrain.get(pos).ok()) {
                     block_changes.set(pos, block.into_vacant());
+                    if let Some(player) = players.get(entity) {
+                        block_logger.log(player.uuid(), pos, *block, block.into_vacant());
+                    }
                 }
             },
             ClientGeneral::PlaceBlock(pos, block) => {
-                if can_build.get(entity).is_some() {
-                    block_changes.try_set(pos, block);
+                if can_build.get(entity).is_some() && block_changes.try_set(pos, block).is_some() {
+                    if let (Some(player), Ok(old_block)) = (players.get(entity), terrain.get(pos)) {
+                        block_logger.log(player.uuid(), pos, *old_block, block);
+                    }
                 }
             },
             ClientGeneral::TerrainChunkRequest { key } => {
@@ -358,9 +364,19 @@ impl Sys {
         login_provider: &mut WriteExpect<'_, LoginProvider>,
         admins: &mut WriteStorage<'_, Admin>,
         players: &mut WriteStorage<'_, Player>,
+        settings: &Read<'_, Settings>,
         editable_settings: &ReadExpect<'_, EditableSettings>,
         msg: ClientRegister,
     ) -> Result<(), crate::error::Error> {
+        if let Some(allowed) = &settings.allowed_asset_packs {
+            if msg.asset_packs.iter().any(|hash| !allowed.contains(hash)) {
+                client.register_stream.send(ServerRegisterAnswer::Err(
+                    RegisterError::AssetPacksNotAllowed,
+                ))?;
+                return Ok(());
+            }
+        }
+
         let (username, uuid) = match login_provider.try_login(
             &msg.token_or_username,
             &*editable_settings.admins,
@@ -436,6 +452,7 @@ impl Sys {
         chat_modes: &ReadStorage<'_, ChatMode>,
         login_provider: &mut WriteExpect<'_, LoginProvider>,
         block_changes: &mut Write<'_, BlockChange>,
+        block_logger: &ReadExpect<'_, BlockLogger>,
         admins: &mut WriteStorage<'_, Admin>,
         positions: &mut WriteStorage<'_, Pos>,
         velocities: &mut WriteStorage<'_, Vel>,
@@ -498,6 +515,7 @@ impl Sys {
                     force_updates,
                     stats,
                     block_changes,
+                    block_logger,
                     positions,
                     velocities,
                     orientations,
@@ -537,6 +555,7 @@ impl Sys {
                     login_provider,
                     admins,
                     players,
+                    settings,
                     editable_settings,
                     msg?,
                 )?;
@@ -565,6 +584,7 @@ impl<'a> System<'a> for Sys {
         ReadStorage<'a, ChatMode>,
         WriteExpect<'a, LoginProvider>,
         Write<'a, BlockChange>,
+        ReadExpect<'a, BlockLogger>,
         WriteStorage<'a, Admin>,
         WriteStorage<'a, Pos>,
         WriteStorage<'a, Vel>,
@@ -598,6 +618,7 @@ impl<'a> System<'a> for Sys {
             chat_modes,
             mut accounts,
             mut block_changes,
+            block_logger,
             mut admins,
             mut positions,
             mut velocities,
@@ -659,6 +680,7 @@ impl<'a> System<'a> for Sys {
                     &chat_modes,
                     &mut accounts,
                     &mut block_changes,
+                    &block_logger,
                     &mut admins,
                     &mut positions,
                     &mut velocities,
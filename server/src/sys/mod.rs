@@ -1,4 +1,5 @@
 pub mod entity_sync;
+pub mod husbandry;
 pub mod invite_timeout;
 pub mod message;
 pub mod object;
@@ -25,6 +26,8 @@ pub type WaypointTimer = SysTimer<waypoint::Sys>;
 pub type InviteTimeoutTimer = SysTimer<invite_timeout::Sys>;
 pub type PersistenceTimer = SysTimer<persistence::Sys>;
 pub type PersistenceScheduler = SysScheduler<persistence::Sys>;
+pub type HusbandryTimer = SysTimer<husbandry::Sys>;
+pub type HusbandryScheduler = SysScheduler<husbandry::Sys>;
 
 // System names
 // Note: commented names may be useful in the future
@@ -37,6 +40,7 @@ const WAYPOINT_SYS: &str = "server_waypoint_sys";
 const INVITE_TIMEOUT_SYS: &str = "server_invite_timeout_sys";
 const PERSISTENCE_SYS: &str = "server_persistence_sys";
 const OBJECT_SYS: &str = "server_object_sys";
+const HUSBANDRY_SYS: &str = "server_husbandry_sys";
 
 pub fn add_server_systems(dispatch_builder: &mut DispatcherBuilder) {
     dispatch_builder.add(terrain::Sys, TERRAIN_SYS, &[]);
@@ -44,6 +48,7 @@ pub fn add_server_systems(dispatch_builder: &mut DispatcherBuilder) {
     dispatch_builder.add(invite_timeout::Sys, INVITE_TIMEOUT_SYS, &[]);
     dispatch_builder.add(persistence::Sys, PERSISTENCE_SYS, &[]);
     dispatch_builder.add(object::Sys, OBJECT_SYS, &[]);
+    dispatch_builder.add(husbandry::Sys, HUSBANDRY_SYS, &[]);
 }
 
 pub fn run_sync_systems(ecs: &mut specs::World) {
@@ -75,6 +80,8 @@ impl<S> SysScheduler<S> {
         }
     }
 
+    pub fn interval(&self) -> Duration { self.interval }
+
     pub fn should_run(&mut self) -> bool {
         if self.last_run.elapsed() > self.interval {
             self.last_run = Instant::now();
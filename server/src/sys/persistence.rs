@@ -1,10 +1,13 @@
 use crate::{
-    persistence::character_updater,
+    market::Reputation,
+    persistence::{character_updater, world_time::WorldTimeSaver},
+    stable::Stable,
     sys::{SysScheduler, SysTimer},
 };
 use common::{
-    comp::{Inventory, Loadout, Player, Stats},
+    comp::{AbilitySelection, Buffs, Inventory, Loadout, Player, Stats},
     span,
+    state::TimeOfDay,
 };
 use specs::{Join, ReadExpect, ReadStorage, System, Write};
 
@@ -17,7 +20,13 @@ impl<'a> System<'a> for Sys {
         ReadStorage<'a, Stats>,
         ReadStorage<'a, Inventory>,
         ReadStorage<'a, Loadout>,
+        ReadStorage<'a, AbilitySelection>,
+        ReadStorage<'a, Buffs>,
+        ReadStorage<'a, Stable>,
+        ReadStorage<'a, Reputation>,
         ReadExpect<'a, character_updater::CharacterUpdater>,
+        ReadExpect<'a, TimeOfDay>,
+        ReadExpect<'a, WorldTimeSaver>,
         Write<'a, SysScheduler<Self>>,
         Write<'a, SysTimer<Self>>,
     );
@@ -29,7 +38,13 @@ impl<'a> System<'a> for Sys {
             player_stats,
             player_inventories,
             player_loadouts,
+            player_ability_selections,
+            player_buffs,
+            player_stables,
+            player_reputations,
             updater,
+            time_of_day,
+            world_time_saver,
             mut scheduler,
             mut timer,
         ): Self::SystemData,
@@ -43,14 +58,33 @@ impl<'a> System<'a> for Sys {
                     &player_stats,
                     &player_inventories,
                     &player_loadouts,
+                    player_ability_selections.maybe(),
+                    player_buffs.maybe(),
+                    player_stables.maybe(),
+                    player_reputations.maybe(),
                 )
                     .join()
-                    .filter_map(|(player, stats, inventory, loadout)| {
-                        player
-                            .character_id
-                            .map(|id| (id, stats, inventory, loadout))
-                    }),
+                    .filter_map(
+                        |(
+                            player,
+                            stats,
+                            inventory,
+                            loadout,
+                            selection,
+                            buffs,
+                            stable,
+                            reputation,
+                        )| {
+                            player.character_id.map(|id| {
+                                (
+                                    id, stats, inventory, loadout, selection, buffs, stable,
+                                    reputation,
+                                )
+                            })
+                        },
+                    ),
             );
+            world_time_saver.save(time_of_day.0);
             timer.end();
         }
     }
@@ -1,9 +1,9 @@
 use super::SysTimer;
 use common::{
     comp::{
-        BeamSegment, Body, Buffs, CanBuild, CharacterState, Collider, Energy, Gravity, Group, Item,
-        LightEmitter, Loadout, Mass, MountState, Mounting, Ori, Player, Pos, Scale, Shockwave,
-        Stats, Sticky, Vel,
+        AbilitySelection, BeamSegment, Body, Buffs, CanBuild, CharacterState, Collider, Energy,
+        Gravity, Group, Item, LightEmitter, Loadout, Mass, MountState, Mounting, Ori, Player, Pos,
+        Scale, Shockwave, Stats, Sticky, Vel,
     },
     msg::EcsCompPacket,
     span,
@@ -58,6 +58,7 @@ pub struct TrackedComps<'a> {
     pub sticky: ReadStorage<'a, Sticky>,
     pub gravity: ReadStorage<'a, Gravity>,
     pub loadout: ReadStorage<'a, Loadout>,
+    pub ability_selection: ReadStorage<'a, AbilitySelection>,
     pub character_state: ReadStorage<'a, CharacterState>,
     pub shockwave: ReadStorage<'a, Shockwave>,
     pub beam_segment: ReadStorage<'a, BeamSegment>,
@@ -136,6 +137,10 @@ impl<'a> TrackedComps<'a> {
             .get(entity)
             .cloned()
             .map(|c| comps.push(c.into()));
+        self.ability_selection
+            .get(entity)
+            .cloned()
+            .map(|c| comps.push(c.into()));
         self.character_state
             .get(entity)
             .cloned()
@@ -176,6 +181,7 @@ pub struct ReadTrackers<'a> {
     pub sticky: ReadExpect<'a, UpdateTracker<Sticky>>,
     pub gravity: ReadExpect<'a, UpdateTracker<Gravity>>,
     pub loadout: ReadExpect<'a, UpdateTracker<Loadout>>,
+    pub ability_selection: ReadExpect<'a, UpdateTracker<AbilitySelection>>,
     pub character_state: ReadExpect<'a, UpdateTracker<CharacterState>>,
     pub shockwave: ReadExpect<'a, UpdateTracker<Shockwave>>,
     pub beam_segment: ReadExpect<'a, UpdateTracker<BeamSegment>>,
@@ -212,6 +218,12 @@ impl<'a> ReadTrackers<'a> {
             .with_component(&comps.uid, &*self.sticky, &comps.sticky, filter)
             .with_component(&comps.uid, &*self.gravity, &comps.gravity, filter)
             .with_component(&comps.uid, &*self.loadout, &comps.loadout, filter)
+            .with_component(
+                &comps.uid,
+                &*self.ability_selection,
+                &comps.ability_selection,
+                filter,
+            )
             .with_component(
                 &comps.uid,
                 &*self.character_state,
@@ -245,6 +257,7 @@ pub struct WriteTrackers<'a> {
     sticky: WriteExpect<'a, UpdateTracker<Sticky>>,
     gravity: WriteExpect<'a, UpdateTracker<Gravity>>,
     loadout: WriteExpect<'a, UpdateTracker<Loadout>>,
+    ability_selection: WriteExpect<'a, UpdateTracker<AbilitySelection>>,
     character_state: WriteExpect<'a, UpdateTracker<CharacterState>>,
     shockwave: WriteExpect<'a, UpdateTracker<Shockwave>>,
     beam: WriteExpect<'a, UpdateTracker<BeamSegment>>,
@@ -270,6 +283,9 @@ fn record_changes(comps: &TrackedComps, trackers: &mut WriteTrackers) {
     trackers.sticky.record_changes(&comps.sticky);
     trackers.gravity.record_changes(&comps.gravity);
     trackers.loadout.record_changes(&comps.loadout);
+    trackers
+        .ability_selection
+        .record_changes(&comps.ability_selection);
     trackers
         .character_state
         .record_changes(&comps.character_state);
@@ -306,6 +322,7 @@ fn record_changes(comps: &TrackedComps, trackers: &mut WriteTrackers) {
     log_counts!(sticky, "Stickies");
     log_counts!(gravity, "Gravitys");
     log_counts!(loadout, "Loadouts");
+    log_counts!(ability_selection, "Ability Selections");
     log_counts!(character_state, "Character States");
     log_counts!(shockwave, "Shockwaves");
     log_counts!(beam, "Beams");
@@ -331,6 +348,7 @@ pub fn register_trackers(world: &mut World) {
     world.register_tracker::<Sticky>();
     world.register_tracker::<Gravity>();
     world.register_tracker::<Loadout>();
+    world.register_tracker::<AbilitySelection>();
     world.register_tracker::<CharacterState>();
     world.register_tracker::<Shockwave>();
     world.register_tracker::<BeamSegment>();
@@ -4,7 +4,7 @@ use super::{
 };
 use crate::client::{self, Client, RegionSubscription};
 use common::{
-    comp::{Ori, Player, Pos, Vel},
+    comp::{Instance, Ori, Player, Pos, Vel},
     msg::ServerGeneral,
     region::{region_in_vd, regions_in_vd, Event as RegionEvent, RegionMap},
     span,
@@ -32,6 +32,7 @@ impl<'a> System<'a> for Sys {
         ReadStorage<'a, Vel>,
         ReadStorage<'a, Ori>,
         ReadStorage<'a, Player>,
+        ReadStorage<'a, Instance>,
         WriteStorage<'a, Client>,
         WriteStorage<'a, RegionSubscription>,
         Write<'a, DeletedEntities>,
@@ -50,6 +51,7 @@ impl<'a> System<'a> for Sys {
             velocities,
             orientations,
             players,
+            instances,
             mut clients,
             mut subscriptions,
             mut deleted_entities,
@@ -192,7 +194,10 @@ impl<'a> System<'a> for Sys {
                                 &entities,
                             )
                                 .join()
-                                .filter(|(_, _, _, _, e)| *e != client_entity)
+                                .filter(|(_, _, _, _, e)| {
+                                    *e != client_entity
+                                        && instances.get(*e) == instances.get(client_entity)
+                                })
                             {
                                 // Send message to create entity and tracked components and physics
                                 // components
@@ -237,6 +242,8 @@ pub fn initialize_region_subscription(world: &World, entity: specs::Entity) {
 
         let region_map = world.read_resource::<RegionMap>();
         let tracked_comps = TrackedComps::fetch(world);
+        let instances = world.read_storage::<Instance>();
+        let client_instance = instances.get(entity);
         for key in &regions {
             if let Some(region) = region_map.get(*key) {
                 for (pos, vel, ori, _, entity) in (
@@ -247,6 +254,8 @@ pub fn initialize_region_subscription(world: &World, entity: specs::Entity) {
                     &world.entities(),
                 )
                     .join()
+                    // Entities in other dungeon instances are invisible to the client
+                    .filter(|(_, _, _, _, e)| instances.get(*e) == client_instance)
                 {
                     // Send message to create entity and tracked components and physics components
                     client.send_msg(ServerGeneral::CreateEntity(
@@ -275,3 +284,27 @@ pub fn initialize_region_subscription(world: &World, entity: specs::Entity) {
         );
     }
 }
+
+/// Deletes every entity the client currently knows about and sends them again,
+/// for when the set of entities visible to it changes (e.g. when entering a
+/// dungeon instance)
+pub fn reset_region_subscription(world: &World, entity: specs::Entity) {
+    if let (Some(subscription), Some(client)) = (
+        world.read_storage::<RegionSubscription>().get(entity),
+        world.write_storage::<Client>().get_mut(entity),
+    ) {
+        let region_map = world.read_resource::<RegionMap>();
+        let uids = world.read_storage::<Uid>();
+        for key in &subscription.regions {
+            if let Some(region) = region_map.get(*key) {
+                for (&uid, _, e) in (&uids, region.entities(), &world.entities()).join() {
+                    if e != entity {
+                        client.send_msg(ServerGeneral::DeleteEntity(uid));
+                    }
+                }
+            }
+        }
+    }
+
+    initialize_region_subscription(world, entity);
+}
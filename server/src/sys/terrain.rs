@@ -1,9 +1,11 @@
 use super::SysTimer;
-use crate::{chunk_generator::ChunkGenerator, client::Client, Tick};
+use crate::{
+    bounty::Bounties, chunk_generator::ChunkGenerator, client::Client, territory::Territories, Tick,
+};
 use common::{
     comp::{self, bird_medium, Alignment, Player, Pos},
     event::{EventBus, ServerEvent},
-    generation::get_npc_name,
+    generation::{get_npc_name, EntityInfo},
     msg::ServerGeneral,
     npc::NPC_NAMES,
     span,
@@ -12,7 +14,7 @@ use common::{
     LoadoutBuilder,
 };
 use rand::Rng;
-use specs::{Join, Read, ReadStorage, System, Write, WriteExpect, WriteStorage};
+use specs::{Join, Read, ReadExpect, ReadStorage, System, Write, WriteExpect, WriteStorage};
 use std::sync::Arc;
 use vek::*;
 
@@ -32,6 +34,8 @@ impl<'a> System<'a> for Sys {
         WriteExpect<'a, ChunkGenerator>,
         WriteExpect<'a, TerrainGrid>,
         Write<'a, TerrainChanges>,
+        ReadExpect<'a, Territories>,
+        ReadExpect<'a, Bounties>,
         ReadStorage<'a, Pos>,
         ReadStorage<'a, Player>,
         WriteStorage<'a, Client>,
@@ -46,6 +50,8 @@ impl<'a> System<'a> for Sys {
             mut chunk_generator,
             mut terrain,
             mut terrain_changes,
+            territories,
+            bounties,
             positions,
             players,
             mut clients,
@@ -112,84 +118,15 @@ impl<'a> System<'a> for Sys {
                     continue;
                 }
 
-                let mut body = entity.body;
-                let name = entity.name.unwrap_or_else(|| "Unnamed".to_string());
-                let alignment = entity.alignment;
-                let main_tool = entity.main_tool;
-                let mut stats = comp::Stats::new(name, body);
-                // let damage = stats.level.level() as i32; TODO: Make NPC base damage
-                // non-linearly depend on their level
-
-                let mut scale = entity.scale;
-
-                // TODO: Remove this and implement scaling or level depending on stuff like
-                // species instead
-                stats.level.set_level(
-                    entity.level.unwrap_or_else(|| {
-                        (rand::thread_rng().gen_range(1, 9) as f32 * scale) as u32
-                    }),
-                );
-
-                // Replace stuff if it's a boss
-                if entity.is_giant {
-                    if rand::random::<f32>() < 0.65 && entity.alignment != Alignment::Enemy {
-                        let body_new = comp::humanoid::Body::random();
-                        body = comp::Body::Humanoid(body_new);
-                        stats = comp::Stats::new(
-                            format!(
-                                "Gentle Giant {}",
-                                get_npc_name(&NPC_NAMES.humanoid, body_new.species)
-                            ),
-                            body,
-                        );
-                    }
-                    stats.level.set_level(rand::thread_rng().gen_range(30, 35));
-                    scale = 2.0 + rand::random::<f32>();
+                // Outlaws stay dead once their bounty has been claimed
+                if bounties.is_claimed(&entity) {
+                    continue;
                 }
 
-                let loadout =
-                    LoadoutBuilder::build_loadout(body, alignment, main_tool, entity.is_giant)
-                        .build();
-
-                stats.update_max_hp(stats.body_type);
-
-                stats
-                    .health
-                    .set_to(stats.health.maximum(), comp::HealthSource::Revive);
-
-                let can_speak = match body {
-                    comp::Body::Humanoid(_) => alignment == comp::Alignment::Npc,
-                    comp::Body::BirdMedium(bird_medium) => match bird_medium.species {
-                        // Parrots like to have a word in this, too...
-                        bird_medium::Species::Parrot => alignment == comp::Alignment::Npc,
-                        _ => false,
-                    },
-                    _ => false,
-                };
-
-                // TODO: This code sets an appropriate base_damage for the enemy. This doesn't
-                // work because the damage is now saved in an ability
-                /*
-                if let Some(item::ItemKind::Tool(item::ToolData { base_damage, .. })) =
-                    &mut loadout.active_item.map(|i| i.item.kind)
-                {
-                    *base_damage = stats.level.level() as u32 * 3;
+                // Whoever holds an outpost decides what lives there
+                if let Some(entity) = territories.adjust_spawn(entity) {
+                    server_emitter.emit(create_npc_event(entity));
                 }
-                */
-                server_emitter.emit(ServerEvent::CreateNpc {
-                    pos: Pos(entity.pos),
-                    stats,
-                    loadout,
-                    agent: if entity.has_agency {
-                        Some(comp::Agent::new(entity.pos, can_speak, &body))
-                    } else {
-                        None
-                    },
-                    body,
-                    alignment,
-                    scale: comp::Scale(scale),
-                    drop_item: entity.loot_drop,
-                })
             }
         }
 
@@ -248,3 +185,93 @@ pub fn chunk_in_vd(
 
     adjusted_dist_sqr <= vd.pow(2)
 }
+
+/// Turns an entity generated alongside terrain into the event that spawns it
+pub fn create_npc_event(entity: EntityInfo) -> ServerEvent {
+    let mut body = entity.body;
+    let name = entity.name.unwrap_or_else(|| "Unnamed".to_string());
+    let alignment = entity.alignment;
+    let main_tool = entity.main_tool;
+    let mut stats = comp::Stats::new(name, body);
+    // let damage = stats.level.level() as i32; TODO: Make NPC base damage
+    // non-linearly depend on their level
+
+    let mut scale = entity.scale;
+
+    // TODO: Remove this and implement scaling or level depending on stuff like
+    // species instead
+    stats.level.set_level(
+        entity
+            .level
+            .unwrap_or_else(|| (rand::thread_rng().gen_range(1, 9) as f32 * scale) as u32),
+    );
+
+    // Replace stuff if it's a boss
+    if entity.is_giant {
+        if rand::random::<f32>() < 0.65 && entity.alignment != Alignment::Enemy {
+            let body_new = comp::humanoid::Body::random();
+            body = comp::Body::Humanoid(body_new);
+            stats = comp::Stats::new(
+                format!(
+                    "Gentle Giant {}",
+                    get_npc_name(&NPC_NAMES.humanoid, body_new.species)
+                ),
+                body,
+            );
+        }
+        stats.level.set_level(rand::thread_rng().gen_range(30, 35));
+        scale = 2.0 + rand::random::<f32>();
+    }
+
+    let loadout =
+        LoadoutBuilder::build_loadout(body, alignment, main_tool, entity.is_giant).build();
+
+    stats.update_max_hp(stats.body_type);
+
+    stats
+        .health
+        .set_to(stats.health.maximum(), comp::HealthSource::Revive);
+
+    let can_speak = match body {
+        comp::Body::Humanoid(_) => alignment == comp::Alignment::Npc,
+        comp::Body::BirdMedium(bird_medium) => match bird_medium.species {
+            // Parrots like to have a word in this, too...
+            bird_medium::Species::Parrot => alignment == comp::Alignment::Npc,
+            _ => false,
+        },
+        _ => false,
+    };
+
+    // TODO: This code sets an appropriate base_damage for the enemy. This doesn't
+    // work because the damage is now saved in an ability
+    /*
+    if let Some(item::ItemKind::Tool(item::ToolData { base_damage, .. })) =
+        &mut loadout.active_item.map(|i| i.item.kind)
+    {
+        *base_damage = stats.level.level() as u32 * 3;
+    }
+    */
+    ServerEvent::CreateNpc {
+        pos: Pos(entity.pos),
+        stats,
+        loadout,
+        agent: if entity.has_agency {
+            Some(comp::Agent {
+                facing: entity.facing,
+                night_patrol: entity.night_patrol,
+                ..comp::Agent::new(entity.pos, can_speak, &body)
+            })
+        } else {
+            None
+        },
+        body,
+        alignment,
+        scale: comp::Scale(scale),
+        drop_item: entity.loot_drop,
+        boss: entity
+            .boss
+            .map(|boss| comp::BossState::new(comp::BossConfig::load_expect(&boss))),
+        market: entity.market,
+        bounty: entity.bounty,
+    }
+}
@@ -0,0 +1,489 @@
+//! Outposts that factions fight over.
+//!
+//! Castles double as outposts. Every so often an outpost near players comes
+//! under attack from the faction opposing whoever holds it, and on servers
+//! that allow it player factions can seize outposts by holding them. The owner
+//! of an outpost decides what spawns around it, whether a merchant trades
+//! there and whether it is safe from player attacks.
+
+use crate::{events::handle_create_npc, state_ext::StateExt, sys, Server, Tick};
+use common::{
+    comp::{self, Alignment, ChatType, Item},
+    event::ServerEvent,
+    generation::EntityInfo,
+    state::Time,
+    terrain::Block,
+    vol::ReadVol,
+};
+use rand::{seq::SliceRandom, Rng};
+use specs::{Entity as EcsEntity, Join, WorldExt};
+use tracing::{debug, error};
+use vek::*;
+
+/// How many ticks pass between updates of the contested outposts
+const UPDATE_INTERVAL: u64 = 30;
+/// Seconds between attacks on outposts
+const EVENT_INTERVAL: f64 = 20.0 * 60.0;
+/// How close a player has to be to an outpost for it to be attacked
+const EVENT_RANGE: f32 = 300.0;
+/// Seconds that attackers need to outnumber defenders for to take an outpost
+const CAPTURE_TIME: f32 = 60.0;
+/// Seconds after which attackers give up on taking an outpost
+const CONTEST_DURATION: f64 = 5.0 * 60.0;
+/// Number of NPCs in a raiding party
+const RAID_SIZE: usize = 6;
+/// Number of NPCs defending an outpost when it is attacked
+const DEFENDER_COUNT: usize = 4;
+/// Level of the NPCs fighting over outposts
+const FIGHTER_LEVEL: u32 = 15;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TerritoryOwner {
+    /// Guards of the nearby towns
+    Guards,
+    /// Bandits preying on travellers
+    Bandits,
+    /// A player faction
+    Players(String),
+}
+
+impl TerritoryOwner {
+    pub fn name(&self) -> String {
+        match self {
+            TerritoryOwner::Guards => "The town guard".to_string(),
+            TerritoryOwner::Bandits => "Bandits".to_string(),
+            TerritoryOwner::Players(faction) => format!("The faction {}", faction),
+        }
+    }
+
+    /// Whether the owner is hostile towards players
+    pub fn is_hostile(&self) -> bool { matches!(self, TerritoryOwner::Bandits) }
+
+    /// The faction that tries to take outposts away from this owner
+    fn rival(&self) -> TerritoryOwner {
+        match self {
+            TerritoryOwner::Bandits => TerritoryOwner::Guards,
+            TerritoryOwner::Guards | TerritoryOwner::Players(_) => TerritoryOwner::Bandits,
+        }
+    }
+
+    /// What the owner's NPCs look like, if it has any
+    fn fighter(&self, pos: Vec3<f32>) -> Option<EntityInfo> {
+        let (alignment, name, weapon) = match self {
+            TerritoryOwner::Guards => (
+                Alignment::Npc,
+                "Outpost Guard",
+                "common.items.weapons.sword.short_sword_0",
+            ),
+            TerritoryOwner::Bandits => (
+                Alignment::Enemy,
+                "Bandit Raider",
+                "common.items.weapons.sword.zweihander_sword_0",
+            ),
+            TerritoryOwner::Players(_) => return None,
+        };
+        Some(
+            EntityInfo::at(pos)
+                .with_body(comp::Body::Humanoid(comp::humanoid::Body::random()))
+                .with_alignment(alignment)
+                .with_name(name)
+                .with_level(FIGHTER_LEVEL)
+                .with_main_tool(Item::new_from_asset_expect(weapon)),
+        )
+    }
+}
+
+pub struct Contest {
+    pub attacker: TerritoryOwner,
+    /// Share of the capture completed by the attackers, from 0 to 1
+    pub progress: f32,
+    started: f64,
+    attackers: Vec<EcsEntity>,
+    defenders: Vec<EcsEntity>,
+}
+
+pub struct Outpost {
+    /// Site id of the castle
+    pub site: u64,
+    pub center: Vec2<i32>,
+    pub radius: f32,
+    pub owner: TerritoryOwner,
+    pub contest: Option<Contest>,
+    merchant: Option<EcsEntity>,
+}
+
+impl Outpost {
+    pub fn contains(&self, wpos: Vec2<f32>) -> bool {
+        wpos.distance_squared(self.center.map(|e| e as f32)) < self.radius.powi(2)
+    }
+
+    /// Outposts held by friendly factions keep players safe from each other,
+    /// unless they are under attack
+    pub fn is_safe_zone(&self) -> bool { !self.owner.is_hostile() && self.contest.is_none() }
+}
+
+#[derive(Default)]
+pub struct Territories {
+    outposts: Vec<Outpost>,
+    next_event: f64,
+}
+
+impl Territories {
+    pub fn new(outposts: Vec<(u64, Vec2<i32>, f32, bool)>) -> Self {
+        Self {
+            outposts: outposts
+                .into_iter()
+                .map(|(site, center, radius, hostile)| Outpost {
+                    site,
+                    center,
+                    radius,
+                    owner: if hostile {
+                        TerritoryOwner::Bandits
+                    } else {
+                        TerritoryOwner::Guards
+                    },
+                    contest: None,
+                    merchant: None,
+                })
+                .collect(),
+            next_event: EVENT_INTERVAL,
+        }
+    }
+
+    pub fn outposts(&self) -> &[Outpost] { &self.outposts }
+
+    pub fn outpost_at(&self, wpos: Vec2<f32>) -> Option<&Outpost> {
+        self.outposts.iter().find(|outpost| outpost.contains(wpos))
+    }
+
+    pub fn is_safe_zone(&self, wpos: Vec2<f32>) -> bool {
+        self.outpost_at(wpos).map_or(false, Outpost::is_safe_zone)
+    }
+
+    /// Changes an entity spawned alongside terrain to suit the owner of the
+    /// outpost it spawns in, or removes it entirely
+    pub fn adjust_spawn(&self, mut entity: EntityInfo) -> Option<EntityInfo> {
+        match self
+            .outpost_at(entity.pos.xy())
+            .map(|outpost| &outpost.owner)
+        {
+            // Friendly factions keep their outposts clear of hostile creatures
+            Some(owner) if !owner.is_hostile() => {
+                if entity.alignment == Alignment::Enemy && !entity.is_giant {
+                    return None;
+                }
+            },
+            // Townsfolk don't take kindly to bandits moving in
+            Some(_) => {
+                if entity.alignment == Alignment::Npc {
+                    entity.alignment = Alignment::Enemy;
+                }
+            },
+            None => {},
+        }
+        Some(entity)
+    }
+}
+
+/// Starts attacks on outposts, decides the outcome of ongoing ones and keeps
+/// merchants at friendly outposts.
+pub fn update_territories(server: &mut Server) {
+    if !server.settings().territory_events
+        || server.state.ecs().read_resource::<Tick>().0 % UPDATE_INTERVAL != 0
+    {
+        return;
+    }
+
+    let time = server.state.ecs().read_resource::<Time>().0;
+    let dt = UPDATE_INTERVAL as f32 * server.state.get_delta_time();
+    let outpost_count = server
+        .state
+        .ecs()
+        .read_resource::<Territories>()
+        .outposts
+        .len();
+
+    let players = {
+        let ecs = server.state.ecs();
+        (
+            &ecs.read_storage::<comp::Player>(),
+            &ecs.read_storage::<comp::Pos>(),
+            &ecs.read_storage::<comp::Stats>(),
+            ecs.read_storage::<comp::Faction>().maybe(),
+        )
+            .join()
+            .filter(|(_, _, stats, _)| !stats.is_dead)
+            .map(|(_, pos, _, faction)| (pos.0.xy(), faction.map(|f| f.0.clone())))
+            .collect::<Vec<_>>()
+    };
+
+    for i in 0..outpost_count {
+        update_contest(server, i, time, dt, &players);
+        update_merchant(server, i, &players);
+    }
+
+    // Player factions lay claim to outposts by entering them
+    if server.settings().player_territory_capture {
+        for i in 0..outpost_count {
+            let claimant = {
+                let territories = server.state.ecs().read_resource::<Territories>();
+                let outpost = &territories.outposts[i];
+                players
+                    .iter()
+                    .filter(|(pos, _)| outpost.contest.is_none() && outpost.contains(*pos))
+                    .filter_map(|(_, faction)| faction.clone())
+                    .map(TerritoryOwner::Players)
+                    .find(|faction| *faction != outpost.owner)
+            };
+            if let Some(claimant) = claimant {
+                start_contest(server, i, claimant, time);
+            }
+        }
+    }
+
+    // Raid an outpost near players
+    let target = {
+        let mut territories = server.state.ecs().write_resource::<Territories>();
+        if time >= territories.next_event {
+            territories.next_event = time + EVENT_INTERVAL;
+            let targets = territories
+                .outposts
+                .iter()
+                .enumerate()
+                .filter(|(_, outpost)| {
+                    outpost.contest.is_none()
+                        && players.iter().any(|(pos, _)| {
+                            pos.distance(outpost.center.map(|e| e as f32))
+                                < outpost.radius + EVENT_RANGE
+                        })
+                })
+                .map(|(i, outpost)| (i, outpost.owner.rival()))
+                .collect::<Vec<_>>();
+            targets.choose(&mut rand::thread_rng()).cloned()
+        } else {
+            None
+        }
+    };
+    if let Some((i, attacker)) = target {
+        start_contest(server, i, attacker, time);
+    }
+}
+
+fn start_contest(server: &mut Server, i: usize, attacker: TerritoryOwner, time: f64) {
+    let (site, center, radius, owner) = {
+        let territories = server.state.ecs().read_resource::<Territories>();
+        let outpost = &territories.outposts[i];
+        (
+            outpost.site,
+            outpost.center.map(|e| e as f32),
+            outpost.radius,
+            outpost.owner.clone(),
+        )
+    };
+    debug!(?site, ?owner, ?attacker, "Outpost attacked");
+
+    // Player factions fight for themselves, NPC factions send their own fighters
+    let mut rng = rand::thread_rng();
+    let attackers = (0..RAID_SIZE)
+        .filter_map(|_| {
+            let angle = rng.gen_range(0.0, std::f32::consts::PI * 2.0);
+            let offset = Vec2::new(angle.cos(), angle.sin()) * (radius + 16.0);
+            spawn_fighter(server, &attacker, center + offset)
+        })
+        .collect::<Vec<_>>();
+    // Guards don't fight players, so player factions only have to drive out bandits
+    let defender_count = match attacker {
+        TerritoryOwner::Players(_) if !owner.is_hostile() => 0,
+        _ => DEFENDER_COUNT,
+    };
+    let defenders = (0..defender_count)
+        .filter_map(|_| {
+            let offset = Vec2::new(rng.gen_range(-8.0, 8.0), rng.gen_range(-8.0, 8.0));
+            spawn_fighter(server, &owner, center + offset)
+        })
+        .collect::<Vec<_>>();
+
+    server.state.send_chat(ChatType::Meta.chat_msg(format!(
+        "{} are attacking an outpost held by {}!",
+        attacker.name(),
+        owner.name().to_lowercase(),
+    )));
+    let mut territories = server.state.ecs().write_resource::<Territories>();
+    territories.outposts[i].contest = Some(Contest {
+        attacker,
+        progress: 0.0,
+        started: time,
+        attackers,
+        defenders,
+    });
+}
+
+/// Moves the capture of an outpost along depending on which side has more
+/// fighters inside it, and ends the attack once it is decided, sending the
+/// fighters of both sides that are left away.
+fn update_contest(
+    server: &mut Server,
+    i: usize,
+    time: f64,
+    dt: f32,
+    players: &[(Vec2<f32>, Option<String>)],
+) {
+    let (message, fighters) = {
+        let ecs = server.state.ecs();
+        let positions = ecs.read_storage::<comp::Pos>();
+        let stats = ecs.read_storage::<comp::Stats>();
+        let mut territories = ecs.write_resource::<Territories>();
+        let outpost = &mut territories.outposts[i];
+        let contest = match &mut outpost.contest {
+            Some(contest) => contest,
+            None => return,
+        };
+
+        let area = (outpost.center.map(|e| e as f32), outpost.radius);
+        let in_area = |pos: Vec2<f32>| pos.distance_squared(area.0) < area.1.powi(2);
+        // Counts the fighters of a side that are still standing, and those of them
+        // inside the outpost
+        let count = |npcs: &[EcsEntity], side: &TerritoryOwner| {
+            let standing = npcs
+                .iter()
+                .filter(|npc| {
+                    ecs.is_alive(**npc) && stats.get(**npc).map_or(false, |stats| !stats.is_dead)
+                })
+                .collect::<Vec<_>>();
+            let inside = standing
+                .iter()
+                .filter(|npc| {
+                    positions
+                        .get(***npc)
+                        .map_or(false, |pos| in_area(pos.0.xy()))
+                })
+                .count();
+            let members = match side {
+                TerritoryOwner::Players(name) => players
+                    .iter()
+                    .filter(|(pos, faction)| in_area(*pos) && faction.as_ref() == Some(name))
+                    .count(),
+                _ => 0,
+            };
+            (standing.len() + members, inside + members)
+        };
+        let (attackers_left, attackers) = count(&contest.attackers, &contest.attacker);
+        let (_, defenders) = count(&contest.defenders, &outpost.owner);
+
+        if attackers > defenders {
+            contest.progress = (contest.progress + dt / CAPTURE_TIME).min(1.0);
+        } else if defenders > attackers {
+            contest.progress = (contest.progress - dt / CAPTURE_TIME).max(0.0);
+        }
+
+        let message = if contest.progress >= 1.0 {
+            let old_owner = std::mem::replace(&mut outpost.owner, contest.attacker.clone());
+            format!(
+                "{} took an outpost from {}!",
+                outpost.owner.name(),
+                old_owner.name().to_lowercase()
+            )
+        } else if attackers_left == 0 || time > contest.started + CONTEST_DURATION {
+            format!(
+                "{} held off the attack on their outpost.",
+                outpost.owner.name()
+            )
+        } else {
+            return;
+        };
+        debug!(site = ?outpost.site, owner = ?outpost.owner, "Outpost attack ended");
+        let fighters = outpost
+            .contest
+            .take()
+            .into_iter()
+            .flat_map(|contest| contest.attackers.into_iter().chain(contest.defenders))
+            .filter(|npc| ecs.is_alive(*npc))
+            .collect::<Vec<_>>();
+        (message, fighters)
+    };
+    server.state.send_chat(ChatType::Meta.chat_msg(message));
+    for npc in fighters {
+        if let Err(e) = server.state.delete_entity_recorded(npc) {
+            error!(?e, "Failed to delete outpost fighter");
+        }
+    }
+}
+
+/// Keeps a merchant at outposts held by friendly factions while players are
+/// around, and sends them away while the outpost is hostile or under attack.
+fn update_merchant(server: &mut Server, i: usize, players: &[(Vec2<f32>, Option<String>)]) {
+    let (center, wants_merchant, merchant) = {
+        let ecs = server.state.ecs();
+        let territories = ecs.read_resource::<Territories>();
+        let outpost = &territories.outposts[i];
+        let center = outpost.center.map(|e| e as f32);
+        let players_near = players
+            .iter()
+            .any(|(pos, _)| pos.distance(center) < outpost.radius + EVENT_RANGE);
+        (
+            center,
+            outpost.is_safe_zone() && players_near,
+            outpost.merchant.filter(|merchant| ecs.is_alive(*merchant)),
+        )
+    };
+
+    let merchant = match (merchant, wants_merchant) {
+        (Some(merchant), false) => {
+            if let Err(e) = server.state.delete_entity_recorded(merchant) {
+                error!(?e, "Failed to delete outpost merchant");
+            }
+            None
+        },
+        (None, true) => ground_pos(server, center).and_then(|pos| {
+            spawn_npc(
+                server,
+                EntityInfo::at(pos)
+                    .with_body(comp::Body::Humanoid(comp::humanoid::Body::random()))
+                    .with_alignment(Alignment::Npc)
+                    .with_name("Outpost Merchant"),
+            )
+        }),
+        (merchant, _) => merchant,
+    };
+    let mut territories = server.state.ecs().write_resource::<Territories>();
+    territories.outposts[i].merchant = merchant;
+}
+
+fn spawn_fighter(server: &mut Server, side: &TerritoryOwner, wpos: Vec2<f32>) -> Option<EcsEntity> {
+    let info = side.fighter(ground_pos(server, wpos)?)?;
+    spawn_npc(server, info)
+}
+
+fn spawn_npc(server: &mut Server, info: EntityInfo) -> Option<EcsEntity> {
+    if let ServerEvent::CreateNpc {
+        pos,
+        stats,
+        loadout,
+        body,
+        agent,
+        alignment,
+        scale,
+        drop_item,
+        boss,
+        market,
+        bounty,
+    } = sys::terrain::create_npc_event(info)
+    {
+        Some(handle_create_npc(
+            server, pos, stats, loadout, body, agent, alignment, scale, drop_item, boss, market,
+            bounty,
+        ))
+    } else {
+        None
+    }
+}
+
+/// Finds the top of the ground at a position, if its terrain is loaded
+fn ground_pos(server: &Server, wpos: Vec2<f32>) -> Option<Vec3<f32>> {
+    let terrain = server.state.terrain();
+    let chunk = terrain.get_key(terrain.pos_key(wpos.map(|e| e.floor() as i32).into()))?;
+    let top = Vec3::new(wpos.x, wpos.y, chunk.get_max_z() as f32);
+    let bottom = Vec3::new(wpos.x, wpos.y, chunk.get_min_z() as f32);
+    let (dist, _) = terrain.ray(top, bottom).until(Block::is_solid).cast();
+    Some(top - Vec3::unit_z() * (dist - 1.0))
+}
@@ -40,6 +40,31 @@ impl World {
 
     pub fn tick(&self, dt: Duration) {}
 
+    pub fn dungeon_at(&self, _index: IndexRef, _wpos: Vec3<i32>) -> Option<(u64, Aabb<i32>)> {
+        None
+    }
+
+    pub fn dungeon_bounds(&self, _index: IndexRef) -> Vec<Aabb<i32>> { Vec::new() }
+
+    pub fn dungeon_supplement(
+        &self,
+        _index: IndexRef,
+        _site: u64,
+        _rng: &mut impl Rng,
+    ) -> ChunkSupplement {
+        ChunkSupplement::default()
+    }
+
+    pub fn dungeon_reusable_keys(&self, _index: IndexRef, _site: u64) -> bool { false }
+
+    pub fn outposts(&self, _index: IndexRef) -> Vec<(u64, Vec2<i32>, f32, bool)> { Vec::new() }
+
+    pub fn bounties(&self) -> Vec<(String, String, Vec2<i32>, Vec2<i32>, u32)> { Vec::new() }
+
+    pub fn markets(&self, _index: IndexRef) -> Vec<(u64, Vec2<i32>, f32, Vec<(String, f32, f32)>)> {
+        Vec::new()
+    }
+
     #[inline(always)]
     pub const fn map_size_lg(&self) -> MapSizeLg { DEFAULT_WORLD_CHUNKS_LG }
 
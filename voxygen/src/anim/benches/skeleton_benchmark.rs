@@ -0,0 +1,47 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use voxygen_anim::{
+    arthropod::ArthropodSkeleton, biped_large::BipedLargeSkeleton, bird_medium::BirdMediumSkeleton,
+    bird_small::BirdSmallSkeleton, character::CharacterSkeleton, colossus::ColossusSkeleton,
+    compute_matrices, dragon::DragonSkeleton, fish_medium::FishMediumSkeleton,
+    fish_small::FishSmallSkeleton, fixture::FixtureSkeleton, golem::GolemSkeleton,
+    object::ObjectSkeleton, quadruped_low::QuadrupedLowSkeleton,
+    quadruped_medium::QuadrupedMediumSkeleton, quadruped_small::QuadrupedSmallSkeleton,
+    theropod::TheropodSkeleton, turret::TurretSkeleton, vek::*, Skeleton, MAX_BONE_COUNT,
+};
+
+fn bench_skeleton<S: Skeleton + Default>(c: &mut Criterion, name: &str) {
+    let skeleton = S::default();
+    let base_mat = Mat4::rotation_z(0.7) * Mat4::scaling_3d(Vec3::from(1.5));
+    let mut buf = [Default::default(); MAX_BONE_COUNT];
+    c.bench_function(&format!("compute matrices: {}", name), |b| {
+        b.iter(|| {
+            black_box(compute_matrices(
+                black_box(&skeleton),
+                black_box(base_mat),
+                &mut buf,
+            ));
+        })
+    });
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    bench_skeleton::<ArthropodSkeleton>(c, "arthropod");
+    bench_skeleton::<BipedLargeSkeleton>(c, "biped_large");
+    bench_skeleton::<BirdMediumSkeleton>(c, "bird_medium");
+    bench_skeleton::<BirdSmallSkeleton>(c, "bird_small");
+    bench_skeleton::<CharacterSkeleton>(c, "character");
+    bench_skeleton::<ColossusSkeleton>(c, "colossus");
+    bench_skeleton::<DragonSkeleton>(c, "dragon");
+    bench_skeleton::<FishMediumSkeleton>(c, "fish_medium");
+    bench_skeleton::<FishSmallSkeleton>(c, "fish_small");
+    bench_skeleton::<FixtureSkeleton>(c, "fixture");
+    bench_skeleton::<GolemSkeleton>(c, "golem");
+    bench_skeleton::<ObjectSkeleton>(c, "object");
+    bench_skeleton::<QuadrupedLowSkeleton>(c, "quadruped_low");
+    bench_skeleton::<QuadrupedMediumSkeleton>(c, "quadruped_medium");
+    bench_skeleton::<QuadrupedSmallSkeleton>(c, "quadruped_small");
+    bench_skeleton::<TheropodSkeleton>(c, "theropod");
+    bench_skeleton::<TurretSkeleton>(c, "turret");
+}
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
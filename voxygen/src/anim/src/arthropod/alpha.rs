@@ -0,0 +1,56 @@
+use super::{
+    super::{vek::*, Animation},
+    ArthropodSkeleton, SkeletonAttr,
+};
+use std::f32::consts::PI;
+
+pub struct AlphaAnimation;
+
+impl Animation for AlphaAnimation {
+    type Dependency = (f32, f64);
+    type Skeleton = ArthropodSkeleton;
+
+    #[cfg(feature = "use-dyn-lib")]
+    const UPDATE_FN: &'static [u8] = b"arthropod_alpha\0";
+
+    #[cfg_attr(feature = "be-dyn-lib", export_name = "arthropod_alpha")]
+    fn update_skeleton_inner(
+        skeleton: &Self::Skeleton,
+        (_velocity, _global_time): Self::Dependency,
+        anim_time: f64,
+        _rate: &mut f32,
+        skeleton_attr: &SkeletonAttr,
+    ) -> Self::Skeleton {
+        let mut next = (*skeleton).clone();
+
+        // Reared back during the wind up, then lunging forward to either snap
+        // the mandibles shut or, for those with one, drive the stinger down
+        let windup = (anim_time as f32 / 0.4).min(1.0);
+        let strike = ((anim_time as f32 - 0.4) / 0.15).max(0.0).min(1.0);
+        let lunge = windup - strike * 1.8;
+        let snap = (strike * PI).sin();
+
+        next.head.orientation = Quaternion::rotation_x(lunge * 0.3);
+
+        next.mandible_l.orientation = Quaternion::rotation_z(windup * -0.5 + snap * 0.8);
+        next.mandible_r.orientation = Quaternion::rotation_z(windup * 0.5 - snap * 0.8);
+
+        next.chest.position = Vec3::new(
+            0.0,
+            skeleton_attr.chest.0 + lunge * -1.0,
+            skeleton_attr.chest.1 + windup * 0.5,
+        ) * skeleton_attr.scaler
+            / 11.0;
+        next.chest.orientation = Quaternion::rotation_x(lunge * 0.15);
+
+        if skeleton_attr.stinger {
+            next.abdomen.orientation = Quaternion::rotation_x(1.2 + windup * 0.6 - strike * 1.2);
+        }
+
+        next.leg_fl.orientation =
+            Quaternion::rotation_z(-0.5) * Quaternion::rotation_y(windup * 0.3);
+        next.leg_fr.orientation =
+            Quaternion::rotation_z(0.5) * Quaternion::rotation_y(windup * -0.3);
+        next
+    }
+}
@@ -0,0 +1,144 @@
+use super::{
+    super::{vek::*, Animation},
+    ArthropodSkeleton, SkeletonAttr,
+};
+use std::{f32::consts::PI, ops::Mul};
+
+pub struct IdleAnimation;
+
+impl Animation for IdleAnimation {
+    type Dependency = f64;
+    type Skeleton = ArthropodSkeleton;
+
+    #[cfg(feature = "use-dyn-lib")]
+    const UPDATE_FN: &'static [u8] = b"arthropod_idle\0";
+
+    #[cfg_attr(feature = "be-dyn-lib", export_name = "arthropod_idle")]
+    fn update_skeleton_inner(
+        skeleton: &Self::Skeleton,
+        global_time: Self::Dependency,
+        anim_time: f64,
+        _rate: &mut f32,
+        skeleton_attr: &SkeletonAttr,
+    ) -> Self::Skeleton {
+        let mut next = (*skeleton).clone();
+
+        let breathe = (anim_time as f32 * 1.2 + 1.5 * PI).sin();
+        let twitch = (anim_time as f32 * 9.0).sin().max(0.0).powf(8.0);
+
+        let look = Vec2::new(
+            ((global_time + anim_time) as f32 / 4.0)
+                .floor()
+                .mul(7331.0)
+                .sin()
+                * 0.25,
+            ((global_time + anim_time) as f32 / 4.0)
+                .floor()
+                .mul(1337.0)
+                .sin()
+                * 0.1,
+        );
+
+        next.head.position = Vec3::new(0.0, skeleton_attr.head.0, skeleton_attr.head.1);
+        next.head.orientation = Quaternion::rotation_z(look.x) * Quaternion::rotation_x(look.y);
+        next.head.scale = Vec3::one();
+
+        next.mandible_l.position = Vec3::new(
+            -skeleton_attr.mandible.0,
+            skeleton_attr.mandible.1,
+            skeleton_attr.mandible.2,
+        );
+        next.mandible_l.orientation = Quaternion::rotation_z(twitch * -0.3);
+        next.mandible_l.scale = Vec3::one();
+
+        next.mandible_r.position = Vec3::new(
+            skeleton_attr.mandible.0,
+            skeleton_attr.mandible.1,
+            skeleton_attr.mandible.2,
+        );
+        next.mandible_r.orientation = Quaternion::rotation_z(twitch * 0.3);
+        next.mandible_r.scale = Vec3::one();
+
+        next.chest.position = Vec3::new(
+            0.0,
+            skeleton_attr.chest.0,
+            skeleton_attr.chest.1 + breathe * 0.1,
+        ) * skeleton_attr.scaler
+            / 11.0;
+        next.chest.orientation = Quaternion::rotation_x(0.0);
+        next.chest.scale = Vec3::one() * skeleton_attr.scaler / 11.0;
+
+        next.abdomen.position = Vec3::new(0.0, skeleton_attr.abdomen.0, skeleton_attr.abdomen.1);
+        next.abdomen.orientation = if skeleton_attr.stinger {
+            Quaternion::rotation_x(1.2 + breathe * 0.05)
+        } else {
+            Quaternion::rotation_x(breathe * 0.03)
+        };
+        next.abdomen.scale = Vec3::one() * (1.0 + breathe * 0.02);
+
+        next.leg_fl.position = Vec3::new(
+            -skeleton_attr.leg_f.0,
+            skeleton_attr.leg_f.1,
+            skeleton_attr.leg_f.2,
+        );
+        next.leg_fl.orientation = Quaternion::rotation_z(-0.5);
+        next.leg_fl.scale = Vec3::one();
+
+        next.leg_fcl.position = Vec3::new(
+            -skeleton_attr.leg_fc.0,
+            skeleton_attr.leg_fc.1,
+            skeleton_attr.leg_fc.2,
+        );
+        next.leg_fcl.orientation = Quaternion::rotation_z(-0.15);
+        next.leg_fcl.scale = Vec3::one();
+
+        next.leg_bcl.position = Vec3::new(
+            -skeleton_attr.leg_bc.0,
+            skeleton_attr.leg_bc.1,
+            skeleton_attr.leg_bc.2,
+        );
+        next.leg_bcl.orientation = Quaternion::rotation_z(0.15);
+        next.leg_bcl.scale = Vec3::one();
+
+        next.leg_bl.position = Vec3::new(
+            -skeleton_attr.leg_b.0,
+            skeleton_attr.leg_b.1,
+            skeleton_attr.leg_b.2,
+        );
+        next.leg_bl.orientation = Quaternion::rotation_z(0.5);
+        next.leg_bl.scale = Vec3::one();
+
+        next.leg_fr.position = Vec3::new(
+            skeleton_attr.leg_f.0,
+            skeleton_attr.leg_f.1,
+            skeleton_attr.leg_f.2,
+        );
+        next.leg_fr.orientation = Quaternion::rotation_z(0.5);
+        next.leg_fr.scale = Vec3::one();
+
+        next.leg_fcr.position = Vec3::new(
+            skeleton_attr.leg_fc.0,
+            skeleton_attr.leg_fc.1,
+            skeleton_attr.leg_fc.2,
+        );
+        next.leg_fcr.orientation = Quaternion::rotation_z(0.15);
+        next.leg_fcr.scale = Vec3::one();
+
+        next.leg_bcr.position = Vec3::new(
+            skeleton_attr.leg_bc.0,
+            skeleton_attr.leg_bc.1,
+            skeleton_attr.leg_bc.2,
+        );
+        next.leg_bcr.orientation = Quaternion::rotation_z(-0.15);
+        next.leg_bcr.scale = Vec3::one();
+
+        next.leg_br.position = Vec3::new(
+            skeleton_attr.leg_b.0,
+            skeleton_attr.leg_b.1,
+            skeleton_attr.leg_b.2,
+        );
+        next.leg_br.orientation = Quaternion::rotation_z(-0.5);
+        next.leg_br.scale = Vec3::one();
+        next
+    }
+}
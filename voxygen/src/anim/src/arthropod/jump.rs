@@ -0,0 +1,126 @@
+use super::{
+    super::{vek::*, Animation},
+    ArthropodSkeleton, SkeletonAttr,
+};
+
+pub struct JumpAnimation;
+
+impl Animation for JumpAnimation {
+    type Dependency = (f32, f64);
+    type Skeleton = ArthropodSkeleton;
+
+    #[cfg(feature = "use-dyn-lib")]
+    const UPDATE_FN: &'static [u8] = b"arthropod_jump\0";
+
+    #[cfg_attr(feature = "be-dyn-lib", export_name = "arthropod_jump")]
+    fn update_skeleton_inner(
+        skeleton: &Self::Skeleton,
+        _global_time: Self::Dependency,
+        _anim_time: f64,
+        _rate: &mut f32,
+        skeleton_attr: &SkeletonAttr,
+    ) -> Self::Skeleton {
+        let mut next = (*skeleton).clone();
+
+        next.head.position = Vec3::new(0.0, skeleton_attr.head.0, skeleton_attr.head.1);
+        next.head.orientation = Quaternion::rotation_x(0.2);
+        next.head.scale = Vec3::one();
+
+        next.mandible_l.position = Vec3::new(
+            -skeleton_attr.mandible.0,
+            skeleton_attr.mandible.1,
+            skeleton_attr.mandible.2,
+        );
+        next.mandible_l.orientation = Quaternion::rotation_z(0.3);
+        next.mandible_l.scale = Vec3::one();
+
+        next.mandible_r.position = Vec3::new(
+            skeleton_attr.mandible.0,
+            skeleton_attr.mandible.1,
+            skeleton_attr.mandible.2,
+        );
+        next.mandible_r.orientation = Quaternion::rotation_z(-0.3);
+        next.mandible_r.scale = Vec3::one();
+
+        next.chest.position = Vec3::new(0.0, skeleton_attr.chest.0, skeleton_attr.chest.1)
+            * skeleton_attr.scaler
+            / 11.0;
+        next.chest.orientation = Quaternion::rotation_x(0.1);
+        next.chest.scale = Vec3::one() * skeleton_attr.scaler / 11.0;
+
+        next.abdomen.position = Vec3::new(0.0, skeleton_attr.abdomen.0, skeleton_attr.abdomen.1);
+        next.abdomen.orientation = if skeleton_attr.stinger {
+            Quaternion::rotation_x(1.4)
+        } else {
+            Quaternion::rotation_x(-0.1)
+        };
+        next.abdomen.scale = Vec3::one();
+
+        // All legs are spread out and raised, ready to grab onto whatever is
+        // landed on
+        next.leg_fl.position = Vec3::new(
+            -skeleton_attr.leg_f.0,
+            skeleton_attr.leg_f.1,
+            skeleton_attr.leg_f.2,
+        );
+        next.leg_fl.orientation = Quaternion::rotation_z(-0.7) * Quaternion::rotation_y(0.4);
+        next.leg_fl.scale = Vec3::one();
+
+        next.leg_fcl.position = Vec3::new(
+            -skeleton_attr.leg_fc.0,
+            skeleton_attr.leg_fc.1,
+            skeleton_attr.leg_fc.2,
+        );
+        next.leg_fcl.orientation = Quaternion::rotation_z(-0.25) * Quaternion::rotation_y(0.3);
+        next.leg_fcl.scale = Vec3::one();
+
+        next.leg_bcl.position = Vec3::new(
+            -skeleton_attr.leg_bc.0,
+            skeleton_attr.leg_bc.1,
+            skeleton_attr.leg_bc.2,
+        );
+        next.leg_bcl.orientation = Quaternion::rotation_z(0.25) * Quaternion::rotation_y(0.3);
+        next.leg_bcl.scale = Vec3::one();
+
+        next.leg_bl.position = Vec3::new(
+            -skeleton_attr.leg_b.0,
+            skeleton_attr.leg_b.1,
+            skeleton_attr.leg_b.2,
+        );
+        next.leg_bl.orientation = Quaternion::rotation_z(0.7) * Quaternion::rotation_y(0.2);
+        next.leg_bl.scale = Vec3::one();
+
+        next.leg_fr.position = Vec3::new(
+            skeleton_attr.leg_f.0,
+            skeleton_attr.leg_f.1,
+            skeleton_attr.leg_f.2,
+        );
+        next.leg_fr.orientation = Quaternion::rotation_z(0.7) * Quaternion::rotation_y(-0.4);
+        next.leg_fr.scale = Vec3::one();
+
+        next.leg_fcr.position = Vec3::new(
+            skeleton_attr.leg_fc.0,
+            skeleton_attr.leg_fc.1,
+            skeleton_attr.leg_fc.2,
+        );
+        next.leg_fcr.orientation = Quaternion::rotation_z(0.25) * Quaternion::rotation_y(-0.3);
+        next.leg_fcr.scale = Vec3::one();
+
+        next.leg_bcr.position = Vec3::new(
+            skeleton_attr.leg_bc.0,
+            skeleton_attr.leg_bc.1,
+            skeleton_attr.leg_bc.2,
+        );
+        next.leg_bcr.orientation = Quaternion::rotation_z(-0.25) * Quaternion::rotation_y(-0.3);
+        next.leg_bcr.scale = Vec3::one();
+
+        next.leg_br.position = Vec3::new(
+            skeleton_attr.leg_b.0,
+            skeleton_attr.leg_b.1,
+            skeleton_attr.leg_b.2,
+        );
+        next.leg_br.orientation = Quaternion::rotation_z(-0.7) * Quaternion::rotation_y(-0.2);
+        next.leg_br.scale = Vec3::one();
+        next
+    }
+}
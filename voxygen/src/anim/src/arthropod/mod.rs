@@ -0,0 +1,167 @@
+pub mod alpha;
+pub mod idle;
+pub mod jump;
+pub mod run;
+
+// Reexports
+pub use self::{
+    alpha::AlphaAnimation, idle::IdleAnimation, jump::JumpAnimation, run::RunAnimation,
+};
+
+use super::{make_bone, vek::*, FigureBoneData, Skeleton};
+use common::comp::{self};
+use core::convert::TryFrom;
+
+pub type Body = comp::arthropod::Body;
+
+skeleton_impls!(struct ArthropodSkeleton {
+    + head,
+    + mandible_l,
+    + mandible_r,
+    + chest,
+    + abdomen,
+    + leg_fl,
+    + leg_fcl,
+    + leg_bcl,
+    + leg_bl,
+    + leg_fr,
+    + leg_fcr,
+    + leg_bcr,
+    + leg_br,
+});
+
+impl Skeleton for ArthropodSkeleton {
+    type Attr = SkeletonAttr;
+    type Body = Body;
+
+    const BONE_COUNT: usize = 13;
+    #[cfg(feature = "use-dyn-lib")]
+    const COMPUTE_FN: &'static [u8] = b"arthropod_compute_mats\0";
+
+    #[cfg_attr(feature = "be-dyn-lib", export_name = "arthropod_compute_mats")]
+    fn compute_matrices_inner(
+        &self,
+        base_mat: Mat4<f32>,
+        buf: &mut [FigureBoneData; super::MAX_BONE_COUNT],
+    ) -> Vec3<f32> {
+        let [head, mandible_l, mandible_r, chest, abdomen, leg_fl, leg_fcl, leg_bcl, leg_bl, leg_fr, leg_fcr, leg_bcr, leg_br] =
+            self.local_mats();
+
+        let chest_mat = base_mat * chest;
+        let head_mat = chest_mat * head;
+
+        *(<&mut [_; Self::BONE_COUNT]>::try_from(&mut buf[0..Self::BONE_COUNT]).unwrap()) = [
+            make_bone(head_mat),
+            make_bone(head_mat * mandible_l),
+            make_bone(head_mat * mandible_r),
+            make_bone(chest_mat),
+            make_bone(chest_mat * abdomen),
+            make_bone(chest_mat * leg_fl),
+            make_bone(chest_mat * leg_fcl),
+            make_bone(chest_mat * leg_bcl),
+            make_bone(chest_mat * leg_bl),
+            make_bone(chest_mat * leg_fr),
+            make_bone(chest_mat * leg_fcr),
+            make_bone(chest_mat * leg_bcr),
+            make_bone(chest_mat * leg_br),
+        ];
+        Vec3::default()
+    }
+}
+
+pub struct SkeletonAttr {
+    head: (f32, f32),
+    mandible: (f32, f32, f32),
+    chest: (f32, f32),
+    abdomen: (f32, f32),
+    leg_f: (f32, f32, f32),
+    leg_fc: (f32, f32, f32),
+    leg_bc: (f32, f32, f32),
+    leg_b: (f32, f32, f32),
+    scaler: f32,
+    /// Whether the abdomen ends in a stinger that gets curled over the back
+    /// when attacking, rather than being dragged along behind
+    stinger: bool,
+}
+
+impl<'a> std::convert::TryFrom<&'a comp::Body> for SkeletonAttr {
+    type Error = ();
+
+    fn try_from(body: &'a comp::Body) -> Result<Self, Self::Error> {
+        match body {
+            comp::Body::Arthropod(body) => Ok(SkeletonAttr::from(body)),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Default for SkeletonAttr {
+    fn default() -> Self {
+        Self {
+            head: (0.0, 0.0),
+            mandible: (0.0, 0.0, 0.0),
+            chest: (0.0, 0.0),
+            abdomen: (0.0, 0.0),
+            leg_f: (0.0, 0.0, 0.0),
+            leg_fc: (0.0, 0.0, 0.0),
+            leg_bc: (0.0, 0.0, 0.0),
+            leg_b: (0.0, 0.0, 0.0),
+            scaler: 0.0,
+            stinger: false,
+        }
+    }
+}
+
+impl<'a> From<&'a Body> for SkeletonAttr {
+    fn from(body: &'a Body) -> Self {
+        use comp::arthropod::Species::*;
+        Self {
+            head: match (body.species, body.body_type) {
+                (Spider, _) => (5.0, 0.5),
+                (Scorpion, _) => (6.0, 0.0),
+                (Beetle, _) => (7.0, 0.5),
+            },
+            mandible: match (body.species, body.body_type) {
+                (Spider, _) => (1.5, 3.0, -1.0),
+                (Scorpion, _) => (2.5, 4.0, -0.5),
+                (Beetle, _) => (2.0, 4.5, 0.0),
+            },
+            chest: match (body.species, body.body_type) {
+                (Spider, _) => (0.0, 6.0),
+                (Scorpion, _) => (0.0, 5.0),
+                (Beetle, _) => (0.0, 6.5),
+            },
+            abdomen: match (body.species, body.body_type) {
+                (Spider, _) => (-5.0, 1.5),
+                (Scorpion, _) => (-6.0, 1.0),
+                (Beetle, _) => (-6.5, 0.5),
+            },
+            leg_f: match (body.species, body.body_type) {
+                (Spider, _) => (3.0, 3.0, -1.0),
+                (Scorpion, _) => (3.0, 2.5, -1.5),
+                (Beetle, _) => (3.5, 3.5, -2.0),
+            },
+            leg_fc: match (body.species, body.body_type) {
+                (Spider, _) => (3.5, 1.0, -1.0),
+                (Scorpion, _) => (3.5, 1.0, -1.5),
+                (Beetle, _) => (4.0, 1.0, -2.0),
+            },
+            leg_bc: match (body.species, body.body_type) {
+                (Spider, _) => (3.5, -1.0, -1.0),
+                (Scorpion, _) => (3.5, -1.0, -1.5),
+                (Beetle, _) => (4.0, -1.5, -2.0),
+            },
+            leg_b: match (body.species, body.body_type) {
+                (Spider, _) => (3.0, -3.0, -1.0),
+                (Scorpion, _) => (3.0, -3.0, -1.5),
+                (Beetle, _) => (3.5, -4.0, -2.0),
+            },
+            scaler: match (body.species, body.body_type) {
+                (Spider, _) => 1.0,
+                (Scorpion, _) => 1.1,
+                (Beetle, _) => 1.2,
+            },
+            stinger: matches!(body.species, Scorpion),
+        }
+    }
+}
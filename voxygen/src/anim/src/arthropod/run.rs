@@ -0,0 +1,166 @@
+use super::{
+    super::{vek::*, Animation},
+    ArthropodSkeleton, SkeletonAttr,
+};
+use std::f32::consts::PI;
+
+pub struct RunAnimation;
+
+impl Animation for RunAnimation {
+    type Dependency = (f32, Vec3<f32>, Vec3<f32>, f64);
+    type Skeleton = ArthropodSkeleton;
+
+    #[cfg(feature = "use-dyn-lib")]
+    const UPDATE_FN: &'static [u8] = b"arthropod_run\0";
+
+    #[cfg_attr(feature = "be-dyn-lib", export_name = "arthropod_run")]
+    fn update_skeleton_inner(
+        skeleton: &Self::Skeleton,
+        (velocity, orientation, last_ori, _global_time): Self::Dependency,
+        anim_time: f64,
+        rate: &mut f32,
+        skeleton_attr: &SkeletonAttr,
+    ) -> Self::Skeleton {
+        let mut next = (*skeleton).clone();
+
+        *rate = (velocity / 6.0).min(1.5).max(0.5);
+
+        let step = anim_time as f32 * 14.0;
+
+        // Each leg runs a quarter of a cycle behind the one in front of it,
+        // sending a wave down either side of the body, while the right side
+        // moves half a cycle apart from the left so that both sides are never
+        // lifted at once
+        let phase_l = [step, step - PI * 0.5, step - PI, step - PI * 1.5];
+        let phase_r = [step + PI, step + PI * 0.5, step, step - PI * 0.5];
+        // Legs swing forward while lifted and push back while planted
+        let swing = |phase: f32| phase.sin() * 0.3;
+        let lift = |phase: f32| phase.cos().max(0.0) * 0.4;
+
+        let bob = (step * 4.0).sin();
+
+        let ori: Vec2<f32> = Vec2::from(orientation);
+        let last_ori = Vec2::from(last_ori);
+        let tilt = if ::vek::Vec2::new(ori, last_ori)
+            .map(|o| o.magnitude_squared())
+            .map(|m| m > 0.001 && m.is_finite())
+            .reduce_and()
+            && ori.angle_between(last_ori).is_finite()
+        {
+            ori.angle_between(last_ori).min(0.2)
+                * last_ori.determine_side(Vec2::zero(), ori).signum()
+        } else {
+            0.0
+        };
+
+        next.head.position = Vec3::new(0.0, skeleton_attr.head.0, skeleton_attr.head.1);
+        next.head.orientation = Quaternion::rotation_z(tilt * -1.0);
+        next.head.scale = Vec3::one();
+
+        next.mandible_l.position = Vec3::new(
+            -skeleton_attr.mandible.0,
+            skeleton_attr.mandible.1,
+            skeleton_attr.mandible.2,
+        );
+        next.mandible_l.orientation = Quaternion::rotation_z(0.0);
+        next.mandible_l.scale = Vec3::one();
+
+        next.mandible_r.position = Vec3::new(
+            skeleton_attr.mandible.0,
+            skeleton_attr.mandible.1,
+            skeleton_attr.mandible.2,
+        );
+        next.mandible_r.orientation = Quaternion::rotation_z(0.0);
+        next.mandible_r.scale = Vec3::one();
+
+        next.chest.position = Vec3::new(
+            0.0,
+            skeleton_attr.chest.0,
+            skeleton_attr.chest.1 + bob * 0.2,
+        ) * skeleton_attr.scaler
+            / 11.0;
+        next.chest.orientation = Quaternion::rotation_y(tilt * 1.5);
+        next.chest.scale = Vec3::one() * skeleton_attr.scaler / 11.0;
+
+        next.abdomen.position = Vec3::new(0.0, skeleton_attr.abdomen.0, skeleton_attr.abdomen.1);
+        next.abdomen.orientation = if skeleton_attr.stinger {
+            Quaternion::rotation_x(1.0 + bob * 0.05) * Quaternion::rotation_z(tilt * 2.0)
+        } else {
+            Quaternion::rotation_x(bob * 0.04) * Quaternion::rotation_z(tilt * 2.0)
+        };
+        next.abdomen.scale = Vec3::one();
+
+        next.leg_fl.position = Vec3::new(
+            -skeleton_attr.leg_f.0,
+            skeleton_attr.leg_f.1,
+            skeleton_attr.leg_f.2,
+        );
+        next.leg_fl.orientation = Quaternion::rotation_z(-0.5 - swing(phase_l[0]))
+            * Quaternion::rotation_y(lift(phase_l[0]));
+        next.leg_fl.scale = Vec3::one();
+
+        next.leg_fcl.position = Vec3::new(
+            -skeleton_attr.leg_fc.0,
+            skeleton_attr.leg_fc.1,
+            skeleton_attr.leg_fc.2,
+        );
+        next.leg_fcl.orientation = Quaternion::rotation_z(-0.15 - swing(phase_l[1]))
+            * Quaternion::rotation_y(lift(phase_l[1]));
+        next.leg_fcl.scale = Vec3::one();
+
+        next.leg_bcl.position = Vec3::new(
+            -skeleton_attr.leg_bc.0,
+            skeleton_attr.leg_bc.1,
+            skeleton_attr.leg_bc.2,
+        );
+        next.leg_bcl.orientation = Quaternion::rotation_z(0.15 - swing(phase_l[2]))
+            * Quaternion::rotation_y(lift(phase_l[2]));
+        next.leg_bcl.scale = Vec3::one();
+
+        next.leg_bl.position = Vec3::new(
+            -skeleton_attr.leg_b.0,
+            skeleton_attr.leg_b.1,
+            skeleton_attr.leg_b.2,
+        );
+        next.leg_bl.orientation = Quaternion::rotation_z(0.5 - swing(phase_l[3]))
+            * Quaternion::rotation_y(lift(phase_l[3]));
+        next.leg_bl.scale = Vec3::one();
+
+        next.leg_fr.position = Vec3::new(
+            skeleton_attr.leg_f.0,
+            skeleton_attr.leg_f.1,
+            skeleton_attr.leg_f.2,
+        );
+        next.leg_fr.orientation = Quaternion::rotation_z(0.5 + swing(phase_r[0]))
+            * Quaternion::rotation_y(-lift(phase_r[0]));
+        next.leg_fr.scale = Vec3::one();
+
+        next.leg_fcr.position = Vec3::new(
+            skeleton_attr.leg_fc.0,
+            skeleton_attr.leg_fc.1,
+            skeleton_attr.leg_fc.2,
+        );
+        next.leg_fcr.orientation = Quaternion::rotation_z(0.15 + swing(phase_r[1]))
+            * Quaternion::rotation_y(-lift(phase_r[1]));
+        next.leg_fcr.scale = Vec3::one();
+
+        next.leg_bcr.position = Vec3::new(
+            skeleton_attr.leg_bc.0,
+            skeleton_attr.leg_bc.1,
+            skeleton_attr.leg_bc.2,
+        );
+        next.leg_bcr.orientation = Quaternion::rotation_z(-0.15 + swing(phase_r[2]))
+            * Quaternion::rotation_y(-lift(phase_r[2]));
+        next.leg_bcr.scale = Vec3::one();
+
+        next.leg_br.position = Vec3::new(
+            skeleton_attr.leg_b.0,
+            skeleton_attr.leg_b.1,
+            skeleton_attr.leg_b.2,
+        );
+        next.leg_br.orientation = Quaternion::rotation_z(-0.5 + swing(phase_r[3]))
+            * Quaternion::rotation_y(-lift(phase_r[3]));
+        next.leg_br.scale = Vec3::one();
+        next
+    }
+}
@@ -66,44 +66,40 @@ impl Skeleton for BipedLargeSkeleton {
         base_mat: Mat4<f32>,
         buf: &mut [FigureBoneData; super::MAX_BONE_COUNT],
     ) -> Vec3<f32> {
-        let upper_torso = Mat4::<f32>::from(self.upper_torso);
+        let [head, jaw, upper_torso, lower_torso, tail, main, second, shoulder_l, shoulder_r, hand_l, hand_r, leg_l, leg_r, foot_l, foot_r, hold, torso, control, leg_control_l, leg_control_r, arm_control_l, arm_control_r] =
+            self.local_mats();
 
-        let torso_mat = base_mat * Mat4::<f32>::from(self.torso);
+        let torso_mat = base_mat * torso;
         let upper_torso_mat = torso_mat * upper_torso;
 
-        let lower_torso_mat = upper_torso_mat * Mat4::<f32>::from(self.lower_torso);
+        let lower_torso_mat = upper_torso_mat * lower_torso;
 
-        let leg_l = Mat4::<f32>::from(self.leg_l);
-        let leg_r = Mat4::<f32>::from(self.leg_r);
+        let leg_control_l_mat = lower_torso_mat * leg_control_l;
+        let leg_control_r_mat = lower_torso_mat * leg_control_r;
 
-        let leg_control_l = lower_torso_mat * Mat4::<f32>::from(self.leg_control_l);
-        let leg_control_r = lower_torso_mat * Mat4::<f32>::from(self.leg_control_r);
+        let arm_control_l_mat = upper_torso_mat * arm_control_l;
+        let arm_control_r_mat = upper_torso_mat * arm_control_r;
 
-        let arm_control_l = upper_torso_mat * Mat4::<f32>::from(self.arm_control_l);
-        let arm_control_r = upper_torso_mat * Mat4::<f32>::from(self.arm_control_r);
-
-        let head_mat = upper_torso_mat * Mat4::<f32>::from(self.head);
-        let control_mat = Mat4::<f32>::from(self.control);
-        let hand_l_mat = Mat4::<f32>::from(self.hand_l);
+        let head_mat = upper_torso_mat * head;
 
         *(<&mut [_; Self::BONE_COUNT]>::try_from(&mut buf[0..Self::BONE_COUNT]).unwrap()) = [
             make_bone(head_mat),
-            make_bone(head_mat * Mat4::<f32>::from(self.jaw)),
+            make_bone(head_mat * jaw),
             make_bone(upper_torso_mat),
             make_bone(lower_torso_mat),
-            make_bone(lower_torso_mat * Mat4::<f32>::from(self.tail)),
-            make_bone(upper_torso_mat * control_mat * Mat4::<f32>::from(self.main)),
-            make_bone(upper_torso_mat * control_mat * Mat4::<f32>::from(self.second)),
-            make_bone(arm_control_l * Mat4::<f32>::from(self.shoulder_l)),
-            make_bone(arm_control_r * Mat4::<f32>::from(self.shoulder_r)),
-            make_bone(arm_control_l * control_mat * Mat4::<f32>::from(self.hand_l)),
-            make_bone(arm_control_r * control_mat * Mat4::<f32>::from(self.hand_r)),
-            make_bone(leg_control_l * leg_l),
-            make_bone(leg_control_r * leg_r),
-            make_bone(leg_control_l * Mat4::<f32>::from(self.foot_l)),
-            make_bone(leg_control_r * Mat4::<f32>::from(self.foot_r)),
+            make_bone(lower_torso_mat * tail),
+            make_bone(upper_torso_mat * control * main),
+            make_bone(upper_torso_mat * control * second),
+            make_bone(arm_control_l_mat * shoulder_l),
+            make_bone(arm_control_r_mat * shoulder_r),
+            make_bone(arm_control_l_mat * control * hand_l),
+            make_bone(arm_control_r_mat * control * hand_r),
+            make_bone(leg_control_l_mat * leg_l),
+            make_bone(leg_control_r_mat * leg_r),
+            make_bone(leg_control_l_mat * foot_l),
+            make_bone(leg_control_r_mat * foot_r),
             // FIXME: Should this be control_l_mat?
-            make_bone(upper_torso_mat * control_mat * hand_l_mat * Mat4::<f32>::from(self.hold)),
+            make_bone(upper_torso_mat * control * hand_l * hold),
         ];
         Vec3::default()
     }
@@ -33,13 +33,15 @@ impl Skeleton for BirdSmallSkeleton {
         base_mat: Mat4<f32>,
         buf: &mut [FigureBoneData; super::MAX_BONE_COUNT],
     ) -> Vec3<f32> {
-        let torso_mat = base_mat * Mat4::<f32>::from(self.torso);
+        let [head, torso, wing_l, wing_r] = self.local_mats();
+
+        let torso_mat = base_mat * torso;
 
         *(<&mut [_; Self::BONE_COUNT]>::try_from(&mut buf[0..Self::BONE_COUNT]).unwrap()) = [
-            make_bone(torso_mat * Mat4::<f32>::from(self.head)),
+            make_bone(torso_mat * head),
             make_bone(torso_mat),
-            make_bone(torso_mat * Mat4::<f32>::from(self.wing_l)),
-            make_bone(torso_mat * Mat4::<f32>::from(self.wing_r)),
+            make_bone(torso_mat * wing_l),
+            make_bone(torso_mat * wing_r),
         ];
         Vec3::default()
     }
@@ -0,0 +1,97 @@
+use super::{
+    super::{vek::*, Animation},
+    CharacterSkeleton, SkeletonAttr,
+};
+use common::comp::item::ToolKind;
+use std::f32::consts::PI;
+
+pub struct DiveAnimation;
+
+impl Animation for DiveAnimation {
+    type Dependency = (Option<ToolKind>, Option<ToolKind>, Vec3<f32>, f64);
+    type Skeleton = CharacterSkeleton;
+
+    #[cfg(feature = "use-dyn-lib")]
+    const UPDATE_FN: &'static [u8] = b"character_dive\0";
+
+    #[cfg_attr(feature = "be-dyn-lib", export_name = "character_dive")]
+
+    fn update_skeleton_inner(
+        skeleton: &Self::Skeleton,
+        (_active_tool_kind, _second_tool_kind, velocity, _global_time): Self::Dependency,
+        anim_time: f64,
+        rate: &mut f32,
+        skeleton_attr: &SkeletonAttr,
+    ) -> Self::Skeleton {
+        *rate = 1.0;
+        let mut next = (*skeleton).clone();
+
+        // Streamlined, with the arms stretched out over the head and the legs
+        // together, slowly kicking as the dive runs out
+        let kick = (anim_time as f32 * 8.0).sin() * (anim_time as f32 * 1.5).min(1.0);
+        let pitch = {
+            let lateral = Vec2::<f32>::from(velocity).magnitude();
+            if lateral > 0.001 || velocity.z.abs() > 0.001 {
+                velocity.z.atan2(lateral).max(-PI / 2.0).min(0.0)
+            } else {
+                0.0
+            }
+        };
+
+        next.head.position = Vec3::new(0.0, -3.0 + skeleton_attr.head.0, skeleton_attr.head.1);
+        next.head.orientation = Quaternion::rotation_x(0.6);
+        next.head.scale = Vec3::one() * skeleton_attr.head_scale;
+
+        next.chest.position = Vec3::new(0.0, skeleton_attr.chest.0, -10.0 + skeleton_attr.chest.1);
+        next.chest.orientation = Quaternion::rotation_x(0.0);
+        next.chest.scale = Vec3::one();
+
+        next.belt.position = Vec3::new(0.0, skeleton_attr.belt.0, skeleton_attr.belt.1);
+        next.belt.orientation = Quaternion::rotation_x(0.0);
+
+        next.shorts.position = Vec3::new(0.0, skeleton_attr.shorts.0, skeleton_attr.shorts.1);
+        next.shorts.orientation = Quaternion::rotation_x(0.0);
+
+        next.hand_l.position = Vec3::new(
+            -1.5,
+            skeleton_attr.hand.1 + 1.0,
+            skeleton_attr.hand.2 + 14.0,
+        );
+        next.hand_l.orientation = Quaternion::rotation_x(PI) * Quaternion::rotation_y(0.3);
+        next.hand_l.scale = Vec3::one();
+
+        next.hand_r.position =
+            Vec3::new(1.5, skeleton_attr.hand.1 + 1.0, skeleton_attr.hand.2 + 14.0);
+        next.hand_r.orientation = Quaternion::rotation_x(PI) * Quaternion::rotation_y(-0.3);
+        next.hand_r.scale = Vec3::one();
+
+        next.shoulder_l.orientation = Quaternion::rotation_x(PI * 0.9);
+        next.shoulder_r.orientation = Quaternion::rotation_x(PI * 0.9);
+
+        next.foot_l.position = Vec3::new(
+            -skeleton_attr.foot.0 + 1.0,
+            skeleton_attr.foot.1 + kick * 1.5,
+            skeleton_attr.foot.2 - 2.0,
+        );
+        next.foot_l.orientation = Quaternion::rotation_x(-0.6 + kick * 0.3);
+        next.foot_l.scale = Vec3::one();
+
+        next.foot_r.position = Vec3::new(
+            skeleton_attr.foot.0 - 1.0,
+            skeleton_attr.foot.1 - kick * 1.5,
+            skeleton_attr.foot.2 - 2.0,
+        );
+        next.foot_r.orientation = Quaternion::rotation_x(-0.6 - kick * 0.3);
+        next.foot_r.scale = Vec3::one();
+
+        next.glider.scale = Vec3::one() * 0.0;
+        next.hold.scale = Vec3::one() * 0.0;
+
+        // Lying flat when moving level and head down when plunging straight down
+        next.torso.position = Vec3::new(0.0, 0.0, 1.0) * skeleton_attr.scaler;
+        next.torso.orientation = Quaternion::rotation_x(pitch - PI / 2.0);
+        next.torso.scale = Vec3::one() / 11.0 * skeleton_attr.scaler;
+
+        next
+    }
+}
@@ -8,6 +8,7 @@ pub mod chargeswing;
 pub mod climb;
 pub mod dance;
 pub mod dash;
+pub mod dive;
 pub mod equip;
 pub mod glidewield;
 pub mod gliding;
@@ -32,13 +33,13 @@ pub mod wield;
 pub use self::{
     alpha::AlphaAnimation, beam::BeamAnimation, beta::BetaAnimation, block::BlockAnimation,
     blockidle::BlockIdleAnimation, charge::ChargeAnimation, chargeswing::ChargeswingAnimation,
-    climb::ClimbAnimation, dance::DanceAnimation, dash::DashAnimation, equip::EquipAnimation,
-    glidewield::GlideWieldAnimation, gliding::GlidingAnimation, idle::IdleAnimation,
-    jump::JumpAnimation, leapmelee::LeapAnimation, repeater::RepeaterAnimation,
-    roll::RollAnimation, run::RunAnimation, shockwave::ShockwaveAnimation, shoot::ShootAnimation,
-    sit::SitAnimation, sneak::SneakAnimation, spin::SpinAnimation, spinmelee::SpinMeleeAnimation,
-    stand::StandAnimation, swim::SwimAnimation, swimwield::SwimWieldAnimation,
-    wield::WieldAnimation,
+    climb::ClimbAnimation, dance::DanceAnimation, dash::DashAnimation, dive::DiveAnimation,
+    equip::EquipAnimation, glidewield::GlideWieldAnimation, gliding::GlidingAnimation,
+    idle::IdleAnimation, jump::JumpAnimation, leapmelee::LeapAnimation,
+    repeater::RepeaterAnimation, roll::RollAnimation, run::RunAnimation,
+    shockwave::ShockwaveAnimation, shoot::ShootAnimation, sit::SitAnimation, sneak::SneakAnimation,
+    spin::SpinAnimation, spinmelee::SpinMeleeAnimation, stand::StandAnimation, swim::SwimAnimation,
+    swimwield::SwimWieldAnimation, wield::WieldAnimation,
 };
 
 use super::{make_bone, vek::*, FigureBoneData, Skeleton};
@@ -85,38 +86,51 @@ impl Skeleton for CharacterSkeleton {
         base_mat: Mat4<f32>,
         buf: &mut [FigureBoneData; super::MAX_BONE_COUNT],
     ) -> Vec3<f32> {
-        let torso_mat = base_mat * Mat4::<f32>::from(self.torso);
-        let chest_mat = torso_mat * Mat4::<f32>::from(self.chest);
-        let head_mat = chest_mat * Mat4::<f32>::from(self.head);
-        let shorts_mat = chest_mat * Mat4::<f32>::from(self.shorts);
-        let control_mat = chest_mat * Mat4::<f32>::from(self.control);
-        let control_l_mat = control_mat * Mat4::<f32>::from(self.control_l);
-        let control_r_mat = control_mat * Mat4::<f32>::from(self.control_r);
+        let [head, chest, belt, back, shorts, hand_l, hand_r, foot_l, foot_r, shoulder_l, shoulder_r, glider, main, second, lantern, hold, torso, control, control_l, control_r] =
+            self.local_mats();
 
-        let hand_l_mat = Mat4::<f32>::from(self.hand_l);
-        let lantern_mat = Mat4::<f32>::from(self.lantern);
+        let torso_mat = base_mat * torso;
+        let chest_mat = torso_mat * chest;
+        let head_mat = chest_mat * head;
+        let shorts_mat = chest_mat * shorts;
+        let control_mat = chest_mat * control;
+        let control_l_mat = control_mat * control_l;
+        let control_r_mat = control_mat * control_r;
 
         *(<&mut [_; Self::BONE_COUNT]>::try_from(&mut buf[0..Self::BONE_COUNT]).unwrap()) = [
             make_bone(head_mat),
             make_bone(chest_mat),
-            make_bone(chest_mat * Mat4::<f32>::from(self.belt)),
-            make_bone(chest_mat * Mat4::<f32>::from(self.back)),
+            make_bone(chest_mat * belt),
+            make_bone(chest_mat * back),
             make_bone(shorts_mat),
-            make_bone(control_l_mat * hand_l_mat),
-            make_bone(control_r_mat * Mat4::<f32>::from(self.hand_r)),
-            make_bone(torso_mat * Mat4::<f32>::from(self.foot_l)),
-            make_bone(torso_mat * Mat4::<f32>::from(self.foot_r)),
-            make_bone(chest_mat * Mat4::<f32>::from(self.shoulder_l)),
-            make_bone(chest_mat * Mat4::<f32>::from(self.shoulder_r)),
-            make_bone(chest_mat * Mat4::<f32>::from(self.glider)),
-            make_bone(control_l_mat * Mat4::<f32>::from(self.main)),
-            make_bone(control_r_mat * Mat4::<f32>::from(self.second)),
-            make_bone(shorts_mat * lantern_mat),
+            make_bone(control_l_mat * hand_l),
+            make_bone(control_r_mat * hand_r),
+            make_bone(torso_mat * foot_l),
+            make_bone(torso_mat * foot_r),
+            make_bone(chest_mat * shoulder_l),
+            make_bone(chest_mat * shoulder_r),
+            make_bone(chest_mat * glider),
+            make_bone(control_l_mat * main),
+            make_bone(control_r_mat * second),
+            make_bone(shorts_mat * lantern),
             // FIXME: Should this be control_l_mat?
-            make_bone(control_mat * hand_l_mat * Mat4::<f32>::from(self.hold)),
+            make_bone(control_mat * hand_l * hold),
         ];
-        // NOTE: lantern_mat.cols.w = lantern_mat * Vec4::unit_w()
-        (head_mat * lantern_mat.cols.w).xyz()
+        // NOTE: lantern.cols.w = lantern * Vec4::unit_w()
+        (head_mat * lantern.cols.w).xyz()
+    }
+}
+
+impl CharacterSkeleton {
+    /// Swings the back bone on top of its animated pose so that what hangs
+    /// from it (e.g. a cape) points along `dir`, which is relative to the
+    /// character's facing
+    pub fn hang_back(&mut self, dir: Vec3<f32>) {
+        // Keep the cape from swinging forward into the body
+        let swing = dir.y.atan2(-dir.z).max(-1.4).min(0.1);
+        let sway = (-dir.x).atan2(-dir.z).max(-0.6).min(0.6);
+        self.back.orientation =
+            self.back.orientation * Quaternion::rotation_x(swing) * Quaternion::rotation_y(sway);
     }
 }
 
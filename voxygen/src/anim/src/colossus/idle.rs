@@ -0,0 +1,175 @@
+use super::{
+    super::{vek::*, Animation},
+    ColossusSkeleton, SkeletonAttr,
+};
+use std::{f32::consts::PI, ops::Mul};
+
+pub struct IdleAnimation;
+
+impl Animation for IdleAnimation {
+    type Dependency = f64;
+    type Skeleton = ColossusSkeleton;
+
+    #[cfg(feature = "use-dyn-lib")]
+    const UPDATE_FN: &'static [u8] = b"colossus_idle\0";
+
+    #[cfg_attr(feature = "be-dyn-lib", export_name = "colossus_idle")]
+
+    fn update_skeleton_inner(
+        skeleton: &Self::Skeleton,
+        global_time: Self::Dependency,
+        anim_time: f64,
+        _rate: &mut f32,
+        skeleton_attr: &SkeletonAttr,
+    ) -> Self::Skeleton {
+        let mut next = (*skeleton).clone();
+
+        let lab = 0.6;
+        let breathe = (anim_time as f32 * lab as f32 + 1.5 * PI).sin();
+
+        let look = Vec2::new(
+            ((global_time + anim_time) as f32 / 12.0)
+                .floor()
+                .mul(7331.0)
+                .sin()
+                * 0.4,
+            ((global_time + anim_time) as f32 / 12.0)
+                .floor()
+                .mul(1337.0)
+                .sin()
+                * 0.2,
+        );
+
+        next.head.position = Vec3::new(0.0, skeleton_attr.head.0, skeleton_attr.head.1);
+        next.head.orientation =
+            Quaternion::rotation_z(look.x * 0.5) * Quaternion::rotation_x(look.y * 0.5);
+        next.head.scale = Vec3::one();
+
+        next.upper_torso.position = Vec3::new(
+            0.0,
+            skeleton_attr.upper_torso.0,
+            skeleton_attr.upper_torso.1 + breathe * 0.6,
+        );
+        next.upper_torso.orientation = Quaternion::rotation_x(breathe * 0.02);
+        next.upper_torso.scale = Vec3::one();
+
+        next.lower_torso.position = Vec3::new(
+            0.0,
+            skeleton_attr.lower_torso.0,
+            skeleton_attr.lower_torso.1 + breathe * -0.3,
+        );
+        next.lower_torso.orientation = Quaternion::rotation_x(breathe * -0.02);
+        next.lower_torso.scale = Vec3::one();
+
+        next.armor_chest.position = Vec3::new(
+            0.0,
+            skeleton_attr.armor_chest.0,
+            skeleton_attr.armor_chest.1,
+        );
+        next.armor_chest.orientation = Quaternion::rotation_x(0.0);
+        next.armor_chest.scale = Vec3::one() * 1.05;
+
+        next.armor_l.position = Vec3::new(
+            -skeleton_attr.armor.0,
+            skeleton_attr.armor.1,
+            skeleton_attr.armor.2,
+        );
+        next.armor_l.orientation = Quaternion::rotation_y(-0.15);
+        next.armor_l.scale = Vec3::one() * 1.05;
+
+        next.armor_r.position = Vec3::new(
+            skeleton_attr.armor.0,
+            skeleton_attr.armor.1,
+            skeleton_attr.armor.2,
+        );
+        next.armor_r.orientation = Quaternion::rotation_y(0.15);
+        next.armor_r.scale = Vec3::one() * 1.05;
+
+        next.upper_arm_l.position = Vec3::new(
+            -skeleton_attr.upper_arm.0,
+            skeleton_attr.upper_arm.1,
+            skeleton_attr.upper_arm.2,
+        );
+        next.upper_arm_l.orientation =
+            Quaternion::rotation_x(breathe * 0.04) * Quaternion::rotation_y(-0.1);
+        next.upper_arm_l.scale = Vec3::one();
+
+        next.upper_arm_r.position = Vec3::new(
+            skeleton_attr.upper_arm.0,
+            skeleton_attr.upper_arm.1,
+            skeleton_attr.upper_arm.2,
+        );
+        next.upper_arm_r.orientation =
+            Quaternion::rotation_x(breathe * 0.04) * Quaternion::rotation_y(0.1);
+        next.upper_arm_r.scale = Vec3::one();
+
+        next.forearm_l.position = Vec3::new(
+            -skeleton_attr.forearm.0,
+            skeleton_attr.forearm.1,
+            skeleton_attr.forearm.2,
+        );
+        next.forearm_l.orientation = Quaternion::rotation_x(0.2 + breathe * 0.05);
+        next.forearm_l.scale = Vec3::one();
+
+        next.forearm_r.position = Vec3::new(
+            skeleton_attr.forearm.0,
+            skeleton_attr.forearm.1,
+            skeleton_attr.forearm.2,
+        );
+        next.forearm_r.orientation = Quaternion::rotation_x(0.2 + breathe * 0.05);
+        next.forearm_r.scale = Vec3::one();
+
+        next.hand_l.position = Vec3::new(
+            -skeleton_attr.hand.0,
+            skeleton_attr.hand.1,
+            skeleton_attr.hand.2,
+        );
+        next.hand_l.orientation = Quaternion::rotation_x(0.0);
+        next.hand_l.scale = Vec3::one() * 1.02;
+
+        next.hand_r.position = Vec3::new(
+            skeleton_attr.hand.0,
+            skeleton_attr.hand.1,
+            skeleton_attr.hand.2,
+        );
+        next.hand_r.orientation = Quaternion::rotation_x(0.0);
+        next.hand_r.scale = Vec3::one() * 1.02;
+
+        next.leg_l.position = Vec3::new(
+            -skeleton_attr.leg.0,
+            skeleton_attr.leg.1,
+            skeleton_attr.leg.2 + breathe * -0.2,
+        );
+        next.leg_l.orientation = Quaternion::rotation_x(0.0);
+        next.leg_l.scale = Vec3::one();
+
+        next.leg_r.position = Vec3::new(
+            skeleton_attr.leg.0,
+            skeleton_attr.leg.1,
+            skeleton_attr.leg.2 + breathe * -0.2,
+        );
+        next.leg_r.orientation = Quaternion::rotation_x(0.0);
+        next.leg_r.scale = Vec3::one();
+
+        next.foot_l.position = Vec3::new(
+            -skeleton_attr.foot.0,
+            skeleton_attr.foot.1,
+            skeleton_attr.foot.2 + breathe * -0.2,
+        );
+        next.foot_l.orientation = Quaternion::rotation_x(0.0);
+        next.foot_l.scale = Vec3::one();
+
+        next.foot_r.position = Vec3::new(
+            skeleton_attr.foot.0,
+            skeleton_attr.foot.1,
+            skeleton_attr.foot.2 + breathe * -0.2,
+        );
+        next.foot_r.orientation = Quaternion::rotation_x(0.0);
+        next.foot_r.scale = Vec3::one();
+
+        next.torso.position = Vec3::new(0.0, 0.0, 0.0);
+        next.torso.orientation = Quaternion::rotation_z(0.0);
+        next.torso.scale = Vec3::one() / 6.0;
+        next
+    }
+}
@@ -0,0 +1,180 @@
+pub mod idle;
+pub mod smash;
+pub mod stomp;
+pub mod walk;
+
+// Reexports
+pub use self::{
+    idle::IdleAnimation, smash::SmashAnimation, stomp::StompAnimation, walk::WalkAnimation,
+};
+
+use super::{make_bone, vek::*, FigureBoneData, Skeleton};
+use common::comp::{self};
+use core::convert::TryFrom;
+
+pub type Body = comp::colossus::Body;
+
+skeleton_impls!(struct ColossusSkeleton {
+    + head,
+    + upper_torso,
+    + lower_torso,
+    + armor_chest,
+    + armor_l,
+    + armor_r,
+    + upper_arm_l,
+    + upper_arm_r,
+    + forearm_l,
+    + forearm_r,
+    + hand_l,
+    + hand_r,
+    + leg_l,
+    + leg_r,
+    + foot_l,
+    + foot_r,
+    torso,
+});
+
+impl ColossusSkeleton {
+    /// Hides the armor plates that have been broken off, one plate for every
+    /// quarter of health lost: the left pauldron goes first, then the right
+    /// one and finally the chest plate.
+    pub fn shatter_armor(&mut self, health_fraction: f32) {
+        if health_fraction < 0.75 {
+            self.armor_l.scale = Vec3::zero();
+        }
+        if health_fraction < 0.5 {
+            self.armor_r.scale = Vec3::zero();
+        }
+        if health_fraction < 0.25 {
+            self.armor_chest.scale = Vec3::zero();
+        }
+    }
+}
+
+impl Skeleton for ColossusSkeleton {
+    type Attr = SkeletonAttr;
+    type Body = Body;
+
+    const BONE_COUNT: usize = 16;
+    #[cfg(feature = "use-dyn-lib")]
+    const COMPUTE_FN: &'static [u8] = b"colossus_compute_mats\0";
+
+    #[cfg_attr(feature = "be-dyn-lib", export_name = "colossus_compute_mats")]
+    fn compute_matrices_inner(
+        &self,
+        base_mat: Mat4<f32>,
+        buf: &mut [FigureBoneData; super::MAX_BONE_COUNT],
+    ) -> Vec3<f32> {
+        let [head, upper_torso, lower_torso, armor_chest, armor_l, armor_r, upper_arm_l, upper_arm_r, forearm_l, forearm_r, hand_l, hand_r, leg_l, leg_r, foot_l, foot_r, torso] =
+            self.local_mats();
+
+        let torso_mat = base_mat * torso;
+        let upper_torso_mat = torso_mat * upper_torso;
+        let lower_torso_mat = upper_torso_mat * lower_torso;
+        let upper_arm_l_mat = upper_torso_mat * upper_arm_l;
+        let upper_arm_r_mat = upper_torso_mat * upper_arm_r;
+        let forearm_l_mat = upper_arm_l_mat * forearm_l;
+        let forearm_r_mat = upper_arm_r_mat * forearm_r;
+        let leg_l_mat = lower_torso_mat * leg_l;
+        let leg_r_mat = lower_torso_mat * leg_r;
+
+        *(<&mut [_; Self::BONE_COUNT]>::try_from(&mut buf[0..Self::BONE_COUNT]).unwrap()) = [
+            make_bone(upper_torso_mat * head),
+            make_bone(upper_torso_mat),
+            make_bone(lower_torso_mat),
+            make_bone(upper_torso_mat * armor_chest),
+            make_bone(upper_arm_l_mat * armor_l),
+            make_bone(upper_arm_r_mat * armor_r),
+            make_bone(upper_arm_l_mat),
+            make_bone(upper_arm_r_mat),
+            make_bone(forearm_l_mat),
+            make_bone(forearm_r_mat),
+            make_bone(forearm_l_mat * hand_l),
+            make_bone(forearm_r_mat * hand_r),
+            make_bone(leg_l_mat),
+            make_bone(leg_r_mat),
+            make_bone(leg_l_mat * foot_l),
+            make_bone(leg_r_mat * foot_r),
+        ];
+        Vec3::default()
+    }
+}
+
+pub struct SkeletonAttr {
+    head: (f32, f32),
+    upper_torso: (f32, f32),
+    lower_torso: (f32, f32),
+    armor_chest: (f32, f32),
+    armor: (f32, f32, f32),
+    upper_arm: (f32, f32, f32),
+    forearm: (f32, f32, f32),
+    hand: (f32, f32, f32),
+    leg: (f32, f32, f32),
+    foot: (f32, f32, f32),
+}
+
+impl<'a> std::convert::TryFrom<&'a comp::Body> for SkeletonAttr {
+    type Error = ();
+
+    fn try_from(body: &'a comp::Body) -> Result<Self, Self::Error> {
+        match body {
+            comp::Body::Colossus(body) => Ok(SkeletonAttr::from(body)),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Default for SkeletonAttr {
+    fn default() -> Self {
+        Self {
+            head: (0.0, 0.0),
+            upper_torso: (0.0, 0.0),
+            lower_torso: (0.0, 0.0),
+            armor_chest: (0.0, 0.0),
+            armor: (0.0, 0.0, 0.0),
+            upper_arm: (0.0, 0.0, 0.0),
+            forearm: (0.0, 0.0, 0.0),
+            hand: (0.0, 0.0, 0.0),
+            leg: (0.0, 0.0, 0.0),
+            foot: (0.0, 0.0, 0.0),
+        }
+    }
+}
+
+impl<'a> From<&'a Body> for SkeletonAttr {
+    fn from(body: &'a Body) -> Self {
+        use comp::colossus::Species::*;
+        Self {
+            head: match (body.species, body.body_type) {
+                (Titan, _) => (1.0, 13.0),
+            },
+            upper_torso: match (body.species, body.body_type) {
+                (Titan, _) => (0.0, 52.0),
+            },
+            lower_torso: match (body.species, body.body_type) {
+                (Titan, _) => (0.0, -14.0),
+            },
+            armor_chest: match (body.species, body.body_type) {
+                (Titan, _) => (4.5, 2.0),
+            },
+            armor: match (body.species, body.body_type) {
+                (Titan, _) => (0.0, 0.0, 3.5),
+            },
+            upper_arm: match (body.species, body.body_type) {
+                (Titan, _) => (15.0, -1.0, 5.0),
+            },
+            forearm: match (body.species, body.body_type) {
+                (Titan, _) => (2.0, 0.0, -13.0),
+            },
+            hand: match (body.species, body.body_type) {
+                (Titan, _) => (0.0, 0.5, -11.0),
+            },
+            leg: match (body.species, body.body_type) {
+                (Titan, _) => (6.0, 0.0, -4.5),
+            },
+            foot: match (body.species, body.body_type) {
+                (Titan, _) => (0.5, 0.5, -14.0),
+            },
+        }
+    }
+}
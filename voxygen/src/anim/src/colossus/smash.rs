@@ -0,0 +1,75 @@
+use super::{
+    super::{vek::*, Animation},
+    ColossusSkeleton, SkeletonAttr,
+};
+
+pub struct SmashAnimation;
+
+impl Animation for SmashAnimation {
+    type Dependency = (f32, f64);
+    type Skeleton = ColossusSkeleton;
+
+    #[cfg(feature = "use-dyn-lib")]
+    const UPDATE_FN: &'static [u8] = b"colossus_smash\0";
+
+    #[cfg_attr(feature = "be-dyn-lib", export_name = "colossus_smash")]
+
+    fn update_skeleton_inner(
+        skeleton: &Self::Skeleton,
+        (_velocity, _global_time): Self::Dependency,
+        anim_time: f64,
+        _rate: &mut f32,
+        skeleton_attr: &SkeletonAttr,
+    ) -> Self::Skeleton {
+        let mut next = (*skeleton).clone();
+
+        // Both fists are slowly raised overhead, giving players time to get out
+        // of the way, and then brought down in a single quick blow
+        let raise = (anim_time as f32 / 1.2).min(1.0);
+        let slam = ((anim_time as f32 - 1.2) / 0.2).max(0.0).min(1.0);
+        let recover = ((anim_time as f32 - 1.4) / 0.4).max(0.0).min(1.0);
+        let swing = raise - slam * 1.6 + recover * 0.6;
+
+        next.head.orientation = Quaternion::rotation_x(swing * 0.3);
+
+        next.upper_torso.position = Vec3::new(
+            0.0,
+            skeleton_attr.upper_torso.0,
+            skeleton_attr.upper_torso.1 + swing * 2.0,
+        );
+        next.upper_torso.orientation = Quaternion::rotation_x(swing * 0.25);
+
+        next.lower_torso.orientation = Quaternion::rotation_x(swing * -0.15);
+
+        next.upper_arm_l.orientation =
+            Quaternion::rotation_x(swing * 2.4) * Quaternion::rotation_y(-0.3 * raise);
+        next.upper_arm_r.orientation =
+            Quaternion::rotation_x(swing * 2.4) * Quaternion::rotation_y(0.3 * raise);
+
+        next.forearm_l.orientation = Quaternion::rotation_x(0.4 + swing * 0.6);
+        next.forearm_r.orientation = Quaternion::rotation_x(0.4 + swing * 0.6);
+
+        next.hand_l.position = Vec3::new(
+            -skeleton_attr.hand.0 + raise * 2.0,
+            skeleton_attr.hand.1,
+            skeleton_attr.hand.2,
+        );
+        next.hand_r.position = Vec3::new(
+            skeleton_attr.hand.0 - raise * 2.0,
+            skeleton_attr.hand.1,
+            skeleton_attr.hand.2,
+        );
+
+        next.leg_l.position = Vec3::new(
+            -skeleton_attr.leg.0,
+            skeleton_attr.leg.1,
+            skeleton_attr.leg.2 + slam * -1.0,
+        );
+        next.leg_r.position = Vec3::new(
+            skeleton_attr.leg.0,
+            skeleton_attr.leg.1,
+            skeleton_attr.leg.2 + slam * -1.0,
+        );
+        next
+    }
+}
@@ -0,0 +1,78 @@
+use super::{
+    super::{vek::*, Animation},
+    ColossusSkeleton, SkeletonAttr,
+};
+use std::f32::consts::PI;
+
+pub struct StompAnimation;
+
+impl Animation for StompAnimation {
+    type Dependency = (f32, f64);
+    type Skeleton = ColossusSkeleton;
+
+    #[cfg(feature = "use-dyn-lib")]
+    const UPDATE_FN: &'static [u8] = b"colossus_stomp\0";
+
+    #[cfg_attr(feature = "be-dyn-lib", export_name = "colossus_stomp")]
+
+    fn update_skeleton_inner(
+        skeleton: &Self::Skeleton,
+        (_velocity, _global_time): Self::Dependency,
+        anim_time: f64,
+        _rate: &mut f32,
+        skeleton_attr: &SkeletonAttr,
+    ) -> Self::Skeleton {
+        let mut next = (*skeleton).clone();
+
+        // The right leg is lifted during the long wind up, trembling as it
+        // reaches its peak, before being slammed into the ground to release the
+        // shockwave
+        let lift = (anim_time as f32 / 1.8).min(1.0);
+        let stomp = ((anim_time as f32 - 1.8) / 0.3).max(0.0).min(1.0);
+        let recover = ((anim_time as f32 - 2.1) / 1.2).max(0.0).min(1.0);
+        let height = (lift - stomp).max(0.0);
+        let tremble = (anim_time as f32 * 30.0).sin() * height.powf(4.0) * (1.0 - stomp);
+        let quake = (stomp * PI).sin() * (1.0 - recover);
+
+        next.head.orientation = Quaternion::rotation_x(height * 0.2 - quake * 0.2);
+
+        next.upper_torso.position = Vec3::new(
+            tremble * 0.3,
+            skeleton_attr.upper_torso.0,
+            skeleton_attr.upper_torso.1 + height * 2.0 - quake * 2.5,
+        );
+        next.upper_torso.orientation =
+            Quaternion::rotation_y(height * -0.15) * Quaternion::rotation_x(height * 0.15);
+
+        next.lower_torso.orientation = Quaternion::rotation_y(height * 0.1);
+
+        next.upper_arm_l.orientation =
+            Quaternion::rotation_y(-0.2 - height * 0.8) * Quaternion::rotation_x(quake * 0.4);
+        next.upper_arm_r.orientation =
+            Quaternion::rotation_y(0.2 + height * 0.8) * Quaternion::rotation_x(quake * 0.4);
+
+        next.forearm_l.orientation = Quaternion::rotation_x(0.2 + height * 0.4);
+        next.forearm_r.orientation = Quaternion::rotation_x(0.2 + height * 0.4);
+
+        next.leg_r.position = Vec3::new(
+            skeleton_attr.leg.0,
+            skeleton_attr.leg.1 + height * 3.0,
+            skeleton_attr.leg.2 + height * 6.0,
+        );
+        next.leg_r.orientation = Quaternion::rotation_x(height * 0.9);
+
+        next.foot_r.position = Vec3::new(
+            skeleton_attr.foot.0,
+            skeleton_attr.foot.1,
+            skeleton_attr.foot.2 + tremble,
+        );
+        next.foot_r.orientation = Quaternion::rotation_x(height * -0.6);
+
+        next.leg_l.position = Vec3::new(
+            -skeleton_attr.leg.0,
+            skeleton_attr.leg.1,
+            skeleton_attr.leg.2 - quake * 1.5,
+        );
+        next
+    }
+}
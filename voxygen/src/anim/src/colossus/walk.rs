@@ -0,0 +1,190 @@
+use super::{
+    super::{vek::*, Animation},
+    ColossusSkeleton, SkeletonAttr,
+};
+use std::f32::consts::PI;
+
+pub struct WalkAnimation;
+
+impl Animation for WalkAnimation {
+    type Dependency = (f32, Vec3<f32>, Vec3<f32>, f64);
+    type Skeleton = ColossusSkeleton;
+
+    #[cfg(feature = "use-dyn-lib")]
+    const UPDATE_FN: &'static [u8] = b"colossus_walk\0";
+
+    #[cfg_attr(feature = "be-dyn-lib", export_name = "colossus_walk")]
+
+    fn update_skeleton_inner(
+        skeleton: &Self::Skeleton,
+        (velocity, orientation, last_ori, _global_time): Self::Dependency,
+        anim_time: f64,
+        rate: &mut f32,
+        skeleton_attr: &SkeletonAttr,
+    ) -> Self::Skeleton {
+        let mut next = (*skeleton).clone();
+
+        // Long, heavy strides: the walk cycle slows down as the colossus does
+        *rate = (velocity / 4.0).min(1.0).max(0.5);
+
+        let lab = 0.3;
+        let step = anim_time as f32 * 16.0 * lab as f32;
+
+        let footvertl = step.sin();
+        let footvertr = (step + PI).sin();
+        let foothorl = (step + PI * 0.5).sin();
+        let foothorr = (step + PI * 1.5).sin();
+        // The whole body sinks into every footfall
+        let impact = (step * 2.0).cos().abs();
+        let sway = step.sin();
+
+        let ori: Vec2<f32> = Vec2::from(orientation);
+        let last_ori = Vec2::from(last_ori);
+        let tilt = if ::vek::Vec2::new(ori, last_ori)
+            .map(|o| o.magnitude_squared())
+            .map(|m| m > 0.001 && m.is_finite())
+            .reduce_and()
+            && ori.angle_between(last_ori).is_finite()
+        {
+            ori.angle_between(last_ori).min(0.2)
+                * last_ori.determine_side(Vec2::zero(), ori).signum()
+        } else {
+            0.0
+        };
+
+        next.head.position = Vec3::new(0.0, skeleton_attr.head.0, skeleton_attr.head.1);
+        next.head.orientation =
+            Quaternion::rotation_z(sway * -0.1) * Quaternion::rotation_x(impact * -0.05);
+        next.head.scale = Vec3::one();
+
+        next.upper_torso.position = Vec3::new(
+            0.0,
+            skeleton_attr.upper_torso.0,
+            skeleton_attr.upper_torso.1 - impact * 1.5,
+        );
+        next.upper_torso.orientation = Quaternion::rotation_z(sway * 0.1 + tilt * -1.0)
+            * Quaternion::rotation_x(0.1)
+            * Quaternion::rotation_y(tilt * 2.0);
+        next.upper_torso.scale = Vec3::one();
+
+        next.lower_torso.position = Vec3::new(
+            0.0,
+            skeleton_attr.lower_torso.0,
+            skeleton_attr.lower_torso.1,
+        );
+        next.lower_torso.orientation =
+            Quaternion::rotation_z(sway * -0.15) * Quaternion::rotation_x(-0.1);
+        next.lower_torso.scale = Vec3::one();
+
+        next.armor_chest.position = Vec3::new(
+            0.0,
+            skeleton_attr.armor_chest.0,
+            skeleton_attr.armor_chest.1,
+        );
+        next.armor_chest.orientation = Quaternion::rotation_x(0.0);
+        next.armor_chest.scale = Vec3::one() * 1.05;
+
+        next.armor_l.position = Vec3::new(
+            -skeleton_attr.armor.0,
+            skeleton_attr.armor.1,
+            skeleton_attr.armor.2,
+        );
+        next.armor_l.orientation = Quaternion::rotation_y(-0.15);
+        next.armor_l.scale = Vec3::one() * 1.05;
+
+        next.armor_r.position = Vec3::new(
+            skeleton_attr.armor.0,
+            skeleton_attr.armor.1,
+            skeleton_attr.armor.2,
+        );
+        next.armor_r.orientation = Quaternion::rotation_y(0.15);
+        next.armor_r.scale = Vec3::one() * 1.05;
+
+        next.upper_arm_l.position = Vec3::new(
+            -skeleton_attr.upper_arm.0,
+            skeleton_attr.upper_arm.1,
+            skeleton_attr.upper_arm.2,
+        );
+        next.upper_arm_l.orientation =
+            Quaternion::rotation_x(foothorr * 0.3) * Quaternion::rotation_y(-0.1);
+        next.upper_arm_l.scale = Vec3::one();
+
+        next.upper_arm_r.position = Vec3::new(
+            skeleton_attr.upper_arm.0,
+            skeleton_attr.upper_arm.1,
+            skeleton_attr.upper_arm.2,
+        );
+        next.upper_arm_r.orientation =
+            Quaternion::rotation_x(foothorl * 0.3) * Quaternion::rotation_y(0.1);
+        next.upper_arm_r.scale = Vec3::one();
+
+        next.forearm_l.position = Vec3::new(
+            -skeleton_attr.forearm.0,
+            skeleton_attr.forearm.1,
+            skeleton_attr.forearm.2,
+        );
+        next.forearm_l.orientation = Quaternion::rotation_x(0.3 + foothorr * 0.15);
+        next.forearm_l.scale = Vec3::one();
+
+        next.forearm_r.position = Vec3::new(
+            skeleton_attr.forearm.0,
+            skeleton_attr.forearm.1,
+            skeleton_attr.forearm.2,
+        );
+        next.forearm_r.orientation = Quaternion::rotation_x(0.3 + foothorl * 0.15);
+        next.forearm_r.scale = Vec3::one();
+
+        next.hand_l.position = Vec3::new(
+            -skeleton_attr.hand.0,
+            skeleton_attr.hand.1,
+            skeleton_attr.hand.2,
+        );
+        next.hand_l.orientation = Quaternion::rotation_x(0.0);
+        next.hand_l.scale = Vec3::one() * 1.02;
+
+        next.hand_r.position = Vec3::new(
+            skeleton_attr.hand.0,
+            skeleton_attr.hand.1,
+            skeleton_attr.hand.2,
+        );
+        next.hand_r.orientation = Quaternion::rotation_x(0.0);
+        next.hand_r.scale = Vec3::one() * 1.02;
+
+        next.leg_l.position = Vec3::new(
+            -skeleton_attr.leg.0,
+            skeleton_attr.leg.1 + foothorl * 2.0,
+            skeleton_attr.leg.2 + footvertl.max(0.0) * 1.5,
+        );
+        next.leg_l.orientation = Quaternion::rotation_x(foothorl * 0.35);
+        next.leg_l.scale = Vec3::one();
+
+        next.leg_r.position = Vec3::new(
+            skeleton_attr.leg.0,
+            skeleton_attr.leg.1 + foothorr * 2.0,
+            skeleton_attr.leg.2 + footvertr.max(0.0) * 1.5,
+        );
+        next.leg_r.orientation = Quaternion::rotation_x(foothorr * 0.35);
+        next.leg_r.scale = Vec3::one();
+
+        next.foot_l.position = Vec3::new(
+            -skeleton_attr.foot.0,
+            skeleton_attr.foot.1,
+            skeleton_attr.foot.2 + footvertl.max(0.0) * 2.0,
+        );
+        next.foot_l.orientation = Quaternion::rotation_x(foothorl * -0.25 + footvertl * 0.1);
+        next.foot_l.scale = Vec3::one();
+
+        next.foot_r.position = Vec3::new(
+            skeleton_attr.foot.0,
+            skeleton_attr.foot.1,
+            skeleton_attr.foot.2 + footvertr.max(0.0) * 2.0,
+        );
+        next.foot_r.orientation = Quaternion::rotation_x(foothorr * -0.25 + footvertr * 0.1);
+        next.foot_r.scale = Vec3::one();
+
+        next.torso.position = Vec3::new(0.0, 0.0, 0.0);
+        next.torso.orientation = Quaternion::rotation_z(0.0);
+        next.torso.scale = Vec3::one() / 6.0;
+        next
+    }
+}
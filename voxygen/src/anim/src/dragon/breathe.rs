@@ -0,0 +1,161 @@
+use super::{
+    super::{vek::*, Animation},
+    DragonSkeleton, SkeletonAttr,
+};
+use common::states::utils::StageSection;
+use std::f32::consts::PI;
+
+pub struct BreatheAnimation;
+
+impl Animation for BreatheAnimation {
+    type Dependency = (Option<StageSection>, bool, f64);
+    type Skeleton = DragonSkeleton;
+
+    #[cfg(feature = "use-dyn-lib")]
+    const UPDATE_FN: &'static [u8] = b"dragon_breathe\0";
+
+    #[cfg_attr(feature = "be-dyn-lib", export_name = "dragon_breathe")]
+    fn update_skeleton_inner(
+        skeleton: &Self::Skeleton,
+        (stage_section, airborne, _global_time): Self::Dependency,
+        anim_time: f64,
+        _rate: &mut f32,
+        skeleton_attr: &SkeletonAttr,
+    ) -> Self::Skeleton {
+        let mut next = (*skeleton).clone();
+
+        let movement = (anim_time as f32 * 1.5).min(1.0);
+
+        // Rears back to draw breath, then thrusts the head forward and holds the
+        // jaw open for as long as the breath lasts
+        let (rear, thrust, jaw_open) = match stage_section {
+            Some(StageSection::Buildup) => (movement, 0.0, movement * 0.2),
+            Some(StageSection::Cast) => (1.0 - movement, movement, 0.2 + movement * 0.5),
+            Some(StageSection::Recover) => (0.0, 1.0 - movement, 0.7 * (1.0 - movement)),
+            _ => (0.0, 0.0, 0.0),
+        };
+        let shake = (anim_time as f32 * 30.0).sin() * thrust * 0.02;
+
+        // Keeps beating the wings to stay aloft, or braces on the ground
+        let beat = (anim_time as f32 * 3.0).sin();
+        let tip = (anim_time as f32 * 3.0 - PI / 4.0).sin();
+        let (wing_in, wing_out) = if airborne {
+            (0.2 + beat * 0.9, 0.1 + tip * 0.5)
+        } else {
+            (0.8 - rear * 0.4, -2.0 + rear * 1.2)
+        };
+
+        next.head_upper.position =
+            Vec3::new(0.0, skeleton_attr.head_upper.0, skeleton_attr.head_upper.1);
+        next.head_upper.orientation = Quaternion::rotation_x(rear * 0.3 - thrust * 0.2 + shake)
+            * Quaternion::rotation_z(shake);
+        next.head_upper.scale = Vec3::one() * 1.05;
+
+        next.head_lower.position = Vec3::new(
+            0.0,
+            skeleton_attr.head_lower.0 - rear * 1.5 + thrust * 1.5,
+            skeleton_attr.head_lower.1,
+        );
+        next.head_lower.orientation = Quaternion::rotation_x(rear * 0.4 - thrust * 0.3);
+        next.head_lower.scale = Vec3::one() * 1.05;
+
+        next.jaw.position = Vec3::new(0.0, skeleton_attr.jaw.0, skeleton_attr.jaw.1);
+        next.jaw.orientation = Quaternion::rotation_x(-jaw_open);
+        next.jaw.scale = Vec3::one() * 1.05;
+
+        next.chest_front.position = Vec3::new(
+            0.0,
+            skeleton_attr.chest_front.0,
+            skeleton_attr.chest_front.1,
+        );
+        next.chest_front.orientation = if airborne {
+            Quaternion::rotation_x(0.3 + rear * 0.1)
+        } else {
+            Quaternion::rotation_x(rear * 0.15 - thrust * 0.05)
+        };
+        next.chest_front.scale = Vec3::one();
+
+        next.chest_rear.position =
+            Vec3::new(0.0, skeleton_attr.chest_rear.0, skeleton_attr.chest_rear.1);
+        next.chest_rear.orientation = Quaternion::rotation_x(if airborne { -0.1 } else { 0.0 });
+        next.chest_rear.scale = Vec3::one();
+
+        next.tail_front.position =
+            Vec3::new(0.0, skeleton_attr.tail_front.0, skeleton_attr.tail_front.1);
+        next.tail_front.orientation = Quaternion::rotation_x(if airborne { -0.3 } else { 0.1 });
+        next.tail_front.scale = Vec3::one() * 0.98;
+
+        next.tail_rear.position =
+            Vec3::new(0.0, skeleton_attr.tail_rear.0, skeleton_attr.tail_rear.1);
+        next.tail_rear.orientation = Quaternion::rotation_x(if airborne { -0.2 } else { 0.05 });
+        next.tail_rear.scale = Vec3::one() * 0.98;
+
+        let feet = if airborne { -0.5 } else { 0.0 };
+
+        next.foot_fl.position = Vec3::new(
+            -skeleton_attr.feet_f.0,
+            skeleton_attr.feet_f.1,
+            skeleton_attr.feet_f.2,
+        );
+        next.foot_fl.orientation = Quaternion::rotation_x(feet);
+        next.foot_fl.scale = Vec3::one();
+
+        next.foot_fr.position = Vec3::new(
+            skeleton_attr.feet_f.0,
+            skeleton_attr.feet_f.1,
+            skeleton_attr.feet_f.2,
+        );
+        next.foot_fr.orientation = Quaternion::rotation_x(feet);
+        next.foot_fr.scale = Vec3::one();
+
+        next.foot_bl.position = Vec3::new(
+            -skeleton_attr.feet_b.0,
+            skeleton_attr.feet_b.1,
+            skeleton_attr.feet_b.2,
+        );
+        next.foot_bl.orientation = Quaternion::rotation_x(feet);
+        next.foot_bl.scale = Vec3::one();
+
+        next.foot_br.position = Vec3::new(
+            skeleton_attr.feet_b.0,
+            skeleton_attr.feet_b.1,
+            skeleton_attr.feet_b.2,
+        );
+        next.foot_br.orientation = Quaternion::rotation_x(feet);
+        next.foot_br.scale = Vec3::one();
+
+        next.wing_in_l.position = Vec3::new(
+            -skeleton_attr.wing_in.0,
+            skeleton_attr.wing_in.1,
+            skeleton_attr.wing_in.2,
+        );
+        next.wing_in_l.orientation = Quaternion::rotation_y(wing_in);
+        next.wing_in_l.scale = Vec3::one();
+
+        next.wing_in_r.position = Vec3::new(
+            skeleton_attr.wing_in.0,
+            skeleton_attr.wing_in.1,
+            skeleton_attr.wing_in.2,
+        );
+        next.wing_in_r.orientation = Quaternion::rotation_y(-wing_in);
+        next.wing_in_r.scale = Vec3::one();
+
+        next.wing_out_l.position = Vec3::new(
+            -skeleton_attr.wing_out.0,
+            skeleton_attr.wing_out.1,
+            skeleton_attr.wing_out.2,
+        );
+        next.wing_out_l.orientation = Quaternion::rotation_y(wing_out);
+        next.wing_out_l.scale = Vec3::one();
+
+        next.wing_out_r.position = Vec3::new(
+            skeleton_attr.wing_out.0,
+            skeleton_attr.wing_out.1,
+            skeleton_attr.wing_out.2,
+        );
+        next.wing_out_r.orientation = Quaternion::rotation_y(-wing_out);
+        next.wing_out_r.scale = Vec3::one();
+
+        next
+    }
+}
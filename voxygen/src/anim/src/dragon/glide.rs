@@ -0,0 +1,152 @@
+use super::{
+    super::{vek::*, Animation},
+    DragonSkeleton, SkeletonAttr,
+};
+use std::f32::consts::PI;
+
+pub struct GlideAnimation;
+
+impl Animation for GlideAnimation {
+    type Dependency = (Vec3<f32>, Vec3<f32>, f64);
+    type Skeleton = DragonSkeleton;
+
+    #[cfg(feature = "use-dyn-lib")]
+    const UPDATE_FN: &'static [u8] = b"dragon_glide\0";
+
+    #[cfg_attr(feature = "be-dyn-lib", export_name = "dragon_glide")]
+    fn update_skeleton_inner(
+        skeleton: &Self::Skeleton,
+        (orientation, last_ori, _global_time): Self::Dependency,
+        anim_time: f64,
+        _rate: &mut f32,
+        skeleton_attr: &SkeletonAttr,
+    ) -> Self::Skeleton {
+        let mut next = (*skeleton).clone();
+
+        // Wings are held out still, only rippling in the wind
+        let ripple = (anim_time as f32 * 6.0).sin();
+        let ripple_tip = (anim_time as f32 * 6.0 + PI / 3.0).sin();
+        let sway = (anim_time as f32 * 1.0).sin();
+
+        // Bank into turns
+        let ori: Vec2<f32> = Vec2::from(orientation);
+        let last_ori = Vec2::from(last_ori);
+        let tilt = if ::vek::Vec2::new(ori, last_ori)
+            .map(|o| o.magnitude_squared())
+            .map(|m| m > 0.001 && m.is_finite())
+            .reduce_and()
+            && ori.angle_between(last_ori).is_finite()
+        {
+            ori.angle_between(last_ori).min(0.2)
+                * last_ori.determine_side(Vec2::zero(), ori).signum()
+        } else {
+            0.0
+        };
+
+        next.head_upper.position =
+            Vec3::new(0.0, skeleton_attr.head_upper.0, skeleton_attr.head_upper.1);
+        next.head_upper.orientation = Quaternion::rotation_z(tilt * -1.0);
+        next.head_upper.scale = Vec3::one() * 1.05;
+
+        next.head_lower.position =
+            Vec3::new(0.0, skeleton_attr.head_lower.0, skeleton_attr.head_lower.1);
+        next.head_lower.orientation =
+            Quaternion::rotation_z(tilt * -1.0) * Quaternion::rotation_x(-0.1);
+        next.head_lower.scale = Vec3::one() * 1.05;
+
+        next.jaw.position = Vec3::new(0.0, skeleton_attr.jaw.0, skeleton_attr.jaw.1);
+        next.jaw.orientation = Quaternion::rotation_x(0.0);
+        next.jaw.scale = Vec3::one() * 1.05;
+
+        next.chest_front.position = Vec3::new(
+            0.0,
+            skeleton_attr.chest_front.0,
+            skeleton_attr.chest_front.1,
+        );
+        next.chest_front.orientation = Quaternion::rotation_y(tilt * 3.0);
+        next.chest_front.scale = Vec3::one();
+
+        next.chest_rear.position =
+            Vec3::new(0.0, skeleton_attr.chest_rear.0, skeleton_attr.chest_rear.1);
+        next.chest_rear.orientation = Quaternion::rotation_y(0.0);
+        next.chest_rear.scale = Vec3::one();
+
+        // The tail streams out behind, steering
+        next.tail_front.position =
+            Vec3::new(0.0, skeleton_attr.tail_front.0, skeleton_attr.tail_front.1);
+        next.tail_front.orientation = Quaternion::rotation_z(tilt * 1.5 + sway * 0.03);
+        next.tail_front.scale = Vec3::one() * 0.98;
+
+        next.tail_rear.position =
+            Vec3::new(0.0, skeleton_attr.tail_rear.0, skeleton_attr.tail_rear.1);
+        next.tail_rear.orientation = Quaternion::rotation_z(tilt * 2.0 + sway * 0.05);
+        next.tail_rear.scale = Vec3::one() * 0.98;
+
+        // Legs tucked in against the body
+        next.foot_fl.position = Vec3::new(
+            -skeleton_attr.feet_f.0,
+            skeleton_attr.feet_f.1,
+            skeleton_attr.feet_f.2,
+        );
+        next.foot_fl.orientation = Quaternion::rotation_x(-1.3);
+        next.foot_fl.scale = Vec3::one();
+
+        next.foot_fr.position = Vec3::new(
+            skeleton_attr.feet_f.0,
+            skeleton_attr.feet_f.1,
+            skeleton_attr.feet_f.2,
+        );
+        next.foot_fr.orientation = Quaternion::rotation_x(-1.3);
+        next.foot_fr.scale = Vec3::one();
+
+        next.foot_bl.position = Vec3::new(
+            -skeleton_attr.feet_b.0,
+            skeleton_attr.feet_b.1,
+            skeleton_attr.feet_b.2,
+        );
+        next.foot_bl.orientation = Quaternion::rotation_x(-1.3);
+        next.foot_bl.scale = Vec3::one();
+
+        next.foot_br.position = Vec3::new(
+            skeleton_attr.feet_b.0,
+            skeleton_attr.feet_b.1,
+            skeleton_attr.feet_b.2,
+        );
+        next.foot_br.orientation = Quaternion::rotation_x(-1.3);
+        next.foot_br.scale = Vec3::one();
+
+        next.wing_in_l.position = Vec3::new(
+            -skeleton_attr.wing_in.0,
+            skeleton_attr.wing_in.1,
+            skeleton_attr.wing_in.2,
+        );
+        next.wing_in_l.orientation = Quaternion::rotation_y(0.05 + ripple * 0.02);
+        next.wing_in_l.scale = Vec3::one();
+
+        next.wing_in_r.position = Vec3::new(
+            skeleton_attr.wing_in.0,
+            skeleton_attr.wing_in.1,
+            skeleton_attr.wing_in.2,
+        );
+        next.wing_in_r.orientation = Quaternion::rotation_y(-0.05 - ripple * 0.02);
+        next.wing_in_r.scale = Vec3::one();
+
+        next.wing_out_l.position = Vec3::new(
+            -skeleton_attr.wing_out.0,
+            skeleton_attr.wing_out.1,
+            skeleton_attr.wing_out.2,
+        );
+        next.wing_out_l.orientation = Quaternion::rotation_y(0.1 + ripple_tip * 0.04);
+        next.wing_out_l.scale = Vec3::one();
+
+        next.wing_out_r.position = Vec3::new(
+            skeleton_attr.wing_out.0,
+            skeleton_attr.wing_out.1,
+            skeleton_attr.wing_out.2,
+        );
+        next.wing_out_r.orientation = Quaternion::rotation_y(-0.1 - ripple_tip * 0.04);
+        next.wing_out_r.scale = Vec3::one();
+
+        next
+    }
+}
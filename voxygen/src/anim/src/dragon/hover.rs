@@ -0,0 +1,144 @@
+use super::{
+    super::{vek::*, Animation},
+    DragonSkeleton, SkeletonAttr,
+};
+use std::f32::consts::PI;
+
+pub struct HoverAnimation;
+
+impl Animation for HoverAnimation {
+    type Dependency = (f32, f64);
+    type Skeleton = DragonSkeleton;
+
+    #[cfg(feature = "use-dyn-lib")]
+    const UPDATE_FN: &'static [u8] = b"dragon_hover\0";
+
+    #[cfg_attr(feature = "be-dyn-lib", export_name = "dragon_hover")]
+    fn update_skeleton_inner(
+        skeleton: &Self::Skeleton,
+        (velocity, _global_time): Self::Dependency,
+        anim_time: f64,
+        rate: &mut f32,
+        skeleton_attr: &SkeletonAttr,
+    ) -> Self::Skeleton {
+        let mut next = (*skeleton).clone();
+
+        // Beats get quicker while climbing or settling down, which both take more
+        // effort than holding still
+        *rate = (1.0 + velocity / 20.0).min(1.5);
+
+        // Slow, deep wingbeats with the body held upright, heaving up with each
+        // downstroke
+        let beat = (anim_time as f32 * 3.0).sin();
+        let heave = (anim_time as f32 * 3.0 - PI / 2.0).sin();
+        let tip = (anim_time as f32 * 3.0 - PI / 4.0).sin();
+        let sway = (anim_time as f32 * 1.5).sin();
+
+        next.head_upper.position =
+            Vec3::new(0.0, skeleton_attr.head_upper.0, skeleton_attr.head_upper.1);
+        next.head_upper.orientation = Quaternion::rotation_x(-0.15 + heave * 0.05);
+        next.head_upper.scale = Vec3::one() * 1.05;
+
+        next.head_lower.position =
+            Vec3::new(0.0, skeleton_attr.head_lower.0, skeleton_attr.head_lower.1);
+        next.head_lower.orientation = Quaternion::rotation_x(-0.25 + heave * 0.05);
+        next.head_lower.scale = Vec3::one() * 1.05;
+
+        next.jaw.position = Vec3::new(0.0, skeleton_attr.jaw.0, skeleton_attr.jaw.1);
+        next.jaw.orientation = Quaternion::rotation_x(0.0);
+        next.jaw.scale = Vec3::one() * 1.05;
+
+        next.chest_front.position = Vec3::new(
+            0.0,
+            skeleton_attr.chest_front.0,
+            skeleton_attr.chest_front.1 + heave * 1.5,
+        );
+        next.chest_front.orientation = Quaternion::rotation_x(0.35 + heave * 0.05);
+        next.chest_front.scale = Vec3::one();
+
+        next.chest_rear.position =
+            Vec3::new(0.0, skeleton_attr.chest_rear.0, skeleton_attr.chest_rear.1);
+        next.chest_rear.orientation = Quaternion::rotation_x(-0.1);
+        next.chest_rear.scale = Vec3::one();
+
+        next.tail_front.position =
+            Vec3::new(0.0, skeleton_attr.tail_front.0, skeleton_attr.tail_front.1);
+        next.tail_front.orientation =
+            Quaternion::rotation_x(-0.3) * Quaternion::rotation_z(sway * 0.1);
+        next.tail_front.scale = Vec3::one() * 0.98;
+
+        next.tail_rear.position =
+            Vec3::new(0.0, skeleton_attr.tail_rear.0, skeleton_attr.tail_rear.1);
+        next.tail_rear.orientation =
+            Quaternion::rotation_x(-0.2) * Quaternion::rotation_z(sway * 0.15);
+        next.tail_rear.scale = Vec3::one() * 0.98;
+
+        // Legs hang below, ready to set down
+        next.foot_fl.position = Vec3::new(
+            -skeleton_attr.feet_f.0,
+            skeleton_attr.feet_f.1,
+            skeleton_attr.feet_f.2,
+        );
+        next.foot_fl.orientation = Quaternion::rotation_x(-0.5 + heave * 0.1);
+        next.foot_fl.scale = Vec3::one();
+
+        next.foot_fr.position = Vec3::new(
+            skeleton_attr.feet_f.0,
+            skeleton_attr.feet_f.1,
+            skeleton_attr.feet_f.2,
+        );
+        next.foot_fr.orientation = Quaternion::rotation_x(-0.5 + heave * 0.1);
+        next.foot_fr.scale = Vec3::one();
+
+        next.foot_bl.position = Vec3::new(
+            -skeleton_attr.feet_b.0,
+            skeleton_attr.feet_b.1,
+            skeleton_attr.feet_b.2,
+        );
+        next.foot_bl.orientation = Quaternion::rotation_x(-0.3 + heave * 0.1);
+        next.foot_bl.scale = Vec3::one();
+
+        next.foot_br.position = Vec3::new(
+            skeleton_attr.feet_b.0,
+            skeleton_attr.feet_b.1,
+            skeleton_attr.feet_b.2,
+        );
+        next.foot_br.orientation = Quaternion::rotation_x(-0.3 + heave * 0.1);
+        next.foot_br.scale = Vec3::one();
+
+        next.wing_in_l.position = Vec3::new(
+            -skeleton_attr.wing_in.0,
+            skeleton_attr.wing_in.1,
+            skeleton_attr.wing_in.2,
+        );
+        next.wing_in_l.orientation = Quaternion::rotation_y(0.2 + beat * 0.9);
+        next.wing_in_l.scale = Vec3::one();
+
+        next.wing_in_r.position = Vec3::new(
+            skeleton_attr.wing_in.0,
+            skeleton_attr.wing_in.1,
+            skeleton_attr.wing_in.2,
+        );
+        next.wing_in_r.orientation = Quaternion::rotation_y(-0.2 - beat * 0.9);
+        next.wing_in_r.scale = Vec3::one();
+
+        // The tips trail behind the rest of the wing
+        next.wing_out_l.position = Vec3::new(
+            -skeleton_attr.wing_out.0,
+            skeleton_attr.wing_out.1,
+            skeleton_attr.wing_out.2,
+        );
+        next.wing_out_l.orientation = Quaternion::rotation_y(0.1 + tip * 0.5);
+        next.wing_out_l.scale = Vec3::one();
+
+        next.wing_out_r.position = Vec3::new(
+            skeleton_attr.wing_out.0,
+            skeleton_attr.wing_out.1,
+            skeleton_attr.wing_out.2,
+        );
+        next.wing_out_r.orientation = Quaternion::rotation_y(-0.1 - tip * 0.5);
+        next.wing_out_r.scale = Vec3::one();
+
+        next
+    }
+}
@@ -1,9 +1,15 @@
+pub mod breathe;
 pub mod fly;
+pub mod glide;
+pub mod hover;
 pub mod idle;
 pub mod run;
 
 // Reexports
-pub use self::{fly::FlyAnimation, idle::IdleAnimation, run::RunAnimation};
+pub use self::{
+    breathe::BreatheAnimation, fly::FlyAnimation, glide::GlideAnimation, hover::HoverAnimation,
+    idle::IdleAnimation, run::RunAnimation,
+};
 
 use super::{make_bone, vek::*, FigureBoneData, Skeleton};
 use common::comp::{self};
@@ -44,30 +50,33 @@ impl Skeleton for DragonSkeleton {
         base_mat: Mat4<f32>,
         buf: &mut [FigureBoneData; super::MAX_BONE_COUNT],
     ) -> Vec3<f32> {
-        let chest_front_mat = base_mat * Mat4::<f32>::from(self.chest_front);
-        let chest_rear_mat = chest_front_mat * Mat4::<f32>::from(self.chest_rear);
-        let head_lower_mat = chest_front_mat * Mat4::<f32>::from(self.head_lower);
-        let wing_in_l_mat = chest_front_mat * Mat4::<f32>::from(self.wing_in_l);
-        let wing_in_r_mat = chest_front_mat * Mat4::<f32>::from(self.wing_in_r);
-        let tail_front_mat = chest_rear_mat * Mat4::<f32>::from(self.tail_front);
-        let head_upper_mat = head_lower_mat * Mat4::<f32>::from(self.head_upper);
+        let [head_upper, head_lower, jaw, chest_front, chest_rear, tail_front, tail_rear, wing_in_l, wing_in_r, wing_out_l, wing_out_r, foot_fl, foot_fr, foot_bl, foot_br] =
+            self.local_mats();
+
+        let chest_front_mat = base_mat * chest_front;
+        let chest_rear_mat = chest_front_mat * chest_rear;
+        let head_lower_mat = chest_front_mat * head_lower;
+        let wing_in_l_mat = chest_front_mat * wing_in_l;
+        let wing_in_r_mat = chest_front_mat * wing_in_r;
+        let tail_front_mat = chest_rear_mat * tail_front;
+        let head_upper_mat = head_lower_mat * head_upper;
 
         *(<&mut [_; Self::BONE_COUNT]>::try_from(&mut buf[0..Self::BONE_COUNT]).unwrap()) = [
             make_bone(head_upper_mat),
             make_bone(head_lower_mat),
-            make_bone(head_upper_mat * Mat4::<f32>::from(self.jaw)),
+            make_bone(head_upper_mat * jaw),
             make_bone(chest_front_mat),
             make_bone(chest_rear_mat),
             make_bone(tail_front_mat),
-            make_bone(tail_front_mat * Mat4::<f32>::from(self.tail_rear)),
+            make_bone(tail_front_mat * tail_rear),
             make_bone(wing_in_l_mat),
             make_bone(wing_in_r_mat),
-            make_bone(wing_in_l_mat * Mat4::<f32>::from(self.wing_out_l)),
-            make_bone(wing_in_r_mat * Mat4::<f32>::from(self.wing_out_r)),
-            make_bone(chest_front_mat * Mat4::<f32>::from(self.foot_fl)),
-            make_bone(chest_front_mat * Mat4::<f32>::from(self.foot_fr)),
-            make_bone(chest_rear_mat * Mat4::<f32>::from(self.foot_bl)),
-            make_bone(chest_rear_mat * Mat4::<f32>::from(self.foot_br)),
+            make_bone(wing_in_l_mat * wing_out_l),
+            make_bone(wing_in_r_mat * wing_out_r),
+            make_bone(chest_front_mat * foot_fl),
+            make_bone(chest_front_mat * foot_fr),
+            make_bone(chest_rear_mat * foot_bl),
+            make_bone(chest_rear_mat * foot_br),
         ];
         Vec3::default()
     }
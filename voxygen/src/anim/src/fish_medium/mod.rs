@@ -34,16 +34,18 @@ impl Skeleton for FishMediumSkeleton {
         base_mat: Mat4<f32>,
         buf: &mut [FigureBoneData; super::MAX_BONE_COUNT],
     ) -> Vec3<f32> {
-        let torso_mat = base_mat * Mat4::<f32>::from(self.torso);
-        let rear_mat = torso_mat * Mat4::<f32>::from(self.rear);
+        let [head, torso, rear, tail, fin_l, fin_r] = self.local_mats();
+
+        let torso_mat = base_mat * torso;
+        let rear_mat = torso_mat * rear;
 
         *(<&mut [_; Self::BONE_COUNT]>::try_from(&mut buf[0..Self::BONE_COUNT]).unwrap()) = [
-            make_bone(torso_mat * Mat4::<f32>::from(self.head)),
+            make_bone(torso_mat * head),
             make_bone(torso_mat),
             make_bone(rear_mat),
-            make_bone(rear_mat * Mat4::<f32>::from(self.tail)),
-            make_bone(rear_mat * Mat4::<f32>::from(self.fin_l)),
-            make_bone(rear_mat * Mat4::<f32>::from(self.fin_r)),
+            make_bone(rear_mat * tail),
+            make_bone(rear_mat * fin_l),
+            make_bone(rear_mat * fin_r),
         ];
         Vec3::default()
     }
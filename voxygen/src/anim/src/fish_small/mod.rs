@@ -31,12 +31,12 @@ impl Skeleton for FishSmallSkeleton {
         base_mat: Mat4<f32>,
         buf: &mut [FigureBoneData; super::MAX_BONE_COUNT],
     ) -> Vec3<f32> {
-        let torso_mat = base_mat * Mat4::<f32>::from(self.torso);
+        let [torso, tail] = self.local_mats();
 
-        *(<&mut [_; Self::BONE_COUNT]>::try_from(&mut buf[0..Self::BONE_COUNT]).unwrap()) = [
-            make_bone(torso_mat),
-            make_bone(torso_mat * Mat4::<f32>::from(self.tail)),
-        ];
+        let torso_mat = base_mat * torso;
+
+        *(<&mut [_; Self::BONE_COUNT]>::try_from(&mut buf[0..Self::BONE_COUNT]).unwrap()) =
+            [make_bone(torso_mat), make_bone(torso_mat * tail)];
         Vec3::default()
     }
 }
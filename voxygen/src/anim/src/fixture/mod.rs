@@ -2,7 +2,7 @@ use super::{make_bone, vek::*, FigureBoneData, Skeleton};
 
 pub type Body = ();
 
-#[derive(Clone, Default)]
+#[derive(Clone, Default, PartialEq)]
 pub struct FixtureSkeleton;
 
 pub struct SkeletonAttr;
@@ -46,27 +46,30 @@ impl Skeleton for GolemSkeleton {
         base_mat: Mat4<f32>,
         buf: &mut [FigureBoneData; super::MAX_BONE_COUNT],
     ) -> Vec3<f32> {
-        let torso_mat = base_mat * Mat4::<f32>::from(self.torso);
-        let upper_torso_mat = torso_mat * Mat4::<f32>::from(self.upper_torso);
-        let lower_torso_mat = upper_torso_mat * Mat4::<f32>::from(self.lower_torso);
-        let leg_l_mat = lower_torso_mat * Mat4::<f32>::from(self.leg_l);
-        let leg_r_mat = lower_torso_mat * Mat4::<f32>::from(self.leg_r);
-        let shoulder_l_mat = upper_torso_mat * Mat4::<f32>::from(self.shoulder_l);
-        let shoulder_r_mat = upper_torso_mat * Mat4::<f32>::from(self.shoulder_r);
+        let [head, jaw, upper_torso, lower_torso, shoulder_l, shoulder_r, hand_l, hand_r, leg_l, leg_r, foot_l, foot_r, torso] =
+            self.local_mats();
+
+        let torso_mat = base_mat * torso;
+        let upper_torso_mat = torso_mat * upper_torso;
+        let lower_torso_mat = upper_torso_mat * lower_torso;
+        let leg_l_mat = lower_torso_mat * leg_l;
+        let leg_r_mat = lower_torso_mat * leg_r;
+        let shoulder_l_mat = upper_torso_mat * shoulder_l;
+        let shoulder_r_mat = upper_torso_mat * shoulder_r;
 
         *(<&mut [_; Self::BONE_COUNT]>::try_from(&mut buf[0..Self::BONE_COUNT]).unwrap()) = [
-            make_bone(upper_torso_mat * Mat4::<f32>::from(self.head)),
-            make_bone(upper_torso_mat * Mat4::<f32>::from(self.head) * Mat4::<f32>::from(self.jaw)),
+            make_bone(upper_torso_mat * head),
+            make_bone(upper_torso_mat * head * jaw),
             make_bone(upper_torso_mat),
             make_bone(lower_torso_mat),
-            make_bone(upper_torso_mat * Mat4::<f32>::from(self.shoulder_l)),
-            make_bone(upper_torso_mat * Mat4::<f32>::from(self.shoulder_r)),
-            make_bone(shoulder_l_mat * Mat4::<f32>::from(self.hand_l)),
-            make_bone(shoulder_r_mat * Mat4::<f32>::from(self.hand_r)),
+            make_bone(upper_torso_mat * shoulder_l),
+            make_bone(upper_torso_mat * shoulder_r),
+            make_bone(shoulder_l_mat * hand_l),
+            make_bone(shoulder_r_mat * hand_r),
             make_bone(leg_l_mat),
             make_bone(leg_r_mat),
-            make_bone(leg_l_mat * Mat4::<f32>::from(self.foot_l)),
-            make_bone(leg_r_mat * Mat4::<f32>::from(self.foot_r)),
+            make_bone(leg_l_mat * foot_l),
+            make_bone(leg_r_mat * foot_r),
         ];
         Vec3::default()
     }
@@ -4,9 +4,14 @@
 #[cfg(all(feature = "be-dyn-lib", feature = "use-dyn-lib"))]
 compile_error!("Can't use both \"be-dyn-lib\" and \"use-dyn-lib\" features at once");
 
+macro_rules! count_bones {
+    () => { 0 };
+    ($bone:ident $(, $rest:ident)*) => { 1 + count_bones!($($rest),*) };
+}
+
 macro_rules! skeleton_impls {
     { struct $Skeleton:ident { $( $(+)? $bone:ident ),* $(,)? } } => {
-        #[derive(Clone, Default)]
+        #[derive(Clone, Default, PartialEq)]
         pub struct $Skeleton {
             $(
                 $bone: $crate::Bone,
@@ -36,13 +41,23 @@ macro_rules! skeleton_impls {
                 }
             }
         }
+
+        impl $Skeleton {
+            /// Matrices of all bones relative to their parents, in the order the
+            /// bones are declared in
+            fn local_mats(&self) -> [$crate::vek::Mat4<f32>; count_bones!($($bone),*)] {
+                $crate::bone_mats([$(self.$bone),*])
+            }
+        }
     }
 }
 
+pub mod arthropod;
 pub mod biped_large;
 pub mod bird_medium;
 pub mod bird_small;
 pub mod character;
+pub mod colossus;
 pub mod dragon;
 #[cfg(feature = "use-dyn-lib")] pub mod dyn_lib;
 pub mod fish_medium;
@@ -54,6 +69,7 @@ pub mod quadruped_low;
 pub mod quadruped_medium;
 pub mod quadruped_small;
 pub mod theropod;
+pub mod turret;
 pub mod vek;
 
 #[cfg(feature = "use-dyn-lib")]
@@ -63,6 +79,9 @@ pub use dyn_lib::init;
 use std::ffi::CStr;
 
 use self::vek::*;
+use common::assets::{Asset, Ron};
+use serde::de::DeserializeOwned;
+use std::{collections::HashMap, hash::Hash};
 
 type MatRaw = [[f32; 4]; 4];
 
@@ -77,7 +96,85 @@ fn make_bone(mat: Mat4<f32>) -> FigureBoneData {
 
 pub type Bone = Transform<f32, f32, f32>;
 
-pub trait Skeleton: Send + Sync + 'static {
+/// Converts bones to matrices, equivalent to `Mat4::from(bone)` for each bone.
+///
+/// The bones are first split into a structure of arrays, so that the
+/// conversion runs across all bones at once and can be vectorized, rather than
+/// converting the bones one by one.
+#[allow(clippy::needless_range_loop)] // Each index is a lane of every array
+pub fn bone_mats<const N: usize>(bones: [Bone; N]) -> [Mat4<f32>; N] {
+    let mut pos = [[0.0; N]; 3];
+    let mut ori = [[0.0; N]; 4];
+    let mut scale = [[0.0; N]; 3];
+    for (i, bone) in bones.iter().enumerate() {
+        pos[0][i] = bone.position.x;
+        pos[1][i] = bone.position.y;
+        pos[2][i] = bone.position.z;
+        ori[0][i] = bone.orientation.x;
+        ori[1][i] = bone.orientation.y;
+        ori[2][i] = bone.orientation.z;
+        ori[3][i] = bone.orientation.w;
+        scale[0][i] = bone.scale.x;
+        scale[1][i] = bone.scale.y;
+        scale[2][i] = bone.scale.z;
+    }
+
+    // Rotation matrix entries, by row and then column, with each column scaled by
+    // the bone's scale along that axis
+    let mut rot = [[[0.0; N]; 3]; 3];
+    for i in 0..N {
+        let (x, y, z, w) = (ori[0][i], ori[1][i], ori[2][i], ori[3][i]);
+        let (sx, sy, sz) = (scale[0][i], scale[1][i], scale[2][i]);
+        rot[0][0][i] = (1.0 - 2.0 * (y * y + z * z)) * sx;
+        rot[0][1][i] = 2.0 * (x * y - w * z) * sy;
+        rot[0][2][i] = 2.0 * (x * z + w * y) * sz;
+        rot[1][0][i] = 2.0 * (x * y + w * z) * sx;
+        rot[1][1][i] = (1.0 - 2.0 * (x * x + z * z)) * sy;
+        rot[1][2][i] = 2.0 * (y * z - w * x) * sz;
+        rot[2][0][i] = 2.0 * (x * z - w * y) * sx;
+        rot[2][1][i] = 2.0 * (y * z + w * x) * sy;
+        rot[2][2][i] = (1.0 - 2.0 * (x * x + y * y)) * sz;
+    }
+
+    let mut mats = [Mat4::identity(); N];
+    for (i, mat) in mats.iter_mut().enumerate() {
+        #[rustfmt::skip]
+        let bone_mat = Mat4::new(
+            rot[0][0][i], rot[0][1][i], rot[0][2][i], pos[0][i],
+            rot[1][0][i], rot[1][1][i], rot[1][2][i], pos[1][i],
+            rot[2][0][i], rot[2][1][i], rot[2][2][i], pos[2][i],
+            0.0, 0.0, 0.0, 1.0,
+        );
+        *mat = bone_mat;
+    }
+    mats
+}
+
+/// Skeleton attributes by key, as stored in a RON manifest.
+///
+/// NOTE: Anything watching a manifest must load it as this exact type, since
+/// assets are cached by specifier.
+pub type SkeletonAttrManifest<K, A> = HashMap<K, A>;
+
+/// Loads the skeleton attributes for `key` (usually a species and body type)
+/// from a RON manifest mapping keys to attributes, so that they can be tuned
+/// without recompiling. Falls back to the default attributes if the manifest
+/// can't be loaded or has no entry for `key`.
+///
+/// NOTE: The manifest is cached, so changes to it are only picked up once the
+/// asset is reloaded (see `BodySpec` in voxygen, which watches it).
+pub fn load_skeleton_attr<K, A>(specifier: &str, key: &K) -> A
+where
+    K: Eq + Hash + DeserializeOwned + Send + Sync + 'static,
+    A: Clone + Default + DeserializeOwned + Send + Sync + 'static,
+{
+    Ron::<SkeletonAttrManifest<K, A>>::load(specifier)
+        .ok()
+        .and_then(|attrs| attrs.get(key).cloned())
+        .unwrap_or_default()
+}
+
+pub trait Skeleton: Clone + PartialEq + Send + Sync + 'static {
     type Attr;
     type Body;
 
@@ -188,3 +285,44 @@ pub trait Animation {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bone_mats_match_transform_conversion() {
+        let bones = [Bone::default(), Bone {
+            position: Vec3::new(1.0, -2.5, 3.0),
+            orientation: Quaternion::rotation_x(0.4) * Quaternion::rotation_z(-1.2),
+            scale: Vec3::new(0.5, 1.0, 2.0),
+        }];
+        for (mat, bone) in bone_mats(bones).iter().zip(bones.iter()) {
+            let expected = Mat4::<f32>::from(*bone);
+            for (a, b) in mat
+                .into_col_array()
+                .iter()
+                .zip(expected.into_col_array().iter())
+            {
+                assert!((a - b).abs() < 1e-5, "{:?} != {:?}", mat, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn quadruped_medium_attr_manifest_covers_all_bodies() {
+        use common::comp::quadruped_medium::{BodyType, Species, ALL_BODY_TYPES, ALL_SPECIES};
+        type Manifest = SkeletonAttrManifest<(Species, BodyType), quadruped_medium::SkeletonAttr>;
+        let manifest = Ron::<Manifest>::load_expect("voxygen.voxel.quadruped_medium_attr_manifest");
+        for species in ALL_SPECIES.iter() {
+            for body_type in ALL_BODY_TYPES.iter() {
+                assert!(
+                    manifest.contains_key(&(*species, *body_type)),
+                    "No skeleton attributes for {:?} {:?}",
+                    species,
+                    body_type
+                );
+            }
+        }
+    }
+}
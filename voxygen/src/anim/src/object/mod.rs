@@ -3,7 +3,7 @@ use common::comp::{self};
 
 pub type Body = comp::object::Body;
 
-#[derive(Clone, Default)]
+#[derive(Clone, Default, PartialEq)]
 pub struct ObjectSkeleton;
 
 impl<'a, Factor> Lerp<Factor> for &'a ObjectSkeleton {
@@ -41,22 +41,25 @@ impl Skeleton for QuadrupedLowSkeleton {
         base_mat: Mat4<f32>,
         buf: &mut [FigureBoneData; super::MAX_BONE_COUNT],
     ) -> Vec3<f32> {
-        let chest_mat = base_mat * Mat4::<f32>::from(self.chest);
-        let tail_front = chest_mat * Mat4::<f32>::from(self.tail_front);
-        let head_lower_mat = chest_mat * Mat4::<f32>::from(self.head_lower);
-        let head_upper_mat = head_lower_mat * Mat4::<f32>::from(self.head_upper);
+        let [head_upper, head_lower, jaw, chest, tail_front, tail_rear, foot_fl, foot_fr, foot_bl, foot_br] =
+            self.local_mats();
+
+        let chest_mat = base_mat * chest;
+        let tail_front_mat = chest_mat * tail_front;
+        let head_lower_mat = chest_mat * head_lower;
+        let head_upper_mat = head_lower_mat * head_upper;
 
         *(<&mut [_; Self::BONE_COUNT]>::try_from(&mut buf[0..Self::BONE_COUNT]).unwrap()) = [
             make_bone(head_upper_mat),
             make_bone(head_lower_mat),
-            make_bone(head_upper_mat * Mat4::<f32>::from(self.jaw)),
+            make_bone(head_upper_mat * jaw),
             make_bone(chest_mat),
-            make_bone(tail_front),
-            make_bone(tail_front * Mat4::<f32>::from(self.tail_rear)),
-            make_bone(chest_mat * Mat4::<f32>::from(self.foot_fl)),
-            make_bone(chest_mat * Mat4::<f32>::from(self.foot_fr)),
-            make_bone(chest_mat * Mat4::<f32>::from(self.foot_bl)),
-            make_bone(chest_mat * Mat4::<f32>::from(self.foot_br)),
+            make_bone(tail_front_mat),
+            make_bone(tail_front_mat * tail_rear),
+            make_bone(chest_mat * foot_fl),
+            make_bone(chest_mat * foot_fr),
+            make_bone(chest_mat * foot_bl),
+            make_bone(chest_mat * foot_br),
         ];
         Vec3::default()
     }
@@ -36,16 +36,18 @@ impl Skeleton for QuadrupedSmallSkeleton {
         base_mat: Mat4<f32>,
         buf: &mut [FigureBoneData; super::MAX_BONE_COUNT],
     ) -> Vec3<f32> {
-        let chest_mat = base_mat * Mat4::<f32>::from(self.chest);
+        let [head, chest, leg_fl, leg_fr, leg_bl, leg_br, tail] = self.local_mats();
+
+        let chest_mat = base_mat * chest;
 
         *(<&mut [_; Self::BONE_COUNT]>::try_from(&mut buf[0..Self::BONE_COUNT]).unwrap()) = [
-            make_bone(chest_mat * Mat4::<f32>::from(self.head)),
+            make_bone(chest_mat * head),
             make_bone(chest_mat),
-            make_bone(chest_mat * Mat4::<f32>::from(self.leg_fl)),
-            make_bone(chest_mat * Mat4::<f32>::from(self.leg_fr)),
-            make_bone(chest_mat * Mat4::<f32>::from(self.leg_bl)),
-            make_bone(chest_mat * Mat4::<f32>::from(self.leg_br)),
-            make_bone(chest_mat * Mat4::<f32>::from(self.tail)),
+            make_bone(chest_mat * leg_fl),
+            make_bone(chest_mat * leg_fr),
+            make_bone(chest_mat * leg_bl),
+            make_bone(chest_mat * leg_br),
+            make_bone(chest_mat * tail),
         ];
         Vec3::default()
     }
@@ -42,28 +42,31 @@ impl Skeleton for TheropodSkeleton {
         base_mat: Mat4<f32>,
         buf: &mut [FigureBoneData; super::MAX_BONE_COUNT],
     ) -> Vec3<f32> {
-        let chest_front_mat = base_mat * Mat4::<f32>::from(self.chest_front);
-        let neck_mat = chest_front_mat * Mat4::<f32>::from(self.neck);
-        let head_mat = neck_mat * Mat4::<f32>::from(self.head);
-        let chest_back_mat = chest_front_mat * Mat4::<f32>::from(self.chest_back);
-        let tail_front_mat = chest_back_mat * Mat4::<f32>::from(self.tail_front);
-        let leg_l_mat = chest_back_mat * Mat4::<f32>::from(self.leg_l);
-        let leg_r_mat = chest_back_mat * Mat4::<f32>::from(self.leg_r);
+        let [head, jaw, neck, chest_front, chest_back, tail_front, tail_back, hand_l, hand_r, leg_l, leg_r, foot_l, foot_r] =
+            self.local_mats();
+
+        let chest_front_mat = base_mat * chest_front;
+        let neck_mat = chest_front_mat * neck;
+        let head_mat = neck_mat * head;
+        let chest_back_mat = chest_front_mat * chest_back;
+        let tail_front_mat = chest_back_mat * tail_front;
+        let leg_l_mat = chest_back_mat * leg_l;
+        let leg_r_mat = chest_back_mat * leg_r;
 
         *(<&mut [_; Self::BONE_COUNT]>::try_from(&mut buf[0..Self::BONE_COUNT]).unwrap()) = [
             make_bone(head_mat),
-            make_bone(head_mat * Mat4::<f32>::from(self.jaw)),
+            make_bone(head_mat * jaw),
             make_bone(neck_mat),
             make_bone(chest_front_mat),
             make_bone(chest_back_mat),
             make_bone(tail_front_mat),
-            make_bone(tail_front_mat * Mat4::<f32>::from(self.tail_back)),
-            make_bone(chest_front_mat * Mat4::<f32>::from(self.hand_l)),
-            make_bone(chest_front_mat * Mat4::<f32>::from(self.hand_r)),
+            make_bone(tail_front_mat * tail_back),
+            make_bone(chest_front_mat * hand_l),
+            make_bone(chest_front_mat * hand_r),
             make_bone(leg_l_mat),
             make_bone(leg_r_mat),
-            make_bone(leg_l_mat * Mat4::<f32>::from(self.foot_l)),
-            make_bone(leg_r_mat * Mat4::<f32>::from(self.foot_r)),
+            make_bone(leg_l_mat * foot_l),
+            make_bone(leg_r_mat * foot_r),
         ];
         Vec3::default()
     }
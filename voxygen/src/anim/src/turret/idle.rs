@@ -0,0 +1,53 @@
+use super::{
+    super::{vek::*, Animation},
+    SkeletonAttr, TurretSkeleton,
+};
+
+pub struct IdleAnimation;
+
+impl Animation for IdleAnimation {
+    type Dependency = f64;
+    type Skeleton = TurretSkeleton;
+
+    #[cfg(feature = "use-dyn-lib")]
+    const UPDATE_FN: &'static [u8] = b"turret_idle\0";
+
+    #[cfg_attr(feature = "be-dyn-lib", export_name = "turret_idle")]
+    fn update_skeleton_inner(
+        skeleton: &Self::Skeleton,
+        _global_time: Self::Dependency,
+        anim_time: f64,
+        _rate: &mut f32,
+        skeleton_attr: &SkeletonAttr,
+    ) -> Self::Skeleton {
+        let mut next = (*skeleton).clone();
+
+        // Slowly sweeps from side to side, keeping watch
+        let scan = (anim_time as f32 * 0.5).sin();
+        let bob = (anim_time as f32 * 2.0).sin();
+
+        next.base.position =
+            Vec3::new(0.0, skeleton_attr.base.0, skeleton_attr.base.1) * skeleton_attr.scaler;
+        next.base.orientation = Quaternion::rotation_z(0.0);
+        next.base.scale = Vec3::one() * skeleton_attr.scaler / 11.0;
+
+        next.swivel.position = Vec3::new(0.0, skeleton_attr.swivel.0, skeleton_attr.swivel.1);
+        next.swivel.orientation = Quaternion::rotation_z(scan * skeleton_attr.sweep);
+        next.swivel.scale = Vec3::one();
+
+        if skeleton_attr.spin {
+            next.barrel.position = Vec3::new(
+                0.0,
+                skeleton_attr.barrel.0,
+                skeleton_attr.barrel.1 + bob * 0.5,
+            );
+            next.barrel.orientation = Quaternion::rotation_z(anim_time as f32 * 1.5);
+        } else {
+            next.barrel.position = Vec3::new(0.0, skeleton_attr.barrel.0, skeleton_attr.barrel.1);
+            next.barrel.orientation = Quaternion::rotation_x(0.0);
+        }
+        next.barrel.scale = Vec3::one();
+
+        next
+    }
+}
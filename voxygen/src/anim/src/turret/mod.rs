@@ -0,0 +1,115 @@
+pub mod idle;
+pub mod shoot;
+
+// Reexports
+pub use self::{idle::IdleAnimation, shoot::ShootAnimation};
+
+use super::{make_bone, vek::*, FigureBoneData, Skeleton};
+use common::comp::{self};
+use core::convert::TryFrom;
+
+pub type Body = comp::turret::Body;
+
+skeleton_impls!(struct TurretSkeleton {
+    + base,
+    + swivel,
+    + barrel,
+});
+
+impl Skeleton for TurretSkeleton {
+    type Attr = SkeletonAttr;
+    type Body = Body;
+
+    const BONE_COUNT: usize = 3;
+    #[cfg(feature = "use-dyn-lib")]
+    const COMPUTE_FN: &'static [u8] = b"turret_compute_mats\0";
+
+    #[cfg_attr(feature = "be-dyn-lib", export_name = "turret_compute_mats")]
+    fn compute_matrices_inner(
+        &self,
+        base_mat: Mat4<f32>,
+        buf: &mut [FigureBoneData; super::MAX_BONE_COUNT],
+    ) -> Vec3<f32> {
+        let [base, swivel, barrel] = self.local_mats();
+
+        let mount_mat = base_mat * base;
+        let swivel_mat = mount_mat * swivel;
+
+        *(<&mut [_; Self::BONE_COUNT]>::try_from(&mut buf[0..Self::BONE_COUNT]).unwrap()) = [
+            make_bone(mount_mat),
+            make_bone(swivel_mat),
+            make_bone(swivel_mat * barrel),
+        ];
+        Vec3::default()
+    }
+}
+
+pub struct SkeletonAttr {
+    base: (f32, f32),
+    swivel: (f32, f32),
+    barrel: (f32, f32),
+    scaler: f32,
+    /// How far the swivel sweeps to either side while scanning for targets
+    sweep: f32,
+    /// Whether the barrel keeps spinning on its own, like a floating gem,
+    /// rather than only pitching up and down
+    spin: bool,
+}
+
+impl<'a> std::convert::TryFrom<&'a comp::Body> for SkeletonAttr {
+    type Error = ();
+
+    fn try_from(body: &'a comp::Body) -> Result<Self, Self::Error> {
+        match body {
+            comp::Body::Turret(body) => Ok(SkeletonAttr::from(body)),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Default for SkeletonAttr {
+    fn default() -> Self {
+        Self {
+            base: (0.0, 0.0),
+            swivel: (0.0, 0.0),
+            barrel: (0.0, 0.0),
+            scaler: 0.0,
+            sweep: 0.0,
+            spin: false,
+        }
+    }
+}
+
+impl<'a> From<&'a Body> for SkeletonAttr {
+    fn from(body: &'a Body) -> Self {
+        use comp::turret::Species::*;
+        Self {
+            base: match body.species {
+                Ballista => (0.0, 0.0),
+                MagicTurret => (0.0, 0.0),
+                Statue => (0.0, 0.0),
+            },
+            swivel: match body.species {
+                Ballista => (0.0, 6.0),
+                MagicTurret => (0.0, 8.0),
+                Statue => (0.0, 10.0),
+            },
+            barrel: match body.species {
+                Ballista => (2.0, 4.0),
+                MagicTurret => (0.0, 10.0),
+                Statue => (1.0, 9.0),
+            },
+            scaler: match body.species {
+                Ballista => 1.0,
+                MagicTurret => 1.0,
+                Statue => 1.4,
+            },
+            sweep: match body.species {
+                Ballista => 0.15,
+                MagicTurret => 0.0,
+                Statue => 0.05,
+            },
+            spin: matches!(body.species, MagicTurret),
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use super::{
+    super::{vek::*, Animation},
+    SkeletonAttr, TurretSkeleton,
+};
+
+pub struct ShootAnimation;
+
+impl Animation for ShootAnimation {
+    type Dependency = (bool, f64);
+    type Skeleton = TurretSkeleton;
+
+    #[cfg(feature = "use-dyn-lib")]
+    const UPDATE_FN: &'static [u8] = b"turret_shoot\0";
+
+    #[cfg_attr(feature = "be-dyn-lib", export_name = "turret_shoot")]
+    fn update_skeleton_inner(
+        skeleton: &Self::Skeleton,
+        (fired, _global_time): Self::Dependency,
+        anim_time: f64,
+        _rate: &mut f32,
+        skeleton_attr: &SkeletonAttr,
+    ) -> Self::Skeleton {
+        let mut next = (*skeleton).clone();
+
+        let movement = (anim_time as f32 * 2.0).min(1.0);
+
+        // Draws back while aiming, then kicks back once the shot is loosed
+        let (draw, recoil) = if fired {
+            (0.0, (1.0 - movement).powf(2.0))
+        } else {
+            (movement, 0.0)
+        };
+
+        next.base.position =
+            Vec3::new(0.0, skeleton_attr.base.0, skeleton_attr.base.1) * skeleton_attr.scaler;
+        next.base.orientation = Quaternion::rotation_z(0.0);
+        next.base.scale = Vec3::one() * skeleton_attr.scaler / 11.0;
+
+        next.swivel.position = Vec3::new(0.0, skeleton_attr.swivel.0, skeleton_attr.swivel.1);
+        next.swivel.orientation = Quaternion::rotation_z(0.0);
+        next.swivel.scale = Vec3::one();
+
+        next.barrel.position = Vec3::new(
+            0.0,
+            skeleton_attr.barrel.0 - draw * 0.5 - recoil * 1.5,
+            skeleton_attr.barrel.1,
+        );
+        next.barrel.orientation = if skeleton_attr.spin {
+            Quaternion::rotation_z(anim_time as f32 * (4.0 + draw * 8.0))
+        } else {
+            Quaternion::rotation_x(draw * 0.1 + recoil * 0.2)
+        };
+        next.barrel.scale = Vec3::one() * (1.0 + draw * 0.05);
+
+        next
+    }
+}
@@ -16,6 +16,9 @@ use cpal::traits::DeviceTrait;
 use rodio::{source::Source, Decoder, Device};
 use vek::*;
 
+/// Frequency above which sfx are cut off while the listener is muffled, in Hz
+const MUFFLED_CUTOFF: u32 = 800;
+
 #[derive(Default, Clone)]
 pub struct Listener {
     pos: Vec3<f32>,
@@ -40,6 +43,8 @@ pub struct AudioFrontend {
 
     sfx_volume: f32,
     music_volume: f32,
+    /// Whether sfx sound muffled, such as while the listener is under water
+    muffled: bool,
 
     listener: Listener,
 }
@@ -63,6 +68,7 @@ impl AudioFrontend {
             sfx_channels,
             sfx_volume: 1.0,
             music_volume: 1.0,
+            muffled: false,
 
             listener: Listener::default(),
         }
@@ -79,6 +85,7 @@ impl AudioFrontend {
             sfx_channels: Vec::new(),
             sfx_volume: 1.0,
             music_volume: 1.0,
+            muffled: false,
             listener: Listener::default(),
         }
     }
@@ -140,17 +147,29 @@ impl AudioFrontend {
 
     /// Play (once) an sfx file by file path at the give position and volume
     pub fn play_sfx(&mut self, sound: &str, pos: Vec3<f32>, vol: Option<f32>) {
+        self.play_sfx_pitched(sound, pos, vol, 1.0);
+    }
+
+    /// Play (once) an sfx file sped up by `pitch`, raising its pitch, such as
+    /// for playing the notes of an instrument from a single sample
+    pub fn play_sfx_pitched(&mut self, sound: &str, pos: Vec3<f32>, vol: Option<f32>, pitch: f32) {
         if self.audio_device.is_some() {
             let sound = self
                 .sound_cache
                 .load_sound(sound)
-                .amplify(vol.unwrap_or(1.0));
+                .amplify(vol.unwrap_or(1.0))
+                .speed(pitch);
 
             let listener = self.listener.clone();
+            let muffled = self.muffled;
             if let Some(channel) = self.get_sfx_channel() {
                 channel.set_pos(pos);
                 channel.update(&listener);
-                channel.play(sound);
+                if muffled {
+                    channel.play(sound.convert_samples::<f32>().low_pass(MUFFLED_CUTOFF));
+                } else {
+                    channel.play(sound);
+                }
             }
         }
     }
@@ -164,6 +183,9 @@ impl AudioFrontend {
         }
     }
 
+    /// Muffles sfx played from now on, or stops muffling them
+    pub fn set_muffled(&mut self, muffled: bool) { self.muffled = muffled; }
+
     pub fn set_listener_pos(&mut self, pos: Vec3<f32>, ori: Vec3<f32>) {
         self.listener.pos = pos;
         self.listener.ori = ori.normalized();
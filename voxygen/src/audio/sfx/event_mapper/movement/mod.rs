@@ -74,7 +74,9 @@ impl EventMapper for MovementEventMapper {
                     | Body::QuadrupedLow(_)
                     | Body::BirdMedium(_)
                     | Body::BirdSmall(_)
-                    | Body::BipedLarge(_) => Self::map_non_humanoid_movement_event(physics, vel.0),
+                    | Body::BipedLarge(_)
+                    | Body::Colossus(_)
+                    | Body::Arthropod(_) => Self::map_non_humanoid_movement_event(physics, vel.0),
                     _ => SfxEvent::Idle, // Ignore fish, etc...
                 };
 
@@ -200,6 +202,7 @@ impl MovementEventMapper {
             Body::BirdMedium(_) => 0.3,
             Body::BirdSmall(_) => 0.2,
             Body::BipedLarge(_) => 1.0,
+            Body::Colossus(_) => 1.0,
             _ => 0.9,
         }
     }
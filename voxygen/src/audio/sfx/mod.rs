@@ -87,12 +87,13 @@ use crate::{audio::AudioFrontend, scene::Camera};
 use common::{
     assets,
     comp::{
-        item::{ItemKind, ToolCategory},
+        item::{Instrument, ItemKind, ToolCategory},
         object, Body, CharacterAbilityType, InventoryUpdateEvent,
     },
     event::EventBus,
     outcome::Outcome,
     state::State,
+    states::perform,
 };
 use event_mapper::SfxEventMapper;
 use hashbrown::HashMap;
@@ -317,6 +318,35 @@ impl SfxMgr {
                     },
                 }
             },
+            Outcome::SpriteBreak { pos, .. } => {
+                // TODO: from sfx config?
+                audio.play_sfx("voxygen.audio.sfx.crafting.hammer", *pos, None);
+            },
+            Outcome::Splash { pos, speed } => {
+                let file_ref = vec![
+                    "voxygen.audio.sfx.footsteps.water_splash_1",
+                    "voxygen.audio.sfx.footsteps.water_splash_2",
+                    "voxygen.audio.sfx.footsteps.water_splash_3",
+                    "voxygen.audio.sfx.footsteps.water_splash_4",
+                ][rand::thread_rng().gen_range(0, 4)];
+
+                audio.play_sfx(file_ref, *pos, Some((speed / 15.0).min(1.5)));
+            },
+            // Hits already make their own sounds
+            Outcome::HeavyHit { .. } => {},
+            Outcome::Note {
+                pos,
+                instrument,
+                note,
+            } => {
+                let file_ref = match instrument {
+                    Instrument::Lute => "voxygen.audio.sfx.instruments.lute",
+                    Instrument::Drum => "voxygen.audio.sfx.instruments.drum",
+                    Instrument::Flute => "voxygen.audio.sfx.instruments.flute",
+                };
+
+                audio.play_sfx_pitched(file_ref, *pos, None, perform::pitch(*note));
+            },
         }
     }
 
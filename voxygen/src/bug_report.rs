@@ -0,0 +1,127 @@
+//! Bug reports that players can attach to an issue.
+//!
+//! A report is a zip file holding a description of the state of the game when
+//! it was made (version, server, and the position and character state of the
+//! player), the last lines of the log, and optionally a screenshot, so that
+//! glitches in physics or animation can be reproduced.
+
+use crate::settings::voxygen_data_dir;
+use client::Client;
+use common::{
+    comp::{Body, CharacterState, Ori, PhysicsState, Pos, Vel},
+    util::DISPLAY_VERSION_LONG,
+};
+use image::{DynamicImage, ImageOutputFormat};
+use specs::{Component, WorldExt};
+use std::{
+    fmt::Debug,
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+use zip::{write::FileOptions, ZipWriter};
+
+/// How many of the last lines of the log go into a report
+const LOG_LINES: usize = 200;
+const LOG_FILENAME: &str = "voxygen.log";
+
+pub struct BugReport {
+    description: String,
+    state: String,
+    screenshot: Option<DynamicImage>,
+}
+
+impl BugReport {
+    /// Captures the state of the game as seen by the client
+    pub fn capture(client: &Client, description: String) -> Self {
+        let mut state = format!(
+            "Version: {}\nServer: {}\nTime: {}\n",
+            *DISPLAY_VERSION_LONG,
+            client.server_info.name,
+            client.state().get_time(),
+        );
+        state.push_str(&describe::<Pos>(client, "Position"));
+        state.push_str(&describe::<Vel>(client, "Velocity"));
+        state.push_str(&describe::<Ori>(client, "Orientation"));
+        state.push_str(&describe::<Body>(client, "Body"));
+        state.push_str(&describe::<CharacterState>(client, "Character state"));
+        state.push_str(&describe::<PhysicsState>(client, "Physics state"));
+
+        Self {
+            description,
+            state,
+            screenshot: None,
+        }
+    }
+
+    pub fn with_screenshot(mut self, screenshot: DynamicImage) -> Self {
+        self.screenshot = Some(screenshot);
+        self
+    }
+
+    /// Writes the report into a new zip file in the bug report directory,
+    /// returning the path of the file
+    pub fn save(self, logs_path: &Path) -> io::Result<PathBuf> {
+        let mut path = voxygen_data_dir();
+        path.push("bug_reports");
+        fs::create_dir_all(&path)?;
+        path.push(format!(
+            "bug_report_{}.zip",
+            SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .map(|d| d.as_millis())
+                .unwrap_or(0)
+        ));
+
+        let mut zip = ZipWriter::new(fs::File::create(&path)?);
+        let options = FileOptions::default();
+
+        zip.start_file("report.txt", options)?;
+        writeln!(zip, "{}\n", self.description)?;
+        zip.write_all(self.state.as_bytes())?;
+
+        zip.start_file(LOG_FILENAME, options)?;
+        zip.write_all(recent_log_lines(logs_path).as_bytes())?;
+
+        if let Some(screenshot) = self.screenshot {
+            let mut png = Vec::new();
+            screenshot
+                .write_to(&mut png, ImageOutputFormat::Png)
+                .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+            zip.start_file("screenshot.png", options)?;
+            zip.write_all(&png)?;
+        }
+
+        zip.finish()?;
+        Ok(path)
+    }
+}
+
+/// Describes a component of the player's entity
+fn describe<C: Component + Debug>(client: &Client, name: &str) -> String {
+    let storage = client.state().ecs().read_storage::<C>();
+    format!("{}: {:#?}\n", name, storage.get(client.entity()))
+}
+
+/// The last lines of the newest log file
+fn recent_log_lines(logs_path: &Path) -> String {
+    // Log files roll over daily, with the date appended to their name
+    let newest = fs::read_dir(logs_path).ok().and_then(|entries| {
+        entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| {
+                entry
+                    .file_name()
+                    .to_string_lossy()
+                    .starts_with(LOG_FILENAME)
+            })
+            .max_by_key(|entry| entry.metadata().and_then(|m| m.modified()).ok())
+    });
+    let log = match newest.map(|entry| fs::read_to_string(entry.path())) {
+        Some(Ok(log)) => log,
+        _ => return String::new(),
+    };
+    let lines = log.lines().collect::<Vec<_>>();
+    lines[lines.len().saturating_sub(LOG_LINES)..].join("\n")
+}
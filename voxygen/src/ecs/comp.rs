@@ -12,6 +12,10 @@ pub struct HpFloater {
     pub hp_change: i32,
     // Used for randomly offsetting
     pub rand: f32,
+    // Whether the change came from a critical hit
+    pub crit: bool,
+    // Whether the change came from a projectile to the head
+    pub headshot: bool,
 }
 #[derive(Clone, Debug, Default)]
 pub struct HpFloaterList {
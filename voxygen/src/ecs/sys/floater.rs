@@ -73,7 +73,9 @@ impl<'a> System<'a> for Sys {
                 // of events)
                 if match health.last_change.1.cause {
                     HealthSource::Attack { by }
-                    | HealthSource::Projectile { owner: Some(by) }
+                    | HealthSource::Projectile {
+                        owner: Some(by), ..
+                    }
                     | HealthSource::Energy { owner: Some(by) }
                     | HealthSource::Explosion { owner: Some(by) }
                     | HealthSource::Buff { owner: Some(by) }
@@ -94,8 +96,15 @@ impl<'a> System<'a> for Sys {
                 } {
                     hp_floater_list.floaters.push(HpFloater {
                         timer: 0.0,
-                        hp_change: health.last_change.1.amount,
+                        // Only show the health that was actually lost to killing blows
+                        hp_change: health.last_change.1.amount
+                            + health.last_change.1.overkill as i32,
                         rand: rand::random(),
+                        crit: health.last_change.1.crit,
+                        headshot: matches!(
+                            health.last_change.1.cause,
+                            HealthSource::Projectile { headshot: true, .. }
+                        ),
                     });
                 }
             }
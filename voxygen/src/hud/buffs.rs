@@ -131,7 +131,7 @@ impl<'a> Widget for BuffsBar<'a> {
                 .set(state.ids.buffs_align, ui);
 
             // Buffs and Debuffs
-            let (buff_count, debuff_count) = buffs.iter_active().map(get_buff_info).fold(
+            let (buff_count, debuff_count) = buffs.iter_kinds().map(get_buff_info).fold(
                 (0, 0),
                 |(buff_count, debuff_count), info| {
                     if info.is_buff {
@@ -168,7 +168,7 @@ impl<'a> Widget for BuffsBar<'a> {
                 .zip(state.ids.buff_timers.iter().copied())
                 .zip(
                     buffs
-                        .iter_active()
+                        .iter_kinds()
                         .map(get_buff_info)
                         .filter(|info| info.is_buff),
                 )
@@ -182,6 +182,9 @@ impl<'a> Widget for BuffsBar<'a> {
                     }) as u32; // Percentage to determine which frame of the timer overlay is displayed
                     let buff_img = match buff.kind {
                         BuffKind::Regeneration { .. } => self.imgs.buff_plus_0,
+                        BuffKind::Focused { .. } => self.imgs.buff_focus_0,
+                        BuffKind::Taunting { .. } => self.imgs.protection_ico,
+                        BuffKind::Enraged { .. } => self.imgs.fire_spell_1,
                         _ => self.imgs.missing_icon,
                     };
                     let buff_widget = Image::new(buff_img).w_h(20.0, 20.0);
@@ -207,6 +210,9 @@ impl<'a> Widget for BuffsBar<'a> {
                         BuffKind::Regeneration { .. } => {
                             localized_strings.get("buff.title.heal_test")
                         },
+                        BuffKind::Focused { .. } => localized_strings.get("buff.title.focused"),
+                        BuffKind::Taunting { .. } => localized_strings.get("buff.title.taunting"),
+                        BuffKind::Enraged { .. } => localized_strings.get("buff.title.enraged"),
                         _ => localized_strings.get("buff.title.missing"),
                     };
                     let remaining_time = if current_duration.is_none() {
@@ -219,9 +225,18 @@ impl<'a> Widget for BuffsBar<'a> {
                         BuffKind::Regeneration { .. } => {
                             localized_strings.get("buff.desc.heal_test")
                         },
+                        BuffKind::Focused { .. } => localized_strings.get("buff.desc.focused"),
+                        BuffKind::Taunting { .. } => localized_strings.get("buff.desc.taunting"),
+                        BuffKind::Enraged { .. } => localized_strings.get("buff.desc.enraged"),
                         _ => localized_strings.get("buff.desc.missing"),
                     };
-                    let desc = format!("{}\n\n{}\n\n{}", desc_txt, remaining_time, click_to_remove);
+                    let desc = format!(
+                        "{}{}\n\n{}\n\n{}",
+                        desc_txt,
+                        stacks_txt(localized_strings, buff.stacks),
+                        remaining_time,
+                        click_to_remove
+                    );
                     // Timer overlay
                     if Button::image(match duration_percentage as u64 {
                         875..=1000 => self.imgs.nothing, // 8/8
@@ -258,7 +273,7 @@ impl<'a> Widget for BuffsBar<'a> {
                 .zip(state.ids.debuff_timers.iter().copied())
                 .zip(
                     buffs
-                        .iter_active()
+                        .iter_kinds()
                         .map(get_buff_info)
                         .filter(|info| !info.is_buff),
                 )
@@ -272,6 +287,10 @@ impl<'a> Widget for BuffsBar<'a> {
                     }) as u32; // Percentage to determine which frame of the timer overlay is displayed
                     let debuff_img = match debuff.kind {
                         BuffKind::Bleeding { .. } => self.imgs.debuff_bleed_0,
+                        BuffKind::Burning { .. } => self.imgs.debuff_burning_0,
+                        BuffKind::Frozen { .. } => self.imgs.debuff_skull_0,
+                        BuffKind::Poisoned { .. } => self.imgs.debuff_skull_0,
+                        BuffKind::Exhausted { .. } => self.imgs.debuff_skull_0,
                         BuffKind::Cursed { .. } => self.imgs.debuff_skull_0,
                         _ => self.imgs.missing_icon,
                     };
@@ -299,6 +318,12 @@ impl<'a> Widget for BuffsBar<'a> {
                         BuffKind::Bleeding { .. } => {
                             localized_strings.get("debuff.title.bleed_test")
                         },
+                        BuffKind::Burning { .. } => localized_strings.get("debuff.title.burning"),
+                        BuffKind::Frozen { .. } => localized_strings.get("debuff.title.frozen"),
+                        BuffKind::Poisoned { .. } => localized_strings.get("debuff.title.poisoned"),
+                        BuffKind::Exhausted { .. } => {
+                            localized_strings.get("debuff.title.exhausted")
+                        },
                         _ => localized_strings.get("buff.title.missing"),
                     };
                     let remaining_time = if current_duration.is_none() {
@@ -310,9 +335,20 @@ impl<'a> Widget for BuffsBar<'a> {
                         BuffKind::Bleeding { .. } => {
                             localized_strings.get("debuff.desc.bleed_test")
                         },
+                        BuffKind::Burning { .. } => localized_strings.get("debuff.desc.burning"),
+                        BuffKind::Frozen { .. } => localized_strings.get("debuff.desc.frozen"),
+                        BuffKind::Poisoned { .. } => localized_strings.get("debuff.desc.poisoned"),
+                        BuffKind::Exhausted { .. } => {
+                            localized_strings.get("debuff.desc.exhausted")
+                        },
                         _ => localized_strings.get("debuff.desc.missing"),
                     };
-                    let desc = format!("{}\n\n{}", desc_txt, remaining_time);
+                    let desc = format!(
+                        "{}{}\n\n{}",
+                        desc_txt,
+                        stacks_txt(localized_strings, debuff.stacks),
+                        remaining_time
+                    );
                     Image::new(match duration_percentage as u64 {
                         875..=1000 => self.imgs.nothing, // 8/8
                         750..=874 => self.imgs.buff_0,   // 7/8
@@ -367,7 +403,7 @@ impl<'a> Widget for BuffsBar<'a> {
                 .copied()
                 .zip(state.ids.buff_timers.iter().copied())
                 .zip(state.ids.buff_txts.iter().copied())
-                .zip(buffs.iter_active().map(get_buff_info))
+                .zip(buffs.iter_kinds().map(get_buff_info))
                 .enumerate()
                 .for_each(|(i, (((id, timer_id), txt_id), buff))| {
                     let max_duration = buff.data.duration;
@@ -379,7 +415,14 @@ impl<'a> Widget for BuffsBar<'a> {
                     }) as u32;
                     let buff_img = match buff.kind {
                         BuffKind::Regeneration { .. } => self.imgs.buff_plus_0,
+                        BuffKind::Focused { .. } => self.imgs.buff_focus_0,
+                        BuffKind::Taunting { .. } => self.imgs.protection_ico,
+                        BuffKind::Enraged { .. } => self.imgs.fire_spell_1,
                         BuffKind::Bleeding { .. } => self.imgs.debuff_bleed_0,
+                        BuffKind::Burning { .. } => self.imgs.debuff_burning_0,
+                        BuffKind::Frozen { .. } => self.imgs.debuff_skull_0,
+                        BuffKind::Poisoned { .. } => self.imgs.debuff_skull_0,
+                        BuffKind::Exhausted { .. } => self.imgs.debuff_skull_0,
                         BuffKind::Cursed { .. } => self.imgs.debuff_skull_0,
                     };
                     let buff_widget = Image::new(buff_img).w_h(40.0, 40.0);
@@ -405,9 +448,18 @@ impl<'a> Widget for BuffsBar<'a> {
                         BuffKind::Regeneration { .. } => {
                             localized_strings.get("buff.title.heal_test")
                         },
+                        BuffKind::Focused { .. } => localized_strings.get("buff.title.focused"),
+                        BuffKind::Taunting { .. } => localized_strings.get("buff.title.taunting"),
+                        BuffKind::Enraged { .. } => localized_strings.get("buff.title.enraged"),
                         BuffKind::Bleeding { .. } => {
                             localized_strings.get("debuff.title.bleed_test")
                         },
+                        BuffKind::Burning { .. } => localized_strings.get("debuff.title.burning"),
+                        BuffKind::Frozen { .. } => localized_strings.get("debuff.title.frozen"),
+                        BuffKind::Poisoned { .. } => localized_strings.get("debuff.title.poisoned"),
+                        BuffKind::Exhausted { .. } => {
+                            localized_strings.get("debuff.title.exhausted")
+                        },
                         _ => localized_strings.get("buff.title.missing"),
                     };
                     let remaining_time = if current_duration.is_none() {
@@ -420,15 +472,25 @@ impl<'a> Widget for BuffsBar<'a> {
                         BuffKind::Regeneration { .. } => {
                             localized_strings.get("buff.desc.heal_test")
                         },
+                        BuffKind::Focused { .. } => localized_strings.get("buff.desc.focused"),
+                        BuffKind::Taunting { .. } => localized_strings.get("buff.desc.taunting"),
+                        BuffKind::Enraged { .. } => localized_strings.get("buff.desc.enraged"),
                         BuffKind::Bleeding { .. } => {
                             localized_strings.get("debuff.desc.bleed_test")
                         },
+                        BuffKind::Burning { .. } => localized_strings.get("debuff.desc.burning"),
+                        BuffKind::Frozen { .. } => localized_strings.get("debuff.desc.frozen"),
+                        BuffKind::Poisoned { .. } => localized_strings.get("debuff.desc.poisoned"),
+                        BuffKind::Exhausted { .. } => {
+                            localized_strings.get("debuff.desc.exhausted")
+                        },
                         _ => localized_strings.get("buff.desc.missing"),
                     };
+                    let stacks = stacks_txt(localized_strings, buff.stacks);
                     let desc = if buff.is_buff {
-                        format!("{}\n\n{}", desc_txt, click_to_remove)
+                        format!("{}{}\n\n{}", desc_txt, stacks, click_to_remove)
                     } else {
-                        desc_txt.to_string()
+                        format!("{}{}", desc_txt, stacks)
                     };
                     // Timer overlay
                     if Button::image(match duration_percentage as u64 {
@@ -472,3 +534,13 @@ impl<'a> Widget for BuffsBar<'a> {
         event
     }
 }
+
+/// Tooltip line showing how many stacks of a buff are in effect, empty if there
+/// is only one
+fn stacks_txt(localized_strings: &VoxygenLocalization, stacks: u32) -> String {
+    if stacks > 1 {
+        format!("\n{}: {}", localized_strings.get("buff.stacks"), stacks)
+    } else {
+        String::new()
+    }
+}
@@ -0,0 +1,338 @@
+use super::{
+    img_ids::{Imgs, ImgsRot},
+    TEXT_COLOR, UI_HIGHLIGHT_0, UI_MAIN,
+};
+use crate::{
+    i18n::VoxygenLocalization,
+    ui::{fonts::ConrodVoxygenFonts, ImageFrame, Tooltip, TooltipManager, Tooltipable},
+};
+use client::{self, Client};
+use common::comp::{
+    breakdown::{self, Contribution, StatSource},
+    item::armor::Protection,
+    Affixes, Body, BuffKind, Buffs, DamageSource, Energy, Loadout, ModifierKind, StatBreakdown,
+    Stats,
+};
+use conrod_core::{
+    color,
+    widget::{self, Button, Image, Rectangle, Text},
+    widget_ids, Color, Colorable, Positionable, Sizeable, Widget, WidgetCommon,
+};
+
+widget_ids! {
+    pub struct Ids {
+        frame,
+        close,
+        title,
+        content_align,
+        labels[],
+        values[],
+    }
+}
+
+pub enum Event {
+    Close,
+}
+
+#[derive(WidgetCommon)]
+pub struct CharacterSheet<'a> {
+    client: &'a Client,
+    imgs: &'a Imgs,
+    rot_imgs: &'a ImgsRot,
+    fonts: &'a ConrodVoxygenFonts,
+    tooltip_manager: &'a mut TooltipManager,
+    localized_strings: &'a std::sync::Arc<VoxygenLocalization>,
+
+    #[conrod(common_builder)]
+    common: widget::CommonBuilder,
+}
+
+impl<'a> CharacterSheet<'a> {
+    pub fn new(
+        client: &'a Client,
+        imgs: &'a Imgs,
+        rot_imgs: &'a ImgsRot,
+        fonts: &'a ConrodVoxygenFonts,
+        tooltip_manager: &'a mut TooltipManager,
+        localized_strings: &'a std::sync::Arc<VoxygenLocalization>,
+    ) -> Self {
+        Self {
+            client,
+            imgs,
+            rot_imgs,
+            fonts,
+            tooltip_manager,
+            localized_strings,
+            common: widget::CommonBuilder::default(),
+        }
+    }
+
+    fn source_name(&self, source: &StatSource) -> String {
+        match source {
+            StatSource::Base => self
+                .localized_strings
+                .get("hud.character_sheet.source.base")
+                .to_string(),
+            StatSource::Level => self
+                .localized_strings
+                .get("hud.character_sheet.source.level")
+                .to_string(),
+            StatSource::Armor(name) => name.clone(),
+            StatSource::Buff(kind) => self
+                .localized_strings
+                .get(match kind {
+                    BuffKind::Regeneration { .. } => "buff.title.heal_test",
+                    BuffKind::Focused { .. } => "buff.title.focused",
+                    BuffKind::Taunting { .. } => "buff.title.taunting",
+                    BuffKind::Enraged { .. } => "buff.title.enraged",
+                    BuffKind::Bleeding { .. } => "debuff.title.bleed_test",
+                    BuffKind::Burning { .. } => "debuff.title.burning",
+                    BuffKind::Frozen { .. } => "debuff.title.frozen",
+                    BuffKind::Poisoned { .. } => "debuff.title.poisoned",
+                    BuffKind::Exhausted { .. } => "debuff.title.exhausted",
+                    _ => "buff.title.missing",
+                })
+                .to_string(),
+            StatSource::Affix(affix) => affix.name().to_string(),
+        }
+    }
+
+    /// One line per source contributing to a stat, shown as percentages if
+    /// `percent` is set
+    fn describe(&self, contributions: &[Contribution], percent: bool) -> String {
+        contributions
+            .iter()
+            .map(|c| {
+                let value = match (c.kind, percent) {
+                    (ModifierKind::Additive, false) => format!("{:+}", c.value.round()),
+                    (ModifierKind::Additive, true) => format!("{:+.0}%", c.value * 100.0),
+                    (ModifierKind::Multiplicative, _) => format!("x{:.2}", c.value),
+                };
+                format!("{}: {}", self.source_name(&c.source), value)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn damage_source_name(&self, source: DamageSource) -> &str {
+        self.localized_strings.get(match source {
+            DamageSource::Melee => "hud.character_sheet.damage.melee",
+            DamageSource::Projectile => "hud.character_sheet.damage.projectile",
+            DamageSource::Explosion => "hud.character_sheet.damage.explosion",
+            DamageSource::Shockwave => "hud.character_sheet.damage.shockwave",
+            DamageSource::Energy => "hud.character_sheet.damage.energy",
+            DamageSource::Falling => "hud.character_sheet.damage.falling",
+            DamageSource::Hazard => "hud.character_sheet.damage.hazard",
+            DamageSource::Healing => "hud.character_sheet.damage.healing",
+        })
+    }
+
+    /// Rows of the sheet as a label, a value and the tooltip explaining the
+    /// value
+    fn rows(&self, stats: &StatBreakdown) -> Vec<(String, String, String)> {
+        let i18n = &self.localized_strings;
+        let mut rows = vec![
+            (
+                i18n.get("hud.character_sheet.health").to_string(),
+                format!(
+                    "{}/{}",
+                    stats.health,
+                    breakdown::total(&stats.max_health) as u32
+                ),
+                self.describe(&stats.max_health, false),
+            ),
+            (
+                i18n.get("hud.character_sheet.energy").to_string(),
+                format!("{}/{}", stats.energy, stats.max_energy),
+                i18n.get("hud.character_sheet.energy_desc").to_string(),
+            ),
+            (
+                i18n.get("hud.character_sheet.energy_regen").to_string(),
+                format!("{:.1}/s", stats.energy_regen),
+                self.describe(&stats.energy_regen_modifier, true),
+            ),
+            (
+                i18n.get("hud.character_sheet.damage_reduction").to_string(),
+                format!("{:.0}%", stats.damage_reduction * 100.0),
+                if stats.armor.is_empty() {
+                    i18n.get("hud.character_sheet.no_armor").to_string()
+                } else {
+                    stats
+                        .armor
+                        .iter()
+                        .map(|(name, protection)| match protection {
+                            Protection::Normal(protection) => format!("{}: {}", name, protection),
+                            Protection::Invincible => {
+                                format!("{}: {}", name, i18n.get("hud.character_sheet.invincible"))
+                            },
+                        })
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                },
+            ),
+            (
+                i18n.get("hud.character_sheet.movement_speed").to_string(),
+                format!("{:.0}%", breakdown::total(&stats.movement_speed) * 100.0),
+                self.describe(&stats.movement_speed, true),
+            ),
+            (
+                i18n.get("hud.character_sheet.crit_chance").to_string(),
+                format!("{:.0}%", stats.crit.chance * 100.0),
+                i18n.get("hud.character_sheet.crit_desc").replace(
+                    "{bonus}",
+                    &format!("{:.0}", (stats.crit.multiplier - 1.0) * 100.0),
+                ),
+            ),
+        ];
+        rows.extend(
+            stats
+                .resistances
+                .iter()
+                .map(|(source, resistance, blocking)| {
+                    (
+                        i18n.get("hud.character_sheet.resistance")
+                            .replace("{source}", self.damage_source_name(*source)),
+                        format!("{:.0}%", resistance * 100.0),
+                        if source.can_block() {
+                            i18n.get("hud.character_sheet.resistance_blocking")
+                                .replace("{resistance}", &format!("{:.0}", blocking * 100.0))
+                        } else {
+                            i18n.get("hud.character_sheet.resistance_unblockable")
+                                .to_string()
+                        },
+                    )
+                }),
+        );
+        rows
+    }
+}
+
+impl<'a> Widget for CharacterSheet<'a> {
+    type Event = Option<Event>;
+    type State = Ids;
+    type Style = ();
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State { Ids::new(id_gen) }
+
+    #[allow(clippy::unused_unit)] // TODO: Pending review in #587
+    fn style(&self) -> Self::Style { () }
+
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        let widget::UpdateArgs {
+            id: _, state, ui, ..
+        } = args;
+
+        let mut event = None;
+
+        // Tooltips
+        let stat_tooltip = Tooltip::new({
+            // Edge images [t, b, r, l]
+            // Corner images [tr, tl, br, bl]
+            let edge = &self.rot_imgs.tt_side;
+            let corner = &self.rot_imgs.tt_corner;
+            ImageFrame::new(
+                [edge.cw180, edge.none, edge.cw270, edge.cw90],
+                [corner.none, corner.cw270, corner.cw90, corner.cw180],
+                Color::Rgba(0.08, 0.07, 0.04, 1.0),
+                5.0,
+            )
+        })
+        .title_font_size(self.fonts.cyri.scale(15))
+        .parent(ui.window)
+        .desc_font_size(self.fonts.cyri.scale(12))
+        .font_id(self.fonts.cyri.conrod_id)
+        .desc_text_color(TEXT_COLOR);
+
+        Image::new(self.imgs.window_3)
+            .top_left_with_margins_on(ui.window, 200.0, 25.0)
+            .w_h(103.0 * 4.0, 122.0 * 4.0)
+            .color(Some(UI_MAIN))
+            .set(state.frame, ui);
+
+        // X-Button
+        if Button::image(self.imgs.close_button)
+            .w_h(28.0, 28.0)
+            .hover_image(self.imgs.close_button_hover)
+            .press_image(self.imgs.close_button_press)
+            .top_right_with_margins_on(state.frame, 0.0, 0.0)
+            .set(state.close, ui)
+            .was_clicked()
+        {
+            event = Some(Event::Close);
+        }
+
+        // Title
+        Text::new(&self.localized_strings.get("hud.character_sheet"))
+            .mid_top_with_margin_on(state.frame, 6.0)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(14))
+            .color(TEXT_COLOR)
+            .set(state.title, ui);
+
+        // Content Alignment
+        Rectangle::fill_with([95.0 * 4.0, 108.0 * 4.0], color::TRANSPARENT)
+            .mid_top_with_margin_on(state.frame, 40.0)
+            .set(state.content_align, ui);
+
+        // Contents
+        let stats = {
+            let ecs = self.client.state().ecs();
+            let entity = self.client.entity();
+            let bodies = ecs.read_storage::<Body>();
+            let stats = ecs.read_storage::<Stats>();
+            bodies
+                .get(entity)
+                .zip(stats.get(entity))
+                .map(|(body, stats)| {
+                    StatBreakdown::new(
+                        body,
+                        stats,
+                        ecs.read_storage::<Energy>().get(entity),
+                        ecs.read_storage::<Loadout>().get(entity),
+                        ecs.read_storage::<Buffs>().get(entity),
+                        ecs.read_storage::<Affixes>().get(entity),
+                    )
+                })
+        };
+        let rows = match stats {
+            Some(stats) => self.rows(&stats),
+            None => return event,
+        };
+
+        if state.labels.len() < rows.len() {
+            state.update(|state| {
+                state
+                    .labels
+                    .resize(rows.len(), &mut ui.widget_id_generator());
+                state
+                    .values
+                    .resize(rows.len(), &mut ui.widget_id_generator());
+            });
+        }
+
+        for (i, (label, value, desc)) in rows.iter().enumerate() {
+            let text = Text::new(label)
+                .font_id(self.fonts.cyri.conrod_id)
+                .font_size(self.fonts.cyri.scale(14))
+                .color(TEXT_COLOR);
+            let text = if i == 0 {
+                text.top_left_with_margins_on(state.content_align, 10.0, 10.0)
+            } else {
+                text.down_from(state.labels[i - 1], 10.0)
+            };
+            text.set(state.labels[i], ui);
+
+            // Hovering the value explains where it comes from
+            Text::new(value)
+                .align_middle_y_of(state.labels[i])
+                .align_right_of(state.content_align)
+                .font_id(self.fonts.cyri.conrod_id)
+                .font_size(self.fonts.cyri.scale(14))
+                .color(UI_HIGHLIGHT_0)
+                .with_tooltip(self.tooltip_manager, label, desc, &stat_tooltip, TEXT_COLOR)
+                .set(state.values[i], ui);
+        }
+
+        event
+    }
+}
@@ -16,6 +16,7 @@ widget_ids! {
         menu_button_4,
         menu_button_5,
         menu_button_6,
+        menu_button_7,
     }
 }
 
@@ -50,6 +51,7 @@ pub struct State {
 
 pub enum Event {
     OpenSettings(SettingsTab),
+    ReportBug,
     CharacterSelection,
     Logout,
     Quit,
@@ -74,7 +76,7 @@ impl<'a> Widget for EscMenu<'a> {
         let widget::UpdateArgs { state, ui, .. } = args;
 
         Image::new(self.imgs.esc_frame)
-            .w_h(240.0, 380.0)
+            .w_h(240.0, 440.0)
             .color(Some(Color::Rgba(1.0, 1.0, 1.0, 0.9)))
             .middle_of(ui.window)
             .set(state.ids.esc_bg, ui);
@@ -133,12 +135,28 @@ impl<'a> Widget for EscMenu<'a> {
         {
             return Some(Event::OpenSettings(SettingsTab::Controls));
         };
-        // Characters
+        // Report a bug
         if Button::image(self.imgs.button)
             .mid_bottom_with_margin_on(state.ids.menu_button_3, -55.0)
             .w_h(210.0, 50.0)
             .hover_image(self.imgs.button_hover)
             .press_image(self.imgs.button_press)
+            .label(&self.localized_strings.get("esc_menu.report_bug"))
+            .label_y(conrod_core::position::Relative::Scalar(3.0))
+            .label_color(TEXT_COLOR)
+            .label_font_size(self.fonts.cyri.scale(20))
+            .label_font_id(self.fonts.cyri.conrod_id)
+            .set(state.ids.menu_button_7, ui)
+            .was_clicked()
+        {
+            return Some(Event::ReportBug);
+        };
+        // Characters
+        if Button::image(self.imgs.button)
+            .mid_bottom_with_margin_on(state.ids.menu_button_7, -55.0)
+            .w_h(210.0, 50.0)
+            .hover_image(self.imgs.button_hover)
+            .press_image(self.imgs.button_press)
             .label(&self.localized_strings.get("common.characters"))
             .label_y(conrod_core::position::Relative::Scalar(3.0))
             .label_color(TEXT_COLOR)
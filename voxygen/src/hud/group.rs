@@ -464,7 +464,7 @@ impl<'a> Widget for Group<'a> {
                             .copied()
                             .zip(state.ids.buff_timers.iter().copied())
                             .skip(total_buff_count - buff_count)
-                            .zip(buffs.iter_active().map(get_buff_info))
+                            .zip(buffs.iter_kinds().map(get_buff_info))
                             .for_each(|((id, timer_id), buff)| {
                                 let max_duration = buff.data.duration;
                                 let pulsating_col = Color::Rgba(1.0, 1.0, 1.0, buff_ani);
@@ -477,7 +477,14 @@ impl<'a> Widget for Group<'a> {
                                 }) as u32; // Percentage to determine which frame of the timer overlay is displayed
                                 let buff_img = match buff.kind {
                                     BuffKind::Regeneration { .. } => self.imgs.buff_plus_0,
+                                    BuffKind::Focused { .. } => self.imgs.buff_focus_0,
+                                    BuffKind::Taunting { .. } => self.imgs.protection_ico,
+                                    BuffKind::Enraged { .. } => self.imgs.fire_spell_1,
                                     BuffKind::Bleeding { .. } => self.imgs.debuff_bleed_0,
+                                    BuffKind::Burning { .. } => self.imgs.debuff_burning_0,
+                                    BuffKind::Frozen { .. } => self.imgs.debuff_skull_0,
+                                    BuffKind::Poisoned { .. } => self.imgs.debuff_skull_0,
+                                    BuffKind::Exhausted { .. } => self.imgs.debuff_skull_0,
                                     BuffKind::Cursed { .. } => self.imgs.debuff_skull_0,
                                 };
                                 let buff_widget = Image::new(buff_img).w_h(15.0, 15.0);
@@ -507,9 +514,30 @@ impl<'a> Widget for Group<'a> {
                                     BuffKind::Regeneration { .. } => {
                                         localized_strings.get("buff.title.heal_test")
                                     },
+                                    BuffKind::Focused { .. } => {
+                                        localized_strings.get("buff.title.focused")
+                                    },
+                                    BuffKind::Taunting { .. } => {
+                                        localized_strings.get("buff.title.taunting")
+                                    },
+                                    BuffKind::Enraged { .. } => {
+                                        localized_strings.get("buff.title.enraged")
+                                    },
                                     BuffKind::Bleeding { .. } => {
                                         localized_strings.get("debuff.title.bleed_test")
                                     },
+                                    BuffKind::Burning { .. } => {
+                                        localized_strings.get("debuff.title.burning")
+                                    },
+                                    BuffKind::Frozen { .. } => {
+                                        localized_strings.get("debuff.title.frozen")
+                                    },
+                                    BuffKind::Poisoned { .. } => {
+                                        localized_strings.get("debuff.title.poisoned")
+                                    },
+                                    BuffKind::Exhausted { .. } => {
+                                        localized_strings.get("debuff.title.exhausted")
+                                    },
                                     _ => localized_strings.get("buff.title.missing"),
                                 };
                                 let remaining_time = if current_duration.is_none() {
@@ -524,9 +552,30 @@ impl<'a> Widget for Group<'a> {
                                     BuffKind::Regeneration { .. } => {
                                         localized_strings.get("buff.desc.heal_test")
                                     },
+                                    BuffKind::Focused { .. } => {
+                                        localized_strings.get("buff.desc.focused")
+                                    },
+                                    BuffKind::Taunting { .. } => {
+                                        localized_strings.get("buff.desc.taunting")
+                                    },
+                                    BuffKind::Enraged { .. } => {
+                                        localized_strings.get("buff.desc.enraged")
+                                    },
                                     BuffKind::Bleeding { .. } => {
                                         localized_strings.get("debuff.desc.bleed_test")
                                     },
+                                    BuffKind::Burning { .. } => {
+                                        localized_strings.get("debuff.desc.burning")
+                                    },
+                                    BuffKind::Frozen { .. } => {
+                                        localized_strings.get("debuff.desc.frozen")
+                                    },
+                                    BuffKind::Poisoned { .. } => {
+                                        localized_strings.get("debuff.desc.poisoned")
+                                    },
+                                    BuffKind::Exhausted { .. } => {
+                                        localized_strings.get("debuff.desc.exhausted")
+                                    },
                                     _ => localized_strings.get("buff.desc.missing"),
                                 };
                                 let desc = format!("{}\n\n{}", desc_txt, remaining_time);
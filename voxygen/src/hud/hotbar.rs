@@ -84,6 +84,8 @@ impl State {
                             ToolKind::Hammer(_) => true,
                             ToolKind::Axe(_) => true,
                             ToolKind::Bow(_) => true,
+                            ToolKind::Shield(_) => true,
+                            ToolKind::Sceptre(_) => true,
                             _ => false,
                         }
                     } else {
@@ -272,6 +272,7 @@ image_ids! {
         hammerleap: "voxygen.element.icons.skill_hammerleap",
         skill_axe_leap_slash: "voxygen.element.icons.skill_axe_leap_slash",
         skill_bow_jump_burst: "voxygen.element.icons.skill_bow_jump_burst",
+        summon: "voxygen.element.icons.collar",
         missing_icon: "voxygen.element.icons.missing_icon_grey",
 
         // Buttons
@@ -352,10 +353,12 @@ image_ids! {
 
         // Buffs
         buff_plus_0: "voxygen.element.icons.de_buffs.buff_plus_0",
+        buff_focus_0: "voxygen.element.icons.de_buffs.buff_focus_0",
 
         // Debuffs
         debuff_skull_0: "voxygen.element.icons.de_buffs.debuff_skull_0",
         debuff_bleed_0: "voxygen.element.icons.de_buffs.debuff_bleed_0",
+        debuff_burning_0: "voxygen.element.icons.de_buffs.debuff_burning_0",
 
         // Animation Frames
         // Buff Frame
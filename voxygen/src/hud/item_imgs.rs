@@ -4,7 +4,7 @@ use common::{
     comp::item::{
         armor::{Armor, ArmorKind},
         tool::{Tool, ToolKind},
-        Glider, ItemKind, Lantern, Throwable, Utility,
+        Glider, Instrument, ItemKind, Lantern, Throwable, Utility,
     },
     figure::Segment,
 };
@@ -24,9 +24,12 @@ pub enum ItemKey {
     Glider(String),
     Armor(ArmorKind),
     Utility(Utility),
+    Instrument(Instrument),
     Consumable(String),
     Throwable(Throwable),
+    Ammo(String),
     Ingredient(String),
+    Dye(String),
     Empty,
 }
 
@@ -38,9 +41,12 @@ impl From<&ItemKind> for ItemKey {
             ItemKind::Glider(Glider { kind, .. }) => ItemKey::Glider(kind.clone()),
             ItemKind::Armor(Armor { kind, .. }) => ItemKey::Armor(kind.clone()),
             ItemKind::Utility { kind, .. } => ItemKey::Utility(*kind),
+            ItemKind::Instrument { kind } => ItemKey::Instrument(*kind),
             ItemKind::Consumable { kind, .. } => ItemKey::Consumable(kind.clone()),
             ItemKind::Throwable { kind, .. } => ItemKey::Throwable(*kind),
+            ItemKind::Ammo { kind, .. } => ItemKey::Ammo(kind.clone()),
             ItemKind::Ingredient { kind, .. } => ItemKey::Ingredient(kind.clone()),
+            ItemKind::Dye { kind, .. } => ItemKey::Dye(kind.clone()),
         }
     }
 }
@@ -31,6 +31,7 @@ widget_ids! {
         map_title,
         qlog_title,
         zoom_slider,
+        marker,
     }
 }
 
@@ -228,6 +229,27 @@ impl<'a> Widget for Map<'a> {
         {
             events.push(Event::MapZoom(new_val as f64));
         }
+        // Marker, such as the camp of a hunted outlaw
+        if let Some(marker) = self.client.map_marker() {
+            let rel = (marker.map(|e| e as f32) - Vec2::from(player_pos))
+                .map2(TerrainChunkSize::RECT_SIZE, |e, sz| e as f64 / sz as f64)
+                / Vec2::new(w_src, h_src)
+                * 760.0;
+            // Only show the marker while it is within the shown part of the map
+            if rel.map(|e| e.abs() < 380.0).reduce_and() {
+                Image::new(self.imgs.skull)
+                    .x_y_position_relative_to(
+                        state.ids.grid,
+                        position::Relative::Scalar(rel.x),
+                        position::Relative::Scalar(rel.y),
+                    )
+                    .w_h(20.0, 20.0)
+                    .floating(true)
+                    .parent(ui.window)
+                    .set(state.ids.marker, ui);
+            }
+        }
+
         // Cursor pos relative to playerpos and widget size
         // Cursor stops moving on an axis as soon as it's position exceeds the maximum
         // // size of the widget
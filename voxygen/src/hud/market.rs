@@ -0,0 +1,255 @@
+use super::{
+    img_ids::Imgs, CRITICAL_HP_COLOR, HP_COLOR, TEXT_COLOR, TEXT_GRAY_COLOR, UI_HIGHLIGHT_0,
+    UI_MAIN,
+};
+use crate::{i18n::VoxygenLocalization, ui::fonts::ConrodVoxygenFonts};
+use common::{
+    comp::TradeAction,
+    msg::{MarketInfo, PriceTrend},
+};
+use conrod_core::{
+    color,
+    widget::{self, Button, Image, Rectangle, Text},
+    widget_ids, Colorable, Positionable, Sizeable, Widget, WidgetCommon,
+};
+
+widget_ids! {
+    pub struct Ids {
+        frame,
+        close,
+        title,
+        content_align,
+        discount,
+        header_good,
+        header_stock,
+        header_buy,
+        header_sell,
+        goods[],
+        stock[],
+        buy[],
+        sell[],
+        trends[],
+    }
+}
+
+/// Horizontal offsets of the stock and price columns from the middle of the
+/// window
+const STOCK_X: f64 = -30.0;
+const BUY_X: f64 = 40.0;
+const SELL_X: f64 = 110.0;
+const TREND_X: f64 = 170.0;
+
+pub enum Event {
+    Close,
+    /// Buy or sell one of the good at the given index
+    Trade(usize, TradeAction),
+}
+
+/// Prices at the market of the town whose merchant the player talked to, where
+/// goods can be bought and sold by clicking on their prices
+#[derive(WidgetCommon)]
+pub struct Market<'a> {
+    market: &'a MarketInfo,
+    imgs: &'a Imgs,
+    fonts: &'a ConrodVoxygenFonts,
+    localized_strings: &'a std::sync::Arc<VoxygenLocalization>,
+
+    #[conrod(common_builder)]
+    common: widget::CommonBuilder,
+}
+
+impl<'a> Market<'a> {
+    pub fn new(
+        market: &'a MarketInfo,
+        imgs: &'a Imgs,
+        fonts: &'a ConrodVoxygenFonts,
+        localized_strings: &'a std::sync::Arc<VoxygenLocalization>,
+    ) -> Self {
+        Self {
+            market,
+            imgs,
+            fonts,
+            localized_strings,
+            common: widget::CommonBuilder::default(),
+        }
+    }
+}
+
+impl<'a> Widget for Market<'a> {
+    type Event = Option<Event>;
+    type State = Ids;
+    type Style = ();
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State { Ids::new(id_gen) }
+
+    #[allow(clippy::unused_unit)] // TODO: Pending review in #587
+    fn style(&self) -> Self::Style { () }
+
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        let widget::UpdateArgs {
+            id: _, state, ui, ..
+        } = args;
+
+        let mut event = None;
+
+        Image::new(self.imgs.window_3)
+            .top_left_with_margins_on(ui.window, 200.0, 25.0)
+            .w_h(103.0 * 4.0, 122.0 * 4.0)
+            .color(Some(UI_MAIN))
+            .set(state.frame, ui);
+
+        // X-Button
+        if Button::image(self.imgs.close_button)
+            .w_h(28.0, 28.0)
+            .hover_image(self.imgs.close_button_hover)
+            .press_image(self.imgs.close_button_press)
+            .top_right_with_margins_on(state.frame, 0.0, 0.0)
+            .set(state.close, ui)
+            .was_clicked()
+        {
+            event = Some(Event::Close);
+        }
+
+        // Title
+        Text::new(&self.localized_strings.get("hud.market"))
+            .mid_top_with_margin_on(state.frame, 6.0)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(14))
+            .color(TEXT_COLOR)
+            .set(state.title, ui);
+
+        // Content Alignment
+        Rectangle::fill_with([95.0 * 4.0, 108.0 * 4.0], color::TRANSPARENT)
+            .mid_top_with_margin_on(state.frame, 40.0)
+            .set(state.content_align, ui);
+
+        // Discount from the player's reputation in the town
+        let discount = if self.market.discount > 0.0 {
+            self.localized_strings.get("hud.market.discount").replace(
+                "{discount}",
+                &format!("{:.0}", self.market.discount * 100.0),
+            )
+        } else {
+            self.localized_strings
+                .get("hud.market.no_discount")
+                .to_string()
+        };
+        Text::new(&discount)
+            .top_left_with_margins_on(state.content_align, 10.0, 10.0)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(12))
+            .color(TEXT_GRAY_COLOR)
+            .set(state.discount, ui);
+
+        // Column headers
+        Text::new(&self.localized_strings.get("hud.market.good"))
+            .down_from(state.discount, 15.0)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(14))
+            .color(TEXT_COLOR)
+            .set(state.header_good, ui);
+        Text::new(&self.localized_strings.get("hud.market.stock"))
+            .align_middle_y_of(state.header_good)
+            .x_relative_to(state.content_align, STOCK_X)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(14))
+            .color(TEXT_COLOR)
+            .set(state.header_stock, ui);
+        Text::new(&self.localized_strings.get("hud.market.buy"))
+            .align_middle_y_of(state.header_good)
+            .x_relative_to(state.content_align, BUY_X)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(14))
+            .color(TEXT_COLOR)
+            .set(state.header_buy, ui);
+        Text::new(&self.localized_strings.get("hud.market.sell"))
+            .align_middle_y_of(state.header_good)
+            .x_relative_to(state.content_align, SELL_X)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(14))
+            .color(TEXT_COLOR)
+            .set(state.header_sell, ui);
+
+        let goods = &self.market.goods;
+        if state.goods.len() < goods.len() {
+            state.update(|state| {
+                let mut id_gen = ui.widget_id_generator();
+                state.goods.resize(goods.len(), &mut id_gen);
+                state.stock.resize(goods.len(), &mut id_gen);
+                state.buy.resize(goods.len(), &mut id_gen);
+                state.sell.resize(goods.len(), &mut id_gen);
+                state.trends.resize(goods.len(), &mut id_gen);
+            });
+        }
+
+        for (i, good) in goods.iter().enumerate() {
+            let name = Text::new(&good.name)
+                .font_id(self.fonts.cyri.conrod_id)
+                .font_size(self.fonts.cyri.scale(14))
+                .color(TEXT_COLOR);
+            let name = if i == 0 {
+                name.down_from(state.header_good, 10.0)
+            } else {
+                name.down_from(state.goods[i - 1], 8.0)
+            };
+            name.set(state.goods[i], ui);
+
+            Text::new(&good.stock.to_string())
+                .align_middle_y_of(state.goods[i])
+                .x_relative_to(state.content_align, STOCK_X)
+                .font_id(self.fonts.cyri.conrod_id)
+                .font_size(self.fonts.cyri.scale(14))
+                .color(TEXT_GRAY_COLOR)
+                .set(state.stock[i], ui);
+
+            for (price, x, id, action) in [
+                (good.buy, BUY_X, state.buy[i], TradeAction::Buy),
+                (good.sell, SELL_X, state.sell[i], TradeAction::Sell),
+            ]
+            .iter()
+            {
+                if good.tradeable {
+                    if Button::image(self.imgs.button)
+                        .w_h(50.0, 20.0)
+                        .align_middle_y_of(state.goods[i])
+                        .x_relative_to(state.content_align, *x)
+                        .hover_image(self.imgs.button_hover)
+                        .press_image(self.imgs.button_press)
+                        .label(&price.to_string())
+                        .label_color(UI_HIGHLIGHT_0)
+                        .label_font_id(self.fonts.cyri.conrod_id)
+                        .label_font_size(self.fonts.cyri.scale(14))
+                        .set(*id, ui)
+                        .was_clicked()
+                    {
+                        event = Some(Event::Trade(i, *action));
+                    }
+                } else {
+                    Text::new(&price.to_string())
+                        .align_middle_y_of(state.goods[i])
+                        .x_relative_to(state.content_align, *x)
+                        .font_id(self.fonts.cyri.conrod_id)
+                        .font_size(self.fonts.cyri.scale(14))
+                        .color(UI_HIGHLIGHT_0)
+                        .set(*id, ui);
+                }
+            }
+
+            // Rising prices are bad news for buyers, falling ones good news
+            let (trend, color) = match good.trend {
+                PriceTrend::Rising => ("hud.market.rising", CRITICAL_HP_COLOR),
+                PriceTrend::Steady => ("hud.market.steady", TEXT_GRAY_COLOR),
+                PriceTrend::Falling => ("hud.market.falling", HP_COLOR),
+            };
+            Text::new(&self.localized_strings.get(trend))
+                .align_middle_y_of(state.goods[i])
+                .x_relative_to(state.content_align, TREND_X)
+                .font_id(self.fonts.cyri.conrod_id)
+                .font_size(self.fonts.cyri.scale(14))
+                .color(color)
+                .set(state.trends[i], ui);
+        }
+
+        event
+    }
+}
@@ -4,7 +4,7 @@ use super::{
 };
 use crate::ui::{fonts::ConrodVoxygenFonts, img_ids};
 use client::{self, Client};
-use common::{comp, terrain::TerrainChunkSize, vol::RectVolSize};
+use common::{comp, terrain::TerrainChunkSize, time::Date, vol::RectVolSize};
 use conrod_core::{
     color, position,
     widget::{self, Button, Image, Rectangle, Text},
@@ -19,6 +19,7 @@ widget_ids! {
         mmap_frame_2,
         mmap_frame_bg,
         mmap_location,
+        mmap_date,
         mmap_button,
         mmap_plus,
         mmap_minus,
@@ -301,6 +302,15 @@ impl<'a> Widget for MiniMap<'a> {
                 .set(state.ids.mmap_location, ui),
         }
 
+        // Date
+        Text::new(&Date::from_time_of_day(self.client.state().get_time_of_day()).to_string())
+            .down_from(state.ids.mmap_frame, 4.0)
+            .align_middle_x_of(state.ids.mmap_frame)
+            .font_size(self.fonts.cyri.scale(14))
+            .font_id(self.fonts.cyri.conrod_id)
+            .color(TEXT_COLOR)
+            .set(state.ids.mmap_date, ui);
+
         None
     }
 }
@@ -1,6 +1,7 @@
 mod bag;
 mod buffs;
 mod buttons;
+mod character_sheet;
 mod chat;
 mod crafting;
 mod esc_menu;
@@ -9,9 +10,11 @@ mod hotbar;
 mod img_ids;
 mod item_imgs;
 mod map;
+mod market;
 mod minimap;
 mod overhead;
 mod overitem;
+mod perform;
 mod popup;
 mod settings_window;
 mod skillbar;
@@ -27,6 +30,7 @@ pub use settings_window::ScaleChange;
 use bag::Bag;
 use buffs::BuffsBar;
 use buttons::Buttons;
+use character_sheet::CharacterSheet;
 use chat::Chat;
 use chrono::NaiveTime;
 use crafting::Crafting;
@@ -35,7 +39,9 @@ use group::Group;
 use img_ids::Imgs;
 use item_imgs::ItemImgs;
 use map::Map;
+use market::Market;
 use minimap::MiniMap;
+use perform::Perform;
 use popup::Popup;
 use serde::{Deserialize, Serialize};
 use settings_window::{SettingsTab, SettingsWindow};
@@ -67,6 +73,7 @@ use common::{
     span,
     sync::Uid,
     terrain::TerrainChunk,
+    time::Date,
     vol::RectRasterableVol,
 };
 use conrod_core::{
@@ -167,6 +174,7 @@ widget_ids! {
     struct Ids {
         // Crosshair
         crosshair_inner,
+        crosshair_headshot,
         crosshair_outer,
 
         // SCT
@@ -244,6 +252,9 @@ widget_ids! {
         social,
         quest,
         spell,
+        character_sheet,
+        market,
+        perform,
         skillbar,
         buttons,
         buffs,
@@ -278,6 +289,7 @@ pub struct BuffInfo {
     data: comp::BuffData,
     is_buff: bool,
     dur: Option<Duration>,
+    stacks: u32,
 }
 
 pub struct DebugInfo {
@@ -307,6 +319,8 @@ pub struct HudInfo {
 pub enum Event {
     ToggleTips(bool),
     SendMessage(String),
+    /// Saves a bug report with the given description
+    ReportBug(String),
     AdjustMousePan(u32),
     AdjustMouseZoom(u32),
     ToggleZoomInvert(bool),
@@ -327,6 +341,7 @@ pub enum Event {
     AdjustWindowSize([u16; 2]),
     ChangeFullscreenMode(FullScreenSettings),
     ToggleParticlesEnabled(bool),
+    ToggleDecalsEnabled(bool),
     CrosshairTransp(f32),
     ChatTransp(f32),
     ChatCharName(bool),
@@ -347,6 +362,9 @@ pub enum Event {
     UseSlot(comp::slot::Slot),
     SwapSlots(comp::slot::Slot, comp::slot::Slot),
     DropSlot(comp::slot::Slot),
+    DyeArmor(usize, comp::slot::EquipSlot),
+    SetAppearance(usize, comp::slot::EquipSlot),
+    ClearAppearance(comp::slot::EquipSlot),
     ChangeHotbarState(Box<HotbarState>),
     Ability3(bool),
     Logout,
@@ -358,7 +376,21 @@ pub enum Event {
     ChangeRenderMode(Box<RenderMode>),
     ChangeAutoWalkBehavior(PressBehavior),
     ChangeStopAutoWalkOnInput(bool),
+    AdjustCameraShake(f32),
+    AdjustHitstop(f32),
+    AdjustShoulderOffset(f32),
     CraftRecipe(String),
+    /// Buys or sells one of a good at the market of a merchant
+    Trade {
+        merchant: common::sync::Uid,
+        good: usize,
+        action: comp::TradeAction,
+    },
+    SelectAbility {
+        tool: comp::item::ToolCategory,
+        slot: usize,
+        index: usize,
+    },
     InviteMember(common::sync::Uid),
     AcceptInvite,
     DeclineInvite,
@@ -366,6 +398,10 @@ pub enum Event {
     LeaveGroup,
     AssignLeader(common::sync::Uid),
     RemoveBuff(BuffKind),
+    /// Plays a note of the instrument being performed with
+    PlayNote(u8),
+    /// Plays one of the preset songs on the instrument being performed with
+    PlaySong(usize),
 }
 
 // TODO: Are these the possible layouts we want?
@@ -428,6 +464,8 @@ pub struct Show {
     bag: bool,
     social: bool,
     spell: bool,
+    character_sheet: bool,
+    market: bool,
     group: bool,
     group_menu: bool,
     esc_menu: bool,
@@ -460,6 +498,8 @@ impl Show {
             self.crafting = false;
             self.social = false;
             self.spell = false;
+            self.character_sheet = false;
+            self.market = false;
             self.want_grab = !open;
         }
     }
@@ -468,6 +508,8 @@ impl Show {
         if !self.esc_menu {
             self.social = open;
             self.spell = false;
+            self.character_sheet = false;
+            self.market = false;
             self.want_grab = !open;
         }
     }
@@ -485,11 +527,35 @@ impl Show {
         if !self.esc_menu {
             self.social = false;
             self.crafting = false;
+            self.character_sheet = false;
+            self.market = false;
             self.spell = open;
             self.want_grab = !open;
         }
     }
 
+    fn character_sheet(&mut self, open: bool) {
+        if !self.esc_menu {
+            self.social = false;
+            self.crafting = false;
+            self.spell = false;
+            self.market = false;
+            self.character_sheet = open;
+            self.want_grab = !open;
+        }
+    }
+
+    fn market(&mut self, open: bool) {
+        if !self.esc_menu {
+            self.social = false;
+            self.crafting = false;
+            self.spell = false;
+            self.character_sheet = false;
+            self.market = open;
+            self.want_grab = !open;
+        }
+    }
+
     fn toggle_map(&mut self) { self.map(!self.map) }
 
     fn toggle_mini_map(&mut self) { self.mini_map = !self.mini_map; }
@@ -505,6 +571,8 @@ impl Show {
             self.social = false;
             self.crafting = false;
             self.spell = false;
+            self.character_sheet = false;
+            self.market = false;
             self.want_grab = !open;
         }
     }
@@ -537,6 +605,8 @@ impl Show {
             || self.social
             || self.crafting
             || self.spell
+            || self.character_sheet
+            || self.market
             || self.help
             || self.intro
             || !matches!(self.open_windows, Windows::None)
@@ -548,6 +618,8 @@ impl Show {
             self.map = false;
             self.social = false;
             self.spell = false;
+            self.character_sheet = false;
+            self.market = false;
             self.crafting = false;
             self.open_windows = Windows::None;
             self.want_grab = true;
@@ -588,7 +660,10 @@ impl Show {
     fn toggle_spell(&mut self) {
         self.spell = !self.spell;
         self.social = false;
+        self.character_sheet = false;
     }
+
+    fn toggle_character_sheet(&mut self) { self.character_sheet(!self.character_sheet) }
 }
 
 pub struct Hud {
@@ -601,6 +676,8 @@ pub struct Hud {
     rot_imgs: ImgsRot,
     new_messages: VecDeque<comp::ChatMsg>,
     new_notifications: VecDeque<common::msg::Notification>,
+    /// Prices at the market of the last merchant the player talked to
+    market: Option<common::msg::MarketInfo>,
     speech_bubbles: HashMap<Uid, comp::SpeechBubble>,
     show: Show,
     //never_show: bool,
@@ -618,6 +695,9 @@ pub struct Hud {
     hotbar: hotbar::State,
     events: Vec<Event>,
     crosshair_opacity: f32,
+    /// Whether the player is performing with an instrument, making the hotbar
+    /// keys play notes
+    performing: bool,
 }
 
 impl Hud {
@@ -675,6 +755,7 @@ impl Hud {
             ids,
             new_messages: VecDeque::new(),
             new_notifications: VecDeque::new(),
+            market: None,
             speech_bubbles: HashMap::new(),
             //intro: false,
             //intro_2: false,
@@ -690,6 +771,8 @@ impl Hud {
                 ui: true,
                 social: false,
                 spell: false,
+                character_sheet: false,
+                market: false,
                 group: false,
                 group_menu: false,
                 mini_map: true,
@@ -714,6 +797,7 @@ impl Hud {
             hotbar: hotbar_state,
             events: Vec::new(),
             crosshair_opacity: 0.0,
+            performing: false,
         }
     }
 
@@ -825,11 +909,33 @@ impl Hud {
                         .middle_of(self.ids.crosshair_outer)
                         .color(Some(Color::Rgba(1.0, 1.0, 1.0, 0.6)))
                         .set(self.ids.crosshair_inner, ui_widgets);
+
+                    // Flash a hit marker around the crosshair after landing a headshot
+                    const HEADSHOT_MARKER_TIME: f32 = 0.3;
+                    let headshot_timer = (&entities, &hp_floater_lists)
+                        .join()
+                        .filter(|(entity, _)| *entity != me)
+                        .flat_map(|(_, hpfl)| hpfl.floaters.iter())
+                        .filter(|floater| floater.headshot)
+                        .map(|floater| floater.timer)
+                        .fold(None, |min: Option<f32>, timer| {
+                            Some(min.map_or(timer, |min| min.min(timer)))
+                        });
+                    if let Some(timer) = headshot_timer.filter(|t| *t < HEADSHOT_MARKER_TIME) {
+                        let fade = 1.0 - timer / HEADSHOT_MARKER_TIME;
+                        Image::new(self.imgs.crosshair_outer_edges)
+                            .w_h(21.0 * (2.5 - fade as f64), 21.0 * (2.5 - fade as f64))
+                            .middle_of(self.ids.crosshair_outer)
+                            .color(Some(Color::Rgba(1.0, 0.2, 0.6, fade)))
+                            .set(self.ids.crosshair_headshot, ui_widgets);
+                    }
                 }
             }
 
             // Max amount the sct font size increases when "flashing"
             const FLASH_MAX: u32 = 2;
+            // Amount the sct font size increases for critical hits
+            const CRIT_FONT_BONUS: u32 = 8;
 
             // Get player position.
             let player_pos = client
@@ -1255,6 +1361,8 @@ impl Hud {
                     const MED_OR: Rgb<f32> = Rgb::new(1.0, 0.776, 0.247);
                     const DARK_ORANGE: Rgb<f32> = Rgb::new(1.0, 0.7, 0.0);
                     const RED_ORANGE: Rgb<f32> = Rgb::new(1.0, 0.349, 0.0);
+                    const CRIT_COLOR: Rgb<f32> = Rgb::new(1.0, 0.85, 0.0);
+                    const HEADSHOT_COLOR: Rgb<f32> = Rgb::new(1.0, 0.2, 0.6);
                     const DAMAGE_COLORS: [Rgb<f32>; 6] = [
                         WHITE,
                         LIGHT_OR,
@@ -1367,8 +1475,17 @@ impl Hud {
                                     FLASH_MAX * (((1.0 - floater.timer / 0.1) * 10.0) as u32)
                                 } else {
                                     0
-                                };
-                            let font_col = font_col(font_size);
+                                }
+                                + if floater.crit { CRIT_FONT_BONUS } else { 0 };
+                            // Critical hits and headshots stand out with their own color
+                            // and a mark
+                            let (font_col, crit_mark) = if floater.headshot {
+                                (HEADSHOT_COLOR, if floater.crit { "!!" } else { "!" })
+                            } else if floater.crit {
+                                (CRIT_COLOR, "!")
+                            } else {
+                                (font_col(font_size), "")
+                            };
                             // Timer sets the widget offset
                             let y = (floater.timer as f64
                                 / crate::ecs::sys::floater::HP_SHOWTIME as f64
@@ -1380,7 +1497,12 @@ impl Hud {
                                 + 0.2;
                             if floater.hp_change.abs() < 10 {
                                 // Damage and heal below 10/10 are shown as decimals
-                                Text::new(&format!("{}", (floater.hp_change.abs() as f32 / 10.0)))
+                                let text = format!(
+                                    "{}{}",
+                                    floater.hp_change.abs() as f32 / 10.0,
+                                    crit_mark
+                                );
+                                Text::new(&text)
                                     .font_size(font_size)
                                     .font_id(self.fonts.cyri.conrod_id)
                                     .color(if floater.hp_change < 0 {
@@ -1391,7 +1513,7 @@ impl Hud {
                                     .x_y(0.0, y - 3.0)
                                     .position_ingame(ingame_pos)
                                     .set(sct_bg_id, ui_widgets);
-                                Text::new(&format!("{}", (floater.hp_change.abs() as f32 / 10.0)))
+                                Text::new(&text)
                                     .font_size(font_size)
                                     .font_id(self.fonts.cyri.conrod_id)
                                     .x_y(0.0, y)
@@ -1404,7 +1526,9 @@ impl Hud {
                                     .set(sct_id, ui_widgets);
                             } else {
                                 // Damage and heal above 10/10 are shown rounded
-                                Text::new(&format!("{}", (floater.hp_change / 10).abs()))
+                                let text =
+                                    format!("{}{}", (floater.hp_change / 10).abs(), crit_mark);
+                                Text::new(&text)
                                     .font_size(font_size)
                                     .font_id(self.fonts.cyri.conrod_id)
                                     .color(if floater.hp_change < 0 {
@@ -1415,7 +1539,7 @@ impl Hud {
                                     .x_y(0.0, y - 3.0)
                                     .position_ingame(ingame_pos)
                                     .set(sct_bg_id, ui_widgets);
-                                Text::new(&format!("{}", (floater.hp_change / 10).abs()))
+                                Text::new(&text)
                                     .font_size(font_size)
                                     .font_id(self.fonts.cyri.conrod_id)
                                     .x_y(0.0, y)
@@ -1592,8 +1716,9 @@ impl Hud {
                 0,
             );
             Text::new(&format!(
-                "Time: {}",
-                current_time.format("%H:%M").to_string()
+                "Time: {} ({})",
+                current_time.format("%H:%M").to_string(),
+                Date::from_time_of_day(time_in_seconds),
             ))
             .color(TEXT_COLOR)
             .down_from(self.ids.loaded_distance, 5.0)
@@ -1900,7 +2025,7 @@ impl Hud {
             Some(stats),
             Some(loadout),
             Some(energy),
-            Some(_character_state),
+            Some(character_state),
             Some(_controller),
             Some(inventory),
         ) = (
@@ -1920,7 +2045,7 @@ impl Hud {
                 &stats,
                 &loadout,
                 &energy,
-                //&character_state,
+                &character_state,
                 self.pulse,
                 //&controller,
                 &inventory,
@@ -1931,6 +2056,19 @@ impl Hud {
                 &self.show,
             )
             .set(self.ids.skillbar, ui_widgets);
+
+            // Songs to play while performing with an instrument
+            self.performing = if let comp::CharacterState::Perform(perform) = character_state {
+                if let Some(song) =
+                    Perform::new(perform.song, &self.imgs, &self.fonts, &self.voxygen_i18n)
+                        .set(self.ids.perform, ui_widgets)
+                {
+                    events.push(Event::PlaySong(song));
+                }
+                true
+            } else {
+                false
+            };
         }
 
         // Crafting
@@ -2137,6 +2275,9 @@ impl Hud {
                     settings_window::Event::ToggleParticlesEnabled(particles_enabled) => {
                         events.push(Event::ToggleParticlesEnabled(particles_enabled));
                     },
+                    settings_window::Event::ToggleDecalsEnabled(decals_enabled) => {
+                        events.push(Event::ToggleDecalsEnabled(decals_enabled));
+                    },
                     settings_window::Event::AdjustWindowSize(new_size) => {
                         events.push(Event::AdjustWindowSize(new_size));
                     },
@@ -2155,6 +2296,15 @@ impl Hud {
                     settings_window::Event::ChangeStopAutoWalkOnInput(state) => {
                         events.push(Event::ChangeStopAutoWalkOnInput(state));
                     },
+                    settings_window::Event::AdjustCameraShake(camera_shake) => {
+                        events.push(Event::AdjustCameraShake(camera_shake));
+                    },
+                    settings_window::Event::AdjustHitstop(hitstop) => {
+                        events.push(Event::AdjustHitstop(hitstop));
+                    },
+                    settings_window::Event::AdjustShoulderOffset(offset) => {
+                        events.push(Event::AdjustShoulderOffset(offset));
+                    },
                 }
             }
         }
@@ -2198,7 +2348,7 @@ impl Hud {
 
         // Spellbook
         if self.show.spell {
-            match Spell::new(
+            for event in Spell::new(
                 &self.show,
                 client,
                 &self.imgs,
@@ -2207,11 +2357,50 @@ impl Hud {
             )
             .set(self.ids.spell, ui_widgets)
             {
-                Some(spell::Event::Close) => {
-                    self.show.spell(false);
+                match event {
+                    spell::Event::Close => {
+                        self.show.spell(false);
+                        self.show.want_grab = true;
+                        self.force_ungrab = false;
+                    },
+                    spell::Event::SelectAbility { tool, slot, index } => {
+                        events.push(Event::SelectAbility { tool, slot, index });
+                    },
+                }
+            }
+        }
+        // Character Sheet
+        if self.show.character_sheet {
+            if let Some(character_sheet::Event::Close) = CharacterSheet::new(
+                client,
+                &self.imgs,
+                &self.rot_imgs,
+                &self.fonts,
+                tooltip_manager,
+                &self.voxygen_i18n,
+            )
+            .set(self.ids.character_sheet, ui_widgets)
+            {
+                self.show.character_sheet(false);
+                self.show.want_grab = true;
+                self.force_ungrab = false;
+            }
+        }
+        // Market
+        if let (true, Some(market)) = (self.show.market, &self.market) {
+            match Market::new(market, &self.imgs, &self.fonts, &self.voxygen_i18n)
+                .set(self.ids.market, ui_widgets)
+            {
+                Some(market::Event::Close) => {
+                    self.show.market(false);
                     self.show.want_grab = true;
                     self.force_ungrab = false;
                 },
+                Some(market::Event::Trade(good, action)) => events.push(Event::Trade {
+                    merchant: market.merchant,
+                    good,
+                    action,
+                }),
                 None => {},
             }
         }
@@ -2267,6 +2456,7 @@ impl Hud {
                     events.push(Event::Logout);
                 },
                 Some(esc_menu::Event::Quit) => events.push(Event::Quit),
+                Some(esc_menu::Event::ReportBug) => events.push(Event::ReportBug(String::new())),
                 Some(esc_menu::Event::CharacterSelection) => {
                     // Unpause the game if we are on singleplayer so that we can logout
                     #[cfg(feature = "singleplayer")]
@@ -2327,6 +2517,8 @@ impl Hud {
         }
 
         // Maintain slot manager
+        // Holding shift changes the look of worn armor instead of swapping it
+        let shift = global_state.window.modifiers().shift();
         for event in self.slot_manager.maintain(ui_widgets) {
             use comp::slot::Slot;
             use slots::SlotKind::*;
@@ -2335,10 +2527,27 @@ impl Hud {
                 Equip(e) => Some(Slot::Equip(e)),
                 Hotbar(_) => None,
             };
+            let is_dye = |slot_kind| match slot_kind {
+                Inventory(i) => client
+                    .inventories()
+                    .get(client.entity())
+                    .and_then(|inv| inv.get(i.0))
+                    .map_or(
+                        false,
+                        |item| matches!(item.kind(), comp::item::ItemKind::Dye { .. }),
+                    ),
+                _ => false,
+            };
             match event {
                 slot::Event::Dragged(a, b) => {
-                    // Swap between slots
-                    if let (Some(a), Some(b)) = (to_slot(a), to_slot(b)) {
+                    if let (Inventory(i), Equip(e), true) = (a, b, is_dye(a)) {
+                        // Dye worn armor
+                        events.push(Event::DyeArmor(i.0, e));
+                    } else if let (Inventory(i), Equip(e), true) = (a, b, shift) {
+                        // Wear the look of the dragged armor
+                        events.push(Event::SetAppearance(i.0, e));
+                    } else if let (Some(a), Some(b)) = (to_slot(a), to_slot(b)) {
+                        // Swap between slots
                         events.push(Event::SwapSlots(a, b));
                     } else if let (Inventory(i), Hotbar(h)) = (a, b) {
                         self.hotbar.add_inventory_link(h, i.0);
@@ -2359,7 +2568,9 @@ impl Hud {
                 },
                 slot::Event::Used(from) => {
                     // Item used (selected and then clicked again)
-                    if let Some(from) = to_slot(from) {
+                    if let (Equip(e), true) = (from, shift) {
+                        events.push(Event::ClearAppearance(e));
+                    } else if let Some(from) = to_slot(from) {
                         events.push(Event::UseSlot(from));
                     } else if let Hotbar(h) = from {
                         self.hotbar.get(h).map(|s| {
@@ -2386,6 +2597,12 @@ impl Hud {
         self.new_notifications.push_back(msg);
     }
 
+    /// Opens the market window with the prices a merchant showed the player
+    pub fn show_market(&mut self, market: common::msg::MarketInfo) {
+        self.market = Some(market);
+        self.show.market(true);
+    }
+
     pub fn scale_change(&mut self, scale_change: ScaleChange) -> ScaleMode {
         let scale_mode = match scale_change {
             ScaleChange::Adjust(scale) => ScaleMode::Absolute(scale),
@@ -2475,6 +2692,15 @@ impl Hud {
                 true
             },
 
+            // Hotbar keys play notes while performing with an instrument
+            WinEvent::InputUpdate(key, state)
+                if self.performing && !self.typing() && perform::note(key).is_some() =>
+            {
+                if state {
+                    self.events.extend(perform::note(key).map(Event::PlayNote));
+                }
+                true
+            },
             // Press key while not typing
             WinEvent::InputUpdate(key, state) if !self.typing() => match key {
                 GameInput::Command if state => {
@@ -2503,6 +2729,10 @@ impl Hud {
                     self.show.toggle_spell();
                     true
                 },
+                GameInput::CharacterSheet if state => {
+                    self.show.toggle_character_sheet();
+                    true
+                },
                 GameInput::Settings if state => {
                     self.show.toggle_settings(global_state);
                     true
@@ -2727,11 +2957,12 @@ pub fn get_quality_col<I: ItemDesc>(item: &I) -> Color {
     }
 }
 // Get info about applied buffs
-fn get_buff_info(buff: &comp::Buff) -> BuffInfo {
+fn get_buff_info((buff, stacks): (&comp::Buff, u32)) -> BuffInfo {
     BuffInfo {
         kind: buff.kind,
         data: buff.data,
         is_buff: buff.kind.is_buff(),
         dur: buff.time,
+        stacks,
     }
 }
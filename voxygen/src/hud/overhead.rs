@@ -229,7 +229,7 @@ impl<'a> Widget for Overhead<'a> {
                     .iter()
                     .copied()
                     .zip(state.ids.buff_timers.iter().copied())
-                    .zip(buffs.iter_active().map(get_buff_info))
+                    .zip(buffs.iter_kinds().map(get_buff_info))
                     .enumerate()
                     .for_each(|(i, ((id, timer_id), buff))| {
                         // Limit displayed buffs
@@ -242,7 +242,14 @@ impl<'a> Widget for Overhead<'a> {
                         }) as u32; // Percentage to determine which frame of the timer overlay is displayed
                         let buff_img = match buff.kind {
                             BuffKind::Regeneration { .. } => self.imgs.buff_plus_0,
+                            BuffKind::Focused { .. } => self.imgs.buff_focus_0,
+                            BuffKind::Taunting { .. } => self.imgs.protection_ico,
+                            BuffKind::Enraged { .. } => self.imgs.fire_spell_1,
                             BuffKind::Bleeding { .. } => self.imgs.debuff_bleed_0,
+                            BuffKind::Burning { .. } => self.imgs.debuff_burning_0,
+                            BuffKind::Frozen { .. } => self.imgs.debuff_skull_0,
+                            BuffKind::Poisoned { .. } => self.imgs.debuff_skull_0,
+                            BuffKind::Exhausted { .. } => self.imgs.debuff_skull_0,
                             BuffKind::Cursed { .. } => self.imgs.debuff_skull_0,
                         };
                         let buff_widget = Image::new(buff_img).w_h(20.0, 20.0);
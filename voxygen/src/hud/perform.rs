@@ -0,0 +1,137 @@
+use super::{img_ids::Imgs, TEXT_COLOR, TEXT_GRAY_COLOR, UI_HIGHLIGHT_0, UI_MAIN};
+use crate::{i18n::VoxygenLocalization, ui::fonts::ConrodVoxygenFonts, window::GameInput};
+use common::states::perform::SONGS;
+use conrod_core::{
+    widget::{self, Button, Image, Text},
+    widget_ids, Colorable, Positionable, Sizeable, Widget, WidgetCommon,
+};
+
+widget_ids! {
+    pub struct Ids {
+        frame,
+        title,
+        keys,
+        songs[],
+    }
+}
+
+/// The note played by a hotbar key while performing, from the lowest note on
+/// the first slot to the highest on the eighth
+pub fn note(input: GameInput) -> Option<u8> {
+    match input {
+        GameInput::Slot1 => Some(0),
+        GameInput::Slot2 => Some(1),
+        GameInput::Slot3 => Some(2),
+        GameInput::Slot4 => Some(3),
+        GameInput::Slot5 => Some(4),
+        GameInput::Slot6 => Some(5),
+        GameInput::Slot7 => Some(6),
+        GameInput::Slot8 => Some(7),
+        _ => None,
+    }
+}
+
+/// Preset songs the player can play while performing with an instrument,
+/// started by clicking on them
+#[derive(WidgetCommon)]
+pub struct Perform<'a> {
+    /// The song being played, if any
+    playing: Option<usize>,
+    imgs: &'a Imgs,
+    fonts: &'a ConrodVoxygenFonts,
+    localized_strings: &'a std::sync::Arc<VoxygenLocalization>,
+
+    #[conrod(common_builder)]
+    common: widget::CommonBuilder,
+}
+
+impl<'a> Perform<'a> {
+    pub fn new(
+        playing: Option<usize>,
+        imgs: &'a Imgs,
+        fonts: &'a ConrodVoxygenFonts,
+        localized_strings: &'a std::sync::Arc<VoxygenLocalization>,
+    ) -> Self {
+        Self {
+            playing,
+            imgs,
+            fonts,
+            localized_strings,
+            common: widget::CommonBuilder::default(),
+        }
+    }
+}
+
+impl<'a> Widget for Perform<'a> {
+    /// The song that was clicked on
+    type Event = Option<usize>;
+    type State = Ids;
+    type Style = ();
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State { Ids::new(id_gen) }
+
+    #[allow(clippy::unused_unit)] // TODO: Pending review in #587
+    fn style(&self) -> Self::Style { () }
+
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        let widget::UpdateArgs {
+            id: _, state, ui, ..
+        } = args;
+
+        let mut event = None;
+
+        Image::new(self.imgs.window_3)
+            .bottom_right_with_margins_on(ui.window, 250.0, 25.0)
+            .w_h(103.0 * 2.0, 122.0 * 1.5)
+            .color(Some(UI_MAIN))
+            .set(state.frame, ui);
+
+        // Title
+        Text::new(&self.localized_strings.get("hud.perform"))
+            .mid_top_with_margin_on(state.frame, 6.0)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(14))
+            .color(TEXT_COLOR)
+            .set(state.title, ui);
+
+        Text::new(&self.localized_strings.get("hud.perform.keys"))
+            .mid_top_with_margin_on(state.frame, 34.0)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(12))
+            .color(TEXT_GRAY_COLOR)
+            .set(state.keys, ui);
+
+        if state.songs.len() < SONGS.len() {
+            state.update(|state| {
+                state
+                    .songs
+                    .resize(SONGS.len(), &mut ui.widget_id_generator())
+            });
+        }
+
+        for (i, song) in SONGS.iter().enumerate() {
+            let button = Button::image(self.imgs.button)
+                .w_h(160.0, 24.0)
+                .hover_image(self.imgs.button_hover)
+                .press_image(self.imgs.button_press)
+                .label(&self.localized_strings.get(song.name))
+                .label_color(if self.playing == Some(i) {
+                    UI_HIGHLIGHT_0
+                } else {
+                    TEXT_COLOR
+                })
+                .label_font_id(self.fonts.cyri.conrod_id)
+                .label_font_size(self.fonts.cyri.scale(12));
+            let button = if i == 0 {
+                button.down_from(state.keys, 12.0)
+            } else {
+                button.down_from(state.songs[i - 1], 6.0)
+            };
+            if button.set(state.songs[i], ui).was_clicked() {
+                event = Some(i);
+            }
+        }
+
+        event
+    }
+}
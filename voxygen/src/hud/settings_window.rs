@@ -142,6 +142,8 @@ widget_ids! {
         //
         particles_button,
         particles_label,
+        decals_button,
+        decals_label,
         //
         fullscreen_button,
         fullscreen_label,
@@ -210,6 +212,15 @@ widget_ids! {
         auto_walk_behavior_list,
         stop_auto_walk_on_input_button,
         stop_auto_walk_on_input_label,
+        camera_shake_text,
+        camera_shake_slider,
+        camera_shake_value,
+        hitstop_text,
+        hitstop_slider,
+        hitstop_value,
+        shoulder_offset_text,
+        shoulder_offset_slider,
+        shoulder_offset_value,
     }
 }
 
@@ -283,6 +294,7 @@ pub enum Event {
     AdjustWindowSize([u16; 2]),
     ChangeFullscreenMode(FullScreenSettings),
     ToggleParticlesEnabled(bool),
+    ToggleDecalsEnabled(bool),
     ChangeRenderMode(Box<RenderMode>),
     AdjustMusicVolume(f32),
     AdjustSfxVolume(f32),
@@ -304,6 +316,9 @@ pub enum Event {
     ChangeFreeLookBehavior(PressBehavior),
     ChangeAutoWalkBehavior(PressBehavior),
     ChangeStopAutoWalkOnInput(bool),
+    AdjustCameraShake(f32),
+    AdjustHitstop(f32),
+    AdjustShoulderOffset(f32),
 }
 
 pub enum ScaleChange {
@@ -1549,6 +1564,106 @@ impl<'a> Widget for SettingsWindow<'a> {
             .graphics_for(state.ids.stop_auto_walk_on_input_button)
             .color(TEXT_COLOR)
             .set(state.ids.stop_auto_walk_on_input_label, ui);
+
+            // Camera shake
+            let camera_shake = self.global_state.settings.gameplay.camera_shake;
+            Text::new(&self.localized_strings.get("hud.settings.camera_shake"))
+                .down_from(state.ids.free_look_behavior_list, 10.0)
+                .font_size(self.fonts.cyri.scale(14))
+                .font_id(self.fonts.cyri.conrod_id)
+                .color(TEXT_COLOR)
+                .set(state.ids.camera_shake_text, ui);
+
+            if let Some(new_val) = ImageSlider::continuous(
+                camera_shake,
+                0.0,
+                1.0,
+                self.imgs.slider_indicator,
+                self.imgs.slider,
+            )
+            .w_h(208.0, 22.0)
+            .down_from(state.ids.camera_shake_text, 8.0)
+            .track_breadth(12.0)
+            .slider_length(10.0)
+            .pad_track((5.0, 5.0))
+            .set(state.ids.camera_shake_slider, ui)
+            {
+                events.push(Event::AdjustCameraShake(new_val));
+            }
+
+            Text::new(&format!("{:.0}%", camera_shake * 100.0))
+                .right_from(state.ids.camera_shake_slider, 8.0)
+                .font_size(self.fonts.cyri.scale(14))
+                .font_id(self.fonts.cyri.conrod_id)
+                .color(TEXT_COLOR)
+                .set(state.ids.camera_shake_value, ui);
+
+            // Hitstop
+            let hitstop = self.global_state.settings.gameplay.hitstop;
+            Text::new(&self.localized_strings.get("hud.settings.hitstop"))
+                .down_from(state.ids.free_look_behavior_list, 10.0)
+                .right_from(state.ids.camera_shake_text, 250.0)
+                .font_size(self.fonts.cyri.scale(14))
+                .font_id(self.fonts.cyri.conrod_id)
+                .color(TEXT_COLOR)
+                .set(state.ids.hitstop_text, ui);
+
+            if let Some(new_val) = ImageSlider::continuous(
+                hitstop,
+                0.0,
+                1.0,
+                self.imgs.slider_indicator,
+                self.imgs.slider,
+            )
+            .w_h(208.0, 22.0)
+            .down_from(state.ids.hitstop_text, 8.0)
+            .track_breadth(12.0)
+            .slider_length(10.0)
+            .pad_track((5.0, 5.0))
+            .set(state.ids.hitstop_slider, ui)
+            {
+                events.push(Event::AdjustHitstop(new_val));
+            }
+
+            Text::new(&format!("{:.0}%", hitstop * 100.0))
+                .right_from(state.ids.hitstop_slider, 8.0)
+                .font_size(self.fonts.cyri.scale(14))
+                .font_id(self.fonts.cyri.conrod_id)
+                .color(TEXT_COLOR)
+                .set(state.ids.hitstop_value, ui);
+
+            // Shoulder offset
+            let shoulder_offset = self.global_state.settings.gameplay.camera_shoulder_offset;
+            Text::new(&self.localized_strings.get("hud.settings.shoulder_offset"))
+                .down_from(state.ids.camera_shake_slider, 10.0)
+                .font_size(self.fonts.cyri.scale(14))
+                .font_id(self.fonts.cyri.conrod_id)
+                .color(TEXT_COLOR)
+                .set(state.ids.shoulder_offset_text, ui);
+
+            if let Some(new_val) = ImageSlider::continuous(
+                shoulder_offset,
+                -2.0,
+                2.0,
+                self.imgs.slider_indicator,
+                self.imgs.slider,
+            )
+            .w_h(208.0, 22.0)
+            .down_from(state.ids.shoulder_offset_text, 8.0)
+            .track_breadth(12.0)
+            .slider_length(10.0)
+            .pad_track((5.0, 5.0))
+            .set(state.ids.shoulder_offset_slider, ui)
+            {
+                events.push(Event::AdjustShoulderOffset(new_val));
+            }
+
+            Text::new(&format!("{:.1}", shoulder_offset))
+                .right_from(state.ids.shoulder_offset_slider, 8.0)
+                .font_size(self.fonts.cyri.scale(14))
+                .font_id(self.fonts.cyri.conrod_id)
+                .color(TEXT_COLOR)
+                .set(state.ids.shoulder_offset_value, ui);
         }
 
         // 3) Controls Tab --------------------------------
@@ -2312,6 +2427,29 @@ impl<'a> Widget for SettingsWindow<'a> {
                 events.push(Event::ToggleParticlesEnabled(particles_enabled));
             }
 
+            // Decals
+            Text::new(&self.localized_strings.get("hud.settings.decals"))
+                .font_size(self.fonts.cyri.scale(14))
+                .font_id(self.fonts.cyri.conrod_id)
+                .right_from(state.ids.particles_button, 20.0)
+                .color(TEXT_COLOR)
+                .set(state.ids.decals_label, ui);
+
+            let decals_enabled = ToggleButton::new(
+                self.global_state.settings.graphics.decals_enabled,
+                self.imgs.checkbox,
+                self.imgs.checkbox_checked,
+            )
+            .w_h(18.0, 18.0)
+            .right_from(state.ids.decals_label, 10.0)
+            .hover_images(self.imgs.checkbox_mo, self.imgs.checkbox_checked_mo)
+            .press_images(self.imgs.checkbox_press, self.imgs.checkbox_checked)
+            .set(state.ids.decals_button, ui);
+
+            if self.global_state.settings.graphics.decals_enabled != decals_enabled {
+                events.push(Event::ToggleDecalsEnabled(decals_enabled));
+            }
+
             // Resolution, Bit Depth and Refresh Rate
             let video_modes: Vec<VideoMode> = self
                 .global_state
@@ -20,7 +20,7 @@ use common::comp::{
         tool::{Tool, ToolKind},
         Hands, ItemKind,
     },
-    Energy, Inventory, Loadout, Stats,
+    CharacterState, Energy, Inventory, Loadout, Stats,
 };
 use conrod_core::{
     color,
@@ -64,6 +64,9 @@ widget_ids! {
         hp_txt_alignment,
         hp_txt_bg,
         hp_txt,
+        // Cast-Bar
+        cast_bar_bg,
+        cast_bar_filling,
         // Stamina-Bar
         stamina_alignment,
         stamina_filling,
@@ -77,6 +80,8 @@ widget_ids! {
         m1_text_bg,
         m1_slot_act,
         m1_content,
+        ammo_count,
+        ammo_count_bg,
         m2_slot,
         m2_slot_bg,
         m2_text,
@@ -126,7 +131,7 @@ pub struct Skillbar<'a> {
     stats: &'a Stats,
     loadout: &'a Loadout,
     energy: &'a Energy,
-    // character_state: &'a CharacterState,
+    character_state: &'a CharacterState,
     // controller: &'a ControllerInputs,
     inventory: &'a Inventory,
     hotbar: &'a hotbar::State,
@@ -150,7 +155,7 @@ impl<'a> Skillbar<'a> {
         stats: &'a Stats,
         loadout: &'a Loadout,
         energy: &'a Energy,
-        // character_state: &'a CharacterState,
+        character_state: &'a CharacterState,
         pulse: f32,
         // controller: &'a ControllerInputs,
         inventory: &'a Inventory,
@@ -170,7 +175,7 @@ impl<'a> Skillbar<'a> {
             loadout,
             energy,
             common: widget::CommonBuilder::default(),
-            // character_state,
+            character_state,
             pulse,
             // controller,
             inventory,
@@ -365,6 +370,31 @@ impl<'a> Widget for Skillbar<'a> {
             .color(Some(XP_COLOR))
             .bottom_left_with_margins_on(state.ids.exp_alignment, 0.0, 0.0)
             .set(state.ids.exp_filling, ui);
+        // Cast bar, also showing the progress of a revive, the time left until
+        // bleeding out while downed and the time left to reel in a biting fish
+        let cast_bar = match self.character_state {
+            CharacterState::CastSpell(data) => data
+                .cast_progress()
+                .map(|progress| (progress, STAMINA_COLOR)),
+            CharacterState::Revive(data) => Some((data.progress(), HP_COLOR)),
+            CharacterState::Downed(data) => Some((1.0 - data.bleed_out_progress(), LOW_HP_COLOR)),
+            CharacterState::Fishing(data) => {
+                data.bite_progress().map(|progress| (progress, XP_COLOR))
+            },
+            _ => None,
+        };
+        if let Some((progress, bar_color)) = cast_bar {
+            Image::new(self.imgs.bar_content)
+                .w_h(240.0, 14.0)
+                .color(Some(BLACK))
+                .mid_top_with_margin_on(state.ids.alignment, -24.0)
+                .set(state.ids.cast_bar_bg, ui);
+            Image::new(self.imgs.bar_content)
+                .w_h(236.0 * f64::from(progress), 10.0)
+                .color(Some(bar_color))
+                .top_left_with_margins_on(state.ids.cast_bar_bg, 2.0, 2.0)
+                .set(state.ids.cast_bar_filling, ui);
+        }
         // Health and Stamina bar
         // Alignment
         Rectangle::fill_with([240.0, 17.0], color::TRANSPARENT)
@@ -533,6 +563,11 @@ impl<'a> Widget for Skillbar<'a> {
                                     "Whirlwind",
                                     "\nMove forward while spinning with \n your sword.",
                                 )),
+                                ToolKind::Shield(_) => Some((
+                                    "Taunt",
+                                    "\nMakes enemies you fight \nmore likely to attack you, \nand \
+                                     keeps you from being \nknocked back.",
+                                )),
                                 ToolKind::Debug(kind) => match kind.as_ref() {
                                     "Boost" => Some((
                                         "Possessing Arrow",
@@ -633,6 +668,31 @@ impl<'a> Widget for Skillbar<'a> {
         .w_h(36.0, 36.0)
         .middle_of(state.ids.m1_slot_bg)
         .set(state.ids.m1_content, ui);
+        // Arrows left to shoot with a bow
+        if let Some(ItemKind::Tool(Tool {
+            kind: ToolKind::Bow(_),
+            ..
+        })) = self.loadout.active_item.as_ref().map(|i| i.item.kind())
+        {
+            let ammo = self.inventory.ammo_count();
+            let ammo_txt = ammo.to_string();
+            Text::new(&ammo_txt)
+                .bottom_right_with_margins_on(state.ids.m1_slot_bg, 2.0, 4.0)
+                .font_size(self.fonts.cyri.scale(12))
+                .font_id(self.fonts.cyri.conrod_id)
+                .color(BLACK)
+                .set(state.ids.ammo_count_bg, ui);
+            Text::new(&ammo_txt)
+                .bottom_left_with_margins_on(state.ids.ammo_count_bg, 1.0, 1.0)
+                .font_size(self.fonts.cyri.scale(12))
+                .font_id(self.fonts.cyri.conrod_id)
+                .color(if ammo == 0 {
+                    CRITICAL_HP_COLOR
+                } else {
+                    TEXT_COLOR
+                })
+                .set(state.ids.ammo_count, ui);
+        }
         // Slot M2
         Image::new(self.imgs.inv_slot)
             .w_h(40.0, 40.0)
@@ -88,6 +88,8 @@ pub enum HotbarImage {
     HammerLeap,
     AxeLeapSlash,
     BowJumpBurst,
+    ShieldTaunt,
+    SceptreSummon,
 }
 
 type HotbarSource<'a> = (&'a hotbar::State, &'a Inventory, &'a Loadout, &'a Energy);
@@ -121,6 +123,8 @@ impl<'a> SlotKey<HotbarSource<'a>, HotbarImageSource<'a>> for HotbarSlot {
                                 _ => None,
                             },
                             ToolKind::Sword(_) => Some(HotbarImage::SwordWhirlwind),
+                            ToolKind::Shield(_) => Some(HotbarImage::ShieldTaunt),
+                            ToolKind::Sceptre(_) => Some(HotbarImage::SceptreSummon),
                             _ => None,
                         },
                         _ => None,
@@ -142,6 +146,14 @@ impl<'a> SlotKey<HotbarSource<'a>, HotbarImageSource<'a>> for HotbarSlot {
                             image_key,
                             (energy.current() < 450).then_some(Color::Rgba(0.3, 0.3, 0.3, 0.8)),
                         ),
+                        HotbarImage::ShieldTaunt => (
+                            image_key,
+                            (energy.current() < 300).then_some(Color::Rgba(0.3, 0.3, 0.3, 0.8)),
+                        ),
+                        HotbarImage::SceptreSummon => (
+                            image_key,
+                            (energy.current() < 800).then_some(Color::Rgba(0.3, 0.3, 0.3, 0.8)),
+                        ),
                         _ => (
                             image_key,
                             (energy.current() < 1000).then_some(Color::Rgba(1.0, 1.0, 1.0, 1.0)),
@@ -171,6 +183,8 @@ impl<'a> SlotKey<HotbarSource<'a>, HotbarImageSource<'a>> for HotbarSlot {
             HotbarImage::HammerLeap => imgs.hammerleap,
             HotbarImage::AxeLeapSlash => imgs.skill_axe_leap_slash,
             HotbarImage::BowJumpBurst => imgs.skill_bow_jump_burst,
+            HotbarImage::ShieldTaunt => imgs.protection_ico,
+            HotbarImage::SceptreSummon => imgs.summon,
         }
     }
 }
@@ -1,9 +1,13 @@
-use super::{img_ids::Imgs, Show, TEXT_COLOR, UI_MAIN};
+use super::{img_ids::Imgs, Show, TEXT_COLOR, TEXT_GRAY_COLOR, UI_MAIN};
 use crate::{i18n::VoxygenLocalization, ui::fonts::ConrodVoxygenFonts};
+use common::comp::{
+    item::{ItemKind, ToolCategory},
+    AbilitySelection, CharacterAbility, Loadout,
+};
 use conrod_core::{
     color,
     widget::{self, Button, Image, Rectangle, Text},
-    widget_ids, Colorable, Positionable, Sizeable, Widget, WidgetCommon,
+    widget_ids, Colorable, Labelable, Positionable, Sizeable, Widget, WidgetCommon,
 };
 
 use client::{self, Client};
@@ -15,13 +19,16 @@ widget_ids! {
         spell_title,
         frame,
         content_align,
+        no_weapon,
+        slot_titles[],
+        options[],
     }
 }
 
 #[derive(WidgetCommon)]
 pub struct Spell<'a> {
     _show: &'a Show,
-    _client: &'a Client,
+    client: &'a Client,
 
     imgs: &'a Imgs,
     fonts: &'a ConrodVoxygenFonts,
@@ -34,14 +41,14 @@ pub struct Spell<'a> {
 impl<'a> Spell<'a> {
     pub fn new(
         show: &'a Show,
-        _client: &'a Client,
+        client: &'a Client,
         imgs: &'a Imgs,
         fonts: &'a ConrodVoxygenFonts,
         localized_strings: &'a std::sync::Arc<VoxygenLocalization>,
     ) -> Self {
         Self {
             _show: show,
-            _client,
+            client,
             imgs,
             fonts,
             localized_strings,
@@ -56,10 +63,42 @@ impl<'a> Spell<'a> {
 
 pub enum Event {
     Close,
+    SelectAbility {
+        tool: ToolCategory,
+        slot: usize,
+        index: usize,
+    },
+}
+
+/// Localization key of the name of an ability
+fn ability_name(ability: &CharacterAbility) -> &'static str {
+    match ability {
+        CharacterAbility::BasicMelee { .. } => "hud.spell.ability.basic_melee",
+        CharacterAbility::BasicRanged { .. } => "hud.spell.ability.basic_ranged",
+        CharacterAbility::RepeaterRanged { .. } => "hud.spell.ability.repeater_ranged",
+        CharacterAbility::Boost { .. } => "hud.spell.ability.boost",
+        CharacterAbility::DashMelee { .. } => "hud.spell.ability.dash_melee",
+        CharacterAbility::BasicBlock => "hud.spell.ability.block",
+        CharacterAbility::Roll => "hud.spell.ability.roll",
+        CharacterAbility::ComboMelee { .. } => "hud.spell.ability.combo_melee",
+        CharacterAbility::LeapMelee { .. } => "hud.spell.ability.leap_melee",
+        CharacterAbility::SpinMelee {
+            is_helicopter: true,
+            ..
+        } => "hud.spell.ability.helicopter",
+        CharacterAbility::SpinMelee { .. } => "hud.spell.ability.spin_melee",
+        CharacterAbility::ChargedMelee { .. } => "hud.spell.ability.charged_melee",
+        CharacterAbility::ChargedRanged { .. } => "hud.spell.ability.charged_ranged",
+        CharacterAbility::Shockwave { .. } => "hud.spell.ability.shockwave",
+        CharacterAbility::BasicBeam { .. } => "hud.spell.ability.beam",
+        CharacterAbility::Charge { .. } => "hud.spell.ability.charge",
+        CharacterAbility::CastSpell { .. } => "hud.spell.ability.cast_spell",
+        CharacterAbility::DualStrike { .. } => "hud.spell.ability.dual_strike",
+    }
 }
 
 impl<'a> Widget for Spell<'a> {
-    type Event = Option<Event>;
+    type Event = Vec<Event>;
     type State = Ids;
     type Style = ();
 
@@ -73,6 +112,8 @@ impl<'a> Widget for Spell<'a> {
             id: _, state, ui, ..
         } = args;
 
+        let mut events = Vec::new();
+
         Image::new(self.imgs.window_3)
             .top_left_with_margins_on(ui.window, 200.0, 25.0)
             .w_h(103.0 * 4.0, 122.0 * 4.0)
@@ -88,7 +129,7 @@ impl<'a> Widget for Spell<'a> {
             .set(state.spell_close, ui)
             .was_clicked()
         {
-            return Some(Event::Close);
+            events.push(Event::Close);
         }
 
         // Title
@@ -106,9 +147,102 @@ impl<'a> Widget for Spell<'a> {
             .set(state.content_align, ui);
 
         // Contents
+        // The ability options of the active weapon, one row per ability slot
+        let ecs = self.client.state().ecs();
+        let loadouts = ecs.read_storage::<Loadout>();
+        let selections = ecs.read_storage::<AbilitySelection>();
+        let tool = loadouts
+            .get(self.client.entity())
+            .and_then(|loadout| loadout.active_item.as_ref())
+            .and_then(|item| match item.item.kind() {
+                ItemKind::Tool(tool) => Some(tool.clone()),
+                _ => None,
+            });
+        let tool = match tool {
+            Some(tool) => tool,
+            None => {
+                Text::new(&self.localized_strings.get("hud.spell.no_weapon"))
+                    .mid_top_with_margin_on(state.content_align, 10.0)
+                    .font_id(self.fonts.cyri.conrod_id)
+                    .font_size(self.fonts.cyri.scale(14))
+                    .color(TEXT_GRAY_COLOR)
+                    .set(state.no_weapon, ui);
+                return events;
+            },
+        };
+        let category = ToolCategory::from(&tool.kind);
+        let options = tool.get_ability_options();
+        let option_count = options.iter().map(Vec::len).sum::<usize>();
+
+        if state.slot_titles.len() < options.len() || state.options.len() < option_count {
+            state.update(|state| {
+                state
+                    .slot_titles
+                    .resize(options.len(), &mut ui.widget_id_generator());
+                state
+                    .options
+                    .resize(option_count, &mut ui.widget_id_generator());
+            });
+        }
 
-        // Frame
+        let mut option_id = 0;
+        let mut row_start = 0;
+        for (slot, slot_options) in options.iter().enumerate() {
+            let selected = selections
+                .get(self.client.entity())
+                .map(|selection| selection.get(category, slot))
+                .filter(|index| *index < slot_options.len())
+                .unwrap_or(0);
+            let skill = self
+                .localized_strings
+                .get("hud.spell.skill")
+                .replace("{number}", &(slot + 1).to_string());
+            let title = Text::new(&skill)
+                .font_id(self.fonts.cyri.conrod_id)
+                .font_size(self.fonts.cyri.scale(16))
+                .color(TEXT_COLOR);
+            // Each row starts below the first option of the previous one
+            let title = if slot == 0 {
+                title.top_left_with_margins_on(state.content_align, 10.0, 10.0)
+            } else {
+                title.down_from(state.options[row_start], 15.0)
+            };
+            title.set(state.slot_titles[slot], ui);
+            row_start = option_id;
+
+            for (index, ability) in slot_options.iter().enumerate() {
+                let button = Button::image(self.imgs.nothing);
+                let button = if index == 0 {
+                    button.down_from(state.slot_titles[slot], 5.0)
+                } else {
+                    button.right_from(state.options[option_id - 1], 5.0)
+                };
+                if button
+                    .w_h(120.0, 20.0)
+                    .hover_image(self.imgs.selection_hover)
+                    .press_image(self.imgs.selection_press)
+                    .label(&self.localized_strings.get(ability_name(ability)))
+                    .label_color(if index == selected {
+                        TEXT_COLOR
+                    } else {
+                        TEXT_GRAY_COLOR
+                    })
+                    .label_font_size(self.fonts.cyri.scale(12))
+                    .label_font_id(self.fonts.cyri.conrod_id)
+                    .set(state.options[option_id], ui)
+                    .was_clicked()
+                    && index != selected
+                {
+                    events.push(Event::SelectAbility {
+                        tool: category,
+                        slot,
+                        index,
+                    });
+                }
+                option_id += 1;
+            }
+        }
 
-        None
+        events
     }
 }
@@ -25,9 +25,12 @@ pub fn item_text<'a>(item: &'a impl ItemDesc) -> (&'_ str, Cow<'a, str>) {
         ItemKind::Glider(_glider) => Cow::Owned(glider_desc(item.description())),
         ItemKind::Consumable { .. } => Cow::Owned(consumable_desc(item.description())),
         ItemKind::Throwable { .. } => Cow::Owned(throwable_desc(item.description())),
+        ItemKind::Ammo { damage, .. } => Cow::Owned(ammo_desc(*damage, item.description())),
         ItemKind::Utility { .. } => Cow::Owned(utility_desc(item.description())),
+        ItemKind::Instrument { .. } => Cow::Owned(instrument_desc(item.description())),
         ItemKind::Ingredient { .. } => Cow::Owned(ingredient_desc(item.description())),
         ItemKind::Lantern { .. } => Cow::Owned(lantern_desc(item.description())),
+        ItemKind::Dye { .. } => Cow::Owned(dye_desc(item.description())),
         //_ => Cow::Borrowed(item.description()),
     };
 
@@ -44,12 +47,22 @@ fn throwable_desc(desc: &str) -> String {
     format!("Can be thrown\n\n{}\n\n<Right-Click to use>", desc)
 }
 
+fn ammo_desc(damage: f32, desc: &str) -> String {
+    format!("Ammo\n\nDamage: {:.0}%\n\n{}", damage * 100.0, desc)
+}
+
 fn utility_desc(desc: &str) -> String { format!("{}\n\n<Right-Click to use>", desc) }
 
+fn instrument_desc(desc: &str) -> String {
+    format!("Instrument\n\n{}\n\n<Right-Click to play>", desc)
+}
+
 fn ingredient_desc(desc: &str) -> String { format!("Crafting Ingredient\n\n{}", desc) }
 
 fn lantern_desc(desc: &str) -> String { format!("Lantern\n\n{}\n\n<Right-Click to use>", desc) }
 
+fn dye_desc(desc: &str) -> String { format!("Dye\n\n{}\n\n<Drag onto worn armor to use>", desc) }
+
 // Armor Description
 fn armor_desc(armor: &Armor, desc: &str) -> String {
     // TODO: localization
@@ -12,11 +12,13 @@ pub struct KeyState {
     pub toggle_lantern: bool,
     pub toggle_sit: bool,
     pub toggle_sneak: bool,
+    pub toggle_crouch: bool,
     pub toggle_dance: bool,
     pub auto_walk: bool,
     pub swap_loadout: bool,
     pub respawn: bool,
     pub collect: bool,
+    pub fish: bool,
     pub analog_matrix: Vec2<f32>,
 }
 
@@ -34,11 +36,13 @@ impl Default for KeyState {
             toggle_lantern: false,
             toggle_sit: false,
             toggle_sneak: false,
+            toggle_crouch: false,
             toggle_dance: false,
             auto_walk: false,
             swap_loadout: false,
             respawn: false,
             collect: false,
+            fish: false,
             analog_matrix: Vec2::zero(),
         }
     }
@@ -8,6 +8,7 @@
 #[macro_use]
 pub mod ui;
 pub mod audio;
+pub mod bug_report;
 pub mod controller;
 mod ecs;
 pub mod error;
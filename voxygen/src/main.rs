@@ -15,7 +15,7 @@ use veloren_voxygen::{
 };
 
 use common::{
-    assets::{watch, Asset},
+    assets::{self, watch, Asset},
     clock::Clock,
 };
 use std::panic;
@@ -131,6 +131,11 @@ fn main() {
         default_hook(panic_info);
     }));
 
+    // Use the enabled asset packs in place of the built-in assets, and keep track
+    // of any newly found packs
+    assets::set_overrides(settings.asset_packs.open_enabled());
+    settings.save_to_file_warn();
+
     // Initialise watcher for animation hotreloading
     #[cfg(feature = "hot-anim")]
     anim::init();
@@ -125,6 +125,9 @@ impl PlayState for MainMenuState {
                             client::Error::NotOnWhitelist => {
                                 localized_strings.get("main.login.not_on_whitelist").into()
                             },
+                            client::Error::AssetPacksNotAllowed => localized_strings
+                                .get("main.login.asset_packs_not_allowed")
+                                .into(),
                             client::Error::Banned(reason) => format!(
                                 "{}: {}",
                                 localized_strings.get("main.login.banned"),
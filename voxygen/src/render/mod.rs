@@ -18,7 +18,7 @@ pub use self::{
     pipelines::{
         figure::{
             BoneData as FigureBoneData, BoneMeshes, FigureModel, FigurePipeline,
-            Locals as FigureLocals,
+            Locals as FigureLocals, Outline,
         },
         fluid::FluidPipeline,
         lod_terrain::{Locals as LodTerrainLocals, LodData, LodTerrainPipeline},
@@ -38,7 +38,8 @@ pub use self::{
     },
     renderer::{
         ColLightFmt, ColLightInfo, LodAltFmt, LodColorFmt, LodTextureFmt, Renderer,
-        ShadowDepthStencilFmt, TgtColorFmt, TgtDepthStencilFmt, WinColorFmt, WinDepthFmt,
+        ShadowDepthStencilFmt, TgtColorFmt, TgtDepthStencilFmt, TgtOutlineFmt, WinColorFmt,
+        WinDepthFmt,
     },
     texture::Texture,
 };
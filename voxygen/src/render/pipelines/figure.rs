@@ -1,5 +1,7 @@
 use super::{
-    super::{Mesh, Model, Pipeline, TerrainPipeline, TgtColorFmt, TgtDepthStencilFmt},
+    super::{
+        Mesh, Model, Pipeline, TerrainPipeline, TgtColorFmt, TgtDepthStencilFmt, TgtOutlineFmt,
+    },
     shadow, Globals, Light, Shadow,
 };
 use crate::mesh::greedy::GreedyMesh;
@@ -46,11 +48,28 @@ gfx_defines! {
         light_shadows: gfx::ConstantBuffer<shadow::Locals> = "u_light_shadows",
 
         tgt_color: gfx::BlendTarget<TgtColorFmt> = ("tgt_color", ColorMask::all(), gfx::preset::blend::ALPHA),
+        tgt_outline: gfx::RenderTarget<TgtOutlineFmt> = "tgt_outline",
         tgt_depth_stencil: gfx::DepthTarget<TgtDepthStencilFmt> = gfx::preset::depth::LESS_EQUAL_WRITE,
         // tgt_depth_stencil: gfx::DepthStencilTarget<TgtDepthStencilFmt> = (gfx::preset::depth::LESS_EQUAL_WRITE,Stencil::new(Comparison::Always,0xff,(StencilOp::Keep,StencilOp::Keep,StencilOp::Replace))),
     }
 }
 
+/// Outline drawn around a figure by the post-process pass
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Outline {
+    None = 0,
+    /// The entity under the crosshair
+    Target = 1,
+    /// An entity under the crosshair that can be picked up
+    Interactable = 2,
+    /// A member of the player's group, outlined only where it is hidden
+    Group = 3,
+}
+
+impl Default for Outline {
+    fn default() -> Self { Self::None }
+}
+
 impl Locals {
     pub fn new(
         model_mat: anim::vek::Mat4<f32>,
@@ -58,9 +77,11 @@ impl Locals {
         pos: anim::vek::Vec3<f32>,
         atlas_offs: Vec2<i32>,
         is_player: bool,
+        outline: Outline,
     ) -> Self {
         let mut flags = 0;
         flags |= is_player as u32;
+        flags |= (outline as u32) << 1;
 
         Self {
             model_mat: model_mat.into_col_arrays(),
@@ -80,6 +101,7 @@ impl Default for Locals {
             anim::vek::Vec3::default(),
             Vec2::default(),
             false,
+            Outline::None,
         )
     }
 }
@@ -11,7 +11,10 @@ pub mod ui;
 
 use super::Consts;
 use crate::scene::camera::CameraMode;
-use common::terrain::BlockKind;
+use common::{
+    terrain::BlockKind,
+    time::{moon_phase, DAYS_PER_YEAR, DAY_SECS},
+};
 use gfx::{self, gfx_constant_struct_meta, gfx_defines, gfx_impl_struct_meta};
 use vek::*;
 
@@ -95,7 +98,14 @@ impl Globals {
             focus_off: Vec4::from(focus_pos).map(|e: f32| e.trunc()).into_array(),
             focus_pos: Vec4::from(focus_pos).map(|e: f32| e.fract()).into_array(),
             view_distance: [view_distance, tgt_detail, map_bounds.x, map_bounds.y],
-            time_of_day: [time_of_day as f32; 4],
+            // The time of day counts up from the start of the calendar, which is too
+            // large to pass on as it is without losing precision
+            time_of_day: [
+                time_of_day.rem_euclid(DAY_SECS * DAYS_PER_YEAR as f64) as f32,
+                moon_phase(time_of_day),
+                0.0,
+                0.0,
+            ],
             sun_dir: Vec4::from_direction(Self::get_sun_dir(time_of_day)).into_array(),
             moon_dir: Vec4::from_direction(Self::get_moon_dir(time_of_day)).into_array(),
             tick: [tick as f32; 4],
@@ -132,7 +142,7 @@ impl Globals {
 
     fn get_angle_rad(time_of_day: f64) -> f32 {
         const TIME_FACTOR: f32 = (std::f32::consts::PI * 2.0) / (3600.0 * 24.0);
-        time_of_day as f32 * TIME_FACTOR
+        time_of_day.rem_euclid(DAY_SECS) as f32 * TIME_FACTOR
     }
 
     pub fn get_sun_dir(time_of_day: f64) -> Vec3<f32> {
@@ -113,6 +113,12 @@ pub enum ParticleMode {
     EnergyNature = 14,
     FlameThrower = 15,
     FireShockwave = 16,
+    Footprint = 17,
+    Scorch = 18,
+    BloodSplat = 19,
+    Debris = 20,
+    WaterSplash = 21,
+    WaterfallSpray = 22,
 }
 
 impl ParticleMode {
@@ -1,5 +1,5 @@
 use super::{
-    super::{Mesh, Pipeline, TgtColorFmt, TgtDepthStencilFmt, Tri, WinColorFmt},
+    super::{Mesh, Pipeline, TgtColorFmt, TgtDepthStencilFmt, TgtOutlineFmt, Tri, WinColorFmt},
     Globals,
 };
 use gfx::{
@@ -30,6 +30,7 @@ gfx_defines! {
 
         color_sampler: gfx::TextureSampler<<TgtColorFmt as gfx::format::Formatted>::View> = "src_color",
         depth_sampler: gfx::TextureSampler<<TgtDepthStencilFmt as gfx::format::Formatted>::View> = "src_depth",
+        outline_sampler: gfx::TextureSampler<<TgtOutlineFmt as gfx::format::Formatted>::View> = "src_outline",
 
         noise: gfx::TextureSampler<f32> = "t_noise",
 
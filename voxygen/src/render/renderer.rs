@@ -36,6 +36,9 @@ use vek::*;
 pub type TgtColorFmt = gfx::format::Srgba8;
 /// Represents the format of the pre-processed depth and stencil target.
 pub type TgtDepthStencilFmt = gfx::format::Depth;
+/// Represents the format of the target marking which outline, if any, to draw
+/// around each pixel's figure.
+pub type TgtOutlineFmt = (gfx::format::R8, gfx::format::Unorm);
 
 /// Represents the format of the window's color target.
 pub type WinColorFmt = gfx::format::Srgba8;
@@ -50,6 +53,8 @@ pub type TgtColorView = gfx::handle::RenderTargetView<gfx_backend::Resources, Tg
 /// A handle to a pre-processed depth target.
 pub type TgtDepthStencilView =
     gfx::handle::DepthStencilView<gfx_backend::Resources, TgtDepthStencilFmt>;
+/// A handle to a pre-processed outline target.
+pub type TgtOutlineView = gfx::handle::RenderTargetView<gfx_backend::Resources, TgtOutlineFmt>;
 
 /// A handle to a window color target.
 pub type WinColorView = gfx::handle::RenderTargetView<gfx_backend::Resources, WinColorFmt>;
@@ -89,6 +94,12 @@ pub type TgtDepthRes = gfx::handle::ShaderResourceView<
     <TgtDepthStencilFmt as gfx::format::Formatted>::View,
 >;
 
+/// A handle to a render outline target as a resource.
+pub type TgtOutlineRes = gfx::handle::ShaderResourceView<
+    gfx_backend::Resources,
+    <TgtOutlineFmt as gfx::format::Formatted>::View,
+>;
+
 /// A handle to a greedy meshed color-light texture as a resource.
 pub type ColLightRes = gfx::handle::ShaderResourceView<
     gfx_backend::Resources,
@@ -148,9 +159,11 @@ pub struct Renderer {
 
     tgt_color_view: TgtColorView,
     tgt_depth_stencil_view: TgtDepthStencilView,
+    tgt_outline_view: TgtOutlineView,
 
     tgt_color_res: TgtColorRes,
     tgt_depth_res: TgtDepthRes,
+    tgt_outline_res: TgtOutlineRes,
 
     sampler: Sampler<gfx_backend::Resources>,
 
@@ -166,6 +179,7 @@ pub struct Renderer {
     lod_terrain_pipeline: GfxPipeline<lod_terrain::pipe::Init<'static>>,
     postprocess_pipeline: GfxPipeline<postprocess::pipe::Init<'static>>,
     player_shadow_pipeline: GfxPipeline<figure::pipe::Init<'static>>,
+    figure_outline_pipeline: GfxPipeline<figure::pipe::Init<'static>>,
 
     shader_reload_indicator: ReloadIndicator,
 
@@ -215,6 +229,7 @@ impl Renderer {
             lod_terrain_pipeline,
             postprocess_pipeline,
             player_shadow_pipeline,
+            figure_outline_pipeline,
             point_shadow_pipeline,
             terrain_directed_shadow_pipeline,
             figure_directed_shadow_pipeline,
@@ -225,8 +240,14 @@ impl Renderer {
             &mut shader_reload_indicator,
         )?;
 
-        let (tgt_color_view, tgt_depth_stencil_view, tgt_color_res, tgt_depth_res) =
-            Self::create_rt_views(&mut factory, (dims.0, dims.1), &mode)?;
+        let (
+            tgt_color_view,
+            tgt_depth_stencil_view,
+            tgt_outline_view,
+            tgt_color_res,
+            tgt_depth_res,
+            tgt_outline_res,
+        ) = Self::create_rt_views(&mut factory, (dims.0, dims.1), &mode)?;
 
         let shadow_map = if let (
             Some(point_pipeline),
@@ -286,9 +307,11 @@ impl Renderer {
 
             tgt_color_view,
             tgt_depth_stencil_view,
+            tgt_outline_view,
 
             tgt_color_res,
             tgt_depth_res,
+            tgt_outline_res,
 
             sampler,
 
@@ -304,6 +327,7 @@ impl Renderer {
             lod_terrain_pipeline,
             postprocess_pipeline,
             player_shadow_pipeline,
+            figure_outline_pipeline,
 
             shader_reload_indicator,
 
@@ -363,12 +387,20 @@ impl Renderer {
 
         // Avoid panics when creating texture with w,h of 0,0.
         if dims.0 != 0 && dims.1 != 0 {
-            let (tgt_color_view, tgt_depth_stencil_view, tgt_color_res, tgt_depth_res) =
-                Self::create_rt_views(&mut self.factory, (dims.0, dims.1), &self.mode)?;
+            let (
+                tgt_color_view,
+                tgt_depth_stencil_view,
+                tgt_outline_view,
+                tgt_color_res,
+                tgt_depth_res,
+                tgt_outline_res,
+            ) = Self::create_rt_views(&mut self.factory, (dims.0, dims.1), &self.mode)?;
             self.tgt_color_res = tgt_color_res;
             self.tgt_depth_res = tgt_depth_res;
+            self.tgt_outline_res = tgt_outline_res;
             self.tgt_color_view = tgt_color_view;
             self.tgt_depth_stencil_view = tgt_depth_stencil_view;
+            self.tgt_outline_view = tgt_outline_view;
             if let (Some(shadow_map), ShadowMode::Map(mode)) =
                 (self.shadow_map.as_mut(), self.mode.shadow)
             {
@@ -399,11 +431,22 @@ impl Renderer {
         Ok(())
     }
 
+    #[allow(clippy::type_complexity)]
     fn create_rt_views(
         factory: &mut gfx_device_gl::Factory,
         size: (u16, u16),
         mode: &RenderMode,
-    ) -> Result<(TgtColorView, TgtDepthStencilView, TgtColorRes, TgtDepthRes), RenderError> {
+    ) -> Result<
+        (
+            TgtColorView,
+            TgtDepthStencilView,
+            TgtOutlineView,
+            TgtColorRes,
+            TgtDepthRes,
+            TgtOutlineRes,
+        ),
+        RenderError,
+    > {
         let kind = match mode.aa {
             AaMode::None | AaMode::Fxaa => {
                 gfx::texture::Kind::D2(size.0, size.1, gfx::texture::AaMode::Single)
@@ -456,11 +499,32 @@ impl Renderer {
         let tgt_depth_stencil_view =
             factory.view_texture_as_depth_stencil_trivial(&tgt_depth_stencil_tex)?;
 
+        // The outline target is drawn to alongside the color target, so it has to be
+        // multisampled in the same way. The post-process pass reads it through the
+        // sampler declared by the shader of the anti-aliasing mode.
+        let outline_cty = <<TgtOutlineFmt as gfx::format::Formatted>::Channel as gfx::format::ChannelTyped
+                >::get_channel_type();
+        let tgt_outline_tex = factory.create_texture(
+            kind,
+            levels,
+            gfx::memory::Bind::SHADER_RESOURCE | gfx::memory::Bind::RENDER_TARGET,
+            gfx::memory::Usage::Data,
+            Some(outline_cty),
+        )?;
+        let tgt_outline_res = factory.view_texture_as_shader_resource::<TgtOutlineFmt>(
+            &tgt_outline_tex,
+            (0, levels - 1),
+            gfx::format::Swizzle::new(),
+        )?;
+        let tgt_outline_view = factory.view_texture_as_render_target(&tgt_outline_tex, 0, None)?;
+
         Ok((
             tgt_color_view,
             tgt_depth_stencil_view,
+            tgt_outline_view,
             tgt_color_res,
             tgt_depth_res,
+            tgt_outline_res,
         ))
     }
 
@@ -695,11 +759,12 @@ impl Renderer {
         }
     }
 
-    /// Queue the clearing of the depth target ready for a new frame to be
-    /// rendered.
+    /// Queue the clearing of the depth and outline targets ready for a new
+    /// frame to be rendered.
     pub fn clear(&mut self) {
         span!(_guard, "clear", "Renderer::clear");
         self.encoder.clear_depth(&self.tgt_depth_stencil_view, 1.0);
+        self.encoder.clear(&self.tgt_outline_view, 0.0);
         // self.encoder.clear_stencil(&self.tgt_depth_stencil_view, 0);
         self.encoder.clear_depth(&self.win_depth_view, 1.0);
     }
@@ -763,6 +828,7 @@ impl Renderer {
                 lod_terrain_pipeline,
                 postprocess_pipeline,
                 player_shadow_pipeline,
+                figure_outline_pipeline,
                 point_shadow_pipeline,
                 terrain_directed_shadow_pipeline,
                 figure_directed_shadow_pipeline,
@@ -777,6 +843,7 @@ impl Renderer {
                 self.lod_terrain_pipeline = lod_terrain_pipeline;
                 self.postprocess_pipeline = postprocess_pipeline;
                 self.player_shadow_pipeline = player_shadow_pipeline;
+                self.figure_outline_pipeline = figure_outline_pipeline;
                 if let (
                     Some(point_pipeline),
                     Some(terrain_directed_pipeline),
@@ -1080,11 +1147,56 @@ impl Renderer {
                 alt: (lod.alt.srv.clone(), lod.alt.sampler.clone()),
                 horizon: (lod.horizon.srv.clone(), lod.horizon.sampler.clone()),
                 tgt_color: self.tgt_color_view.clone(),
+                tgt_outline: self.tgt_outline_view.clone(),
                 tgt_depth_stencil: (self.tgt_depth_stencil_view.clone()/* , (1, 1) */),
             },
         );
     }
 
+    /// Queue the marking of the parts of the provided figure model hidden
+    /// behind what has already been rendered, so that they are outlined in
+    /// the post-process pass.
+    pub fn render_figure_outline(
+        &mut self,
+        model: &figure::FigureModel,
+        col_lights: &Texture<ColLightFmt>,
+        global: &GlobalModel,
+        locals: &Consts<figure::Locals>,
+        bones: &Consts<figure::BoneData>,
+        lod: &lod_terrain::LodData,
+    ) {
+        let model = &model.opaque;
+
+        self.encoder.draw(
+            &gfx::Slice {
+                start: model.vertex_range().start,
+                end: model.vertex_range().end,
+                base_vertex: 0,
+                instances: None,
+                buffer: gfx::IndexBuffer::Auto,
+            },
+            &self.figure_outline_pipeline.pso,
+            &figure::pipe::Data {
+                vbuf: model.vbuf.clone(),
+                col_lights: (col_lights.srv.clone(), col_lights.sampler.clone()),
+                locals: locals.buf.clone(),
+                globals: global.globals.buf.clone(),
+                bones: bones.buf.clone(),
+                lights: global.lights.buf.clone(),
+                shadows: global.shadows.buf.clone(),
+                light_shadows: global.shadow_mats.buf.clone(),
+                point_shadow_maps: (self.noise_tex.srv.clone(), self.noise_tex.sampler.clone()),
+                directed_shadow_maps: (self.noise_tex.srv.clone(), self.noise_tex.sampler.clone()),
+                noise: (self.noise_tex.srv.clone(), self.noise_tex.sampler.clone()),
+                alt: (lod.alt.srv.clone(), lod.alt.sampler.clone()),
+                horizon: (lod.horizon.srv.clone(), lod.horizon.sampler.clone()),
+                tgt_color: self.tgt_color_view.clone(),
+                tgt_outline: self.tgt_outline_view.clone(),
+                tgt_depth_stencil: self.tgt_depth_stencil_view.clone(),
+            },
+        );
+    }
+
     /// Queue the rendering of the player silhouette in the upcoming frame.
     pub fn render_player_shadow(
         &mut self,
@@ -1140,6 +1252,7 @@ impl Renderer {
                 alt: (lod.alt.srv.clone(), lod.alt.sampler.clone()),
                 horizon: (lod.horizon.srv.clone(), lod.horizon.sampler.clone()),
                 tgt_color: self.tgt_color_view.clone(),
+                tgt_outline: self.tgt_outline_view.clone(),
                 tgt_depth_stencil: (self.tgt_depth_stencil_view.clone()/* , (0, 0) */),
             },
         ); */
@@ -1199,6 +1312,7 @@ impl Renderer {
                 alt: (lod.alt.srv.clone(), lod.alt.sampler.clone()),
                 horizon: (lod.horizon.srv.clone(), lod.horizon.sampler.clone()),
                 tgt_color: self.tgt_color_view.clone(),
+                tgt_outline: self.tgt_outline_view.clone(),
                 tgt_depth_stencil: (self.tgt_depth_stencil_view.clone()/* , (1, 1) */),
             },
         );
@@ -1668,6 +1782,7 @@ impl Renderer {
                 horizon: (lod.horizon.srv.clone(), lod.horizon.sampler.clone()),
                 color_sampler: (self.tgt_color_res.clone(), self.sampler.clone()),
                 depth_sampler: (self.tgt_depth_res.clone(), self.sampler.clone()),
+                outline_sampler: (self.tgt_outline_res.clone(), self.sampler.clone()),
                 noise: (self.noise_tex.srv.clone(), self.noise_tex.sampler.clone()),
                 tgt_color: self.win_color_view.clone(),
             },
@@ -1698,6 +1813,7 @@ fn create_pipelines(
         GfxPipeline<lod_terrain::pipe::Init<'static>>,
         GfxPipeline<postprocess::pipe::Init<'static>>,
         GfxPipeline<figure::pipe::Init<'static>>,
+        GfxPipeline<figure::pipe::Init<'static>>,
         Option<GfxPipeline<shadow::pipe::Init<'static>>>,
         Option<GfxPipeline<shadow::pipe::Init<'static>>>,
         Option<GfxPipeline<shadow::figure_pipe::Init<'static>>>,
@@ -1938,6 +2054,31 @@ fn create_pipelines(
         gfx::state::CullFace::Back,
     )?;
 
+    // Construct a pipeline for marking the hidden parts of outlined figures
+    let figure_outline_pipeline = create_pipeline(
+        factory,
+        figure::pipe::Init {
+            tgt_color: (
+                "tgt_color",
+                gfx::state::ColorMask::empty(),
+                gfx::preset::blend::ALPHA,
+            ),
+            tgt_depth_stencil: gfx::state::Depth {
+                fun: Comparison::Greater,
+                write: false,
+            },
+            ..figure::pipe::new()
+        },
+        &figure_vert,
+        &Glsl::load_watched(
+            "voxygen.shaders.figure-outline-frag",
+            shader_reload_indicator,
+        )
+        .unwrap(),
+        &include_ctx,
+        gfx::state::CullFace::Back,
+    )?;
+
     // Construct a pipeline for rendering point light terrain shadow maps.
     let point_shadow_pipeline = match create_shadow_pipeline(
         factory,
@@ -2019,6 +2160,7 @@ fn create_pipelines(
         lod_terrain_pipeline,
         postprocess_pipeline,
         player_shadow_pipeline,
+        figure_outline_pipeline,
         point_shadow_pipeline,
         terrain_directed_shadow_pipeline,
         figure_directed_shadow_pipeline,
@@ -1,4 +1,4 @@
-use common::{span, terrain::TerrainGrid, vol::ReadVol};
+use common::{ray::Ray, span, terrain::TerrainGrid, vol::ReadVol};
 use std::f32::consts::PI;
 use treeculler::Frustum;
 use vek::*;
@@ -11,6 +11,14 @@ const THIRD_PERSON_INTERP_TIME: f32 = 0.1;
 const FREEFLY_INTERP_TIME: f32 = 0.0;
 const LERP_ORI_RATE: f32 = 15.0;
 pub const MIN_ZOOM: f32 = 0.1;
+const MIN_THIRD_PERSON: f32 = 2.35;
+/// Closest that the third-person camera can be zoomed in to the focus, unless
+/// set otherwise
+const DEFAULT_MIN_DIST: f32 = 2.0;
+/// How much of the shake wears off each second
+const SHAKE_DECAY: f32 = 2.5;
+/// Largest angle the camera is thrown off by at full shake, in radians
+const MAX_SHAKE_ANGLE: f32 = 0.04;
 
 // Possible TODO: Add more modes
 #[derive(PartialEq, Clone, Copy, Eq, Hash)]
@@ -44,6 +52,17 @@ pub struct Camera {
     fov: f32,
     aspect: f32,
     mode: CameraMode,
+    /// Closest and farthest third-person distances from the focus
+    min_dist: f32,
+    max_dist: f32,
+    /// Sideways offset of the third-person camera, positive to the right
+    shoulder_offset: f32,
+    /// Rate at which the orientation catches up with its target when smoothed
+    ori_rate: f32,
+    /// How much faster quick cursor movements turn the camera
+    pan_acceleration: f32,
+    /// Current strength of the camera shake, from 0.0 to 1.0
+    shake: f32,
 
     last_time: Option<f64>,
 
@@ -64,6 +83,12 @@ impl Camera {
             fov: 1.1,
             aspect,
             mode,
+            min_dist: DEFAULT_MIN_DIST,
+            max_dist: f32::INFINITY,
+            shoulder_offset: 0.0,
+            ori_rate: LERP_ORI_RATE,
+            pan_acceleration: 0.0,
+            shake: 0.0,
 
             last_time: None,
 
@@ -92,8 +117,30 @@ impl Camera {
         is_transparent: fn(&V::Vox) -> bool,
     ) {
         span!(_guard, "compute_dependents", "Camera::compute_dependents");
+        // Keep the shoulder offset from pushing the camera into walls. The focus is in
+        // the air, so this ray stops at the first block that isn't.
+        let shoulder = if self.mode == CameraMode::ThirdPerson && self.shoulder_offset != 0.0 {
+            let side = self.right() * self.shoulder_offset;
+            let offset = self.shoulder_offset.abs();
+            match Ray::<_, _, fn(&V::Vox, Vec3<i32>)>::new(
+                terrain,
+                self.focus,
+                self.focus + side,
+                |block| !is_transparent(block),
+            )
+            .ignore_error()
+            .max_iter(500)
+            .cast()
+            {
+                (d, Ok(Some(_))) => (d - 0.1).max(0.0).min(offset) * self.shoulder_offset.signum(),
+                _ => self.shoulder_offset,
+            }
+        } else {
+            0.0
+        };
         let dist = {
-            let (start, end) = (self.focus - self.forward() * self.dist, self.focus);
+            let end = self.focus + self.right() * shoulder;
+            let start = end - self.forward() * self.dist;
 
             match terrain
                 .ray(start, end)
@@ -109,11 +156,22 @@ impl Camera {
             .max(0.0)
         };
 
+        // Shaking jolts the view around, more violently the stronger the shake
+        let shake = {
+            let time = self.last_time.unwrap_or(0.0) as f32;
+            let angle = self.shake.powi(2) * MAX_SHAKE_ANGLE;
+            Vec3::new(
+                (time * 41.0).sin() + (time * 23.0).sin() * 0.5,
+                (time * 37.0).sin() + (time * 29.0).cos() * 0.5,
+                (time * 31.0).cos() * 0.5,
+            ) * angle
+        };
+
         self.dependents.view_mat = Mat4::<f32>::identity()
-            * Mat4::translation_3d(-Vec3::unit_z() * dist)
-            * Mat4::rotation_z(self.ori.z)
-            * Mat4::rotation_x(self.ori.y)
-            * Mat4::rotation_y(self.ori.x)
+            * Mat4::translation_3d(-Vec3::unit_x() * shoulder - Vec3::unit_z() * dist)
+            * Mat4::rotation_z(self.ori.z + shake.z)
+            * Mat4::rotation_x(self.ori.y + shake.y)
+            * Mat4::rotation_y(self.ori.x + shake.x)
             * Mat4::rotation_3d(PI / 2.0, -Vec4::unit_x())
             * Mat4::translation_3d(-self.focus.map(|e| e.fract()));
         self.dependents.view_mat_inv = self.dependents.view_mat.inverted();
@@ -171,11 +229,23 @@ impl Camera {
         self.ori.z = ori.z.rem_euclid(2.0 * PI);
     }
 
+    /// Point the camera at `focus` from `pos`, regardless of the distance
+    /// limits.
+    pub fn look_from(&mut self, pos: Vec3<f32>, focus: Vec3<f32>) {
+        let offset = focus - pos;
+        let dir = offset.try_normalized().unwrap_or_else(Vec3::unit_y);
+        self.tgt_focus = focus;
+        self.tgt_dist = offset.magnitude();
+        self.set_orientation(Vec3::new(dir.x.atan2(dir.y), (-dir.z).asin(), 0.0));
+    }
+
     /// Zoom the camera by the given delta, limiting the input accordingly.
     pub fn zoom_by(&mut self, delta: f32) {
         if self.mode == CameraMode::ThirdPerson {
-            // Clamp camera dist to the 2 <= x <= infinity range
-            self.tgt_dist = (self.tgt_dist + delta).max(2.0);
+            // Clamp camera dist to the configured range
+            self.tgt_dist = (self.tgt_dist + delta)
+                .max(self.min_dist)
+                .min(self.max_dist);
         }
     }
 
@@ -183,18 +253,18 @@ impl Camera {
     pub fn zoom_switch(&mut self, delta: f32) {
         if delta > 0_f32 || self.mode != CameraMode::FirstPerson {
             let t = self.tgt_dist + delta;
-            const MIN_THIRD_PERSON: f32 = 2.35;
+            let min_third_person = MIN_THIRD_PERSON.max(self.min_dist);
             match self.mode {
                 CameraMode::ThirdPerson => {
-                    if t < MIN_THIRD_PERSON {
+                    if t < min_third_person {
                         self.set_mode(CameraMode::FirstPerson);
                     } else {
-                        self.tgt_dist = t;
+                        self.tgt_dist = t.min(self.max_dist);
                     }
                 },
                 CameraMode::FirstPerson => {
                     self.set_mode(CameraMode::ThirdPerson);
-                    self.tgt_dist = MIN_THIRD_PERSON;
+                    self.tgt_dist = min_third_person;
                 },
                 _ => {},
             }
@@ -207,9 +277,40 @@ impl Camera {
     /// Set the distance of the camera from the focus (i.e., zoom).
     pub fn set_distance(&mut self, dist: f32) { self.tgt_dist = dist; }
 
+    /// Set the closest and farthest distances of the third-person camera from
+    /// the focus, with no limit on how far it can go if `max` is `None`.
+    pub fn set_distance_limits(&mut self, min: f32, max: Option<f32>) {
+        self.min_dist = min.max(MIN_ZOOM);
+        self.max_dist = max.map_or(f32::INFINITY, |max| max.max(self.min_dist));
+        self.zoom_by(0.0);
+    }
+
+    /// Get the sideways offset of the third-person camera.
+    pub fn shoulder_offset(&self) -> f32 { self.shoulder_offset }
+
+    /// Set the sideways offset of the third-person camera, positive to the
+    /// right of the focus.
+    pub fn set_shoulder_offset(&mut self, offset: f32) { self.shoulder_offset = offset; }
+
+    /// Set how quickly smoothed camera rotation catches up with the input.
+    pub fn set_smoothing(&mut self, rate: f32) { self.ori_rate = rate.max(0.0); }
+
+    /// Get how much faster quick cursor movements turn the camera.
+    pub fn pan_acceleration(&self) -> f32 { self.pan_acceleration }
+
+    /// Set how much faster quick cursor movements turn the camera.
+    pub fn set_pan_acceleration(&mut self, acceleration: f32) {
+        self.pan_acceleration = acceleration.max(0.0);
+    }
+
+    /// Shake the camera, adding `amount` to the current shake, which is capped
+    /// at 1.0 and wears off over time.
+    pub fn shake(&mut self, amount: f32) { self.shake = (self.shake + amount).min(1.0); }
+
     pub fn update(&mut self, time: f64, dt: f32, smoothing_enabled: bool) {
         // This is horribly frame time dependent, but so is most of the game
         let delta = self.last_time.replace(time).map_or(0.0, |t| time - t);
+        self.shake = (self.shake - SHAKE_DECAY * dt).max(0.0);
         if (self.dist - self.tgt_dist).abs() > 0.01 {
             self.dist = f32::lerp(
                 self.dist,
@@ -246,10 +347,11 @@ impl Camera {
         };
 
         if smoothing_enabled {
+            let rate = self.ori_rate * dt;
             self.set_ori_instant(Vec3::new(
-                lerp_angle(self.ori.x, self.tgt_ori.x, LERP_ORI_RATE * dt),
-                Lerp::lerp(self.ori.y, self.tgt_ori.y, LERP_ORI_RATE * dt),
-                lerp_angle(self.ori.z, self.tgt_ori.z, LERP_ORI_RATE * dt),
+                lerp_angle(self.ori.x, self.tgt_ori.x, rate),
+                Lerp::lerp(self.ori.y, self.tgt_ori.y, rate),
+                lerp_angle(self.ori.z, self.tgt_ori.z, rate),
             ));
         } else {
             self.set_ori_instant(self.tgt_ori)
@@ -13,7 +13,7 @@ use common::{
         item::{
             armor::{Armor, ArmorKind},
             tool::ToolKind,
-            ItemKind,
+            Item, ItemKind,
         },
         CharacterState, Loadout,
     },
@@ -53,7 +53,9 @@ enum FigureModelEntryFuture<const N: usize> {
     Done(FigureModelEntry<N>),
 }
 
-const LOD_COUNT: usize = 3;
+/// Number of models generated for each figure, from full detail down to the
+/// coarse stand-in drawn beyond the figure LoD render distance.
+const LOD_COUNT: usize = 4;
 
 type FigureModelEntryLod<'b> = Option<&'b FigureModelEntry<LOD_COUNT>>;
 
@@ -73,14 +75,37 @@ pub(super) struct CharacterToolKey {
     pub second: Option<ToolKind>,
 }
 
+/// How a worn piece of armor looks.
+#[derive(Eq, Hash, PartialEq)]
+pub(super) struct ArmorLook {
+    pub kind: String,
+    pub dye: Option<Rgb<u8>>,
+}
+
+impl ArmorLook {
+    /// The look of `item` if it looks like the kind of armor picked out by
+    /// `select`, which may differ from the item itself when its appearance is
+    /// overridden.
+    fn of(item: Option<&Item>, select: impl Fn(&ArmorKind) -> Option<&String>) -> Option<Self> {
+        let item = item?;
+        match item.look() {
+            ItemKind::Armor(Armor { kind, .. }) => select(kind).map(|kind| Self {
+                kind: kind.clone(),
+                dye: item.dye(),
+            }),
+            _ => None,
+        }
+    }
+}
+
 /// Character data that exists in third person only.
 #[derive(Eq, Hash, PartialEq)]
 pub(super) struct CharacterThirdPersonKey {
-    pub shoulder: Option<String>,
-    pub chest: Option<String>,
-    pub belt: Option<String>,
-    pub back: Option<String>,
-    pub pants: Option<String>,
+    pub shoulder: Option<ArmorLook>,
+    pub chest: Option<ArmorLook>,
+    pub belt: Option<ArmorLook>,
+    pub back: Option<ArmorLook>,
+    pub pants: Option<ArmorLook>,
 }
 
 #[derive(Eq, Hash, PartialEq)]
@@ -102,8 +127,8 @@ pub(super) struct CharacterCacheKey {
     pub tool: Option<CharacterToolKey>,
     pub lantern: Option<String>,
     pub glider: Option<String>,
-    pub hand: Option<String>,
-    pub foot: Option<String>,
+    pub hand: Option<ArmorLook>,
+    pub foot: Option<ArmorLook>,
 }
 
 impl CharacterCacheKey {
@@ -131,51 +156,26 @@ impl CharacterCacheKey {
                 None
             } else {
                 Some(CharacterThirdPersonKey {
-                    shoulder: if let Some(ItemKind::Armor(Armor {
-                        kind: ArmorKind::Shoulder(armor),
-                        ..
-                    })) = loadout.shoulder.as_ref().map(|i| i.kind())
-                    {
-                        Some(armor.clone())
-                    } else {
-                        None
-                    },
-                    chest: if let Some(ItemKind::Armor(Armor {
-                        kind: ArmorKind::Chest(armor),
-                        ..
-                    })) = loadout.chest.as_ref().map(|i| i.kind())
-                    {
-                        Some(armor.clone())
-                    } else {
-                        None
-                    },
-                    belt: if let Some(ItemKind::Armor(Armor {
-                        kind: ArmorKind::Belt(armor),
-                        ..
-                    })) = loadout.belt.as_ref().map(|i| i.kind())
-                    {
-                        Some(armor.clone())
-                    } else {
-                        None
-                    },
-                    back: if let Some(ItemKind::Armor(Armor {
-                        kind: ArmorKind::Back(armor),
-                        ..
-                    })) = loadout.back.as_ref().map(|i| i.kind())
-                    {
-                        Some(armor.clone())
-                    } else {
-                        None
-                    },
-                    pants: if let Some(ItemKind::Armor(Armor {
-                        kind: ArmorKind::Pants(armor),
-                        ..
-                    })) = loadout.pants.as_ref().map(|i| i.kind())
-                    {
-                        Some(armor.clone())
-                    } else {
-                        None
-                    },
+                    shoulder: ArmorLook::of(loadout.shoulder.as_ref(), |armor| match armor {
+                        ArmorKind::Shoulder(armor) => Some(armor),
+                        _ => None,
+                    }),
+                    chest: ArmorLook::of(loadout.chest.as_ref(), |armor| match armor {
+                        ArmorKind::Chest(armor) => Some(armor),
+                        _ => None,
+                    }),
+                    belt: ArmorLook::of(loadout.belt.as_ref(), |armor| match armor {
+                        ArmorKind::Belt(armor) => Some(armor),
+                        _ => None,
+                    }),
+                    back: ArmorLook::of(loadout.back.as_ref(), |armor| match armor {
+                        ArmorKind::Back(armor) => Some(armor),
+                        _ => None,
+                    }),
+                    pants: ArmorLook::of(loadout.pants.as_ref(), |armor| match armor {
+                        ArmorKind::Pants(armor) => Some(armor),
+                        _ => None,
+                    }),
                 })
             },
             tool: if are_tools_visible {
@@ -212,24 +212,14 @@ impl CharacterCacheKey {
             } else {
                 None
             },
-            hand: if let Some(ItemKind::Armor(Armor {
-                kind: ArmorKind::Hand(armor),
-                ..
-            })) = loadout.hand.as_ref().map(|i| i.kind())
-            {
-                Some(armor.clone())
-            } else {
-                None
-            },
-            foot: if let Some(ItemKind::Armor(Armor {
-                kind: ArmorKind::Foot(armor),
-                ..
-            })) = loadout.foot.as_ref().map(|i| i.kind())
-            {
-                Some(armor.clone())
-            } else {
-                None
-            },
+            hand: ArmorLook::of(loadout.hand.as_ref(), |armor| match armor {
+                ArmorKind::Hand(armor) => Some(armor),
+                _ => None,
+            }),
+            foot: ArmorLook::of(loadout.foot.as_ref(), |armor| match armor {
+                ArmorKind::Foot(armor) => Some(armor),
+                _ => None,
+            }),
         }
     }
 }
@@ -315,7 +305,6 @@ where
         Skel::Body: Clone + Send + Sync + 'static,
         <Skel::Body as BodySpec>::Spec: Send + Sync + 'static,
     {
-        let skeleton_attr = (&body).into();
         let key = FigureKey {
             body,
             extra: loadout.map(|loadout| {
@@ -370,6 +359,9 @@ where
             },
             Entry::Vacant(v) => {
                 let key = v.key().clone();
+                // Worked out once per model rather than every frame, since some skeleton
+                // attributes are loaded from assets
+                let skeleton_attr = (&key.body).into();
                 let slot = Arc::new(atomic::AtomicCell::new(None));
                 let manifests = Arc::clone(&self.manifests);
                 let slot_ = Arc::clone(&slot);
@@ -422,10 +414,10 @@ where
                         //
                         // Our new figure maximum is constrained to at most 2^8 × 2^8 × 2^8.
                         // This uses at most 24 bits to store every vertex exactly once.
-                        // Greedy meshing can store each vertex in up to 3 quads, we have 3
+                        // Greedy meshing can store each vertex in up to 3 quads, we have 4
                         // greedy models, and we store 1.5x the vertex count, so the maximum
-                        // total space a model can take up is 3 * 3 * 1.5 * 2^24; rounding
-                        // up to 4 * 4 * 2^24 gets us to 2^28, which clearly still fits in a
+                        // total space a model can take up is 3 * 4 * 1.5 * 2^24; rounding
+                        // up to 4 * 8 * 2^24 gets us to 2^29, which clearly still fits in a
                         // u32.
                         //
                         // (We could also, though we prefer not to, reason backwards from the
@@ -500,10 +492,34 @@ where
                         (opaque, bounds)
                     }
 
+                    // Barely more than a silhouette, only meant to be seen from far away
+                    fn generate_mesh_lod_min<'a>(
+                        greedy: &mut GreedyMesh<'a>,
+                        opaque_mesh: &mut Mesh<TerrainPipeline>,
+                        segment: &'a Segment,
+                        offset: Vec3<f32>,
+                        bone_idx: u8,
+                    ) -> BoneMeshes {
+                        let lod_scale = 0.15;
+                        let (opaque, _, _, bounds) =
+                            Meshable::<FigurePipeline, &mut GreedyMesh>::generate_mesh(
+                                segment.scaled_by(Vec3::broadcast(lod_scale)),
+                                (
+                                    greedy,
+                                    opaque_mesh,
+                                    offset * lod_scale,
+                                    Vec3::one() / lod_scale,
+                                    bone_idx,
+                                ),
+                            );
+                        (opaque, bounds)
+                    }
+
                     let models = [
                         make_model(generate_mesh),
                         make_model(generate_mesh_lod_mid),
                         make_model(generate_mesh_lod_low),
+                        make_model(generate_mesh_lod_min),
                     ];
 
                     slot_.store(Some(MeshWorkerResponse {
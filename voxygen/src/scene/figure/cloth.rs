@@ -0,0 +1,95 @@
+use anim::vek::*;
+
+/// Number of simulated points along the cloth, including the pinned one
+const POINT_COUNT: usize = 4;
+/// Distance between neighbouring points
+const SEGMENT_LEN: f32 = 0.3;
+/// Share of its velocity a point keeps from one step to the next
+const DAMPING: f32 = 0.96;
+const GRAVITY: f32 = 30.0;
+/// Number of times per step the distance constraints are resolved
+const CONSTRAINT_ITERATIONS: usize = 3;
+/// Past this distance from its anchor the cloth is reset rather than
+/// simulated, which happens after teleporting
+const RESET_DIST: f32 = 5.0;
+
+#[derive(Clone, Copy)]
+struct Point {
+    pos: Vec3<f32>,
+    old_pos: Vec3<f32>,
+}
+
+/// A strip of cloth hanging from a single point, like a cape, simulated as a
+/// chain of points with verlet integration.
+pub struct Cloth {
+    points: [Point; POINT_COUNT],
+}
+
+impl Cloth {
+    /// Creates cloth hanging straight down from `anchor`
+    pub fn new(anchor: Vec3<f32>) -> Self {
+        let mut points = [Point {
+            pos: anchor,
+            old_pos: anchor,
+        }; POINT_COUNT];
+        for (i, point) in points.iter_mut().enumerate() {
+            point.pos.z -= i as f32 * SEGMENT_LEN;
+            point.old_pos = point.pos;
+        }
+        Self { points }
+    }
+
+    /// Moves the pinned point to `anchor` and lets the rest of the cloth
+    /// follow, pushed around by `wind`
+    pub fn update(&mut self, anchor: Vec3<f32>, wind: Vec3<f32>, dt: f32) {
+        if self.points[0].pos.distance_squared(anchor) > RESET_DIST.powi(2) {
+            *self = Self::new(anchor);
+            return;
+        }
+        // Large steps make the simulation explode, so lag behind instead
+        let dt = dt.min(0.05);
+
+        self.points[0] = Point {
+            pos: anchor,
+            old_pos: anchor,
+        };
+        let accel = wind - Vec3::unit_z() * GRAVITY;
+        for point in self.points.iter_mut().skip(1) {
+            let vel = (point.pos - point.old_pos) * DAMPING;
+            point.old_pos = point.pos;
+            point.pos += vel + accel * dt * dt;
+        }
+
+        for _ in 0..CONSTRAINT_ITERATIONS {
+            for i in 0..POINT_COUNT - 1 {
+                let delta = self.points[i + 1].pos - self.points[i].pos;
+                let dist = delta.magnitude().max(0.001);
+                let correction = delta * (dist - SEGMENT_LEN) / dist;
+                if i == 0 {
+                    // The anchor doesn't move
+                    self.points[i + 1].pos -= correction;
+                } else {
+                    self.points[i].pos += correction * 0.5;
+                    self.points[i + 1].pos -= correction * 0.5;
+                }
+            }
+        }
+    }
+
+    /// Direction from the anchor to the end of the cloth
+    pub fn hang_dir(&self) -> Vec3<f32> {
+        (self.points[POINT_COUNT - 1].pos - self.points[0].pos)
+            .try_normalized()
+            .unwrap_or(-Vec3::unit_z())
+    }
+}
+
+/// Gentle gusts of wind that change direction over time
+pub fn wind(time: f64) -> Vec3<f32> {
+    let time = time as f32;
+    Vec3::new(
+        (time * 0.3).sin() + (time * 1.7).sin() * 0.4,
+        (time * 0.2).cos() + (time * 1.3).cos() * 0.4,
+        0.0,
+    ) * 3.0
+}
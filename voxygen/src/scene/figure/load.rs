@@ -1,10 +1,12 @@
-use super::cache::FigureKey;
+use super::cache::{ArmorLook, FigureKey};
 use common::{
     assets::{self, watch::ReloadIndicator, Asset, AssetWith, Ron},
     comp::{
+        arthropod::{self, BodyType as ABodyType, Species as ASpecies},
         biped_large::{self, BodyType as BLBodyType, Species as BLSpecies},
         bird_medium::{self, BodyType as BMBodyType, Species as BMSpecies},
         bird_small,
+        colossus::{self, BodyType as CBodyType, Species as CSpecies},
         dragon::{self, BodyType as DBodyType, Species as DSpecies},
         fish_medium, fish_small,
         golem::{self, BodyType as GBodyType, Species as GSpecies},
@@ -15,6 +17,7 @@ use common::{
         quadruped_medium::{self, BodyType as QMBodyType, Species as QMSpecies},
         quadruped_small::{self, BodyType as QSBodyType, Species as QSSpecies},
         theropod::{self, BodyType as TBodyType, Species as TSpecies},
+        turret::{self, Species as TuSpecies},
     },
     figure::{DynaUnionizer, MatSegment, Material, Segment},
 };
@@ -72,6 +75,20 @@ fn recolor_grey(rgb: Rgb<u8>, color: Rgb<u8>) -> Rgb<u8> {
     }
 }
 
+/// Dyes a color by recoloring its brightness, so the shading of the armor is
+/// kept
+fn recolor_dyed(rgb: Rgb<u8>, color: Rgb<u8>) -> Rgb<u8> {
+    let grey = rgb.r as f32 * 0.299 + rgb.g as f32 * 0.587 + rgb.b as f32 * 0.114;
+    recolor_grey(Rgb::broadcast(grey as u8), color)
+}
+
+fn dye_segment(segment: MatSegment, dye: Option<Rgb<u8>>) -> MatSegment {
+    match dye {
+        Some(dye) => segment.map_rgb(|rgb| recolor_dyed(rgb, dye)),
+        None => segment,
+    }
+}
+
 /// A set of reloadable specifications for a Body.
 pub trait BodySpec: Sized {
     type Spec;
@@ -321,7 +338,16 @@ struct HumArmorHandSpec(ArmorVoxSpecMap<String, SidedArmorVoxSpec>);
 #[derive(Deserialize)]
 struct HumArmorBeltSpec(ArmorVoxSpecMap<String, ArmorVoxSpec>);
 #[derive(Deserialize)]
-struct HumArmorBackSpec(ArmorVoxSpecMap<String, ArmorVoxSpec>);
+struct BackVoxSpec {
+    vox_spec: VoxSpec<f32>,
+    color: Option<[u8; 3]>,
+    /// Whether the piece sways with movement and wind like a cape, rather than
+    /// staying put like a backpack
+    #[serde(default)]
+    cloth: bool,
+}
+#[derive(Deserialize)]
+struct HumArmorBackSpec(ArmorVoxSpecMap<String, BackVoxSpec>);
 #[derive(Deserialize)]
 struct HumArmorPantsSpec(ArmorVoxSpecMap<String, ArmorVoxSpec>);
 #[derive(Deserialize)]
@@ -372,8 +398,8 @@ make_vox_spec!(
         let tool = loadout.tool.as_ref();
         let lantern = loadout.lantern.as_deref();
         let glider = loadout.glider.as_deref();
-        let hand = loadout.hand.as_deref();
-        let foot = loadout.foot.as_deref();
+        let hand = loadout.hand.as_ref();
+        let foot = loadout.foot.as_ref();
 
         [
             third_person.map(|_| {
@@ -386,28 +412,28 @@ make_vox_spec!(
                 spec.armor_chest.asset.mesh_chest(
                     body,
                     &spec.color.asset,
-                    loadout.chest.as_deref(),
+                    loadout.chest.as_ref(),
                 )
             }),
             third_person.map(|loadout| {
                 spec.armor_belt.asset.mesh_belt(
                     body,
                     &spec.color.asset,
-                    loadout.belt.as_deref(),
+                    loadout.belt.as_ref(),
                 )
             }),
             third_person.map(|loadout| {
                 spec.armor_back.asset.mesh_back(
                     body,
                     &spec.color.asset,
-                    loadout.back.as_deref(),
+                    loadout.back.as_ref(),
                 )
             }),
             third_person.map(|loadout| {
                 spec.armor_pants.asset.mesh_pants(
                     body,
                     &spec.color.asset,
-                    loadout.pants.as_deref(),
+                    loadout.pants.as_ref(),
                 )
             }),
             Some(spec.armor_hand.asset.mesh_left_hand(
@@ -434,14 +460,14 @@ make_vox_spec!(
                 spec.armor_shoulder.asset.mesh_left_shoulder(
                     body,
                     &spec.color.asset,
-                    loadout.shoulder.as_deref(),
+                    loadout.shoulder.as_ref(),
                 )
             }),
             third_person.map(|loadout| {
                 spec.armor_shoulder.asset.mesh_right_shoulder(
                     body,
                     &spec.color.asset,
-                    loadout.shoulder.as_deref(),
+                    loadout.shoulder.as_ref(),
                 )
             }),
             Some(spec.armor_glider.asset.mesh_glider(
@@ -477,10 +503,11 @@ impl HumArmorShoulderSpec {
         &self,
         body: &Body,
         color_spec: &HumColorSpec,
-        shoulder: Option<&str>,
+        shoulder: Option<&ArmorLook>,
         flipped: bool,
     ) -> BoneMeshes {
-        let spec = if let Some(shoulder) = shoulder {
+        let dye = shoulder.and_then(|shoulder| shoulder.dye);
+        let spec = if let Some(shoulder) = shoulder.map(|shoulder| &shoulder.kind) {
             match self.0.map.get(shoulder) {
                 Some(spec) => spec,
                 None => {
@@ -493,11 +520,14 @@ impl HumArmorShoulderSpec {
         };
 
         let mut shoulder_segment = color_spec.color_segment(
-            if flipped {
-                graceful_load_mat_segment_flipped(&spec.left.vox_spec.0)
-            } else {
-                graceful_load_mat_segment(&spec.right.vox_spec.0)
-            },
+            dye_segment(
+                if flipped {
+                    graceful_load_mat_segment_flipped(&spec.left.vox_spec.0)
+                } else {
+                    graceful_load_mat_segment(&spec.right.vox_spec.0)
+                },
+                dye,
+            ),
             body.species.skin_color(body.skin),
             color_spec.hair_color(body.species, body.hair_color),
             body.species.eye_color(body.eye_color),
@@ -531,7 +561,7 @@ impl HumArmorShoulderSpec {
         &self,
         body: &Body,
         color_spec: &HumColorSpec,
-        shoulder: Option<&str>,
+        shoulder: Option<&ArmorLook>,
     ) -> BoneMeshes {
         self.mesh_shoulder(body, color_spec, shoulder, true)
     }
@@ -540,7 +570,7 @@ impl HumArmorShoulderSpec {
         &self,
         body: &Body,
         color_spec: &HumColorSpec,
-        shoulder: Option<&str>,
+        shoulder: Option<&ArmorLook>,
     ) -> BoneMeshes {
         self.mesh_shoulder(body, color_spec, shoulder, false)
     }
@@ -551,9 +581,10 @@ impl HumArmorChestSpec {
         &self,
         body: &Body,
         color_spec: &HumColorSpec,
-        chest: Option<&str>,
+        chest: Option<&ArmorLook>,
     ) -> BoneMeshes {
-        let spec = if let Some(chest) = chest {
+        let dye = chest.and_then(|chest| chest.dye);
+        let spec = if let Some(chest) = chest.map(|chest| &chest.kind) {
             match self.0.map.get(chest) {
                 Some(spec) => spec,
                 None => {
@@ -576,7 +607,7 @@ impl HumArmorChestSpec {
 
         let bare_chest = graceful_load_mat_segment("armor.empty");
 
-        let mut chest_armor = graceful_load_mat_segment(&spec.vox_spec.0);
+        let mut chest_armor = dye_segment(graceful_load_mat_segment(&spec.vox_spec.0), dye);
 
         if let Some(color) = spec.color {
             let chest_color = Vec3::from(color);
@@ -598,10 +629,11 @@ impl HumArmorHandSpec {
         &self,
         body: &Body,
         color_spec: &HumColorSpec,
-        hand: Option<&str>,
+        hand: Option<&ArmorLook>,
         flipped: bool,
     ) -> BoneMeshes {
-        let spec = if let Some(hand) = hand {
+        let dye = hand.and_then(|hand| hand.dye);
+        let spec = if let Some(hand) = hand.map(|hand| &hand.kind) {
             match self.0.map.get(hand) {
                 Some(spec) => spec,
                 None => {
@@ -614,11 +646,14 @@ impl HumArmorHandSpec {
         };
 
         let mut hand_segment = color_spec.color_segment(
-            if flipped {
-                graceful_load_mat_segment_flipped(&spec.left.vox_spec.0)
-            } else {
-                graceful_load_mat_segment(&spec.right.vox_spec.0)
-            },
+            dye_segment(
+                if flipped {
+                    graceful_load_mat_segment_flipped(&spec.left.vox_spec.0)
+                } else {
+                    graceful_load_mat_segment(&spec.right.vox_spec.0)
+                },
+                dye,
+            ),
             body.species.skin_color(body.skin),
             color_spec.hair_color(body.species, body.hair_color),
             body.species.eye_color(body.eye_color),
@@ -646,7 +681,7 @@ impl HumArmorHandSpec {
         &self,
         body: &Body,
         color_spec: &HumColorSpec,
-        hand: Option<&str>,
+        hand: Option<&ArmorLook>,
     ) -> BoneMeshes {
         self.mesh_hand(body, color_spec, hand, true)
     }
@@ -655,15 +690,21 @@ impl HumArmorHandSpec {
         &self,
         body: &Body,
         color_spec: &HumColorSpec,
-        hand: Option<&str>,
+        hand: Option<&ArmorLook>,
     ) -> BoneMeshes {
         self.mesh_hand(body, color_spec, hand, false)
     }
 }
 // Belt
 impl HumArmorBeltSpec {
-    fn mesh_belt(&self, body: &Body, color_spec: &HumColorSpec, belt: Option<&str>) -> BoneMeshes {
-        let spec = if let Some(belt) = belt {
+    fn mesh_belt(
+        &self,
+        body: &Body,
+        color_spec: &HumColorSpec,
+        belt: Option<&ArmorLook>,
+    ) -> BoneMeshes {
+        let dye = belt.and_then(|belt| belt.dye);
+        let spec = if let Some(belt) = belt.map(|belt| &belt.kind) {
             match self.0.map.get(belt) {
                 Some(spec) => spec,
                 None => {
@@ -676,7 +717,7 @@ impl HumArmorBeltSpec {
         };
 
         let mut belt_segment = color_spec.color_segment(
-            graceful_load_mat_segment(&spec.vox_spec.0),
+            dye_segment(graceful_load_mat_segment(&spec.vox_spec.0), dye),
             body.species.skin_color(body.skin),
             color_spec.hair_color(body.species, body.hair_color),
             body.species.eye_color(body.eye_color),
@@ -690,10 +731,25 @@ impl HumArmorBeltSpec {
         (belt_segment, Vec3::from(spec.vox_spec.1))
     }
 }
+/// Whether the back armor of this kind is cloth that should be simulated
+pub fn back_is_cloth(kind: &str) -> bool {
+    Ron::<HumArmorBackSpec>::load_expect("voxygen.voxel.humanoid_armor_back_manifest")
+        .0
+        .map
+        .get(kind)
+        .map_or(false, |spec| spec.cloth)
+}
+
 // Cape
 impl HumArmorBackSpec {
-    fn mesh_back(&self, body: &Body, color_spec: &HumColorSpec, back: Option<&str>) -> BoneMeshes {
-        let spec = if let Some(back) = back {
+    fn mesh_back(
+        &self,
+        body: &Body,
+        color_spec: &HumColorSpec,
+        back: Option<&ArmorLook>,
+    ) -> BoneMeshes {
+        let dye = back.and_then(|back| back.dye);
+        let spec = if let Some(back) = back.map(|back| &back.kind) {
             match self.0.map.get(back) {
                 Some(spec) => spec,
                 None => {
@@ -706,7 +762,7 @@ impl HumArmorBackSpec {
         };
 
         let mut back_segment = color_spec.color_segment(
-            graceful_load_mat_segment(&spec.vox_spec.0),
+            dye_segment(graceful_load_mat_segment(&spec.vox_spec.0), dye),
             body.species.skin_color(body.skin),
             color_spec.hair_color(body.species, body.hair_color),
             body.species.eye_color(body.eye_color),
@@ -725,9 +781,10 @@ impl HumArmorPantsSpec {
         &self,
         body: &Body,
         color_spec: &HumColorSpec,
-        pants: Option<&str>,
+        pants: Option<&ArmorLook>,
     ) -> BoneMeshes {
-        let spec = if let Some(pants) = pants {
+        let dye = pants.and_then(|pants| pants.dye);
+        let spec = if let Some(pants) = pants.map(|pants| &pants.kind) {
             match self.0.map.get(pants) {
                 Some(spec) => spec,
                 None => {
@@ -750,7 +807,7 @@ impl HumArmorPantsSpec {
 
         let bare_pants = graceful_load_mat_segment("armor.empty");
 
-        let mut pants_armor = graceful_load_mat_segment(&spec.vox_spec.0);
+        let mut pants_armor = dye_segment(graceful_load_mat_segment(&spec.vox_spec.0), dye);
 
         if let Some(color) = spec.color {
             let pants_color = Vec3::from(color);
@@ -772,10 +829,11 @@ impl HumArmorFootSpec {
         &self,
         body: &Body,
         color_spec: &HumColorSpec,
-        foot: Option<&str>,
+        foot: Option<&ArmorLook>,
         flipped: bool,
     ) -> BoneMeshes {
-        let spec = if let Some(foot) = foot {
+        let dye = foot.and_then(|foot| foot.dye);
+        let spec = if let Some(foot) = foot.map(|foot| &foot.kind) {
             match self.0.map.get(foot) {
                 Some(spec) => spec,
                 None => {
@@ -788,11 +846,14 @@ impl HumArmorFootSpec {
         };
 
         let mut foot_segment = color_spec.color_segment(
-            if flipped {
-                graceful_load_mat_segment_flipped(&spec.vox_spec.0)
-            } else {
-                graceful_load_mat_segment(&spec.vox_spec.0)
-            },
+            dye_segment(
+                if flipped {
+                    graceful_load_mat_segment_flipped(&spec.vox_spec.0)
+                } else {
+                    graceful_load_mat_segment(&spec.vox_spec.0)
+                },
+                dye,
+            ),
             body.species.skin_color(body.skin),
             color_spec.hair_color(body.species, body.hair_color),
             body.species.eye_color(body.eye_color),
@@ -810,7 +871,7 @@ impl HumArmorFootSpec {
         &self,
         body: &Body,
         color_spec: &HumColorSpec,
-        foot: Option<&str>,
+        foot: Option<&ArmorLook>,
     ) -> BoneMeshes {
         self.mesh_foot(body, color_spec, foot, true)
     }
@@ -819,7 +880,7 @@ impl HumArmorFootSpec {
         &self,
         body: &Body,
         color_spec: &HumColorSpec,
-        foot: Option<&str>,
+        foot: Option<&ArmorLook>,
     ) -> BoneMeshes {
         self.mesh_foot(body, color_spec, foot, false)
     }
@@ -1261,11 +1322,17 @@ struct QuadrupedMediumLateralSubSpec {
     lateral: VoxSimple,
 }
 
+type QuadrupedMediumAttrSpec =
+    anim::SkeletonAttrManifest<(QMSpecies, QMBodyType), anim::quadruped_medium::SkeletonAttr>;
+
 make_vox_spec!(
     quadruped_medium::Body,
     struct QuadrupedMediumSpec {
         central: QuadrupedMediumCentralSpec = "voxygen.voxel.quadruped_medium_central_manifest",
         lateral: QuadrupedMediumLateralSpec = "voxygen.voxel.quadruped_medium_lateral_manifest",
+        // Not used for meshing, only watched so that the figure cache (and with it
+        // the skeleton attributes) is rebuilt when the attributes change
+        attr: QuadrupedMediumAttrSpec = "voxygen.voxel.quadruped_medium_attr_manifest",
     },
     |FigureKey { body, .. }, spec| {
         [
@@ -3278,6 +3345,782 @@ impl GolemLateralSpec {
     }
 }
 
+////
+#[derive(Deserialize)]
+struct ColossusCentralSpec(HashMap<(CSpecies, CBodyType), SidedCCentralVoxSpec>);
+
+#[derive(Deserialize)]
+struct SidedCCentralVoxSpec {
+    head: ColossusCentralSubSpec,
+    torso_upper: ColossusCentralSubSpec,
+    torso_lower: ColossusCentralSubSpec,
+    armor_chest: ColossusCentralSubSpec,
+}
+#[derive(Deserialize)]
+struct ColossusCentralSubSpec {
+    offset: [f32; 3], // Should be relative to initial origin
+    central: VoxSimple,
+}
+
+#[derive(Deserialize)]
+struct ColossusLateralSpec(HashMap<(CSpecies, CBodyType), SidedCLateralVoxSpec>);
+
+#[derive(Deserialize)]
+struct SidedCLateralVoxSpec {
+    armor_l: ColossusLateralSubSpec,
+    armor_r: ColossusLateralSubSpec,
+    upper_arm_l: ColossusLateralSubSpec,
+    upper_arm_r: ColossusLateralSubSpec,
+    forearm_l: ColossusLateralSubSpec,
+    forearm_r: ColossusLateralSubSpec,
+    hand_l: ColossusLateralSubSpec,
+    hand_r: ColossusLateralSubSpec,
+    leg_l: ColossusLateralSubSpec,
+    leg_r: ColossusLateralSubSpec,
+    foot_l: ColossusLateralSubSpec,
+    foot_r: ColossusLateralSubSpec,
+}
+#[derive(Deserialize)]
+struct ColossusLateralSubSpec {
+    offset: [f32; 3], // Should be relative to initial origin
+    lateral: VoxSimple,
+}
+
+make_vox_spec!(
+    colossus::Body,
+    struct ColossusSpec {
+        central: ColossusCentralSpec = "voxygen.voxel.colossus_central_manifest",
+        lateral: ColossusLateralSpec = "voxygen.voxel.colossus_lateral_manifest",
+    },
+    |FigureKey { body, .. }, spec| {
+        [
+            Some(spec.central.asset.mesh_head(
+                body.species,
+                body.body_type,
+            )),
+            Some(spec.central.asset.mesh_torso_upper(
+                body.species,
+                body.body_type,
+            )),
+            Some(spec.central.asset.mesh_torso_lower(
+                body.species,
+                body.body_type,
+            )),
+            Some(spec.central.asset.mesh_armor_chest(
+                body.species,
+                body.body_type,
+            )),
+            Some(spec.lateral.asset.mesh_armor_l(
+                body.species,
+                body.body_type,
+            )),
+            Some(spec.lateral.asset.mesh_armor_r(
+                body.species,
+                body.body_type,
+            )),
+            Some(spec.lateral.asset.mesh_upper_arm_l(
+                body.species,
+                body.body_type,
+            )),
+            Some(spec.lateral.asset.mesh_upper_arm_r(
+                body.species,
+                body.body_type,
+            )),
+            Some(spec.lateral.asset.mesh_forearm_l(
+                body.species,
+                body.body_type,
+            )),
+            Some(spec.lateral.asset.mesh_forearm_r(
+                body.species,
+                body.body_type,
+            )),
+            Some(spec.lateral.asset.mesh_hand_l(
+                body.species,
+                body.body_type,
+            )),
+            Some(spec.lateral.asset.mesh_hand_r(
+                body.species,
+                body.body_type,
+            )),
+            Some(spec.lateral.asset.mesh_leg_l(
+                body.species,
+                body.body_type,
+            )),
+            Some(spec.lateral.asset.mesh_leg_r(
+                body.species,
+                body.body_type,
+            )),
+            Some(spec.lateral.asset.mesh_foot_l(
+                body.species,
+                body.body_type,
+            )),
+            Some(spec.lateral.asset.mesh_foot_r(
+                body.species,
+                body.body_type,
+            )),
+        ]
+    },
+);
+
+impl ColossusCentralSpec {
+    fn mesh_head(&self, species: CSpecies, body_type: CBodyType) -> BoneMeshes {
+        let spec = match self.0.get(&(species, body_type)) {
+            Some(spec) => spec,
+            None => {
+                error!(
+                    "No head specification exists for the combination of {:?} and {:?}",
+                    species, body_type
+                );
+                return load_mesh("not_found", Vec3::new(-5.0, -5.0, -2.5));
+            },
+        };
+        let central = graceful_load_segment(&spec.head.central.0);
+
+        (central, Vec3::from(spec.head.offset))
+    }
+
+    fn mesh_torso_upper(&self, species: CSpecies, body_type: CBodyType) -> BoneMeshes {
+        let spec = match self.0.get(&(species, body_type)) {
+            Some(spec) => spec,
+            None => {
+                error!(
+                    "No torso upper specification exists for the combination of {:?} and {:?}",
+                    species, body_type
+                );
+                return load_mesh("not_found", Vec3::new(-5.0, -5.0, -2.5));
+            },
+        };
+        let central = graceful_load_segment(&spec.torso_upper.central.0);
+
+        (central, Vec3::from(spec.torso_upper.offset))
+    }
+
+    fn mesh_torso_lower(&self, species: CSpecies, body_type: CBodyType) -> BoneMeshes {
+        let spec = match self.0.get(&(species, body_type)) {
+            Some(spec) => spec,
+            None => {
+                error!(
+                    "No torso lower specification exists for the combination of {:?} and {:?}",
+                    species, body_type
+                );
+                return load_mesh("not_found", Vec3::new(-5.0, -5.0, -2.5));
+            },
+        };
+        let central = graceful_load_segment(&spec.torso_lower.central.0);
+
+        (central, Vec3::from(spec.torso_lower.offset))
+    }
+
+    fn mesh_armor_chest(&self, species: CSpecies, body_type: CBodyType) -> BoneMeshes {
+        let spec = match self.0.get(&(species, body_type)) {
+            Some(spec) => spec,
+            None => {
+                error!(
+                    "No chest armor specification exists for the combination of {:?} and {:?}",
+                    species, body_type
+                );
+                return load_mesh("not_found", Vec3::new(-5.0, -5.0, -2.5));
+            },
+        };
+        let central = graceful_load_segment(&spec.armor_chest.central.0);
+
+        (central, Vec3::from(spec.armor_chest.offset))
+    }
+}
+impl ColossusLateralSpec {
+    fn mesh_armor_l(&self, species: CSpecies, body_type: CBodyType) -> BoneMeshes {
+        let spec = match self.0.get(&(species, body_type)) {
+            Some(spec) => spec,
+            None => {
+                error!(
+                    "No shoulder armor specification exists for the combination of {:?} and {:?}",
+                    species, body_type
+                );
+                return load_mesh("not_found", Vec3::new(-5.0, -5.0, -2.5));
+            },
+        };
+        let lateral = graceful_load_segment(&spec.armor_l.lateral.0);
+
+        (lateral, Vec3::from(spec.armor_l.offset))
+    }
+
+    fn mesh_armor_r(&self, species: CSpecies, body_type: CBodyType) -> BoneMeshes {
+        let spec = match self.0.get(&(species, body_type)) {
+            Some(spec) => spec,
+            None => {
+                error!(
+                    "No shoulder armor specification exists for the combination of {:?} and {:?}",
+                    species, body_type
+                );
+                return load_mesh("not_found", Vec3::new(-5.0, -5.0, -2.5));
+            },
+        };
+        let lateral = graceful_load_segment(&spec.armor_r.lateral.0);
+
+        (lateral, Vec3::from(spec.armor_r.offset))
+    }
+
+    fn mesh_upper_arm_l(&self, species: CSpecies, body_type: CBodyType) -> BoneMeshes {
+        let spec = match self.0.get(&(species, body_type)) {
+            Some(spec) => spec,
+            None => {
+                error!(
+                    "No upper arm specification exists for the combination of {:?} and {:?}",
+                    species, body_type
+                );
+                return load_mesh("not_found", Vec3::new(-5.0, -5.0, -2.5));
+            },
+        };
+        let lateral = graceful_load_segment(&spec.upper_arm_l.lateral.0);
+
+        (lateral, Vec3::from(spec.upper_arm_l.offset))
+    }
+
+    fn mesh_upper_arm_r(&self, species: CSpecies, body_type: CBodyType) -> BoneMeshes {
+        let spec = match self.0.get(&(species, body_type)) {
+            Some(spec) => spec,
+            None => {
+                error!(
+                    "No upper arm specification exists for the combination of {:?} and {:?}",
+                    species, body_type
+                );
+                return load_mesh("not_found", Vec3::new(-5.0, -5.0, -2.5));
+            },
+        };
+        let lateral = graceful_load_segment(&spec.upper_arm_r.lateral.0);
+
+        (lateral, Vec3::from(spec.upper_arm_r.offset))
+    }
+
+    fn mesh_forearm_l(&self, species: CSpecies, body_type: CBodyType) -> BoneMeshes {
+        let spec = match self.0.get(&(species, body_type)) {
+            Some(spec) => spec,
+            None => {
+                error!(
+                    "No forearm specification exists for the combination of {:?} and {:?}",
+                    species, body_type
+                );
+                return load_mesh("not_found", Vec3::new(-5.0, -5.0, -2.5));
+            },
+        };
+        let lateral = graceful_load_segment(&spec.forearm_l.lateral.0);
+
+        (lateral, Vec3::from(spec.forearm_l.offset))
+    }
+
+    fn mesh_forearm_r(&self, species: CSpecies, body_type: CBodyType) -> BoneMeshes {
+        let spec = match self.0.get(&(species, body_type)) {
+            Some(spec) => spec,
+            None => {
+                error!(
+                    "No forearm specification exists for the combination of {:?} and {:?}",
+                    species, body_type
+                );
+                return load_mesh("not_found", Vec3::new(-5.0, -5.0, -2.5));
+            },
+        };
+        let lateral = graceful_load_segment(&spec.forearm_r.lateral.0);
+
+        (lateral, Vec3::from(spec.forearm_r.offset))
+    }
+
+    fn mesh_hand_l(&self, species: CSpecies, body_type: CBodyType) -> BoneMeshes {
+        let spec = match self.0.get(&(species, body_type)) {
+            Some(spec) => spec,
+            None => {
+                error!(
+                    "No hand specification exists for the combination of {:?} and {:?}",
+                    species, body_type
+                );
+                return load_mesh("not_found", Vec3::new(-5.0, -5.0, -2.5));
+            },
+        };
+        let lateral = graceful_load_segment(&spec.hand_l.lateral.0);
+
+        (lateral, Vec3::from(spec.hand_l.offset))
+    }
+
+    fn mesh_hand_r(&self, species: CSpecies, body_type: CBodyType) -> BoneMeshes {
+        let spec = match self.0.get(&(species, body_type)) {
+            Some(spec) => spec,
+            None => {
+                error!(
+                    "No hand specification exists for the combination of {:?} and {:?}",
+                    species, body_type
+                );
+                return load_mesh("not_found", Vec3::new(-5.0, -5.0, -2.5));
+            },
+        };
+        let lateral = graceful_load_segment(&spec.hand_r.lateral.0);
+
+        (lateral, Vec3::from(spec.hand_r.offset))
+    }
+
+    fn mesh_leg_l(&self, species: CSpecies, body_type: CBodyType) -> BoneMeshes {
+        let spec = match self.0.get(&(species, body_type)) {
+            Some(spec) => spec,
+            None => {
+                error!(
+                    "No leg specification exists for the combination of {:?} and {:?}",
+                    species, body_type
+                );
+                return load_mesh("not_found", Vec3::new(-5.0, -5.0, -2.5));
+            },
+        };
+        let lateral = graceful_load_segment(&spec.leg_l.lateral.0);
+
+        (lateral, Vec3::from(spec.leg_l.offset))
+    }
+
+    fn mesh_leg_r(&self, species: CSpecies, body_type: CBodyType) -> BoneMeshes {
+        let spec = match self.0.get(&(species, body_type)) {
+            Some(spec) => spec,
+            None => {
+                error!(
+                    "No leg specification exists for the combination of {:?} and {:?}",
+                    species, body_type
+                );
+                return load_mesh("not_found", Vec3::new(-5.0, -5.0, -2.5));
+            },
+        };
+        let lateral = graceful_load_segment(&spec.leg_r.lateral.0);
+
+        (lateral, Vec3::from(spec.leg_r.offset))
+    }
+
+    fn mesh_foot_l(&self, species: CSpecies, body_type: CBodyType) -> BoneMeshes {
+        let spec = match self.0.get(&(species, body_type)) {
+            Some(spec) => spec,
+            None => {
+                error!(
+                    "No foot specification exists for the combination of {:?} and {:?}",
+                    species, body_type
+                );
+                return load_mesh("not_found", Vec3::new(-5.0, -5.0, -2.5));
+            },
+        };
+        let lateral = graceful_load_segment(&spec.foot_l.lateral.0);
+
+        (lateral, Vec3::from(spec.foot_l.offset))
+    }
+
+    fn mesh_foot_r(&self, species: CSpecies, body_type: CBodyType) -> BoneMeshes {
+        let spec = match self.0.get(&(species, body_type)) {
+            Some(spec) => spec,
+            None => {
+                error!(
+                    "No foot specification exists for the combination of {:?} and {:?}",
+                    species, body_type
+                );
+                return load_mesh("not_found", Vec3::new(-5.0, -5.0, -2.5));
+            },
+        };
+        let lateral = graceful_load_segment(&spec.foot_r.lateral.0);
+
+        (lateral, Vec3::from(spec.foot_r.offset))
+    }
+}
+
+////
+#[derive(Deserialize)]
+struct ArthropodCentralSpec(HashMap<(ASpecies, ABodyType), SidedACentralVoxSpec>);
+
+#[derive(Deserialize)]
+struct SidedACentralVoxSpec {
+    head: ArthropodCentralSubSpec,
+    mandible_l: ArthropodCentralSubSpec,
+    mandible_r: ArthropodCentralSubSpec,
+    chest: ArthropodCentralSubSpec,
+    abdomen: ArthropodCentralSubSpec,
+}
+#[derive(Deserialize)]
+struct ArthropodCentralSubSpec {
+    offset: [f32; 3], // Should be relative to initial origin
+    central: VoxSimple,
+}
+
+#[derive(Deserialize)]
+struct ArthropodLateralSpec(HashMap<(ASpecies, ABodyType), SidedALateralVoxSpec>);
+
+#[derive(Deserialize)]
+struct SidedALateralVoxSpec {
+    leg_fl: ArthropodLateralSubSpec,
+    leg_fcl: ArthropodLateralSubSpec,
+    leg_bcl: ArthropodLateralSubSpec,
+    leg_bl: ArthropodLateralSubSpec,
+    leg_fr: ArthropodLateralSubSpec,
+    leg_fcr: ArthropodLateralSubSpec,
+    leg_bcr: ArthropodLateralSubSpec,
+    leg_br: ArthropodLateralSubSpec,
+}
+#[derive(Deserialize)]
+struct ArthropodLateralSubSpec {
+    offset: [f32; 3], // Should be relative to initial origin
+    lateral: VoxSimple,
+}
+
+make_vox_spec!(
+    arthropod::Body,
+    struct ArthropodSpec {
+        central: ArthropodCentralSpec = "voxygen.voxel.arthropod_central_manifest",
+        lateral: ArthropodLateralSpec = "voxygen.voxel.arthropod_lateral_manifest",
+    },
+    |FigureKey { body, .. }, spec| {
+        [
+            Some(spec.central.asset.mesh_head(
+                body.species,
+                body.body_type,
+            )),
+            Some(spec.central.asset.mesh_mandible_l(
+                body.species,
+                body.body_type,
+            )),
+            Some(spec.central.asset.mesh_mandible_r(
+                body.species,
+                body.body_type,
+            )),
+            Some(spec.central.asset.mesh_chest(
+                body.species,
+                body.body_type,
+            )),
+            Some(spec.central.asset.mesh_abdomen(
+                body.species,
+                body.body_type,
+            )),
+            Some(spec.lateral.asset.mesh_leg_fl(
+                body.species,
+                body.body_type,
+            )),
+            Some(spec.lateral.asset.mesh_leg_fcl(
+                body.species,
+                body.body_type,
+            )),
+            Some(spec.lateral.asset.mesh_leg_bcl(
+                body.species,
+                body.body_type,
+            )),
+            Some(spec.lateral.asset.mesh_leg_bl(
+                body.species,
+                body.body_type,
+            )),
+            Some(spec.lateral.asset.mesh_leg_fr(
+                body.species,
+                body.body_type,
+            )),
+            Some(spec.lateral.asset.mesh_leg_fcr(
+                body.species,
+                body.body_type,
+            )),
+            Some(spec.lateral.asset.mesh_leg_bcr(
+                body.species,
+                body.body_type,
+            )),
+            Some(spec.lateral.asset.mesh_leg_br(
+                body.species,
+                body.body_type,
+            )),
+            None,
+            None,
+            None,
+        ]
+    },
+);
+
+impl ArthropodCentralSpec {
+    fn mesh_head(&self, species: ASpecies, body_type: ABodyType) -> BoneMeshes {
+        let spec = match self.0.get(&(species, body_type)) {
+            Some(spec) => spec,
+            None => {
+                error!(
+                    "No head specification exists for the combination of {:?} and {:?}",
+                    species, body_type
+                );
+                return load_mesh("not_found", Vec3::new(-5.0, -5.0, -2.5));
+            },
+        };
+        let central = graceful_load_segment(&spec.head.central.0);
+
+        (central, Vec3::from(spec.head.offset))
+    }
+
+    fn mesh_mandible_l(&self, species: ASpecies, body_type: ABodyType) -> BoneMeshes {
+        let spec = match self.0.get(&(species, body_type)) {
+            Some(spec) => spec,
+            None => {
+                error!(
+                    "No mandible specification exists for the combination of {:?} and {:?}",
+                    species, body_type
+                );
+                return load_mesh("not_found", Vec3::new(-5.0, -5.0, -2.5));
+            },
+        };
+        let central = graceful_load_segment(&spec.mandible_l.central.0);
+
+        (central, Vec3::from(spec.mandible_l.offset))
+    }
+
+    fn mesh_mandible_r(&self, species: ASpecies, body_type: ABodyType) -> BoneMeshes {
+        let spec = match self.0.get(&(species, body_type)) {
+            Some(spec) => spec,
+            None => {
+                error!(
+                    "No mandible specification exists for the combination of {:?} and {:?}",
+                    species, body_type
+                );
+                return load_mesh("not_found", Vec3::new(-5.0, -5.0, -2.5));
+            },
+        };
+        let central = graceful_load_segment(&spec.mandible_r.central.0);
+
+        (central, Vec3::from(spec.mandible_r.offset))
+    }
+
+    fn mesh_chest(&self, species: ASpecies, body_type: ABodyType) -> BoneMeshes {
+        let spec = match self.0.get(&(species, body_type)) {
+            Some(spec) => spec,
+            None => {
+                error!(
+                    "No chest specification exists for the combination of {:?} and {:?}",
+                    species, body_type
+                );
+                return load_mesh("not_found", Vec3::new(-5.0, -5.0, -2.5));
+            },
+        };
+        let central = graceful_load_segment(&spec.chest.central.0);
+
+        (central, Vec3::from(spec.chest.offset))
+    }
+
+    fn mesh_abdomen(&self, species: ASpecies, body_type: ABodyType) -> BoneMeshes {
+        let spec = match self.0.get(&(species, body_type)) {
+            Some(spec) => spec,
+            None => {
+                error!(
+                    "No abdomen specification exists for the combination of {:?} and {:?}",
+                    species, body_type
+                );
+                return load_mesh("not_found", Vec3::new(-5.0, -5.0, -2.5));
+            },
+        };
+        let central = graceful_load_segment(&spec.abdomen.central.0);
+
+        (central, Vec3::from(spec.abdomen.offset))
+    }
+}
+impl ArthropodLateralSpec {
+    fn mesh_leg_fl(&self, species: ASpecies, body_type: ABodyType) -> BoneMeshes {
+        let spec = match self.0.get(&(species, body_type)) {
+            Some(spec) => spec,
+            None => {
+                error!(
+                    "No leg specification exists for the combination of {:?} and {:?}",
+                    species, body_type
+                );
+                return load_mesh("not_found", Vec3::new(-5.0, -5.0, -2.5));
+            },
+        };
+        let lateral = graceful_load_segment(&spec.leg_fl.lateral.0);
+
+        (lateral, Vec3::from(spec.leg_fl.offset))
+    }
+
+    fn mesh_leg_fcl(&self, species: ASpecies, body_type: ABodyType) -> BoneMeshes {
+        let spec = match self.0.get(&(species, body_type)) {
+            Some(spec) => spec,
+            None => {
+                error!(
+                    "No leg specification exists for the combination of {:?} and {:?}",
+                    species, body_type
+                );
+                return load_mesh("not_found", Vec3::new(-5.0, -5.0, -2.5));
+            },
+        };
+        let lateral = graceful_load_segment(&spec.leg_fcl.lateral.0);
+
+        (lateral, Vec3::from(spec.leg_fcl.offset))
+    }
+
+    fn mesh_leg_bcl(&self, species: ASpecies, body_type: ABodyType) -> BoneMeshes {
+        let spec = match self.0.get(&(species, body_type)) {
+            Some(spec) => spec,
+            None => {
+                error!(
+                    "No leg specification exists for the combination of {:?} and {:?}",
+                    species, body_type
+                );
+                return load_mesh("not_found", Vec3::new(-5.0, -5.0, -2.5));
+            },
+        };
+        let lateral = graceful_load_segment(&spec.leg_bcl.lateral.0);
+
+        (lateral, Vec3::from(spec.leg_bcl.offset))
+    }
+
+    fn mesh_leg_bl(&self, species: ASpecies, body_type: ABodyType) -> BoneMeshes {
+        let spec = match self.0.get(&(species, body_type)) {
+            Some(spec) => spec,
+            None => {
+                error!(
+                    "No leg specification exists for the combination of {:?} and {:?}",
+                    species, body_type
+                );
+                return load_mesh("not_found", Vec3::new(-5.0, -5.0, -2.5));
+            },
+        };
+        let lateral = graceful_load_segment(&spec.leg_bl.lateral.0);
+
+        (lateral, Vec3::from(spec.leg_bl.offset))
+    }
+
+    fn mesh_leg_fr(&self, species: ASpecies, body_type: ABodyType) -> BoneMeshes {
+        let spec = match self.0.get(&(species, body_type)) {
+            Some(spec) => spec,
+            None => {
+                error!(
+                    "No leg specification exists for the combination of {:?} and {:?}",
+                    species, body_type
+                );
+                return load_mesh("not_found", Vec3::new(-5.0, -5.0, -2.5));
+            },
+        };
+        let lateral = graceful_load_segment(&spec.leg_fr.lateral.0);
+
+        (lateral, Vec3::from(spec.leg_fr.offset))
+    }
+
+    fn mesh_leg_fcr(&self, species: ASpecies, body_type: ABodyType) -> BoneMeshes {
+        let spec = match self.0.get(&(species, body_type)) {
+            Some(spec) => spec,
+            None => {
+                error!(
+                    "No leg specification exists for the combination of {:?} and {:?}",
+                    species, body_type
+                );
+                return load_mesh("not_found", Vec3::new(-5.0, -5.0, -2.5));
+            },
+        };
+        let lateral = graceful_load_segment(&spec.leg_fcr.lateral.0);
+
+        (lateral, Vec3::from(spec.leg_fcr.offset))
+    }
+
+    fn mesh_leg_bcr(&self, species: ASpecies, body_type: ABodyType) -> BoneMeshes {
+        let spec = match self.0.get(&(species, body_type)) {
+            Some(spec) => spec,
+            None => {
+                error!(
+                    "No leg specification exists for the combination of {:?} and {:?}",
+                    species, body_type
+                );
+                return load_mesh("not_found", Vec3::new(-5.0, -5.0, -2.5));
+            },
+        };
+        let lateral = graceful_load_segment(&spec.leg_bcr.lateral.0);
+
+        (lateral, Vec3::from(spec.leg_bcr.offset))
+    }
+
+    fn mesh_leg_br(&self, species: ASpecies, body_type: ABodyType) -> BoneMeshes {
+        let spec = match self.0.get(&(species, body_type)) {
+            Some(spec) => spec,
+            None => {
+                error!(
+                    "No leg specification exists for the combination of {:?} and {:?}",
+                    species, body_type
+                );
+                return load_mesh("not_found", Vec3::new(-5.0, -5.0, -2.5));
+            },
+        };
+        let lateral = graceful_load_segment(&spec.leg_br.lateral.0);
+
+        (lateral, Vec3::from(spec.leg_br.offset))
+    }
+}
+
+////
+#[derive(Deserialize)]
+struct TurretCentralSpec(HashMap<TuSpecies, SidedTuCentralVoxSpec>);
+
+#[derive(Deserialize)]
+struct SidedTuCentralVoxSpec {
+    base: TurretCentralSubSpec,
+    swivel: TurretCentralSubSpec,
+    barrel: TurretCentralSubSpec,
+}
+#[derive(Deserialize)]
+struct TurretCentralSubSpec {
+    offset: [f32; 3], // Should be relative to initial origin
+    central: VoxSimple,
+}
+
+make_vox_spec!(
+    turret::Body,
+    struct TurretSpec {
+        central: TurretCentralSpec = "voxygen.voxel.turret_central_manifest",
+    },
+    |FigureKey { body, .. }, spec| {
+        [
+            Some(spec.central.asset.mesh_base(body.species)),
+            Some(spec.central.asset.mesh_swivel(body.species)),
+            Some(spec.central.asset.mesh_barrel(body.species)),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        ]
+    },
+);
+
+impl TurretCentralSpec {
+    fn mesh_base(&self, species: TuSpecies) -> BoneMeshes {
+        let spec = match self.0.get(&species) {
+            Some(spec) => spec,
+            None => {
+                error!("No base specification exists for {:?}", species);
+                return load_mesh("not_found", Vec3::new(-5.0, -5.0, -2.5));
+            },
+        };
+        let central = graceful_load_segment(&spec.base.central.0);
+
+        (central, Vec3::from(spec.base.offset))
+    }
+
+    fn mesh_swivel(&self, species: TuSpecies) -> BoneMeshes {
+        let spec = match self.0.get(&species) {
+            Some(spec) => spec,
+            None => {
+                error!("No swivel specification exists for {:?}", species);
+                return load_mesh("not_found", Vec3::new(-5.0, -5.0, -2.5));
+            },
+        };
+        let central = graceful_load_segment(&spec.swivel.central.0);
+
+        (central, Vec3::from(spec.swivel.offset))
+    }
+
+    fn mesh_barrel(&self, species: TuSpecies) -> BoneMeshes {
+        let spec = match self.0.get(&species) {
+            Some(spec) => spec,
+            None => {
+                error!("No barrel specification exists for {:?}", species);
+                return load_mesh("not_found", Vec3::new(-5.0, -5.0, -2.5));
+            },
+        };
+        let central = graceful_load_segment(&spec.barrel.central.0);
+
+        (central, Vec3::from(spec.barrel.offset))
+    }
+}
+
 /////
 
 #[derive(Deserialize)]
@@ -3650,6 +4493,8 @@ fn mesh_object(obj: &object::Body) -> BoneMeshes {
         Body::TrainingDummy => ("object.training_dummy", Vec3::new(-7.0, -5.0, 0.0)),
         Body::MultiArrow => ("weapon.projectile.multi-arrow", Vec3::new(-4.0, -9.5, -5.0)),
         Body::BoltNature => ("weapon.projectile.nature-bolt", Vec3::new(-6.0, -6.0, -6.0)),
+        // TODO: Give carts a model of their own
+        Body::Cart => ("object.crate", Vec3::new(-7.0, -7.0, 0.0)),
     };
     load_mesh(name, offset)
 }
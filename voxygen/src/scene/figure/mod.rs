@@ -1,14 +1,16 @@
 mod cache;
+mod cloth;
 pub mod load;
 
 pub use cache::FigureModelCache;
+use cloth::Cloth;
 pub use load::load_mesh; // TODO: Don't make this public.
 
 use crate::{
     ecs::comp::Interpolated,
     render::{
         ColLightFmt, ColLightInfo, Consts, FigureBoneData, FigureLocals, FigureModel, GlobalModel,
-        Mesh, RenderError, Renderer, ShadowPipeline, TerrainPipeline, Texture,
+        Mesh, Outline, RenderError, Renderer, ShadowPipeline, TerrainPipeline, Texture,
     },
     scene::{
         camera::{Camera, CameraMode, Dependents},
@@ -16,22 +18,26 @@ use crate::{
     },
 };
 use anim::{
-    biped_large::BipedLargeSkeleton, bird_medium::BirdMediumSkeleton,
-    bird_small::BirdSmallSkeleton, character::CharacterSkeleton, dragon::DragonSkeleton,
-    fish_medium::FishMediumSkeleton, fish_small::FishSmallSkeleton, golem::GolemSkeleton,
-    object::ObjectSkeleton, quadruped_low::QuadrupedLowSkeleton,
+    arthropod::ArthropodSkeleton, biped_large::BipedLargeSkeleton, bird_medium::BirdMediumSkeleton,
+    bird_small::BirdSmallSkeleton, character::CharacterSkeleton, colossus::ColossusSkeleton,
+    dragon::DragonSkeleton, fish_medium::FishMediumSkeleton, fish_small::FishSmallSkeleton,
+    golem::GolemSkeleton, object::ObjectSkeleton, quadruped_low::QuadrupedLowSkeleton,
     quadruped_medium::QuadrupedMediumSkeleton, quadruped_small::QuadrupedSmallSkeleton,
-    theropod::TheropodSkeleton, Animation, Skeleton,
+    theropod::TheropodSkeleton, turret::TurretSkeleton, Animation, Skeleton,
 };
 use common::{
     comp::{
-        item::{ItemKind, ToolKind},
-        Body, CharacterState, Item, Last, LightAnimation, LightEmitter, Loadout, Ori, PhysicsState,
-        Pos, Scale, Stats, Vel,
+        buff::FROZEN_MAX_STACKS,
+        item::{
+            armor::{Armor, ArmorKind},
+            ItemKind, ToolKind,
+        },
+        Body, BuffKind, Buffs, CharacterState, Group, Item, Last, LightAnimation, LightEmitter,
+        Loadout, Ori, PhysicsState, Pos, Scale, Stats, Vel,
     },
     span,
     state::{DeltaTime, State},
-    states::utils::StageSection,
+    states::{dual_strike, fly, swim, utils::StageSection},
     terrain::TerrainChunk,
     vol::RectRasterableVol,
 };
@@ -50,6 +56,19 @@ use vek::*;
 const DAMAGE_FADE_COEFFICIENT: f64 = 5.0;
 const MOVING_THRESHOLD: f32 = 0.7;
 const MOVING_THRESHOLD_SQR: f32 = MOVING_THRESHOLD * MOVING_THRESHOLD;
+/// Horizontal speed below which flying creatures are shown hovering
+const HOVER_SPEED: f32 = 5.0;
+/// Fractions of the figure LoD render distance past which the mid and low
+/// detail models are used. Beyond the full distance only the coarsest model is
+/// drawn.
+const FIGURE_MID_DETAIL_FACTOR: f32 = 0.5;
+const FIGURE_LOW_DETAIL_FACTOR: f32 = 0.75;
+
+/// Color multiplier showing the buffs an entity is affected by
+fn buff_tint(buffs: &Buffs) -> Rgba<f32> {
+    let frozen = buffs.stacks(BuffKind::Frozen) as f32 / FROZEN_MAX_STACKS as f32;
+    Rgba::lerp(Rgba::one(), Rgba::new(0.6, 0.8, 1.6, 1.0), frozen.min(1.0))
+}
 
 /// camera data, figure LOD render distance.
 pub type CameraData<'a> = (&'a Camera, f32);
@@ -94,7 +113,12 @@ struct FigureMgrStates {
     fish_small_states: HashMap<EcsEntity, FigureState<FishSmallSkeleton>>,
     biped_large_states: HashMap<EcsEntity, FigureState<BipedLargeSkeleton>>,
     golem_states: HashMap<EcsEntity, FigureState<GolemSkeleton>>,
+    colossus_states: HashMap<EcsEntity, FigureState<ColossusSkeleton>>,
+    arthropod_states: HashMap<EcsEntity, FigureState<ArthropodSkeleton>>,
+    turret_states: HashMap<EcsEntity, FigureState<TurretSkeleton>>,
     object_states: HashMap<EcsEntity, FigureState<ObjectSkeleton>>,
+    /// Simulated capes of characters that wear one
+    capes: HashMap<EcsEntity, Cloth>,
 }
 
 impl FigureMgrStates {
@@ -112,7 +136,11 @@ impl FigureMgrStates {
             fish_small_states: HashMap::new(),
             biped_large_states: HashMap::new(),
             golem_states: HashMap::new(),
+            colossus_states: HashMap::new(),
+            arthropod_states: HashMap::new(),
+            turret_states: HashMap::new(),
             object_states: HashMap::new(),
+            capes: HashMap::new(),
         }
     }
 
@@ -168,6 +196,15 @@ impl FigureMgrStates {
                 .get_mut(&entity)
                 .map(DerefMut::deref_mut),
             Body::Golem(_) => self.golem_states.get_mut(&entity).map(DerefMut::deref_mut),
+            Body::Colossus(_) => self
+                .colossus_states
+                .get_mut(&entity)
+                .map(DerefMut::deref_mut),
+            Body::Arthropod(_) => self
+                .arthropod_states
+                .get_mut(&entity)
+                .map(DerefMut::deref_mut),
+            Body::Turret(_) => self.turret_states.get_mut(&entity).map(DerefMut::deref_mut),
             Body::Object(_) => self.object_states.get_mut(&entity).map(DerefMut::deref_mut),
         }
     }
@@ -178,7 +215,10 @@ impl FigureMgrStates {
         Q: Hash + Eq,
     {
         match body {
-            Body::Humanoid(_) => self.character_states.remove(&entity).map(|e| e.meta),
+            Body::Humanoid(_) => {
+                self.capes.remove(&entity);
+                self.character_states.remove(&entity).map(|e| e.meta)
+            },
             Body::QuadrupedSmall(_) => self.quadruped_small_states.remove(&entity).map(|e| e.meta),
             Body::QuadrupedMedium(_) => {
                 self.quadruped_medium_states.remove(&entity).map(|e| e.meta)
@@ -192,6 +232,9 @@ impl FigureMgrStates {
             Body::FishSmall(_) => self.fish_small_states.remove(&entity).map(|e| e.meta),
             Body::BipedLarge(_) => self.biped_large_states.remove(&entity).map(|e| e.meta),
             Body::Golem(_) => self.golem_states.remove(&entity).map(|e| e.meta),
+            Body::Colossus(_) => self.colossus_states.remove(&entity).map(|e| e.meta),
+            Body::Arthropod(_) => self.arthropod_states.remove(&entity).map(|e| e.meta),
+            Body::Turret(_) => self.turret_states.remove(&entity).map(|e| e.meta),
             Body::Object(_) => self.object_states.remove(&entity).map(|e| e.meta),
         }
     }
@@ -210,7 +253,12 @@ impl FigureMgrStates {
         self.fish_small_states.retain(|k, v| f(k, &mut *v));
         self.biped_large_states.retain(|k, v| f(k, &mut *v));
         self.golem_states.retain(|k, v| f(k, &mut *v));
+        self.colossus_states.retain(|k, v| f(k, &mut *v));
+        self.arthropod_states.retain(|k, v| f(k, &mut *v));
+        self.turret_states.retain(|k, v| f(k, &mut *v));
         self.object_states.retain(|k, v| f(k, &mut *v));
+        let character_states = &self.character_states;
+        self.capes.retain(|k, _| character_states.contains_key(k));
     }
 
     fn count(&self) -> usize {
@@ -227,6 +275,9 @@ impl FigureMgrStates {
             + self.fish_small_states.len()
             + self.biped_large_states.len()
             + self.golem_states.len()
+            + self.colossus_states.len()
+            + self.arthropod_states.len()
+            + self.turret_states.len()
             + self.object_states.len()
     }
 
@@ -290,6 +341,21 @@ impl FigureMgrStates {
                 .iter()
                 .filter(|(_, c)| c.visible())
                 .count()
+            + self
+                .colossus_states
+                .iter()
+                .filter(|(_, c)| c.visible())
+                .count()
+            + self
+                .arthropod_states
+                .iter()
+                .filter(|(_, c)| c.visible())
+                .count()
+            + self
+                .turret_states
+                .iter()
+                .filter(|(_, c)| c.visible())
+                .count()
             + self
                 .object_states
                 .iter()
@@ -313,7 +379,12 @@ pub struct FigureMgr {
     biped_large_model_cache: FigureModelCache<BipedLargeSkeleton>,
     object_model_cache: FigureModelCache<ObjectSkeleton>,
     golem_model_cache: FigureModelCache<GolemSkeleton>,
+    colossus_model_cache: FigureModelCache<ColossusSkeleton>,
+    arthropod_model_cache: FigureModelCache<ArthropodSkeleton>,
+    turret_model_cache: FigureModelCache<TurretSkeleton>,
     states: FigureMgrStates,
+    /// Seconds left for which each entity holds its pose after a heavy hit
+    hitstop: HashMap<EcsEntity, f32>,
 }
 
 impl FigureMgr {
@@ -333,12 +404,23 @@ impl FigureMgr {
             biped_large_model_cache: FigureModelCache::new(),
             object_model_cache: FigureModelCache::new(),
             golem_model_cache: FigureModelCache::new(),
+            colossus_model_cache: FigureModelCache::new(),
+            arthropod_model_cache: FigureModelCache::new(),
+            turret_model_cache: FigureModelCache::new(),
             states: FigureMgrStates::default(),
+            hitstop: HashMap::new(),
         }
     }
 
     pub fn col_lights(&self) -> &FigureColLights { &self.col_lights }
 
+    /// Freezes the animation of the entity for `duration` seconds, so that a
+    /// heavy hit is felt
+    pub fn hitstop(&mut self, entity: EcsEntity, duration: f32) {
+        let remaining = self.hitstop.entry(entity).or_insert(0.0);
+        *remaining = remaining.max(duration);
+    }
+
     pub fn clean(&mut self, tick: u64) {
         span!(_guard, "clean", "FigureManager::clean");
         self.model_cache.clean(&mut self.col_lights, tick);
@@ -362,6 +444,9 @@ impl FigureMgr {
             .clean(&mut self.col_lights, tick);
         self.object_model_cache.clean(&mut self.col_lights, tick);
         self.golem_model_cache.clean(&mut self.col_lights, tick);
+        self.colossus_model_cache.clean(&mut self.col_lights, tick);
+        self.arthropod_model_cache.clean(&mut self.col_lights, tick);
+        self.turret_model_cache.clean(&mut self.col_lights, tick);
     }
 
     #[allow(clippy::redundant_pattern_matching)]
@@ -466,6 +551,11 @@ impl FigureMgr {
         let dt_lerp = (15.0 * dt).min(1.0);
         let frustum = camera.frustum();
 
+        self.hitstop.retain(|_, remaining| {
+            *remaining -= dt;
+            *remaining > 0.0
+        });
+
         // Sun shadows--find the bounding box of the shadow map plane (i.e. the bounds
         // of the image rendered from the light).  If the position projected
         // with the ray_mat matrix is valid, and shadows are otherwise enabled,
@@ -528,6 +618,9 @@ impl FigureMgr {
 
         let focus_pos = anim::vek::Vec3::<f32>::from(camera.get_focus_pos());
 
+        let groups = ecs.read_storage::<Group>();
+        let player_group = groups.get(scene_data.player_entity);
+
         let mut update_buf = [Default::default(); anim::MAX_BONE_COUNT];
 
         for (
@@ -545,6 +638,7 @@ impl FigureMgr {
                 stats,
                 loadout,
                 item,
+                buffs,
             ),
         ) in (
             &ecs.entities(),
@@ -559,6 +653,7 @@ impl FigureMgr {
             ecs.read_storage::<Stats>().maybe(),
             ecs.read_storage::<Loadout>().maybe(),
             ecs.read_storage::<Item>().maybe(),
+            ecs.read_storage::<Buffs>().maybe(),
         )
             .join()
             .enumerate()
@@ -605,6 +700,27 @@ impl FigureMgr {
 
             // Check whether we could have been shadowing last frame.
             let mut state = self.states.get_mut(body, &entity);
+
+            // Outline the entity under the crosshair, and group members where they are hidden
+            let outline = if scene_data.target_entity == Some(entity) {
+                if item.is_some() {
+                    Outline::Interactable
+                } else {
+                    Outline::Target
+                }
+            } else if scene_data.outline_group_members
+                && !is_player
+                && player_group.is_some()
+                && groups.get(entity) == player_group
+            {
+                Outline::Group
+            } else {
+                Outline::None
+            };
+            if let Some(meta) = state.as_mut() {
+                meta.outline = outline;
+                meta.hitstop = self.hitstop.contains_key(&entity);
+            }
             let can_shadow_prev = state
                 .as_mut()
                 .map(|state| state.can_shadow_sun())
@@ -672,7 +788,9 @@ impl FigureMgr {
                 vek::Rgba::new(5.0, 5.0, 5.0, 1.0)
             } else {
                 vek::Rgba::one()
-            };
+            }
+            // Tint figures affected by buffs such as being frozen
+            * buffs.map_or(vek::Rgba::one(), buff_tint);
 
             let scale = scale.map(|s| s.0).unwrap_or(1.0);
 
@@ -937,7 +1055,7 @@ impl FigureMgr {
                                 skeleton_attr,
                             )
                         },
-                        CharacterState::Sneak { .. } => {
+                        CharacterState::Sneak { .. } | CharacterState::Crouch { .. } => {
                             anim::character::SneakAnimation::update_skeleton(
                                 &CharacterSkeleton::default(),
                                 (active_tool_kind, vel.0, ori, state.last_ori, time),
@@ -1121,6 +1239,50 @@ impl FigureMgr {
                                 skeleton_attr,
                             )
                         },
+                        CharacterState::DualStrike(s) => {
+                            let strike = s.strike();
+                            let stage_time = s.timer.as_secs_f64();
+                            let stage_progress = match s.stage_section {
+                                StageSection::Buildup => {
+                                    stage_time / strike.buildup_duration.as_secs_f64()
+                                },
+                                StageSection::Swing => {
+                                    stage_time / strike.swing_duration.as_secs_f64()
+                                },
+                                StageSection::Recover => {
+                                    stage_time / strike.recover_duration.as_secs_f64()
+                                },
+                                _ => 0.0,
+                            };
+                            let dependency = (
+                                active_tool_kind,
+                                second_tool_kind,
+                                vel.0.magnitude(),
+                                time,
+                                Some(s.stage_section),
+                            );
+                            // Alternate between the swings of each hand
+                            match s.hand {
+                                dual_strike::Hand::Main => {
+                                    anim::character::AlphaAnimation::update_skeleton(
+                                        &target_base,
+                                        dependency,
+                                        stage_progress,
+                                        &mut state_animation_rate,
+                                        skeleton_attr,
+                                    )
+                                },
+                                dual_strike::Hand::Off => {
+                                    anim::character::BetaAnimation::update_skeleton(
+                                        &target_base,
+                                        dependency,
+                                        stage_progress,
+                                        &mut state_animation_rate,
+                                        skeleton_attr,
+                                    )
+                                },
+                            }
+                        },
                         CharacterState::ComboMelee(s) => {
                             let stage_index = (s.stage - 1) as usize;
                             let stage_time = s.timer.as_secs_f64();
@@ -1249,6 +1411,30 @@ impl FigureMgr {
                                 skeleton_attr,
                             )
                         },
+                        CharacterState::Swim(s) => match s.stage {
+                            swim::Stage::Dive => anim::character::DiveAnimation::update_skeleton(
+                                &target_base,
+                                (active_tool_kind, second_tool_kind, vel.0, time),
+                                state.state_time,
+                                &mut state_animation_rate,
+                                skeleton_attr,
+                            ),
+                            swim::Stage::Dodge => anim::character::RollAnimation::update_skeleton(
+                                &target_base,
+                                (
+                                    active_tool_kind,
+                                    second_tool_kind,
+                                    ori,
+                                    state.last_ori,
+                                    time,
+                                ),
+                                state.state_time,
+                                &mut state_animation_rate,
+                                skeleton_attr,
+                            ),
+                            // Fast swimming is ordinary swimming, just quicker
+                            swim::Stage::Sprint => target_base,
+                        },
                         CharacterState::Climb { .. } => {
                             anim::character::ClimbAnimation::update_skeleton(
                                 &CharacterSkeleton::default(),
@@ -1258,7 +1444,10 @@ impl FigureMgr {
                                 skeleton_attr,
                             )
                         },
-                        CharacterState::Sit { .. } => {
+                        // TODO: Give downed and reviving characters their own animations
+                        CharacterState::Sit { .. }
+                        | CharacterState::Downed(_)
+                        | CharacterState::Revive(_) => {
                             anim::character::SitAnimation::update_skeleton(
                                 &CharacterSkeleton::default(),
                                 (active_tool_kind, second_tool_kind, time),
@@ -1296,6 +1485,32 @@ impl FigureMgr {
                     };
 
                     state.skeleton = anim::vek::Lerp::lerp(&state.skeleton, &target_bones, dt_lerp);
+
+                    let cape = loadout
+                        .and_then(|loadout| loadout.back.as_ref())
+                        .and_then(|back| match back.look() {
+                            ItemKind::Armor(Armor {
+                                kind: ArmorKind::Back(kind),
+                                ..
+                            }) => Some(kind),
+                            _ => None,
+                        })
+                        .filter(|kind| load::back_is_cloth(kind));
+                    if cape.is_some() {
+                        // The cape hangs from the character's shoulders
+                        let anchor = pos.0 + anim::vek::Vec3::unit_z() * 1.4 * scale;
+                        let sim = self
+                            .states
+                            .capes
+                            .entry(entity)
+                            .or_insert_with(|| Cloth::new(anchor));
+                        sim.update(anchor, cloth::wind(time), dt);
+                        let facing = anim::vek::Quaternion::rotation_z(ori.x.atan2(ori.y));
+                        state.skeleton.hang_back(facing * sim.hang_dir());
+                    } else {
+                        self.states.capes.remove(&entity);
+                    }
+
                     state.update(
                         renderer,
                         pos.0,
@@ -1906,8 +2121,54 @@ impl FigureMgr {
                             skeleton_attr,
                         ),
                     };
+                    let target_bones = match &character {
+                        CharacterState::Fly(s) => {
+                            let horizontal_speed = vel.0.xy().magnitude();
+                            match s.stage {
+                                // Gliding on outstretched wings whenever not climbing
+                                fly::Stage::Airborne
+                                    if horizontal_speed > HOVER_SPEED && vel.0.z <= 0.0 =>
+                                {
+                                    anim::dragon::GlideAnimation::update_skeleton(
+                                        &target_base,
+                                        (ori, state.last_ori, time),
+                                        state.state_time,
+                                        &mut state_animation_rate,
+                                        skeleton_attr,
+                                    )
+                                },
+                                fly::Stage::Airborne if horizontal_speed > HOVER_SPEED => {
+                                    anim::dragon::FlyAnimation::update_skeleton(
+                                        &target_base,
+                                        (vel.0.magnitude(), time),
+                                        state.state_time,
+                                        &mut state_animation_rate,
+                                        skeleton_attr,
+                                    )
+                                },
+                                // Hovering in place, which also covers taking off and landing
+                                _ => anim::dragon::HoverAnimation::update_skeleton(
+                                    &target_base,
+                                    (vel.0.magnitude(), time),
+                                    state.state_time,
+                                    &mut state_animation_rate,
+                                    skeleton_attr,
+                                ),
+                            }
+                        },
+                        CharacterState::BasicBeam(s) => {
+                            anim::dragon::BreatheAnimation::update_skeleton(
+                                &target_base,
+                                (Some(s.stage_section), !physics.on_ground, time),
+                                state.state_time,
+                                &mut state_animation_rate,
+                                skeleton_attr,
+                            )
+                        },
+                        _ => target_base,
+                    };
 
-                    state.skeleton = anim::vek::Lerp::lerp(&state.skeleton, &target_base, dt_lerp);
+                    state.skeleton = anim::vek::Lerp::lerp(&state.skeleton, &target_bones, dt_lerp);
                     state.update(
                         renderer,
                         pos.0,
@@ -2673,6 +2934,271 @@ impl FigureMgr {
                         &mut update_buf,
                     );
                 },
+                Body::Colossus(body) => {
+                    let (model, skeleton_attr) = self.colossus_model_cache.get_or_create_model(
+                        renderer,
+                        &mut self.col_lights,
+                        *body,
+                        loadout,
+                        tick,
+                        player_camera_mode,
+                        player_character_state,
+                        scene_data.thread_pool,
+                    );
+
+                    let state = self
+                        .states
+                        .colossus_states
+                        .entry(entity)
+                        .or_insert_with(|| FigureState::new(renderer, ColossusSkeleton::default()));
+
+                    let (character, last_character) = match (character, last_character) {
+                        (Some(c), Some(l)) => (c, l),
+                        _ => continue,
+                    };
+
+                    if !character.same_variant(&last_character.0) {
+                        state.state_time = 0.0;
+                    }
+
+                    let target_base = match (
+                        physics.on_ground,
+                        vel.0.magnitude_squared() > MOVING_THRESHOLD_SQR, // Moving
+                        physics.in_fluid.is_some(),                       // In water
+                    ) {
+                        // Walking
+                        (true, true, false) => anim::colossus::WalkAnimation::update_skeleton(
+                            &ColossusSkeleton::default(),
+                            (vel.0.magnitude(), ori, state.last_ori, time),
+                            state.state_time,
+                            &mut state_animation_rate,
+                            skeleton_attr,
+                        ),
+                        _ => anim::colossus::IdleAnimation::update_skeleton(
+                            &ColossusSkeleton::default(),
+                            time,
+                            state.state_time,
+                            &mut state_animation_rate,
+                            skeleton_attr,
+                        ),
+                    };
+                    let mut target_bones = match &character {
+                        CharacterState::BasicMelee(_) => {
+                            anim::colossus::SmashAnimation::update_skeleton(
+                                &target_base,
+                                (vel.0.magnitude(), time),
+                                state.state_time,
+                                &mut state_animation_rate,
+                                skeleton_attr,
+                            )
+                        },
+                        CharacterState::Shockwave(_) => {
+                            anim::colossus::StompAnimation::update_skeleton(
+                                &target_base,
+                                (vel.0.magnitude(), time),
+                                state.state_time,
+                                &mut state_animation_rate,
+                                skeleton_attr,
+                            )
+                        },
+                        // TODO!
+                        _ => target_base,
+                    };
+                    if let Some(stats) = stats {
+                        target_bones.shatter_armor(
+                            stats.health.current() as f32 / stats.health.maximum().max(1) as f32,
+                        );
+                    }
+
+                    state.skeleton = anim::vek::Lerp::lerp(&state.skeleton, &target_bones, dt_lerp);
+                    state.update(
+                        renderer,
+                        pos.0,
+                        ori,
+                        scale,
+                        col,
+                        dt,
+                        state_animation_rate,
+                        model,
+                        lpindex,
+                        in_frustum,
+                        is_player,
+                        camera,
+                        &mut update_buf,
+                    );
+                },
+                Body::Arthropod(body) => {
+                    let (model, skeleton_attr) = self.arthropod_model_cache.get_or_create_model(
+                        renderer,
+                        &mut self.col_lights,
+                        *body,
+                        loadout,
+                        tick,
+                        player_camera_mode,
+                        player_character_state,
+                        scene_data.thread_pool,
+                    );
+
+                    let state = self
+                        .states
+                        .arthropod_states
+                        .entry(entity)
+                        .or_insert_with(|| {
+                            FigureState::new(renderer, ArthropodSkeleton::default())
+                        });
+
+                    let (character, last_character) = match (character, last_character) {
+                        (Some(c), Some(l)) => (c, l),
+                        _ => continue,
+                    };
+
+                    if !character.same_variant(&last_character.0) {
+                        state.state_time = 0.0;
+                    }
+
+                    let target_base = match (
+                        physics.on_ground,
+                        vel.0.magnitude_squared() > MOVING_THRESHOLD_SQR, // Moving
+                        physics.in_fluid.is_some(),                       // In water
+                    ) {
+                        // Standing
+                        (true, false, false) => anim::arthropod::IdleAnimation::update_skeleton(
+                            &ArthropodSkeleton::default(),
+                            time,
+                            state.state_time,
+                            &mut state_animation_rate,
+                            skeleton_attr,
+                        ),
+                        // Running
+                        (true, true, false) => anim::arthropod::RunAnimation::update_skeleton(
+                            &ArthropodSkeleton::default(),
+                            (vel.0.magnitude(), ori, state.last_ori, time),
+                            state.state_time,
+                            &mut state_animation_rate,
+                            skeleton_attr,
+                        ),
+                        // In air
+                        (false, _, false) => anim::arthropod::JumpAnimation::update_skeleton(
+                            &ArthropodSkeleton::default(),
+                            (vel.0.magnitude(), time),
+                            state.state_time,
+                            &mut state_animation_rate,
+                            skeleton_attr,
+                        ),
+                        _ => anim::arthropod::IdleAnimation::update_skeleton(
+                            &ArthropodSkeleton::default(),
+                            time,
+                            state.state_time,
+                            &mut state_animation_rate,
+                            skeleton_attr,
+                        ),
+                    };
+                    let target_bones = match &character {
+                        // Scuttling up a wall uses the same leg cycle as on the ground
+                        CharacterState::Climb { .. } => {
+                            anim::arthropod::RunAnimation::update_skeleton(
+                                &target_base,
+                                (vel.0.magnitude(), ori, state.last_ori, time),
+                                state.state_time,
+                                &mut state_animation_rate,
+                                skeleton_attr,
+                            )
+                        },
+                        CharacterState::BasicMelee(_) => {
+                            anim::arthropod::AlphaAnimation::update_skeleton(
+                                &target_base,
+                                (vel.0.magnitude(), time),
+                                state.state_time,
+                                &mut state_animation_rate,
+                                skeleton_attr,
+                            )
+                        },
+                        // TODO!
+                        _ => target_base,
+                    };
+
+                    state.skeleton = anim::vek::Lerp::lerp(&state.skeleton, &target_bones, dt_lerp);
+                    state.update(
+                        renderer,
+                        pos.0,
+                        ori,
+                        scale,
+                        col,
+                        dt,
+                        state_animation_rate,
+                        model,
+                        lpindex,
+                        in_frustum,
+                        is_player,
+                        camera,
+                        &mut update_buf,
+                    );
+                },
+                Body::Turret(body) => {
+                    let (model, skeleton_attr) = self.turret_model_cache.get_or_create_model(
+                        renderer,
+                        &mut self.col_lights,
+                        *body,
+                        loadout,
+                        tick,
+                        player_camera_mode,
+                        player_character_state,
+                        scene_data.thread_pool,
+                    );
+
+                    let state =
+                        self.states.turret_states.entry(entity).or_insert_with(|| {
+                            FigureState::new(renderer, TurretSkeleton::default())
+                        });
+
+                    let (character, last_character) = match (character, last_character) {
+                        (Some(c), Some(l)) => (c, l),
+                        _ => continue,
+                    };
+
+                    if !character.same_variant(&last_character.0) {
+                        state.state_time = 0.0;
+                    }
+
+                    // Turrets never move, so they only scan around or shoot
+                    let target_base = anim::turret::IdleAnimation::update_skeleton(
+                        &TurretSkeleton::default(),
+                        time,
+                        state.state_time,
+                        &mut state_animation_rate,
+                        skeleton_attr,
+                    );
+                    let target_bones = match &character {
+                        CharacterState::BasicRanged(data) => {
+                            anim::turret::ShootAnimation::update_skeleton(
+                                &target_base,
+                                (data.exhausted, time),
+                                state.state_time,
+                                &mut state_animation_rate,
+                                skeleton_attr,
+                            )
+                        },
+                        // TODO!
+                        _ => target_base,
+                    };
+
+                    state.skeleton = anim::vek::Lerp::lerp(&state.skeleton, &target_bones, dt_lerp);
+                    state.update(
+                        renderer,
+                        pos.0,
+                        ori,
+                        scale,
+                        col,
+                        dt,
+                        state_animation_rate,
+                        model,
+                        lpindex,
+                        in_frustum,
+                        is_player,
+                        camera,
+                        &mut update_buf,
+                    );
+                },
                 Body::Object(body) => {
                     let (model, _) = self.object_model_cache.get_or_create_model(
                         renderer,
@@ -2820,6 +3346,61 @@ impl FigureMgr {
         }
     }
 
+    /// Marks the parts of outlined group members hidden behind terrain and
+    /// other figures, which must happen after those have been rendered.
+    #[allow(clippy::too_many_arguments)]
+    pub fn render_outlines(
+        &self,
+        renderer: &mut Renderer,
+        state: &State,
+        player_entity: EcsEntity,
+        tick: u64,
+        global: &GlobalModel,
+        lod: &LodData,
+        (camera, figure_lod_render_distance): CameraData,
+    ) {
+        span!(_guard, "render_outlines", "FigureManager::render_outlines");
+        let ecs = state.ecs();
+
+        let character_state_storage = state.read_storage::<common::comp::CharacterState>();
+        let character_state = character_state_storage.get(player_entity);
+
+        for (entity, pos, body, _, loadout) in (
+            &ecs.entities(),
+            &ecs.read_storage::<Pos>(),
+            &ecs.read_storage::<Body>(),
+            ecs.read_storage::<Stats>().maybe(),
+            ecs.read_storage::<Loadout>().maybe(),
+        )
+            .join()
+            .filter(|(entity, _, _, stats, _)| {
+                *entity != player_entity && stats.map_or(true, |s| !s.is_dead)
+            })
+        {
+            if let Some((locals, bone_consts, model, col_lights)) = self.get_model_for_render(
+                tick,
+                camera,
+                character_state,
+                entity,
+                body,
+                loadout,
+                false,
+                pos.0,
+                figure_lod_render_distance,
+                |state| state.visible() && state.outline == Outline::Group,
+            ) {
+                renderer.render_figure_outline(
+                    model,
+                    &col_lights,
+                    global,
+                    locals,
+                    bone_consts,
+                    lod,
+                );
+            }
+        }
+    }
+
     #[allow(clippy::too_many_arguments)] // TODO: Pending review in #587
     pub fn render_player(
         &self,
@@ -2914,6 +3495,9 @@ impl FigureMgr {
             biped_large_model_cache,
             object_model_cache,
             golem_model_cache,
+            colossus_model_cache,
+            arthropod_model_cache,
+            turret_model_cache,
             states:
                 FigureMgrStates {
                     character_states,
@@ -2928,7 +3512,11 @@ impl FigureMgr {
                     fish_small_states,
                     biped_large_states,
                     golem_states,
+                    colossus_states,
+                    arthropod_states,
+                    turret_states,
                     object_states,
+                    ..
                 },
         } = self;
         let col_lights = &*col_lights_;
@@ -3137,6 +3725,57 @@ impl FigureMgr {
                         ),
                     )
                 }),
+            Body::Colossus(body) => colossus_states
+                .get(&entity)
+                .filter(|state| filter_state(&*state))
+                .map(move |state| {
+                    (
+                        state.locals(),
+                        state.bone_consts(),
+                        colossus_model_cache.get_model(
+                            col_lights,
+                            *body,
+                            loadout,
+                            tick,
+                            player_camera_mode,
+                            character_state,
+                        ),
+                    )
+                }),
+            Body::Arthropod(body) => arthropod_states
+                .get(&entity)
+                .filter(|state| filter_state(&*state))
+                .map(move |state| {
+                    (
+                        state.locals(),
+                        state.bone_consts(),
+                        arthropod_model_cache.get_model(
+                            col_lights,
+                            *body,
+                            loadout,
+                            tick,
+                            player_camera_mode,
+                            character_state,
+                        ),
+                    )
+                }),
+            Body::Turret(body) => turret_states
+                .get(&entity)
+                .filter(|state| filter_state(&*state))
+                .map(move |state| {
+                    (
+                        state.locals(),
+                        state.bone_consts(),
+                        turret_model_cache.get_model(
+                            col_lights,
+                            *body,
+                            loadout,
+                            tick,
+                            player_camera_mode,
+                            character_state,
+                        ),
+                    )
+                }),
             Body::Object(body) => object_states
                 .get(&entity)
                 .filter(|state| filter_state(&*state))
@@ -3157,12 +3796,15 @@ impl FigureMgr {
         } {
             let model_entry = model_entry?;
 
-            let figure_low_detail_distance = figure_lod_render_distance * 0.75;
-            let figure_mid_detail_distance = figure_lod_render_distance * 0.5;
+            let figure_low_detail_distance = figure_lod_render_distance * FIGURE_LOW_DETAIL_FACTOR;
+            let figure_mid_detail_distance = figure_lod_render_distance * FIGURE_MID_DETAIL_FACTOR;
 
-            let model = if pos.distance_squared(cam_pos) > figure_low_detail_distance.powf(2.0) {
+            let dist_sqr = pos.distance_squared(cam_pos);
+            let model = if dist_sqr > figure_lod_render_distance.powf(2.0) {
+                &model_entry.models[3]
+            } else if dist_sqr > figure_low_detail_distance.powf(2.0) {
                 &model_entry.models[2]
-            } else if pos.distance_squared(cam_pos) > figure_mid_detail_distance.powf(2.0) {
+            } else if dist_sqr > figure_mid_detail_distance.powf(2.0) {
                 &model_entry.models[1]
             } else {
                 &model_entry.models[0]
@@ -3291,6 +3933,9 @@ pub struct FigureStateMeta {
     lpindex: u8,
     can_shadow_sun: bool,
     visible: bool,
+    outline: Outline,
+    /// Whether the figure is holding its pose after a heavy hit
+    hitstop: bool,
     last_pos: Option<anim::vek::Vec3<f32>>,
     avg_vel: anim::vek::Vec3<f32>,
 }
@@ -3307,6 +3952,8 @@ impl FigureStateMeta {
 pub struct FigureState<S> {
     meta: FigureStateMeta,
     skeleton: S,
+    /// The skeleton and base matrix the current bone consts were computed from
+    computed: Option<(S, anim::vek::Mat4<f32>)>,
 }
 
 impl<S> Deref for FigureState<S> {
@@ -3335,10 +3982,13 @@ impl<S: Skeleton> FigureState<S> {
                 lpindex: 0,
                 visible: false,
                 can_shadow_sun: false,
+                outline: Outline::None,
+                hitstop: false,
                 last_pos: None,
                 avg_vel: anim::vek::Vec3::zero(),
             },
             skeleton,
+            computed: None,
         }
     }
 
@@ -3385,9 +4035,12 @@ impl<S: Skeleton> FigureState<S> {
         /* let radius = vek::Extent3::<f32>::from(model.bounds.half_size()).reduce_partial_max();
         let _bounds = BoundingSphere::new(pos.into_array(), scale * 0.8 * radius); */
 
-        self.last_ori = vek::Lerp::lerp(self.last_ori, ori, 15.0 * dt);
+        // Animations stand still during a hitstop
+        let anim_dt = if self.hitstop { 0.0 } else { dt };
 
-        self.state_time += (dt * state_animation_rate) as f64;
+        self.last_ori = vek::Lerp::lerp(self.last_ori, ori, 15.0 * anim_dt);
+
+        self.state_time += (anim_dt * state_animation_rate) as f64;
 
         let mat = anim::vek::Mat4::rotation_z(-ori.x.atan2(ori.y))
             * anim::vek::Mat4::rotation_x(ori.z.atan2(anim::vek::Vec2::from(ori).magnitude()))
@@ -3400,20 +4053,31 @@ impl<S: Skeleton> FigureState<S> {
             pos,
             vek::Vec2::new(atlas_offs.x, atlas_offs.y),
             is_player,
+            self.outline,
         );
         renderer.update_consts(&mut self.locals, &[locals]).unwrap();
 
-        let lantern_offset = anim::compute_matrices(&self.skeleton, mat, buf);
-
-        let new_bone_consts = figure_bone_data_from_anim(buf);
-
-        renderer
-            .update_consts(
-                &mut self.meta.bone_consts,
-                &new_bone_consts[0..S::BONE_COUNT],
-            )
-            .unwrap();
-        self.lantern_offset = lantern_offset;
+        // Computing and uploading the bones is skipped when neither the skeleton nor
+        // its transform changed since the last update (e.g. for static objects)
+        // TODO: Upload the bones of all figures into one buffer per frame instead of a
+        // buffer per figure. This needs the figure shaders to index into a shared
+        // buffer, since each draw currently binds its own bone consts.
+        if self.computed.as_ref().map_or(true, |(skeleton, last_mat)| {
+            *skeleton != self.skeleton || *last_mat != mat
+        }) {
+            let lantern_offset = anim::compute_matrices(&self.skeleton, mat, buf);
+
+            let new_bone_consts = figure_bone_data_from_anim(buf);
+
+            renderer
+                .update_consts(
+                    &mut self.meta.bone_consts,
+                    &new_bone_consts[0..S::BONE_COUNT],
+                )
+                .unwrap();
+            self.lantern_offset = lantern_offset;
+            self.computed = Some((self.skeleton.clone(), mat));
+        }
 
         let smoothing = (5.0 * dt).min(1.0);
         if let Some(last_pos) = self.last_pos {
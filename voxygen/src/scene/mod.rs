@@ -29,13 +29,15 @@ use common::{
     comp::humanoid::DEFAULT_HUMANOID_EYE_HEIGHT,
     outcome::Outcome,
     span,
+    spiral::Spiral2d,
     state::{DeltaTime, State},
+    sync::{Uid, UidAllocator},
     terrain::{BlockKind, TerrainChunk},
-    vol::ReadVol,
+    vol::{ReadVol, RectRasterableVol},
 };
 use comp::item::Reagent;
 use num::traits::{Float, FloatConst};
-use specs::{Entity as EcsEntity, Join, WorldExt};
+use specs::{saveload::MarkerAllocator, Entity as EcsEntity, Join, WorldExt};
 use vek::*;
 
 // TODO: Don't hard-code this.
@@ -45,6 +47,12 @@ const MAX_LIGHT_COUNT: usize = 31;
 const MAX_SHADOW_COUNT: usize = 24;
 const NUM_DIRECTED_LIGHTS: usize = 1;
 const LIGHT_DIST_RADIUS: f32 = 64.0; // The distance beyond which lights may not emit light from their origin
+/// Range, in chunks, around the player within which windows are lit at night
+const WINDOW_LIGHT_RANGE: usize = 1;
+const WINDOW_LIGHT_COLOR: Rgb<f32> = Rgb::new(1.0, 0.6, 0.25);
+const WINDOW_LIGHT_STRENGTH: f32 = 1.5;
+const GLOW_MUSHROOM_LIGHT_COLOR: Rgb<f32> = Rgb::new(0.2, 0.8, 1.0);
+const GLOW_MUSHROOM_LIGHT_STRENGTH: f32 = 0.6;
 const SHADOW_DIST_RADIUS: f32 = 8.0;
 const SHADOW_MAX_DIST: f32 = 96.0; // The distance beyond which shadows may not be visible
 /// The minimum sin γ we will use before switching to uniform mapping.
@@ -57,6 +65,18 @@ const SHADOW_FAR: f32 = 128.0; // Far plane for shadow map point light rendering
 /// Used for first person camera effects
 const RUNNING_THRESHOLD: f32 = 0.7;
 
+/// Seconds that the fighters hold their pose for when the lightest heavy hit
+/// lands
+const HITSTOP_DURATION: f32 = 0.06;
+/// Heavy hits stronger than this are felt no more strongly
+const MAX_HIT_MAGNITUDE: f32 = 2.5;
+/// Camera shake from the lightest heavy hit the player takes part in
+const HIT_SHAKE: f32 = 0.3;
+/// Explosion power that shakes the camera fully up close
+const EXPLOSION_SHAKE_POWER: f32 = 6.0;
+/// Distance, in explosion radii, beyond which explosions don't shake the camera
+const EXPLOSION_SHAKE_RANGE: f32 = 6.0;
+
 /// is_daylight, array of active lights.
 pub type LightData<'a> = (bool, &'a [Light]);
 
@@ -66,6 +86,16 @@ struct EventLight {
     fadeout: fn(f32) -> f32,
 }
 
+/// Camera move of a scripted sequence, which takes the camera away from the
+/// player until the sequence ends
+struct CutsceneShot {
+    /// Camera position and focus at the start and end of the move
+    from: (Vec3<f32>, Vec3<f32>),
+    to: (Vec3<f32>, Vec3<f32>),
+    duration: f32,
+    elapsed: f32,
+}
+
 struct Skybox {
     model: Model<SkyboxPipeline>,
     locals: Consts<SkyboxLocals>,
@@ -81,6 +111,9 @@ pub struct Scene {
     camera: Camera,
     camera_input_state: Vec2<f32>,
     event_lights: Vec<EventLight>,
+    cutscene_shot: Option<CutsceneShot>,
+    /// Camera orientation and distance to go back to once a sequence ends
+    cutscene_return: Option<(Vec3<f32>, f32)>,
 
     skybox: Skybox,
     postprocess: PostProcess,
@@ -113,8 +146,15 @@ pub struct SceneData<'a> {
     pub mouse_smoothing: bool,
     pub sprite_render_distance: f32,
     pub particles_enabled: bool,
+    pub decals_enabled: bool,
     pub figure_lod_render_distance: f32,
     pub is_aiming: bool,
+    /// Whether to outline group members hidden behind terrain or figures
+    pub outline_group_members: bool,
+    /// Strength of camera shake, from 0.0 for none to 1.0 for full
+    pub camera_shake: f32,
+    /// Length of hitstops, from 0.0 for none to 1.0 for full
+    pub hitstop: f32,
 }
 
 impl<'a> SceneData<'a> {
@@ -278,6 +318,8 @@ impl Scene {
             camera: Camera::new(resolution.x / resolution.y, CameraMode::ThirdPerson),
             camera_input_state: Vec2::zero(),
             event_lights: Vec::new(),
+            cutscene_shot: None,
+            cutscene_return: None,
 
             skybox: Skybox {
                 model: renderer.create_model(&create_skybox_mesh()).unwrap(),
@@ -323,6 +365,30 @@ impl Scene {
     /// Get a mutable reference to the scene's camera.
     pub fn camera_mut(&mut self) -> &mut Camera { &mut self.camera }
 
+    /// Move the camera to `pos`, looking at `focus`, over `duration` seconds.
+    /// The camera stays there until the sequence ends.
+    pub fn play_cutscene_shot(&mut self, pos: Vec3<f32>, focus: Vec3<f32>, duration: f32) {
+        let from_focus = self.camera.get_focus_pos();
+        let from_pos = from_focus - self.camera.forward() * self.camera.get_distance();
+        self.cutscene_return
+            .get_or_insert((self.camera.get_orientation(), self.camera.get_distance()));
+        self.cutscene_shot = Some(CutsceneShot {
+            from: (from_pos, from_focus),
+            to: (pos, focus),
+            duration,
+            elapsed: 0.0,
+        });
+    }
+
+    /// Hand the camera back to the player once a sequence is over
+    pub fn end_cutscene(&mut self) {
+        self.cutscene_shot = None;
+        if let Some((ori, dist)) = self.cutscene_return.take() {
+            self.camera.set_orientation(ori);
+            self.camera.set_distance(dist);
+        }
+    }
+
     /// Set the block position that the player is interacting with
     pub fn set_select_pos(&mut self, pos: Option<Vec3<i32>>) { self.select_pos = pos; }
 
@@ -341,7 +407,10 @@ impl Scene {
             },
             // Panning the cursor makes the camera rotate
             Event::CursorPan(delta) => {
-                self.camera.rotate_by(Vec3::from(delta) * CURSOR_PAN_SCALE);
+                let delta = Vec3::from(delta) * CURSOR_PAN_SCALE;
+                // Quick movements turn the camera further than slow ones
+                let acceleration = 1.0 + self.camera.pan_acceleration() * delta.magnitude();
+                self.camera.rotate_by(delta * acceleration);
                 true
             },
             // Zoom the camera when a zoom event occurs
@@ -385,6 +454,18 @@ impl Scene {
         self.particle_mgr.handle_outcome(&outcome, &scene_data);
         self.sfx_mgr.handle_outcome(&outcome, audio);
 
+        // Explosions shake the camera, the more the closer they are
+        if let Outcome::Explosion {
+            pos, power, radius, ..
+        } = outcome
+        {
+            let focus_off = self.camera.get_focus_pos().map(f32::trunc);
+            let dist = pos.distance(self.camera.dependents().cam_pos + focus_off);
+            let falloff = (1.0 - dist / (radius.max(1.0) * EXPLOSION_SHAKE_RANGE)).max(0.0);
+            let amount = (power.abs() / EXPLOSION_SHAKE_POWER).min(1.0) * falloff;
+            self.camera.shake(amount * scene_data.camera_shake);
+        }
+
         match outcome {
             Outcome::Explosion {
                 pos,
@@ -421,7 +502,38 @@ impl Scene {
                 },
                 fadeout: |timeout| timeout * 2.0,
             }),
-            Outcome::ProjectileShot { .. } => {},
+            Outcome::HeavyHit {
+                attacker,
+                target,
+                magnitude,
+                ..
+            } => {
+                let magnitude = magnitude.min(MAX_HIT_MAGNITUDE);
+                let ecs = scene_data.state.ecs();
+                if scene_data.hitstop > 0.0 {
+                    let uid_allocator = ecs.read_resource::<UidAllocator>();
+                    let duration = HITSTOP_DURATION * magnitude * scene_data.hitstop;
+                    for uid in [attacker, target].iter() {
+                        let entity = uid_allocator.retrieve_entity_internal((**uid).into());
+                        if let Some(entity) = entity {
+                            self.figure_mgr.hitstop(entity, duration);
+                        }
+                    }
+                }
+                // Only hits the player deals or takes shake the camera
+                let player_uid = ecs
+                    .read_storage::<Uid>()
+                    .get(scene_data.player_entity)
+                    .copied();
+                if player_uid.map_or(false, |uid| uid == *attacker || uid == *target) {
+                    self.camera
+                        .shake(HIT_SHAKE * magnitude * scene_data.camera_shake);
+                }
+            },
+            Outcome::ProjectileShot { .. }
+            | Outcome::SpriteBreak { .. }
+            | Outcome::Splash { .. }
+            | Outcome::Note { .. } => {},
         }
     }
 
@@ -503,14 +615,25 @@ impl Scene {
             CameraMode::Freefly => 0.0,
         };
 
-        match self.camera.get_mode() {
-            CameraMode::FirstPerson | CameraMode::ThirdPerson => {
-                self.camera.set_focus_pos(
-                    player_pos + Vec3::unit_z() * (up - tilt.min(0.0).sin() * dist * 0.6),
-                );
-            },
-            CameraMode::Freefly => {},
-        };
+        if let Some(shot) = &mut self.cutscene_shot {
+            shot.elapsed += scene_data.state.get_delta_time();
+            let t = (shot.elapsed / shot.duration.max(0.001)).min(1.0);
+            // Ease in and out of the move
+            let t = t * t * (3.0 - 2.0 * t);
+            self.camera.look_from(
+                Lerp::lerp(shot.from.0, shot.to.0, t),
+                Lerp::lerp(shot.from.1, shot.to.1, t),
+            );
+        } else {
+            match self.camera.get_mode() {
+                CameraMode::FirstPerson | CameraMode::ThirdPerson => {
+                    self.camera.set_focus_pos(
+                        player_pos + Vec3::unit_z() * (up - tilt.min(0.0).sin() * dist * 0.6),
+                    );
+                },
+                CameraMode::Freefly => {},
+            };
+        }
 
         // Tick camera for interpolation.
         self.camera.update(
@@ -534,6 +657,52 @@ impl Scene {
         let loaded_distance =
             (0.98 * self.loaded_distance + 0.02 * scene_data.loaded_distance).max(0.01);
 
+        let player_chunk = player_pos.xy().map2(TerrainChunk::RECT_SIZE, |e, sz| {
+            (e.floor() as i32).div_euclid(sz as i32)
+        });
+
+        // Buildings light up their windows at night
+        let window_lights = if scene_data.state.get_day_period().is_dark() {
+            Spiral2d::new()
+                .take((WINDOW_LIGHT_RANGE * 2 + 1).pow(2))
+                .filter_map(|offset| {
+                    let chunk_pos = player_chunk + offset;
+                    self.terrain.get(chunk_pos).map(|chunk| (chunk_pos, chunk))
+                })
+                .flat_map(|(chunk_pos, chunk)| {
+                    let chunk_origin =
+                        Vec3::from(chunk_pos * TerrainChunk::RECT_SIZE.map(|e| e as i32));
+                    chunk
+                        .blocks_of_interest
+                        .windows
+                        .iter()
+                        .map(move |window| (chunk_origin + window).map(|e| e as f32 + 0.5))
+                })
+                .map(|pos| Light::new(pos, WINDOW_LIGHT_COLOR, WINDOW_LIGHT_STRENGTH))
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        // Glowing mushrooms light up caves whatever the time of day
+        let glow_lights = Spiral2d::new()
+            .take((WINDOW_LIGHT_RANGE * 2 + 1).pow(2))
+            .filter_map(|offset| {
+                let chunk_pos = player_chunk + offset;
+                self.terrain.get(chunk_pos).map(|chunk| (chunk_pos, chunk))
+            })
+            .flat_map(|(chunk_pos, chunk)| {
+                let chunk_origin =
+                    Vec3::from(chunk_pos * TerrainChunk::RECT_SIZE.map(|e| e as i32));
+                chunk
+                    .blocks_of_interest
+                    .glow_mushrooms
+                    .iter()
+                    .map(move |pos| (chunk_origin + pos).map(|e| e as f32 + 0.5))
+            })
+            .map(|pos| Light::new(pos, GLOW_MUSHROOM_LIGHT_COLOR, GLOW_MUSHROOM_LIGHT_STRENGTH))
+            .collect::<Vec<_>>();
+
         // Update light constants
         let lights = &mut self.light_data;
         lights.clear();
@@ -579,7 +748,9 @@ impl Scene {
                     self.event_lights
                         .iter()
                         .map(|el| el.light.with_strength((el.fadeout)(el.timeout))),
-                ),
+                )
+                .chain(window_lights)
+                .chain(glow_lights),
         );
         lights.sort_by_key(|light| light.get_pos().distance_squared(player_pos) as i32);
         lights.truncate(MAX_LIGHT_COUNT);
@@ -634,6 +805,14 @@ impl Scene {
         let focus_pos = self.camera.get_focus_pos();
         let focus_off = focus_pos.map(|e| e.trunc());
 
+        // Sample the terrain at the camera to tell whether it's under water.
+        let cam_medium = scene_data
+            .state
+            .terrain()
+            .get((cam_pos + focus_off).map(|e| e.floor() as i32))
+            .map(|b| b.kind())
+            .unwrap_or(BlockKind::Air);
+
         // Update global constants.
         renderer
             .update_consts(&mut self.data.globals, &[Globals::new(
@@ -651,12 +830,7 @@ impl Scene {
                 lights.len(),
                 shadows.len(),
                 NUM_DIRECTED_LIGHTS,
-                scene_data
-                    .state
-                    .terrain()
-                    .get((cam_pos + focus_off).map(|e| e.floor() as i32))
-                    .map(|b| b.kind())
-                    .unwrap_or(BlockKind::Air),
+                cam_medium,
                 self.select_pos.map(|e| e - focus_off.map(|e| e as i32)),
                 scene_data.gamma,
                 scene_data.ambiance,
@@ -986,6 +1160,7 @@ impl Scene {
             .maintain(renderer, &scene_data, &self.terrain);
 
         // Maintain audio
+        audio.set_muffled(cam_medium.is_liquid());
         self.sfx_mgr.maintain(
             audio,
             scene_data.state,
@@ -1072,6 +1247,18 @@ impl Scene {
             scene_data.sprite_render_distance,
         );
 
+        // Mark hidden group members for outlining, now that everything that
+        // could hide them has been rendered.
+        self.figure_mgr.render_outlines(
+            renderer,
+            state,
+            player_entity,
+            tick,
+            global,
+            lod,
+            camera_data,
+        );
+
         // Render particle effects.
         self.particle_mgr.render(renderer, scene_data, global, lod);
 
@@ -8,27 +8,40 @@ use crate::{
 };
 use common::{
     assets::Asset,
-    comp::{item::Reagent, object, Body, CharacterState, Ori, Pos, Shockwave},
+    comp::{
+        item::Reagent, object, Body, BuffKind, Buffs, CharacterState, Controller, Ori,
+        PhysicsState, Pos, Shockwave, Stats, Vel,
+    },
     figure::Segment,
     outcome::Outcome,
     span,
     spiral::Spiral2d,
     state::DeltaTime,
-    states::utils::StageSection,
-    terrain::TerrainChunk,
-    vol::{RectRasterableVol, SizedVol},
+    states::{throw, utils::StageSection},
+    terrain::{BiomeKind, BlockKind, TerrainChunk},
+    vol::{ReadVol, RectRasterableVol, SizedVol},
 };
 use dot_vox::DotVoxData;
 use hashbrown::HashMap;
 use rand::prelude::*;
-use specs::{Join, WorldExt};
-use std::{f32::consts::PI, time::Duration};
+use specs::{Entity as EcsEntity, Join, WorldExt};
+use std::{collections::VecDeque, f32::consts::PI, time::Duration};
 use vek::*;
 
 pub struct ParticleMgr {
     /// keep track of lifespans
     particles: Vec<Particle>,
 
+    /// Ground marks (footprints, scorch marks, blood), oldest first. Bounded
+    /// by `MAX_DECALS` so long play sessions don't grow this forever.
+    decals: VecDeque<Particle>,
+
+    /// Where each entity last left a footprint, and which foot it used
+    footprints: HashMap<EcsEntity, (Vec3<f32>, bool)>,
+
+    /// Health of each entity on the previous tick, used to spot heavy hits
+    last_health: HashMap<EcsEntity, u32>,
+
     /// keep track of timings
     scheduler: HeartbeatScheduler,
 
@@ -43,6 +56,9 @@ impl ParticleMgr {
     pub fn new(renderer: &mut Renderer) -> Self {
         Self {
             particles: Vec::new(),
+            decals: VecDeque::new(),
+            footprints: HashMap::new(),
+            last_health: HashMap::new(),
             scheduler: HeartbeatScheduler::new(),
             instances: default_instances(renderer),
             model_cache: default_cache(renderer),
@@ -89,6 +105,18 @@ impl ParticleMgr {
                                 )
                             },
                         );
+
+                        if scene_data.decals_enabled {
+                            // The scorch mark is sized by the explosion radius, which the
+                            // shader reads back from the instance direction
+                            self.add_decal(Particle::new_beam(
+                                Duration::from_secs(SCORCH_LIFESPAN),
+                                time,
+                                ParticleMode::Scorch,
+                                *pos,
+                                *pos + Vec3::new(radius * SCORCH_SCALE, 0.0, 0.0),
+                            ));
+                        }
                     }
                 } else {
                     self.particles.resize_with(
@@ -123,7 +151,32 @@ impl ParticleMgr {
                     );
                 }
             },
-            Outcome::ProjectileShot { .. } => {},
+            Outcome::ProjectileShot { .. } | Outcome::HeavyHit { .. } | Outcome::Note { .. } => {},
+            Outcome::SpriteBreak { pos, .. } => {
+                self.particles.resize_with(self.particles.len() + 40, || {
+                    Particle::new(
+                        Duration::from_millis(800),
+                        time,
+                        ParticleMode::Debris,
+                        *pos + Vec3::<f32>::zero().map(|_| rng.gen_range(-0.4, 0.4)),
+                    )
+                });
+            },
+            Outcome::Splash { pos, speed } => {
+                // The harder the plunge, the more water is thrown up and the higher it
+                // flies, which the shader reads back from the instance direction
+                let count = (*speed * SPLASH_DROPS_PER_SPEED).min(MAX_SPLASH_DROPS) as usize;
+                self.particles
+                    .resize_with(self.particles.len() + count, || {
+                        Particle::new_beam(
+                            Duration::from_millis(1200),
+                            time,
+                            ParticleMode::WaterSplash,
+                            *pos,
+                            *pos + Vec3::unit_z() * speed.min(MAX_SPLASH_SPEED),
+                        )
+                    });
+            },
         }
     }
 
@@ -145,7 +198,9 @@ impl ParticleMgr {
             // add new Particle
             self.maintain_body_particles(scene_data);
             self.maintain_boost_particles(scene_data);
+            self.maintain_burning_particles(scene_data);
             self.maintain_beam_particles(scene_data);
+            self.maintain_throw_particles(scene_data);
             self.maintain_block_particles(scene_data, terrain);
             self.maintain_shockwave_particles(scene_data);
         } else {
@@ -156,9 +211,152 @@ impl ParticleMgr {
             self.scheduler.clear();
         }
 
+        if scene_data.decals_enabled {
+            let time = scene_data.state.get_time();
+            self.decals.retain(|d| d.alive_until > time);
+
+            self.maintain_footprint_decals(scene_data);
+            self.maintain_blood_decals(scene_data);
+        } else {
+            self.decals.clear();
+            self.footprints.clear();
+            self.last_health.clear();
+        }
+
         self.upload_particles(renderer);
     }
 
+    /// Adds a ground mark, evicting the oldest one if the pool is full.
+    fn add_decal(&mut self, decal: Particle) {
+        if self.decals.len() >= MAX_DECALS {
+            self.decals.pop_front();
+        }
+        self.decals.push_back(decal);
+    }
+
+    fn maintain_footprint_decals(&mut self, scene_data: &SceneData) {
+        span!(
+            _guard,
+            "footprint_decals",
+            "ParticleMgr::maintain_footprint_decals"
+        );
+        let state = scene_data.state;
+        let ecs = state.ecs();
+        let time = state.get_time();
+        let terrain = state.terrain();
+
+        let mut new_decals = Vec::new();
+        let mut seen = Vec::new();
+        for (entity, pos, vel, body, physics) in (
+            &ecs.entities(),
+            &ecs.read_storage::<Pos>(),
+            &ecs.read_storage::<Vel>(),
+            &ecs.read_storage::<Body>(),
+            &ecs.read_storage::<PhysicsState>(),
+        )
+            .join()
+        {
+            if matches!(body, Body::Object(_))
+                || !physics.on_ground
+                || vel.0.xy().magnitude_squared() < FOOTPRINT_MIN_SPEED.powi(2)
+            {
+                continue;
+            }
+
+            let ground_pos = (pos.0 - Vec3::unit_z() * 0.5).map(|e| e.floor() as i32);
+            let leaves_prints = match terrain.get(ground_pos).map(|b| b.kind()) {
+                Ok(BlockKind::Sand) => true,
+                Ok(BlockKind::Grass) => terrain
+                    .get_key(terrain.pos_key(ground_pos))
+                    .map_or(false, |chunk| {
+                        matches!(chunk.meta().biome(), BiomeKind::Snowlands)
+                    }),
+                _ => false,
+            };
+            if !leaves_prints {
+                continue;
+            }
+            seen.push(entity);
+
+            let (last_pos, left) = self.footprints.entry(entity).or_insert((pos.0, false));
+            if last_pos.xy().distance_squared(pos.0.xy()) < FOOTPRINT_STRIDE.powi(2) {
+                continue;
+            }
+
+            let dir = vel.0.xy().normalized();
+            let side = Vec2::new(-dir.y, dir.x) * if *left { 0.15 } else { -0.15 };
+            let print_pos = Vec3::new(
+                pos.0.x + side.x,
+                pos.0.y + side.y,
+                ground_pos.z as f32 + 1.0,
+            );
+            new_decals.push(Particle::new_beam(
+                Duration::from_secs(FOOTPRINT_LIFESPAN),
+                time,
+                ParticleMode::Footprint,
+                print_pos,
+                print_pos + Vec3::from(dir),
+            ));
+            *last_pos = pos.0;
+            *left = !*left;
+        }
+
+        // Forget entities that have stepped off printable ground
+        self.footprints.retain(|entity, _| seen.contains(entity));
+        for decal in new_decals {
+            self.add_decal(decal);
+        }
+    }
+
+    fn maintain_blood_decals(&mut self, scene_data: &SceneData) {
+        span!(_guard, "blood_decals", "ParticleMgr::maintain_blood_decals");
+        let state = scene_data.state;
+        let ecs = state.ecs();
+        let time = state.get_time();
+        let mut rng = thread_rng();
+
+        let mut new_decals = Vec::new();
+        let mut last_health = HashMap::new();
+        for (entity, pos, body, stats) in (
+            &ecs.entities(),
+            &ecs.read_storage::<Pos>(),
+            &ecs.read_storage::<Body>(),
+            &ecs.read_storage::<Stats>(),
+        )
+            .join()
+        {
+            if matches!(body, Body::Object(_)) {
+                continue;
+            }
+            let health = stats.health.current();
+            let lost = self
+                .last_health
+                .get(&entity)
+                .map_or(0, |last| last.saturating_sub(health));
+            if lost >= BLOOD_SPLAT_MIN_DAMAGE {
+                let splats = (lost / BLOOD_SPLAT_MIN_DAMAGE).min(4);
+                for _ in 0..splats {
+                    new_decals.push(Particle::new(
+                        Duration::from_secs(BLOOD_SPLAT_LIFESPAN),
+                        time,
+                        ParticleMode::BloodSplat,
+                        Vec3::new(
+                            pos.0.x + rng.gen_range(-0.6, 0.6),
+                            pos.0.y + rng.gen_range(-0.6, 0.6),
+                            pos.0.z.floor(),
+                        ),
+                    ));
+                }
+            }
+            last_health.insert(entity, health);
+        }
+
+        self.last_health = last_health;
+        for decal in new_decals {
+            self.add_decal(decal);
+        }
+    }
+
     fn maintain_body_particles(&mut self, scene_data: &SceneData) {
         span!(
             _guard,
@@ -354,6 +552,35 @@ impl ParticleMgr {
         }
     }
 
+    fn maintain_burning_particles(&mut self, scene_data: &SceneData) {
+        span!(
+            _guard,
+            "burning_particles",
+            "ParticleMgr::maintain_burning_particles"
+        );
+        let state = scene_data.state;
+        let ecs = state.ecs();
+        let time = state.get_time();
+
+        for (pos, buffs) in (&ecs.read_storage::<Pos>(), &ecs.read_storage::<Buffs>()).join() {
+            if buffs.kinds.contains_key(&BuffKind::Burning) {
+                for _ in 0..self.scheduler.heartbeats(Duration::from_millis(20)) {
+                    let offset = Vec3::new(
+                        thread_rng().gen_range(-0.4, 0.4),
+                        thread_rng().gen_range(-0.4, 0.4),
+                        thread_rng().gen_range(0.0, 1.6),
+                    );
+                    self.particles.push(Particle::new(
+                        Duration::from_millis(250),
+                        time,
+                        ParticleMode::CampfireFire,
+                        pos.0 + offset,
+                    ));
+                }
+            }
+        }
+    }
+
     fn maintain_beam_particles(&mut self, scene_data: &SceneData) {
         let state = scene_data.state;
         let ecs = state.ecs();
@@ -417,6 +644,44 @@ impl ParticleMgr {
         }
     }
 
+    fn maintain_throw_particles(&mut self, scene_data: &SceneData) {
+        span!(
+            _guard,
+            "throw_particles",
+            "ParticleMgr::maintain_throw_particles"
+        );
+        let state = scene_data.state;
+        let ecs = state.ecs();
+        let time = state.get_time();
+
+        for (pos, ori, character_state, controller) in (
+            &ecs.read_storage::<Pos>(),
+            &ecs.read_storage::<Ori>(),
+            &ecs.read_storage::<CharacterState>(),
+            ecs.read_storage::<Controller>().maybe(),
+        )
+            .join()
+        {
+            if let CharacterState::Throw(t) = character_state {
+                if t.stage_section == StageSection::Buildup {
+                    // Only the local player knows where they're aiming, everyone else
+                    // previews along their orientation
+                    let dir = controller.map_or(ori.0, |c| c.inputs.look_dir);
+                    for _ in 0..self.scheduler.heartbeats(Duration::from_millis(50)) {
+                        for point in throw::arc_preview(pos.0, dir, 16, 0.05) {
+                            self.particles.push(Particle::new(
+                                Duration::from_millis(50),
+                                time,
+                                ParticleMode::GunPowderSpark,
+                                point,
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
     #[allow(clippy::same_item_push)] // TODO: Pending review in #587
     fn maintain_block_particles(
         &mut self,
@@ -502,6 +767,14 @@ impl ParticleMgr {
                 mode: ParticleMode::Bee,
                 cond: |sd| sd.state.get_day_period().is_light(),
             },
+            BlockParticles {
+                blocks: |boi| &boi.waterfalls,
+                range: 4,
+                rate: 4.0,
+                lifetime: 1.5,
+                mode: ParticleMode::WaterfallSpray,
+                cond: |_| true,
+            },
         ];
 
         let mut rng = thread_rng();
@@ -617,6 +890,7 @@ impl ParticleMgr {
         let all_cpu_instances = self
             .particles
             .iter()
+            .chain(self.decals.iter())
             .map(|p| p.instance)
             .collect::<Vec<ParticleInstance>>();
 
@@ -636,7 +910,7 @@ impl ParticleMgr {
         lod: &LodData,
     ) {
         span!(_guard, "render", "ParticleMgr::render");
-        if scene_data.particles_enabled {
+        if scene_data.particles_enabled || scene_data.decals_enabled {
             let model = &self
                 .model_cache
                 .get(DEFAULT_MODEL_KEY)
@@ -661,6 +935,25 @@ fn default_instances(renderer: &mut Renderer) -> Instances<ParticleInstance> {
 
 const DEFAULT_MODEL_KEY: &str = "voxygen.voxel.particle";
 
+/// Upper bound on live ground marks; the oldest is dropped to make room.
+const MAX_DECALS: usize = 512;
+/// Entities slower than this (in blocks per second) don't leave footprints.
+const FOOTPRINT_MIN_SPEED: f32 = 1.0;
+/// Distance walked between two footprints.
+const FOOTPRINT_STRIDE: f32 = 0.8;
+const FOOTPRINT_LIFESPAN: u64 = 20;
+/// Converts an explosion radius into the particle scale the shader expects.
+const SCORCH_SCALE: f32 = 8.0;
+const SCORCH_LIFESPAN: u64 = 30;
+/// Health lost in one tick before a blood splat is left on the ground.
+const BLOOD_SPLAT_MIN_DAMAGE: u32 = 100;
+const BLOOD_SPLAT_LIFESPAN: u64 = 15;
+/// Droplets thrown up by a splash for each block per second of entry speed.
+const SPLASH_DROPS_PER_SPEED: f32 = 4.0;
+const MAX_SPLASH_DROPS: f32 = 120.0;
+/// Entry speed above which a splash gets no higher.
+const MAX_SPLASH_SPEED: f32 = 25.0;
+
 fn default_cache(renderer: &mut Renderer) -> HashMap<&'static str, Model<ParticlePipeline>> {
     let mut model_cache = HashMap::new();
 
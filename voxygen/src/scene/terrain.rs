@@ -35,6 +35,11 @@ use treeculler::{BVol, Frustum, AABB};
 use vek::*;
 
 const SPRITE_SCALE: Vec3<f32> = Vec3::new(1.0 / 11.0, 1.0 / 11.0, 1.0 / 11.0);
+/// Width in blocks of the cells that the sprites of a chunk are grouped into
+/// for culling
+const SPRITE_CELL_SIZE: i32 = 16;
+/// How far the model of a sprite can reach out of its block, in blocks
+const SPRITE_CELL_PADDING: f32 = 4.0;
 
 #[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
 enum Visibility {
@@ -49,7 +54,7 @@ pub struct TerrainChunkData {
     opaque_model: Model<TerrainPipeline>,
     fluid_model: Option<Model<FluidPipeline>>,
     col_lights: guillotiere::AllocId,
-    sprite_instances: HashMap<(SpriteKind, usize), Instances<SpriteInstance>>,
+    sprite_cells: Vec<SpriteCell<Instances<SpriteInstance>>>,
     locals: Consts<TerrainLocals>,
     pub blocks_of_interest: BlocksOfInterest,
 
@@ -75,11 +80,26 @@ struct MeshWorkerResponse {
     opaque_mesh: Mesh<TerrainPipeline>,
     fluid_mesh: Mesh<FluidPipeline>,
     col_lights_info: ColLightInfo,
-    sprite_instances: HashMap<(SpriteKind, usize), Vec<SpriteInstance>>,
+    sprite_cells: Vec<SpriteCell<Vec<SpriteInstance>>>,
     started_tick: u64,
     blocks_of_interest: BlocksOfInterest,
 }
 
+/// The sprites of a chunk within a cell of `SPRITE_CELL_SIZE` columns. The
+/// instances of each cell are uploaded once when the chunk is meshed, and the
+/// cell is culled by distance and against the view frustum as a whole, so that
+/// nothing needs to be rebuilt as the camera moves.
+///
+/// TODO: Cull the instances on the GPU with a compute pre-pass and indirect
+/// draws once the renderer supports them (the gfx GL backend has neither).
+struct SpriteCell<I> {
+    /// World space bounds of the sprites in the cell
+    bounds: Aabb<f32>,
+    instances: HashMap<(SpriteKind, usize), I>,
+    visible: bool,
+    frustum_last_plane_index: u8,
+}
+
 #[derive(Deserialize)]
 /// Configuration data for an individual sprite model.
 struct SpriteModelConfig<Model> {
@@ -135,9 +155,9 @@ fn mesh_worker<V: BaseVol<Vox = Block> + RectRasterableVol + ReadVol + Debug>(
         fluid_mesh,
         col_lights_info,
         // Extract sprite locations from volume
-        sprite_instances: {
+        sprite_cells: {
             span!(_guard, "extract sprite_instances");
-            let mut instances = HashMap::new();
+            let mut cells = HashMap::new();
 
             for x in 0..V::RECT_SIZE.x as i32 {
                 for y in 0..V::RECT_SIZE.y as i32 {
@@ -179,13 +199,30 @@ fn mesh_worker<V: BaseVol<Vox = Block> + RectRasterableVol + ReadVol + Debug>(
                                 ori,
                             );
 
-                            instances.entry(key).or_insert(Vec::new()).push(instance);
+                            let wpos = wpos.map(|e| e as f32);
+                            let bounds = Aabb {
+                                min: wpos - SPRITE_CELL_PADDING,
+                                max: wpos + 1.0 + SPRITE_CELL_PADDING,
+                            };
+                            let cell = cells
+                                .entry(rel_pos.xy() / SPRITE_CELL_SIZE)
+                                .or_insert_with(|| SpriteCell {
+                                    bounds,
+                                    instances: HashMap::new(),
+                                    visible: false,
+                                    frustum_last_plane_index: 0,
+                                });
+                            cell.bounds.expand_to_contain(bounds);
+                            cell.instances
+                                .entry(key)
+                                .or_insert(Vec::new())
+                                .push(instance);
                         }
                     }
                 }
             }
 
-            instances
+            cells.into_iter().map(|(_, cell)| cell).collect()
         },
         blocks_of_interest: BlocksOfInterest::from_chunk(&chunk),
         started_tick,
@@ -721,16 +758,26 @@ impl<V: RectRasterableVol> Terrain<V> {
                             None
                         },
                         col_lights: allocation.id,
-                        sprite_instances: response
-                            .sprite_instances
+                        sprite_cells: response
+                            .sprite_cells
                             .into_iter()
-                            .map(|(kind, instances)| {
-                                (
-                                    kind,
-                                    renderer.create_instances(&instances).expect(
-                                        "Failed to upload chunk sprite instances to the GPU!",
-                                    ),
-                                )
+                            .map(|cell| SpriteCell {
+                                bounds: cell.bounds,
+                                instances: cell
+                                    .instances
+                                    .into_iter()
+                                    .map(|(kind, instances)| {
+                                        (
+                                            kind,
+                                            renderer.create_instances(&instances).expect(
+                                                "Failed to upload chunk sprite instances to the \
+                                                 GPU!",
+                                            ),
+                                        )
+                                    })
+                                    .collect(),
+                                visible: false,
+                                frustum_last_plane_index: 0,
                             })
                             .collect(),
                         locals: renderer
@@ -815,6 +862,17 @@ impl<V: RectRasterableVol> Terrain<V> {
             } else {
                 Visibility::InRange
             };
+
+            // Cull the sprites of visible chunks a cell at a time
+            for cell in &mut chunk.sprite_cells {
+                cell.visible = in_frustum && {
+                    let (cell_in_frustum, last_plane_index) =
+                        AABB::new(cell.bounds.min.into_array(), cell.bounds.max.into_array())
+                            .coherent_test_against_frustum(&frustum, cell.frustum_last_plane_index);
+                    cell.frustum_last_plane_index = last_plane_index;
+                    cell_in_frustum
+                };
+            }
             let chunk_box = Aabb {
                 min: Vec3::from(chunk_min),
                 max: Vec3::from(chunk_max),
@@ -1090,7 +1148,6 @@ impl<V: RectRasterableVol> Terrain<V> {
                 let sprite_high_detail_distance = sprite_render_distance * 0.15;
 
                 let chunk_center = pos.map2(chunk_size, |e, sz| (e as f32 + 0.5) * sz);
-                let focus_dist_sqrd = Vec2::from(focus_pos).distance_squared(chunk_center);
                 let dist_sqrd =
                     Vec2::from(cam_pos)
                         .distance_squared(chunk_center)
@@ -1104,8 +1161,15 @@ impl<V: RectRasterableVol> Terrain<V> {
                         .min(Vec2::from(cam_pos).distance_squared(
                             chunk_center + chunk_size.x * 0.5 - chunk_size.y * 0.5,
                         ));
-                if focus_dist_sqrd < sprite_render_distance.powf(2.0) {
-                    for (kind, instances) in (&chunk.sprite_instances).into_iter() {
+                for cell in chunk.sprite_cells.iter().filter(|cell| cell.visible) {
+                    let nearest_in_cell = Vec2::from(focus_pos)
+                        .clamped(Vec2::from(cell.bounds.min), Vec2::from(cell.bounds.max));
+                    if Vec2::from(focus_pos).distance_squared(nearest_in_cell)
+                        >= sprite_render_distance.powf(2.0)
+                    {
+                        continue;
+                    }
+                    for (kind, instances) in (&cell.instances).into_iter() {
                         let SpriteData { model, locals, .. } = if kind
                             .0
                             .elim_case_pure(&self.sprite_config)
@@ -1,11 +1,14 @@
 use common::{
     span,
     terrain::{BlockKind, SpriteKind, TerrainChunk},
-    vol::{IntoVolIterator, RectRasterableVol},
+    vol::{IntoVolIterator, ReadVol, RectRasterableVol},
 };
 use rand::prelude::*;
 use vek::*;
 
+/// How many blocks water has to fall to make spray where it lands
+const WATERFALL_MIN_DROP: i32 = 4;
+
 pub struct BlocksOfInterest {
     pub leaves: Vec<Vec3<i32>>,
     pub grass: Vec<Vec3<i32>>,
@@ -13,6 +16,10 @@ pub struct BlocksOfInterest {
     pub beehives: Vec<Vec3<i32>>,
     pub reeds: Vec<Vec3<i32>>,
     pub flowers: Vec<Vec3<i32>>,
+    pub windows: Vec<Vec3<i32>>,
+    pub glow_mushrooms: Vec<Vec3<i32>>,
+    /// The surface of the water at the foot of waterfalls
+    pub waterfalls: Vec<Vec3<i32>>,
 }
 
 impl BlocksOfInterest {
@@ -24,6 +31,9 @@ impl BlocksOfInterest {
         let mut beehives = Vec::new();
         let mut reeds = Vec::new();
         let mut flowers = Vec::new();
+        let mut windows = Vec::new();
+        let mut glow_mushrooms = Vec::new();
+        let mut waterfalls = Vec::new();
 
         chunk
             .vol_iter(
@@ -45,6 +55,7 @@ impl BlocksOfInterest {
                         grass.push(pos)
                     }
                 },
+                BlockKind::Water if is_waterfall_foot(chunk, pos) => waterfalls.push(pos),
                 _ => match block.get_sprite() {
                     Some(SpriteKind::Ember) => embers.push(pos),
                     Some(SpriteKind::Beehive) => beehives.push(pos),
@@ -55,6 +66,11 @@ impl BlocksOfInterest {
                     Some(SpriteKind::WhiteFlower) => flowers.push(pos),
                     Some(SpriteKind::YellowFlower) => flowers.push(pos),
                     Some(SpriteKind::Sunflower) => flowers.push(pos),
+                    Some(SpriteKind::Window1)
+                    | Some(SpriteKind::Window2)
+                    | Some(SpriteKind::Window3)
+                    | Some(SpriteKind::Window4) => windows.push(pos),
+                    Some(SpriteKind::GlowMushroom) => glow_mushrooms.push(pos),
                     _ => {},
                 },
             });
@@ -66,6 +82,24 @@ impl BlocksOfInterest {
             beehives,
             reeds,
             flowers,
+            windows,
+            glow_mushrooms,
+            waterfalls,
         }
     }
 }
+
+/// Whether a block is on the surface of the water at the foot of a waterfall,
+/// next to water that falls from well above it
+fn is_waterfall_foot(chunk: &TerrainChunk, pos: Vec3<i32>) -> bool {
+    let is_liquid = |pos| chunk.get(pos).map_or(false, |block| block.is_liquid());
+    !is_liquid(pos + Vec3::unit_z())
+        && [
+            -Vec3::unit_x(),
+            Vec3::unit_x(),
+            -Vec3::unit_y(),
+            Vec3::unit_y(),
+        ]
+        .iter()
+        .any(|dir| is_liquid(pos + *dir + Vec3::unit_z() * WATERFALL_MIN_DROP))
+}
@@ -1,5 +1,6 @@
 use crate::{
     audio::sfx::{SfxEvent, SfxEventItem},
+    bug_report::BugReport,
     ecs::MyEntity,
     hud::{DebugInfo, Event as HudEvent, Hud, HudInfo, PressBehavior},
     i18n::{i18n_asset_key, VoxygenLocalization},
@@ -19,16 +20,19 @@ use common::{
         ChatMsg, ChatType, InventoryUpdateEvent, Pos, Vel, MAX_MOUNT_RANGE_SQR,
         MAX_PICKUP_RANGE_SQR,
     },
+    cutscene::CutsceneEvent,
     event::EventBus,
     outcome::Outcome,
     span,
+    states::revive::REVIVE_RANGE,
+    sync::Uid,
     terrain::{Block, BlockKind},
     util::Dir,
     vol::ReadVol,
 };
 use specs::{Join, WorldExt};
 use std::{cell::RefCell, rc::Rc, sync::Arc, time::Duration};
-use tracing::{error, info};
+use tracing::{error, info, warn};
 use vek::*;
 
 /// The action to perform after a tick
@@ -53,6 +57,8 @@ pub struct SessionState {
     free_look: bool,
     auto_walk: bool,
     is_aiming: bool,
+    /// Whether a scripted sequence is holding back the player's inputs
+    input_locked: bool,
     target_entity: Option<specs::Entity>,
     selected_entity: Option<(specs::Entity, std::time::Instant)>,
 }
@@ -68,9 +74,13 @@ impl SessionState {
             &*client.borrow(),
             &global_state.settings,
         );
-        scene
-            .camera_mut()
-            .set_fov_deg(global_state.settings.graphics.fov);
+        let gameplay = &global_state.settings.gameplay;
+        let camera = scene.camera_mut();
+        camera.set_fov_deg(global_state.settings.graphics.fov);
+        camera.set_distance_limits(gameplay.camera_min_distance, gameplay.camera_max_distance);
+        camera.set_shoulder_offset(gameplay.camera_shoulder_offset);
+        camera.set_smoothing(gameplay.camera_smoothing);
+        camera.set_pan_acceleration(gameplay.camera_acceleration);
         let hud = Hud::new(global_state, &client.borrow());
         let voxygen_i18n = VoxygenLocalization::load_expect(&i18n_asset_key(
             &global_state.settings.language.selected_language,
@@ -93,11 +103,23 @@ impl SessionState {
             free_look: false,
             auto_walk: false,
             is_aiming: false,
+            input_locked: false,
             target_entity: None,
             selected_entity: None,
         }
     }
 
+    /// Holds back or gives back the player's inputs, letting go of everything
+    /// held down so that nothing is stuck once they are given back
+    fn lock_input(&mut self, locked: bool) {
+        if locked && !self.input_locked {
+            self.stop_auto_walk();
+            self.key_state = KeyState::default();
+            self.inputs = comp::ControllerInputs::default();
+        }
+        self.input_locked = locked;
+    }
+
     fn stop_auto_walk(&mut self) {
         self.auto_walk = false;
         self.hud.auto_walk(false);
@@ -114,6 +136,7 @@ impl SessionState {
         span!(_guard, "tick", "Session::tick");
         self.inputs.tick(dt);
 
+        let mut input_lock = None;
         let mut client = self.client.borrow_mut();
         for event in client.tick(self.inputs.clone(), dt, crate::ecs::sys::add_local_systems)? {
             match event {
@@ -178,14 +201,56 @@ impl SessionState {
                     global_state.settings.save_to_file_warn();
                 },
                 client::Event::Outcome(outcome) => outcomes.push(outcome),
+                client::Event::Market(market) => self.hud.show_market(market),
+                client::Event::Cutscene(event) => match event {
+                    CutsceneEvent::Camera {
+                        pos,
+                        focus,
+                        duration,
+                    } => self.scene.play_cutscene_shot(pos, focus, duration),
+                    CutsceneEvent::LockInput(locked) => input_lock = Some(locked),
+                    CutsceneEvent::End => {
+                        self.scene.end_cutscene();
+                        input_lock = Some(false);
+                    },
+                },
             }
         }
+        drop(client);
+
+        if let Some(locked) = input_lock {
+            self.lock_input(locked);
+        }
 
         Ok(TickAction::Continue)
     }
 
     /// Clean up the session (and the client attached to it) after a tick.
     pub fn cleanup(&mut self) { self.client.borrow_mut().cleanup(); }
+
+    /// Captures the state of the game into a bug report, which is saved on
+    /// another thread. Where it was saved to is shown in the chat.
+    fn report_bug(&mut self, global_state: &mut GlobalState, description: String) {
+        let mut report = BugReport::capture(&self.client.borrow(), description);
+        if global_state.settings.gameplay.bug_report_screenshot {
+            match global_state.window.renderer_mut().create_screenshot() {
+                Ok(screenshot) => report = report.with_screenshot(screenshot),
+                Err(e) => warn!(?e, "Couldn't take a screenshot for the bug report"),
+            }
+        }
+
+        let logs_path = global_state.settings.log.logs_path.clone();
+        let sender = global_state.window.message_sender();
+        std::thread::spawn(move || {
+            let _result = sender.send(match report.save(&logs_path) {
+                Ok(path) => format!("Bug report saved to {}", path.to_string_lossy()),
+                Err(e) => {
+                    warn!(?e, "Couldn't save bug report");
+                    String::from("Couldn't save bug report")
+                },
+            });
+        });
+    }
 }
 
 impl PlayState for SessionState {
@@ -258,6 +323,28 @@ impl PlayState for SessionState {
             };
             self.is_aiming = is_aiming;
 
+            // With the camera over a shoulder, its direction no longer passes through the
+            // character, so aim from the focus at whatever is under the crosshair instead
+            let aim_dir = if self.scene.camera().get_mode() == CameraMode::ThirdPerson
+                && self.scene.camera().shoulder_offset() != 0.0
+            {
+                let aim_dist = self
+                    .client
+                    .borrow()
+                    .state()
+                    .terrain()
+                    .ray(cam_pos, cam_pos + cam_dir * MAX_TARGET_RANGE)
+                    .max_iter(500)
+                    .until(|block| block.is_filled())
+                    .cast()
+                    .0;
+                (cam_pos + cam_dir * aim_dist - focus_pos)
+                    .try_normalized()
+                    .unwrap_or(cam_dir)
+            } else {
+                cam_dir
+            };
+
             // Check to see whether we're aiming at anything
             let (build_pos, select_pos, target_entity) =
                 under_cursor(&self.client.borrow(), cam_pos, cam_dir);
@@ -272,14 +359,16 @@ impl PlayState for SessionState {
                 .get(self.client.borrow().entity())
                 .is_some();
 
-            // Only highlight collectables
+            // Only highlight collectables and locked blocks
             self.scene.set_select_pos(select_pos.filter(|sp| {
                 self.client
                     .borrow()
                     .state()
                     .terrain()
                     .get(*sp)
-                    .map(|b| b.is_collectible() || can_build)
+                    .map(|b| {
+                        b.is_collectible() || b.is_locked() || b.is_bounty_board() || can_build
+                    })
                     .unwrap_or(false)
             }));
 
@@ -290,6 +379,19 @@ impl PlayState for SessionState {
                     continue;
                 }
 
+                // Scripted sequences can hold back control of the character and camera
+                if self.input_locked
+                    && matches!(
+                        event,
+                        Event::InputUpdate(..)
+                            | Event::AnalogGameInput(_)
+                            | Event::CursorPan(_)
+                            | Event::Zoom(_)
+                    )
+                {
+                    continue;
+                }
+
                 match event {
                     Event::Close => {
                         return PlayStateResult::Shutdown;
@@ -352,6 +454,9 @@ impl PlayState for SessionState {
                     Event::InputUpdate(GameInput::SwimDown, state) => {
                         self.inputs.swimdown.set_state(state);
                     },
+                    Event::InputUpdate(GameInput::SwimSprint, state) => {
+                        self.inputs.swimsprint.set_state(state);
+                    },
                     Event::InputUpdate(GameInput::Sit, state)
                         if state != self.key_state.toggle_sit =>
                     {
@@ -370,7 +475,14 @@ impl PlayState for SessionState {
                             self.stop_auto_walk();
                             self.client.borrow_mut().toggle_dance();
                         }
-                    }
+                    },
+                    Event::InputUpdate(GameInput::Fish, state) if state != self.key_state.fish => {
+                        self.key_state.fish = state;
+                        if state {
+                            self.stop_auto_walk();
+                            self.client.borrow_mut().fish();
+                        }
+                    },
                     Event::InputUpdate(GameInput::Sneak, state)
                         if state != self.key_state.toggle_sneak =>
                     {
@@ -379,6 +491,15 @@ impl PlayState for SessionState {
                             self.stop_auto_walk();
                             self.client.borrow_mut().toggle_sneak();
                         }
+                    },
+                    Event::InputUpdate(GameInput::Crouch, state)
+                        if state != self.key_state.toggle_crouch =>
+                    {
+                        self.key_state.toggle_crouch = state;
+                        if state {
+                            self.stop_auto_walk();
+                            self.client.borrow_mut().toggle_crouch();
+                        }
                     }
                     Event::InputUpdate(GameInput::MoveForward, state) => {
                         if state && global_state.settings.gameplay.stop_auto_walk_on_input {
@@ -500,38 +621,87 @@ impl PlayState for SessionState {
                         if state {
                             let mut client = self.client.borrow_mut();
 
-                            // Collect terrain sprites
-                            if let Some(select_pos) = self.scene.select_pos() {
-                                client.collect_block(select_pos);
-                            }
-
-                            // Collect lootable entities
-                            let player_pos = client
-                                .state()
-                                .read_storage::<comp::Pos>()
-                                .get(client.entity())
-                                .copied();
-
-                            if let Some(player_pos) = player_pos {
-                                let entity = self.target_entity.or_else(|| {
+                            // Revive downed group members before interacting with anything else
+                            let downed_member = {
+                                let ecs = client.state().ecs();
+                                let positions = ecs.read_storage::<comp::Pos>();
+                                let groups = ecs.read_storage::<comp::Group>();
+                                let player_pos = positions.get(client.entity()).copied();
+                                let group = groups.get(client.entity()).copied();
+                                player_pos.zip(group).and_then(|(player_pos, group)| {
                                     (
-                                        &client.state().ecs().entities(),
-                                        &client.state().ecs().read_storage::<comp::Pos>(),
-                                        &client.state().ecs().read_storage::<comp::Item>(),
+                                        &ecs.read_storage::<Uid>(),
+                                        &positions,
+                                        &groups,
+                                        &ecs.read_storage::<comp::CharacterState>(),
                                     )
                                         .join()
-                                        .filter(|(_, pos, _)| {
-                                            pos.0.distance_squared(player_pos.0)
-                                                < MAX_PICKUP_RANGE_SQR
+                                        .filter(|(_, pos, member_group, character_state)| {
+                                            **member_group == group
+                                                && character_state.is_downed()
+                                                && pos.0.distance_squared(player_pos.0)
+                                                    < REVIVE_RANGE.powi(2)
                                         })
-                                        .min_by_key(|(_, pos, _)| {
-                                            (pos.0.distance_squared(player_pos.0) * 1000.0) as i32
-                                        })
-                                        .map(|(entity, _, _)| entity)
-                                });
+                                        .map(|(uid, _, _, _)| *uid)
+                                        .next()
+                                })
+                            };
+
+                            if let Some(target) = downed_member {
+                                client.revive(target);
+                            } else {
+                                // Collect terrain sprites, unlock locked ones or read bounty
+                                // boards
+                                if let Some(select_pos) = self.scene.select_pos() {
+                                    let block =
+                                        client.state().terrain().get(select_pos).ok().copied();
+                                    if block.map_or(false, |b| b.is_locked()) {
+                                        client.unlock_block(select_pos);
+                                    } else if block.map_or(false, |b| b.is_bounty_board()) {
+                                        client.read_bounty_board(select_pos);
+                                    } else {
+                                        client.collect_block(select_pos);
+                                    }
+                                }
 
-                                if let Some(entity) = entity {
-                                    client.pick_up(entity);
+                                // Collect lootable entities
+                                let player_pos = client
+                                    .state()
+                                    .read_storage::<comp::Pos>()
+                                    .get(client.entity())
+                                    .copied();
+
+                                if let Some(player_pos) = player_pos {
+                                    let entity = self.target_entity.or_else(|| {
+                                        (
+                                            &client.state().ecs().entities(),
+                                            &client.state().ecs().read_storage::<comp::Pos>(),
+                                            &client.state().ecs().read_storage::<comp::Item>(),
+                                        )
+                                            .join()
+                                            .filter(|(_, pos, _)| {
+                                                pos.0.distance_squared(player_pos.0)
+                                                    < MAX_PICKUP_RANGE_SQR
+                                            })
+                                            .min_by_key(|(_, pos, _)| {
+                                                (pos.0.distance_squared(player_pos.0) * 1000.0)
+                                                    as i32
+                                            })
+                                            .map(|(entity, _, _)| entity)
+                                    });
+
+                                    // Talk to targeted NPCs rather than picking them up
+                                    if let Some(entity) = entity {
+                                        if client
+                                            .state()
+                                            .read_storage::<comp::Item>()
+                                            .contains(entity)
+                                        {
+                                            client.pick_up(entity);
+                                        } else {
+                                            client.talk(entity);
+                                        }
+                                    }
                                 }
                             }
                         }
@@ -621,7 +791,7 @@ impl PlayState for SessionState {
             if !self.free_look {
                 self.walk_forward_dir = self.scene.camera().forward_xy();
                 self.walk_right_dir = self.scene.camera().right_xy();
-                self.inputs.look_dir = Dir::from_unnormalized(cam_dir + aim_dir_offset).unwrap();
+                self.inputs.look_dir = Dir::from_unnormalized(aim_dir + aim_dir_offset).unwrap();
             }
 
             // Get the current state of movement related inputs
@@ -777,10 +947,19 @@ impl PlayState for SessionState {
             // Maintain the UI.
             for event in hud_events {
                 match event {
+                    // Bug reports are made by the client, so the command never reaches the
+                    // server
+                    HudEvent::SendMessage(msg) if msg == "/bug" || msg.starts_with("/bug ") => {
+                        let description = msg.trim_start_matches("/bug").trim().to_string();
+                        self.report_bug(global_state, description);
+                    },
                     HudEvent::SendMessage(msg) => {
                         // TODO: Handle result
                         self.client.borrow_mut().send_chat(msg);
                     },
+                    HudEvent::ReportBug(description) => {
+                        self.report_bug(global_state, description);
+                    },
                     HudEvent::CharacterSelection => {
                         self.client.borrow_mut().request_remove_character()
                     },
@@ -930,7 +1109,18 @@ impl PlayState for SessionState {
                         client.remove_buff(buff_id);
                     },
                     HudEvent::UseSlot(x) => self.client.borrow_mut().use_slot(x),
+                    HudEvent::PlayNote(note) => self.client.borrow_mut().play_note(note),
+                    HudEvent::PlaySong(song) => self.client.borrow_mut().play_song(song),
                     HudEvent::SwapSlots(a, b) => self.client.borrow_mut().swap_slots(a, b),
+                    HudEvent::DyeArmor(dye, equip_slot) => {
+                        self.client.borrow_mut().dye_armor(dye, equip_slot)
+                    },
+                    HudEvent::SetAppearance(slot, equip_slot) => {
+                        self.client.borrow_mut().set_appearance(slot, equip_slot)
+                    },
+                    HudEvent::ClearAppearance(equip_slot) => {
+                        self.client.borrow_mut().clear_appearance(equip_slot)
+                    },
                     HudEvent::DropSlot(x) => {
                         let mut client = self.client.borrow_mut();
                         client.drop_slot(x);
@@ -1009,6 +1199,10 @@ impl PlayState for SessionState {
                         global_state.settings.graphics.particles_enabled = particles_enabled;
                         global_state.settings.save_to_file_warn();
                     },
+                    HudEvent::ToggleDecalsEnabled(decals_enabled) => {
+                        global_state.settings.graphics.decals_enabled = decals_enabled;
+                        global_state.settings.save_to_file_warn();
+                    },
                     HudEvent::AdjustWindowSize(new_size) => {
                         global_state.window.set_size(new_size.into());
                         global_state.settings.graphics.window_size = new_size;
@@ -1030,9 +1224,32 @@ impl PlayState for SessionState {
                     HudEvent::ChangeStopAutoWalkOnInput(state) => {
                         global_state.settings.gameplay.stop_auto_walk_on_input = state;
                     },
+                    HudEvent::AdjustCameraShake(camera_shake) => {
+                        global_state.settings.gameplay.camera_shake = camera_shake;
+                        global_state.settings.save_to_file_warn();
+                    },
+                    HudEvent::AdjustHitstop(hitstop) => {
+                        global_state.settings.gameplay.hitstop = hitstop;
+                        global_state.settings.save_to_file_warn();
+                    },
+                    HudEvent::AdjustShoulderOffset(offset) => {
+                        global_state.settings.gameplay.camera_shoulder_offset = offset;
+                        global_state.settings.save_to_file_warn();
+                        self.scene.camera_mut().set_shoulder_offset(offset);
+                    },
                     HudEvent::CraftRecipe(r) => {
                         self.client.borrow_mut().craft_recipe(&r);
                     },
+                    HudEvent::Trade {
+                        merchant,
+                        good,
+                        action,
+                    } => {
+                        self.client.borrow_mut().trade(merchant, good, action);
+                    },
+                    HudEvent::SelectAbility { tool, slot, index } => {
+                        self.client.borrow_mut().select_ability(tool, slot, index);
+                    },
                     HudEvent::InviteMember(uid) => {
                         self.client.borrow_mut().send_group_invite(uid);
                     },
@@ -1070,12 +1287,16 @@ impl PlayState for SessionState {
                     sprite_render_distance: global_state.settings.graphics.sprite_render_distance
                         as f32,
                     particles_enabled: global_state.settings.graphics.particles_enabled,
+                    decals_enabled: global_state.settings.graphics.decals_enabled,
                     figure_lod_render_distance: global_state
                         .settings
                         .graphics
                         .figure_lod_render_distance
                         as f32,
                     is_aiming,
+                    outline_group_members: global_state.settings.graphics.outline_group_members,
+                    camera_shake: global_state.settings.gameplay.camera_shake,
+                    hitstop: global_state.settings.gameplay.hitstop,
                 };
 
                 // Runs if either in a multiplayer server or the singleplayer server is unpaused
@@ -1134,7 +1355,11 @@ impl PlayState for SessionState {
                 sprite_render_distance: settings.graphics.sprite_render_distance as f32,
                 figure_lod_render_distance: settings.graphics.figure_lod_render_distance as f32,
                 particles_enabled: settings.graphics.particles_enabled,
+                decals_enabled: settings.graphics.decals_enabled,
                 is_aiming: self.is_aiming,
+                outline_group_members: settings.graphics.outline_group_members,
+                camera_shake: settings.gameplay.camera_shake,
+                hitstop: settings.gameplay.hitstop,
             };
             self.scene.render(
                 renderer,
@@ -1176,7 +1401,12 @@ fn under_cursor(
 
     let cam_ray = terrain
         .ray(cam_pos, cam_pos + cam_dir * 100.0)
-        .until(|block| block.is_filled() || block.is_collectible())
+        .until(|block| {
+            block.is_filled()
+                || block.is_collectible()
+                || block.is_locked()
+                || block.is_bounty_board()
+        })
         .cast();
 
     let cam_dist = cam_ray.0;
@@ -5,11 +5,12 @@ use crate::{
     ui::ScaleMode,
     window::{FullScreenSettings, GameInput, KeyMouse},
 };
+use common::assets::pack::{self, AssetPack};
 use directories_next::UserDirs;
 use hashbrown::{HashMap, HashSet};
 use serde::{Deserialize, Serialize};
 use std::{fs, path::PathBuf};
-use tracing::warn;
+use tracing::{info, warn};
 use winit::event::{MouseButton, VirtualKeyCode};
 
 // ControlSetting-like struct used by Serde, to handle not serializing/building
@@ -136,7 +137,9 @@ impl ControlSettings {
             GameInput::ClimbDown => KeyMouse::Key(VirtualKeyCode::LControl),
             GameInput::SwimUp => KeyMouse::Key(VirtualKeyCode::Space),
             GameInput::SwimDown => KeyMouse::Key(VirtualKeyCode::LShift),
+            GameInput::SwimSprint => KeyMouse::Key(VirtualKeyCode::LControl),
             GameInput::Sneak => KeyMouse::Key(VirtualKeyCode::LControl),
+            GameInput::Crouch => KeyMouse::Key(VirtualKeyCode::X),
             //GameInput::WallLeap => MIDDLE_CLICK_KEY,
             GameInput::ToggleLantern => KeyMouse::Key(VirtualKeyCode::G),
             GameInput::Mount => KeyMouse::Key(VirtualKeyCode::F),
@@ -145,6 +148,7 @@ impl ControlSettings {
             GameInput::Social => KeyMouse::Key(VirtualKeyCode::O),
             GameInput::Crafting => KeyMouse::Key(VirtualKeyCode::C),
             GameInput::Spellbook => KeyMouse::Key(VirtualKeyCode::P),
+            GameInput::CharacterSheet => KeyMouse::Key(VirtualKeyCode::V),
             GameInput::Settings => KeyMouse::Key(VirtualKeyCode::N),
             GameInput::Help => KeyMouse::Key(VirtualKeyCode::F1),
             GameInput::ToggleInterface => KeyMouse::Key(VirtualKeyCode::F2),
@@ -155,6 +159,7 @@ impl ControlSettings {
             GameInput::Roll => MIDDLE_CLICK_KEY,
             GameInput::Respawn => KeyMouse::Key(VirtualKeyCode::Space),
             GameInput::Interact => KeyMouse::Key(VirtualKeyCode::E),
+            GameInput::Fish => KeyMouse::Key(VirtualKeyCode::R),
             GameInput::ToggleWield => KeyMouse::Key(VirtualKeyCode::T),
             //GameInput::Charge => KeyMouse::Key(VirtualKeyCode::Key1),
             GameInput::FreeLook => KeyMouse::Key(VirtualKeyCode::L),
@@ -203,7 +208,9 @@ impl Default for ControlSettings {
             GameInput::ClimbDown,
             GameInput::SwimUp,
             GameInput::SwimDown,
+            GameInput::SwimSprint,
             GameInput::Sneak,
+            GameInput::Crouch,
             //GameInput::WallLeap,
             GameInput::ToggleLantern,
             GameInput::Mount,
@@ -215,6 +222,7 @@ impl Default for ControlSettings {
             GameInput::Social,
             GameInput::Crafting,
             GameInput::Spellbook,
+            GameInput::CharacterSheet,
             GameInput::Settings,
             GameInput::ToggleInterface,
             GameInput::Help,
@@ -225,6 +233,7 @@ impl Default for ControlSettings {
             GameInput::Roll,
             GameInput::Respawn,
             GameInput::Interact,
+            GameInput::Fish,
             GameInput::ToggleWield,
             //GameInput::Charge,
             GameInput::FreeLook,
@@ -314,6 +323,7 @@ pub mod con_settings {
         pub climb_down: Button,
         pub swimup: Button,
         pub swimdown: Button,
+        pub swimsprint: Button,
         pub sneak: Button,
         //pub wall_leap: Button,
         pub toggle_lantern: Button,
@@ -406,6 +416,7 @@ pub mod con_settings {
                 climb_down: Button::Simple(GilButton::Unknown),
                 swimup: Button::Simple(GilButton::South),
                 swimdown: Button::Simple(GilButton::Unknown),
+                swimsprint: Button::Simple(GilButton::Unknown),
                 sneak: Button::Simple(GilButton::Unknown),
                 //wall_leap: Button::Simple(GilButton::Unknown),
                 toggle_lantern: Button::Simple(GilButton::East),
@@ -500,6 +511,21 @@ pub struct GameplaySettings {
     pub speech_bubble_icon: bool,
     pub mouse_y_inversion: bool,
     pub smooth_pan_enable: bool,
+    /// How quickly smoothed panning catches up with the cursor
+    pub camera_smoothing: f32,
+    /// How much faster quick cursor movements turn the camera
+    pub camera_acceleration: f32,
+    pub camera_min_distance: f32,
+    /// Farthest the third-person camera can zoom out, with no limit if unset
+    pub camera_max_distance: Option<f32>,
+    /// Sideways offset of the third-person camera, positive to the right
+    pub camera_shoulder_offset: f32,
+    /// Strength of the camera shake from big impacts and explosions, from 0.0
+    /// for none to 1.0 for full
+    pub camera_shake: f32,
+    /// Length of the pause in animations when a heavy hit lands, from 0.0 for
+    /// none to 1.0 for full
+    pub hitstop: f32,
     pub crosshair_transp: f32,
     pub chat_transp: f32,
     pub chat_character_name: bool,
@@ -515,6 +541,8 @@ pub struct GameplaySettings {
     pub stop_auto_walk_on_input: bool,
     pub map_zoom: f64,
     pub loading_tips: bool,
+    /// Whether bug reports include a screenshot
+    pub bug_report_screenshot: bool,
 }
 
 impl Default for GameplaySettings {
@@ -525,6 +553,13 @@ impl Default for GameplaySettings {
             zoom_inversion: false,
             mouse_y_inversion: false,
             smooth_pan_enable: true,
+            camera_smoothing: 15.0,
+            camera_acceleration: 0.0,
+            camera_min_distance: 2.0,
+            camera_max_distance: None,
+            camera_shoulder_offset: 0.0,
+            camera_shake: 1.0,
+            hitstop: 1.0,
             toggle_debug: false,
             sct: true,
             sct_player_batch: true,
@@ -546,6 +581,7 @@ impl Default for GameplaySettings {
             stop_auto_walk_on_input: true,
             map_zoom: 4.0,
             loading_tips: true,
+            bug_report_screenshot: true,
         }
     }
 }
@@ -614,9 +650,12 @@ pub struct GraphicsSettings {
     pub view_distance: u32,
     pub sprite_render_distance: u32,
     pub particles_enabled: bool,
+    pub decals_enabled: bool,
     pub figure_lod_render_distance: u32,
     pub max_fps: u32,
     pub fov: u16,
+    /// Whether to outline group members hidden behind terrain or figures
+    pub outline_group_members: bool,
     pub gamma: f32,
     pub ambiance: f32,
     pub render_mode: RenderMode,
@@ -631,9 +670,11 @@ impl Default for GraphicsSettings {
             view_distance: 10,
             sprite_render_distance: 150,
             particles_enabled: true,
+            decals_enabled: true,
             figure_lod_render_distance: 250,
             max_fps: 60,
             fov: 50,
+            outline_group_members: true,
             gamma: 1.0,
             ambiance: 20.0,
             render_mode: RenderMode::default(),
@@ -699,6 +740,68 @@ impl Default for LanguageSettings {
     }
 }
 
+/// An asset pack found in the asset pack directory
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AssetPackEntry {
+    pub name: String,
+    pub enabled: bool,
+}
+
+/// `AssetPackSettings` lists the asset packs that can be used in place of the
+/// built-in assets. Packs earlier in the list take precedence over later ones.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AssetPackSettings {
+    /// Directory holding each asset pack in a directory of its own
+    pub path: PathBuf,
+    pub packs: Vec<AssetPackEntry>,
+}
+
+impl Default for AssetPackSettings {
+    fn default() -> Self {
+        let mut path = voxygen_data_dir();
+        path.push("asset_packs");
+
+        Self {
+            path,
+            packs: Vec::new(),
+        }
+    }
+}
+
+impl AssetPackSettings {
+    /// Opens the enabled asset packs in order of precedence. Packs newly
+    /// found in the directory are added to the end of the list, disabled, and
+    /// packs that are gone are removed from it.
+    pub fn open_enabled(&mut self) -> Vec<AssetPack> {
+        let found = pack::find_packs(&self.path);
+        self.packs.retain(|entry| found.contains(&entry.name));
+        for name in found {
+            if !self.packs.iter().any(|entry| entry.name == name) {
+                self.packs.push(AssetPackEntry {
+                    name,
+                    enabled: false,
+                });
+            }
+        }
+
+        self.packs
+            .iter()
+            .filter(|entry| entry.enabled)
+            .filter_map(|entry| match AssetPack::open(self.path.join(&entry.name)) {
+                Ok(pack) => {
+                    info!(name = ?pack.name, hash = pack.hash, "Using asset pack");
+                    Some(pack)
+                },
+                Err(e) => {
+                    warn!(?e, name = ?entry.name, "Failed to open asset pack");
+                    None
+                },
+            })
+            .collect()
+    }
+}
+
 /// `Settings` contains everything that can be configured in the settings.ron
 /// file.
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -717,6 +820,7 @@ pub struct Settings {
     pub language: LanguageSettings,
     pub screenshots_path: PathBuf,
     pub controller: GamepadSettings,
+    pub asset_packs: AssetPackSettings,
 }
 
 impl Default for Settings {
@@ -751,6 +855,7 @@ impl Default for Settings {
             language: LanguageSettings::default(),
             screenshots_path,
             controller: GamepadSettings::default(),
+            asset_packs: AssetPackSettings::default(),
         }
     }
 }
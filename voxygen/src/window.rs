@@ -44,7 +44,9 @@ pub enum GameInput {
     ClimbDown,
     SwimUp,
     SwimDown,
+    SwimSprint,
     Sneak,
+    Crouch,
     //WallLeap,
     ToggleLantern,
     Mount,
@@ -56,6 +58,7 @@ pub enum GameInput {
     Social,
     Crafting,
     Spellbook,
+    CharacterSheet,
     Settings,
     ToggleInterface,
     Help,
@@ -66,6 +69,7 @@ pub enum GameInput {
     Roll,
     Respawn,
     Interact,
+    Fish,
     ToggleWield,
     //Charge,
     SwapLoadout,
@@ -95,7 +99,9 @@ impl GameInput {
             GameInput::ClimbDown => "gameinput.climbdown",
             GameInput::SwimUp => "gameinput.swimup",
             GameInput::SwimDown => "gameinput.swimdown",
+            GameInput::SwimSprint => "gameinput.swimsprint",
             GameInput::Sneak => "gameinput.sneak",
+            GameInput::Crouch => "gameinput.crouch",
             //GameInput::WallLeap => "gameinput.wallleap",
             GameInput::ToggleLantern => "gameinput.togglelantern",
             GameInput::Mount => "gameinput.mount",
@@ -108,6 +114,7 @@ impl GameInput {
             GameInput::Social => "gameinput.social",
             GameInput::Crafting => "gameinput.crafting",
             GameInput::Spellbook => "gameinput.spellbook",
+            GameInput::CharacterSheet => "gameinput.charactersheet",
             GameInput::Settings => "gameinput.settings",
             GameInput::ToggleInterface => "gameinput.toggleinterface",
             GameInput::Help => "gameinput.help",
@@ -118,6 +125,7 @@ impl GameInput {
             GameInput::Roll => "gameinput.roll",
             GameInput::Respawn => "gameinput.respawn",
             GameInput::Interact => "gameinput.interact",
+            GameInput::Fish => "gameinput.fish",
             GameInput::ToggleWield => "gameinput.togglewield",
             //GameInput::Charge => "gameinput.charge",
             GameInput::FreeLook => "gameinput.freelook",
@@ -156,7 +164,9 @@ impl GameInput {
             GameInput::ClimbDown,
             GameInput::SwimUp,
             GameInput::SwimDown,
+            GameInput::SwimSprint,
             GameInput::Sneak,
+            GameInput::Crouch,
             GameInput::ToggleLantern,
             GameInput::Mount,
             GameInput::Chat,
@@ -167,6 +177,7 @@ impl GameInput {
             GameInput::Social,
             GameInput::Crafting,
             GameInput::Spellbook,
+            GameInput::CharacterSheet,
             GameInput::Settings,
             GameInput::ToggleInterface,
             GameInput::Help,
@@ -177,6 +188,7 @@ impl GameInput {
             GameInput::Roll,
             GameInput::Respawn,
             GameInput::Interact,
+            GameInput::Fish,
             GameInput::ToggleWield,
             GameInput::FreeLook,
             GameInput::AutoWalk,
@@ -292,7 +304,7 @@ pub enum Event {
     AnalogMenuInput(AnalogMenuInput),
     /// Update of the analog inputs recognized by the game
     AnalogGameInput(AnalogGameInput),
-    /// We tried to save a screenshot
+    /// We tried to save a screenshot or a bug report
     ScreenshotMessage(String),
 }
 
@@ -501,7 +513,7 @@ pub struct Window {
     controller_settings: ControllerSettings,
     cursor_position: winit::dpi::PhysicalPosition<f64>,
     mouse_emulation_vec: Vec2<f32>,
-    // Currently used to send and receive screenshot result messages
+    // Currently used to send and receive screenshot and bug report result messages
     message_sender: channel::Sender<String>,
     message_receiver: channel::Receiver<String>,
     // Used for screenshots & fullscreen toggle to deduplicate/postpone to after event handler
@@ -625,6 +637,10 @@ impl Window {
 
     pub fn renderer(&self) -> &Renderer { &self.renderer }
 
+    /// Sender for messages that are shown in the chat, for work done on other
+    /// threads
+    pub fn message_sender(&self) -> channel::Sender<String> { self.message_sender.clone() }
+
     pub fn renderer_mut(&mut self) -> &mut Renderer { &mut self.renderer }
 
     pub fn resolve_deduplicated_events(&mut self, settings: &mut Settings) {
@@ -1052,6 +1068,8 @@ impl Window {
 
     pub fn is_cursor_grabbed(&self) -> bool { self.cursor_grabbed }
 
+    pub fn modifiers(&self) -> winit::event::ModifiersState { self.modifiers }
+
     pub fn grab_cursor(&mut self, grab: bool) {
         self.cursor_grabbed = grab;
         self.window.window().set_cursor_visible(!grab);
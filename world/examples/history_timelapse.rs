@@ -0,0 +1,37 @@
+//! Generates a world without rendering anything on screen, writing snapshots of
+//! its map as its history is simulated to the directory given as the first
+//! argument, every number of years given as the second.
+//!
+//! The snapshots can be turned into a video with, for example:
+//! `ffmpeg -framerate 10 -pattern_type glob -i 'timelapse/*.png' history.mp4`
+
+use std::path::PathBuf;
+use tracing::Level;
+use tracing_subscriber::{filter::LevelFilter, EnvFilter, FmtSubscriber};
+use veloren_world::{
+    sim::{FileOpts, WorldOpts, DEFAULT_WORLD_MAP},
+    sim2::{timelapse::TimelapseOpts, HistoryOpts},
+    World,
+};
+
+fn main() {
+    FmtSubscriber::builder()
+        .with_max_level(Level::ERROR)
+        .with_env_filter(EnvFilter::from_default_env().add_directive(LevelFilter::INFO.into()))
+        .init();
+
+    let mut args = std::env::args().skip(1);
+    let dir = args
+        .next()
+        .map_or_else(|| PathBuf::from("timelapse"), PathBuf::from);
+    let interval = args.next().and_then(|s| s.parse().ok()).unwrap_or(10.0);
+
+    World::generate(5284, WorldOpts {
+        seed_elements: false,
+        world_file: FileOpts::LoadAsset(DEFAULT_WORLD_MAP.into()),
+        history: HistoryOpts {
+            timelapse: Some(TimelapseOpts { dir, interval }),
+            ..HistoryOpts::default()
+        },
+    });
+}
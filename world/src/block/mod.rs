@@ -3,7 +3,7 @@ mod natural;
 use crate::{
     column::{ColumnGen, ColumnSample},
     util::{RandomField, Sampler, SmallCache},
-    IndexRef,
+    IndexRef, CONFIG,
 };
 use common::{
     terrain::{
@@ -183,7 +183,7 @@ impl<'a> BlockGen<'a> {
             //cliffs,
             cliff_hill,
             close_cliffs,
-            // temp,
+            temp,
             // humidity,
             stone_col,
             ..
@@ -311,8 +311,13 @@ impl<'a> BlockGen<'a> {
             .or_else(|| {
                 // Water
                 if (wposf.z as f32) < water_height {
-                    // Ocean
-                    Some(water)
+                    if temp < CONFIG.snow_temp && (wposf.z as f32) >= water_height - 1.0 {
+                        // Water freezes over in the cold
+                        Some(Block::new(BlockKind::Ice, Rgb::new(200, 225, 245)))
+                    } else {
+                        // Ocean
+                        Some(water)
+                    }
                 } else {
                     None
                 }
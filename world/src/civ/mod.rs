@@ -1,12 +1,18 @@
 #![allow(dead_code)]
 
 mod econ;
+mod roads;
 
+pub use self::roads::{generate_trade_roads, MIN_TRADE_SHARE};
 use self::{Occupation::*, Stock::*};
 use crate::{
     config::CONFIG,
     sim::WorldSim,
-    site::{Castle, Dungeon, Settlement, Site as WorldSite},
+    site::{
+        economy::{Good, NeighborInformation},
+        faction::{Relation, Stance},
+        Castle, Dungeon, Faction, Port, Settlement, Site as WorldSite,
+    },
     util::{attempt, seed_expan, MapVec, CARDINALS, NEIGHBORS},
     Index,
 };
@@ -56,11 +62,43 @@ pub struct Civs {
     >,
 
     sites: Store<Site>,
+
+    outlaws: Vec<Outlaw>,
+    bandit_camps: Vec<BanditCamp>,
 }
 
 // Change this to get rid of particularly horrid seeds
 const SEED_SKIP: u8 = 0;
 
+/// How many chunks from the sea a site can be and still count as on the coast
+const COAST_DIST: i32 = 4;
+/// How many chunks from the sea a port can be, so that its piers reach the
+/// water
+const PORT_COAST_DIST: i32 = 2;
+/// Fewest chunks between a port and any other site
+const PORT_SPACING: i32 = 16;
+/// Furthest that ships from a port sail to trade, in chunks
+const PORT_SEA_RANGE: f32 = 200.0;
+/// How much further ships have to sail between two sites than the straight
+/// distance between them
+const SEA_DETOUR: f32 = 1.5;
+/// How many chunks around a site its people gather the natural goods of the
+/// land from
+const ENDOWMENT_RADIUS: i32 = 6;
+/// Most that the land around a site can yield of a natural good, relative to
+/// what the economy recipes give as usual
+const MAX_ENDOWMENT: f32 = 2.0;
+/// How many chunks a cave tunnel winds through from one end to the other
+const CAVE_STEPS: i32 = 200;
+/// How many chunks the branches off a cave tunnel wind through
+const BRANCH_STEPS: Range<i32> = 30..80;
+const MAX_CAVE_BRANCHES: usize = 4;
+/// How far below the surface caves go at their deepest
+const CAVE_DEPTH: f32 = 250.0;
+/// How many chunks from a dungeon a cave can be and still have a tunnel dug
+/// to the dungeon
+const DUNGEON_CAVE_DIST: i32 = 24;
+
 pub struct GenCtx<'a, R: Rng> {
     sim: &'a mut WorldSim,
     rng: R,
@@ -127,6 +165,40 @@ impl Civs {
             });
         }
 
+        // Ports on the coast, where ships land fish and trade goods
+        for _ in 0..initial_civ_count {
+            attempt(5, || {
+                let loc = find_port_loc(&mut ctx)?;
+                if this
+                    .sites
+                    .values()
+                    .any(|site| site.center.distance_squared(loc) < PORT_SPACING.pow(2))
+                {
+                    return None;
+                }
+                this.establish_site(&mut ctx.reseed(), loc, |place| Site {
+                    kind: SiteKind::Port,
+                    center: loc,
+                    place,
+
+                    population: 0.0,
+
+                    stocks: Stocks::from_default(100.0),
+                    surplus: Stocks::from_default(0.0),
+                    values: Stocks::from_default(None),
+
+                    labors: MapVec::from_default(0.01),
+                    yields: MapVec::from_default(1.0),
+                    productivity: MapVec::from_default(1.0),
+
+                    last_exports: Stocks::from_default(0.0),
+                    export_targets: Stocks::from_default(0.0),
+                    //trade_states: Stocks::default(),
+                    coin: 1000.0,
+                })
+            });
+        }
+
         // Tick
         const SIM_YEARS: usize = 1000;
         for _ in 0..SIM_YEARS {
@@ -143,6 +215,7 @@ impl Civs {
                 SiteKind::Settlement => 10.0,
                 SiteKind::Dungeon => 2.0,
                 SiteKind::Castle => 5.0,
+                SiteKind::Port => 3.0,
             };
 
             let (raise, raise_dist): (f32, i32) = match &site.kind {
@@ -189,7 +262,8 @@ impl Civs {
 
         // Place sites in world
         let mut cnt = 0;
-        for sim_site in this.sites.values() {
+        let mut placed = HashMap::new();
+        for (sim_id, sim_site) in this.sites.iter() {
             cnt += 1;
             let wpos = sim_site
                 .center
@@ -208,7 +282,14 @@ impl Civs {
                 SiteKind::Castle => {
                     WorldSite::castle(Castle::generate(wpos, Some(ctx.sim), &mut rng))
                 },
+                SiteKind::Port => WorldSite::port(Port::generate(wpos, Some(ctx.sim), &mut rng)),
             });
+            placed.insert(sim_id, site);
+            index.sites[site].economy.endowment = site_endowment(ctx.sim, sim_site.center);
+            if let SiteKind::Port = sim_site.kind {
+                // Fishing boats go out from ports, whatever the land around them is like
+                index.sites[site].economy.endowment[Good::Fish] = MAX_ENDOWMENT;
+            }
             let site_ref = &index.sites[site];
 
             let radius_chunks =
@@ -223,72 +304,315 @@ impl Civs {
         }
         info!(?cnt, "all sites placed");
 
+        this.connect_dungeons_to_caves(&mut ctx, index, &placed);
+
+        // Sites trade with their neighbours along the tracks between them, and by sea
+        // when both of them are on the coast
+        for (a, dests) in this.track_map.iter() {
+            for (b, track) in dests.iter() {
+                let track = this.tracks.get(*track);
+                let (a_center, b_center) = (this.sites.get(*a).center, this.sites.get(*b).center);
+                let sea_distance =
+                    if loc_is_coastal(ctx.sim, a_center) && loc_is_coastal(ctx.sim, b_center) {
+                        // Ships have to follow the coast rather than sail straight there
+                        Some((a_center.distance_squared(b_center) as f32).sqrt() * SEA_DETOUR)
+                    } else {
+                        None
+                    };
+                for (from, to) in [(a, b), (b, a)].iter() {
+                    index.sites[placed[*from]]
+                        .economy
+                        .neighbors
+                        .push(NeighborInformation {
+                            id: placed[*to],
+                            distance: track.path.len() as f32,
+                            cost: Some(track.cost),
+                            sea_distance,
+                        });
+                }
+            }
+        }
+
+        // Ships from ports also sail to the sites along the coast that no road leads to
+        for (a, site) in this.sites.iter() {
+            if !matches!(site.kind, SiteKind::Port) {
+                continue;
+            }
+            for (b, other) in this.sites.iter() {
+                let distance = (site.center.distance_squared(other.center) as f32).sqrt();
+                if a == b
+                    || !matches!(
+                        other.kind,
+                        SiteKind::Settlement | SiteKind::Castle | SiteKind::Port
+                    )
+                    || distance > PORT_SEA_RANGE
+                    || !loc_is_coastal(ctx.sim, other.center)
+                    || index.sites[placed[&a]]
+                        .economy
+                        .neighbors
+                        .iter()
+                        .any(|neighbor| neighbor.id == placed[&b])
+                {
+                    continue;
+                }
+                for (from, to) in [(a, b), (b, a)].iter() {
+                    index.sites[placed[from]]
+                        .economy
+                        .neighbors
+                        .push(NeighborInformation {
+                            id: placed[to],
+                            distance: distance * SEA_DETOUR,
+                            cost: None,
+                            sea_distance: Some(distance * SEA_DETOUR),
+                        });
+                }
+            }
+        }
+
+        // Each civilisation rules the sites closest to its capital
+        this.generate_factions(index, &placed);
+        info!(
+            factions = index.factions.values().count(),
+            "all factions created"
+        );
+
+        // Drive outlaws out of towns, now that the sites they must avoid are in place
+        this.generate_outlaws(&mut ctx.reseed());
+        info!(outlaws = this.outlaws.len(), "all outlaws created");
+
         //this.display_info();
 
         this
     }
 
+    /// Gives each civilisation a faction that rules the sites closest to its
+    /// capital along the tracks. Sites that can't be reached from any capital
+    /// by road, such as those only ships sail to, are left independent.
+    fn generate_factions(&self, index: &mut Index, placed: &HashMap<Id<Site>, Id<WorldSite>>) {
+        let mut queue = Vec::new();
+        let mut closest = HashMap::new();
+        for civ in self.civs.values() {
+            let capital = placed[&civ.capital];
+            let faction = index.factions.insert(Faction::new(capital));
+            closest.insert(capital, (0.0, faction));
+            queue.push(capital);
+        }
+
+        // Spread out from the capitals, keeping the cheapest way to each site
+        while let Some(site) = queue.pop() {
+            let (cost, faction) = closest[&site];
+            for neighbor in index.sites[site].economy.neighbors.iter() {
+                let cost = match neighbor.cost {
+                    Some(road_cost) => cost + road_cost,
+                    None => continue,
+                };
+                if closest
+                    .get(&neighbor.id)
+                    .map_or(true, |(closest_cost, _)| cost < *closest_cost)
+                {
+                    closest.insert(neighbor.id, (cost, faction));
+                    queue.push(neighbor.id);
+                }
+            }
+        }
+        for (site, (_, faction)) in closest {
+            index.sites[site].faction = Some(faction);
+        }
+
+        // Factions start out neutral to each other
+        let count = index.factions.values().count();
+        for (id, faction) in index.factions.iter_mut() {
+            faction.relations = vec![Relation::default(); count];
+            faction.relations[id.id() as usize] = Relation {
+                stance: Stance::Allied,
+                standing: 1.0,
+            };
+        }
+    }
+
+    /// Gives some towns an outlaw that they have put a bounty on, hiding in a
+    /// camp out in the wilderness.
+    fn generate_outlaws(&mut self, ctx: &mut GenCtx<impl Rng>) {
+        // Chance of a town having an outlaw
+        const OUTLAW_CHANCE: f64 = 0.5;
+        // Range of distances, in chunks, between a town and the outlaw's camp
+        const CAMP_DIST: Range<f32> = 16.0..48.0;
+
+        let towns = self
+            .sites
+            .values()
+            .filter(|site| matches!(site.kind, SiteKind::Settlement))
+            .map(|site| (site.center, site.coin))
+            .collect::<Vec<_>>();
+
+        for (town, coin) in towns {
+            if !ctx.rng.gen_bool(OUTLAW_CHANCE) {
+                continue;
+            }
+
+            let camp = attempt(16, || {
+                let dist = ctx.rng.gen_range(CAMP_DIST.start, CAMP_DIST.end);
+                let angle = ctx.rng.gen_range(0.0, std::f32::consts::PI * 2.0);
+                let camp = town + Vec2::new(angle.cos(), angle.sin()).map(|e| (e * dist) as i32);
+                ctx.sim
+                    .get(camp)
+                    .filter(|chunk| {
+                        chunk.sites.is_empty() && !chunk.is_underwater() && chunk.chaos < 0.5
+                    })
+                    .map(|_| camp)
+            });
+
+            if let Some(camp) = camp {
+                let years_ago = ctx.rng.gen_range(1, 60);
+                self.outlaws.push(Outlaw {
+                    name: generate_outlaw_name(&mut ctx.rng),
+                    town,
+                    camp,
+                    crime: *OUTLAW_CRIMES.choose(&mut ctx.rng).unwrap(),
+                    years_ago,
+                    // Rich towns pay more, and old grudges are worth less
+                    bounty: (coin * 0.05) as u32 + ctx.rng.gen_range(0, 50) + 60 / years_ago,
+                });
+            }
+        }
+    }
+
+    /// Sets up bandit camps near the sites where crime got out of hand over
+    /// the history of the world, more of them the worse it got.
+    pub fn generate_bandit_camps(&mut self, seed: u32, index: &Index, sim: &WorldSim) {
+        // Share of the people of a site in crime for each bandit camp near it
+        const CRIME_PER_CAMP: f32 = 0.1;
+        // Range of distances, in chunks, between a site and the bandit camps near it
+        const CAMP_DIST: Range<f32> = 6.0..20.0;
+
+        let mut rng = ChaChaRng::from_seed(seed_expan::rng_state(seed.wrapping_add(1)));
+        for site in index.sites.values() {
+            let crime = site.economy.crime;
+            let center = site
+                .get_origin()
+                .map2(TerrainChunkSize::RECT_SIZE, |e, sz: u32| {
+                    e.div_euclid(sz as i32)
+                });
+
+            for _ in 0..(crime / CRIME_PER_CAMP) as u32 {
+                let camp = attempt(16, || {
+                    let dist = rng.gen_range(CAMP_DIST.start, CAMP_DIST.end);
+                    let angle = rng.gen_range(0.0, std::f32::consts::PI * 2.0);
+                    let camp =
+                        center + Vec2::new(angle.cos(), angle.sin()).map(|e| (e * dist) as i32);
+                    sim.get(camp)
+                        .filter(|chunk| {
+                            chunk.sites.is_empty() && !chunk.is_underwater() && chunk.chaos < 0.5
+                        })
+                        .map(|_| camp)
+                });
+
+                if let Some(camp) = camp {
+                    self.bandit_camps.push(BanditCamp {
+                        site: center,
+                        camp,
+                        // The more desperate the people, the more and the
+                        // tougher the bandits
+                        bandits: rng.gen_range(2, 4) + (crime * 8.0) as u32,
+                        level: 5 + (crime * 30.0) as u32,
+                    });
+                }
+            }
+        }
+    }
+
     // TODO: Move this
+    /// Digs a tunnel that comes up to the surface at both ends and is deepest
+    /// half way along, with a few branches off it. Some of the branches rise
+    /// to the surface as further entrances and the rest wind on deep
+    /// underground.
     fn generate_cave(&self, ctx: &mut GenCtx<impl Rng>) {
-        let mut pos = ctx
+        let pos = ctx
             .sim
             .get_size()
             .map(|sz| ctx.rng.gen_range(0, sz as i32) as f32);
-        let mut vel = pos
+        let vel = pos
             .map2(ctx.sim.get_size(), |pos, sz| sz as f32 / 2.0 - pos)
             .try_normalized()
             .unwrap_or_else(Vec2::unit_y);
 
-        let path = (-100..100)
-            .filter_map(|i: i32| {
-                let depth = (i.abs() as f32 / 100.0 * std::f32::consts::PI / 2.0).cos();
-                vel = (vel
-                    + Vec2::new(
-                        ctx.rng.gen_range(-0.35, 0.35),
-                        ctx.rng.gen_range(-0.35, 0.35),
-                    ))
+        let tunnel = walk_cave(ctx, pos, vel, CAVE_STEPS, |t| {
+            (t * std::f32::consts::PI).sin()
+        });
+        let tunnel_alts = cave_alts(ctx, &tunnel);
+        dig_cave(ctx, &tunnel_alts, 0);
+
+        if tunnel.len() < 5 {
+            return;
+        }
+        for _ in 0..ctx.rng.gen_range(0, MAX_CAVE_BRANCHES + 1) {
+            let fork = ctx.rng.gen_range(tunnel.len() / 5, tunnel.len() * 4 / 5);
+            let (fork_pos, fork_depth) = tunnel[fork];
+            let vel = Vec2::new(ctx.rng.gen_range(-1.0, 1.0), ctx.rng.gen_range(-1.0, 1.0))
                 .try_normalized()
                 .unwrap_or_else(Vec2::unit_y);
-                let old_pos = pos.map(|e| e as i32);
-                pos = (pos + vel * 0.5)
-                    .clamped(Vec2::zero(), ctx.sim.get_size().map(|e| e as f32 - 1.0));
-                Some((pos.map(|e| e as i32), depth)).filter(|(pos, _)| *pos != old_pos)
-            })
-            .collect::<Vec<_>>();
+            let steps = ctx.rng.gen_range(BRANCH_STEPS.start, BRANCH_STEPS.end);
+            let pos = fork_pos.map(|e| e as f32 + 0.5);
+            let branch = if ctx.rng.gen() {
+                walk_cave(ctx, pos, vel, steps, |t| fork_depth * (1.0 - t))
+            } else {
+                walk_cave(ctx, pos, vel, steps, |t| fork_depth * (1.0 - 0.3 * t))
+            };
 
-        for locs in path.windows(3) {
-            let to_prev_idx = NEIGHBORS
-                .iter()
-                .enumerate()
-                .find(|(_, dir)| **dir == locs[0].0 - locs[1].0)
-                .expect("Track locations must be neighbors")
-                .0;
-            let to_next_idx = NEIGHBORS
-                .iter()
-                .enumerate()
-                .find(|(_, dir)| **dir == locs[2].0 - locs[1].0)
-                .expect("Track locations must be neighbors")
-                .0;
-
-            ctx.sim.get_mut(locs[0].0).unwrap().cave.0.neighbors |=
-                1 << ((to_prev_idx as u8 + 4) % 8);
-            ctx.sim.get_mut(locs[1].0).unwrap().cave.0.neighbors |=
-                (1 << (to_prev_idx as u8)) | (1 << (to_next_idx as u8));
-            ctx.sim.get_mut(locs[2].0).unwrap().cave.0.neighbors |=
-                1 << ((to_next_idx as u8 + 4) % 8);
+            let mut branch_alts = vec![tunnel_alts[fork]];
+            branch_alts.extend(cave_alts(ctx, &branch));
+            dig_cave(ctx, &branch_alts, 1);
         }
+    }
 
-        for loc in path.iter() {
-            let mut chunk = ctx.sim.get_mut(loc.0).unwrap();
-            let depth = loc.1 * 250.0 - 20.0;
-            chunk.cave.1.alt =
-                chunk.alt - depth + ctx.rng.gen_range(-4.0, 4.0) * (depth > 10.0) as i32 as f32;
-            chunk.cave.1.width = ctx.rng.gen_range(6.0, 32.0);
-            chunk.cave.0.offset = Vec2::new(ctx.rng.gen_range(-16, 17), ctx.rng.gen_range(-16, 17));
+    /// Digs a tunnel from each dungeon to the closest deep cave, if there is
+    /// one nearby, that comes out on the topmost floor of the dungeon
+    fn connect_dungeons_to_caves(
+        &self,
+        ctx: &mut GenCtx<impl Rng>,
+        index: &Index,
+        placed: &HashMap<Id<Site>, Id<WorldSite>>,
+    ) {
+        for (sim_id, sim_site) in self.sites.iter() {
+            let floor_alt = match &index.sites[placed[&sim_id]].kind {
+                crate::site::SiteKind::Dungeon(dungeon) => dungeon.top_floor_alt() as f32,
+                _ => continue,
+            };
+            let cave = Spiral2d::new()
+                .take(((DUNGEON_CAVE_DIST * 2 + 1) as usize).pow(2))
+                .map(|offs| sim_site.center + offs)
+                .find(|pos| {
+                    ctx.sim.get(*pos).map_or(false, |chunk| {
+                        chunk.cave.0.is_way()
+                            && chunk.cave.1.alt + chunk.cave.1.width + 20.0 < chunk.alt
+                    })
+                });
+            let cave = match cave {
+                Some(cave) => cave,
+                None => continue,
+            };
 
-            if chunk.cave.1.alt + chunk.cave.1.width + 5.0 > chunk.alt {
-                chunk.spawn_rate = 0.0;
+            let mut path = vec![cave];
+            let mut pos = cave;
+            while pos != sim_site.center {
+                pos += (sim_site.center - pos).map(|e| e.signum());
+                path.push(pos);
+            }
+            if path.len() < 3 {
+                continue;
             }
+            // Come out a little above the floor, since the tunnel is hollowed out
+            // around its altitude
+            let cave_alt = ctx.sim.get(cave).unwrap().cave.1.alt;
+            let path_alts = path
+                .iter()
+                .enumerate()
+                .map(|(i, pos)| {
+                    let t = i as f32 / (path.len() - 1) as f32;
+                    (*pos, Lerp::lerp(cave_alt, floor_alt + 8.0, t))
+                })
+                .collect::<Vec<_>>();
+            dig_cave(ctx, &path_alts, 1);
         }
     }
 
@@ -296,6 +620,10 @@ impl Civs {
 
     pub fn sites(&self) -> impl Iterator<Item = &Site> + '_ { self.sites.values() }
 
+    pub fn outlaws(&self) -> &[Outlaw] { &self.outlaws }
+
+    pub fn bandit_camps(&self) -> &[BanditCamp] { &self.bandit_camps }
+
     #[allow(dead_code)]
     #[allow(clippy::print_literal)] // TODO: Pending review in #587
     fn display_info(&self) {
@@ -477,13 +805,15 @@ impl Civs {
         let mut nearby = self
             .sites
             .iter()
-            .filter(|(_, p)| matches!(p.kind, SiteKind::Settlement | SiteKind::Castle))
+            .filter(|(_, p)| {
+                matches!(p.kind, SiteKind::Settlement | SiteKind::Castle | SiteKind::Port)
+            })
             .map(|(id, p)| (id, (p.center.distance_squared(loc) as f32).sqrt()))
             .filter(|(_, dist)| *dist < MAX_NEIGHBOR_DISTANCE)
             .collect::<Vec<_>>();
         nearby.sort_by_key(|(_, dist)| *dist as i32);
 
-        if let SiteKind::Settlement | SiteKind::Castle = self.sites[site].kind {
+        if let SiteKind::Settlement | SiteKind::Castle | SiteKind::Port = self.sites[site].kind {
             for (nearby, _) in nearby.into_iter().take(5) {
                 // Find a novel path
                 if let Some((path, cost)) = find_path(ctx, loc, self.sites.get(nearby).center) {
@@ -495,30 +825,7 @@ impl Civs {
                         .is_none()
                     {
                         // Write the track to the world as a path
-                        for locs in path.nodes().windows(3) {
-                            let to_prev_idx = NEIGHBORS
-                                .iter()
-                                .enumerate()
-                                .find(|(_, dir)| **dir == locs[0] - locs[1])
-                                .expect("Track locations must be neighbors")
-                                .0;
-                            let to_next_idx = NEIGHBORS
-                                .iter()
-                                .enumerate()
-                                .find(|(_, dir)| **dir == locs[2] - locs[1])
-                                .expect("Track locations must be neighbors")
-                                .0;
-
-                            ctx.sim.get_mut(locs[0]).unwrap().path.0.neighbors |=
-                                1 << ((to_prev_idx as u8 + 4) % 8);
-                            ctx.sim.get_mut(locs[2]).unwrap().path.0.neighbors |=
-                                1 << ((to_next_idx as u8 + 4) % 8);
-                            let mut chunk = ctx.sim.get_mut(locs[1]).unwrap();
-                            chunk.path.0.neighbors |=
-                                (1 << (to_prev_idx as u8)) | (1 << (to_next_idx as u8));
-                            chunk.path.0.offset =
-                                Vec2::new(ctx.rng.gen_range(-16, 17), ctx.rng.gen_range(-16, 17));
-                        }
+                        write_path(ctx.sim, &mut ctx.rng, &path);
 
                         // Take note of the track
                         let track = self.tracks.insert(Track { cost, path });
@@ -638,6 +945,105 @@ fn find_path(
         .and_then(|path| astar.get_cheapest_cost().map(|cost| (path, cost)))
 }
 
+/// Write a path to the world, connecting each of its chunks to the next
+fn write_path(sim: &mut WorldSim, rng: &mut impl Rng, path: &Path<Vec2<i32>>) {
+    for locs in path.nodes().windows(3) {
+        let to_prev_idx = NEIGHBORS
+            .iter()
+            .enumerate()
+            .find(|(_, dir)| **dir == locs[0] - locs[1])
+            .expect("Track locations must be neighbors")
+            .0;
+        let to_next_idx = NEIGHBORS
+            .iter()
+            .enumerate()
+            .find(|(_, dir)| **dir == locs[2] - locs[1])
+            .expect("Track locations must be neighbors")
+            .0;
+
+        sim.get_mut(locs[0]).unwrap().path.0.neighbors |= 1 << ((to_prev_idx as u8 + 4) % 8);
+        sim.get_mut(locs[2]).unwrap().path.0.neighbors |= 1 << ((to_next_idx as u8 + 4) % 8);
+        let mut chunk = sim.get_mut(locs[1]).unwrap();
+        chunk.path.0.neighbors |= (1 << (to_prev_idx as u8)) | (1 << (to_next_idx as u8));
+        chunk.path.0.offset = Vec2::new(rng.gen_range(-16, 17), rng.gen_range(-16, 17));
+    }
+}
+
+/// Wander across the map from a position, returning each chunk passed through
+/// along with how deep underground the cave should be there, as a share of
+/// `CAVE_DEPTH` given by `depth` for how far along the cave the chunk is
+fn walk_cave(
+    ctx: &mut GenCtx<impl Rng>,
+    mut pos: Vec2<f32>,
+    mut vel: Vec2<f32>,
+    steps: i32,
+    depth: impl Fn(f32) -> f32,
+) -> Vec<(Vec2<i32>, f32)> {
+    (0..steps)
+        .filter_map(|i| {
+            let depth = depth(i as f32 / steps as f32);
+            vel = (vel
+                + Vec2::new(
+                    ctx.rng.gen_range(-0.35, 0.35),
+                    ctx.rng.gen_range(-0.35, 0.35),
+                ))
+            .try_normalized()
+            .unwrap_or_else(Vec2::unit_y);
+            let old_pos = pos.map(|e| e as i32);
+            pos =
+                (pos + vel * 0.5).clamped(Vec2::zero(), ctx.sim.get_size().map(|e| e as f32 - 1.0));
+            Some((pos.map(|e| e as i32), depth)).filter(|(pos, _)| *pos != old_pos)
+        })
+        .collect()
+}
+
+/// Turn the depths of the chunks of a cave into the altitudes of the cave
+fn cave_alts(ctx: &mut GenCtx<impl Rng>, path: &[(Vec2<i32>, f32)]) -> Vec<(Vec2<i32>, f32)> {
+    path.iter()
+        .map(|(pos, depth)| {
+            let depth = depth * CAVE_DEPTH - 20.0;
+            let alt = ctx.sim.get(*pos).unwrap().alt - depth
+                + ctx.rng.gen_range(-4.0, 4.0) * (depth > 10.0) as i32 as f32;
+            (*pos, alt)
+        })
+        .collect()
+}
+
+/// Write a cave to the world, connecting each of its chunks to the next. The
+/// first `dug` chunks already belong to another cave and keep its shape.
+fn dig_cave(ctx: &mut GenCtx<impl Rng>, path: &[(Vec2<i32>, f32)], dug: usize) {
+    for locs in path.windows(3) {
+        let to_prev_idx = NEIGHBORS
+            .iter()
+            .enumerate()
+            .find(|(_, dir)| **dir == locs[0].0 - locs[1].0)
+            .expect("Track locations must be neighbors")
+            .0;
+        let to_next_idx = NEIGHBORS
+            .iter()
+            .enumerate()
+            .find(|(_, dir)| **dir == locs[2].0 - locs[1].0)
+            .expect("Track locations must be neighbors")
+            .0;
+
+        ctx.sim.get_mut(locs[0].0).unwrap().cave.0.neighbors |= 1 << ((to_prev_idx as u8 + 4) % 8);
+        ctx.sim.get_mut(locs[1].0).unwrap().cave.0.neighbors |=
+            (1 << (to_prev_idx as u8)) | (1 << (to_next_idx as u8));
+        ctx.sim.get_mut(locs[2].0).unwrap().cave.0.neighbors |= 1 << ((to_next_idx as u8 + 4) % 8);
+    }
+
+    for (pos, alt) in path.iter().skip(dug) {
+        let mut chunk = ctx.sim.get_mut(*pos).unwrap();
+        chunk.cave.1.alt = *alt;
+        chunk.cave.1.width = ctx.rng.gen_range(6.0, 32.0);
+        chunk.cave.0.offset = Vec2::new(ctx.rng.gen_range(-16, 17), ctx.rng.gen_range(-16, 17));
+
+        if chunk.cave.1.alt + chunk.cave.1.width + 5.0 > chunk.alt {
+            chunk.spawn_rate = 0.0;
+        }
+    }
+}
+
 /// Return Some if travel between a location and a chunk next to it is permitted
 /// If permitted, the approximate relative const of traversal is given
 // (TODO: by whom?)
@@ -672,6 +1078,84 @@ fn loc_suitable_for_walking(sim: &WorldSim, loc: Vec2<i32>) -> bool {
     }
 }
 
+/// Return true if a location is close enough to the sea for ships to put in
+fn loc_is_coastal(sim: &WorldSim, loc: Vec2<i32>) -> bool { loc_near_sea(sim, loc, COAST_DIST) }
+
+/// Return true if there is sea within the given number of chunks of a location
+fn loc_near_sea(sim: &WorldSim, loc: Vec2<i32>, dist: i32) -> bool {
+    Spiral2d::new()
+        .take(((dist * 2 + 1) as usize).pow(2))
+        .any(|offs| {
+            sim.get(loc + offs)
+                .map_or(false, |chunk| chunk.river.is_ocean())
+        })
+}
+
+/// Return how much of each natural good the land around a location yields,
+/// relative to what the economy recipes give as usual. Sites in the mountains
+/// can't farm and have to get their grain from elsewhere, while sites by the
+/// water catch plenty of fish.
+fn site_endowment(sim: &WorldSim, loc: Vec2<i32>) -> MapVec<Good, f32> {
+    let mut chunks = 0;
+    let (mut arable, mut wooded, mut rocky, mut mountainous, mut water) = (0.0, 0.0, 0.0, 0.0, 0.0);
+    for pos in Spiral2d::new()
+        .take(((ENDOWMENT_RADIUS * 2 + 1) as usize).pow(2))
+        .map(|offs| loc + offs)
+    {
+        let chunk = match sim.get(pos) {
+            Some(chunk) => chunk,
+            None => continue,
+        };
+        chunks += 1;
+        if loc_is_arable(sim, pos) {
+            arable += 1.0;
+        }
+        wooded += chunk.tree_density;
+        rocky += chunk.rockiness;
+        if sim
+            .get_gradient_approx(pos)
+            .map_or(false, |grad| grad > 0.7)
+        {
+            mountainous += 1.0;
+        }
+        if chunk.river.is_river() || chunk.river.is_lake() || chunk.river.is_ocean() {
+            water += 1.0;
+        }
+    }
+    if chunks == 0 {
+        return MapVec::from_default(1.0);
+    }
+
+    // Half of the land being good for something is enough for the usual yield
+    let share = |count: f32| (count / chunks as f32 * 2.0).min(MAX_ENDOWMENT);
+    MapVec::from_list(
+        &[
+            (Good::Wheat, share(arable)),
+            (Good::Flax, share(arable)),
+            (Good::Logs, share(wooded)),
+            (Good::Game, (share(wooded) + share(arable)) / 2.0),
+            (Good::Rock, (share(rocky) + share(mountainous)) / 2.0),
+            (Good::Ore, share(mountainous)),
+            (Good::Fish, share(water)),
+        ],
+        1.0,
+    )
+}
+
+/// Return true if the land at a location is warm, wet and flat enough to farm
+fn loc_is_arable(sim: &WorldSim, loc: Vec2<i32>) -> bool {
+    sim.get(loc).map_or(false, |chunk| {
+        chunk.humidity > 0.35
+            && chunk.temp > -0.3
+            && chunk.temp < 0.75
+            && chunk.chaos < 0.5
+            && sim
+                .get_gradient_approx(loc)
+                .map(|grad| grad < 0.7)
+                .unwrap_or(false)
+    })
+}
+
 /// Return true if a site could be constructed between a location and a chunk
 /// next to it is permitted (TODO: by whom?)
 fn site_in_dir(sim: &WorldSim, a: Vec2<i32>, dir: Vec2<i32>) -> bool {
@@ -736,6 +1220,51 @@ fn find_site_loc(
     None
 }
 
+/// Attempt to search for a location on the shore that's suitable for a port, by
+/// following the land downhill until it meets the sea
+fn find_port_loc(ctx: &mut GenCtx<impl Rng>) -> Option<Vec2<i32>> {
+    const MAX_ATTEMPTS: usize = 100;
+    for _ in 0..MAX_ATTEMPTS {
+        let start = Vec2::new(
+            ctx.rng.gen_range(0, ctx.sim.get_size().x as i32),
+            ctx.rng.gen_range(0, ctx.sim.get_size().y as i32),
+        );
+        let sim = &*ctx.sim;
+        let loc = follow_downhill(
+            start,
+            |loc| loc_suitable_for_site(sim, loc) && loc_near_sea(sim, loc, PORT_COAST_DIST),
+            |loc| {
+                sim.get(loc).and_then(|c| c.downhill).map(|downhill| {
+                    downhill.map2(TerrainChunkSize::RECT_SIZE, |e, sz: u32| e / (sz as i32))
+                })
+            },
+        );
+        if loc.is_some() {
+            return loc;
+        }
+    }
+    None
+}
+
+/// Follow the land downhill from a chunk, given the chunk downhill of each one,
+/// until reaching one that is suitable
+fn follow_downhill(
+    start: Vec2<i32>,
+    suitable: impl Fn(Vec2<i32>) -> bool,
+    downhill: impl Fn(Vec2<i32>) -> Option<Vec2<i32>>,
+) -> Option<Vec2<i32>> {
+    // Most chunks followed downhill before giving up
+    const MAX_STEPS: usize = 256;
+    let mut loc = start;
+    for _ in 0..MAX_STEPS {
+        if suitable(loc) {
+            return Some(loc);
+        }
+        loc = downhill(loc)?;
+    }
+    None
+}
+
 #[derive(Debug)]
 pub struct Civ {
     capital: Id<Site>,
@@ -768,16 +1297,7 @@ impl NaturalResources {
         self.wood += chunk.tree_density;
         self.rock += chunk.rockiness;
         self.river += if chunk.river.is_river() { 5.0 } else { 0.0 };
-        self.farmland += if chunk.humidity > 0.35
-            && chunk.temp > -0.3
-            && chunk.temp < 0.75
-            && chunk.chaos < 0.5
-            && ctx
-                .sim
-                .get_gradient_approx(loc)
-                .map(|grad| grad < 0.7)
-                .unwrap_or(false)
-        {
+        self.farmland += if loc_is_arable(ctx.sim, loc) {
             1.0
         } else {
             0.0
@@ -793,6 +1313,67 @@ pub struct Track {
     path: Path<Vec2<i32>>,
 }
 
+const OUTLAW_CRIMES: [&str; 6] = [
+    "robbing the town's granary",
+    "murdering a merchant",
+    "burning down a farmstead",
+    "stealing the town's coin",
+    "waylaying travellers on the roads",
+    "poisoning the town's well",
+];
+
+/// An outlaw who fled from a town after committing a crime and now hides out
+/// in a camp in the wilderness.
+#[derive(Clone, Debug)]
+pub struct Outlaw {
+    pub name: String,
+    /// Chunk position of the town that put a bounty on the outlaw
+    pub town: Vec2<i32>,
+    /// Chunk position of the outlaw's camp
+    pub camp: Vec2<i32>,
+    pub crime: &'static str,
+    pub years_ago: u32,
+    /// Coins paid out for killing the outlaw
+    pub bounty: u32,
+}
+
+/// A camp of bandits that prey on a site where crime got out of hand
+#[derive(Debug)]
+pub struct BanditCamp {
+    /// Chunk position of the site that the bandits prey on
+    pub site: Vec2<i32>,
+    /// Chunk position of the camp
+    pub camp: Vec2<i32>,
+    /// How many bandits live in the camp
+    pub bandits: u32,
+    pub level: u32,
+}
+
+fn generate_outlaw_name(rng: &mut impl Rng) -> String {
+    let names = [
+        "Garrick", "Mara", "Tobin", "Sela", "Dorran", "Vesna", "Brom", "Ilsa", "Corvin", "Hesk",
+        "Rurik", "Nell",
+    ];
+    let epithets = [
+        "the Red",
+        "the Butcher",
+        "Blackhand",
+        "the Fox",
+        "Two-Knives",
+        "the Crow",
+        "Ironjaw",
+        "the Silent",
+        "Quickfingers",
+        "the Wolf",
+    ];
+
+    format!(
+        "{} {}",
+        names.choose(rng).unwrap(),
+        epithets.choose(rng).unwrap()
+    )
+}
+
 #[derive(Debug)]
 pub struct Site {
     pub kind: SiteKind,
@@ -863,6 +1444,7 @@ pub enum SiteKind {
     Settlement,
     Dungeon,
     Castle,
+    Port,
 }
 
 impl Site {
@@ -1093,3 +1675,44 @@ impl Default for TradeState {
 }
 
 pub type Stocks<T> = MapVec<Stock, T>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A slope running down to the sea at `x = 0`, with the chunk downhill of
+    /// each one being the next one towards the sea
+    fn downhill_to_sea(loc: Vec2<i32>) -> Option<Vec2<i32>> {
+        if loc.x > 0 {
+            Some(loc - Vec2::unit_x())
+        } else {
+            None
+        }
+    }
+
+    #[test]
+    fn ports_are_found_downhill_by_the_sea() {
+        let by_the_sea = |loc: Vec2<i32>| loc.x > 0 && loc.x <= PORT_COAST_DIST;
+        assert_eq!(
+            follow_downhill(Vec2::new(40, 7), by_the_sea, downhill_to_sea),
+            Some(Vec2::new(PORT_COAST_DIST, 7))
+        );
+        // Starting out by the sea already, the port stays there
+        assert_eq!(
+            follow_downhill(Vec2::new(1, 7), by_the_sea, downhill_to_sea),
+            Some(Vec2::new(1, 7))
+        );
+    }
+
+    #[test]
+    fn no_port_is_found_where_the_land_never_meets_the_sea() {
+        // The slope runs out before reaching anywhere suitable
+        assert_eq!(
+            follow_downhill(Vec2::new(40, 7), |_| false, downhill_to_sea),
+            None
+        );
+        // The land goes round in circles, and the search gives up
+        let in_circles = |loc: Vec2<i32>| Some(Vec2::new(loc.y, -loc.x) + 1);
+        assert_eq!(follow_downhill(Vec2::zero(), |_| false, in_circles), None);
+    }
+}
@@ -0,0 +1,230 @@
+//! Roads along the busiest trade routes. Once the history of the world has
+//! been simulated, the sites that traded the most with each other are joined
+//! by wide roads that keep off the mountains and cross rivers where they are
+//! narrowest, so the bridges over them are short.
+
+use super::{loc_suitable_for_walking, write_path};
+use crate::{
+    sim::{RiverKind, WorldSim},
+    site::Site,
+    util::NEIGHBORS,
+    Index,
+};
+use common::{astar::Astar, path::Path, store::Id, terrain::TerrainChunkSize, vol::RectVolSize};
+use core::{cmp::Ordering, hash::BuildHasherDefault, ops::Range};
+use fxhash::FxHasher64;
+use rand::prelude::*;
+use rand_chacha::ChaChaRng;
+use tracing::info;
+use vek::*;
+
+/// Share of the trade along the busiest route that a route needs to get a road
+pub const MIN_TRADE_SHARE: f32 = 0.1;
+/// Widths of the roads along the quietest and the busiest routes, in blocks
+const ROAD_WIDTH: Range<f32> = 6.0..9.0;
+/// Gradient of the land above which it counts as mountainous
+const MOUNTAIN_GRADIENT: f32 = 1.0;
+/// Cost of building a road over a mountainous chunk
+const MOUNTAIN_COST: f32 = 100.0;
+/// Cost of crossing a river for each block of its width
+const RIVER_COST: f32 = 1.0;
+/// How many chunks are searched for the way between two sites
+const MAX_ITERS: usize = 40000;
+
+/// Lays roads along the trade routes that saw the most trade over the history
+/// of the world, widening any paths that are already there.
+pub fn generate_trade_roads(seed: u32, index: &Index, sim: &mut WorldSim) {
+    let mut rng = ChaChaRng::seed_from_u64(seed as u64);
+
+    let chunk_pos = |site: Id<Site>| {
+        index.sites[site]
+            .get_origin()
+            .map2(TerrainChunkSize::RECT_SIZE, |e, sz: u32| {
+                e.div_euclid(sz as i32)
+            })
+    };
+    let mut roads = 0;
+    for (a, b, share) in road_routes(index) {
+        if let Some(path) = find_road(sim, chunk_pos(a), chunk_pos(b)) {
+            write_path(sim, &mut rng, &path);
+            let width = Lerp::lerp(ROAD_WIDTH.start, ROAD_WIDTH.end, share.sqrt());
+            for loc in path.nodes() {
+                if let Some(chunk) = sim.get_mut(*loc) {
+                    chunk.path.1.width = chunk.path.1.width.max(width);
+                }
+            }
+            roads += 1;
+        }
+    }
+    info!(?roads, "all trade roads laid");
+}
+
+/// The trade routes that get a road, busiest first, along with the share of the
+/// trade along the busiest route that went along each of them
+fn road_routes(index: &Index) -> Vec<(Id<Site>, Id<Site>, f32)> {
+    let traded_along = |from: Id<Site>, to: Id<Site>| {
+        index.sites[from]
+            .economy
+            .route_trade
+            .get(&to.id())
+            .copied()
+            .unwrap_or(0.0)
+    };
+    let mut pairs = index
+        .sites
+        .iter()
+        .flat_map(|(id, site)| {
+            site.economy
+                .neighbors
+                .iter()
+                // Routes that are only sailed don't need a road
+                .filter(|n| n.cost.is_some())
+                .map(move |n| (id, n.id))
+        })
+        .filter(|(a, b)| a.id() < b.id())
+        .collect::<Vec<_>>();
+    pairs.sort_unstable_by_key(|(a, b)| (a.id(), b.id()));
+    pairs.dedup();
+
+    // Trade goes both ways along a route, so both sides count
+    let mut routes = pairs
+        .into_iter()
+        .map(|(a, b)| (a, b, traded_along(a, b) + traded_along(b, a)))
+        .filter(|(_, _, traded)| *traded > 0.0)
+        .collect::<Vec<_>>();
+    // The busiest roads are laid first, so that quieter ones can join them
+    routes.sort_by(|(_, _, a), (_, _, b)| b.partial_cmp(a).unwrap_or(Ordering::Equal));
+    let busiest = match routes.first() {
+        Some((_, _, traded)) => *traded,
+        None => return Vec::new(),
+    };
+
+    routes
+        .into_iter()
+        .map(|(a, b, traded)| (a, b, traded / busiest))
+        .take_while(|(_, _, share)| *share >= MIN_TRADE_SHARE)
+        .collect()
+}
+
+/// Attempt to find the cheapest way for a road between two locations
+fn find_road(sim: &WorldSim, a: Vec2<i32>, b: Vec2<i32>) -> Option<Path<Vec2<i32>>> {
+    let heuristic = move |l: &Vec2<i32>| (l.distance_squared(b) as f32).sqrt();
+    let neighbors = |l: &Vec2<i32>| {
+        let l = *l;
+        NEIGHBORS
+            .iter()
+            .filter(move |dir| road_cost(sim, l, **dir).is_some())
+            .map(move |dir| l + *dir)
+    };
+    let transition = |a: &Vec2<i32>, b: &Vec2<i32>| road_cost(sim, *a, *b - *a).unwrap_or(10000.0);
+    let satisfied = |l: &Vec2<i32>| *l == b;
+    // FxHasher64 keeps the roads the same across computers, as with the tracks
+    let mut astar = Astar::new(
+        MAX_ITERS,
+        a,
+        heuristic,
+        BuildHasherDefault::<FxHasher64>::default(),
+    );
+    astar
+        .poll(MAX_ITERS, heuristic, neighbors, transition, satisfied)
+        .into_path()
+}
+
+/// Return the cost of a road between a location and a chunk next to it, if one
+/// can be built there at all
+fn road_cost(sim: &WorldSim, a: Vec2<i32>, dir: Vec2<i32>) -> Option<f32> {
+    if !loc_suitable_for_walking(sim, a) || !loc_suitable_for_walking(sim, a + dir) {
+        return None;
+    }
+    let a_chunk = sim.get(a)?;
+    let b_chunk = sim.get(a + dir)?;
+
+    // Diagonal steps are longer
+    let length = dir.map(|e| e as f32).magnitude();
+    let hill_cost = ((b_chunk.alt - a_chunk.alt).abs() / 2.5).powf(2.0);
+    let mountain_cost = if sim
+        .get_gradient_approx(a + dir)
+        .map_or(false, |grad| grad > MOUNTAIN_GRADIENT)
+    {
+        MOUNTAIN_COST
+    } else {
+        0.0
+    };
+    let river_cost = match b_chunk.river.river_kind {
+        Some(RiverKind::River { cross_section }) => cross_section.x * RIVER_COST,
+        _ if b_chunk.river.near_river() => RIVER_COST,
+        _ => 0.0,
+    };
+    let wild_cost = if b_chunk.path.0.is_way() {
+        0.0 // Following existing paths has no additional cost!
+    } else {
+        2.0
+    };
+    Some(length + hill_cost + mountain_cost + river_cost + wild_cost)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::site::{economy::NeighborInformation, Settlement};
+
+    /// Makes an index of three settlements that can all reach each other, the
+    /// first and the last only by sea, that traded the given coins along each
+    /// route
+    fn index_with_trade(trade: &[(usize, usize, f32)]) -> (Index, Vec<Id<Site>>) {
+        let (mut index, _) = Index::new(1234);
+        let mut rng = ChaChaRng::seed_from_u64(1234);
+        let sites = (0..3)
+            .map(|i| {
+                let settlement = Settlement::generate(Vec2::new(i * 2000, 0), None, &mut rng);
+                index.sites.insert(Site::settlement(settlement))
+            })
+            .collect::<Vec<_>>();
+        for (a, b, by_land) in [(0, 1, true), (1, 2, true), (0, 2, false)].iter() {
+            for (from, to) in [(*a, *b), (*b, *a)].iter() {
+                index.sites[sites[*from]]
+                    .economy
+                    .neighbors
+                    .push(NeighborInformation {
+                        id: sites[*to],
+                        distance: 50.0,
+                        cost: if *by_land { Some(100.0) } else { None },
+                        sea_distance: if *by_land { None } else { Some(50.0) },
+                    });
+            }
+        }
+        for (from, to, coins) in trade {
+            index.sites[sites[*from]]
+                .economy
+                .route_trade
+                .insert(sites[*to].id(), *coins);
+        }
+        (index, sites)
+    }
+
+    #[test]
+    fn roads_follow_the_busiest_land_routes() {
+        let (index, sites) = index_with_trade(&[
+            (0, 1, 100.0),
+            (1, 0, 50.0),
+            (1, 2, 10.0),
+            (2, 1, 10.0),
+            // Sea routes don't need roads, however busy they are
+            (2, 0, 500.0),
+        ]);
+        let routes = road_routes(&index);
+        assert_eq!(routes.len(), 2);
+        assert_eq!(routes[0], (sites[0], sites[1], 1.0));
+        assert_eq!((routes[1].0, routes[1].1), (sites[1], sites[2]));
+        assert!((routes[1].2 - 20.0 / 150.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn quiet_routes_get_no_road() {
+        let (index, sites) = index_with_trade(&[(0, 1, 100.0), (1, 2, 5.0)]);
+        assert_eq!(road_routes(&index), vec![(sites[0], sites[1], 1.0)]);
+
+        let (index, _) = index_with_trade(&[]);
+        assert!(road_routes(&index).is_empty());
+    }
+}
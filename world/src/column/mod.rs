@@ -22,6 +22,21 @@ use std::{
 use tracing::error;
 use vek::*;
 
+/// Drop in the height of a river from one chunk to the next above which it
+/// falls over a cliff instead of running down a slope
+const WATERFALL_HEIGHT: f32 = 12.0;
+/// How far along the stretch of a river from one chunk to the next the edge of
+/// its waterfall is
+const WATERFALL_EDGE: f64 = 0.75;
+/// Share of the stretch of a river from one chunk to the next that its
+/// waterfall falls over
+const WATERFALL_WIDTH: f64 = 0.05;
+/// Width of a river, in blocks, at which the land beside it is flat floodplain
+/// for as much as it can be
+const FLOODPLAIN_RIVER_WIDTH: f32 = 48.0;
+/// Most of the land sloping down to a river that can be flat floodplain
+const MAX_FLOODPLAIN: f64 = 0.6;
+
 pub struct ColumnGen<'a> {
     pub sim: &'a WorldSim,
 }
@@ -274,6 +289,19 @@ impl<'a> Sampler<'a> for ColumnGen<'a> {
                     )
                 },
             };
+            // Rivers that drop far enough run level up to the edge of the cliff and
+            // then fall straight down it
+            let waterfall = kind.is_river()
+                && chunkj.alt.max(chunkj.water_alt)
+                    - downhill_chunk.alt.max(downhill_chunk.water_alt)
+                    > WATERFALL_HEIGHT;
+            let river_t = if waterfall {
+                ((river_t - WATERFALL_EDGE) / WATERFALL_WIDTH)
+                    .max(0.0)
+                    .min(1.0)
+            } else {
+                river_t
+            };
             let river_width_max =
                 if let Some(RiverKind::River { cross_section }) = downhill_chunk.river.river_kind {
                     cross_section.x as f64
@@ -421,7 +449,17 @@ impl<'a> Sampler<'a> for ColumnGen<'a> {
                         let river_scale = river_dist / scale_factor;
                         let river_alt =
                             Lerp::lerp(river_chunk.alt, downhill_river_chunk.alt, river_t as f32);
-                        let river_alt = Lerp::lerp(river_alt, alt, river_scale as f32);
+                        // Wide rivers spread out over flat floodplains before the land
+                        // rises away from them
+                        let floodplain = if let RiverKind::River { cross_section } = kind {
+                            (cross_section.x / FLOODPLAIN_RIVER_WIDTH).min(1.0) as f64
+                                * MAX_FLOODPLAIN
+                        } else {
+                            0.0
+                        };
+                        let plain_scale =
+                            ((river_scale - floodplain) / (1.0 - floodplain)).max(0.0);
+                        let river_alt = Lerp::lerp(river_alt, alt, plain_scale as f32);
                         let river_alt_diff = river_alt - alt;
                         let river_alt_inv = river_alt_diff as f64;
                         river_overlap_distance_product += (1.0 - river_scale) * river_alt_inv;
@@ -1,7 +1,16 @@
-use crate::{site::Site, Colors};
+use crate::{
+    site::{
+        economy::{EconomyHistory, Good},
+        faction::Stance,
+        Faction, Site,
+    },
+    util::MapVec,
+    Colors,
+};
 use common::{
     assets::{watch::ReloadIndicator, Asset, Ron},
-    store::Store,
+    store::{Id, Store},
+    time::Season,
 };
 use core::ops::Deref;
 use noise::{Seedable, SuperSimplex};
@@ -14,6 +23,7 @@ pub struct Index {
     pub time: f32,
     pub noise: Noise,
     pub sites: Store<Site>,
+    pub factions: Store<Faction>,
     indicator: ReloadIndicator,
 }
 
@@ -62,11 +72,84 @@ impl Index {
                 time: 0.0,
                 noise: Noise::new(seed),
                 sites: Store::default(),
+                factions: Store::default(),
                 indicator,
             },
             colors,
         )
     }
+
+    /// The season at the current time of the history simulation
+    pub fn season(&self) -> Season { Season::from_day(self.time) }
+
+    /// Price in coins of a good at a site, if the site has any use for it
+    pub fn price(&self, site: Id<Site>, good: Good) -> Option<f32> {
+        self.sites.get(site).economy.price(good)
+    }
+
+    /// The site with the most wealth in stock
+    pub fn richest_site(&self) -> Option<Id<Site>> {
+        self.sites
+            .iter()
+            .map(|(id, site)| (id, site.economy.wealth()))
+            .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
+            .map(|(id, _)| id)
+    }
+
+    /// The sites that a site trades with, along with the worth in coins of the
+    /// goods on the road between them in either direction, busiest first
+    pub fn trade_partners(&self, site: Id<Site>) -> Vec<(Id<Site>, f32)> {
+        let economy = &self.sites.get(site).economy;
+
+        let mut partners: Vec<(Id<Site>, f32)> = Vec::new();
+        let mut add = |partner: Id<Site>, value: f32| match partners
+            .iter_mut()
+            .find(|(id, _)| *id == partner)
+        {
+            Some((_, total)) => *total += value,
+            None => partners.push((partner, value)),
+        };
+        let worth = |goods: &MapVec<Good, f32>| {
+            goods
+                .iter()
+                .map(|(good, amount)| amount * economy.prices[good])
+                .sum::<f32>()
+        };
+
+        // Caravans sent out by the site
+        for caravan in economy.caravans.iter() {
+            if let Some(to) = economy.destination(caravan) {
+                add(to, worth(&caravan.goods));
+            }
+        }
+        // Caravans on their way to the site
+        for (id, other) in self.sites.iter().filter(|(id, _)| *id != site) {
+            for caravan in other.economy.caravans.iter() {
+                if caravan.to == site.id() {
+                    add(id, worth(&caravan.goods));
+                }
+            }
+        }
+
+        partners.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap_or(std::cmp::Ordering::Equal));
+        partners
+    }
+
+    /// Where the factions ruling two sites stand with each other. Sites of the
+    /// same faction are always allied, and sites that no faction rules are
+    /// neutral to everyone.
+    pub fn stance(&self, a: Id<Site>, b: Id<Site>) -> Stance {
+        match (self.sites.get(a).faction, self.sites.get(b).faction) {
+            (Some(a), Some(b)) if a == b => Stance::Allied,
+            (Some(a), Some(b)) => self.factions.get(a).relation(b).stance,
+            _ => Stance::Neutral,
+        }
+    }
+
+    /// The state of a site's economy over the last few ticks of the simulation
+    pub fn economy_history(&self, site: Id<Site>) -> &EconomyHistory {
+        &self.sites.get(site).economy.history
+    }
 }
 
 impl IndexOwned {
@@ -77,6 +160,10 @@ impl IndexOwned {
         }
     }
 
+    /// Mutable access to the index, which is only possible while no chunks are
+    /// being generated from it.
+    pub fn get_mut(&mut self) -> Option<&mut Index> { Arc::get_mut(&mut self.index) }
+
     /// NOTE: Callback is called only when colors actually have to be reloaded.
     /// The server is responsible for making sure that all affected chunks are
     /// reloaded; a naive approach will just regenerate every chunk on the
@@ -106,6 +193,7 @@ impl IndexOwned {
 pub struct Noise {
     pub cave_nz: SuperSimplex,
     pub scatter_nz: SuperSimplex,
+    pub ore_nz: SuperSimplex,
 }
 
 impl Noise {
@@ -114,6 +202,7 @@ impl Noise {
         Self {
             cave_nz: SuperSimplex::new().set_seed(seed + 0),
             scatter_nz: SuperSimplex::new().set_seed(seed + 1),
+            ore_nz: SuperSimplex::new().set_seed(seed + 2),
         }
     }
 }
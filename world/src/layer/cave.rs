@@ -0,0 +1,199 @@
+use crate::{column::ColumnSample, sim::Cave, IndexRef, CONFIG};
+use common::{
+    comp,
+    terrain::{BiomeKind, Block, BlockKind},
+};
+use noise::NoiseFn;
+use rand::prelude::*;
+use vek::*;
+
+/// What a cave is like inside, which follows from the land above it
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum CaveBiome {
+    Stone,
+    /// Damp caves overgrown with mushrooms, some of which glow
+    Fungal,
+    /// Caves under snowy land, whose lakes are frozen over
+    Frozen,
+    /// Caves under deserts, whose lakes are of lava
+    Scorched,
+}
+
+impl CaveBiome {
+    pub fn at(col_sample: &ColumnSample) -> Self {
+        match col_sample.chunk.get_biome() {
+            BiomeKind::Snowlands => CaveBiome::Frozen,
+            BiomeKind::Desert => CaveBiome::Scorched,
+            BiomeKind::Forest => CaveBiome::Fungal,
+            _ if col_sample.humidity > CONFIG.jungle_hum => CaveBiome::Fungal,
+            _ => CaveBiome::Stone,
+        }
+    }
+
+    /// The lottery of sprites scattered on the floor of the cave
+    pub fn scatter(self) -> &'static str {
+        match self {
+            CaveBiome::Stone => "common.cave_scatter",
+            CaveBiome::Fungal => "common.cave_scatter.fungal",
+            CaveBiome::Frozen => "common.cave_scatter.frozen",
+            CaveBiome::Scorched => "common.cave_scatter.scorched",
+        }
+    }
+
+    /// What the underground lakes of the cave are filled with
+    pub fn lake_block(self, index: IndexRef) -> Block {
+        match self {
+            CaveBiome::Frozen => Block::new(BlockKind::Ice, index.colors.layer.ice.into()),
+            CaveBiome::Scorched => Block::new(BlockKind::Lava, index.colors.layer.lava.into()),
+            _ => Block::new(BlockKind::Water, Rgb::zero()),
+        }
+    }
+
+    /// Chance of each block of the floor of the cave growing a glowing mushroom
+    pub fn glow_flora_chance(self) -> f32 {
+        match self {
+            CaveBiome::Fungal => 0.01,
+            CaveBiome::Stone => 0.001,
+            _ => 0.0,
+        }
+    }
+
+    /// Picks a creature that lives in the cave, and whether it is hostile
+    pub fn creature(self, rng: &mut impl Rng) -> (comp::Body, bool) {
+        use comp::{arthropod, biped_large, quadruped_low, quadruped_medium, quadruped_small};
+
+        match rng.gen_range(0, 7) {
+            0 => {
+                let species: &[_] = match self {
+                    CaveBiome::Stone => &[
+                        quadruped_small::Species::Truffler,
+                        quadruped_small::Species::Dodarock,
+                        quadruped_small::Species::Holladon,
+                        quadruped_small::Species::Batfox,
+                    ],
+                    CaveBiome::Fungal => &[
+                        quadruped_small::Species::Truffler,
+                        quadruped_small::Species::Fungome,
+                        quadruped_small::Species::Axolotl,
+                        quadruped_small::Species::Batfox,
+                    ],
+                    CaveBiome::Frozen => &[
+                        quadruped_small::Species::Holladon,
+                        quadruped_small::Species::Rat,
+                        quadruped_small::Species::Batfox,
+                    ],
+                    CaveBiome::Scorched => &[
+                        quadruped_small::Species::Dodarock,
+                        quadruped_small::Species::Gecko,
+                        quadruped_small::Species::Holladon,
+                    ],
+                };
+                let species = *species.choose(rng).unwrap();
+                (
+                    quadruped_small::Body::random_with(rng, &species).into(),
+                    false,
+                )
+            },
+            1 => {
+                let species: &[_] = match self {
+                    CaveBiome::Stone => &[
+                        quadruped_medium::Species::Tarasque,
+                        quadruped_medium::Species::Bonerattler,
+                    ],
+                    CaveBiome::Fungal => &[
+                        quadruped_medium::Species::Catoblepas,
+                        quadruped_medium::Species::Tarasque,
+                    ],
+                    CaveBiome::Frozen => &[
+                        quadruped_medium::Species::Frostfang,
+                        quadruped_medium::Species::Roshwalr,
+                    ],
+                    CaveBiome::Scorched => &[
+                        quadruped_medium::Species::Bonerattler,
+                        quadruped_medium::Species::Tarasque,
+                    ],
+                };
+                let species = *species.choose(rng).unwrap();
+                (
+                    quadruped_medium::Body::random_with(rng, &species).into(),
+                    true,
+                )
+            },
+            2 => {
+                let species: &[_] = match self {
+                    CaveBiome::Stone => &[
+                        quadruped_low::Species::Rocksnapper,
+                        quadruped_low::Species::Salamander,
+                    ],
+                    CaveBiome::Fungal => &[
+                        quadruped_low::Species::Maneater,
+                        quadruped_low::Species::Salamander,
+                    ],
+                    CaveBiome::Frozen => &[
+                        quadruped_low::Species::Pangolin,
+                        quadruped_low::Species::Rocksnapper,
+                    ],
+                    CaveBiome::Scorched => &[
+                        quadruped_low::Species::Salamander,
+                        quadruped_low::Species::Asp,
+                    ],
+                };
+                let species = *species.choose(rng).unwrap();
+                (quadruped_low::Body::random_with(rng, &species).into(), true)
+            },
+            3 => {
+                let species: &[_] = match self {
+                    CaveBiome::Scorched => {
+                        &[arthropod::Species::Scorpion, arthropod::Species::Beetle]
+                    },
+                    CaveBiome::Frozen => &[arthropod::Species::Spider],
+                    _ => &[arthropod::Species::Beetle, arthropod::Species::Spider],
+                };
+                let species = *species.choose(rng).unwrap();
+                (arthropod::Body::random_with(rng, &species).into(), true)
+            },
+            _ => {
+                let species: &[_] = match self {
+                    CaveBiome::Stone => &[
+                        biped_large::Species::Ogre,
+                        biped_large::Species::Cyclops,
+                        biped_large::Species::Troll,
+                    ],
+                    CaveBiome::Fungal => {
+                        &[biped_large::Species::Troll, biped_large::Species::Cyclops]
+                    },
+                    CaveBiome::Frozen => {
+                        &[biped_large::Species::Wendigo, biped_large::Species::Troll]
+                    },
+                    CaveBiome::Scorched => {
+                        &[biped_large::Species::Cyclops, biped_large::Species::Ogre]
+                    },
+                };
+                let species = *species.choose(rng).unwrap();
+                (biped_large::Body::random_with(rng, &species).into(), true)
+            },
+        }
+    }
+}
+
+/// Altitude up to which some stretches of deep caves are flooded by a lake,
+/// which is the floor of the cave where there is none
+pub fn lake_level(
+    index: IndexRef,
+    wpos2d: Vec2<i32>,
+    cave: &Cave,
+    cave_depth: f32,
+    cave_base: i32,
+) -> i32 {
+    if cave_depth > 60.0
+        && index
+            .noise
+            .cave_nz
+            .get(wpos2d.map(|e| e as f64 * 0.01).into_array())
+            > 0.3
+    {
+        (cave.alt - cave.width * 0.3) as i32
+    } else {
+        cave_base
+    }
+}
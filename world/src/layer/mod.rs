@@ -1,7 +1,9 @@
+mod cave;
 pub mod scatter;
 
 pub use self::scatter::apply_scatter_to;
 
+use self::cave::CaveBiome;
 use crate::{
     column::ColumnSample,
     util::{RandomField, Sampler},
@@ -28,6 +30,9 @@ use vek::*;
 pub struct Colors {
     pub bridge: (u8, u8, u8),
     pub stalagtite: (u8, u8, u8),
+    pub ice: (u8, u8, u8),
+    pub lava: (u8, u8, u8),
+    pub ore_vein: (u8, u8, u8),
 }
 
 const EMPTY_AIR: Block = Block::air(SpriteKind::Empty);
@@ -157,6 +162,13 @@ pub fn apply_caves_to<'a>(
                 let cave_base = (cave.alt + cave_floor) as i32;
                 let cave_roof = (cave.alt + cave_height) as i32;
 
+                let biome = CaveBiome::at(col_sample);
+                let cave_depth = (col_sample.alt - cave.alt).max(0.0);
+                let difficulty = cave_depth / 100.0;
+
+                // Lakes fill the bottom of some stretches of deep caves
+                let lake_level = cave::lake_level(index, wpos2d, &cave, cave_depth, cave_base);
+
                 for z in cave_base..cave_roof {
                     if cave_x < 0.95
                         || index.noise.cave_nz.get(
@@ -165,7 +177,12 @@ pub fn apply_caves_to<'a>(
                                 .into_array(),
                         ) < 0.0
                     {
-                        let _ = vol.set(Vec3::new(offs.x, offs.y, z), EMPTY_AIR);
+                        let block = if z < lake_level {
+                            biome.lake_block(index)
+                        } else {
+                            EMPTY_AIR
+                        };
+                        let _ = vol.set(Vec3::new(offs.x, offs.y, z), block);
                     }
                 }
 
@@ -190,24 +207,53 @@ pub fn apply_caves_to<'a>(
                     );
                 }
 
-                let cave_depth = (col_sample.alt - cave.alt).max(0.0);
-                let difficulty = cave_depth / 100.0;
+                // Ore veins run through the rock around deep caves
+                let mut floor_vein = false;
+                if cave_depth > 40.0 {
+                    for z in (cave_base - 3..cave_base).chain(cave_roof..cave_roof + 3) {
+                        let pos = Vec3::new(offs.x, offs.y, z);
+                        let vein = index.noise.ore_nz.get(
+                            Vec3::new(wpos2d.x, wpos2d.y, z)
+                                .map(|e| e as f64 * 0.08)
+                                .into_array(),
+                        ) > 0.6;
+                        if vein && vol.get(pos).map_or(false, |block| block.is_solid()) {
+                            let _ = vol.set(
+                                pos,
+                                Block::new(BlockKind::WeakRock, index.colors.layer.ore_vein.into()),
+                            );
+                            floor_vein |= z == cave_base - 1;
+                        }
+                    }
+                }
+
+                // Nothing grows at the bottom of a lake
+                if cave_base < lake_level {
+                    continue;
+                }
 
-                // Scatter things in caves
-                if RandomField::new(index.seed).chance(wpos2d.into(), 0.001 * difficulty.powf(1.5))
+                let floor = Vec3::new(offs.x, offs.y, cave_base);
+                if floor_vein && RandomField::new(index.seed + 3).chance(wpos2d.into(), 0.05) {
+                    let _ = vol.map(floor, |block| block.with_sprite(SpriteKind::VeloriteFrag));
+                } else if cave_base < surface_z - 15
+                    && RandomField::new(index.seed + 2)
+                        .chance(wpos2d.into(), biome.glow_flora_chance())
+                {
+                    let _ = vol.map(floor, |block| block.with_sprite(SpriteKind::GlowMushroom));
+                } else if RandomField::new(index.seed)
+                    .chance(wpos2d.into(), 0.001 * difficulty.powf(1.5))
                     && cave_base < surface_z as i32 - 25
                 {
-                    let kind = *Lottery::<SpriteKind>::load_expect("common.cave_scatter")
+                    // Scatter things in caves
+                    let kind = *Lottery::<SpriteKind>::load_expect(biome.scatter())
                         .choose_seeded(RandomField::new(index.seed + 1).get(wpos2d.into()));
-                    let _ = vol.map(Vec3::new(offs.x, offs.y, cave_base), |block| {
-                        block.with_sprite(kind)
-                    });
+                    let _ = vol.map(floor, |block| block.with_sprite(kind));
                 }
             }
         }
     }
 }
-#[allow(clippy::eval_order_dependence)]
+
 pub fn apply_caves_supplement<'a>(
     // NOTE: Used only for dynamic elements like chests and entities!
     dynamic_rng: &mut impl Rng,
@@ -246,54 +292,18 @@ pub fn apply_caves_supplement<'a>(
                 let cave_depth = (col_sample.alt - cave.alt).max(0.0);
                 let difficulty = cave_depth / 200.0;
 
-                // Scatter things in caves
+                // Scatter things in caves, though not at the bottom of lakes
                 if RandomField::new(index.seed).chance(wpos2d.into(), 0.001 * difficulty)
                     && cave_base < surface_z as i32 - 40
+                    && cave::lake_level(index, wpos2d, &cave, cave_depth, cave_base) <= cave_base
                 {
-                    let is_hostile: bool;
+                    let (body, is_hostile) = CaveBiome::at(col_sample).creature(dynamic_rng);
                     let entity = EntityInfo::at(Vec3::new(
                         wpos2d.x as f32,
                         wpos2d.y as f32,
                         cave_base as f32,
                     ))
-                    .with_body(match dynamic_rng.gen_range(0, 6) {
-                        0 => {
-                            is_hostile = false;
-                            let species = match dynamic_rng.gen_range(0, 4) {
-                                0 => comp::quadruped_small::Species::Truffler,
-                                1 => comp::quadruped_small::Species::Dodarock,
-                                2 => comp::quadruped_small::Species::Holladon,
-                                _ => comp::quadruped_small::Species::Batfox,
-                            };
-                            comp::quadruped_small::Body::random_with(dynamic_rng, &species).into()
-                        },
-                        1 => {
-                            is_hostile = true;
-                            let species = match dynamic_rng.gen_range(0, 5) {
-                                0 => comp::quadruped_medium::Species::Tarasque,
-                                _ => comp::quadruped_medium::Species::Bonerattler,
-                            };
-                            comp::quadruped_medium::Body::random_with(dynamic_rng, &species).into()
-                        },
-                        2 => {
-                            is_hostile = true;
-                            let species = match dynamic_rng.gen_range(0, 4) {
-                                1 => comp::quadruped_low::Species::Rocksnapper,
-                                _ => comp::quadruped_low::Species::Salamander,
-                            };
-                            comp::quadruped_low::Body::random_with(dynamic_rng, &species).into()
-                        },
-                        _ => {
-                            is_hostile = true;
-                            let species = match dynamic_rng.gen_range(0, 8) {
-                                0 => comp::biped_large::Species::Ogre,
-                                1 => comp::biped_large::Species::Cyclops,
-                                2 => comp::biped_large::Species::Wendigo,
-                                _ => comp::biped_large::Species::Troll,
-                            };
-                            comp::biped_large::Body::random_with(dynamic_rng, &species).into()
-                        },
-                    })
+                    .with_body(body)
                     .with_alignment(if is_hostile {
                         comp::Alignment::Enemy
                     } else {
@@ -36,10 +36,12 @@ use crate::{
     util::{Grid, Sampler},
 };
 use common::{
-    comp::{self, bird_medium, quadruped_low, quadruped_medium, quadruped_small},
+    comp::{self, arthropod, bird_medium, quadruped_low, quadruped_medium, quadruped_small},
     generation::{ChunkSupplement, EntityInfo},
     msg::WorldMapMsg,
-    terrain::{Block, BlockKind, SpriteKind, TerrainChunk, TerrainChunkMeta, TerrainChunkSize},
+    terrain::{
+        BiomeKind, Block, BlockKind, SpriteKind, TerrainChunk, TerrainChunkMeta, TerrainChunkSize,
+    },
     vol::{ReadVol, RectVolSize, WriteVol},
 };
 use rand::Rng;
@@ -71,10 +73,34 @@ impl World {
         // NOTE: Generating index first in order to quickly fail if the color manifest
         // is broken.
         let (mut index, colors) = Index::new(seed);
+        let history = opts.history.clone();
         let mut sim = sim::WorldSim::generate(seed, opts);
-        let civs = civ::Civs::generate(seed, &mut sim, &mut index);
+        let mut civs = civ::Civs::generate(seed, &mut sim, &mut index);
 
-        sim2::simulate(&mut index, &mut sim);
+        let mut timelapse = history.timelapse.as_ref().and_then(|opts| {
+            sim2::timelapse::Timelapse::new(opts, &sim, IndexRef {
+                colors: &colors,
+                index: &index,
+            })
+        });
+        sim2::simulate(
+            &mut index,
+            &history,
+            &mut *history.report.reporter(),
+            timelapse.as_mut(),
+        );
+        civ::generate_trade_roads(seed, &index, &mut sim);
+        civs.generate_bandit_camps(seed, &index, &sim);
+        for site in index.sites.values_mut() {
+            site.grow(&sim);
+        }
+
+        if let Some(timelapse) = &mut timelapse {
+            timelapse.finish(&sim, IndexRef {
+                colors: &colors,
+                index: &index,
+            });
+        }
 
         (Self { sim, civs }, IndexOwned::new(index, colors))
     }
@@ -89,6 +115,176 @@ impl World {
 
     pub fn get_map_data(&self, index: IndexRef) -> WorldMapMsg { self.sim.get_map(index) }
 
+    /// The bounds of the floors of every dungeon.
+    pub fn dungeon_bounds(&self, index: IndexRef) -> Vec<Aabb<i32>> {
+        index
+            .sites
+            .values()
+            .filter_map(|site| match &site.kind {
+                site::SiteKind::Dungeon(dungeon) => Some(dungeon.bounds()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Finds the dungeon whose floors contain `wpos`, returning the id of its
+    /// site and its bounds.
+    pub fn dungeon_at(&self, index: IndexRef, wpos: Vec3<i32>) -> Option<(u64, Aabb<i32>)> {
+        index.sites.iter().find_map(|(id, site)| match &site.kind {
+            site::SiteKind::Dungeon(dungeon) => Some(dungeon.bounds())
+                .filter(|bounds| bounds.contains_point(wpos))
+                .map(|bounds| (id.id(), bounds)),
+            _ => None,
+        })
+    }
+
+    /// Generates a fresh set of inhabitants for the dungeon with the given site
+    /// id, independently of the ones spawned alongside its terrain.
+    pub fn dungeon_supplement(
+        &self,
+        index: IndexRef,
+        site: u64,
+        rng: &mut impl Rng,
+    ) -> ChunkSupplement {
+        let mut supplement = ChunkSupplement::default();
+
+        if let Some(dungeon) = find_dungeon(index.index, site) {
+            let chunk_size = TerrainChunkSize::RECT_SIZE.map(|e| e as i32);
+            let bounds = dungeon.bounds();
+            let min_chunk = Vec2::from(bounds.min).map2(chunk_size, |e: i32, sz| e.div_euclid(sz));
+            let max_chunk = Vec2::from(bounds.max).map2(chunk_size, |e: i32, sz| e.div_euclid(sz));
+            for y in min_chunk.y..=max_chunk.y {
+                for x in min_chunk.x..=max_chunk.x {
+                    dungeon.apply_supplement(
+                        rng,
+                        Vec2::new(x, y) * chunk_size,
+                        |_| None,
+                        &mut supplement,
+                    );
+                }
+            }
+        }
+
+        supplement
+    }
+
+    /// Whether the keys of the dungeon with the given site id are kept after
+    /// unlocking a gate rather than being consumed.
+    pub fn dungeon_reusable_keys(&self, index: IndexRef, site: u64) -> bool {
+        find_dungeon(index.index, site).map_or(false, |dungeon| dungeon.reusable_keys())
+    }
+
+    /// Lists the castles that factions fight over, giving the id of each site,
+    /// the centre and radius of its walls and whether it starts out held by
+    /// hostile forces.
+    pub fn outposts(&self, index: IndexRef) -> Vec<(u64, Vec2<i32>, f32, bool)> {
+        index
+            .sites
+            .iter()
+            .filter_map(|(id, site)| match &site.kind {
+                site::SiteKind::Castle(castle) => Some((
+                    id.id(),
+                    castle.get_origin(),
+                    castle.walls_radius(),
+                    castle.is_evil(),
+                )),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Lists the outlaws that towns have put bounties on, giving the name of
+    /// each outlaw, a description of their crime, the world positions of the
+    /// town and of the outlaw's camp and the reward for killing them. The id of
+    /// each bounty is its place in the list.
+    pub fn bounties(&self) -> Vec<(String, String, Vec2<i32>, Vec2<i32>, u32)> {
+        let to_wpos = |chunk_pos: Vec2<i32>| {
+            chunk_pos.map2(TerrainChunkSize::RECT_SIZE, |e, sz: u32| {
+                e * sz as i32 + sz as i32 / 2
+            })
+        };
+        self.civs
+            .outlaws()
+            .iter()
+            .map(|outlaw| {
+                (
+                    outlaw.name.clone(),
+                    format!("{} years ago, for {}", outlaw.years_ago, outlaw.crime),
+                    to_wpos(outlaw.town),
+                    to_wpos(outlaw.camp),
+                    outlaw.bounty,
+                )
+            })
+            .collect()
+    }
+
+    /// The history of the site with the `site` id as its people tell it, oldest
+    /// events first
+    pub fn site_lore(&self, index: IndexRef, site: u64) -> Vec<String> {
+        index
+            .sites
+            .iter()
+            .find(|(id, _)| id.id() == site)
+            .map(|(_, site)| site.economy.lore().collect())
+            .unwrap_or_default()
+    }
+
+    /// Lists the markets of the towns and ports, giving the site id and origin
+    /// of each town and the coins in its economy, which its market pays out of
+    /// and takes coins into, along with the name, price in coins and stock of
+    /// each of the goods traded there, as found by simulating the economy of
+    /// the world.
+    pub fn markets(&self, index: IndexRef) -> Vec<(u64, Vec2<i32>, f32, Vec<(String, f32, f32)>)> {
+        index
+            .sites
+            .iter()
+            .filter(|(_, site)| {
+                matches!(site.kind, site::SiteKind::Settlement(_) | site::SiteKind::Port(_))
+            })
+            .map(|(id, site)| {
+                let goods = site::economy::ALL_GOODS
+                    .iter()
+                    .filter(|good| **good != site::economy::Good::Coin)
+                    .filter(|good| site.economy.values[**good].is_some())
+                    .map(|good| {
+                        (
+                            format!("{:?}", good),
+                            site.economy.prices[*good],
+                            site.economy.stocks[*good],
+                        )
+                    })
+                    .collect();
+                (
+                    id.id(),
+                    site.get_origin(),
+                    site.economy.stocks[site::economy::Good::Coin],
+                    goods,
+                )
+            })
+            .collect()
+    }
+
+    /// Loot table of the fish that can be caught in the water at the given
+    /// world position, which depends on the biome and on whether the water is
+    /// the sea, a lake or a river.
+    pub fn fish_table(&self, wpos: Vec2<i32>) -> &'static str {
+        let chunk = match self.sim.get_wpos(wpos) {
+            Some(chunk) => chunk,
+            None => return "common.loot_tables.fishing.river",
+        };
+        if chunk.river.is_ocean() {
+            return "common.loot_tables.fishing.ocean";
+        }
+        match chunk.get_biome() {
+            BiomeKind::Ocean => "common.loot_tables.fishing.ocean",
+            BiomeKind::Snowlands | BiomeKind::Mountain => "common.loot_tables.fishing.cold",
+            BiomeKind::Desert => "common.loot_tables.fishing.desert",
+            BiomeKind::Swamp => "common.loot_tables.fishing.lake",
+            _ if chunk.river.is_lake() => "common.loot_tables.fishing.lake",
+            _ => "common.loot_tables.fishing.river",
+        }
+    }
+
     pub fn sample_columns(
         &self,
     ) -> impl Sampler<Index = (Vec2<i32>, IndexRef), Sample = Option<ColumnSample>> + '_ {
@@ -151,7 +347,12 @@ impl World {
             },
         };
 
-        let meta = TerrainChunkMeta::new(sim_chunk.get_name(&self.sim), sim_chunk.get_biome());
+        let meta = TerrainChunkMeta::new(
+            sim_chunk.get_name(&self.sim),
+            sim_chunk.get_biome(),
+            sim_chunk.temp,
+            sim_chunk.river_flow(),
+        );
 
         let mut chunk = TerrainChunk::new(base_z, stone, air, meta);
 
@@ -235,22 +436,25 @@ impl World {
                 // TODO: REFACTOR: Define specific alignments in a config file instead of here
                 let is_hostile: bool;
                 let is_giant = dynamic_rng.gen_range(0, 8) == 0;
+                let is_desert =
+                    sim_chunk.temp > CONFIG.desert_temp && sim_chunk.humidity < CONFIG.desert_hum;
                 let quadmed = comp::Body::QuadrupedMedium(quadruped_medium::Body::random()); // Not all of them are hostile so we have to do the rng here
                 let quadlow = comp::Body::QuadrupedLow(quadruped_low::Body::random()); // Not all of them are hostile so we have to do the rng here
                 let entity = EntityInfo::at(gen_entity_pos(&mut dynamic_rng))
                     .do_if(is_giant, |e| e.into_giant())
                     .with_body(match dynamic_rng.gen_range(0, 5) {
+                        0 if is_desert => {
+                            is_hostile = true;
+                            arthropod::Body::random_with(
+                                &mut dynamic_rng,
+                                &arthropod::Species::Scorpion,
+                            )
+                            .into()
+                        },
                         0 => {
                             match quadmed {
                                 comp::Body::QuadrupedMedium(quadruped_medium) => {
-                                    match quadruped_medium.species {
-                                        quadruped_medium::Species::Catoblepas => is_hostile = false,
-                                        quadruped_medium::Species::Mouflon => is_hostile = false,
-                                        quadruped_medium::Species::Tuskram => is_hostile = false,
-                                        quadruped_medium::Species::Deer => is_hostile = false,
-                                        quadruped_medium::Species::Hirdrasil => is_hostile = false,
-                                        _ => is_hostile = true,
-                                    }
+                                    is_hostile = quadruped_medium.stats().hostile
                                 },
                                 _ => is_hostile = true,
                             };
@@ -298,6 +502,61 @@ impl World {
             supplement.add_entity(EntityInfo::at(gen_entity_pos(&mut dynamic_rng)).into_waypoint());
         }
 
+        // Apply outlaw camps
+        for (id, outlaw) in self
+            .civs
+            .outlaws()
+            .iter()
+            .enumerate()
+            .filter(|(_, o)| o.camp == chunk_pos)
+        {
+            let level = 10 + outlaw.bounty / 10;
+            supplement.add_entity(
+                EntityInfo::at(gen_entity_pos(&mut dynamic_rng))
+                    .with_body(comp::Body::Humanoid(comp::humanoid::Body::random()))
+                    .with_alignment(comp::Alignment::Enemy)
+                    .with_name(outlaw.name.clone())
+                    .with_bounty(id as u64)
+                    .with_level(level)
+                    .with_main_tool(comp::Item::new_from_asset_expect(
+                        "common.items.weapons.sword.zweihander_sword_0",
+                    )),
+            );
+            for _ in 0..dynamic_rng.gen_range(2, 5) {
+                supplement.add_entity(
+                    EntityInfo::at(gen_entity_pos(&mut dynamic_rng))
+                        .with_body(comp::Body::Humanoid(comp::humanoid::Body::random()))
+                        .with_alignment(comp::Alignment::Enemy)
+                        .with_name("Outlaw Henchman")
+                        .with_level(level / 2)
+                        .with_main_tool(comp::Item::new_from_asset_expect(
+                            "common.items.weapons.sword.short_sword_0",
+                        )),
+                );
+            }
+        }
+
+        // Apply bandit camps
+        for camp in self
+            .civs
+            .bandit_camps()
+            .iter()
+            .filter(|c| c.camp == chunk_pos)
+        {
+            for _ in 0..camp.bandits {
+                supplement.add_entity(
+                    EntityInfo::at(gen_entity_pos(&mut dynamic_rng))
+                        .with_body(comp::Body::Humanoid(comp::humanoid::Body::random()))
+                        .with_alignment(comp::Alignment::Enemy)
+                        .with_name("Bandit")
+                        .with_level(camp.level)
+                        .with_main_tool(comp::Item::new_from_asset_expect(
+                            "common.items.weapons.sword.short_sword_0",
+                        )),
+                );
+            }
+        }
+
         // Apply layer supplement
         layer::apply_caves_supplement(
             &mut dynamic_rng,
@@ -311,6 +570,7 @@ impl World {
         // Apply site supplementary information
         sim_chunk.sites.iter().for_each(|site| {
             index.sites[*site].apply_supplement(
+                *site,
                 &mut dynamic_rng,
                 chunk_wpos2d,
                 sample_get,
@@ -324,3 +584,15 @@ impl World {
         Ok((chunk, supplement))
     }
 }
+
+/// Finds the dungeon with the given site id
+fn find_dungeon(index: &Index, site: u64) -> Option<&site::Dungeon> {
+    index
+        .sites
+        .iter()
+        .find(|(id, _)| id.id() == site)
+        .and_then(|(_, site)| match &site.kind {
+            site::SiteKind::Dungeon(dungeon) => Some(dungeon),
+            _ => None,
+        })
+}
@@ -27,6 +27,7 @@ use crate::{
     block::BlockGen,
     civ::Place,
     column::ColumnGen,
+    sim2::HistoryOpts,
     site::Site,
     util::{seed_expan, FastNoise, RandomField, Sampler, StructureGen2d, LOCALITY, NEIGHBORS},
     IndexRef, CONFIG,
@@ -72,6 +73,10 @@ const DEFAULT_WORLD_CHUNKS_LG: MapSizeLg =
         panic!("Default world chunk size does not satisfy required invariants.");
     };
 
+/// Fastest that rivers flow in the game, in blocks per second, so that swimmers
+/// can still make their way against them
+const MAX_RIVER_FLOW: f32 = 6.0;
+
 /// A structure that holds cached noise values and cumulative distribution
 /// functions for the input that led to those values.  See the definition of
 /// InverseCdf for a description of how to interpret the types of its fields.
@@ -154,6 +159,8 @@ pub struct WorldOpts {
     /// Set to false to disable seeding elements during worldgen.
     pub seed_elements: bool,
     pub world_file: FileOpts,
+    /// How much of the world's history to simulate, and where to checkpoint it.
+    pub history: HistoryOpts,
 }
 
 impl Default for WorldOpts {
@@ -161,6 +168,7 @@ impl Default for WorldOpts {
         Self {
             seed_elements: true,
             world_file: Default::default(),
+            history: Default::default(),
         }
     }
 }
@@ -2285,6 +2293,16 @@ impl SimChunk {
         */
     }
 
+    /// Velocity of the river flowing through the chunk, in blocks per second
+    pub fn river_flow(&self) -> Vec2<f32> {
+        if self.river.is_river() {
+            let flow = self.river.velocity.xy();
+            flow * (MAX_RIVER_FLOW / flow.magnitude().max(MAX_RIVER_FLOW))
+        } else {
+            Vec2::zero()
+        }
+    }
+
     pub fn get_biome(&self) -> BiomeKind {
         if self.alt < CONFIG.sea_level {
             BiomeKind::Ocean
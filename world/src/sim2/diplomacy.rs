@@ -0,0 +1,134 @@
+//! Relations between factions, which warm the more their sites trade with each
+//! other and cool the more their sites on the border compete for the same
+//! goods. Factions that fall out badly enough go to war, which stops the trade
+//! between them until they tire of fighting, and factions that get on well
+//! enough become allies.
+
+use crate::{
+    site::{economy::Good, faction::Stance},
+    Index,
+};
+use tracing::debug;
+
+/// How much standing two factions gain for their trade with each other making
+/// up all of their trade
+const TRADE_WEIGHT: f32 = 0.5;
+/// How much standing two factions lose for their sites on the border being
+/// short of all the same goods
+const COMPETITION_WEIGHT: f32 = 0.3;
+/// How much standing factions at war gain each tick as they tire of fighting
+const WAR_WEARINESS: f32 = 0.05;
+/// Share of the standing between factions that fades each tick, so that old
+/// friendships and grudges are forgotten
+const STANDING_DECAY: f32 = 0.05;
+
+/// Standing below which neutral factions go to war
+const WAR_STANDING: f32 = -0.6;
+/// Standing above which factions at war make peace
+const PEACE_STANDING: f32 = -0.2;
+/// Standing above which neutral factions become allies
+const ALLIANCE_STANDING: f32 = 0.6;
+/// Standing below which allies go their separate ways
+const SPLIT_STANDING: f32 = 0.3;
+
+/// Update the relations between each pair of factions whose sites border each
+/// other, from the trade and competition between them during the last tick
+pub fn tick_diplomacy(index: &mut Index) {
+    let count = index.factions.values().count();
+    let time = index.time;
+
+    // Worth of the goods sent by each faction to each faction, by the prices
+    // where they were sent from
+    let mut trade = vec![vec![0.0; count]; count];
+    let mut exports = vec![0.0; count];
+    // How many of the routes between the sites of each pair of factions there
+    // are, and how short of the same goods the sites at both ends are
+    let mut borders = vec![vec![0u32; count]; count];
+    let mut competition = vec![vec![0.0; count]; count];
+    for site in index.sites.values() {
+        let faction = match site.faction {
+            Some(faction) => faction.id() as usize,
+            None => continue,
+        };
+        let economy = &site.economy;
+
+        for caravan in economy.caravans.iter().filter(|c| c.departure == time) {
+            let worth = caravan
+                .goods
+                .iter()
+                .map(|(good, amount)| amount * economy.prices[good])
+                .sum::<f32>();
+            exports[faction] += worth;
+            let destination = economy.destination(caravan);
+            if let Some(other) = destination.and_then(|to| index.sites[to].faction) {
+                trade[faction][other.id() as usize] += worth;
+            }
+        }
+
+        for neighbor in economy.neighbors.iter() {
+            let other_site = &index.sites[neighbor.id];
+            let other = match other_site.faction {
+                Some(other) if other.id() as usize != faction => other.id() as usize,
+                _ => continue,
+            };
+            let shortages = Good::list()
+                .iter()
+                .filter(|good| {
+                    economy.surplus[**good] < 0.0 && other_site.economy.surplus[**good] < 0.0
+                })
+                .count();
+            borders[faction][other] += 1;
+            competition[faction][other] += shortages as f32 / Good::list().len() as f32;
+        }
+    }
+
+    for (a, faction) in index.factions.values_mut().enumerate() {
+        for (b, relation) in faction.relations.iter_mut().enumerate() {
+            if a == b || borders[a][b] == 0 {
+                continue;
+            }
+
+            let dependence = (trade[a][b] + trade[b][a]) / (exports[a] + exports[b]).max(1.0);
+            relation.standing += dependence * TRADE_WEIGHT;
+            if relation.stance == Stance::War {
+                // The contested goods are fought over rather than competed for
+                relation.standing += WAR_WEARINESS;
+            } else {
+                relation.standing -= competition[a][b] / borders[a][b] as f32 * COMPETITION_WEIGHT;
+            }
+            relation.standing = (relation.standing * (1.0 - STANDING_DECAY))
+                .max(-1.0)
+                .min(1.0);
+
+            let stance = match relation.stance {
+                Stance::War if relation.standing > PEACE_STANDING => Stance::Neutral,
+                Stance::Allied if relation.standing < SPLIT_STANDING => Stance::Neutral,
+                Stance::Neutral if relation.standing < WAR_STANDING => Stance::War,
+                Stance::Neutral if relation.standing > ALLIANCE_STANDING => Stance::Allied,
+                stance => stance,
+            };
+            if stance != relation.stance && a < b {
+                debug!(?a, ?b, ?stance, "Factions changed their stance");
+            }
+            relation.stance = stance;
+        }
+    }
+}
+
+/// Flag the sites that border a faction that their own faction is at war
+/// with as fortified, so that they are generated ready to defend themselves
+pub fn fortify_borders(index: &mut Index) {
+    let fortified = index
+        .sites
+        .iter()
+        .map(|(id, site)| {
+            site.economy
+                .neighbors
+                .iter()
+                .any(|neighbor| index.stance(id, neighbor.id) == Stance::War)
+        })
+        .collect::<Vec<_>>();
+    for (site, fortified) in index.sites.values_mut().zip(fortified) {
+        site.fortified = fortified;
+    }
+}
@@ -0,0 +1,96 @@
+//! Disasters and strokes of luck that strike sites at random while the history
+//! of the world is simulated, so that no two sites share the same history.
+//!
+//! Each tick draws from its own random generator, seeded from the world seed
+//! and the number of the tick, so a history resumed from a checkpoint turns
+//! out the same as one simulated in a single go.
+
+use super::{TICK_PERIOD, YEAR};
+use crate::{util::seed_expan, Index};
+use rand::prelude::*;
+use rand_chacha::ChaChaRng;
+use serde::{Deserialize, Serialize};
+
+/// Chance of a plague breaking out in a site each tick
+const PLAGUE_CHANCE: f32 = 0.004;
+/// Chance of the crops of a site failing each tick
+const CROP_FAILURE_CHANCE: f32 = 0.01;
+/// Chance of a new ore deposit being found near a site each tick
+const DISCOVERY_CHANCE: f32 = 0.003;
+
+/// Least and most of the people of a site that a plague kills
+const PLAGUE_DEATHS: (f32, f32) = (0.1, 0.4);
+/// How many ticks the farmland of a site takes to recover from a crop failure
+const CROP_FAILURE_TICKS: u32 = 4;
+/// Share of their usual harvest that farmers bring in while the crops fail
+pub const CROP_FAILURE_YIELD: f32 = 0.2;
+/// How much each deposit found near a site raises the yield of its mines
+pub const DISCOVERY_YIELD: f32 = 0.5;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum EventKind {
+    /// A plague that killed some of the people
+    Plague { deaths: f32 },
+    /// The crops failed, leaving the farmers with a fraction of their harvest
+    /// for a year
+    CropFailure,
+    /// A new ore deposit was found, making the mines more productive for good
+    Discovery,
+}
+
+/// Something that happened to a site while its history was simulated
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SiteEvent {
+    pub time: f32,
+    pub kind: EventKind,
+}
+
+impl SiteEvent {
+    /// Tells of the event the way the people of the site remember it
+    pub fn describe(&self) -> String {
+        let year = (self.time / YEAR) as i32;
+        match self.kind {
+            EventKind::Plague { deaths } => format!(
+                "In the year {}, a plague took {} lives.",
+                year,
+                deaths.round() as u32
+            ),
+            EventKind::CropFailure => format!(
+                "In the year {}, the crops failed and the granaries ran empty.",
+                year
+            ),
+            EventKind::Discovery => format!(
+                "In the year {}, a rich seam of ore was struck nearby.",
+                year
+            ),
+        }
+    }
+}
+
+/// Strikes sites with plagues, crop failures and discoveries at random, and
+/// lets the farmland of sites recover from earlier crop failures
+pub fn tick_events(index: &mut Index) {
+    let tick = (index.time / TICK_PERIOD) as u32;
+    let seed = seed_expan::diffuse_mult(&[index.seed, tick]);
+    let mut rng = ChaChaRng::from_seed(seed_expan::rng_state(seed));
+    let time = index.time;
+
+    for site in index.sites.values_mut() {
+        let economy = &mut site.economy;
+        economy.crop_failure_ticks = economy.crop_failure_ticks.saturating_sub(1);
+
+        let kind = if rng.gen::<f32>() < PLAGUE_CHANCE {
+            let deaths = economy.die_off(rng.gen_range(PLAGUE_DEATHS.0, PLAGUE_DEATHS.1));
+            EventKind::Plague { deaths }
+        } else if rng.gen::<f32>() < CROP_FAILURE_CHANCE {
+            economy.crop_failure_ticks = CROP_FAILURE_TICKS;
+            EventKind::CropFailure
+        } else if rng.gen::<f32>() < DISCOVERY_CHANCE {
+            economy.deposits += 1;
+            EventKind::Discovery
+        } else {
+            continue;
+        };
+        economy.events.push(SiteEvent { time, kind });
+    }
+}
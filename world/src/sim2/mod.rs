@@ -1,98 +1,499 @@
+pub mod diplomacy;
+pub mod events;
+pub mod report;
+pub mod timelapse;
+
 use crate::{
-    sim::WorldSim,
     site::{
-        economy::{Good, Labor},
-        Site,
+        economy::{Caravan, Economy, EconomyRecipes, Good, Labor, ALL_GOODS},
+        faction::{Relation, Stance},
     },
     util::MapVec,
     Index,
 };
-use common::store::Id;
-use tracing::debug;
+use common::time::Season;
+use rayon::prelude::*;
+use report::{EconomyReport, EconomyReporter, SiteReport};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter},
+    path::{Path, PathBuf},
+};
+use timelapse::{Timelapse, TimelapseOpts};
+use tracing::{debug, info, warn};
+use vek::*;
 
 const MONTH: f32 = 30.0;
 const YEAR: f32 = 12.0 * MONTH;
-const TICK_PERIOD: f32 = 3.0 * MONTH; // 3 months
-const HISTORY_DAYS: f32 = 500.0 * YEAR; // 500 years
-
-const GENERATE_CSV: bool = false;
-
-pub fn simulate(index: &mut Index, world: &mut WorldSim) {
-    use std::io::Write;
-    let mut f = if GENERATE_CSV {
-        let mut f = std::fs::File::create("economy.csv").unwrap();
-        write!(f, "Population,").unwrap();
-        for g in Good::list() {
-            write!(f, "{:?} Value,", g).unwrap();
-        }
-        for g in Good::list() {
-            write!(f, "{:?} LaborVal,", g).unwrap();
-        }
-        for g in Good::list() {
-            write!(f, "{:?} Stock,", g).unwrap();
-        }
-        for g in Good::list() {
-            write!(f, "{:?} Surplus,", g).unwrap();
+pub const TICK_PERIOD: f32 = 3.0 * MONTH; // 3 months
+const HISTORY_YEARS: f32 = 500.0;
+/// How much history is simulated between saves of the checkpoint
+const CHECKPOINT_PERIOD: f32 = 50.0 * YEAR;
+
+/// How many ticks pass between reports on the economies of sites
+const REPORT_INTERVAL: i32 = 5;
+
+/// Share of a site's surplus of a good that it sends to its neighbours each tick
+const EXPORT_FRACTION: f32 = 0.5;
+/// Share of a caravan's goods used up on the way for each unit of route cost
+/// and of weight
+const TRANSPORT_COST: f32 = 0.0005;
+/// How far a caravan travels in a day, in chunks
+const CARAVAN_SPEED: f32 = 20.0;
+/// Share of a ship's goods used up on the way for each chunk sailed and unit
+/// of weight
+const SHIPPING_COST: f32 = 0.0001;
+/// How far a ship sails in a day, in chunks
+const SHIP_SPEED: f32 = 15.0;
+
+/// Share of a struggling site's population that moves away each tick
+const MIGRATION_RATE: f32 = 0.05;
+/// How many ticks in a row a site has to be short of food before people leave
+const FAMINE_TICKS: u32 = 4;
+/// Share of the workforce that has to be idle before people leave
+const MAX_UNEMPLOYMENT: f32 = 0.3;
+
+/// Goods that people turn to crime to get when they are short of them
+const ESSENTIAL_GOODS: [Good; 2] = [Good::Food, Good::Wood];
+/// Most of the people of a site that can turn to crime
+pub const MAX_CRIME: f32 = 0.5;
+/// Share of the way to the crime that hardship drives people to that they
+/// turn to each tick
+const CRIME_GROWTH: f32 = 0.25;
+/// Share of the crime that fades each tick once times get better
+const CRIME_FADE: f32 = 0.05;
+
+/// How much history to simulate during worldgen, and where to checkpoint it.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HistoryOpts {
+    /// Years of history that the world should have behind it once worldgen is
+    /// done.
+    pub years: f32,
+    /// If set, the simulation resumes from the checkpoint at this path (when
+    /// it exists) instead of starting over, and saves its progress there as
+    /// it goes. Raising `years` on a world with a checkpoint simulates only
+    /// the extra years.
+    pub checkpoint: Option<PathBuf>,
+    /// Where to report on the economies of sites as they develop.
+    #[serde(default)]
+    pub report: EconomyReport,
+    /// If set, snapshots of the map are rendered as the history is simulated,
+    /// to be played back as a timelapse.
+    #[serde(default)]
+    pub timelapse: Option<TimelapseOpts>,
+}
+
+impl Default for HistoryOpts {
+    fn default() -> Self {
+        Self {
+            years: HISTORY_YEARS,
+            checkpoint: None,
+            report: EconomyReport::None,
+            timelapse: None,
         }
-        for l in Labor::list() {
-            write!(f, "{:?} Labor,", l).unwrap();
+    }
+}
+
+/// The state of the history simulation at some point in time.
+///
+/// Sites are regenerated from the world seed rather than saved, so only the
+/// state that the simulation changes is stored here. The origin of each site
+/// is kept alongside its economy to make sure that a checkpoint is only ever
+/// applied to the world it was saved from.
+#[derive(Serialize, Deserialize)]
+struct Checkpoint {
+    seed: u32,
+    time: f32,
+    sites: Vec<(Vec2<i32>, Economy)>,
+    /// The relations of each faction with each faction
+    relations: Vec<Vec<Relation>>,
+}
+
+impl Checkpoint {
+    fn new(index: &Index) -> Self {
+        Self {
+            seed: index.seed,
+            time: index.time,
+            sites: index
+                .sites
+                .values()
+                .map(|site| (site.get_origin(), site.economy.clone()))
+                .collect(),
+            relations: index
+                .factions
+                .values()
+                .map(|faction| faction.relations.clone())
+                .collect(),
         }
-        for l in Labor::list() {
-            write!(f, "{:?} Productivity,", l).unwrap();
+    }
+
+    fn matches(&self, index: &Index) -> bool {
+        self.seed == index.seed
+            && self.sites.len() == index.sites.values().count()
+            && self.relations.len() == index.factions.values().count()
+            && self
+                .sites
+                .iter()
+                .zip(index.sites.values())
+                .all(|((origin, _), site)| *origin == site.get_origin())
+    }
+
+    fn apply(self, index: &mut Index) {
+        index.time = self.time;
+        for ((_, economy), site) in self.sites.into_iter().zip(index.sites.values_mut()) {
+            let neighbors = std::mem::take(&mut site.economy.neighbors);
+            let endowment = std::mem::take(&mut site.economy.endowment);
+            site.economy = Economy {
+                neighbors,
+                endowment,
+                ..economy
+            };
         }
-        for l in Labor::list() {
-            write!(f, "{:?} Yields,", l).unwrap();
+        for (relations, faction) in self.relations.into_iter().zip(index.factions.values_mut()) {
+            faction.relations = relations;
         }
-        writeln!(f).unwrap();
-        Some(f)
-    } else {
-        None
+    }
+}
+
+/// Restores the history saved at `path`, if it belongs to this world.
+fn load_checkpoint(index: &mut Index, path: &Path) {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) => {
+            warn!(?e, ?path, "Couldn't read path for history checkpoint");
+            return;
+        },
+    };
+
+    let checkpoint: Checkpoint = match bincode::deserialize_from(BufReader::new(file)) {
+        Ok(checkpoint) => checkpoint,
+        Err(e) => {
+            warn!(?e, ?path, "Couldn't parse history checkpoint");
+            return;
+        },
     };
+    if !checkpoint.matches(index) {
+        warn!(
+            ?path,
+            "History checkpoint was saved from a different world, simulating history from scratch"
+        );
+        return;
+    }
+
+    info!(
+        ?path,
+        "Resuming history from year {}",
+        (checkpoint.time / YEAR) as i32
+    );
+    checkpoint.apply(index);
+}
+
+fn save_checkpoint(index: &Index, path: &Path) {
+    // Write to a temporary file first so that an interrupted save doesn't
+    // clobber the previous checkpoint
+    let tmp_path = path.with_extension("tmp");
+    let file = match File::create(&tmp_path) {
+        Ok(file) => file,
+        Err(e) => {
+            warn!(?e, ?tmp_path, "Couldn't create file for history checkpoint");
+            return;
+        },
+    };
+
+    if let Err(e) = bincode::serialize_into(BufWriter::new(file), &Checkpoint::new(index)) {
+        warn!(?e, "Couldn't write history checkpoint");
+    } else if let Err(e) = std::fs::rename(&tmp_path, path) {
+        warn!(?e, ?path, "Couldn't replace history checkpoint");
+    }
+}
+
+/// Serializes the state of the history simulation, so that a server that keeps
+/// it ticking can carry on from where it was after a restart.
+pub fn save_state(index: &Index) -> Result<Vec<u8>, bincode::Error> {
+    bincode::serialize(&Checkpoint::new(index))
+}
+
+/// Restores the state saved by `save_state`, if it belongs to this world.
+/// Returns whether it was restored.
+pub fn load_state(index: &mut Index, state: &[u8]) -> bool {
+    match bincode::deserialize::<Checkpoint>(state) {
+        Ok(checkpoint) if checkpoint.matches(index) => {
+            checkpoint.apply(index);
+            true
+        },
+        Ok(_) => {
+            warn!("Saved economy state was saved from a different world, ignoring it");
+            false
+        },
+        Err(e) => {
+            warn!(?e, "Couldn't parse saved economy state");
+            false
+        },
+    }
+}
 
-    for i in 0..(HISTORY_DAYS / TICK_PERIOD) as i32 {
+/// Applies changes to the stocks of sites made outside of the simulation, such
+/// as by players trading at town markets. Each change is given by the site id,
+/// the name of the good and how much was added to its stock, which is negative
+/// when goods were taken out. Stocks never go below nothing.
+pub fn change_stocks(index: &mut Index, changes: &[(u64, String, f32)]) {
+    for (site, name, change) in changes {
+        let good = match ALL_GOODS.iter().find(|good| format!("{:?}", good) == *name) {
+            Some(good) => *good,
+            None => {
+                warn!(?name, "Unknown good in a change to the stock of a site");
+                continue;
+            },
+        };
+        if let Some((_, site)) = index.sites.iter_mut().find(|(id, _)| id.id() == *site) {
+            site.economy.stocks[good] = (site.economy.stocks[good] + change).max(0.0);
+        }
+    }
+}
+
+pub fn simulate(
+    index: &mut Index,
+    opts: &HistoryOpts,
+    reporter: &mut dyn EconomyReporter,
+    mut timelapse: Option<&mut Timelapse>,
+) {
+    if let Some(path) = opts.checkpoint.as_ref().filter(|path| path.exists()) {
+        load_checkpoint(index, path);
+    }
+    if let Some(timelapse) = timelapse.as_mut() {
+        timelapse.tick(index);
+    }
+    let mut reporting = reporter.is_enabled();
+
+    let ticks = ((opts.years * YEAR - index.time) / TICK_PERIOD).max(0.0) as i32;
+    for i in 0..ticks {
         if (index.time / YEAR) as i32 % 50 == 0 && (index.time % YEAR) as i32 == 0 {
             debug!("Year {}", (index.time / YEAR) as i32);
         }
 
-        tick(index, world, TICK_PERIOD);
+        tick(index, TICK_PERIOD);
 
-        if let Some(f) = f.as_mut() {
-            if i % 5 == 0 {
-                let site = index.sites.values().next().unwrap();
-                write!(f, "{},", site.economy.pop).unwrap();
-                for g in Good::list() {
-                    write!(f, "{:?},", site.economy.values[*g].unwrap_or(-1.0)).unwrap();
-                }
-                for g in Good::list() {
-                    write!(f, "{:?},", site.economy.labor_values[*g].unwrap_or(-1.0)).unwrap();
-                }
-                for g in Good::list() {
-                    write!(f, "{:?},", site.economy.stocks[*g]).unwrap();
-                }
-                for g in Good::list() {
-                    write!(f, "{:?},", site.economy.marginal_surplus[*g]).unwrap();
-                }
-                for l in Labor::list() {
-                    write!(f, "{:?},", site.economy.labors[*l] * site.economy.pop).unwrap();
-                }
-                for l in Labor::list() {
-                    write!(f, "{:?},", site.economy.productivity[*l]).unwrap();
+        if let Some(path) = &opts.checkpoint {
+            if (index.time % CHECKPOINT_PERIOD) as i32 == 0 || i + 1 == ticks {
+                save_checkpoint(index, path);
+            }
+        }
+
+        if reporting && i % REPORT_INTERVAL == 0 {
+            let result = index
+                .sites
+                .values()
+                .try_for_each(|site| reporter.report(&SiteReport::new(index.time, site)));
+            if let Err(e) = result {
+                warn!(
+                    ?e,
+                    "Couldn't report on site economies, no more reports will be made"
+                );
+                reporting = false;
+            }
+        }
+
+        if let Some(timelapse) = timelapse.as_mut() {
+            timelapse.tick(index);
+        }
+    }
+
+    diplomacy::fortify_borders(index);
+}
+
+pub fn tick(index: &mut Index, dt: f32) {
+    events::tick_events(index);
+
+    // Each economy is ticked from its own state alone, so the sites are ticked in
+    // parallel. What passes between sites is gathered up and applied afterwards in
+    // the order of the sites by trade and migration, so the history turns out the
+    // same for a given seed however the work is split between threads.
+    let time = index.time;
+    let recipes = EconomyRecipes::load();
+    index
+        .sites
+        .par_values_mut()
+        .for_each(|site| tick_site_economy(&mut site.economy, &recipes, time, dt));
+    tick_trade(index);
+    diplomacy::tick_diplomacy(index);
+    tick_migration(index);
+
+    index.time += dt;
+    let time = index.time;
+    for site in index.sites.values_mut() {
+        site.economy.record(time);
+    }
+}
+
+/// Trade goods between neighbouring sites. Caravans that have arrived unload
+/// their goods, and then each site sends its surplus goods out to the
+/// neighbours that are short of them, so long as they are worth more there
+/// once the cost of getting them there is taken into account. The longer and
+/// rougher the road and the heavier the goods, the more of them are used up on
+/// the way, and perishable goods decay while the caravan travels. Sites on the
+/// coast ship their bulk goods to each other, which is slower but much
+/// cheaper, and ship everything to the sites that no road leads to. Sites of
+/// factions at war with each other don't trade at all.
+pub fn tick_trade(index: &mut Index) {
+    let time = index.time;
+
+    // Unload caravans that have arrived
+    let mut deliveries = Vec::new();
+    for site in index.sites.values_mut() {
+        site.economy.imports = MapVec::default();
+        site.economy.exports = MapVec::default();
+        let neighbors = &site.economy.neighbors;
+        site.economy.caravans.retain(|caravan| {
+            let arrived = caravan.arrival <= time;
+            if arrived {
+                if let Some(neighbor) = neighbors.iter().find(|n| n.id.id() == caravan.to) {
+                    deliveries.push((neighbor.id, caravan.goods.clone()));
                 }
-                for l in Labor::list() {
-                    write!(f, "{:?},", site.economy.yields[*l]).unwrap();
+            }
+            !arrived
+        });
+    }
+    for (site, goods) in deliveries {
+        let economy = &mut index.sites[site].economy;
+        for (good, amount) in goods.iter() {
+            economy.stocks[good] += *amount;
+            economy.imports[good] += *amount;
+        }
+    }
+
+    // Send out new caravans
+    let mut caravans = Vec::new();
+    for (id, site) in index.sites.iter() {
+        let economy = &site.economy;
+        for neighbor in economy.neighbors.iter() {
+            // Factions at war don't trade with each other
+            if index.stance(id, neighbor.id) == Stance::War {
+                continue;
+            }
+            let other = &index.sites[neighbor.id].economy;
+
+            // Bulk goods go by ship between sites on the coast, and everything else by
+            // road. Sites with only one way between them send everything along it.
+            let road = neighbor.cost.map(|cost| {
+                (
+                    false,
+                    neighbor.distance / CARAVAN_SPEED,
+                    cost * TRANSPORT_COST,
+                )
+            });
+            let sea = neighbor
+                .sea_distance
+                .map(|distance| (true, distance / SHIP_SPEED, distance * SHIPPING_COST));
+            let both = road.is_some() && sea.is_some();
+            for (by_sea, travel_time, cost_per_weight) in road.into_iter().chain(sea) {
+                let carries = |good: Good| !both || good.is_bulk() == by_sea;
+                // Share of the goods left when they arrive, the rest being used up on the way
+                let kept = |good: Good| (1.0 - cost_per_weight * good.weight()).max(0.0);
+
+                let sent = economy.surplus.clone().map(|good, surplus| {
+                    let available = surplus.min(economy.stocks[good]);
+                    let deficit = -other.surplus[good];
+                    let worth_it = match (economy.values[good], other.values[good]) {
+                        (Some(here), Some(there)) => there * kept(good) > here,
+                        // Goods without a value are either very scarce at the other site, or
+                        // very plentiful here
+                        _ => kept(good) > 0.0,
+                    };
+                    if carries(good) && available > 0.0 && deficit > 0.0 && worth_it {
+                        (available / economy.neighbors.len() as f32).min(deficit) * EXPORT_FRACTION
+                    } else {
+                        0.0
+                    }
+                });
+                if sent.iter().all(|(_, amount)| *amount <= 0.0) {
+                    continue;
                 }
-                writeln!(f).unwrap();
+
+                // The neighbour pays for the goods at this site's prices, and only orders as
+                // much as it can afford
+                let cost = sent
+                    .iter()
+                    .map(|(good, amount)| amount * economy.prices[good])
+                    .sum::<f32>();
+                let affordable = if cost > 0.0 {
+                    (other.stocks[Good::Coin] / cost).min(1.0)
+                } else {
+                    1.0
+                };
+                let sent = sent.map(|_, amount| amount * affordable);
+                let paid = cost * affordable;
+
+                // Perishable goods spoil on the way, more so on long journeys
+                let goods = sent.clone().map(|good, amount| {
+                    let decay_rate = good.decay_rate(Season::warmth(time + travel_time / 2.0));
+                    amount * kept(good) * (1.0 - decay_rate).powf(travel_time / TICK_PERIOD)
+                });
+                caravans.push((id, neighbor.id, sent, paid, Caravan {
+                    to: neighbor.id.id(),
+                    by_sea,
+                    goods,
+                    departure: time,
+                    arrival: time + travel_time,
+                }));
             }
         }
     }
+    for (site, buyer, sent, paid, caravan) in caravans {
+        let buyer_coins = &mut index.sites[buyer].economy.stocks[Good::Coin];
+        let paid = paid.min(*buyer_coins);
+        *buyer_coins -= paid;
+
+        let economy = &mut index.sites[site].economy;
+        for (good, amount) in sent.iter() {
+            economy.stocks[good] = (economy.stocks[good] - *amount).max(0.0);
+            economy.exports[good] += *amount;
+        }
+        economy.stocks[Good::Coin] += paid;
+        *economy.route_trade.entry(caravan.to).or_default() += paid;
+        economy.caravans.push(caravan);
+    }
 }
 
-pub fn tick(index: &mut Index, _world: &mut WorldSim, dt: f32) {
-    for site in index.sites.ids() {
-        tick_site_economy(index, site, dt);
+/// Move people out of struggling sites. When a site has been short of food for
+/// a while, or too many of its workers are idle, some of its people move to
+/// the most prosperous of its neighbours, as long as that neighbour pays
+/// better and isn't struggling itself. Over the years, this empties some
+/// sites and makes others boom.
+pub fn tick_migration(index: &mut Index) {
+    let struggling = |economy: &Economy| {
+        economy.hungry_ticks >= FAMINE_TICKS || economy.unemployment > MAX_UNEMPLOYMENT
+    };
+
+    let mut moves = Vec::new();
+    for (id, site) in index.sites.iter() {
+        let economy = &site.economy;
+        if !struggling(economy) {
+            continue;
+        }
+        let destination = economy
+            .neighbors
+            .iter()
+            .map(|neighbor| (neighbor.id, &index.sites[neighbor.id].economy))
+            .filter(|(_, other)| !struggling(other) && other.wages > economy.wages)
+            .max_by(|(_, a), (_, b)| {
+                a.wages
+                    .partial_cmp(&b.wages)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .map(|(id, _)| id);
+        if let Some(destination) = destination {
+            moves.push((id, destination, economy.workforce() * MIGRATION_RATE));
+        }
     }
 
-    index.time += dt;
+    for site in index.sites.values_mut() {
+        site.economy.migration = 0.0;
+    }
+    for (from, to, migrants) in moves {
+        let (migrants, coins) = index.sites[from].economy.emigrate(migrants);
+        index.sites[to].economy.immigrate(migrants, coins);
+    }
 }
 
 /// Simulate a site's economy. This simulation is roughly equivalent to the
@@ -112,41 +513,48 @@ pub fn tick(index: &mut Index, _world: &mut WorldSim, dt: f32) {
 /// dynamically react to environmental changes. If a product becomes available
 /// through a mechanism such as trade, an entire arm of the economy may
 /// materialise to take advantage of this.
-pub fn tick_site_economy(index: &mut Index, site: Id<Site>, dt: f32) {
-    let site = &mut index.sites[site];
+///
+/// Only the economy of the site itself is read and changed, so that the
+/// economies of all sites can be ticked at once. What the site uses up and
+/// makes is set by `recipes`.
+pub fn tick_site_economy(economy: &mut Economy, recipes: &EconomyRecipes, time: f32, dt: f32) {
+    // How warm the time of year is over the tick, which sets how well crops grow
+    // and game can be found, and how quickly food spoils
+    let warmth = Season::warmth(time + dt / 2.0);
 
-    let orders = site.economy.get_orders();
-    let productivity = site.economy.get_productivity();
+    let orders = economy.get_orders(recipes);
+    let productivity = economy
+        .get_productivity(recipes)
+        .map(|labor, (good, rate)| (good, rate * labor.seasonal_productivity(warmth)));
 
     let mut demand = MapVec::from_default(0.0);
     for (labor, orders) in &orders {
         let scale = if let Some(labor) = labor {
-            site.economy.labors[*labor]
+            economy.labors[*labor] * economy.workforce()
         } else {
-            1.0
-        } * site.economy.pop;
+            economy.pop
+        };
         for (good, amount) in orders {
             demand[*good] += *amount * scale;
         }
     }
 
-    let mut supply = site.economy.stocks.clone(); //MapVec::from_default(0.0);
+    let mut supply = economy.stocks.clone(); //MapVec::from_default(0.0);
     for (labor, (output_good, _)) in productivity.iter() {
-        supply[*output_good] +=
-            site.economy.yields[labor] * site.economy.labors[labor] * site.economy.pop;
+        supply[*output_good] += economy.yields[labor] * economy.labors[labor] * economy.workforce();
     }
 
-    let stocks = &site.economy.stocks;
-    site.economy.surplus = demand
+    let stocks = &economy.stocks;
+    economy.surplus = demand
         .clone()
         .map(|g, demand| supply[g] + stocks[g] - demand);
-    site.economy.marginal_surplus = demand.clone().map(|g, demand| supply[g] - demand);
+    economy.marginal_surplus = demand.clone().map(|g, demand| supply[g] - demand);
 
     // Update values according to the surplus of each stock
     // Note that values are used for workforce allocation and are not the same thing
     // as price
-    let values = &mut site.economy.values;
-    site.economy.surplus.iter().for_each(|(good, surplus)| {
+    let values = &mut economy.values;
+    economy.surplus.iter().for_each(|(good, surplus)| {
         // Value rationalisation
         let val = 2.0f32.powf(1.0 - *surplus / demand[good]);
         let smooth = 0.8;
@@ -156,6 +564,7 @@ pub fn tick_site_economy(index: &mut Index, site: Id<Site>, dt: f32) {
             None
         };
     });
+    economy.update_prices(&demand);
 
     // Update export targets based on relative values
     // let value_avg = values
@@ -164,42 +573,44 @@ pub fn tick_site_economy(index: &mut Index, site: Id<Site>, dt: f32) {
     //     .sum::<f32>()
     //     .max(0.01)
     //     / values.iter().filter(|(_, v)| v.is_some()).count() as f32;
-    //let export_targets = &mut site.economy.export_targets;
+    //let export_targets = &mut economy.export_targets;
     //let last_exports = &self.last_exports;
-    // site.economy.values.iter().for_each(|(stock, value)| {
+    // economy.values.iter().for_each(|(stock, value)| {
     //     let rvalue = (*value).map(|v| v - value_avg).unwrap_or(0.0);
     //     //let factor = if export_targets[stock] > 0.0 { 1.0 / rvalue } else {
     // rvalue };     //export_targets[stock] = last_exports[stock] - rvalue *
     // 0.1; // + (trade_states[stock].sell_belief.price -
     // trade_states[stock].buy_belief.price) * 0.025; });
 
-    //let pop = site.economy.pop;
+    //let pop = economy.pop;
 
     // Redistribute workforce according to relative good values
     let labor_ratios = productivity.clone().map(|labor, (output_good, _)| {
-        site.economy.values[output_good].unwrap_or(0.0)
-            * site.economy.productivity[labor]
-        //(site.economy.prices[output_good] - site.economy.material_costs[output_good]) * site.economy.yields[labor]
+        economy.values[output_good].unwrap_or(0.0)
+            * economy.productivity[labor]
+        //(economy.prices[output_good] - economy.material_costs[output_good]) * economy.yields[labor]
         //* demand[output_good] / supply[output_good].max(0.001)
     });
     let labor_ratio_sum = labor_ratios.iter().map(|(_, r)| *r).sum::<f32>().max(0.01);
+    let labors_before = economy.labors.clone();
     productivity.iter().for_each(|(labor, _)| {
         let smooth = 0.8;
-        site.economy.labors[labor] = smooth * site.economy.labors[labor]
+        economy.labors[labor] = smooth * economy.labors[labor]
             + (1.0 - smooth)
                 * (labor_ratios[labor].max(labor_ratio_sum / 1000.0) / labor_ratio_sum);
     });
+    economy.reassign(&labors_before);
 
     // Production
-    let stocks_before = site.economy.stocks.clone();
+    let stocks_before = economy.stocks.clone();
     let mut total_labor_values = MapVec::<_, f32>::default();
     let mut total_outputs = MapVec::<_, f32>::default();
     for (labor, orders) in orders.iter() {
         let scale = if let Some(labor) = labor {
-            site.economy.labors[*labor]
+            economy.labors[*labor] * economy.workforce()
         } else {
-            1.0
-        } * site.economy.pop;
+            economy.pop
+        };
 
         // For each order, we try to find the minimum satisfaction rate - this limits
         // how much we can produce! For example, if we need 0.25 fish and
@@ -226,26 +637,26 @@ pub fn tick_site_economy(index: &mut Index, site: Id<Site>, dt: f32) {
             let used = quantity * labor_productivity;
 
             // Material cost of each factor of production
-            total_materials_cost += used * site.economy.labor_values[*good].unwrap_or(0.0);
+            total_materials_cost += used * economy.labor_values[*good].unwrap_or(0.0);
 
             // Deplete stocks accordingly
-            site.economy.stocks[*good] = (site.economy.stocks[*good] - used).max(0.0);
+            economy.stocks[*good] = (economy.stocks[*good] - used).max(0.0);
         }
 
         // Industries produce things
         if let Some(labor) = labor {
             let (stock, rate) = productivity[*labor];
-            let workers = site.economy.labors[*labor] * site.economy.pop;
+            let workers = economy.labors[*labor] * economy.workforce();
             let final_rate = rate;
             let yield_per_worker =
                 labor_productivity * final_rate * (1.0 + workers / 100.0).min(3.0);
-            site.economy.yields[*labor] = yield_per_worker;
-            site.economy.productivity[*labor] = labor_productivity;
+            economy.yields[*labor] = yield_per_worker;
+            economy.productivity[*labor] = labor_productivity;
             let total_output = yield_per_worker * workers;
-            site.economy.stocks[stock] += total_output;
+            economy.stocks[stock] += total_output;
 
             // Materials cost per unit
-            site.economy.material_costs[stock] = total_materials_cost / total_output.max(0.001);
+            economy.material_costs[stock] = total_materials_cost / total_output.max(0.001);
             // Labor costs
             let wages = 1.0;
             let total_labor_cost = workers * wages;
@@ -255,8 +666,27 @@ pub fn tick_site_economy(index: &mut Index, site: Id<Site>, dt: f32) {
         }
     }
 
+    // Workers are idle when they lack the materials to work with
+    let idle = Labor::list()
+        .iter()
+        .map(|labor| economy.labors[*labor] * (1.0 - economy.productivity[*labor]))
+        .sum::<f32>();
+    let smooth = 0.8;
+    economy.unemployment = smooth * economy.unemployment + (1.0 - smooth) * idle;
+
+    // Workers are paid what their output is worth at the site's prices
+    let workers = economy.labors.iter().map(|(_, l)| *l).sum::<f32>() * economy.workforce();
+    let output_value = total_outputs
+        .iter()
+        .map(|(stock, output)| output * economy.prices[stock])
+        .sum::<f32>();
+    if workers > 0.0 {
+        let smooth = 0.8;
+        economy.wages = smooth * economy.wages + (1.0 - smooth) * output_value / workers;
+    }
+
     // Update labour values per unit
-    site.economy.labor_values = total_labor_values.map(|stock, tlv| {
+    economy.labor_values = total_labor_values.map(|stock, tlv| {
         let total_output = total_outputs[stock];
         if total_output > 0.01 {
             Some(tlv / total_outputs[stock])
@@ -266,21 +696,53 @@ pub fn tick_site_economy(index: &mut Index, site: Id<Site>, dt: f32) {
     });
 
     // Decay stocks
-    site.economy
+    economy
         .stocks
         .iter_mut()
-        .for_each(|(c, v)| *v *= 1.0 - c.decay_rate());
+        .for_each(|(c, v)| *v *= 1.0 - c.decay_rate(warmth));
 
     // Decay stocks
-    site.economy.replenish(index.time);
+    economy.replenish(time, recipes);
+
+    // Births, ageing and deaths
+    let fed = economy.surplus[Good::Food] > 0.0;
+    if fed {
+        economy.hungry_ticks = 0;
+    } else {
+        economy.hungry_ticks += 1;
+    }
+    economy.age(dt / YEAR, fed);
 
-    // Births/deaths
-    const NATURAL_BIRTH_RATE: f32 = 0.05;
-    const DEATH_RATE: f32 = 0.005;
-    let birth_rate = if site.economy.surplus[Good::Food] > 0.0 {
-        NATURAL_BIRTH_RATE
+    // People turn to crime when essential goods run short or get much dearer than
+    // usual, and slowly go back to honest work once times get better
+    let hardship = ESSENTIAL_GOODS
+        .iter()
+        .map(|good| {
+            let shortage = (-economy.surplus[*good] / demand[*good].max(0.001)).max(0.0);
+            let (total, samples) = economy
+                .history
+                .prices(*good)
+                .fold((0.0, 0), |(total, samples), (_, price)| {
+                    (total + price, samples + 1)
+                });
+            let price_spike = if total > 0.0 {
+                (economy.prices[*good] * samples as f32 / total - 1.0).max(0.0)
+            } else {
+                0.0
+            };
+            (shortage + price_spike).min(1.0)
+        })
+        .fold(0.0, f32::max);
+    let target_crime = hardship * MAX_CRIME;
+    let crime_rate = if target_crime > economy.crime {
+        CRIME_GROWTH
     } else {
-        0.0
+        CRIME_FADE
     };
-    site.economy.pop += dt / YEAR * site.economy.pop * (birth_rate - DEATH_RATE);
+    economy.crime += (target_crime - economy.crime) * crime_rate;
+
+    // The money supply grows along with the population
+    economy.mint(dt / YEAR);
 }
+
+#[cfg(test)] mod tests;
@@ -0,0 +1,318 @@
+//! Reports on the economies of sites as the history of the world is simulated.
+
+use crate::site::{
+    economy::{Good, Labor},
+    Site,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::PathBuf,
+};
+use tracing::warn;
+use vek::*;
+
+/// Where reports on the economies of sites go while history is simulated.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum EconomyReport {
+    None,
+    /// A CSV file with a row for each site at each report
+    Csv(PathBuf),
+    /// A file with a JSON object for each site at each report, one per line
+    JsonLines(PathBuf),
+}
+
+impl Default for EconomyReport {
+    fn default() -> Self { EconomyReport::None }
+}
+
+impl EconomyReport {
+    /// Creates the reporter writing to the chosen file. Reports are skipped if
+    /// the file can't be created.
+    pub fn reporter(&self) -> Box<dyn EconomyReporter> {
+        let create = |path: &PathBuf| match File::create(path) {
+            Ok(file) => Some(BufWriter::new(file)),
+            Err(e) => {
+                warn!(?e, ?path, "Couldn't create file for economy reports");
+                None
+            },
+        };
+        match self {
+            EconomyReport::None => Box::new(NoReport),
+            EconomyReport::Csv(path) => match create(path) {
+                Some(file) => Box::new(CsvReport::new(file)),
+                None => Box::new(NoReport),
+            },
+            EconomyReport::JsonLines(path) => match create(path) {
+                Some(file) => Box::new(JsonLinesReport::new(file)),
+                None => Box::new(NoReport),
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct GoodReport {
+    pub good: Good,
+    pub value: Option<f32>,
+    pub labor_value: Option<f32>,
+    pub price: f32,
+    pub stock: f32,
+    pub surplus: f32,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct LaborReport {
+    pub labor: Labor,
+    pub workers: f32,
+    pub productivity: f32,
+    pub yields: f32,
+    /// Years that the workers have worked at the labor on average
+    pub experience: f32,
+}
+
+/// The state of a site's economy at some point in history
+#[derive(Clone, Debug, Serialize)]
+pub struct SiteReport {
+    pub time: f32,
+    pub origin: Vec2<i32>,
+    pub pop: f32,
+    /// People of working age
+    pub workforce: f32,
+    pub wages: f32,
+    pub unemployment: f32,
+    /// People who moved in since the last tick, less those who moved out
+    pub migration: f32,
+    pub goods: Vec<GoodReport>,
+    pub labors: Vec<LaborReport>,
+}
+
+impl SiteReport {
+    pub fn new(time: f32, site: &Site) -> Self {
+        let economy = &site.economy;
+        Self {
+            time,
+            origin: site.get_origin(),
+            pop: economy.pop,
+            workforce: economy.workforce(),
+            wages: economy.wages,
+            unemployment: economy.unemployment,
+            migration: economy.migration,
+            goods: Good::list()
+                .iter()
+                .map(|good| GoodReport {
+                    good: *good,
+                    value: economy.values[*good],
+                    labor_value: economy.labor_values[*good],
+                    price: economy.prices[*good],
+                    stock: economy.stocks[*good],
+                    surplus: economy.marginal_surplus[*good],
+                })
+                .collect(),
+            labors: Labor::list()
+                .iter()
+                .map(|labor| LaborReport {
+                    labor: *labor,
+                    workers: economy.labors[*labor] * economy.workforce(),
+                    productivity: economy.productivity[*labor],
+                    yields: economy.yields[*labor],
+                    experience: economy.experience[*labor],
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Receives the state of each site's economy every so often while history is
+/// simulated.
+pub trait EconomyReporter {
+    fn report(&mut self, report: &SiteReport) -> io::Result<()>;
+
+    /// Whether there is any point in putting reports together
+    fn is_enabled(&self) -> bool { true }
+}
+
+/// Throws reports away.
+pub struct NoReport;
+
+impl EconomyReporter for NoReport {
+    fn report(&mut self, _report: &SiteReport) -> io::Result<()> { Ok(()) }
+
+    fn is_enabled(&self) -> bool { false }
+}
+
+/// Writes a row for each report, with a column for each statistic of each good
+/// and labor.
+pub struct CsvReport<W: Write> {
+    out: W,
+    header_written: bool,
+}
+
+impl<W: Write> CsvReport<W> {
+    pub fn new(out: W) -> Self {
+        Self {
+            out,
+            header_written: false,
+        }
+    }
+
+    fn write_header(&mut self) -> io::Result<()> {
+        write!(
+            self.out,
+            "Time,Site X,Site Y,Population,Workforce,Wages,Unemployment,Migration,"
+        )?;
+        for stat in &["Value", "LaborVal", "Price", "Stock", "Surplus"] {
+            for g in Good::list() {
+                write!(self.out, "{:?} {},", g, stat)?;
+            }
+        }
+        for stat in &["Labor", "Productivity", "Yields", "Experience"] {
+            for l in Labor::list() {
+                write!(self.out, "{:?} {},", l, stat)?;
+            }
+        }
+        writeln!(self.out)
+    }
+}
+
+impl<W: Write> EconomyReporter for CsvReport<W> {
+    fn report(&mut self, report: &SiteReport) -> io::Result<()> {
+        if !self.header_written {
+            self.write_header()?;
+            self.header_written = true;
+        }
+
+        write!(
+            self.out,
+            "{},{},{},{},{},{},{},{},",
+            report.time,
+            report.origin.x,
+            report.origin.y,
+            report.pop,
+            report.workforce,
+            report.wages,
+            report.unemployment,
+            report.migration
+        )?;
+        for g in &report.goods {
+            write!(self.out, "{:?},", g.value.unwrap_or(-1.0))?;
+        }
+        for g in &report.goods {
+            write!(self.out, "{:?},", g.labor_value.unwrap_or(-1.0))?;
+        }
+        for g in &report.goods {
+            write!(self.out, "{:?},", g.price)?;
+        }
+        for g in &report.goods {
+            write!(self.out, "{:?},", g.stock)?;
+        }
+        for g in &report.goods {
+            write!(self.out, "{:?},", g.surplus)?;
+        }
+        for l in &report.labors {
+            write!(self.out, "{:?},", l.workers)?;
+        }
+        for l in &report.labors {
+            write!(self.out, "{:?},", l.productivity)?;
+        }
+        for l in &report.labors {
+            write!(self.out, "{:?},", l.yields)?;
+        }
+        for l in &report.labors {
+            write!(self.out, "{:?},", l.experience)?;
+        }
+        writeln!(self.out)
+    }
+}
+
+/// Writes each report as a JSON object on its own line.
+pub struct JsonLinesReport<W: Write> {
+    out: W,
+}
+
+impl<W: Write> JsonLinesReport<W> {
+    pub fn new(out: W) -> Self { Self { out } }
+}
+
+impl<W: Write> EconomyReporter for JsonLinesReport<W> {
+    fn report(&mut self, report: &SiteReport) -> io::Result<()> {
+        serde_json::to_writer(&mut self.out, report)?;
+        writeln!(self.out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn site_report() -> SiteReport {
+        SiteReport {
+            time: 90.0,
+            origin: Vec2::new(1, 2),
+            pop: 100.0,
+            workforce: 60.0,
+            wages: 1.5,
+            unemployment: 0.1,
+            migration: -2.0,
+            goods: Good::list()
+                .iter()
+                .map(|good| GoodReport {
+                    good: *good,
+                    value: Some(1.0),
+                    labor_value: None,
+                    price: 2.0,
+                    stock: 3.0,
+                    surplus: 0.0,
+                })
+                .collect(),
+            labors: Labor::list()
+                .iter()
+                .map(|labor| LaborReport {
+                    labor: *labor,
+                    workers: 4.0,
+                    productivity: 1.0,
+                    yields: 1.0,
+                    experience: 0.5,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn csv_rows_match_the_header() {
+        let mut out = Vec::new();
+        let mut csv = CsvReport::new(&mut out);
+        csv.report(&site_report()).unwrap();
+        csv.report(&site_report()).unwrap();
+        drop(csv);
+
+        let text = String::from_utf8(out).unwrap();
+        let lines = text.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 3);
+        let columns = lines[0].split(',').count();
+        assert!(lines.iter().all(|line| line.split(',').count() == columns));
+        assert!(lines[1].starts_with("90,1,2,100,60,1.5,0.1,-2,1.0,"));
+    }
+
+    #[test]
+    fn json_lines_parse_back() {
+        let mut out = Vec::new();
+        let mut json = JsonLinesReport::new(&mut out);
+        json.report(&site_report()).unwrap();
+        json.report(&site_report()).unwrap();
+        drop(json);
+
+        let text = String::from_utf8(out).unwrap();
+        assert_eq!(text.lines().count(), 2);
+        for line in text.lines() {
+            let report: serde_json::Value = serde_json::from_str(line).unwrap();
+            assert_eq!(report["pop"], 100.0);
+            assert_eq!(
+                report["goods"].as_array().map(Vec::len),
+                Some(Good::list().len())
+            );
+            assert_eq!(report["goods"][0]["labor_value"], serde_json::Value::Null);
+        }
+    }
+}
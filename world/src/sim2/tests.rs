@@ -0,0 +1,409 @@
+//! Checks that the history simulation keeps the economies of sites in a sane
+//! state, and that it turns out the same as it did when the snapshot of it was
+//! last taken. The simulation runs on small made up indices, so no world map
+//! has to be generated.
+//!
+//! Changes to the economy that are meant to change the history should update
+//! the snapshot by running the tests with `VELOREN_BLESS_HISTORY=1` set.
+
+use super::{
+    change_stocks, load_state, report::EconomyReport, save_state, simulate, tick_migration,
+    tick_trade, HistoryOpts, FAMINE_TICKS, MAX_CRIME, YEAR,
+};
+use crate::{
+    site::{
+        economy::{Good, Labor, NeighborInformation},
+        faction::Relation,
+        Faction, Settlement, Site,
+    },
+    Index,
+};
+use rand::prelude::*;
+use rand_chacha::ChaChaRng;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use vek::*;
+
+const SEED: u32 = 1234;
+/// How many sites there are in the made up indices
+const SITES: usize = 6;
+/// Years of history simulated by the tests
+const YEARS: u32 = 100;
+/// Relative difference between a value and its snapshot that is allowed for,
+/// since floating point maths isn't quite the same on every computer
+const TOLERANCE: f32 = 1e-3;
+
+const SNAPSHOT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/sim2/history_snapshot.ron");
+
+/// Makes an index with a ring of settlements, each trading with the ones next
+/// to it, some of them by sea and the first two only by sea, split between two
+/// factions
+fn synthetic_index(seed: u32) -> Index {
+    let (mut index, _) = Index::new(seed);
+    let mut rng = ChaChaRng::seed_from_u64(seed as u64);
+
+    let sites = (0..SITES)
+        .map(|i| {
+            let wpos = Vec2::new(i as i32 * 2000, 0);
+            let settlement = Settlement::generate(wpos, None, &mut rng);
+            index.sites.insert(Site::settlement(settlement))
+        })
+        .collect::<Vec<_>>();
+    for (i, site) in sites.iter().copied().enumerate() {
+        let next = sites[(i + 1) % SITES];
+        let distance = rng.gen_range(20.0, 80.0);
+        let cost = distance * rng.gen_range(1.0, 4.0);
+        // Every other pair of sites is on the coast, and the first pair has no road
+        let sea_distance = if i % 2 == 0 { Some(distance) } else { None };
+        let cost = if i == 0 { None } else { Some(cost) };
+        for (from, to) in [(site, next), (next, site)].iter() {
+            index.sites[*from]
+                .economy
+                .neighbors
+                .push(NeighborInformation {
+                    id: *to,
+                    distance,
+                    cost,
+                    sea_distance,
+                });
+        }
+    }
+
+    for sites in sites.chunks(SITES / 2) {
+        let faction = index.factions.insert(Faction::new(sites[0]));
+        for site in sites {
+            index.sites[*site].faction = Some(faction);
+        }
+    }
+    for faction in index.factions.values_mut() {
+        faction.relations = vec![Relation::default(); 2];
+    }
+
+    index
+}
+
+/// Simulates the history of the index up to the given year
+fn simulate_until(index: &mut Index, years: u32) {
+    let opts = HistoryOpts {
+        years: years as f32,
+        checkpoint: None,
+        report: EconomyReport::None,
+        timelapse: None,
+    };
+    simulate(index, &opts, &mut *opts.report.reporter(), None);
+}
+
+/// Panics if the economy of any of the sites is in a state that the
+/// simulation should never get it into
+fn check_invariants(index: &Index) {
+    let year = (index.time / YEAR) as u32;
+    for (id, site) in index.sites.iter() {
+        let economy = &site.economy;
+        assert!(
+            economy.pop.is_finite() && economy.pop > 0.0,
+            "{:?} has a population of {} in year {}",
+            id,
+            economy.pop,
+            year
+        );
+        let cohorts = &economy.cohorts;
+        assert!(
+            cohorts.children >= 0.0
+                && cohorts.adults >= 0.0
+                && cohorts.elders >= 0.0
+                && (cohorts.total() - economy.pop).abs() <= TOLERANCE * economy.pop,
+            "{:?} has cohorts of {:?} for a population of {} in year {}",
+            id,
+            cohorts,
+            economy.pop,
+            year
+        );
+        for labor in Labor::list().iter().copied() {
+            let experience = economy.experience[labor];
+            assert!(
+                experience.is_finite() && experience >= 0.0,
+                "{:?} has {} years of experience as {:?} in year {}",
+                id,
+                experience,
+                labor,
+                year
+            );
+        }
+        assert!(
+            economy.crime >= 0.0 && economy.crime <= MAX_CRIME,
+            "{:?} has a crime of {} in year {}",
+            id,
+            economy.crime,
+            year
+        );
+        for good in Good::list().iter().copied() {
+            let price = economy.prices[good];
+            assert!(
+                price.is_finite() && price >= 0.0,
+                "{:?} has a price of {} for {:?} in year {}",
+                id,
+                price,
+                good,
+                year
+            );
+            let stock = economy.stocks[good];
+            assert!(
+                stock.is_finite() && stock >= 0.0,
+                "{:?} has a stock of {} of {:?} in year {}",
+                id,
+                stock,
+                good,
+                year
+            );
+            // Values are only kept between these bounds, and smoothing them over time
+            // mustn't take them outside of them
+            if let Some(value) = economy.values[good] {
+                assert!(
+                    value > 0.001 && value < 1000.0,
+                    "{:?} has a value of {} for {:?} in year {}",
+                    id,
+                    value,
+                    good,
+                    year
+                );
+            }
+        }
+    }
+}
+
+/// The state of the economy of a site at the end of the history, in the order
+/// of `Good::list`
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct SiteSnapshot {
+    pop: f32,
+    prices: Vec<f32>,
+    stocks: Vec<f32>,
+}
+
+impl SiteSnapshot {
+    fn new(site: &Site) -> Self {
+        let economy = &site.economy;
+        Self {
+            pop: economy.pop,
+            prices: Good::list()
+                .iter()
+                .map(|good| economy.prices[*good])
+                .collect(),
+            stocks: Good::list()
+                .iter()
+                .map(|good| economy.stocks[*good])
+                .collect(),
+        }
+    }
+
+    fn is_close_to(&self, other: &Self) -> bool {
+        let close = |a: &f32, b: &f32| (a - b).abs() <= TOLERANCE * a.abs().max(b.abs()).max(1.0);
+        close(&self.pop, &other.pop)
+            && self.prices.len() == other.prices.len()
+            && self
+                .prices
+                .iter()
+                .zip(other.prices.iter())
+                .all(|(a, b)| close(a, b))
+            && self.stocks.len() == other.stocks.len()
+            && self
+                .stocks
+                .iter()
+                .zip(other.stocks.iter())
+                .all(|(a, b)| close(a, b))
+    }
+}
+
+fn snapshot(index: &Index) -> Vec<SiteSnapshot> {
+    index.sites.values().map(SiteSnapshot::new).collect()
+}
+
+#[test]
+fn invariants_hold_over_history() {
+    let mut index = synthetic_index(SEED);
+    check_invariants(&index);
+    for year in 1..=YEARS {
+        simulate_until(&mut index, year);
+        check_invariants(&index);
+    }
+}
+
+#[test]
+fn history_is_deterministic() {
+    let mut a = synthetic_index(SEED);
+    let mut b = synthetic_index(SEED);
+    simulate_until(&mut a, YEARS);
+    // Simulating in steps has to end up in the same place as doing it at once
+    simulate_until(&mut b, YEARS / 2);
+    simulate_until(&mut b, YEARS);
+    assert_eq!(snapshot(&a), snapshot(&b));
+}
+
+#[test]
+fn saved_state_loads_back() {
+    let mut a = synthetic_index(SEED);
+    simulate_until(&mut a, YEARS / 2);
+    let state = save_state(&a).unwrap();
+
+    let mut b = synthetic_index(SEED);
+    assert!(load_state(&mut b, &state));
+    assert_eq!(b.time, a.time);
+    assert_eq!(snapshot(&b), snapshot(&a));
+
+    // State saved from another world, or that isn't state at all, is left alone
+    let mut other = synthetic_index(SEED + 1);
+    let before = snapshot(&other);
+    assert!(!load_state(&mut other, &state));
+    assert!(!load_state(&mut other, b"not a saved economy"));
+    assert_eq!(snapshot(&other), before);
+}
+
+#[test]
+fn events_are_the_same_when_resumed_from_a_checkpoint() {
+    let mut a = synthetic_index(SEED);
+    simulate_until(&mut a, YEARS);
+
+    let mut b = synthetic_index(SEED);
+    simulate_until(&mut b, YEARS / 2);
+    let state = save_state(&b).unwrap();
+    let mut b = synthetic_index(SEED);
+    assert!(load_state(&mut b, &state));
+    simulate_until(&mut b, YEARS);
+
+    let events = |index: &Index| {
+        index
+            .sites
+            .values()
+            .map(|site| site.economy.lore().collect::<Vec<_>>())
+            .collect::<Vec<_>>()
+    };
+    assert!(events(&a).iter().any(|lore| !lore.is_empty()));
+    assert_eq!(events(&a), events(&b));
+}
+
+#[test]
+fn history_matches_snapshot() {
+    let mut index = synthetic_index(SEED);
+    simulate_until(&mut index, YEARS);
+    let history = snapshot(&index);
+
+    let path = Path::new(SNAPSHOT);
+    if std::env::var("VELOREN_BLESS_HISTORY").is_ok() {
+        let ron = ron::ser::to_string_pretty(&history, ron::ser::PrettyConfig::default())
+            .expect("Failed to serialize the history snapshot");
+        std::fs::write(path, ron).expect("Failed to write the history snapshot");
+        return;
+    }
+
+    let expected: Vec<SiteSnapshot> = ron::de::from_str(&std::fs::read_to_string(path).expect(
+        "Failed to read the history snapshot, run the tests with VELOREN_BLESS_HISTORY=1 set to \
+         create it",
+    ))
+    .expect("Failed to parse the history snapshot");
+    assert_eq!(history.len(), expected.len(), "The number of sites changed");
+    for (i, (site, expected)) in history.iter().zip(expected.iter()).enumerate() {
+        assert!(
+            site.is_close_to(expected),
+            "The history of site {} changed from the snapshot:\n{:?}\nto:\n{:?}\nIf this is meant \
+             to happen, run the tests with VELOREN_BLESS_HISTORY=1 set to update the snapshot",
+            i,
+            expected,
+            site
+        );
+    }
+}
+
+#[test]
+fn stock_changes_apply_to_sites() {
+    let mut index = synthetic_index(SEED);
+    let (id, coins) = index
+        .sites
+        .iter()
+        .map(|(id, site)| (id, site.economy.stocks[Good::Coin]))
+        .next()
+        .unwrap();
+    change_stocks(&mut index, &[
+        (id.id(), "Coin".to_string(), 10.0),
+        (id.id(), "Wood".to_string(), -1.0e9),
+        (id.id(), "Unobtainium".to_string(), 1.0),
+    ]);
+    assert_eq!(index.sites[id].economy.stocks[Good::Coin], coins + 10.0);
+    assert_eq!(index.sites[id].economy.stocks[Good::Wood], 0.0);
+}
+
+#[test]
+fn people_move_to_the_best_paying_neighbour_that_isnt_struggling() {
+    let mut index = synthetic_index(SEED);
+    for site in index.sites.values_mut() {
+        site.economy.hungry_ticks = 0;
+        site.economy.unemployment = 0.0;
+        site.economy.wages = 1.0;
+    }
+    let (from, neighbors) = index
+        .sites
+        .iter()
+        .map(|(id, site)| (id, site.economy.neighbors.clone()))
+        .next()
+        .unwrap();
+    let (better, best) = (neighbors[0].id, neighbors[1].id);
+    index.sites[from].economy.hungry_ticks = FAMINE_TICKS;
+    index.sites[better].economy.wages = 2.0;
+    // The best paying neighbour is going hungry as well, so nobody moves there
+    index.sites[best].economy.wages = 3.0;
+    index.sites[best].economy.hungry_ticks = FAMINE_TICKS;
+
+    let workforce = |index: &Index| {
+        index
+            .sites
+            .values()
+            .map(|site| site.economy.workforce())
+            .sum::<f32>()
+    };
+    let before = workforce(&index);
+    tick_migration(&mut index);
+
+    assert!(index.sites[from].economy.migration < 0.0);
+    assert_eq!(
+        index.sites[better].economy.migration,
+        -index.sites[from].economy.migration
+    );
+    assert_eq!(index.sites[best].economy.migration, 0.0);
+    assert!((workforce(&index) - before).abs() < 1e-3 * before);
+}
+
+#[test]
+fn goods_are_shipped_to_sites_no_road_leads_to() {
+    let mut index = synthetic_index(SEED);
+    for site in index.sites.values_mut() {
+        site.economy.surplus = Default::default();
+        site.economy.stocks[Good::Coin] = 1000.0;
+    }
+    let (from, to) = index
+        .sites
+        .iter()
+        .find_map(|(id, site)| {
+            let neighbor = site.economy.neighbors.iter().find(|n| n.cost.is_none())?;
+            Some((id, neighbor.id))
+        })
+        .unwrap();
+    // Tools aren't bulk goods, but there's no road to take them along
+    let economy = &mut index.sites[from].economy;
+    economy.surplus[Good::Tools] = 10.0;
+    economy.stocks[Good::Tools] = 10.0;
+    economy.values[Good::Tools] = Some(1.0);
+    economy.prices[Good::Tools] = 1.0;
+    let economy = &mut index.sites[to].economy;
+    economy.surplus[Good::Tools] = -10.0;
+    economy.values[Good::Tools] = Some(10.0);
+
+    tick_trade(&mut index);
+
+    let caravan = index.sites[from]
+        .economy
+        .caravans
+        .iter()
+        .find(|caravan| caravan.to == to.id())
+        .expect("No goods were sent to the site");
+    assert!(caravan.by_sea);
+    assert!(caravan.goods[Good::Tools] > 0.0);
+}
@@ -0,0 +1,237 @@
+//! Snapshots of the map of the world taken as its history is simulated, written
+//! out as a numbered sequence of images to be played back as a timelapse.
+//!
+//! Each snapshot shows the sites in the colour of the faction that rules them,
+//! growing with their population, and the trade routes between them, brighter
+//! the more has been traded along them. Routes that are busy enough to get a
+//! road are drawn thicker. The last snapshot is drawn over the map once the
+//! roads have been laid and the sites have grown, so that changes to the
+//! generation of history can be reviewed by eye.
+
+use super::YEAR;
+use crate::{
+    civ::MIN_TRADE_SHARE,
+    column::ColumnGen,
+    sim::{sample_pos, sample_wpos, WorldSim},
+    site::SiteKind,
+    util::Sampler,
+    Index, IndexRef, CONFIG,
+};
+use common::{
+    terrain::{map::MapConfig, uniform_idx_as_vec2, TerrainChunkSize},
+    vol::RectVolSize,
+};
+use image::{Rgb, RgbImage};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{ops::RangeInclusive, path::PathBuf};
+use tracing::{info, warn};
+use vek::{Lerp, Vec2};
+
+/// Colours of the sites of each faction, in the order of their ids
+const FACTION_COLORS: [[u8; 3]; 8] = [
+    [230, 25, 75],
+    [60, 180, 75],
+    [255, 225, 25],
+    [0, 130, 200],
+    [245, 130, 48],
+    [145, 30, 180],
+    [70, 240, 240],
+    [240, 50, 230],
+];
+/// Colour of the sites that no faction rules
+const INDEPENDENT_COLOR: [u8; 3] = [255, 255, 255];
+const ROAD_COLOR: [u8; 3] = [255, 200, 80];
+const SEA_ROUTE_COLOR: [u8; 3] = [120, 200, 255];
+/// Largest radius of a site on the map, in pixels
+const MAX_SITE_RADIUS: f32 = 8.0;
+
+/// Where to write the snapshots of a timelapse of the history, and how often to
+/// take them.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TimelapseOpts {
+    /// Directory that the images are written to
+    pub dir: PathBuf,
+    /// Years of history between snapshots
+    pub interval: f32,
+}
+
+pub struct Timelapse {
+    dir: PathBuf,
+    interval: f32,
+    /// The map that the snapshots are drawn over, one pixel to a chunk
+    base: RgbImage,
+    /// Time of the last snapshot
+    last: Option<f32>,
+    frames: u32,
+}
+
+impl Timelapse {
+    /// Renders the map that the snapshots are drawn over. Returns `None` if the
+    /// directory for the images can't be created.
+    pub fn new(opts: &TimelapseOpts, sim: &WorldSim, index: IndexRef) -> Option<Self> {
+        if let Err(e) = std::fs::create_dir_all(&opts.dir) {
+            warn!(?e, dir = ?opts.dir, "Couldn't create directory for the history timelapse");
+            return None;
+        }
+        Some(Self {
+            dir: opts.dir.clone(),
+            interval: opts.interval,
+            base: render_map(sim, index),
+            last: None,
+            frames: 0,
+        })
+    }
+
+    /// Takes a snapshot if enough of the history has passed since the last one
+    pub fn tick(&mut self, index: &Index) {
+        if self
+            .last
+            .map_or(true, |last| index.time - last >= self.interval * YEAR)
+        {
+            self.snapshot(index);
+        }
+    }
+
+    /// Takes the last snapshot, over the map as it is at the end of worldgen
+    pub fn finish(&mut self, sim: &WorldSim, index: IndexRef) {
+        self.base = render_map(sim, index);
+        self.snapshot(index.index);
+        info!(frames = self.frames, dir = ?self.dir, "Wrote the history timelapse");
+    }
+
+    fn snapshot(&mut self, index: &Index) {
+        let mut image = self.base.clone();
+        let to_pixel = |wpos: Vec2<i32>| {
+            wpos.map2(TerrainChunkSize::RECT_SIZE, |e, sz: u32| {
+                e.div_euclid(sz as i32)
+            })
+        };
+
+        let busiest = index
+            .sites
+            .values()
+            .flat_map(|site| site.economy.route_trade.values().copied())
+            .fold(0.0, f32::max);
+        for site in index.sites.values() {
+            let economy = &site.economy;
+            for neighbor in economy.neighbors.iter() {
+                let traded = match economy.route_trade.get(&neighbor.id.id()) {
+                    Some(traded) if *traded > 0.0 => traded,
+                    _ => continue,
+                };
+                let share = traded / busiest;
+                draw_line(
+                    &mut image,
+                    to_pixel(site.get_origin()),
+                    to_pixel(index.sites[neighbor.id].get_origin()),
+                    // Routes without a road between their ends are only sailed
+                    if neighbor.cost.is_some() {
+                        ROAD_COLOR
+                    } else {
+                        SEA_ROUTE_COLOR
+                    },
+                    0.25 + share * 0.75,
+                    share >= MIN_TRADE_SHARE,
+                );
+            }
+        }
+
+        for site in index.sites.values() {
+            if let SiteKind::Dungeon(_) = site.kind {
+                continue;
+            }
+            let color = site.faction.map_or(INDEPENDENT_COLOR, |faction| {
+                FACTION_COLORS[faction.id() as usize % FACTION_COLORS.len()]
+            });
+            let radius = (site.economy.pop.max(0.0).sqrt() / 4.0)
+                .max(1.0)
+                .min(MAX_SITE_RADIUS);
+            draw_dot(&mut image, to_pixel(site.get_origin()), radius, color);
+        }
+
+        let path = self.dir.join(format!(
+            "{:05}_year_{:04}.png",
+            self.frames,
+            (index.time / YEAR) as i32
+        ));
+        if let Err(e) = image.save(&path) {
+            warn!(?e, ?path, "Couldn't write history timelapse snapshot");
+        }
+        self.frames += 1;
+        self.last = Some(index.time);
+    }
+}
+
+/// Renders the map of the world as it is shown in game, one pixel to a chunk
+fn render_map(sim: &WorldSim, index: IndexRef) -> RgbImage {
+    let map_size_lg = sim.map_size_lg();
+    let config = MapConfig::orthographic(
+        map_size_lg,
+        RangeInclusive::new(CONFIG.sea_level, CONFIG.sea_level + sim.max_height),
+    );
+    let column_gen = ColumnGen::new(sim);
+    let samples = (0..map_size_lg.chunks_len())
+        .into_par_iter()
+        .map(|posi| {
+            let wpos = uniform_idx_as_vec2(map_size_lg, posi)
+                * TerrainChunkSize::RECT_SIZE.map(|e| e as i32);
+            column_gen.get((wpos, index))
+        })
+        .collect::<Vec<_>>();
+
+    let size = map_size_lg.chunks().map(u32::from);
+    let mut image = RgbImage::new(size.x, size.y);
+    config.generate(
+        |pos| sample_pos(&config, sim, index, Some(&samples[..]), pos),
+        |pos| sample_wpos(&config, sim, pos),
+        |pos, (r, g, b, _)| image.put_pixel(pos.x as u32, pos.y as u32, Rgb([r, g, b])),
+    );
+    image
+}
+
+/// Blends a colour into a pixel of the image, if the pixel is on it
+fn blend(image: &mut RgbImage, pos: Vec2<i32>, color: [u8; 3], alpha: f32) {
+    if pos.x < 0 || pos.y < 0 || pos.x >= image.width() as i32 || pos.y >= image.height() as i32 {
+        return;
+    }
+    let pixel = image.get_pixel_mut(pos.x as u32, pos.y as u32);
+    for (e, c) in pixel.0.iter_mut().zip(color.iter()) {
+        *e = Lerp::lerp(*e as f32, *c as f32, alpha) as u8;
+    }
+}
+
+fn draw_line(
+    image: &mut RgbImage,
+    from: Vec2<i32>,
+    to: Vec2<i32>,
+    color: [u8; 3],
+    alpha: f32,
+    thick: bool,
+) {
+    let steps = (to - from).map(|e| e.abs()).reduce_max().max(1);
+    for i in 0..=steps {
+        let pos = Lerp::lerp(
+            from.map(|e| e as f32),
+            to.map(|e| e as f32),
+            i as f32 / steps as f32,
+        )
+        .map(|e| e.round() as i32);
+        blend(image, pos, color, alpha);
+        if thick {
+            blend(image, pos + Vec2::unit_x(), color, alpha);
+            blend(image, pos + Vec2::unit_y(), color, alpha);
+        }
+    }
+}
+
+fn draw_dot(image: &mut RgbImage, centre: Vec2<i32>, radius: f32, color: [u8; 3]) {
+    let extent = radius.ceil() as i32;
+    for y in -extent..=extent {
+        for x in -extent..=extent {
+            if ((x * x + y * y) as f32).sqrt() <= radius {
+                blend(image, centre + Vec2::new(x, y), color, 1.0);
+            }
+        }
+    }
+}
@@ -162,6 +162,12 @@ impl Castle {
 
     pub fn radius(&self) -> f32 { 1200.0 }
 
+    /// Radius of the castle walls
+    pub fn walls_radius(&self) -> f32 { self.radius as f32 }
+
+    /// Whether the castle is held by hostile forces
+    pub fn is_evil(&self) -> bool { self.evil }
+
     #[allow(clippy::needless_update)] // TODO: Pending review in #587
     pub fn spawn_rules(&self, wpos: Vec2<i32>) -> SpawnRules {
         SpawnRules {
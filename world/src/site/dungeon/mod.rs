@@ -32,6 +32,7 @@ pub struct Dungeon {
     #[allow(dead_code)]
     noise: RandomField,
     floors: Vec<Floor>,
+    reusable_keys: bool,
 }
 
 pub struct GenCtx<'a, R: Rng> {
@@ -48,6 +49,19 @@ const ALT_OFFSET: i32 = -2;
 
 const LEVELS: usize = 5;
 
+/// Chance for the stairs leading down from a floor to be locked behind a gate
+const LOCKED_STAIRS_CHANCE: f64 = 0.5;
+/// Chance for the key of a locked floor to be carried by a mini-boss rather
+/// than hidden in a side room
+const MINI_BOSS_KEY_CHANCE: f64 = 0.5;
+/// Chance for keys to be kept when they are used to unlock a gate
+const REUSABLE_KEYS_CHANCE: f64 = 0.3;
+/// Chance for the final boss to be a colossus rather than a stone golem
+const COLOSSUS_BOSS_CHANCE: f32 = 0.25;
+
+/// Chance for each corner of a room with enemies to be guarded by a turret
+const TURRET_CHANCE: f32 = 0.3;
+
 impl Dungeon {
     #[allow(clippy::let_and_return)] // TODO: Pending review in #587
     pub fn generate(wpos: Vec2<i32>, sim: Option<&WorldSim>, rng: &mut impl Rng) -> Self {
@@ -68,6 +82,7 @@ impl Dungeon {
                     Some(floor)
                 })
                 .collect(),
+            reusable_keys: ctx.rng.gen_bool(REUSABLE_KEYS_CHANCE),
         };
 
         this
@@ -77,6 +92,33 @@ impl Dungeon {
 
     pub fn radius(&self) -> f32 { 1200.0 }
 
+    /// Whether the keys of this dungeon are kept after unlocking a gate rather
+    /// than being consumed
+    pub fn reusable_keys(&self) -> bool { self.reusable_keys }
+
+    /// The volume spanned by the floors of the dungeon, in world coordinates
+    pub fn bounds(&self) -> Aabb<i32> {
+        let min_tile = -FLOOR_SIZE / 2;
+        let max_tile = min_tile + FLOOR_SIZE;
+        let depth = self
+            .floors
+            .iter()
+            .map(|floor| floor.total_depth())
+            .sum::<i32>();
+        Aabb {
+            min: Vec3::from(self.origin + min_tile * TILE_SIZE)
+                + Vec3::unit_z() * (self.alt + ALT_OFFSET - depth),
+            max: Vec3::from(self.origin + max_tile * TILE_SIZE)
+                + Vec3::unit_z() * (self.alt + ALT_OFFSET),
+        }
+    }
+
+    /// The altitude of the floor of the topmost level, where cave tunnels
+    /// leading into the dungeon come out
+    pub fn top_floor_alt(&self) -> i32 {
+        self.alt + ALT_OFFSET - self.floors.first().map_or(0, |floor| floor.total_depth())
+    }
+
     #[allow(clippy::needless_update)] // TODO: Pending review in #587
     pub fn spawn_rules(&self, wpos: Vec2<i32>) -> SpawnRules {
         SpawnRules {
@@ -217,6 +259,27 @@ pub struct Room {
     pillars: Option<i32>, // Pillars with the given separation
 }
 
+impl Room {
+    /// The tile closest to the center of the room that isn't a pillar
+    fn center_tile(&self) -> Vec2<i32> {
+        let center = self.area.center();
+        let center_is_pillar = self
+            .pillars
+            .map(|pillar_space| center.map(|e| e.rem_euclid(pillar_space) == 0).reduce_and())
+            .unwrap_or(false);
+        center + if center_is_pillar { 1 } else { 0 }
+    }
+}
+
+/// Where the key to the locked stairs of a floor can be found
+#[derive(Copy, Clone)]
+enum KeySource {
+    /// Dropped by a mini-boss guarding the room
+    MiniBoss(Id<Room>),
+    /// Hidden in the middle of the room
+    Hidden(Id<Room>),
+}
+
 struct Floor {
     tile_offset: Vec2<i32>,
     tiles: Grid<Tile>,
@@ -226,6 +289,7 @@ struct Floor {
     #[allow(dead_code)]
     stair_tile: Vec2<i32>,
     final_level: bool,
+    locked_stairs: Option<KeySource>,
 }
 
 const FLOOR_SIZE: Vec2<i32> = Vec2::new(18, 18);
@@ -259,6 +323,7 @@ impl Floor {
             hollow_depth: 30,
             stair_tile: new_stair_tile - tile_offset,
             final_level,
+            locked_stairs: None,
         };
 
         const STAIR_ROOM_HEIGHT: i32 = 13;
@@ -311,6 +376,23 @@ impl Floor {
             }
         }
 
+        // Lock the way down behind a gate, with the key somewhere on the floor
+        if !final_level && ctx.rng.gen_bool(LOCKED_STAIRS_CHANCE) {
+            let side_rooms = this
+                .rooms
+                .iter()
+                .filter(|(_, room)| room.enemy_density.is_some() && !room.boss)
+                .map(|(id, _)| id)
+                .collect::<Vec<_>>();
+            this.locked_stairs = side_rooms.choose(ctx.rng).map(|room| {
+                if ctx.rng.gen_bool(MINI_BOSS_KEY_CHANCE) {
+                    KeySource::MiniBoss(*room)
+                } else {
+                    KeySource::Hidden(*room)
+                }
+            });
+        }
+
         (this, new_stair_tile)
     }
 
@@ -446,8 +528,8 @@ impl Floor {
             for y in area.min.y..area.max.y {
                 let tile_pos = Vec2::new(x, y).map(|e| e.div_euclid(TILE_SIZE)) - self.tile_offset;
                 let wpos2d = origin.xy() + Vec2::new(x, y);
-                if let Some(Tile::Room(room)) = self.tiles.get(tile_pos) {
-                    let room = &self.rooms[*room];
+                if let Some(Tile::Room(room_id)) = self.tiles.get(tile_pos) {
+                    let room = &self.rooms[*room_id];
 
                     let tile_wcenter = origin
                         + Vec3::from(
@@ -502,38 +584,106 @@ impl Floor {
                         supplement.add_entity(entity);
                     }
 
+                    // Turrets watch over the room from its corners
+                    let room_corners = [
+                        Vec2::new(room.area.x, room.area.y),
+                        Vec2::new(room.area.x + room.area.w - 1, room.area.y),
+                        Vec2::new(room.area.x, room.area.y + room.area.h - 1),
+                        Vec2::new(room.area.x + room.area.w - 1, room.area.y + room.area.h - 1),
+                    ];
+                    if room.enemy_density.is_some()
+                        && !room.boss
+                        && !tile_is_pillar
+                        && tile_wcenter.xy() == wpos2d
+                        && room_corners.contains(&tile_pos)
+                        && RandomField::new(room.seed.wrapping_add(3))
+                            .chance(Vec3::from(tile_pos), TURRET_CHANCE)
+                    {
+                        let species = if dynamic_rng.gen() {
+                            comp::turret::Species::MagicTurret
+                        } else {
+                            comp::turret::Species::Statue
+                        };
+                        let facing = (room.area.center() - tile_pos)
+                            .map(|e| e as f32)
+                            .try_normalized()
+                            .unwrap_or_else(Vec2::unit_y);
+                        let entity = EntityInfo::at(tile_wcenter.map(|e| e as f32))
+                            .with_level(dynamic_rng.gen_range(1, 4))
+                            .with_alignment(comp::Alignment::Enemy)
+                            .with_body(comp::Body::Turret(comp::turret::Body::random_with(
+                                dynamic_rng,
+                                &species,
+                            )))
+                            .with_facing(facing)
+                            .with_automatic_name();
+
+                        supplement.add_entity(entity);
+                    }
+
                     if room.boss {
-                        let boss_spawn_tile = room.area.center();
                         // Don't spawn the boss in a pillar
-                        let boss_tile_is_pillar = room
-                            .pillars
-                            .map(|pillar_space| {
-                                boss_spawn_tile
-                                    .map(|e| e.rem_euclid(pillar_space) == 0)
-                                    .reduce_and()
-                            })
-                            .unwrap_or(false);
-                        let boss_spawn_tile =
-                            boss_spawn_tile + if boss_tile_is_pillar { 1 } else { 0 };
+                        let boss_spawn_tile = room.center_tile();
 
                         if tile_pos == boss_spawn_tile && tile_wcenter.xy() == wpos2d {
                             let chosen = Lottery::<String>::load_expect(
                                 "common.loot_tables.loot_table_boss_cultist-leader",
                             );
                             let chosen = chosen.choose();
+                            let (body, name, boss) = if RandomField::new(room.seed.wrapping_add(2))
+                                .chance(Vec3::from(tile_pos), COLOSSUS_BOSS_CHANCE)
+                            {
+                                (
+                                    comp::Body::Colossus(comp::colossus::Body::random_with(
+                                        dynamic_rng,
+                                        &comp::colossus::Species::Titan,
+                                    )),
+                                    "Ancient Colossus",
+                                    "common.bosses.colossus",
+                                )
+                            } else {
+                                (
+                                    comp::Body::Golem(comp::golem::Body::random_with(
+                                        dynamic_rng,
+                                        &comp::golem::Species::StoneGolem,
+                                    )),
+                                    "Stonework Defender",
+                                    "common.bosses.stone_golem",
+                                )
+                            };
                             let entity = EntityInfo::at(tile_wcenter.map(|e| e as f32))
                                 .with_level(dynamic_rng.gen_range(1, 5))
                                 .with_alignment(comp::Alignment::Enemy)
-                                .with_body(comp::Body::Golem(comp::golem::Body::random_with(
-                                    dynamic_rng,
-                                    &comp::golem::Species::StoneGolem,
-                                )))
-                                .with_name("Stonework Defender".to_string())
+                                .with_body(body)
+                                .with_name(name.to_string())
+                                .with_boss(boss)
                                 .with_loot_drop(comp::Item::new_from_asset_expect(chosen));
 
                             supplement.add_entity(entity);
                         }
                     }
+
+                    if let Some(KeySource::MiniBoss(key_room)) = self.locked_stairs {
+                        if *room_id == key_room
+                            && tile_pos == room.center_tile()
+                            && tile_wcenter.xy() == wpos2d
+                        {
+                            let entity = EntityInfo::at(tile_wcenter.map(|e| e as f32))
+                                .into_giant()
+                                .with_level(dynamic_rng.gen_range(3, 8))
+                                .with_alignment(comp::Alignment::Enemy)
+                                .with_body(comp::Body::Humanoid(comp::humanoid::Body::random()))
+                                .with_name("Cultist Warden")
+                                .with_loot_drop(comp::Item::new_from_asset_expect(
+                                    "common.items.utility.key",
+                                ))
+                                .with_main_tool(comp::Item::new_from_asset_expect(
+                                    "common.items.npc_weapons.sword.cultist_purp_2h-0",
+                                ));
+
+                            supplement.add_entity(entity);
+                        }
+                    }
                 }
             }
         }
@@ -541,6 +691,16 @@ impl Floor {
 
     fn total_depth(&self) -> i32 { self.solid_depth + self.hollow_depth }
 
+    /// Position of the key hidden on this floor, relative to the floor
+    fn hidden_key_pos(&self) -> Option<Vec2<i32>> {
+        match self.locked_stairs {
+            Some(KeySource::Hidden(room)) => {
+                Some(self.rooms[room].center_tile() * TILE_SIZE + TILE_SIZE / 2)
+            },
+            _ => None,
+        }
+    }
+
     fn nearest_wall(&self, rpos: Vec2<i32>) -> Option<Vec2<i32>> {
         let tile_pos = rpos.map(|e| e.div_euclid(TILE_SIZE));
 
@@ -606,7 +766,14 @@ impl Floor {
         let tunnel_dist =
             1.0 - (dist_to_wall - wall_thickness).max(0.0) / (TILE_SIZE as f32 - wall_thickness);
 
-        let floor_sprite = if RandomField::new(7331).chance(Vec3::from(pos), 0.00005) {
+        let floor_sprite = if self.hidden_key_pos() == Some(rpos) {
+            BlockMask::new(with_sprite(SpriteKind::Key), 1)
+        } else if matches!(self.tiles.get(tile_pos), Some(Tile::Tunnel))
+            && RandomField::new(4242).chance(Vec3::from(pos), 0.002)
+        {
+            // Spike traps lie in wait along the tunnels between rooms
+            BlockMask::new(with_sprite(SpriteKind::Spikes), 1)
+        } else if RandomField::new(7331).chance(Vec3::from(pos), 0.00005) {
             BlockMask::new(
                 with_sprite(
                     match (RandomField::new(1337).get(Vec3::from(pos)) / 2) % 20 {
@@ -670,8 +837,17 @@ impl Floor {
                 }
             },
             Some(Tile::DownStair(_)) => {
-                make_staircase(Vec3::new(rtile_pos.x, rtile_pos.y, z), 0.0, 0.5, 9.0)
-                    .resolve_with(vacant)
+                // Locked stairs are covered by a gate that needs a key to open
+                if self.locked_stairs.is_some()
+                    && z == 0
+                    && rtile_pos.map(|e| e as f32).magnitude_squared()
+                        < (TILE_SIZE as f32 / 2.0).powf(2.0)
+                {
+                    BlockMask::new(with_sprite(SpriteKind::KeyDoor), 1)
+                } else {
+                    make_staircase(Vec3::new(rtile_pos.x, rtile_pos.y, z), 0.0, 0.5, 9.0)
+                        .resolve_with(vacant)
+                }
             },
             Some(Tile::UpStair(room)) => {
                 let mut block = make_staircase(
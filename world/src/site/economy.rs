@@ -1,7 +1,17 @@
-use crate::util::{DHashMap, MapVec};
+use crate::{
+    sim2::events::{SiteEvent, CROP_FAILURE_YIELD, DISCOVERY_YIELD},
+    site::Site,
+    util::{DHashMap, MapVec},
+};
+use common::{
+    assets::{Asset, Ron},
+    store::Id,
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::VecDeque, sync::Arc};
 
 #[repr(u8)]
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
 pub enum Good {
     Wheat = 0,
     Flour = 1,
@@ -13,11 +23,56 @@ pub enum Good {
     Wood = 7,
     Rock = 8,
     Stone = 9,
+    /// Currency that prices are denominated in
+    Coin = 10,
+    Ore = 11,
+    Ingot = 12,
+    Leather = 13,
+    Flax = 14,
+    Cloth = 15,
+    Tools = 16,
 }
 use Good::*;
 
+pub const ALL_GOODS: [Good; 17] = [
+    Wheat, Flour, Meat, Fish, Game, Food, Logs, Wood, Rock, Stone, Coin, Ore, Ingot, Leather, Flax,
+    Cloth, Tools,
+];
+
+/// Where the recipes of the economy are loaded from
+const RECIPES_MANIFEST: &str = "world.economy.recipes";
+
+/// Coins that each person in a site starts out with
+const STARTING_COINS_PER_PERSON: f32 = 20.0;
+/// Coins minted each year for each person in a site
+const MINTED_COINS_PER_PERSON: f32 = 0.5;
+/// How many times each coin changes hands in a tick, which sets how high prices
+/// are compared to the money in a site
+const MONEY_VELOCITY: f32 = 4.0;
+/// How many ticks of history each site keeps, which is ten years
+const HISTORY_LENGTH: usize = 40;
+/// Share of the output of a site lost to theft for each person in crime
+const CRIME_PRODUCTIVITY_LOSS: f32 = 0.5;
+
+/// Children born each year for each adult in a site with food to spare
+const BIRTH_RATE: f32 = 0.12;
+/// Years that people spend as children, and then working as adults
+const CHILDHOOD_YEARS: f32 = 15.0;
+const WORKING_YEARS: f32 = 40.0;
+/// Share of the people of each age that die each year
+const CHILD_DEATH_RATE: f32 = 0.01;
+const ADULT_DEATH_RATE: f32 = 0.005;
+const ELDER_DEATH_RATE: f32 = 0.08;
+/// Most that experience can add to the output of a labor
+const MAX_SKILL_BONUS: f32 = 0.5;
+/// Years of experience at which workers get half of the most they can from it
+const SKILL_HALF_YEARS: f32 = 5.0;
+/// Years of experience that the workers of a labor can have on average, since
+/// the most experienced of them keep retiring
+const MAX_EXPERIENCE: f32 = WORKING_YEARS / 2.0;
+
 #[repr(u8)]
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
 pub enum Labor {
     Farmer = 0,
     Lumberjack = 1,
@@ -25,9 +80,40 @@ pub enum Labor {
     Fisher = 3,
     Hunter = 4,
     Cook = 5,
+    Smelter = 6,
+    Tanner = 7,
+    Weaver = 8,
+    Blacksmith = 9,
 }
 use Labor::*;
 
+/// The goods that a labor turns into another good
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Recipe {
+    /// Goods used up by each labourer in a tick
+    pub inputs: Vec<(Good, f32)>,
+    /// Good made by each labourer in a tick, and how much of it
+    pub output: (Good, f32),
+}
+
+/// What the people of sites use up and make. These are loaded from assets so
+/// that the economy can be rebalanced without touching the simulation.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EconomyRecipes {
+    /// Goods used up by each person in a tick, whatever their labor
+    pub consumption: Vec<(Good, f32)>,
+    /// What each labor makes, and out of what
+    pub labors: Vec<(Labor, Recipe)>,
+    /// Goods that the land around each site yields, and how much of each at
+    /// most
+    pub natural: Vec<(Good, f32)>,
+}
+
+impl EconomyRecipes {
+    pub fn load() -> Arc<Self> { Ron::<Self>::load_expect(RECIPES_MANIFEST) }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Economy {
     pub pop: f32,
 
@@ -35,6 +121,10 @@ pub struct Economy {
     pub surplus: MapVec<Good, f32>,
     pub marginal_surplus: MapVec<Good, f32>,
     pub values: MapVec<Good, Option<f32>>,
+    /// Price of each good in coins
+    pub prices: MapVec<Good, f32>,
+    /// Coins earned by each worker in a tick
+    pub wages: f32,
 
     pub labor_values: MapVec<Good, Option<f32>>,
     pub material_costs: MapVec<Good, f32>,
@@ -42,17 +132,175 @@ pub struct Economy {
     pub labors: MapVec<Labor, f32>,
     pub yields: MapVec<Labor, f32>,
     pub productivity: MapVec<Labor, f32>,
+    /// Share of the workforce left idle for lack of materials to work with
+    pub unemployment: f32,
+    /// How many ticks in a row the site has been short of food
+    pub hungry_ticks: u32,
+    /// People who moved into the site during the last tick, less those who
+    /// moved out
+    pub migration: f32,
+
+    /// Goods delivered by caravans from other sites during the last tick
+    pub imports: MapVec<Good, f32>,
+    /// Goods sent out on caravans to other sites during the last tick
+    pub exports: MapVec<Good, f32>,
+    /// Caravans sent out by this site that are still on the road
+    pub caravans: Vec<Caravan>,
+    /// Trade routes to neighbouring sites. These are regenerated along with the
+    /// sites, so they aren't saved.
+    #[serde(skip)]
+    pub neighbors: Vec<NeighborInformation>,
+    /// Coins paid for the goods sent along each of the trade routes over the
+    /// whole history, by the id of the site at the other end of the route
+    #[serde(default)]
+    pub route_trade: DHashMap<u64, f32>,
+    /// The state of the economy at the end of each of the last few ticks
+    #[serde(default)]
+    pub history: EconomyHistory,
+
+    /// Ticks left until the farmland recovers from a crop failure
+    #[serde(default)]
+    pub crop_failure_ticks: u32,
+    /// Ore deposits found near the site, each of which makes its mines more
+    /// productive
+    #[serde(default)]
+    pub deposits: u32,
+    /// Plagues, crop failures and discoveries that struck the site, oldest
+    /// first
+    #[serde(default)]
+    pub events: Vec<SiteEvent>,
+    /// Share of the people who live off theft and the black market, which
+    /// grows when essential goods are scarce or dear
+    #[serde(default)]
+    pub crime: f32,
+    /// The people of the site by age, which add up to `pop`
+    #[serde(default)]
+    pub cohorts: Cohorts,
+    /// Years that the workers of each labor have worked at it on average, which
+    /// makes them more productive
+    #[serde(default)]
+    pub experience: MapVec<Labor, f32>,
+    /// How much of each natural good the land around the site yields, relative
+    /// to the economy recipes. This is sampled from the world along with the
+    /// sites, so it isn't saved.
+    #[serde(skip, default = "full_endowment")]
+    pub endowment: MapVec<Good, f32>,
+}
+
+fn full_endowment() -> MapVec<Good, f32> { MapVec::from_default(1.0) }
+
+/// How the people of a site are split between ages. Only adults work, but
+/// everyone eats.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Cohorts {
+    pub children: f32,
+    pub adults: f32,
+    pub elders: f32,
+}
+
+impl Cohorts {
+    /// Splits `pop` people between ages the way a settled site would be
+    pub fn new(pop: f32) -> Self {
+        Self {
+            children: pop * 0.3,
+            adults: pop * 0.55,
+            elders: pop * 0.15,
+        }
+    }
+
+    pub fn total(&self) -> f32 { self.children + self.adults + self.elders }
+}
+
+/// The state of a site's economy at the end of a tick
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EconomySample {
+    pub time: f32,
+    pub pop: f32,
+    pub prices: MapVec<Good, f32>,
+    pub stocks: MapVec<Good, f32>,
+}
+
+/// The last few samples of a site's economy, oldest first, so that trends can
+/// be looked at without running the simulation again
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct EconomyHistory {
+    samples: VecDeque<EconomySample>,
+}
+
+impl EconomyHistory {
+    /// Adds a sample, dropping the oldest one once the history is full
+    pub fn push(&mut self, sample: EconomySample) {
+        if self.samples.len() >= HISTORY_LENGTH {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &EconomySample> + '_ { self.samples.iter() }
+
+    pub fn latest(&self) -> Option<&EconomySample> { self.samples.back() }
+
+    /// Price of a good at each sample, oldest first
+    pub fn prices(&self, good: Good) -> impl Iterator<Item = (f32, f32)> + '_ {
+        self.samples
+            .iter()
+            .map(move |sample| (sample.time, sample.prices[good]))
+    }
+}
+
+/// A trade route from a site to one of its neighbours
+#[derive(Clone, Debug)]
+pub struct NeighborInformation {
+    pub id: Id<Site>,
+    /// Length of the road between the sites, in chunks
+    pub distance: f32,
+    /// Cost of travelling the road, which accounts for the hills, rivers and
+    /// wilderness along it, or `None` if the sites only trade by sea and there
+    /// is no road between them
+    pub cost: Option<f32>,
+    /// Length of the way along the coast between the sites, in chunks, if both
+    /// of them are by the sea and can ship bulk goods to each other
+    pub sea_distance: Option<f32>,
+}
+
+/// A merchant caravan carrying goods between two sites.
+///
+/// Caravans are kept around while they are on the road so that they can be
+/// placed in the world, where they might be attacked before they arrive.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Caravan {
+    /// Id of the site that the caravan is headed for, which is one of the
+    /// neighbours of the site that sent it
+    pub to: u64,
+    /// Whether the goods go by ship rather than by road
+    #[serde(default)]
+    pub by_sea: bool,
+    pub goods: MapVec<Good, f32>,
+    pub departure: f32,
+    pub arrival: f32,
+}
+
+impl Caravan {
+    /// How far along its route the caravan is at `time`, from 0 to 1
+    pub fn progress(&self, time: f32) -> f32 {
+        ((time - self.departure) / (self.arrival - self.departure).max(0.001))
+            .max(0.0)
+            .min(1.0)
+    }
 }
 
 impl Default for Economy {
     fn default() -> Self {
+        let pop = 32.0;
         Self {
-            pop: 32.0,
+            pop,
 
-            stocks: Default::default(),
+            stocks: MapVec::from_list(&[(Coin, pop * STARTING_COINS_PER_PERSON)], 0.0),
             surplus: Default::default(),
             marginal_surplus: Default::default(),
             values: Default::default(),
+            prices: MapVec::from_list(&[(Coin, 1.0)], 0.0),
+            wages: 1.0,
 
             labor_values: Default::default(),
             material_costs: Default::default(),
@@ -60,60 +308,230 @@ impl Default for Economy {
             labors: Default::default(),
             yields: Default::default(),
             productivity: Default::default(),
+            unemployment: 0.0,
+            hungry_ticks: 0,
+            migration: 0.0,
+
+            imports: Default::default(),
+            exports: Default::default(),
+            caravans: Vec::new(),
+            neighbors: Vec::new(),
+            route_trade: DHashMap::default(),
+            history: EconomyHistory::default(),
+
+            crop_failure_ticks: 0,
+            deposits: 0,
+            events: Vec::new(),
+            crime: 0.0,
+            cohorts: Cohorts::new(pop),
+            experience: Default::default(),
+            endowment: full_endowment(),
         }
     }
 }
 
 impl Economy {
-    pub fn get_orders(&self) -> DHashMap<Option<Labor>, Vec<(Good, f32)>> {
-        vec![
-            (None, vec![(Food, 0.5)]),
-            (Some(Cook), vec![
-                (Flour, 12.0),
-                (Meat, 4.0),
-                (Wood, 1.5),
-                (Stone, 1.0),
-            ]),
-            (Some(Lumberjack), vec![(Logs, 0.5)]),
-            (Some(Miner), vec![(Rock, 0.5)]),
-            (Some(Fisher), vec![(Fish, 4.0)]),
-            (Some(Hunter), vec![(Game, 1.0)]),
-            (Some(Farmer), vec![(Wheat, 2.0)]),
-        ]
-        .into_iter()
-        .collect()
-    }
-
-    pub fn get_productivity(&self) -> MapVec<Labor, (Good, f32)> {
-        // Per labourer, per year
-        MapVec::from_list(
-            &[
-                (Farmer, (Flour, 2.0)),
-                (Lumberjack, (Wood, 0.5)),
-                (Miner, (Stone, 0.5)),
-                (Fisher, (Meat, 4.0)),
-                (Hunter, (Meat, 1.0)),
-                (Cook, (Food, 16.0)),
-            ],
-            (Rock, 0.0),
-        )
-        .map(|l, (good, v)| (good, v * (1.0 + self.labors[l])))
-    }
-
-    pub fn replenish(&mut self, time: f32) {
+    /// Price of a good in coins, if the site has any use for it
+    pub fn price(&self, good: Good) -> Option<f32> { self.values[good].map(|_| self.prices[good]) }
+
+    /// Worth in coins of everything the site has in stock
+    pub fn wealth(&self) -> f32 {
+        self.stocks
+            .iter()
+            .map(|(good, stock)| stock * self.prices[good])
+            .sum()
+    }
+
+    /// Records the state of the economy at `time` in its history
+    pub fn record(&mut self, time: f32) {
+        let sample = EconomySample {
+            time,
+            pop: self.pop,
+            prices: self.prices.clone(),
+            stocks: self.stocks.clone(),
+        };
+        self.history.push(sample);
+    }
+
+    /// Goods used up by the people of the site, whatever their labor (`None`),
+    /// and by each labor
+    pub fn get_orders(
+        &self,
+        recipes: &EconomyRecipes,
+    ) -> DHashMap<Option<Labor>, Vec<(Good, f32)>> {
+        std::iter::once((None, recipes.consumption.clone()))
+            .chain(
+                recipes
+                    .labors
+                    .iter()
+                    .map(|(labor, recipe)| (Some(*labor), recipe.inputs.clone())),
+            )
+            .collect()
+    }
+
+    /// Good made by each labor, and how much of it each labourer makes
+    pub fn get_productivity(&self, recipes: &EconomyRecipes) -> MapVec<Labor, (Good, f32)> {
+        let outputs = recipes
+            .labors
+            .iter()
+            .map(|(labor, recipe)| (*labor, recipe.output))
+            .collect::<Vec<_>>();
+        MapVec::from_list(&outputs, (Rock, 0.0)).map(|l, (good, v)| {
+            let crime_loss = 1.0 - self.crime * CRIME_PRODUCTIVITY_LOSS;
+            let rate = v * (1.0 + self.labors[l]) * self.event_productivity(l) * crime_loss;
+            (good, rate * self.skill(l))
+        })
+    }
+
+    /// People of working age, who are split between the labors
+    pub fn workforce(&self) -> f32 { self.cohorts.adults }
+
+    /// Factor by which the experience of the workers of a labor raises their
+    /// output
+    pub fn skill(&self, labor: Labor) -> f32 {
+        let experience = self.experience[labor];
+        1.0 + MAX_SKILL_BONUS * experience / (experience + SKILL_HALF_YEARS)
+    }
+
+    /// Workers who take up a labor start out without any experience of it, so
+    /// they bring down the average of those who were already at it. The
+    /// workforce is split by `before` beforehand and by `labors` now.
+    pub fn reassign(&mut self, before: &MapVec<Labor, f32>) {
+        for labor in Labor::list().iter().copied() {
+            let joined = self.labors[labor] - before[labor];
+            if joined > 0.0 {
+                self.experience[labor] *= before[labor] / self.labors[labor];
+            }
+        }
+    }
+
+    /// Ages the people of the site by `years`, during which children are only
+    /// born if the site has food to spare. Children who grow up join the
+    /// workforce without any experience.
+    pub fn age(&mut self, years: f32, fed: bool) {
+        // Sites saved before ages were kept track of
+        if self.cohorts.total() <= 0.0 && self.pop > 0.0 {
+            self.cohorts = Cohorts::new(self.pop);
+        }
+
+        let Cohorts {
+            children,
+            adults,
+            elders,
+        } = self.cohorts;
+        let births = if fed { adults * BIRTH_RATE * years } else { 0.0 };
+        let grown = children / CHILDHOOD_YEARS * years;
+        let retired = adults / WORKING_YEARS * years;
+        let experienced = adults - retired - adults * ADULT_DEATH_RATE * years;
+        self.cohorts = Cohorts {
+            children: children + births - grown - children * CHILD_DEATH_RATE * years,
+            adults: experienced + grown,
+            elders: elders + retired - elders * ELDER_DEATH_RATE * years,
+        };
+        self.pop = self.cohorts.total();
+
+        let kept = experienced / (experienced + grown).max(0.001);
+        for labor in Labor::list().iter().copied() {
+            self.experience[labor] = (self.experience[labor] * kept + years).min(MAX_EXPERIENCE);
+        }
+    }
+
+    /// Kills off `share` of the people of every age, returning how many died
+    pub fn die_off(&mut self, share: f32) -> f32 {
+        let deaths = self.pop * share;
+        let cohorts = &mut self.cohorts;
+        cohorts.children *= 1.0 - share;
+        cohorts.adults *= 1.0 - share;
+        cohorts.elders *= 1.0 - share;
+        self.pop = cohorts.total();
+        deaths
+    }
+
+    /// Productivity of a labor after the crop failures and discoveries that
+    /// struck the site, relative to normal
+    fn event_productivity(&self, labor: Labor) -> f32 {
+        match labor {
+            Farmer if self.crop_failure_ticks > 0 => CROP_FAILURE_YIELD,
+            Miner => 1.0 + DISCOVERY_YIELD * self.deposits as f32,
+            _ => 1.0,
+        }
+    }
+
+    /// The neighbour that a caravan sent out by the site is headed for
+    pub fn destination(&self, caravan: &Caravan) -> Option<Id<Site>> {
+        self.neighbors
+            .iter()
+            .map(|neighbor| neighbor.id)
+            .find(|id| id.id() == caravan.to)
+    }
+
+    /// The history of the site as its people tell it, oldest events first
+    pub fn lore(&self) -> impl Iterator<Item = String> + '_ {
+        self.events.iter().map(SiteEvent::describe)
+    }
+
+    /// Sets the price of each good from its value, at a level where the coins
+    /// in the site are just enough to pay for what it demands
+    pub fn update_prices(&mut self, demand: &MapVec<Good, f32>) {
+        let demand_value = demand
+            .iter()
+            .filter(|(good, _)| *good != Coin)
+            .map(|(good, demand)| self.values[good].unwrap_or(0.0) * demand)
+            .sum::<f32>();
+        if demand_value <= 0.0 {
+            return;
+        }
+        let price_level = self.stocks[Coin] * MONEY_VELOCITY / demand_value;
+
+        let smooth = 0.8;
+        for good in Good::list().iter().filter(|good| **good != Coin) {
+            if let Some(value) = self.values[*good] {
+                let target = value * price_level;
+                let price = &mut self.prices[*good];
+                *price = if *price > 0.0 {
+                    smooth * *price + (1.0 - smooth) * target
+                } else {
+                    target
+                };
+            }
+        }
+    }
+
+    /// Moves `migrants` adults out of the site, returning how many left and
+    /// the coins they took with them
+    pub fn emigrate(&mut self, migrants: f32) -> (f32, f32) {
+        let migrants = migrants.min(self.cohorts.adults);
+        let coins = self.stocks[Coin] * migrants / self.pop.max(0.001);
+        self.cohorts.adults -= migrants;
+        self.pop = self.cohorts.total();
+        self.stocks[Coin] -= coins;
+        self.migration -= migrants;
+        (migrants, coins)
+    }
+
+    /// Moves `migrants` people into the site, along with their `coins`
+    pub fn immigrate(&mut self, migrants: f32, coins: f32) {
+        // Migrants have to start over at whatever labor they are put to
+        let adults = self.cohorts.adults;
+        for labor in Labor::list().iter().copied() {
+            self.experience[labor] *= adults / (adults + migrants).max(0.001);
+        }
+        self.cohorts.adults += migrants;
+        self.pop = self.cohorts.total();
+        self.stocks[Coin] += coins;
+        self.migration += migrants;
+    }
+
+    /// Mints new coins for the people of the site over `years`
+    pub fn mint(&mut self, years: f32) {
+        self.stocks[Coin] += self.pop * MINTED_COINS_PER_PERSON * years;
+    }
+
+    pub fn replenish(&mut self, time: f32, recipes: &EconomyRecipes) {
         //use rand::Rng;
-        for (i, (g, v)) in [
-            (Wheat, 50.0),
-            (Logs, 20.0),
-            (Rock, 120.0),
-            (Game, 12.0),
-            (Fish, 10.0),
-        ]
-        .iter()
-        .enumerate()
-        {
-            self.stocks[*g] = (*v
-                * (1.25 + (((time * 0.0001 + i as f32).sin() + 1.0) % 1.0) * 0.5)
+        for (i, (g, v)) in recipes.natural.iter().enumerate() {
+            let v = v * self.endowment[*g];
+            self.stocks[*g] = (v * (1.25 + (((time * 0.0001 + i as f32).sin() + 1.0) % 1.0) * 0.5)
                 - self.stocks[*g])
                 * 0.075; //rand::thread_rng().gen_range(0.05, 0.1);
         }
@@ -127,29 +545,58 @@ impl Default for Good {
 }
 
 impl Good {
-    pub fn list() -> &'static [Self] {
-        static GOODS: [Good; 10] = [
-            Wheat, Flour, Meat, Fish, Game, Food, Logs, Wood, Rock, Stone,
-        ];
+    pub fn list() -> &'static [Self] { &ALL_GOODS }
 
-        &GOODS
-    }
-
-    pub fn decay_rate(&self) -> f32 {
+    /// Share of a stock of the good that spoils each tick, given how warm the
+    /// time of year is (see `Season::warmth`). Food spoils faster in summer,
+    /// so sites have to stockpile it.
+    pub fn decay_rate(&self, warmth: f32) -> f32 {
         match self {
-            Food => 0.2,
+            Food => 0.2 * (1.0 + warmth.max(0.0)),
             Wheat => 0.1,
             Meat => 0.25,
             Fish => 0.2,
             _ => 0.0,
         }
     }
+
+    /// How heavy and bulky the good is to carry, which scales how much of it is
+    /// used up to get it to another site
+    pub fn weight(&self) -> f32 {
+        match self {
+            Rock => 3.0,
+            Logs | Ore | Stone => 2.5,
+            Wood | Ingot => 1.5,
+            Leather | Cloth | Tools => 0.5,
+            Coin => 0.1,
+            _ => 1.0,
+        }
+    }
+
+    /// Whether the good is shipped in bulk between sites by the sea. Other goods
+    /// are too perishable or valuable for the slow ships, and go by road unless
+    /// there is no road to take.
+    pub fn is_bulk(&self) -> bool {
+        matches!(self, Wheat | Flour | Logs | Wood | Rock | Stone | Ore | Ingot | Flax)
+    }
 }
 
 impl Labor {
     pub fn list() -> &'static [Self] {
-        static LABORS: [Labor; 6] = [Farmer, Lumberjack, Miner, Fisher, Hunter, Cook];
+        static LABORS: [Labor; 10] = [
+            Farmer, Lumberjack, Miner, Fisher, Hunter, Cook, Smelter, Tanner, Weaver, Blacksmith,
+        ];
 
         &LABORS
     }
+
+    /// Factor by which the time of year scales the productivity of the labor,
+    /// given how warm it is (see `Season::warmth`)
+    pub fn seasonal_productivity(&self, warmth: f32) -> f32 {
+        match self {
+            Farmer => 1.0 + 0.75 * warmth,
+            Hunter => 1.0 + 0.4 * warmth,
+            _ => 1.0,
+        }
+    }
 }
@@ -0,0 +1,55 @@
+use super::Site;
+use common::store::Id;
+use serde::{Deserialize, Serialize};
+
+/// Where two factions stand with each other
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Stance {
+    Allied,
+    Neutral,
+    /// The factions are at war, and their sites don't trade with each other
+    War,
+}
+
+/// The relations of a faction with another faction
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct Relation {
+    pub stance: Stance,
+    /// How well the factions get on, from -1 (bitter enemies) to 1 (firm
+    /// friends), which decides when their stance changes
+    pub standing: f32,
+}
+
+impl Default for Relation {
+    fn default() -> Self {
+        Self {
+            stance: Stance::Neutral,
+            standing: 0.0,
+        }
+    }
+}
+
+/// A group of sites ruled from the same capital
+pub struct Faction {
+    pub capital: Id<Site>,
+    /// Relations with each of the factions, in the order of the factions. The
+    /// relations of a faction with itself are always friendly.
+    pub relations: Vec<Relation>,
+}
+
+impl Faction {
+    pub fn new(capital: Id<Site>) -> Self {
+        Self {
+            capital,
+            relations: Vec::new(),
+        }
+    }
+
+    /// The relations of this faction with another faction
+    pub fn relation(&self, other: Id<Faction>) -> Relation {
+        self.relations
+            .get(other.id() as usize)
+            .copied()
+            .unwrap_or_default()
+    }
+}
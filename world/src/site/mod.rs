@@ -2,17 +2,25 @@ mod block_mask;
 mod castle;
 mod dungeon;
 pub mod economy;
+pub mod faction;
+mod port;
 mod settlement;
 
 // Reexports
 pub use self::{
-    block_mask::BlockMask, castle::Castle, dungeon::Dungeon, economy::Economy,
-    settlement::Settlement,
+    block_mask::BlockMask,
+    castle::Castle,
+    dungeon::Dungeon,
+    economy::Economy,
+    faction::Faction,
+    port::Port,
+    settlement::{Growth, Settlement},
 };
 
-use crate::{column::ColumnSample, IndexRef};
+use crate::{column::ColumnSample, sim::WorldSim, IndexRef};
 use common::{
     generation::ChunkSupplement,
+    store::Id,
     terrain::Block,
     vol::{BaseVol, ReadVol, RectSizedVol, WriteVol},
 };
@@ -24,6 +32,7 @@ use vek::*;
 pub struct Colors {
     pub castle: castle::Colors,
     pub dungeon: dungeon::Colors,
+    pub port: port::Colors,
     pub settlement: settlement::Colors,
 }
 
@@ -38,12 +47,18 @@ impl Default for SpawnRules {
 pub struct Site {
     pub kind: SiteKind,
     pub economy: Economy,
+    /// The faction that rules the site, if any
+    pub faction: Option<Id<Faction>>,
+    /// Whether the site borders a faction that its own faction is at war with,
+    /// and so has to be ready to defend itself
+    pub fortified: bool,
 }
 
 pub enum SiteKind {
     Settlement(Settlement),
     Dungeon(Dungeon),
     Castle(Castle),
+    Port(Port),
 }
 
 impl Site {
@@ -51,6 +66,8 @@ impl Site {
         Self {
             kind: SiteKind::Settlement(s),
             economy: Economy::default(),
+            faction: None,
+            fortified: false,
         }
     }
 
@@ -58,6 +75,8 @@ impl Site {
         Self {
             kind: SiteKind::Dungeon(d),
             economy: Economy::default(),
+            faction: None,
+            fortified: false,
         }
     }
 
@@ -65,6 +84,28 @@ impl Site {
         Self {
             kind: SiteKind::Castle(c),
             economy: Economy::default(),
+            faction: None,
+            fortified: false,
+        }
+    }
+
+    pub fn port(p: Port) -> Self {
+        Self {
+            kind: SiteKind::Port(p),
+            economy: Economy::default(),
+            faction: None,
+            fortified: false,
+        }
+    }
+
+    /// Generates the site again to show how far its economy came over the
+    /// history of the world
+    pub fn grow(&mut self, sim: &WorldSim) {
+        if let SiteKind::Settlement(s) = &mut self.kind {
+            s.grow(Some(sim), Growth {
+                fortified: self.fortified,
+                ..Growth::from_economy(&self.economy)
+            });
         }
     }
 
@@ -73,6 +114,7 @@ impl Site {
             SiteKind::Settlement(s) => s.radius(),
             SiteKind::Dungeon(d) => d.radius(),
             SiteKind::Castle(c) => c.radius(),
+            SiteKind::Port(p) => p.radius(),
         }
     }
 
@@ -81,6 +123,7 @@ impl Site {
             SiteKind::Settlement(s) => s.get_origin(),
             SiteKind::Dungeon(d) => d.get_origin(),
             SiteKind::Castle(c) => c.get_origin(),
+            SiteKind::Port(p) => p.get_origin(),
         }
     }
 
@@ -89,6 +132,7 @@ impl Site {
             SiteKind::Settlement(s) => s.spawn_rules(wpos),
             SiteKind::Dungeon(d) => d.spawn_rules(wpos),
             SiteKind::Castle(c) => c.spawn_rules(wpos),
+            SiteKind::Port(p) => p.spawn_rules(wpos),
         }
     }
 
@@ -103,11 +147,15 @@ impl Site {
             SiteKind::Settlement(s) => s.apply_to(index, wpos2d, get_column, vol),
             SiteKind::Dungeon(d) => d.apply_to(index, wpos2d, get_column, vol),
             SiteKind::Castle(c) => c.apply_to(index, wpos2d, get_column, vol),
+            SiteKind::Port(p) => p.apply_to(index, wpos2d, get_column, vol),
         }
     }
 
+    /// Adds the entities and other dynamic elements of the site to a chunk.
+    /// `id` is the id of the site, which its merchants trade for.
     pub fn apply_supplement<'a>(
         &'a self,
+        id: Id<Site>,
         // NOTE: Used only for dynamic elements like chests and entities!
         dynamic_rng: &mut impl Rng,
         wpos2d: Vec2<i32>,
@@ -116,10 +164,13 @@ impl Site {
     ) {
         match &self.kind {
             SiteKind::Settlement(s) => {
-                s.apply_supplement(dynamic_rng, wpos2d, get_column, supplement)
+                s.apply_supplement(id.id(), dynamic_rng, wpos2d, get_column, supplement)
             },
             SiteKind::Dungeon(d) => d.apply_supplement(dynamic_rng, wpos2d, get_column, supplement),
             SiteKind::Castle(c) => c.apply_supplement(dynamic_rng, wpos2d, get_column, supplement),
+            SiteKind::Port(p) => {
+                p.apply_supplement(id.id(), dynamic_rng, wpos2d, get_column, supplement)
+            },
         }
     }
 }
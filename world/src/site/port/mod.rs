@@ -0,0 +1,685 @@
+use super::SpawnRules;
+use crate::{
+    column::ColumnSample,
+    sim::WorldSim,
+    util::{RandomField, CARDINALS},
+    IndexRef, CONFIG,
+};
+use common::{
+    comp::{self, humanoid, Item},
+    generation::{ChunkSupplement, EntityInfo},
+    terrain::{Block, BlockKind, SpriteKind, TerrainChunkSize},
+    vol::{BaseVol, ReadVol, RectSizedVol, RectVolSize, WriteVol},
+};
+use core::ops::Range;
+use rand::prelude::*;
+use serde::Deserialize;
+use vek::*;
+
+/// Height of the decks of the piers above the sea
+const PIER_HEIGHT: i32 = 2;
+/// How far the piers reach out to sea past the shore
+const PIER_LENGTH: Range<i32> = 24..48;
+/// Distance between the piers along the shore
+const PIER_SPACING: i32 = 28;
+/// Distance between the posts holding up the piers
+const POST_SPACING: i32 = 4;
+/// Furthest the shore can be from the centre of the port
+const MAX_SHORE_DIST: i32 = 160;
+/// Range of distances along the coast from the port to its shipwrecks
+const WRECK_DIST: Range<i32> = 80..200;
+/// Depth of the hulls of shipwrecks, from the keel to the gunwale
+const HULL_DEPTH: i32 = 4;
+/// Height of each of the painted bands of the lighthouse
+const STRIPE_HEIGHT: i32 = 4;
+
+#[derive(Deserialize)]
+pub struct Colors {
+    pub plank: (u8, u8, u8),
+    pub post: (u8, u8, u8),
+    pub wall: (u8, u8, u8),
+    pub roof: (u8, u8, u8),
+    pub stone: (u8, u8, u8),
+    pub stripe: (u8, u8, u8),
+    pub hull: (u8, u8, u8),
+}
+
+/// Positions within the port are given as how far they are out to sea from
+/// its origin, and how far along the shore from it.
+struct Pier {
+    across: i32,
+    start: i32,
+    end: i32,
+    half_width: i32,
+}
+
+struct Warehouse {
+    centre: Vec2<i32>,
+    half_size: Vec2<i32>,
+    height: i32,
+    alt: i32,
+}
+
+struct Lighthouse {
+    centre: Vec2<i32>,
+    radius: i32,
+    height: i32,
+    alt: i32,
+}
+
+/// A ship washed up on a beach near the port, with whatever it carried still
+/// in its hold
+struct Wreck {
+    /// Relative to the origin of the port, like other sites
+    offset: Vec2<i32>,
+    /// Direction of the keel
+    dir: Vec2<f32>,
+    half_length: f32,
+    half_beam: f32,
+    /// How far the wreck has heeled over onto one side
+    tilt: f32,
+    mast: i32,
+    alt: i32,
+}
+
+pub struct Port {
+    origin: Vec2<i32>,
+    seed: u32,
+    /// Direction from the port out to sea
+    sea_dir: Vec2<i32>,
+    radius: i32,
+    piers: Vec<Pier>,
+    warehouses: Vec<Warehouse>,
+    lighthouse: Lighthouse,
+    wrecks: Vec<Wreck>,
+}
+
+fn deck_alt() -> i32 { CONFIG.sea_level as i32 + PIER_HEIGHT }
+
+/// Return how far from a position the land meets the sea, going in the given
+/// direction
+fn find_shore(sim: Option<&WorldSim>, wpos: Vec2<i32>, dir: Vec2<i32>) -> Option<i32> {
+    let sim = sim?;
+    (-MAX_SHORE_DIST / 2..MAX_SHORE_DIST)
+        .step_by(4)
+        .take_while(|dist| {
+            // The search has to start out on land to find the shore
+            *dist > -MAX_SHORE_DIST / 2
+                || sim
+                    .get_alt_approx(wpos + dir * *dist)
+                    .map_or(false, |alt| alt >= CONFIG.sea_level)
+        })
+        .find(|dist| {
+            sim.get_alt_approx(wpos + dir * *dist)
+                .map_or(false, |alt| alt < CONFIG.sea_level)
+        })
+}
+
+impl Port {
+    pub fn generate(wpos: Vec2<i32>, sim: Option<&WorldSim>, rng: &mut impl Rng) -> Self {
+        // The port faces whichever way the sea is closest
+        let (sea_dir, shore) = CARDINALS
+            .iter()
+            .filter_map(|dir| Some((*dir, find_shore(sim, wpos, *dir)?)))
+            .min_by_key(|(_, shore)| *shore)
+            .unwrap_or((Vec2::unit_x(), 32));
+        let shore_dir = Vec2::new(-sea_dir.y, sea_dir.x);
+        let to_wpos = |local: Vec2<i32>| wpos + sea_dir * local.x + shore_dir * local.y;
+        let alt_at = |local: Vec2<i32>| {
+            sim.and_then(|sim| sim.get_alt_approx(to_wpos(local)))
+                .unwrap_or(CONFIG.sea_level) as i32
+        };
+
+        let pier_count = rng.gen_range(2, 5);
+        let piers = (0..pier_count)
+            .map(|i| Pier {
+                across: (i * 2 - (pier_count - 1)) * PIER_SPACING / 2,
+                // Piers start a little way up the beach
+                start: shore - 6,
+                end: shore + rng.gen_range(PIER_LENGTH.start, PIER_LENGTH.end),
+                half_width: rng.gen_range(1, 3),
+            })
+            .collect::<Vec<_>>();
+
+        // Goods are landed into the warehouses behind the piers
+        let warehouses = piers
+            .iter()
+            .enumerate()
+            .filter_map(|(i, pier)| {
+                if i > 0 && rng.gen_bool(0.25) {
+                    return None;
+                }
+                let half_size = Vec2::new(rng.gen_range(5, 8), rng.gen_range(6, 10));
+                let centre = Vec2::new(pier.start - 4 - half_size.x, pier.across);
+                Some(Warehouse {
+                    centre,
+                    half_size,
+                    height: rng.gen_range(5, 8),
+                    alt: alt_at(centre).max(deck_alt()),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        // The lighthouse stands in the shallows past the outermost pier
+        let side = if rng.gen() { 1 } else { -1 };
+        let lighthouse = Lighthouse {
+            centre: Vec2::new(shore + 4, side * (pier_count + 1) * PIER_SPACING / 2),
+            radius: 4,
+            height: rng.gen_range(24, 33),
+            alt: deck_alt() + 1,
+        };
+
+        let wrecks = (0..rng.gen_range(1, 4))
+            .filter_map(|_| {
+                let across = rng.gen_range(WRECK_DIST.start, WRECK_DIST.end)
+                    * if rng.gen() { 1 } else { -1 };
+                let shore = find_shore(sim, to_wpos(Vec2::new(0, across)), sea_dir)?;
+                let offset = to_wpos(Vec2::new(shore - 2, across)) - wpos;
+                // Wrecks lie more or less along the beach they washed up on
+                let angle = rng.gen_range(-0.6, 0.6f32);
+                let keel = shore_dir.map(|e| e as f32);
+                Some(Wreck {
+                    offset,
+                    dir: Vec2::new(
+                        keel.x * angle.cos() - keel.y * angle.sin(),
+                        keel.x * angle.sin() + keel.y * angle.cos(),
+                    ),
+                    half_length: rng.gen_range(10.0, 16.0),
+                    half_beam: rng.gen_range(3.0, 5.0),
+                    tilt: rng.gen_range(-0.3, 0.3),
+                    mast: rng.gen_range(2, 10),
+                    // Half buried in the sand
+                    alt: sim
+                        .and_then(|sim| sim.get_alt_approx(wpos + offset))
+                        .unwrap_or(CONFIG.sea_level) as i32
+                        - 2,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let radius = piers
+            .iter()
+            .map(|pier| Vec2::new(pier.end, pier.across).map(|e| e.abs()).sum())
+            .chain(std::iter::once(
+                lighthouse.centre.map(|e| e.abs()).sum() + lighthouse.radius,
+            ))
+            .chain(
+                wrecks
+                    .iter()
+                    .map(|wreck| wreck.offset.map(|e| e.abs()).sum() + wreck.half_length as i32),
+            )
+            .max()
+            .unwrap_or(0)
+            + 8;
+
+        Self {
+            origin: wpos,
+            seed: rng.gen(),
+            sea_dir,
+            radius,
+            piers,
+            warehouses,
+            lighthouse,
+            wrecks,
+        }
+    }
+
+    pub fn get_origin(&self) -> Vec2<i32> { self.origin }
+
+    pub fn radius(&self) -> f32 { self.radius as f32 }
+
+    fn shore_dir(&self) -> Vec2<i32> { Vec2::new(-self.sea_dir.y, self.sea_dir.x) }
+
+    /// Return the world position of a position within the port
+    fn to_wpos(&self, local: Vec2<i32>) -> Vec2<i32> {
+        self.origin + self.sea_dir * local.x + self.shore_dir() * local.y
+    }
+
+    pub fn spawn_rules(&self, wpos: Vec2<i32>) -> SpawnRules {
+        SpawnRules {
+            trees: self.wrecks.iter().all(|wreck| {
+                wpos.distance_squared(self.origin + wreck.offset)
+                    > (wreck.half_length as i32 + 4).pow(2)
+            }) && self
+                .warehouses
+                .iter()
+                .map(|warehouse| warehouse.centre)
+                .chain(std::iter::once(self.lighthouse.centre))
+                .all(|centre| wpos.distance_squared(self.to_wpos(centre)) > 16i32.pow(2)),
+        }
+    }
+
+    pub fn apply_to<'a>(
+        &'a self,
+        index: IndexRef,
+        wpos2d: Vec2<i32>,
+        mut get_column: impl FnMut(Vec2<i32>) -> Option<&'a ColumnSample<'a>>,
+        vol: &mut (impl BaseVol<Vox = Block> + RectSizedVol + ReadVol + WriteVol),
+    ) {
+        let colors = &index.colors.site.port;
+        let sea_level = CONFIG.sea_level as i32;
+
+        for y in 0..vol.size_xy().y as i32 {
+            for x in 0..vol.size_xy().x as i32 {
+                let offs = Vec2::new(x, y);
+
+                let wpos2d = wpos2d + offs;
+                let rpos = wpos2d - self.origin;
+
+                if rpos.magnitude_squared() > self.radius.pow(2) {
+                    continue;
+                }
+
+                let col_sample = if let Some(col) = get_column(offs) {
+                    col
+                } else {
+                    continue;
+                };
+                let ground = col_sample.alt as i32;
+                let sand = Block::new(
+                    BlockKind::Sand,
+                    col_sample.surface_color.map(|e| (e * 255.0) as u8),
+                );
+                let local = Vec2::new(rpos.dot(self.sea_dir), rpos.dot(self.shore_dir()));
+
+                for z in ground.min(sea_level) - 12..ground.max(sea_level) + 48 {
+                    let block = self
+                        .wrecks
+                        .iter()
+                        .find_map(|wreck| wreck.draw(colors, self.seed, rpos, z, ground, sand))
+                        .or_else(|| self.draw_lighthouse(colors, local, z, ground))
+                        .or_else(|| {
+                            self.warehouses.iter().find_map(|warehouse| {
+                                self.draw_warehouse(colors, warehouse, local, z)
+                            })
+                        })
+                        .or_else(|| {
+                            self.piers.iter().find_map(|pier| {
+                                pier.draw(colors, self.seed, wpos2d, local, z, ground)
+                            })
+                        });
+
+                    if let Some(block) = block {
+                        let pos = Vec3::new(offs.x, offs.y, z);
+                        // Don't drain the sea around the piers and the lighthouse
+                        if block.kind() == BlockKind::Air
+                            && vol.get(pos).map_or(false, |b| b.is_liquid())
+                        {
+                            continue;
+                        }
+                        let _ = vol.set(pos, block);
+                    }
+                }
+            }
+        }
+    }
+
+    fn draw_warehouse(
+        &self,
+        colors: &Colors,
+        warehouse: &Warehouse,
+        local: Vec2<i32>,
+        z: i32,
+    ) -> Option<Block> {
+        let rel = local - warehouse.centre;
+        let dist = rel.map(|e| e.abs());
+        let half_size = warehouse.half_size;
+        // The eaves hang over the walls by a block
+        if dist.x > half_size.x + 1 || dist.y > half_size.y + 1 {
+            return None;
+        }
+
+        let alt = warehouse.alt;
+        // The ridge of the roof runs out towards the sea
+        let roof_z = alt + warehouse.height + (half_size.y + 1 - dist.y) / 2;
+        let is_wall = dist.x == half_size.x || dist.y == half_size.y;
+        let is_outside = dist.x > half_size.x || dist.y > half_size.y;
+
+        if z > roof_z || (is_outside && z < roof_z) {
+            None
+        } else if z >= roof_z - 1 && (z == roof_z || !is_wall) {
+            Some(Block::new(BlockKind::Wood, colors.roof.into()))
+        } else if z < alt {
+            // Foundations to level the floor on uneven ground
+            if z >= alt - 6 {
+                Some(Block::new(BlockKind::Rock, colors.stone.into()))
+            } else {
+                None
+            }
+        } else if z == alt {
+            Some(Block::new(BlockKind::Wood, colors.plank.into()))
+        } else if is_wall {
+            let is_door = dist.x == half_size.x && rel.x > 0 && dist.y <= 1 && z <= alt + 3;
+            let is_window = dist.y == half_size.y
+                && dist.x < half_size.x
+                && z == alt + 3
+                && rel.x.rem_euclid(4) == 0;
+            if is_door {
+                Some(Block::air(SpriteKind::Empty))
+            } else if is_window {
+                Block::air(SpriteKind::Window1).with_ori(if self.sea_dir.x == 0 { 0 } else { 2 })
+            } else if dist.x == half_size.x && dist.y == half_size.y {
+                Some(Block::new(BlockKind::Wood, colors.post.into()))
+            } else {
+                Some(Block::new(BlockKind::Wood, colors.wall.into()))
+            }
+        } else if z == alt + 1
+            && dist.x == half_size.x - 1
+            && rel.x < 0
+            && RandomField::new(self.seed).chance(Vec3::new(local.x, local.y, z), 0.5)
+        {
+            // Crates of goods stacked against the back wall
+            Some(Block::air(SpriteKind::Crate))
+        } else {
+            Some(Block::air(SpriteKind::Empty))
+        }
+    }
+
+    fn draw_lighthouse(
+        &self,
+        colors: &Colors,
+        local: Vec2<i32>,
+        z: i32,
+        ground: i32,
+    ) -> Option<Block> {
+        let lighthouse = &self.lighthouse;
+        let rel = local - lighthouse.centre;
+        let dist = rel.map(|e| e as f32).magnitude();
+        let radius = lighthouse.radius as f32;
+        let (alt, top) = (lighthouse.alt, lighthouse.alt + lighthouse.height);
+        if dist >= radius + 2.0 {
+            return None;
+        }
+
+        let stone = Block::new(BlockKind::Rock, colors.stone.into());
+        if z <= alt {
+            // Built up on a mound of stone from the sea floor
+            if dist < radius + 1.0 && z >= ground - 2 {
+                Some(stone)
+            } else {
+                None
+            }
+        } else if z < top {
+            if dist >= radius {
+                None
+            } else if dist >= radius - 1.0 {
+                let is_door = rel.x < 0 && rel.y.abs() <= 1 && z <= alt + 3;
+                if is_door {
+                    Some(Block::air(SpriteKind::Empty))
+                } else if ((z - alt) / STRIPE_HEIGHT) % 2 == 0 {
+                    Some(Block::new(BlockKind::Rock, colors.wall.into()))
+                } else {
+                    Some(Block::new(BlockKind::Rock, colors.stripe.into()))
+                }
+            } else {
+                Some(Block::air(SpriteKind::Empty))
+            }
+        } else if z == top {
+            Some(stone)
+        } else if z <= top + 3 {
+            // The lamp room, whose windows light up at night to guide ships
+            if dist >= radius + 1.0 {
+                if z == top + 1 {
+                    Some(Block::new(BlockKind::Wood, colors.post.into()))
+                } else {
+                    None
+                }
+            } else if dist >= radius - 2.0 && dist < radius - 1.0 {
+                Block::air(SpriteKind::Window1).with_ori(if rel.x.abs() > rel.y.abs() {
+                    2
+                } else {
+                    0
+                })
+            } else if rel == Vec2::zero() && z == top + 1 {
+                Some(Block::air(SpriteKind::StreetLamp))
+            } else {
+                Some(Block::air(SpriteKind::Empty))
+            }
+        } else if dist < radius - (z - top - 4) as f32 {
+            Some(Block::new(BlockKind::Rock, colors.roof.into()))
+        } else {
+            None
+        }
+    }
+
+    pub fn apply_supplement<'a>(
+        &'a self,
+        site: u64,
+        // NOTE: Used only for dynamic elements like chests and entities!
+        dynamic_rng: &mut impl Rng,
+        wpos2d: Vec2<i32>,
+        _get_column: impl FnMut(Vec2<i32>) -> Option<&'a ColumnSample<'a>>,
+        supplement: &mut ChunkSupplement,
+    ) {
+        let in_chunk = |wpos: Vec2<i32>| {
+            let offs = wpos - wpos2d;
+            offs.x >= 0
+                && offs.y >= 0
+                && offs.x < TerrainChunkSize::RECT_SIZE.x as i32
+                && offs.y < TerrainChunkSize::RECT_SIZE.y as i32
+        };
+
+        // A merchant trades the goods landed at the port in front of the first
+        // warehouse
+        if let Some(warehouse) = self.warehouses.first() {
+            let wpos =
+                self.to_wpos(warehouse.centre + Vec2::unit_x() * (warehouse.half_size.x + 2));
+            if in_chunk(wpos) {
+                supplement.add_entity(
+                    EntityInfo::at(Vec3::new(wpos.x, wpos.y, warehouse.alt + 1).map(|e| e as f32))
+                        .with_body(comp::Body::Humanoid(humanoid::Body::random()))
+                        .with_alignment(comp::Alignment::Npc)
+                        .with_name("Merchant")
+                        .with_market(site),
+                );
+            }
+        }
+
+        // Fishers cast their lines from the ends of the piers
+        for pier in &self.piers {
+            let wpos = self.to_wpos(Vec2::new(pier.end - 1, pier.across));
+            if in_chunk(wpos) && dynamic_rng.gen_bool(0.75) {
+                supplement.add_entity(
+                    EntityInfo::at(Vec3::new(wpos.x, wpos.y, deck_alt() + 1).map(|e| e as f32))
+                        .with_body(comp::Body::Humanoid(humanoid::Body::random()))
+                        .with_alignment(comp::Alignment::Npc)
+                        .with_main_tool(Item::new_from_asset_expect(
+                            "common.items.npc_weapons.tool.fishing_rod",
+                        ))
+                        .with_facing(self.sea_dir.map(|e| e as f32))
+                        .with_automatic_name(),
+                );
+            }
+        }
+    }
+}
+
+impl Pier {
+    fn draw(
+        &self,
+        colors: &Colors,
+        seed: u32,
+        wpos2d: Vec2<i32>,
+        local: Vec2<i32>,
+        z: i32,
+        ground: i32,
+    ) -> Option<Block> {
+        let across = (local.y - self.across).abs();
+        if local.x < self.start || local.x > self.end || across > self.half_width {
+            return None;
+        }
+
+        let deck = deck_alt();
+        let is_post = across == self.half_width
+            && ((local.x - self.start) % POST_SPACING == 0 || local.x == self.end);
+        if z == deck {
+            // Every other plank is a little lighter
+            let shade = (local.x.rem_euclid(2) * 12) as u8;
+            Some(Block::new(
+                BlockKind::Wood,
+                Rgb::<u8>::from(colors.plank).map(|e| e.saturating_add(shade)),
+            ))
+        } else if z < deck {
+            if is_post && z >= ground - 2 {
+                Some(Block::new(BlockKind::Wood, colors.post.into()))
+            } else {
+                None
+            }
+        } else if z == deck + 1 && is_post {
+            // Posts stick up through the deck to tie boats to
+            Some(Block::new(BlockKind::Wood, colors.post.into()))
+        } else if z == deck + 1
+            && local.x > self.start + 8
+            && RandomField::new(seed).chance(Vec3::new(wpos2d.x, wpos2d.y, z), 0.04)
+        {
+            Some(Block::air(SpriteKind::Crate))
+        } else if z <= deck + 3 {
+            // Where the pier runs up the beach, the sand is dug out of the way
+            Some(Block::air(SpriteKind::Empty))
+        } else {
+            None
+        }
+    }
+}
+
+impl Wreck {
+    fn draw(
+        &self,
+        colors: &Colors,
+        seed: u32,
+        rpos: Vec2<i32>,
+        z: i32,
+        ground: i32,
+        sand: Block,
+    ) -> Option<Block> {
+        let rel = (rpos - self.offset).map(|e| e as f32);
+        let u = rel.dot(self.dir);
+        let v = rel.dot(Vec2::new(-self.dir.y, self.dir.x));
+        if u.abs() > self.half_length {
+            return None;
+        }
+        // The hull narrows towards the bow and the stern
+        let half_width = self.half_beam * (1.0 - (u / self.half_length).powi(2)).max(0.0).sqrt();
+        if v.abs() > half_width + 0.5 {
+            return None;
+        }
+
+        let heel = (v * self.tilt) as i32;
+        let keel = self.alt + ((v / self.half_beam).powi(2) * HULL_DEPTH as f32) as i32 + heel;
+        let gunwale = self.alt + HULL_DEPTH + heel;
+        // The hull was smashed open on the rocks in places
+        let is_broken = RandomField::new(seed)
+            .chance(Vec3::new(rpos.x, rpos.y, z).map(|e| e.div_euclid(3)), 0.25);
+        let hull = Block::new(BlockKind::Wood, colors.hull.into());
+
+        if z < keel {
+            if z >= ground {
+                Some(sand)
+            } else {
+                None
+            }
+        } else if z == keel {
+            Some(hull)
+        } else if v.abs() > half_width - 1.0 && z <= gunwale {
+            if is_broken {
+                Some(Block::air(SpriteKind::Empty))
+            } else {
+                Some(hull)
+            }
+        } else if rel.magnitude_squared() < 1.0 && z <= keel + self.mast {
+            Some(Block::new(BlockKind::Wood, colors.post.into()))
+        } else if z == keel + 1 {
+            let chest = self.dir * -self.half_length * 0.4;
+            if rel.distance_squared(chest) < 1.0 {
+                // What was left of the cargo when the ship ran aground
+                Some(Block::air(SpriteKind::Chest))
+            } else if RandomField::new(seed + 1).chance(Vec3::new(rpos.x, rpos.y, z), 0.08) {
+                Some(Block::air(SpriteKind::Crate))
+            } else {
+                Some(Block::air(SpriteKind::Empty))
+            }
+        } else if z <= gunwale + 2 {
+            Some(Block::air(SpriteKind::Empty))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand_chacha::ChaChaRng;
+
+    /// Ports generated away from any world, facing the sea in the same way
+    fn ports() -> impl Iterator<Item = Port> {
+        (0..64).map(|seed| Port::generate(Vec2::zero(), None, &mut ChaChaRng::seed_from_u64(seed)))
+    }
+
+    #[test]
+    fn piers_and_lighthouse_keep_clear_of_each_other() {
+        for port in ports() {
+            let mut piers = port.piers.iter().collect::<Vec<_>>();
+            piers.sort_by_key(|pier| pier.across);
+            for pair in piers.windows(2) {
+                assert!(
+                    pair[1].across - pair[0].across > pair[0].half_width + pair[1].half_width + 1
+                );
+            }
+            for pier in &piers {
+                assert!(pier.start < pier.end);
+                assert!(
+                    port.lighthouse.centre.y.abs() - port.lighthouse.radius
+                        > pier.across.abs() + pier.half_width
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn warehouses_stand_ashore_behind_the_piers() {
+        for port in ports() {
+            assert!(!port.warehouses.is_empty());
+            for warehouse in &port.warehouses {
+                let pier = port
+                    .piers
+                    .iter()
+                    .find(|pier| pier.across == warehouse.centre.y)
+                    .expect("Warehouse isn't behind any pier");
+                // The eaves reach a block past the walls
+                assert!(warehouse.centre.x + warehouse.half_size.x + 1 < pier.start);
+                assert!(warehouse.alt >= deck_alt());
+            }
+        }
+    }
+
+    #[test]
+    fn port_is_drawn_within_its_radius() {
+        for port in ports() {
+            let radius = port.radius();
+            let within =
+                |local: Vec2<i32>| port.to_wpos(local).map(|e| e as f32).magnitude() < radius;
+            for pier in &port.piers {
+                assert!(within(Vec2::new(pier.end, pier.across + pier.half_width)));
+                assert!(within(Vec2::new(pier.end, pier.across - pier.half_width)));
+            }
+            for warehouse in &port.warehouses {
+                let far = warehouse.half_size + 1;
+                for corner in &[
+                    far,
+                    Vec2::new(far.x, -far.y),
+                    -far,
+                    Vec2::new(-far.x, far.y),
+                ] {
+                    assert!(within(warehouse.centre + *corner));
+                }
+            }
+            assert!(within(port.lighthouse.centre + port.lighthouse.radius));
+            // No trees grow through the buildings
+            for warehouse in &port.warehouses {
+                assert!(!port.spawn_rules(port.to_wpos(warehouse.centre)).trees);
+            }
+            assert!(!port.spawn_rules(port.to_wpos(port.lighthouse.centre)).trees);
+        }
+    }
+}
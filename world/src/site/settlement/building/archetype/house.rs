@@ -102,7 +102,18 @@ const SUPPORT_COLORS: [SupportColor; support_color::NUM_VARIANTS] = [
     SupportColor::Support4,
 ];
 
+/// How well a house is built, which goes by how well off the settlement is
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum HouseTier {
+    /// Small single storey huts of bare timber
+    Hovel,
+    Timber,
+    /// Larger houses with stone walls
+    Stone,
+}
+
 pub struct House {
+    pub tier: HouseTier,
     pub colors: ColorTheme,
     pub noise: RandomField,
     pub roof_ribbing: bool,
@@ -177,14 +188,20 @@ impl Attr {
     }
 }
 
-impl Archetype for House {
-    type Attr = Attr;
-
-    fn generate<R: Rng>(rng: &mut R) -> (Self, Skeleton<Self::Attr>) {
-        let len = rng.gen_range(-8, 24).clamped(0, 20);
-        let locus = 6 + rng.gen_range(0, 5);
+impl House {
+    pub fn generate_tier<R: Rng>(rng: &mut R, tier: HouseTier) -> (Self, Skeleton<Attr>) {
+        let len = rng.gen_range(-8, 24).clamped(0, match tier {
+            HouseTier::Hovel => 8,
+            HouseTier::Timber => 20,
+            HouseTier::Stone => 24,
+        });
+        let locus = 6 + rng.gen_range(0, if tier == HouseTier::Hovel { 2 } else { 5 });
         let branches_per_side = 1 + len as usize / 20;
-        let levels = rng.gen_range(1, 3);
+        let levels = match tier {
+            HouseTier::Hovel => 1,
+            HouseTier::Timber => rng.gen_range(1, 3),
+            HouseTier::Stone => rng.gen_range(2, 4),
+        };
         let skel = Skeleton {
             offset: -rng.gen_range(0, len + 7).clamped(0, len),
             ori: if rng.gen() { Ori::East } else { Ori::North },
@@ -195,7 +212,7 @@ impl Archetype for House {
                     mansard: 0,
                     pillar: match rng.gen_range(0, 3) {
                         0 => Pillar::Chimney(rng.gen_range(2, 6)),
-                        1 => Pillar::Tower(5 + rng.gen_range(1, 5)),
+                        1 if tier != HouseTier::Hovel => Pillar::Tower(5 + rng.gen_range(1, 5)),
                         _ => Pillar::None,
                     },
                     levels,
@@ -208,7 +225,8 @@ impl Archetype for House {
                     .map(|flip| (0..branches_per_side).map(move |i| (i, *flip)))
                     .flatten()
                     .filter_map(|(i, flip)| {
-                        if rng.gen() {
+                        // Hovels are too small to have wings
+                        if tier != HouseTier::Hovel && rng.gen() {
                             Some((
                                 i as i32 * len / (branches_per_side - 1).max(1) as i32,
                                 Branch {
@@ -231,6 +249,7 @@ impl Archetype for House {
         };
 
         let this = Self {
+            tier,
             colors: ColorTheme {
                 roof: *ROOF_COLORS.choose(rng).unwrap(),
                 wall: *WALL_COLORS.choose(rng).unwrap(),
@@ -243,6 +262,14 @@ impl Archetype for House {
 
         (this, skel)
     }
+}
+
+impl Archetype for House {
+    type Attr = Attr;
+
+    fn generate<R: Rng>(rng: &mut R) -> (Self, Skeleton<Self::Attr>) {
+        Self::generate_tier(rng, HouseTier::Timber)
+    }
 
     #[allow(clippy::if_same_then_else)] // TODO: Pending review in #587
     #[allow(clippy::int_plus_one)] // TODO: Pending review in #587
@@ -261,8 +288,12 @@ impl Archetype for House {
     ) -> BlockMask {
         let colors = &index.colors.site.settlement.building.archetype.house;
         let roof_color = *self.colors.roof.elim_case_pure(&colors.roof);
-        let wall_color = *self.colors.wall.elim_case_pure(&colors.wall);
         let support_color = *self.colors.support.elim_case_pure(&colors.support);
+        let wall_color = match self.tier {
+            HouseTier::Hovel => support_color,
+            HouseTier::Timber => *self.colors.wall.elim_case_pure(&colors.wall),
+            HouseTier::Stone => colors.foundation,
+        };
 
         let profile = Vec2::new(bound_offset.x, z);
 
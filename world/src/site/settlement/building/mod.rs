@@ -3,7 +3,11 @@ pub mod skeleton;
 
 // Reexports
 pub use self::{
-    archetype::{house::House, keep::Keep, Archetype},
+    archetype::{
+        house::{House, HouseTier},
+        keep::Keep,
+        Archetype,
+    },
     skeleton::*,
 };
 
@@ -24,6 +28,18 @@ pub struct Building<A: Archetype> {
     origin: Vec3<i32>,
 }
 
+impl Building<House> {
+    /// Generates a house built as well as its tier allows
+    pub fn generate_house(rng: &mut impl Rng, origin: Vec3<i32>, tier: HouseTier) -> Self {
+        let (archetype, skel) = House::generate_tier(rng, tier);
+        Self {
+            skel,
+            archetype,
+            origin,
+        }
+    }
+}
+
 impl<A: Archetype> Building<A> {
     pub fn generate(rng: &mut impl Rng, origin: Vec3<i32>) -> Self
     where
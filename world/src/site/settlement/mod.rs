@@ -2,10 +2,10 @@ pub mod building;
 mod town;
 
 use self::{
-    building::{Building, House, Keep},
+    building::{Building, House, HouseTier, Keep},
     town::{District, Town},
 };
-use super::SpawnRules;
+use super::{Economy, SpawnRules};
 use crate::{
     column::ColumnSample,
     sim::WorldSim,
@@ -25,6 +25,7 @@ use common::{
 use fxhash::FxHasher64;
 use hashbrown::{HashMap, HashSet};
 use rand::prelude::*;
+use rand_chacha::ChaChaRng;
 use serde::Deserialize;
 use std::{collections::VecDeque, f32, hash::BuildHasherDefault};
 use vek::*;
@@ -138,8 +139,72 @@ impl Structure {
     }
 }
 
+/// How far a settlement came over the history of the world, which decides how
+/// much of it is built and how well
+#[derive(Copy, Clone, Debug)]
+pub struct Growth {
+    /// How many people live in the settlement
+    pub pop: f32,
+    /// Worth in coins of everything the settlement has in stock
+    pub wealth: f32,
+    /// Whether the settlement is on the border with an enemy, which makes it
+    /// put up a wall however small it is
+    pub fortified: bool,
+}
+
+impl Default for Growth {
+    fn default() -> Self { Self::from_economy(&Economy::default()) }
+}
+
+impl Growth {
+    /// People that a settlement needs before it puts up a wall
+    const WALL_POP: f32 = 64.0;
+    /// Coins that a settlement needs before it puts up a wall
+    const WALL_WEALTH: f32 = 2500.0;
+
+    pub fn from_economy(economy: &Economy) -> Self {
+        Self {
+            pop: economy.pop,
+            wealth: economy.wealth(),
+            fortified: false,
+        }
+    }
+
+    /// Width in tiles of the area around the town centre that is built up
+    fn built_width(&self) -> usize { (8.0 + self.pop.max(0.0).sqrt() * 1.5).min(24.0) as usize }
+
+    /// How well the houses of the settlement are built, going by how well off
+    /// each of its people is
+    fn house_tier(&self) -> HouseTier {
+        let wealth_per_person = self.wealth / self.pop.max(1.0);
+        if wealth_per_person < 10.0 {
+            HouseTier::Hovel
+        } else if wealth_per_person < 40.0 {
+            HouseTier::Timber
+        } else {
+            HouseTier::Stone
+        }
+    }
+
+    fn has_wall(&self) -> bool {
+        self.fortified || (self.pop >= Self::WALL_POP && self.wealth >= Self::WALL_WEALTH)
+    }
+
+    /// How many tiles away from the town centre the market plaza reaches
+    fn plaza_radius(&self) -> i32 {
+        if self.wealth < 2000.0 {
+            0
+        } else if self.wealth < 10000.0 {
+            1
+        } else {
+            2
+        }
+    }
+}
+
 pub struct Settlement {
     seed: u32,
+    growth: Growth,
     origin: Vec2<i32>,
     land: Land,
     farms: Store<Farm>,
@@ -160,9 +225,17 @@ pub struct GenCtx<'a, R: Rng> {
 
 impl Settlement {
     pub fn generate(wpos: Vec2<i32>, sim: Option<&WorldSim>, rng: &mut impl Rng) -> Self {
-        let mut ctx = GenCtx { sim, rng };
+        Self::generate_grown(wpos, sim, rng.gen(), Growth::default())
+    }
+
+    /// Generates the settlement with the given seed as it looks after growing
+    /// this far, so that it can be generated again once it grew further
+    fn generate_grown(wpos: Vec2<i32>, sim: Option<&WorldSim>, seed: u32, growth: Growth) -> Self {
+        let mut rng = ChaChaRng::seed_from_u64(seed as u64);
+        let mut ctx = GenCtx { sim, rng: &mut rng };
         let mut this = Self {
-            seed: ctx.rng.gen(),
+            seed,
+            growth,
             origin: wpos,
             land: Land::new(ctx.rng),
             farms: Store::default(),
@@ -185,6 +258,12 @@ impl Settlement {
         this
     }
 
+    /// Generates the settlement again to show how far it came over the history
+    /// of the world, keeping its layout where it can
+    pub fn grow(&mut self, sim: Option<&WorldSim>, growth: Growth) {
+        *self = Self::generate_grown(self.origin, sim, self.seed, growth);
+    }
+
     pub fn get_origin(&self) -> Vec2<i32> { self.origin }
 
     /// Designate hazardous terrain based on world data
@@ -321,52 +400,70 @@ impl Settlement {
             }
         }
 
+        // Market plaza, kept clear of buildings around the keep
+        let plaza_radius = self.growth.plaza_radius();
+        if let Some(town) = self.town.as_ref().filter(|_| plaza_radius > 0) {
+            let plaza = self.land.new_plot(Plot::Plaza);
+            for x in -plaza_radius..=plaza_radius {
+                for y in -plaza_radius..=plaza_radius {
+                    let offs = Vec2::new(x, y);
+                    let tile = town.base_tile + offs;
+                    if offs != Vec2::zero()
+                        && x.abs() + y.abs() <= plaza_radius
+                        && matches!(self.land.plot_at(tile), Some(Plot::Town { .. }))
+                    {
+                        self.land.set(tile, plaza);
+                    }
+                }
+            }
+        }
+
         // Boundary wall
-        /*
+        if !self.growth.has_wall() {
+            return;
+        }
         let spokes = CARDINALS
             .iter()
             .filter_map(|dir| {
-                self.land.find_tile_dir(origin, *dir, |plot| match plot {
-                    Some(Plot::Water) => false,
-                    Some(Plot::Town) => false,
-                    _ => true,
+                self.land.find_tile_dir(origin, *dir, |plot| {
+                    !matches!(
+                        plot,
+                        Some(Plot::Water) | Some(Plot::Town { .. }) | Some(Plot::Plaza)
+                    )
                 })
             })
             .collect::<Vec<_>>();
         let mut wall_path = Vec::new();
-        for i in 0..spokes.len() {
-            self.land
-                .find_path(spokes[i], spokes[(i + 1) % spokes.len()], |_, to| match to
-                    .map(|to| self.land.plot(to.plot))
-                {
+        for (i, spoke) in spokes.iter().enumerate() {
+            let next = spokes[(i + 1) % spokes.len()];
+            if let Some(path) = self.land.find_path(*spoke, next, |_, to| {
+                match to.map(|to| self.land.plot(to.plot)) {
                     Some(Plot::Hazard) => 200.0,
                     Some(Plot::Water) => 40.0,
-                    Some(Plot::Town) => 10000.0,
+                    Some(Plot::Town { .. }) | Some(Plot::Plaza) => 10000.0,
                     _ => 10.0,
-                })
-                .map(|path| wall_path.extend(path.iter().copied()));
+                }
+            }) {
+                wall_path.extend(path.iter().copied());
+            }
         }
         let grass = self.land.new_plot(Plot::Grass);
-        let buildable = |plot: &Plot| match plot {
-            Plot::Water => false,
-            _ => true,
-        };
+        let buildable = |plot: &Plot| !matches!(plot, Plot::Water);
         for pos in wall_path.iter() {
             if self.land.tile_at(*pos).is_none() {
                 self.land.set(*pos, grass);
             }
-            if self.land.plot_at(*pos).copied().filter(buildable).is_some() {
-                self.land
-                    .tile_at_mut(*pos)
-                    .map(|tile| tile.tower = Some(Tower::Wall));
+            if self.land.plot_at(*pos).map_or(false, buildable) {
+                if let Some(tile) = self.land.tile_at_mut(*pos) {
+                    tile.tower = Some(Tower::Wall);
+                }
             }
         }
-        if wall_path.len() > 0 {
-            wall_path.push(wall_path[0]);
+        if let Some(first) = wall_path.first().copied() {
+            wall_path.push(first);
         }
         self.land
             .write_path(&wall_path, WayKind::Wall, buildable, true);
-        */
     }
 
     pub fn place_buildings(&mut self, ctx: &mut GenCtx<impl Rng>) {
@@ -376,9 +473,10 @@ impl Settlement {
             return;
         };
 
+        let house_tier = self.growth.house_tier();
         for tile in Spiral2d::new()
             .map(|offs| town_center + offs)
-            .take(16usize.pow(2))
+            .take(self.growth.built_width().pow(2))
         {
             // This is a stupid way to decide how to place buildings
             for i in 0..ctx.rng.gen_range(2, 5) {
@@ -426,9 +524,10 @@ impl Settlement {
                                 Vec3::new(house_pos.x, house_pos.y, alt),
                             ))
                         } else {
-                            StructureKind::House(Building::<House>::generate(
+                            StructureKind::House(Building::<House>::generate_house(
                                 ctx.rng,
                                 Vec3::new(house_pos.x, house_pos.y, alt),
+                                house_tier,
                             ))
                         },
                     };
@@ -621,7 +720,7 @@ impl Settlement {
                         Some(Plot::Grass) => Some(colors.plot_grass.into()),
                         Some(Plot::Water) => Some(colors.plot_water.into()),
                         //Some(Plot::Town { district }) => None,
-                        Some(Plot::Town { .. }) => {
+                        Some(Plot::Town { .. }) | Some(Plot::Plaza) => {
                             if let Some((_, path_nearest, _, _)) = col_sample.path {
                                 let path_dir = (path_nearest - wpos2d.map(|e| e as f32))
                                     .rotated_z(f32::consts::PI / 2.0)
@@ -637,7 +736,12 @@ impl Settlement {
                                 if (col_sample.path.map(|(dist, _, _, _)| dist > 6.0 && dist < 7.0).unwrap_or(false) && is_lamp) //roll(0, 50) == 0)
                                     || (roll(0, 2000) == 0 && col_sample.path.map(|(dist, _, _, _)| dist > 20.0).unwrap_or(true))
                                 {
-                                    surface_sprite = Some(SpriteKind::StreetLamp);
+                                    // Towns post their bounties on some of the lamps
+                                    surface_sprite = Some(if roll(1, 12) == 0 {
+                                        SpriteKind::BountyBoard
+                                    } else {
+                                        SpriteKind::StreetLamp
+                                    });
                                 }
                             }
 
@@ -836,6 +940,7 @@ impl Settlement {
     #[allow(clippy::eval_order_dependence)] // TODO: Pending review in #587
     pub fn apply_supplement<'a>(
         &'a self,
+        site: u64,
         // NOTE: Used only for dynamic elements like chests and entities!
         dynamic_rng: &mut impl Rng,
         wpos2d: Vec2<i32>,
@@ -860,18 +965,31 @@ impl Settlement {
 
                 let entity_wpos = Vec3::new(wpos2d.x as f32, wpos2d.y as f32, col_sample.alt + 3.0);
 
-                if matches!(sample.plot, Some(Plot::Town { .. }))
+                if matches!(sample.plot, Some(Plot::Town { .. }) | Some(Plot::Plaza))
                     && RandomField::new(self.seed).chance(Vec3::from(wpos2d), 1.0 / (50.0 * 40.0))
                 {
                     let is_human: bool;
                     let is_dummy =
                         RandomField::new(self.seed + 1).chance(Vec3::from(wpos2d), 1.0 / 15.0);
+                    let is_stable_master = !is_dummy
+                        && RandomField::new(self.seed + 2).chance(Vec3::from(wpos2d), 1.0 / 10.0);
+                    let is_merchant = !is_dummy
+                        && !is_stable_master
+                        && RandomField::new(self.seed + 3).chance(Vec3::from(wpos2d), 1.0 / 8.0);
+                    let is_guard = !is_dummy
+                        && !is_stable_master
+                        && !is_merchant
+                        && RandomField::new(self.seed + 4).chance(Vec3::from(wpos2d), 1.0 / 6.0);
                     let entity = EntityInfo::at(entity_wpos)
                         .with_body(match dynamic_rng.gen_range(0, 5) {
                             _ if is_dummy => {
                                 is_human = false;
                                 object::Body::TrainingDummy.into()
                             },
+                            _ if is_stable_master || is_merchant || is_guard => {
+                                is_human = true;
+                                comp::Body::Humanoid(humanoid::Body::random())
+                            },
                             0 => {
                                 let species = match dynamic_rng.gen_range(0, 3) {
                                     0 => quadruped_small::Species::Pig,
@@ -910,22 +1028,43 @@ impl Settlement {
                         } else {
                             comp::Alignment::Tame
                         })
-                        .do_if(is_human && dynamic_rng.gen(), |entity| {
+                        .do_if(is_stable_master, |entity| {
                             entity.with_main_tool(Item::new_from_asset_expect(
-                                match dynamic_rng.gen_range(0, 7) {
-                                    0 => "common.items.npc_weapons.tool.broom",
-                                    1 => "common.items.npc_weapons.tool.hoe",
-                                    2 => "common.items.npc_weapons.tool.pickaxe",
-                                    3 => "common.items.npc_weapons.tool.pitchfork",
-                                    4 => "common.items.npc_weapons.tool.rake",
-                                    5 => "common.items.npc_weapons.tool.shovel-0",
-                                    _ => "common.items.npc_weapons.tool.shovel-1",
-                                    //_ => "common.items.npc_weapons.bow.starter_bow", TODO: Re-Add this when we have a better way of distributing npc_weapons here
-                                },
+                                "common.items.npc_weapons.tool.pitchfork",
                             ))
                         })
+                        .do_if(is_guard, |entity| {
+                            entity
+                                .with_main_tool(Item::new_from_asset_expect(
+                                    "common.items.weapons.sword.short_sword_0",
+                                ))
+                                .with_night_patrol()
+                        })
+                        .do_if(
+                            is_human && !is_stable_master && !is_guard && dynamic_rng.gen(),
+                            |entity| {
+                                entity.with_main_tool(Item::new_from_asset_expect(
+                                    match dynamic_rng.gen_range(0, 7) {
+                                        0 => "common.items.npc_weapons.tool.broom",
+                                        1 => "common.items.npc_weapons.tool.hoe",
+                                        2 => "common.items.npc_weapons.tool.pickaxe",
+                                        3 => "common.items.npc_weapons.tool.pitchfork",
+                                        4 => "common.items.npc_weapons.tool.rake",
+                                        5 => "common.items.npc_weapons.tool.shovel-0",
+                                        _ => "common.items.npc_weapons.tool.shovel-1",
+                                        //_ => "common.items.npc_weapons.bow.starter_bow", TODO: Re-Add this when we have a better way of distributing npc_weapons here
+                                    },
+                                ))
+                            },
+                        )
                         .do_if(is_dummy, |e| e.with_name("Training Dummy"))
-                        .do_if(!is_dummy, |e| e.with_automatic_name());
+                        .do_if(is_stable_master, |e| e.with_name("Stable Master"))
+                        .do_if(is_merchant, |e| e.with_name("Merchant").with_market(site))
+                        .do_if(is_guard, |e| e.with_name("Town Guard"))
+                        .do_if(
+                            !is_dummy && !is_stable_master && !is_merchant && !is_guard,
+                            |e| e.with_automatic_name(),
+                        );
 
                     supplement.add_entity(entity);
                 }
@@ -942,7 +1081,7 @@ impl Settlement {
             Some(Plot::Dirt) => return Some(colors.plot_dirt.into()),
             Some(Plot::Grass) => return Some(colors.plot_grass.into()),
             Some(Plot::Water) => return Some(colors.plot_water.into()),
-            Some(Plot::Town { .. }) => {
+            Some(Plot::Town { .. }) | Some(Plot::Plaza) => {
                 return Some(
                     Rgb::from(colors.plot_town).map2(Rgb::iota(), |e: u8, i: i32| {
                         e.saturating_add(
@@ -1009,6 +1148,8 @@ pub enum Plot {
     Town {
         district: Option<Id<District>>,
     },
+    /// Open ground in the middle of a town where the market is held
+    Plaza,
     Field {
         farm: Id<Farm>,
         seed: u32,
@@ -1182,7 +1323,6 @@ impl Land {
             .find(|pos| match_fn(self.plot_at(*pos)))
     }
 
-    #[allow(dead_code)]
     fn find_tile_dir(
         &self,
         origin: Vec2<i32>,